@@ -3,18 +3,30 @@
 //! The Agent actor represents an individual AI agent with its own state,
 //! conversation history, and reasoning loop.
 
-use crate::agent::delegation::DelegationTracker;
-use crate::agent::{AgentConfig, AgentState};
-use crate::llm::StreamAccumulator;
+use crate::agent::delegation::{
+    CheckDelegationTimeouts, DelegatedTask, DelegationTracker, DELEGATION_TIMEOUT_ERROR,
+};
+use crate::agent::{AgentConfig, AgentMetrics, AgentState};
+use crate::kernel::ScheduleToolCall;
+use crate::llm::{SamplingParams, StreamAccumulator};
+use crate::memory::{
+    build_system_with_memories, query_store, LoadMemories, MemoriesLoaded, Memory,
+};
 use crate::messages::{
-    AgentStatusResponse, GetAgentStatus, GetStatus, IncomingAgentMessage, IncomingTask, LLMRequest,
-    LLMResponse, LLMStreamEnd, LLMStreamStart, LLMStreamToken, LLMStreamToolCall, Message,
-    StopReason, TaskAccepted, TaskCompleted, TaskFailed, ToolDefinition, UserPrompt,
+    AgentMetricsResponse, AgentStatusResponse, CompleteTask, DelegateTask, GetAgentMetrics,
+    GetAgentStatus, GetStatus, Heartbeat, HeartbeatAck, IncomingAgentMessage, IncomingTask,
+    LLMRequest, LLMResponse, LLMStreamEnd, LLMStreamStart, LLMStreamToken, LLMStreamToolCall,
+    Message, PauseAgent, ResetAgentMetrics, ResumeAgent, StopReason, TaskAccepted, TaskCompleted,
+    TaskExpired, TaskFailed, TaskResult, TaskResultCallback, ToolDefinition, UserPrompt,
 };
 use crate::tools::actor::{ExecuteToolDirect, ToolActorResponse};
-use crate::types::{AgentId, CorrelationId};
+use crate::types::{AgentId, CorrelationId, TaskId};
 use acton_reactive::prelude::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// How long an agent waits for its memories before prompting without them.
+const MEMORY_LOAD_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Internal state for a pending LLM request.
 #[derive(Debug, Clone, Default)]
@@ -42,6 +54,37 @@ pub struct RegisterToolActors {
     pub tools: Vec<(String, ActorHandle, ToolDefinition)>,
 }
 
+/// Message telling an agent to send its tool calls through `kernel`'s
+/// tool call queue instead of directly to tool actors.
+///
+/// The kernel sends this to agents it registers when
+/// `KernelConfig::max_concurrent_tool_calls` is set.
+#[acton_message]
+pub struct UseToolThrottle {
+    /// The kernel that schedules tool calls
+    pub kernel: ActorHandle,
+}
+
+/// Message giving an agent the memory store its prompts draw memories from.
+///
+/// Sent to a kernel, it hands the store to every registered agent whose
+/// [`AgentConfig::memory_limit`] is set, including agents registered later.
+/// An agent without a memory limit ignores it.
+#[acton_message]
+pub struct UseMemoryStore {
+    /// An initialized [`MemoryStore`](crate::memory::MemoryStore)
+    pub store: ActorHandle,
+}
+
+/// Internal message carrying the memories loaded for a prompt.
+#[acton_message]
+struct MemoryContextLoaded {
+    /// The prompt the memories were loaded for
+    correlation_id: CorrelationId,
+    /// The agent's most recent memories
+    memories: Vec<Memory>,
+}
+
 /// The Agent actor state.
 ///
 /// Each agent maintains its own conversation history, state, and pending requests.
@@ -73,6 +116,31 @@ pub struct Agent {
     pub tool_handles: HashMap<String, ActorHandle>,
     /// Tool definitions for tools available to this agent
     pub tool_definitions: Vec<ToolDefinition>,
+    /// Caps each LLM response, overriding the provider's `max_tokens`
+    pub max_tokens: Option<u32>,
+    /// Sampling parameters for this agent's LLM requests
+    pub sampling: Option<SamplingParams>,
+    /// Token usage and tool call statistics
+    pub metrics: AgentMetrics,
+    /// Kernel that schedules this agent's tool calls, if throttled
+    pub tool_throttle: Option<ActorHandle>,
+    /// Prompts received while paused, run in order after resuming
+    pub paused_prompts: VecDeque<UserPrompt>,
+    /// State to restore when the agent resumes
+    state_before_pause: Option<AgentState>,
+    /// Correlation ID of a tool round that finished while paused
+    paused_continuation: Option<String>,
+    /// Background task expiring overdue delegated tasks
+    delegation_timeout_task: Option<tokio::task::JoinHandle<()>>,
+    /// Callbacks waiting for the results of tasks this agent delegated
+    task_callbacks: HashMap<TaskId, TaskResultCallback>,
+    /// Memory store prompts draw memories from
+    memory_store: Option<ActorHandle>,
+    /// How many memories to add to each prompt; None disables memory
+    memory_limit: Option<usize>,
+    /// System prompt with the current prompt's memories, kept for the
+    /// rest of its reasoning loop
+    memory_system_prompt: Option<String>,
 }
 
 impl Agent {
@@ -106,6 +174,9 @@ impl Agent {
                 Reply::ready()
             })
             .before_stop(|actor| {
+                if let Some(task) = &actor.model.delegation_timeout_task {
+                    task.abort();
+                }
                 tracing::info!(
                     agent_id = ?actor.model.id,
                     conversation_length = actor.model.conversation.len(),
@@ -153,6 +224,77 @@ impl Agent {
     pub fn conversation_length(&self) -> usize {
         self.conversation.len()
     }
+
+    /// Returns the agent's token usage and tool call statistics.
+    #[must_use]
+    pub fn metrics(&self) -> &AgentMetrics {
+        &self.metrics
+    }
+
+    /// Returns the next prompt queued while paused, if the agent can run it
+    /// now.
+    fn take_queued_prompt(&mut self) -> Option<UserPrompt> {
+        if self.state.can_accept_prompt() {
+            self.paused_prompts.pop_front()
+        } else {
+            None
+        }
+    }
+
+    /// Moves to [`AgentState::Thinking`] and builds the request continuing
+    /// the reasoning loop after the tool calls of `corr_id_str` finished.
+    fn continuation_request(&mut self, corr_id_str: String) -> Option<LLMRequest> {
+        self.state = AgentState::Thinking;
+
+        let correlation_id = corr_id_str.parse::<CorrelationId>().ok()?;
+        let llm_request = self.llm_request(correlation_id.clone());
+
+        // Re-add to pending
+        self.pending_llm.insert(
+            corr_id_str,
+            PendingLLMRequest {
+                correlation_id: Some(correlation_id),
+                original_prompt: String::new(),
+            },
+        );
+        Some(llm_request)
+    }
+
+    /// Builds the LLM request for the conversation so far, with the system
+    /// prompt and tools, and records it in the metrics.
+    fn llm_request(&mut self, correlation_id: CorrelationId) -> LLMRequest {
+        let system_prompt = self
+            .memory_system_prompt
+            .as_deref()
+            .unwrap_or(&self.system_prompt);
+        let mut messages = Vec::new();
+        if !system_prompt.is_empty() {
+            messages.push(Message::system(system_prompt));
+        }
+        messages.extend(self.conversation.clone());
+        self.metrics.record_llm_call(&messages);
+
+        LLMRequest {
+            correlation_id,
+            agent_id: self.id.clone().unwrap_or_default(),
+            messages,
+            tools: if self.tool_definitions.is_empty() {
+                None
+            } else {
+                Some(self.tool_definitions.clone())
+            },
+            sampling: self.sampling.clone(),
+            max_tokens: self.max_tokens,
+        }
+    }
+
+    /// Builds the [`AgentMetricsResponse`] reporting this agent's metrics.
+    fn metrics_report(&self) -> AgentMetricsResponse {
+        AgentMetricsResponse {
+            agent_id: self.id.clone().unwrap_or_default(),
+            metrics: self.metrics.clone(),
+        }
+    }
 }
 
 /// Configures message handlers for the Agent actor.
@@ -162,12 +304,32 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
         let config = &envelope.message().config;
 
         actor.model.id = Some(config.agent_id());
-        actor.model.system_prompt = config.system_prompt.clone();
+        actor.model.system_prompt = config.resolve_system_prompt().unwrap_or_else(|e| {
+            tracing::error!(
+                error = %e,
+                "Failed to read system prompt file, using configured system prompt"
+            );
+            config.system_prompt.clone()
+        });
         actor.model.name = config.name.clone();
         actor.model.max_conversation_length = config.max_conversation_length;
         actor.model.enable_streaming = config.enable_streaming;
+        actor.model.max_tokens = config.max_tokens;
+        actor.model.sampling = config.sampling_params.clone();
+        actor.model.memory_limit = config.memory_limit;
         actor.model.state = AgentState::Idle;
 
+        if let Some(interval) = config.delegation_timeout_check {
+            actor.model.delegation_tracker = DelegationTracker::new_with_timeout_check(interval);
+            if let Some(previous) = actor.model.delegation_timeout_task.take() {
+                previous.abort();
+            }
+            actor.model.delegation_timeout_task = actor
+                .model
+                .delegation_tracker
+                .spawn_timeout_check(actor.handle().clone());
+        }
+
         tracing::info!(
             agent_id = ?actor.model.id,
             name = ?actor.model.name,
@@ -181,6 +343,17 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
     builder.mutate_on::<UserPrompt>(|actor, envelope| {
         let prompt = envelope.message();
 
+        // Hold prompts until the agent resumes
+        if actor.model.state.is_paused() {
+            tracing::info!(
+                agent_id = ?actor.model.id,
+                correlation_id = %prompt.correlation_id,
+                "Queueing prompt - agent is paused"
+            );
+            actor.model.paused_prompts.push_back(prompt.clone());
+            return Reply::ready();
+        }
+
         // Check if we can accept a new prompt
         if !actor.model.state.can_accept_prompt() {
             tracing::warn!(
@@ -214,25 +387,48 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
             },
         );
 
-        // Build conversation messages including system prompt
-        let mut messages = Vec::new();
-        if !actor.model.system_prompt.is_empty() {
-            messages.push(Message::system(&actor.model.system_prompt));
+        // Load the agent's memories first; the request goes out once they
+        // arrive
+        actor.model.memory_system_prompt = None;
+        if let (Some(store), Some(limit)) =
+            (actor.model.memory_store.clone(), actor.model.memory_limit)
+        {
+            let request = LoadMemories {
+                agent_id: actor.model.id.clone().unwrap_or_default(),
+                limit: Some(limit),
+            };
+            let correlation_id = prompt.correlation_id.clone();
+            let agent = actor.handle().clone();
+            let mut runtime = actor.runtime().clone();
+            return Reply::pending(async move {
+                let loaded = query_store::<MemoriesLoaded>(
+                    &mut runtime,
+                    &store,
+                    request,
+                    MEMORY_LOAD_TIMEOUT,
+                )
+                .await;
+                let memories = match loaded {
+                    Some(loaded) => loaded.memories,
+                    None => {
+                        tracing::warn!(
+                            correlation_id = %correlation_id,
+                            "Memory store did not load memories in time, prompting without them"
+                        );
+                        Vec::new()
+                    }
+                };
+                agent
+                    .send(MemoryContextLoaded {
+                        correlation_id,
+                        memories,
+                    })
+                    .await;
+            });
         }
-        messages.extend(actor.model.conversation.clone());
 
         // Create LLM request with tools if available
-        let llm_request = LLMRequest {
-            correlation_id: prompt.correlation_id.clone(),
-            agent_id: actor.model.id.clone().unwrap_or_default(),
-            messages,
-            tools: if actor.model.tool_definitions.is_empty() {
-                None
-            } else {
-                Some(actor.model.tool_definitions.clone())
-            },
-            sampling: None,
-        };
+        let llm_request = actor.model.llm_request(prompt.correlation_id.clone());
 
         // Broadcast LLM request via broker for LLM Provider to pick up
         let broker = actor.broker().clone();
@@ -242,6 +438,36 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
         })
     });
 
+    // Send the request of a prompt whose memories finished loading
+    builder.mutate_on::<MemoryContextLoaded>(|actor, envelope| {
+        let msg = envelope.message();
+
+        // The prompt may have been abandoned while its memories loaded
+        if !actor
+            .model
+            .pending_llm
+            .contains_key(&msg.correlation_id.to_string())
+        {
+            return Reply::ready();
+        }
+
+        tracing::debug!(
+            agent_id = ?actor.model.id,
+            correlation_id = %msg.correlation_id,
+            memories = msg.memories.len(),
+            "Adding memories to prompt context"
+        );
+        let memories: Vec<&Memory> = msg.memories.iter().collect();
+        let system_prompt = build_system_with_memories(&actor.model.system_prompt, &memories);
+        actor.model.memory_system_prompt = Some(system_prompt.trim_start().to_string());
+
+        let llm_request = actor.model.llm_request(msg.correlation_id.clone());
+        let broker = actor.broker().clone();
+        Reply::pending(async move {
+            broker.broadcast(llm_request).await;
+        })
+    });
+
     // Handle LLM stream start
     builder.mutate_on::<LLMStreamStart>(|actor, envelope| {
         let msg = envelope.message();
@@ -341,6 +567,7 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
                 error = %error,
                 "Tool call processing failed"
             );
+            actor.model.metrics.record_error();
             Box::pin(async {})
         });
 
@@ -376,6 +603,11 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
                 ));
             };
 
+            actor
+                .model
+                .metrics
+                .record_llm_response(&stream.content, &stream.tool_calls);
+
             // Add assistant response to conversation
             if stream.has_tool_calls() {
                 actor.model.add_message(Message::assistant_with_tools(
@@ -388,6 +620,7 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
                 // Execute tool calls
                 let tool_calls = stream.tool_calls.clone();
                 let tool_handles = actor.model.tool_handles.clone();
+                let tool_throttle = actor.model.tool_throttle.clone();
                 let corr_id = msg.correlation_id.clone();
                 let corr_id_str = corr_id.to_string();
 
@@ -418,7 +651,17 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
                                 tc.id.clone(),
                                 tc.arguments.clone(),
                             );
-                            handle.send(exec_msg).await;
+                            match &tool_throttle {
+                                Some(kernel) => {
+                                    kernel
+                                        .send(ScheduleToolCall {
+                                            tool: handle.clone(),
+                                            call: exec_msg,
+                                        })
+                                        .await;
+                                }
+                                None => handle.send(exec_msg).await,
+                            }
                         } else {
                             tracing::warn!(
                                 tool_name = %tc.name,
@@ -451,7 +694,18 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
             // Remove from pending
             actor.model.pending_llm.remove(&corr_id_str);
 
-            Reply::try_ok(())
+            // Report metrics at the end of each turn for kernel aggregation
+            let broker = actor.broker().clone();
+            let report = actor.model.metrics_report();
+            let next_prompt = actor.model.take_queued_prompt();
+            let handle = actor.handle().clone();
+            Reply::try_pending(async move {
+                broker.broadcast(report).await;
+                if let Some(prompt) = next_prompt {
+                    handle.send(prompt).await;
+                }
+                Ok::<(), crate::error::AgentError>(())
+            })
         })
         .on_error::<LLMStreamEnd, crate::error::AgentError>(|actor, envelope, error| {
             let corr_id_str = envelope.message().correlation_id.to_string();
@@ -465,11 +719,18 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
 
             // Clean up the pending request on error
             actor.model.pending_llm.remove(&corr_id_str);
+            actor.model.metrics.record_error();
 
             // Reset state to Idle on error
             actor.model.state = AgentState::Idle;
 
-            Box::pin(async {})
+            let next_prompt = actor.model.take_queued_prompt();
+            let handle = actor.handle().clone();
+            Box::pin(async move {
+                if let Some(prompt) = next_prompt {
+                    handle.send(prompt).await;
+                }
+            })
         });
 
     // Handle complete LLM responses (non-streaming fallback)
@@ -499,6 +760,11 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
             "Received complete LLM response"
         );
 
+        actor
+            .model
+            .metrics
+            .record_llm_response(&msg.content, msg.tool_calls.as_deref().unwrap_or_default());
+
         // Add assistant response to conversation
         if let Some(tool_calls) = &msg.tool_calls {
             actor.model.add_message(Message::assistant_with_tools(
@@ -519,7 +785,21 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
         // Remove from pending
         actor.model.pending_llm.remove(&corr_id_str);
 
-        Reply::ready()
+        if msg.tool_calls.is_some() {
+            return Reply::ready();
+        }
+
+        // Report metrics at the end of each turn for kernel aggregation
+        let broker = actor.broker().clone();
+        let report = actor.model.metrics_report();
+        let next_prompt = actor.model.take_queued_prompt();
+        let handle = actor.handle().clone();
+        Reply::pending(async move {
+            broker.broadcast(report).await;
+            if let Some(prompt) = next_prompt {
+                handle.send(prompt).await;
+            }
+        })
     });
 
     // Handle status requests (read-only)
@@ -558,6 +838,94 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
         })
     });
 
+    // Handle metrics requests (read-only)
+    builder.act_on::<GetAgentMetrics>(|actor, envelope| {
+        let reply = envelope.reply_envelope();
+        let report = actor.model.metrics_report();
+
+        Reply::pending(async move {
+            reply.send(report).await;
+        })
+    });
+
+    // Answer watchdog heartbeats from the kernel
+    builder.act_on::<Heartbeat>(|actor, envelope| {
+        let reply = envelope.reply_envelope();
+        let ack = HeartbeatAck {
+            agent_id: actor.model.id.clone().unwrap_or_default(),
+            state: actor.model.state,
+            last_activity: actor.model.metrics.last_active,
+        };
+
+        Reply::pending(async move {
+            reply.send(ack).await;
+        })
+    });
+
+    // Handle metrics resets
+    builder.mutate_on::<ResetAgentMetrics>(|actor, _envelope| {
+        actor.model.metrics = AgentMetrics::default();
+
+        let broker = actor.broker().clone();
+        let report = actor.model.metrics_report();
+        Reply::pending(async move {
+            broker.broadcast(report).await;
+        })
+    });
+
+    // Suspend the reasoning loop, queueing prompts until resumed
+    builder.mutate_on::<PauseAgent>(|actor, _envelope| {
+        let state = actor.model.state;
+        if !state.can_accept_prompt() && state != AgentState::Executing {
+            tracing::warn!(
+                agent_id = ?actor.model.id,
+                current_state = %state,
+                "Ignoring pause - agent can only pause while idle or executing tools"
+            );
+            return Reply::ready();
+        }
+
+        actor.model.state_before_pause = Some(state);
+        actor.model.state = AgentState::Paused;
+        tracing::info!(agent_id = ?actor.model.id, paused_from = %state, "Agent paused");
+        Reply::ready()
+    });
+
+    builder.mutate_on::<ResumeAgent>(|actor, _envelope| {
+        if !actor.model.state.is_paused() {
+            return Reply::ready();
+        }
+
+        actor.model.state = actor
+            .model
+            .state_before_pause
+            .take()
+            .unwrap_or(AgentState::Idle);
+        tracing::info!(
+            agent_id = ?actor.model.id,
+            state = %actor.model.state,
+            queued_prompts = actor.model.paused_prompts.len(),
+            "Agent resumed"
+        );
+
+        let continuation = actor
+            .model
+            .paused_continuation
+            .take()
+            .and_then(|corr_id_str| actor.model.continuation_request(corr_id_str));
+        let next_prompt = actor.model.take_queued_prompt();
+        let broker = actor.broker().clone();
+        let handle = actor.handle().clone();
+        Reply::pending(async move {
+            if let Some(llm_request) = continuation {
+                broker.broadcast(llm_request).await;
+            }
+            if let Some(prompt) = next_prompt {
+                handle.send(prompt).await;
+            }
+        })
+    });
+
     // =========================================================================
     // Multi-Agent Message Handlers (Phase 6)
     // =========================================================================
@@ -611,6 +979,90 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
         })
     });
 
+    // Report a finished incoming task to the agent that delegated it
+    builder.mutate_on::<CompleteTask>(|actor, envelope| {
+        let msg = envelope.message();
+
+        let Some(info) = actor.model.delegation_tracker.remove_incoming(&msg.task_id) else {
+            tracing::warn!(
+                agent_id = ?actor.model.id,
+                task_id = %msg.task_id,
+                "Completed task was not delegated to this agent"
+            );
+            return Reply::ready();
+        };
+
+        let result = TaskResult {
+            task_id: info.task_id,
+            from: actor.model.id.clone().unwrap_or_default(),
+            to: info.from,
+            result: msg.result.clone(),
+            elapsed: info.received_at.elapsed(),
+        };
+        tracing::info!(
+            task_id = %result.task_id,
+            to = %result.to,
+            success = result.is_success(),
+            "Delegated task finished"
+        );
+
+        let broker = actor.broker().clone();
+        Reply::pending(async move {
+            broker.broadcast(result).await;
+        })
+    });
+
+    // Track tasks this agent delegated; the kernel forwards them here
+    builder.mutate_on::<DelegateTask>(|actor, envelope| {
+        let msg = envelope.message();
+        if actor.model.id.as_ref() != Some(&msg.from) {
+            return Reply::ready();
+        }
+
+        let mut task =
+            DelegatedTask::new(msg.task_id.clone(), msg.to.clone(), msg.task_type.clone());
+        if let Some(deadline) = msg.deadline {
+            task = task.with_deadline(deadline);
+        }
+        actor.model.delegation_tracker.track_outgoing(task);
+        if let Some(callback) = &msg.callback {
+            actor
+                .model
+                .task_callbacks
+                .insert(msg.task_id.clone(), callback.clone());
+        }
+
+        Reply::ready()
+    });
+
+    // Record results of delegated tasks and run their callbacks
+    builder.mutate_on::<TaskResult>(|actor, envelope| {
+        let msg = envelope.message();
+
+        if let Some(task) = actor
+            .model
+            .delegation_tracker
+            .get_outgoing_mut(&msg.task_id)
+        {
+            match &msg.result {
+                Ok(result) => task.complete(result.clone()),
+                Err(error) => task.fail(error),
+            }
+            tracing::info!(
+                task_id = %msg.task_id,
+                from = %msg.from,
+                success = msg.is_success(),
+                elapsed = ?msg.elapsed,
+                "Received delegated task result"
+            );
+        }
+        if let Some(callback) = actor.model.task_callbacks.remove(&msg.task_id) {
+            callback.run(msg.clone());
+        }
+
+        Reply::ready()
+    });
+
     // Handle task acceptance notifications
     builder.mutate_on::<TaskAccepted>(|actor, envelope| {
         let msg = envelope.message();
@@ -670,11 +1122,61 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
         Reply::ready()
     });
 
+    // Fail delegated tasks that are past their deadline
+    builder.mutate_on::<CheckDelegationTimeouts>(|actor, _envelope| {
+        let expired = actor.model.delegation_tracker.expire_overdue();
+        if expired.is_empty() {
+            return Reply::ready();
+        }
+
+        let agent = actor.handle().clone();
+        Reply::pending(async move {
+            for notification in expired {
+                agent.send(notification).await;
+            }
+        })
+    });
+
+    // Handle task expiration notifications
+    builder.mutate_on::<TaskExpired>(|actor, envelope| {
+        let msg = envelope.message();
+
+        if let Some(task) = actor
+            .model
+            .delegation_tracker
+            .get_outgoing_mut(&msg.task_id)
+        {
+            if !task.is_terminal() {
+                task.fail(DELEGATION_TIMEOUT_ERROR);
+            }
+            tracing::warn!(
+                task_id = %msg.task_id,
+                to_agent = %msg.to_agent,
+                elapsed = ?task.elapsed(),
+                "Delegated task expired"
+            );
+        }
+
+        Reply::ready()
+    });
+
     // =========================================================================
     // Per-Agent Tool Handlers
     // =========================================================================
 
     // Handle registration of tool actors
+    builder.mutate_on::<UseToolThrottle>(|actor, envelope| {
+        actor.model.tool_throttle = Some(envelope.message().kernel.clone());
+        tracing::debug!(agent_id = ?actor.model.id, "Tool calls throttled by kernel");
+        Reply::ready()
+    });
+
+    builder.mutate_on::<UseMemoryStore>(|actor, envelope| {
+        actor.model.memory_store = Some(envelope.message().store.clone());
+        tracing::debug!(agent_id = ?actor.model.id, "Prompts draw on memory store");
+        Reply::ready()
+    });
+
     builder.mutate_on::<RegisterToolActors>(|actor, envelope| {
         let msg = envelope.message();
 
@@ -732,8 +1234,10 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
                     agent_id = ?actor.model.id,
                     tool_call_id = %tool_call_id,
                     error = %error,
+                    rate_limited = msg.rate_limited,
                     "Tool execution failed"
                 );
+                actor.model.metrics.record_error();
 
                 // Add error result to conversation
                 actor.model.add_message(Message::tool(
@@ -743,48 +1247,25 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Agent>) {
             }
         }
 
-        // If no more pending tool calls, transition back to Thinking
-        // to continue the reasoning loop
-        if actor.model.pending_tools.is_empty() && actor.model.state == AgentState::Executing {
-            actor.model.state = AgentState::Thinking;
-
-            // Build conversation messages including system prompt and tools
-            let mut messages = Vec::new();
-            if !actor.model.system_prompt.is_empty() {
-                messages.push(Message::system(&actor.model.system_prompt));
-            }
-            messages.extend(actor.model.conversation.clone());
-
-            // Create LLM request to continue reasoning
-            if let Some(corr_id_str) = corr_id_str {
-                if let Ok(correlation_id) = corr_id_str.parse::<CorrelationId>() {
-                    let llm_request = LLMRequest {
-                        correlation_id: correlation_id.clone(),
-                        agent_id: actor.model.id.clone().unwrap_or_default(),
-                        messages,
-                        tools: if actor.model.tool_definitions.is_empty() {
-                            None
-                        } else {
-                            Some(actor.model.tool_definitions.clone())
-                        },
-                        sampling: None,
-                    };
-
-                    // Re-add to pending
-                    actor.model.pending_llm.insert(
-                        corr_id_str,
-                        PendingLLMRequest {
-                            correlation_id: Some(correlation_id),
-                            original_prompt: String::new(),
-                        },
-                    );
+        if !actor.model.pending_tools.is_empty() {
+            return Reply::ready();
+        }
 
+        match actor.model.state {
+            // No more pending tool calls: continue the reasoning loop
+            AgentState::Executing => {
+                if let Some(llm_request) = corr_id_str
+                    .and_then(|corr_id_str| actor.model.continuation_request(corr_id_str))
+                {
                     let broker = actor.broker().clone();
                     return Reply::pending(async move {
                         broker.broadcast(llm_request).await;
                     });
                 }
             }
+            // Continue once the agent resumes
+            AgentState::Paused => actor.model.paused_continuation = corr_id_str,
+            _ => {}
         }
 
         Reply::ready()
@@ -847,4 +1328,10 @@ mod tests {
 
         assert_eq!(agent.conversation_length(), 0);
     }
+
+    #[test]
+    fn new_agent_has_empty_metrics() {
+        let agent = Agent::default();
+        assert_eq!(agent.metrics(), &AgentMetrics::default());
+    }
 }
@@ -1,13 +1,20 @@
 //! Agent configuration.
 //!
 //! Defines configuration options for creating and customizing agents.
+//!
+//! Agents can also be defined declaratively in YAML; see
+//! [`AgentConfig::from_yaml`] and [`AgentConfigSpec`].
 
+use crate::error::ActonAIError;
+use crate::llm::SamplingParams;
+use crate::tools::builtins::BuiltinTools;
 use crate::types::AgentId;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Configuration for creating a new agent.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AgentConfig {
     /// Optional pre-assigned ID for the agent
     pub id: Option<AgentId>,
@@ -36,6 +43,101 @@ pub struct AgentConfig {
     /// hands out.
     #[serde(default)]
     pub skill_paths: Vec<PathBuf>,
+    /// File to read the system prompt from when the agent initializes.
+    ///
+    /// Overrides `system_prompt` when set and readable.
+    #[serde(default)]
+    pub system_prompt_file: Option<PathBuf>,
+    /// Caps each LLM response, overriding the provider's `max_tokens`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Sampling parameters for the agent's LLM requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling_params: Option<SamplingParams>,
+    /// How often to fail delegated tasks that are past their deadline.
+    /// None disables deadline enforcement.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegation_timeout_check: Option<Duration>,
+    /// How many stored memories to add to each prompt's context, once the
+    /// kernel hands the agent a memory store. None disables memory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<usize>,
+}
+
+/// Number of memories [`AgentConfig::with_memory`] adds to each prompt.
+pub const DEFAULT_MEMORY_LIMIT: usize = 10;
+
+/// The declarative subset of an [`AgentConfig`], as read from and written
+/// to YAML.
+///
+/// # Example
+///
+/// ```yaml
+/// system_prompt: You review pull requests.
+/// name: reviewer
+/// tools: [read_file, grep]
+/// max_tokens: 2048
+/// sampling_params:
+///   temperature: 0.2
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentConfigSpec {
+    /// The system prompt that defines the agent's behavior
+    pub system_prompt: String,
+    /// Optional display name for the agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Names of builtin tools to enable
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<String>,
+    /// Maximum number of messages to keep in conversation history
+    #[serde(default = "default_max_conversation_length")]
+    pub max_conversation_length: usize,
+    /// Whether to enable streaming responses
+    #[serde(default = "default_enable_streaming")]
+    pub enable_streaming: bool,
+    /// Caps each LLM response, overriding the provider's `max_tokens`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Sampling parameters for the agent's LLM requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling_params: Option<SamplingParams>,
+}
+
+fn default_max_conversation_length() -> usize {
+    AgentConfig::default().max_conversation_length
+}
+
+fn default_enable_streaming() -> bool {
+    AgentConfig::default().enable_streaming
+}
+
+impl From<&AgentConfig> for AgentConfigSpec {
+    fn from(config: &AgentConfig) -> Self {
+        Self {
+            system_prompt: config.system_prompt.clone(),
+            name: config.name.clone(),
+            tools: config.tools.clone(),
+            max_conversation_length: config.max_conversation_length,
+            enable_streaming: config.enable_streaming,
+            max_tokens: config.max_tokens,
+            sampling_params: config.sampling_params.clone(),
+        }
+    }
+}
+
+impl From<AgentConfigSpec> for AgentConfig {
+    fn from(spec: AgentConfigSpec) -> Self {
+        Self {
+            name: spec.name,
+            tools: spec.tools,
+            max_conversation_length: spec.max_conversation_length,
+            enable_streaming: spec.enable_streaming,
+            max_tokens: spec.max_tokens,
+            sampling_params: spec.sampling_params,
+            ..Self::new(spec.system_prompt)
+        }
+    }
 }
 
 impl AgentConfig {
@@ -63,7 +165,64 @@ impl AgentConfig {
             enable_streaming: true,
             tools: Vec::new(),
             skill_paths: Vec::new(),
+            system_prompt_file: None,
+            max_tokens: None,
+            sampling_params: None,
+            delegation_timeout_check: None,
+            memory_limit: None,
+        }
+    }
+
+    /// Parses an agent definition from YAML (see [`AgentConfigSpec`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `yaml` is not a valid spec or names a tool that is
+    /// not a builtin tool.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use acton_ai::agent::AgentConfig;
+    ///
+    /// let config = AgentConfig::from_yaml("system_prompt: You are helpful.\ntools: [bash]").unwrap();
+    /// assert_eq!(config.tools, vec!["bash"]);
+    /// ```
+    pub fn from_yaml(s: &str) -> Result<Self, serde_yaml::Error> {
+        let spec: AgentConfigSpec = serde_yaml::from_str(s)?;
+        let available = BuiltinTools::available();
+        if let Some(unknown) = spec
+            .tools
+            .iter()
+            .find(|tool| !available.contains(&tool.as_str()))
+        {
+            return Err(<serde_yaml::Error as serde::de::Error>::custom(format!(
+                "unknown builtin tool '{unknown}'"
+            )));
         }
+        Ok(spec.into())
+    }
+
+    /// Reads an agent definition from a YAML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the file cannot be read or is not a
+    /// valid agent definition.
+    pub fn from_yaml_file(path: &Path) -> Result<Self, ActonAIError> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| {
+            ActonAIError::configuration("agent", format!("failed to read {}: {e}", path.display()))
+        })?;
+        Self::from_yaml(&yaml).map_err(|e| {
+            ActonAIError::configuration("agent", format!("invalid {}: {e}", path.display()))
+        })
+    }
+
+    /// Serializes the declarative part of this config (see
+    /// [`AgentConfigSpec`]) as YAML.
+    #[must_use]
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(&AgentConfigSpec::from(self)).unwrap_or_default()
     }
 
     /// Sets a pre-assigned ID for the agent.
@@ -201,6 +360,79 @@ impl AgentConfig {
         self
     }
 
+    /// Caps each LLM response, overriding the provider's `max_tokens`.
+    #[must_use]
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Sets the sampling parameters for the agent's LLM requests.
+    #[must_use]
+    pub fn with_sampling_params(mut self, params: SamplingParams) -> Self {
+        self.sampling_params = Some(params);
+        self
+    }
+
+    /// Checks delegated tasks against their deadlines every `interval`,
+    /// failing those that are overdue (see
+    /// [`DelegationTracker::new_with_timeout_check`](super::DelegationTracker::new_with_timeout_check)).
+    #[must_use]
+    pub fn with_delegation_timeout_check(mut self, interval: Duration) -> Self {
+        self.delegation_timeout_check = Some(interval);
+        self
+    }
+
+    /// Adds the agent's [`DEFAULT_MEMORY_LIMIT`] most recent stored memories
+    /// to the system prompt of each prompt it handles.
+    ///
+    /// Takes effect once the agent is registered with a kernel that has a
+    /// memory store (see [`UseMemoryStore`](super::UseMemoryStore)), such as
+    /// one launched by an [`ActonAIBuilder`](crate::facade::ActonAIBuilder)
+    /// with [`with_memory_store`](crate::facade::ActonAIBuilder::with_memory_store).
+    #[must_use]
+    pub fn with_memory(self) -> Self {
+        self.with_memory_limit(DEFAULT_MEMORY_LIMIT)
+    }
+
+    /// Like [`with_memory`](Self::with_memory), adding up to `limit`
+    /// memories to each prompt.
+    #[must_use]
+    pub fn with_memory_limit(mut self, limit: usize) -> Self {
+        self.memory_limit = Some(limit);
+        self
+    }
+
+    /// Reads the system prompt from `path` when the agent initializes.
+    ///
+    /// The file's contents replace `system_prompt`. If the file cannot be
+    /// read (or is rejected by the default
+    /// [`PathValidator`](crate::tools::security::PathValidator) rules), the
+    /// agent logs the error and keeps `system_prompt`.
+    #[must_use]
+    pub fn with_system_prompt_file(mut self, path: PathBuf) -> Self {
+        self.system_prompt_file = Some(path);
+        self
+    }
+
+    /// Returns the system prompt the agent should use, reading
+    /// `system_prompt_file` if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `system_prompt_file` is set but cannot be read.
+    pub fn resolve_system_prompt(&self) -> Result<String, crate::error::ActonAIError> {
+        match &self.system_prompt_file {
+            Some(path) => {
+                crate::system_prompt::read_system_prompt_file(path).map_err(|e| match &self.id {
+                    Some(id) => e.with_agent_context(id, "reading system prompt file"),
+                    None => e.with_context("reading system prompt file"),
+                })
+            }
+            None => Ok(self.system_prompt.clone()),
+        }
+    }
+
     /// Returns the agent ID, generating a new one if not set.
     #[must_use]
     pub fn agent_id(&self) -> AgentId {
@@ -249,6 +481,37 @@ mod tests {
         assert!(!config.enable_streaming);
     }
 
+    #[test]
+    fn with_memory_sets_default_limit() {
+        assert_eq!(AgentConfig::new("Test").memory_limit, None);
+        assert_eq!(
+            AgentConfig::new("Test").with_memory().memory_limit,
+            Some(DEFAULT_MEMORY_LIMIT)
+        );
+        assert_eq!(
+            AgentConfig::new("Test").with_memory_limit(3).memory_limit,
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn system_prompt_file_overrides_system_prompt() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "You review code.").unwrap();
+
+        let config = AgentConfig::new("Inline").with_system_prompt_file(file.path().to_path_buf());
+        assert_eq!(config.resolve_system_prompt().unwrap(), "You review code.");
+
+        let missing = AgentConfig::new("Inline")
+            .with_system_prompt_file(std::env::temp_dir().join("acton-ai-missing-prompt.md"));
+        let err = missing.resolve_system_prompt().unwrap_err();
+        assert_eq!(err.contexts()[0].operation, "reading system prompt file");
+        assert_eq!(
+            AgentConfig::new("Inline").resolve_system_prompt().unwrap(),
+            "Inline"
+        );
+    }
+
     #[test]
     fn agent_id_generates_new_when_none() {
         let config = AgentConfig::new("Test");
@@ -308,8 +571,8 @@ mod tests {
     fn with_all_builtins_adds_all_tools() {
         let config = AgentConfig::new("Test").with_all_builtins();
 
-        // Should have all 9 builtin tools
-        assert_eq!(config.tools.len(), 9);
+        // Should have all 14 builtin tools
+        assert_eq!(config.tools.len(), 14);
         assert!(config.tools.contains(&"read_file".to_string()));
         assert!(config.tools.contains(&"bash".to_string()));
         assert!(config.tools.contains(&"calculate".to_string()));
@@ -333,6 +596,52 @@ mod tests {
         assert_eq!(deserialized.tools, vec!["read_file", "bash"]);
     }
 
+    #[test]
+    fn yaml_roundtrip_keeps_spec_fields() {
+        let config = AgentConfig::new("You review code.")
+            .with_name("reviewer")
+            .with_tools(&["read_file", "grep"])
+            .with_max_conversation_length(20)
+            .with_streaming(false)
+            .with_max_tokens(2048)
+            .with_sampling_params(SamplingParams {
+                temperature: Some(0.2),
+                ..SamplingParams::default()
+            });
+
+        let yaml = config.to_yaml();
+        assert!(yaml.contains("name: reviewer"));
+        assert_eq!(AgentConfig::from_yaml(&yaml).unwrap(), config);
+    }
+
+    #[test]
+    fn from_yaml_fills_defaults_for_missing_fields() {
+        let config = AgentConfig::from_yaml("system_prompt: Be brief.").unwrap();
+        assert_eq!(config, AgentConfig::new("Be brief."));
+    }
+
+    #[test]
+    fn from_yaml_rejects_unknown_tools() {
+        let err =
+            AgentConfig::from_yaml("system_prompt: x\ntools: [read_file, teleport]").unwrap_err();
+        assert!(err.to_string().contains("teleport"));
+        assert!(AgentConfig::from_yaml("name: missing prompt").is_err());
+    }
+
+    #[test]
+    fn from_yaml_file_reads_agent_definition() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "system_prompt: From file\nmax_tokens: 64\n").unwrap();
+
+        let config = AgentConfig::from_yaml_file(file.path()).unwrap();
+        assert_eq!(config.system_prompt, "From file");
+        assert_eq!(config.max_tokens, Some(64));
+
+        let err = AgentConfig::from_yaml_file(&std::env::temp_dir().join("acton-ai-missing.yaml"))
+            .unwrap_err();
+        assert!(err.is_configuration());
+    }
+
     mod skills_tests {
         use super::*;
 
@@ -4,11 +4,20 @@
 //! including both outgoing (tasks this agent delegated to others) and
 //! incoming (tasks delegated to this agent by others).
 
+use crate::messages::TaskExpired;
 use crate::types::{AgentId, TaskId};
+use acton_reactive::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+/// Error recorded on delegated tasks failed for passing their deadline.
+pub const DELEGATION_TIMEOUT_ERROR: &str = "Delegation timeout: deadline exceeded";
+
+/// Asks an agent to expire its overdue delegated tasks.
+#[acton_message]
+pub(crate) struct CheckDelegationTimeouts;
+
 /// State of a delegated task.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DelegatedTaskState {
@@ -46,6 +55,8 @@ pub struct DelegatedTask {
     pub state: DelegatedTaskState,
     /// When the task was created
     pub created_at: Instant,
+    /// When the task was accepted
+    pub started_at: Option<Instant>,
     /// Optional deadline
     pub deadline: Option<Duration>,
     /// The result if completed
@@ -64,6 +75,7 @@ impl DelegatedTask {
             task_type,
             state: DelegatedTaskState::Pending,
             created_at: Instant::now(),
+            started_at: None,
             deadline: None,
             result: None,
             error: None,
@@ -80,6 +92,16 @@ impl DelegatedTask {
     /// Marks the task as accepted.
     pub fn accept(&mut self) {
         self.state = DelegatedTaskState::Accepted;
+        self.started_at = Some(Instant::now());
+    }
+
+    /// Returns how long the task has been running since it was accepted,
+    /// or zero if it has not been accepted.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.started_at
+            .map(|started_at| started_at.elapsed())
+            .unwrap_or_default()
     }
 
     /// Marks the task as completed with a result.
@@ -136,6 +158,8 @@ pub struct DelegationTracker {
     outgoing: HashMap<TaskId, DelegatedTask>,
     /// Tasks delegated to this agent by others
     incoming: HashMap<TaskId, IncomingTaskInfo>,
+    /// How often overdue outgoing tasks are expired, if at all
+    timeout_check_interval: Option<Duration>,
 }
 
 impl DelegationTracker {
@@ -145,9 +169,65 @@ impl DelegationTracker {
         Self {
             outgoing: HashMap::new(),
             incoming: HashMap::new(),
+            timeout_check_interval: None,
+        }
+    }
+
+    /// Creates a delegation tracker that expires overdue outgoing tasks
+    /// every `interval`.
+    ///
+    /// The owning agent runs the check in a background task started by
+    /// [`spawn_timeout_check`](Self::spawn_timeout_check); agents configured
+    /// with [`AgentConfig::with_delegation_timeout_check`](super::AgentConfig::with_delegation_timeout_check)
+    /// do this on initialization.
+    #[must_use]
+    pub fn new_with_timeout_check(interval: Duration) -> Self {
+        Self {
+            timeout_check_interval: Some(interval),
+            ..Self::new()
         }
     }
 
+    /// Returns how often overdue tasks are expired, if at all.
+    #[must_use]
+    pub fn timeout_check_interval(&self) -> Option<Duration> {
+        self.timeout_check_interval
+    }
+
+    /// Starts the background task that asks `agent` to expire overdue tasks
+    /// on every tick of the timeout check interval.
+    ///
+    /// Returns `None` if the tracker has no timeout check interval.
+    pub(crate) fn spawn_timeout_check(
+        &self,
+        agent: ActorHandle,
+    ) -> Option<tokio::task::JoinHandle<()>> {
+        let interval = self.timeout_check_interval.filter(|i| !i.is_zero())?;
+        Some(tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            loop {
+                ticks.tick().await;
+                agent.send(CheckDelegationTimeouts).await;
+            }
+        }))
+    }
+
+    /// Fails pending and accepted outgoing tasks that are past their
+    /// deadline, returning a [`TaskExpired`] for each.
+    pub fn expire_overdue(&mut self) -> Vec<TaskExpired> {
+        self.outgoing
+            .values_mut()
+            .filter(|task| !task.is_terminal() && task.is_overdue())
+            .map(|task| {
+                task.fail(DELEGATION_TIMEOUT_ERROR);
+                TaskExpired {
+                    task_id: task.task_id.clone(),
+                    to_agent: task.delegated_to.clone(),
+                }
+            })
+            .collect()
+    }
+
     /// Tracks a task that this agent delegated to another.
     pub fn track_outgoing(&mut self, task: DelegatedTask) {
         self.outgoing.insert(task.task_id.clone(), task);
@@ -307,6 +387,60 @@ mod tests {
         assert!(!no_deadline_task.is_overdue());
     }
 
+    #[test]
+    fn delegated_task_elapsed_starts_on_accept() {
+        let mut task = DelegatedTask::new(TaskId::new(), AgentId::new(), "test".to_string());
+        assert_eq!(task.elapsed(), Duration::ZERO);
+        assert!(task.started_at.is_none());
+
+        task.accept();
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(task.started_at.is_some());
+        assert!(task.elapsed() >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn tracker_expires_only_overdue_unfinished_tasks() {
+        let mut tracker = DelegationTracker::new_with_timeout_check(Duration::from_secs(1));
+        assert_eq!(
+            tracker.timeout_check_interval(),
+            Some(Duration::from_secs(1))
+        );
+        let agent_id = AgentId::new();
+        let overdue = TaskId::new();
+        let finished = TaskId::new();
+        let on_time = TaskId::new();
+        for (task_id, deadline) in [
+            (&overdue, Duration::from_millis(1)),
+            (&finished, Duration::from_millis(1)),
+            (&on_time, Duration::from_secs(60)),
+        ] {
+            tracker.track_outgoing(
+                DelegatedTask::new(task_id.clone(), agent_id.clone(), "test".to_string())
+                    .with_deadline(deadline),
+            );
+        }
+        tracker
+            .get_outgoing_mut(&finished)
+            .unwrap()
+            .complete(serde_json::json!({}));
+        std::thread::sleep(Duration::from_millis(5));
+
+        let expired = tracker.expire_overdue();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].task_id, overdue);
+        assert_eq!(expired[0].to_agent, agent_id);
+
+        let task = tracker.get_outgoing(&overdue).unwrap();
+        assert_eq!(task.state, DelegatedTaskState::Failed);
+        assert_eq!(task.error.as_deref(), Some(DELEGATION_TIMEOUT_ERROR));
+        assert_eq!(
+            tracker.get_outgoing(&finished).unwrap().state,
+            DelegatedTaskState::Completed
+        );
+        assert!(tracker.expire_overdue().is_empty());
+    }
+
     #[test]
     fn tracker_outgoing_tasks() {
         let mut tracker = DelegationTracker::new();
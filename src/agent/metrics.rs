@@ -0,0 +1,258 @@
+//! Per-agent usage metrics.
+//!
+//! Each [`Agent`](super::Agent) accumulates an [`AgentMetrics`] as it talks
+//! to its LLM and runs tools. Metrics from several agents can be summed with
+//! `+` and exported in the Prometheus text format.
+
+use crate::memory::{CharRatioEstimator, TokenEstimator};
+use crate::messages::{Message, ToolCall};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::ops::Add;
+use std::time::Instant;
+
+/// Token usage and tool call statistics of one agent (or a sum of agents).
+///
+/// Token counts are estimates from the default [`CharRatioEstimator`], as
+/// providers do not report usage to agents.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentMetrics {
+    /// Estimated tokens sent to the LLM
+    pub total_tokens_in: u64,
+    /// Estimated tokens received from the LLM
+    pub total_tokens_out: u64,
+    /// LLM requests made
+    pub total_llm_calls: u64,
+    /// Tool calls requested by the LLM
+    pub total_tool_calls: u64,
+    /// Tool calls requested by the LLM, by tool name
+    pub tool_call_counts: HashMap<String, u64>,
+    /// Failed tool executions and LLM responses that could not be processed
+    pub error_count: u64,
+    /// When the agent last sent or received a message
+    pub last_active: Option<Instant>,
+}
+
+impl AgentMetrics {
+    /// Records an LLM request carrying `messages`.
+    pub(crate) fn record_llm_call(&mut self, messages: &[Message]) {
+        let estimator = CharRatioEstimator::default();
+        self.total_llm_calls += 1;
+        self.total_tokens_in += messages
+            .iter()
+            .map(|m| estimator.estimate_message(m) as u64)
+            .sum::<u64>();
+        self.touch();
+    }
+
+    /// Records an LLM response with `content` requesting `tool_calls`.
+    pub(crate) fn record_llm_response(&mut self, content: &str, tool_calls: &[ToolCall]) {
+        self.total_tokens_out += CharRatioEstimator::default().estimate_string(content) as u64;
+        for tool_call in tool_calls {
+            self.total_tool_calls += 1;
+            *self
+                .tool_call_counts
+                .entry(tool_call.name.clone())
+                .or_default() += 1;
+        }
+        self.touch();
+    }
+
+    /// Records a failure.
+    pub(crate) fn record_error(&mut self) {
+        self.error_count += 1;
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.last_active = Some(Instant::now());
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format.
+    ///
+    /// Every sample carries `labels` (e.g., `[("agent", "reviewer")]`);
+    /// per-tool counts also carry a `tool` label. `last_active` is exported
+    /// as the seconds since the agent was last active.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use acton_ai::agent::AgentMetrics;
+    ///
+    /// let metrics = AgentMetrics {
+    ///     total_llm_calls: 3,
+    ///     ..AgentMetrics::default()
+    /// };
+    /// let text = metrics.to_prometheus_text(&[("agent", "reviewer")]);
+    /// assert!(text.contains("acton_agent_llm_calls_total{agent=\"reviewer\"} 3\n"));
+    /// ```
+    #[must_use]
+    pub fn to_prometheus_text(&self, labels: &[(&str, &str)]) -> String {
+        let base = format_labels(labels);
+        let mut out = String::new();
+        let mut counter = |name: &str, help: &str, value: u64| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name}{base} {value}");
+        };
+        counter(
+            "acton_agent_tokens_in_total",
+            "Estimated tokens sent to the LLM.",
+            self.total_tokens_in,
+        );
+        counter(
+            "acton_agent_tokens_out_total",
+            "Estimated tokens received from the LLM.",
+            self.total_tokens_out,
+        );
+        counter(
+            "acton_agent_llm_calls_total",
+            "LLM requests made.",
+            self.total_llm_calls,
+        );
+        counter(
+            "acton_agent_tool_calls_total",
+            "Tool calls requested by the LLM.",
+            self.total_tool_calls,
+        );
+        counter(
+            "acton_agent_errors_total",
+            "Failed tool executions and unprocessable LLM responses.",
+            self.error_count,
+        );
+
+        if !self.tool_call_counts.is_empty() {
+            let name = "acton_agent_tool_calls_by_name_total";
+            let _ = writeln!(
+                out,
+                "# HELP {name} Tool calls requested by the LLM, by tool."
+            );
+            let _ = writeln!(out, "# TYPE {name} counter");
+            // Sorted so the output is stable between scrapes
+            let sorted: BTreeMap<_, _> = self.tool_call_counts.iter().collect();
+            for (tool, count) in sorted {
+                let mut tool_labels = labels.to_vec();
+                tool_labels.push(("tool", tool));
+                let _ = writeln!(out, "{name}{} {count}", format_labels(&tool_labels));
+            }
+        }
+
+        if let Some(last_active) = self.last_active {
+            let name = "acton_agent_idle_seconds";
+            let _ = writeln!(
+                out,
+                "# HELP {name} Seconds since the agent was last active."
+            );
+            let _ = writeln!(out, "# TYPE {name} gauge");
+            let _ = writeln!(out, "{name}{base} {}", last_active.elapsed().as_secs_f64());
+        }
+
+        out
+    }
+}
+
+impl Add for AgentMetrics {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self.total_tokens_in += other.total_tokens_in;
+        self.total_tokens_out += other.total_tokens_out;
+        self.total_llm_calls += other.total_llm_calls;
+        self.total_tool_calls += other.total_tool_calls;
+        for (tool, count) in other.tool_call_counts {
+            *self.tool_call_counts.entry(tool).or_default() += count;
+        }
+        self.error_count += other.error_count;
+        self.last_active = self.last_active.max(other.last_active);
+        self
+    }
+}
+
+impl std::iter::Sum for AgentMetrics {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// Formats `{k="v",...}`, escaping values per the exposition format.
+fn format_labels(labels: &[(&str, &str)]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{key}=\"{value}\"")
+        })
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_call(name: &str) -> ToolCall {
+        ToolCall {
+            id: format!("call_{name}"),
+            name: name.to_string(),
+            arguments: serde_json::json!({}),
+        }
+    }
+
+    #[test]
+    fn records_calls_tokens_and_tools() {
+        let mut metrics = AgentMetrics::default();
+        metrics.record_llm_call(&[Message::user("What is in main.rs?")]);
+        metrics.record_llm_response("Let me look.", &[tool_call("read_file")]);
+        metrics.record_error();
+
+        assert_eq!(metrics.total_llm_calls, 1);
+        assert!(metrics.total_tokens_in > 0);
+        assert!(metrics.total_tokens_out > 0);
+        assert_eq!(metrics.total_tool_calls, 1);
+        assert_eq!(metrics.tool_call_counts["read_file"], 1);
+        assert_eq!(metrics.error_count, 1);
+        assert!(metrics.last_active.is_some());
+    }
+
+    #[test]
+    fn add_sums_counts_and_merges_tools() {
+        let mut a = AgentMetrics::default();
+        a.record_llm_response("", &[tool_call("bash"), tool_call("grep")]);
+        let mut b = AgentMetrics::default();
+        b.record_llm_response("", &[tool_call("bash")]);
+        b.record_error();
+
+        let total: AgentMetrics = [a.clone(), b.clone()].into_iter().sum();
+        assert_eq!(total.total_tool_calls, 3);
+        assert_eq!(total.tool_call_counts["bash"], 2);
+        assert_eq!(total.tool_call_counts["grep"], 1);
+        assert_eq!(total.error_count, 1);
+        assert_eq!(total.last_active, b.last_active.max(a.last_active));
+    }
+
+    #[test]
+    fn prometheus_text_labels_every_sample() {
+        let mut metrics = AgentMetrics {
+            total_tokens_in: 10,
+            ..AgentMetrics::default()
+        };
+        metrics.record_llm_response("", &[tool_call("bash")]);
+
+        let text = metrics.to_prometheus_text(&[("agent", "a\"b")]);
+        assert!(text.contains("# TYPE acton_agent_tokens_in_total counter\n"));
+        assert!(text.contains("acton_agent_tokens_in_total{agent=\"a\\\"b\"} 10\n"));
+        assert!(text
+            .contains("acton_agent_tool_calls_by_name_total{agent=\"a\\\"b\",tool=\"bash\"} 1\n"));
+        assert!(text.contains("# TYPE acton_agent_idle_seconds gauge\n"));
+
+        let unlabeled = AgentMetrics::default().to_prometheus_text(&[]);
+        assert!(unlabeled.contains("acton_agent_errors_total 0\n"));
+        assert!(!unlabeled.contains("idle_seconds"));
+    }
+}
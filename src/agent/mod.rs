@@ -6,9 +6,16 @@
 mod actor;
 mod config;
 mod delegation;
+mod metrics;
 mod state;
 
-pub use actor::{Agent, InitAgent, PendingLLMRequest, RegisterToolActors};
-pub use config::AgentConfig;
-pub use delegation::{DelegatedTask, DelegatedTaskState, DelegationTracker, IncomingTaskInfo};
+pub use actor::{
+    Agent, InitAgent, PendingLLMRequest, RegisterToolActors, UseMemoryStore, UseToolThrottle,
+};
+pub use config::{AgentConfig, AgentConfigSpec, DEFAULT_MEMORY_LIMIT};
+pub use delegation::{
+    DelegatedTask, DelegatedTaskState, DelegationTracker, IncomingTaskInfo,
+    DELEGATION_TIMEOUT_ERROR,
+};
+pub use metrics::AgentMetrics;
 pub use state::AgentState;
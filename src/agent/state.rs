@@ -21,6 +21,8 @@ pub enum AgentState {
     Completed,
     /// Agent is stopping
     Stopping,
+    /// Agent is suspended; prompts are queued until it resumes
+    Paused,
 }
 
 impl AgentState {
@@ -36,11 +38,29 @@ impl AgentState {
         matches!(self, Self::Thinking | Self::Executing | Self::Waiting)
     }
 
+    /// Returns true if the agent is suspended.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        matches!(self, Self::Paused)
+    }
+
     /// Returns true if the agent is in a terminal state.
     #[must_use]
     pub fn is_terminal(&self) -> bool {
         matches!(self, Self::Stopping)
     }
+
+    /// Weights a capability score by how available an agent in this state
+    /// is: idle and completed agents keep their score, busy agents get
+    /// half, and paused or stopping agents get none.
+    #[must_use]
+    pub fn weighted_score(&self, score: f32) -> f32 {
+        match self {
+            Self::Idle | Self::Completed => score,
+            Self::Thinking | Self::Executing | Self::Waiting => score / 2.0,
+            Self::Paused | Self::Stopping => 0.0,
+        }
+    }
 }
 
 impl fmt::Display for AgentState {
@@ -52,6 +72,7 @@ impl fmt::Display for AgentState {
             Self::Waiting => write!(f, "waiting"),
             Self::Completed => write!(f, "completed"),
             Self::Stopping => write!(f, "stopping"),
+            Self::Paused => write!(f, "paused"),
         }
     }
 }
@@ -60,6 +81,15 @@ impl fmt::Display for AgentState {
 mod tests {
     use super::*;
 
+    #[test]
+    fn weighted_score_halves_busy_agents() {
+        assert_eq!(AgentState::Idle.weighted_score(0.8), 0.8);
+        assert_eq!(AgentState::Thinking.weighted_score(0.8), 0.4);
+        assert_eq!(AgentState::Executing.weighted_score(0.8), 0.4);
+        assert_eq!(AgentState::Stopping.weighted_score(0.8), 0.0);
+        assert_eq!(AgentState::Paused.weighted_score(0.8), 0.0);
+    }
+
     #[test]
     fn default_state_is_idle() {
         assert_eq!(AgentState::default(), AgentState::Idle);
@@ -73,6 +103,7 @@ mod tests {
         assert!(!AgentState::Executing.can_accept_prompt());
         assert!(!AgentState::Waiting.can_accept_prompt());
         assert!(!AgentState::Stopping.can_accept_prompt());
+        assert!(!AgentState::Paused.can_accept_prompt());
     }
 
     #[test]
@@ -83,6 +114,14 @@ mod tests {
         assert!(AgentState::Waiting.is_active());
         assert!(!AgentState::Completed.is_active());
         assert!(!AgentState::Stopping.is_active());
+        assert!(!AgentState::Paused.is_active());
+    }
+
+    #[test]
+    fn is_paused_only_when_paused() {
+        assert!(AgentState::Paused.is_paused());
+        assert!(!AgentState::Idle.is_paused());
+        assert!(!AgentState::Executing.is_paused());
     }
 
     #[test]
@@ -101,6 +140,7 @@ mod tests {
         assert_eq!(AgentState::Waiting.to_string(), "waiting");
         assert_eq!(AgentState::Completed.to_string(), "completed");
         assert_eq!(AgentState::Stopping.to_string(), "stopping");
+        assert_eq!(AgentState::Paused.to_string(), "paused");
     }
 
     #[test]
@@ -112,6 +152,7 @@ mod tests {
             AgentState::Waiting,
             AgentState::Completed,
             AgentState::Stopping,
+            AgentState::Paused,
         ];
 
         for state in states {
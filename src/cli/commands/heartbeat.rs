@@ -266,12 +266,16 @@ mod tests {
                 content: "hello".to_string(),
                 tool_calls: None,
                 tool_call_id: None,
+                timestamp: None,
+                is_injected: false,
             },
             Message {
                 role: MessageRole::Assistant,
                 content: "hi there".to_string(),
                 tool_calls: None,
                 tool_call_id: None,
+                timestamp: None,
+                is_injected: false,
             },
         ];
 
@@ -288,6 +292,8 @@ mod tests {
                 content: format!("message {i}"),
                 tool_calls: None,
                 tool_call_id: None,
+                timestamp: None,
+                is_injected: false,
             })
             .collect();
 
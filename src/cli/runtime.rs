@@ -37,30 +37,36 @@ impl CliRuntime {
         provider_override: Option<&str>,
         skill_paths: &[PathBuf],
     ) -> Result<Self, CliError> {
-        // Load config — try explicit path first, then default search paths.
-        // Track the source path so we can log it at info level for operators
-        // trying to understand which file wins.
-        let (loaded_config, resolved_config_path) = if let Some(path) = config_path {
+        // Load config — try explicit path first, then the layered default
+        // search paths. Track the source paths so we can log them at info
+        // level for operators trying to understand which files apply.
+        let (loaded_config, resolved_config_paths) = if let Some(path) = config_path {
             let cfg = config::from_path(path).map_err(|e| {
                 CliError::configuration(format!(
                     "failed to load config from {}: {e}",
                     path.display()
                 ))
             })?;
-            (Some(cfg), Some(path.clone()))
+            (Some(cfg), vec![path.clone()])
         } else {
-            let found = config::search_paths().into_iter().find(|p| p.exists());
-            match &found {
-                Some(p) => (Some(config::from_path(p).map_err(|e| {
-                    CliError::configuration(format!("failed to load config from {}: {e}", p.display()))
-                })?), found.clone()),
-                None => (None, None),
+            let mut found: Vec<PathBuf> =
+                config::search_paths().into_iter().filter(|p| p.exists()).collect();
+            found.reverse();
+            if found.is_empty() {
+                (None, found)
+            } else {
+                let cfg = config::load_with_env_overrides().map_err(|e| {
+                    CliError::configuration(format!("failed to load config: {e}"))
+                })?;
+                (Some(cfg), found)
             }
         };
 
-        match &resolved_config_path {
-            Some(p) => tracing::info!(path = %p.display(), "loaded config"),
-            None => tracing::info!("no config file found; using defaults"),
+        if resolved_config_paths.is_empty() {
+            tracing::info!("no config file found; using defaults");
+        }
+        for p in &resolved_config_paths {
+            tracing::info!(path = %p.display(), "loaded config");
         }
 
         if let Some(cfg) = &loaded_config {
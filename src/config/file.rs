@@ -3,8 +3,10 @@
 //! This module handles loading acton-ai configuration from TOML files
 //! at XDG-compliant locations.
 
-use crate::config::types::ActonAIConfig;
+use crate::config::types::{ActonAIConfig, RateLimitFileConfig};
 use crate::error::{ActonAIError, ActonAIErrorKind};
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Default configuration file name for project-local config.
@@ -16,11 +18,19 @@ const XDG_CONFIG_NAME: &str = "config.toml";
 /// Application name for XDG directory lookup.
 const APP_NAME: &str = "acton-ai";
 
-/// Loads configuration from the default search paths.
+/// Environment variable overriding `default_provider` in
+/// [`load_with_env_overrides`].
+pub const DEFAULT_PROVIDER_ENV: &str = "ACTON_AI_DEFAULT_PROVIDER";
+
+/// Environment variable overriding `log_level` in
+/// [`load_with_env_overrides`].
+pub const LOG_LEVEL_ENV: &str = "ACTON_AI_LOG_LEVEL";
+
+/// Loads and merges the configuration files at the default search paths.
 ///
-/// Search order:
-/// 1. `./acton-ai.toml` (project-local)
-/// 2. `~/.config/acton-ai/config.toml` (XDG config)
+/// Layers, each [merged](ActonAIConfig::merge) over the previous one:
+/// 1. `~/.config/acton-ai/config.toml` (XDG config)
+/// 2. `./acton-ai.toml` (project-local)
 ///
 /// Returns an empty configuration if no config file is found.
 ///
@@ -39,22 +49,58 @@ const APP_NAME: &str = "acton-ai";
 /// }
 /// ```
 pub fn load() -> Result<ActonAIConfig, ActonAIError> {
-    // Try project-local config first
-    let local_path = PathBuf::from(LOCAL_CONFIG_NAME);
-    if local_path.exists() {
-        return from_path(&local_path);
-    }
+    // Search paths are listed most specific first; layer the other way round
+    let mut layers = search_paths();
+    layers.reverse();
+    load_layers(&layers)
+}
 
-    // Try XDG config directory
-    if let Some(config_dir) = dirs::config_dir() {
-        let xdg_path = config_dir.join(APP_NAME).join(XDG_CONFIG_NAME);
-        if xdg_path.exists() {
-            return from_path(&xdg_path);
-        }
-    }
+/// Merges the files among `paths` that exist, later ones taking precedence.
+fn load_layers(paths: &[PathBuf]) -> Result<ActonAIConfig, ActonAIError> {
+    paths
+        .iter()
+        .filter(|path| path.exists())
+        .try_fold(ActonAIConfig::default(), |config, path| {
+            Ok(config.merge(from_path(path)?))
+        })
+}
 
-    // No config file found - return empty config
-    Ok(ActonAIConfig::default())
+/// Loads configuration like [`load`], then applies environment overrides.
+///
+/// `ACTON_AI_DEFAULT_PROVIDER` replaces `default_provider` and
+/// `ACTON_AI_LOG_LEVEL` replaces `log_level`. Empty variables are ignored.
+///
+/// # Errors
+///
+/// Returns an error if a config file exists but cannot be parsed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use acton_ai::config::load_with_env_overrides;
+///
+/// // ACTON_AI_DEFAULT_PROVIDER=local picks [providers.local]
+/// let config = load_with_env_overrides()?;
+/// ```
+pub fn load_with_env_overrides() -> Result<ActonAIConfig, ActonAIError> {
+    Ok(apply_env_overrides(load()?, |name| {
+        std::env::var(name).ok()
+    }))
+}
+
+/// Applies the environment override layer, reading variables with `var`.
+fn apply_env_overrides(
+    mut config: ActonAIConfig,
+    var: impl Fn(&str) -> Option<String>,
+) -> ActonAIConfig {
+    let var = |name| var(name).filter(|value: &String| !value.is_empty());
+    if let Some(provider) = var(DEFAULT_PROVIDER_ENV) {
+        config.default_provider = Some(provider);
+    }
+    if let Some(level) = var(LOG_LEVEL_ENV) {
+        config.log_level = Some(level);
+    }
+    config
 }
 
 /// Loads configuration from a specific file path.
@@ -128,6 +174,54 @@ pub fn from_str(toml_str: &str) -> Result<ActonAIConfig, ActonAIError> {
     })
 }
 
+/// A standalone rate limit file, keyed by provider name.
+#[derive(Debug, Deserialize)]
+struct RateLimitFile {
+    #[serde(default)]
+    providers: HashMap<String, RateLimitFileConfig>,
+}
+
+/// Loads per-provider rate limits from a standalone TOML file.
+///
+/// The file holds only rate limits, so it can be managed separately from
+/// provider credentials:
+///
+/// ```toml
+/// [providers.claude]
+/// requests_per_minute = 40
+/// tokens_per_minute = 50000
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use acton_ai::config::load_rate_limits;
+/// use std::path::Path;
+///
+/// let limits = load_rate_limits(Path::new("/etc/acton-ai/rate-limits.toml"))?;
+/// ```
+pub fn load_rate_limits(path: &Path) -> Result<HashMap<String, RateLimitFileConfig>, ActonAIError> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        ActonAIError::new(ActonAIErrorKind::Configuration {
+            field: "rate_limit_file".to_string(),
+            reason: format!("failed to read '{}': {}", path.display(), e),
+        })
+    })?;
+
+    toml::from_str::<RateLimitFile>(&contents)
+        .map(|file| file.providers)
+        .map_err(|e| {
+            ActonAIError::new(ActonAIErrorKind::Configuration {
+                field: "rate_limit_file".to_string(),
+                reason: format!("failed to parse '{}': {}", path.display(), e),
+            })
+        })
+}
+
 /// Returns the paths that would be searched for configuration files.
 ///
 /// This is useful for diagnostics and user guidance.
@@ -186,6 +280,75 @@ mod tests {
         let _ = config;
     }
 
+    fn write_config(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn project_local_config_overrides_global() {
+        let temp_dir = TempDir::new().unwrap();
+        let global = write_config(
+            temp_dir.path(),
+            "global.toml",
+            r#"
+default_provider = "claude"
+
+[providers.claude]
+type = "anthropic"
+model = "claude-sonnet-4-20250514"
+
+[sandbox.limits]
+max_execution_ms = 1000
+max_memory_mb = 128
+        "#,
+        );
+        let local = write_config(
+            temp_dir.path(),
+            "acton-ai.toml",
+            r#"
+default_provider = "local"
+
+[providers.local]
+type = "ollama"
+model = "qwen2.5:7b"
+
+[sandbox.limits]
+max_memory_mb = 512
+        "#,
+        );
+        let missing = temp_dir.path().join("missing.toml");
+
+        let config = load_layers(&[global, missing, local]).unwrap();
+
+        assert_eq!(config.default_provider.as_deref(), Some("local"));
+        assert_eq!(config.provider_count(), 2);
+        let limits = config.sandbox.unwrap().limits.unwrap();
+        assert_eq!(limits.max_execution_ms, Some(1000));
+        assert_eq!(limits.max_memory_mb, Some(512));
+    }
+
+    #[test]
+    fn env_overrides_are_the_final_layer() {
+        let config = from_str(
+            r#"
+default_provider = "local"
+log_level = "info"
+        "#,
+        )
+        .unwrap();
+
+        let config = apply_env_overrides(config, |name| match name {
+            DEFAULT_PROVIDER_ENV => Some("claude".to_string()),
+            LOG_LEVEL_ENV => Some(String::new()),
+            _ => None,
+        });
+
+        assert_eq!(config.default_provider.as_deref(), Some("claude"));
+        assert_eq!(config.log_level.as_deref(), Some("info"));
+    }
+
     #[test]
     fn from_str_parses_valid_toml() {
         let toml = r#"
@@ -293,6 +456,38 @@ api_key_env = "OPENAI_API_KEY"
         assert!(err.is_configuration());
     }
 
+    #[test]
+    fn load_rate_limits_reads_provider_limits() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = write_config(
+            temp_dir.path(),
+            "rate-limits.toml",
+            r#"
+[providers.claude]
+requests_per_minute = 40
+tokens_per_minute = 50000
+
+[providers.local]
+requests_per_minute = 1000
+tokens_per_minute = 1000000
+burst_capacity = 20
+        "#,
+        );
+
+        let limits = load_rate_limits(&path).unwrap();
+
+        assert_eq!(limits.len(), 2);
+        assert_eq!(limits["claude"].requests_per_minute, 40);
+        assert_eq!(limits["claude"].tokens_per_minute, 50_000);
+        assert_eq!(limits["local"].burst_capacity, Some(20));
+
+        let invalid = write_config(temp_dir.path(), "invalid.toml", "[providers.claude]\n");
+        assert!(load_rate_limits(&invalid).unwrap_err().is_configuration());
+        assert!(load_rate_limits(&temp_dir.path().join("missing.toml"))
+            .unwrap_err()
+            .is_configuration());
+    }
+
     #[test]
     fn search_paths_includes_local() {
         let paths = search_paths();
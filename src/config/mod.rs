@@ -6,9 +6,13 @@
 //!
 //! # Configuration File Format
 //!
-//! Configuration is stored in TOML format. The search order is:
-//! 1. `./acton-ai.toml` (project-local)
-//! 2. `~/.config/acton-ai/config.toml` (XDG config)
+//! Configuration is stored in TOML format. [`load`] merges these files, later
+//! layers overriding earlier ones:
+//! 1. `~/.config/acton-ai/config.toml` (XDG config)
+//! 2. `./acton-ai.toml` (project-local)
+//!
+//! [`load_with_env_overrides`] adds a final layer from the
+//! `ACTON_AI_DEFAULT_PROVIDER` and `ACTON_AI_LOG_LEVEL` environment variables.
 //!
 //! # Example Configuration
 //!
@@ -61,17 +65,31 @@
 //!
 //! // Parse from a string
 //! let config = config::from_str(toml_content)?;
+//!
+//! // Load per-provider rate limits kept in a separate file
+//! let limits = config::load_rate_limits(Path::new("rate-limits.toml"))?;
+//!
+//! // Report every problem at once, e.g. in CI
+//! for error in config.validate() {
+//!     eprintln!("{error}");
+//! }
 //! ```
 
 mod file;
 mod types;
+mod validate;
 
 // Re-export file loading functions
-pub use file::{from_path, from_str, load, search_paths, xdg_config_dir};
+pub use file::{
+    from_path, from_str, load, load_rate_limits, load_with_env_overrides, search_paths,
+    xdg_config_dir, DEFAULT_PROVIDER_ENV, LOG_LEVEL_ENV,
+};
 
 // Re-export types
 pub use types::{
-    parse_truncation_strategy, ActonAIConfig, ActonAIDefaults, CliFileConfig, ContextFileConfig,
-    JobConfig, NamedProviderConfig, PersistenceFileConfig, RateLimitFileConfig, SandboxFileConfig,
-    SandboxLimitsConfig, SkillsFileConfig,
+    parse_log_level, parse_truncation_strategy, ActonAIConfig, ActonAIDefaults,
+    CircuitBreakerFileConfig, CliFileConfig, ContextFileConfig, JobConfig, NamedProviderConfig,
+    PersistenceFileConfig, RateLimitFileConfig, SandboxFileConfig, SandboxLimitsConfig,
+    SkillsFileConfig,
 };
+pub use validate::{validate_file, ConfigValidationError};
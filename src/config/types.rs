@@ -3,7 +3,10 @@
 //! This module provides types for defining multiple named LLM providers
 //! and sandbox settings in configuration files.
 
-use crate::llm::{ProviderConfig, ProviderType, RateLimitConfig, SamplingParams};
+use crate::kernel::LogLevel;
+use crate::llm::{
+    CircuitBreakerConfig, ProviderConfig, ProviderType, RateLimitConfig, SamplingParams,
+};
 use crate::tools::sandbox::{HardeningMode, ProcessSandboxConfig};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -50,6 +53,14 @@ pub struct ActonAIConfig {
     /// default is specified, an error occurs at launch.
     pub default_provider: Option<String>,
 
+    /// The provider that receives the default provider's requests while its
+    /// circuit breaker is open.
+    ///
+    /// Only takes effect when the default provider has a `circuit_breaker`
+    /// section.
+    #[serde(default)]
+    pub fallback_provider: Option<String>,
+
     /// Sandbox configuration for tool execution isolation.
     ///
     /// When present, configures the [`ProcessSandbox`](crate::tools::sandbox::ProcessSandbox)
@@ -94,6 +105,13 @@ pub struct ActonAIConfig {
     /// global `max_tokens` here.
     #[serde(default)]
     pub context: Option<ContextFileConfig>,
+
+    /// Minimum level of kernel log events. See [`parse_log_level`].
+    ///
+    /// Overridden by the `ACTON_AI_LOG_LEVEL` environment variable when
+    /// loaded with [`load_with_env_overrides`](crate::config::load_with_env_overrides).
+    #[serde(default)]
+    pub log_level: Option<String>,
 }
 
 /// Skills configuration loaded from `[skills]` in TOML.
@@ -149,6 +167,32 @@ pub fn parse_truncation_strategy(s: &str) -> Option<crate::memory::TruncationStr
     }
 }
 
+/// Parses a log level (`trace`, `debug`, `info`, `warn`, or `error`,
+/// case-insensitive) into a [`LogLevel`]; returns `None` for unknown strings.
+#[must_use]
+pub fn parse_log_level(s: &str) -> Option<LogLevel> {
+    match s.trim().to_lowercase().as_str() {
+        "trace" => Some(LogLevel::Trace),
+        "debug" => Some(LogLevel::Debug),
+        "info" => Some(LogLevel::Info),
+        "warn" | "warning" => Some(LogLevel::Warn),
+        "error" => Some(LogLevel::Error),
+        _ => None,
+    }
+}
+
+/// Merges two optional config sections, using `merge` when both are set.
+fn merge_section<T>(
+    base: Option<T>,
+    overlay: Option<T>,
+    merge: impl FnOnce(T, T) -> T,
+) -> Option<T> {
+    match (base, overlay) {
+        (Some(base), Some(overlay)) => Some(merge(base, overlay)),
+        (base, overlay) => overlay.or(base),
+    }
+}
+
 impl ActonAIConfig {
     /// Creates an empty configuration.
     #[must_use]
@@ -170,6 +214,13 @@ impl ActonAIConfig {
         self
     }
 
+    /// Sets the fallback provider name.
+    #[must_use]
+    pub fn with_fallback_provider(mut self, name: impl Into<String>) -> Self {
+        self.fallback_provider = Some(name.into());
+        self
+    }
+
     /// Returns the effective default provider name.
     ///
     /// Returns the explicitly set default, or if exactly one provider
@@ -198,15 +249,99 @@ impl ActonAIConfig {
     pub fn provider_count(&self) -> usize {
         self.providers.len()
     }
+
+    /// Layers `overlay` on top of this configuration.
+    ///
+    /// Providers and jobs are merged by name, with `overlay` winning on
+    /// collisions, and skill paths are appended. Every other setting,
+    /// including each field of the `[sandbox]`, `[defaults]`, `[context]`,
+    /// `[cli]`, and `[persistence]` sections, takes the overlay value when
+    /// the overlay sets it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use acton_ai::config::{ActonAIConfig, NamedProviderConfig};
+    ///
+    /// let global = ActonAIConfig::new()
+    ///     .with_provider("claude", NamedProviderConfig::anthropic("claude-sonnet-4-20250514"))
+    ///     .with_default_provider("claude");
+    /// let local = ActonAIConfig::new()
+    ///     .with_provider("local", NamedProviderConfig::ollama("qwen2.5:7b"))
+    ///     .with_default_provider("local");
+    ///
+    /// let config = ActonAIConfig::merge(global, local);
+    /// assert_eq!(config.provider_count(), 2);
+    /// assert_eq!(config.default_provider.as_deref(), Some("local"));
+    /// ```
+    #[must_use]
+    pub fn merge(mut self, overlay: ActonAIConfig) -> ActonAIConfig {
+        self.providers.extend(overlay.providers);
+
+        ActonAIConfig {
+            providers: self.providers,
+            default_provider: overlay.default_provider.or(self.default_provider),
+            fallback_provider: overlay.fallback_provider.or(self.fallback_provider),
+            sandbox: merge_section(self.sandbox, overlay.sandbox, |base, overlay| {
+                SandboxFileConfig {
+                    hardening: overlay.hardening.or(base.hardening),
+                    limits: merge_section(base.limits, overlay.limits, |base, overlay| {
+                        SandboxLimitsConfig {
+                            max_execution_ms: overlay.max_execution_ms.or(base.max_execution_ms),
+                            max_memory_mb: overlay.max_memory_mb.or(base.max_memory_mb),
+                        }
+                    }),
+                }
+            }),
+            persistence: merge_section(self.persistence, overlay.persistence, |base, overlay| {
+                PersistenceFileConfig {
+                    db_path: overlay.db_path.or(base.db_path),
+                }
+            }),
+            cli: merge_section(self.cli, overlay.cli, |base, overlay| CliFileConfig {
+                default_session: overlay.default_session.or(base.default_session),
+                default_system_prompt: overlay.default_system_prompt.or(base.default_system_prompt),
+            }),
+            jobs: merge_section(self.jobs, overlay.jobs, |mut base, overlay| {
+                base.extend(overlay);
+                base
+            }),
+            defaults: merge_section(self.defaults, overlay.defaults, |base, overlay| {
+                ActonAIDefaults {
+                    max_tool_rounds: overlay.max_tool_rounds.or(base.max_tool_rounds),
+                }
+            }),
+            skills: merge_section(self.skills, overlay.skills, |mut base, overlay| {
+                for path in overlay.paths {
+                    if !base.paths.contains(&path) {
+                        base.paths.push(path);
+                    }
+                }
+                base
+            }),
+            context: merge_section(self.context, overlay.context, |base, overlay| {
+                ContextFileConfig {
+                    max_tokens: overlay.max_tokens.or(base.max_tokens),
+                    reserved_for_response: overlay
+                        .reserved_for_response
+                        .or(base.reserved_for_response),
+                    strategy: overlay.strategy.or(base.strategy),
+                }
+            }),
+            log_level: overlay.log_level.or(self.log_level),
+        }
+    }
 }
 
 /// Configuration for a single named provider.
 ///
-/// This structure supports all provider types (Anthropic, OpenAI, Ollama)
-/// through a unified configuration format.
+/// This structure supports all provider types (Anthropic, OpenAI, Azure
+/// OpenAI, Ollama, Cohere, Gemini, Mistral) through a unified configuration
+/// format.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NamedProviderConfig {
-    /// The provider type: "anthropic", "openai", or "ollama".
+    /// The provider type: "anthropic", "openai", "azure-openai", "ollama",
+    /// "cohere", "gemini", or "mistral".
     #[serde(rename = "type")]
     pub provider_type: String,
 
@@ -231,6 +366,14 @@ pub struct NamedProviderConfig {
     #[serde(default)]
     pub base_url: Option<String>,
 
+    /// Azure OpenAI resource name. Required for `type = "azure-openai"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub azure_resource: Option<String>,
+
+    /// Azure OpenAI deployment name. Defaults to `model` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub azure_deployment: Option<String>,
+
     /// Request timeout in seconds.
     ///
     /// Defaults to 120 seconds for cloud providers, 300 for local.
@@ -282,6 +425,20 @@ pub struct NamedProviderConfig {
     /// When `None`, the global default applies.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub context_window_tokens: Option<usize>,
+
+    /// Circuit breaker settings. The breaker is disabled when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerFileConfig>,
+
+    /// Text placed before the system prompt of every request to this
+    /// provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_prefix: Option<String>,
+
+    /// Text placed after the system prompt of every request to this
+    /// provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_suffix: Option<String>,
 }
 
 impl NamedProviderConfig {
@@ -294,6 +451,8 @@ impl NamedProviderConfig {
             api_key: None,
             api_key_env: Some("ANTHROPIC_API_KEY".to_string()),
             base_url: None,
+            azure_resource: None,
+            azure_deployment: None,
             timeout_secs: None,
             max_tokens: None,
             rate_limit: None,
@@ -305,6 +464,9 @@ impl NamedProviderConfig {
             seed: None,
             stop_sequences: None,
             context_window_tokens: None,
+            circuit_breaker: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
         }
     }
 
@@ -317,6 +479,123 @@ impl NamedProviderConfig {
             api_key: None,
             api_key_env: Some("OPENAI_API_KEY".to_string()),
             base_url: None,
+            azure_resource: None,
+            azure_deployment: None,
+            timeout_secs: None,
+            max_tokens: None,
+            rate_limit: None,
+            temperature: None,
+            top_k: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            seed: None,
+            stop_sequences: None,
+            context_window_tokens: None,
+            circuit_breaker: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
+        }
+    }
+
+    /// Creates a new Cohere provider configuration.
+    #[must_use]
+    pub fn cohere(model: impl Into<String>) -> Self {
+        Self {
+            provider_type: "cohere".to_string(),
+            model: model.into(),
+            api_key: None,
+            api_key_env: Some("CO_API_KEY".to_string()),
+            base_url: None,
+            azure_resource: None,
+            azure_deployment: None,
+            timeout_secs: None,
+            max_tokens: None,
+            rate_limit: None,
+            temperature: None,
+            top_k: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            seed: None,
+            stop_sequences: None,
+            context_window_tokens: None,
+            circuit_breaker: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
+        }
+    }
+
+    /// Creates a new Google Gemini provider configuration.
+    #[must_use]
+    pub fn gemini(model: impl Into<String>) -> Self {
+        Self {
+            provider_type: "gemini".to_string(),
+            model: model.into(),
+            api_key: None,
+            api_key_env: Some("GOOGLE_API_KEY".to_string()),
+            base_url: None,
+            azure_resource: None,
+            azure_deployment: None,
+            timeout_secs: None,
+            max_tokens: None,
+            rate_limit: None,
+            temperature: None,
+            top_k: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            seed: None,
+            stop_sequences: None,
+            context_window_tokens: None,
+            circuit_breaker: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
+        }
+    }
+
+    /// Creates a new Mistral AI provider configuration.
+    #[must_use]
+    pub fn mistral(model: impl Into<String>) -> Self {
+        Self {
+            provider_type: "mistral".to_string(),
+            model: model.into(),
+            api_key: None,
+            api_key_env: Some("MISTRAL_API_KEY".to_string()),
+            base_url: None,
+            azure_resource: None,
+            azure_deployment: None,
+            timeout_secs: None,
+            max_tokens: None,
+            rate_limit: None,
+            temperature: None,
+            top_k: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            seed: None,
+            stop_sequences: None,
+            context_window_tokens: None,
+            circuit_breaker: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
+        }
+    }
+
+    /// Creates a new Azure OpenAI provider configuration.
+    ///
+    /// The deployment name doubles as the model name.
+    #[must_use]
+    pub fn azure_openai(resource: impl Into<String>, deployment: impl Into<String>) -> Self {
+        let deployment = deployment.into();
+        Self {
+            provider_type: "azure-openai".to_string(),
+            model: deployment.clone(),
+            api_key: None,
+            api_key_env: Some("AZURE_OPENAI_API_KEY".to_string()),
+            base_url: None,
+            azure_resource: Some(resource.into()),
+            azure_deployment: Some(deployment),
             timeout_secs: None,
             max_tokens: None,
             rate_limit: None,
@@ -328,6 +607,9 @@ impl NamedProviderConfig {
             seed: None,
             stop_sequences: None,
             context_window_tokens: None,
+            circuit_breaker: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
         }
     }
 
@@ -340,12 +622,11 @@ impl NamedProviderConfig {
             api_key: None,
             api_key_env: None,
             base_url: Some("http://localhost:11434/v1".to_string()),
+            azure_resource: None,
+            azure_deployment: None,
             timeout_secs: Some(300),
             max_tokens: None,
-            rate_limit: Some(RateLimitFileConfig {
-                requests_per_minute: 1000,
-                tokens_per_minute: 1_000_000,
-            }),
+            rate_limit: Some(RateLimitFileConfig::new(1000, 1_000_000)),
             temperature: None,
             top_k: None,
             top_p: None,
@@ -354,6 +635,9 @@ impl NamedProviderConfig {
             seed: None,
             stop_sequences: None,
             context_window_tokens: None,
+            circuit_breaker: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
         }
     }
 
@@ -409,6 +693,27 @@ impl NamedProviderConfig {
         self
     }
 
+    /// Sets the circuit breaker configuration.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerFileConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Sets the text placed before every system prompt.
+    #[must_use]
+    pub fn with_system_prompt_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.system_prompt_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Sets the text placed after every system prompt.
+    #[must_use]
+    pub fn with_system_prompt_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.system_prompt_suffix = Some(suffix.into());
+        self
+    }
+
     /// Sets the temperature.
     #[must_use]
     pub fn with_temperature(mut self, temperature: f64) -> Self {
@@ -441,7 +746,7 @@ impl NamedProviderConfig {
     ///
     /// Resolution order:
     /// 1. `api_key_env` - read from environment variable
-    /// 2. Standard env var based on type (ANTHROPIC_API_KEY, OPENAI_API_KEY)
+    /// 2. Standard env var based on type (ANTHROPIC_API_KEY, OPENAI_API_KEY, CO_API_KEY)
     /// 3. `api_key` - direct value in config (discouraged)
     /// 4. Empty string (for Ollama/local providers)
     #[must_use]
@@ -459,6 +764,10 @@ impl NamedProviderConfig {
         let standard_env = match self.provider_type.to_lowercase().as_str() {
             "anthropic" => Some("ANTHROPIC_API_KEY"),
             "openai" => Some("OPENAI_API_KEY"),
+            "cohere" => Some("CO_API_KEY"),
+            "gemini" => Some("GOOGLE_API_KEY"),
+            "mistral" => Some("MISTRAL_API_KEY"),
+            "azure-openai" => Some("AZURE_OPENAI_API_KEY"),
             _ => None,
         };
 
@@ -490,6 +799,14 @@ impl NamedProviderConfig {
             "anthropic" => ProviderConfig::anthropic(&api_key).with_model(&self.model),
             "openai" => ProviderConfig::openai(&api_key).with_model(&self.model),
             "ollama" => ProviderConfig::ollama(&self.model),
+            "cohere" => ProviderConfig::cohere(&api_key).with_model(&self.model),
+            "gemini" => ProviderConfig::gemini(&api_key).with_model(&self.model),
+            "mistral" => ProviderConfig::mistral(&api_key).with_model(&self.model),
+            "azure-openai" => ProviderConfig::openai_azure(
+                self.azure_resource.as_deref().unwrap_or_default(),
+                self.azure_deployment.as_deref().unwrap_or(&self.model),
+                &api_key,
+            ),
             _ => {
                 // Treat unknown types as OpenAI-compatible
                 let base_url = self
@@ -504,9 +821,18 @@ impl NamedProviderConfig {
 
         // Apply overrides
         if let Some(ref url) = self.base_url {
-            config = config
-                .with_base_url(url)
-                .with_provider_type(ProviderType::openai_compatible(url));
+            config = config.with_base_url(url);
+            // Cohere, Gemini, Mistral and Azure keep their own wire formats behind a
+            // custom base URL
+            if !matches!(
+                config.provider_type,
+                ProviderType::Cohere
+                    | ProviderType::Gemini
+                    | ProviderType::Mistral
+                    | ProviderType::AzureOpenAI { .. }
+            ) {
+                config = config.with_provider_type(ProviderType::openai_compatible(url));
+            }
         }
 
         if let Some(secs) = self.timeout_secs {
@@ -521,6 +847,18 @@ impl NamedProviderConfig {
             config = config.with_rate_limit(rate_limit.to_rate_limit_config());
         }
 
+        if let Some(ref circuit_breaker) = self.circuit_breaker {
+            config = config.with_circuit_breaker(circuit_breaker.to_circuit_breaker_config());
+        }
+
+        if let Some(ref prefix) = self.system_prompt_prefix {
+            config = config.with_system_prompt_prefix(prefix);
+        }
+
+        if let Some(ref suffix) = self.system_prompt_suffix {
+            config = config.with_system_prompt_suffix(suffix);
+        }
+
         // Build sampling params from individual fields
         let mut sampling = SamplingParams::default();
         if let Some(temp) = self.temperature {
@@ -711,6 +1049,13 @@ impl SandboxLimitsConfig {
 /// Rate limiting configuration for config files.
 ///
 /// This is a simplified version of RateLimitConfig for TOML serialization.
+///
+/// ```toml
+/// [providers.claude.rate_limit]
+/// requests_per_minute = 50
+/// tokens_per_minute = 40000
+/// burst_capacity = 10   # optional; enables the token bucket
+/// ```
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct RateLimitFileConfig {
     /// Maximum requests per minute.
@@ -718,6 +1063,11 @@ pub struct RateLimitFileConfig {
 
     /// Maximum tokens per minute (input + output).
     pub tokens_per_minute: u32,
+
+    /// Burst size of a [token bucket](RateLimitConfig::token_bucket).
+    /// When unset, requests are limited per one-minute window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst_capacity: Option<u32>,
 }
 
 impl RateLimitFileConfig {
@@ -727,21 +1077,76 @@ impl RateLimitFileConfig {
         Self {
             requests_per_minute,
             tokens_per_minute,
+            burst_capacity: None,
         }
     }
 
+    /// Sets the burst capacity, enabling the token bucket algorithm.
+    #[must_use]
+    pub fn with_burst_capacity(mut self, burst_capacity: u32) -> Self {
+        self.burst_capacity = Some(burst_capacity);
+        self
+    }
+
     /// Converts to the runtime RateLimitConfig.
     #[must_use]
     pub fn to_rate_limit_config(&self) -> RateLimitConfig {
-        RateLimitConfig::new(self.requests_per_minute, self.tokens_per_minute)
+        let config = RateLimitConfig::new(self.requests_per_minute, self.tokens_per_minute);
+        match self.burst_capacity {
+            Some(burst_capacity) => config.token_bucket(burst_capacity),
+            None => config,
+        }
     }
 }
 
 impl Default for RateLimitFileConfig {
+    fn default() -> Self {
+        Self::new(50, 40_000)
+    }
+}
+
+/// Circuit breaker configuration for config files.
+///
+/// ```toml
+/// [providers.claude.circuit_breaker]
+/// failure_threshold = 5
+/// cooldown_secs = 30
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CircuitBreakerFileConfig {
+    /// Consecutive failures that open the circuit.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// Seconds the circuit stays open before a probe request is let through.
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+fn default_failure_threshold() -> u32 {
+    CircuitBreakerConfig::default().failure_threshold
+}
+
+fn default_cooldown_secs() -> u64 {
+    CircuitBreakerConfig::default().cooldown.as_secs()
+}
+
+impl CircuitBreakerFileConfig {
+    /// Converts to the runtime CircuitBreakerConfig.
+    #[must_use]
+    pub fn to_circuit_breaker_config(&self) -> CircuitBreakerConfig {
+        CircuitBreakerConfig::new(
+            self.failure_threshold,
+            Duration::from_secs(self.cooldown_secs),
+        )
+    }
+}
+
+impl Default for CircuitBreakerFileConfig {
     fn default() -> Self {
         Self {
-            requests_per_minute: 50,
-            tokens_per_minute: 40_000,
+            failure_threshold: default_failure_threshold(),
+            cooldown_secs: default_cooldown_secs(),
         }
     }
 }
@@ -925,6 +1330,71 @@ mod tests {
         assert_eq!(provider.base_url, "http://localhost:11434/v1");
     }
 
+    #[test]
+    fn azure_openai_parses_from_toml() {
+        let toml_str = r#"
+[providers.azure]
+type = "azure-openai"
+model = "gpt-4o"
+api_key = "azure-key"
+azure_resource = "contoso"
+azure_deployment = "gpt-4o-prod"
+"#;
+
+        let config: ActonAIConfig = toml::from_str(toml_str).unwrap();
+        let provider = config.providers.get("azure").unwrap().to_provider_config();
+
+        assert_eq!(
+            provider.provider_type,
+            ProviderType::azure_openai("contoso", "gpt-4o-prod")
+        );
+        assert_eq!(
+            provider.base_url,
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o-prod"
+        );
+        assert_eq!(provider.model, "gpt-4o-prod");
+    }
+
+    #[test]
+    fn named_provider_config_to_provider_config_cohere_keeps_type_with_base_url() {
+        let config = NamedProviderConfig::cohere("command-r-08-2024")
+            .with_api_key("co-key")
+            .with_base_url("https://cohere.internal.example");
+
+        let provider = config.to_provider_config();
+
+        assert_eq!(provider.provider_type, ProviderType::Cohere);
+        assert_eq!(provider.model, "command-r-08-2024");
+        assert_eq!(provider.base_url, "https://cohere.internal.example");
+    }
+
+    #[test]
+    fn named_provider_config_to_provider_config_mistral() {
+        let config = NamedProviderConfig::mistral("mistral-small-latest")
+            .with_api_key("mistral-key")
+            .with_base_url("https://mistral.internal.example");
+
+        let provider = config.to_provider_config();
+
+        assert_eq!(provider.provider_type, ProviderType::Mistral);
+        assert_eq!(provider.model, "mistral-small-latest");
+        assert_eq!(provider.base_url, "https://mistral.internal.example");
+    }
+
+    #[test]
+    fn named_provider_config_to_provider_config_gemini() {
+        let config = NamedProviderConfig::gemini("gemini-1.5-pro").with_api_key("AIza-key");
+
+        let provider = config.to_provider_config();
+
+        assert_eq!(provider.provider_type, ProviderType::Gemini);
+        assert_eq!(provider.model, "gemini-1.5-pro");
+        assert_eq!(
+            provider.base_url,
+            "https://generativelanguage.googleapis.com"
+        );
+    }
+
     #[test]
     fn named_provider_config_resolve_api_key_direct() {
         let config = NamedProviderConfig::anthropic("test").with_api_key("direct-key");
@@ -942,6 +1412,68 @@ mod tests {
 
         assert_eq!(runtime.requests_per_minute, 100);
         assert_eq!(runtime.tokens_per_minute, 50_000);
+        assert_eq!(
+            runtime.algorithm,
+            crate::llm::RateLimitAlgorithm::SlidingWindow
+        );
+
+        let bursty = file_config.with_burst_capacity(5).to_rate_limit_config();
+        assert_eq!(
+            bursty.algorithm,
+            crate::llm::RateLimitAlgorithm::TokenBucket { burst_capacity: 5 }
+        );
+    }
+
+    #[test]
+    fn circuit_breaker_and_fallback_parse_from_toml() {
+        let toml_str = r#"
+default_provider = "claude"
+fallback_provider = "ollama"
+
+[providers.claude]
+type = "anthropic"
+model = "claude-sonnet-4-20250514"
+
+[providers.claude.circuit_breaker]
+failure_threshold = 3
+
+[providers.ollama]
+type = "ollama"
+model = "qwen2.5:7b"
+"#;
+
+        let config: ActonAIConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.fallback_provider, Some("ollama".to_string()));
+
+        let claude = config.providers.get("claude").unwrap().to_provider_config();
+        let breaker = claude.circuit_breaker.unwrap();
+        assert_eq!(breaker.failure_threshold, 3);
+        assert_eq!(breaker.cooldown, Duration::from_secs(30));
+
+        let ollama = config.providers.get("ollama").unwrap().to_provider_config();
+        assert!(ollama.circuit_breaker.is_none());
+    }
+
+    #[test]
+    fn system_prompt_affixes_parse_from_toml() {
+        let toml_str = r#"
+[providers.claude]
+type = "anthropic"
+model = "claude-sonnet-4-20250514"
+system_prompt_prefix = "Never share customer data. "
+system_prompt_suffix = " Answer in English."
+"#;
+
+        let config: ActonAIConfig = toml::from_str(toml_str).unwrap();
+        let claude = config.providers.get("claude").unwrap().to_provider_config();
+        assert_eq!(
+            claude.system_prompt_prefix.as_deref(),
+            Some("Never share customer data. ")
+        );
+        assert_eq!(
+            claude.system_prompt_suffix.as_deref(),
+            Some(" Answer in English.")
+        );
     }
 
     #[test]
@@ -1204,11 +1736,60 @@ max_execution_ms = 60000
         assert!(limits.max_memory_mb.is_none());
     }
 
+    #[test]
+    fn merge_prefers_overlay_field_by_field() {
+        let base = ActonAIConfig {
+            sandbox: Some(
+                SandboxFileConfig::new()
+                    .with_hardening(HardeningMode::Enforce)
+                    .with_limits(SandboxLimitsConfig::new().with_max_execution_ms(1000)),
+            ),
+            skills: Some(SkillsFileConfig {
+                paths: vec![PathBuf::from("shared"), PathBuf::from("global")],
+            }),
+            log_level: Some("info".to_string()),
+            ..ActonAIConfig::new()
+        }
+        .with_provider("claude", NamedProviderConfig::anthropic("old"))
+        .with_default_provider("claude");
+        let overlay = ActonAIConfig {
+            sandbox: Some(
+                SandboxFileConfig::new()
+                    .with_limits(SandboxLimitsConfig::new().with_max_memory_mb(512)),
+            ),
+            skills: Some(SkillsFileConfig {
+                paths: vec![PathBuf::from("shared"), PathBuf::from("local")],
+            }),
+            ..ActonAIConfig::new()
+        }
+        .with_provider("claude", NamedProviderConfig::anthropic("new"));
+
+        let merged = base.merge(overlay);
+
+        assert_eq!(merged.providers["claude"].model, "new");
+        assert_eq!(merged.default_provider.as_deref(), Some("claude"));
+        assert_eq!(merged.log_level.as_deref(), Some("info"));
+        let sandbox = merged.sandbox.unwrap();
+        assert_eq!(sandbox.hardening, Some(HardeningMode::Enforce));
+        let limits = sandbox.limits.unwrap();
+        assert_eq!(limits.max_execution_ms, Some(1000));
+        assert_eq!(limits.max_memory_mb, Some(512));
+        assert_eq!(
+            merged.skills.unwrap().paths,
+            vec![
+                PathBuf::from("shared"),
+                PathBuf::from("global"),
+                PathBuf::from("local")
+            ]
+        );
+    }
+
     #[test]
     fn config_serialization_roundtrip_with_sandbox() {
         let config = ActonAIConfig {
             providers: HashMap::new(),
             default_provider: None,
+            fallback_provider: None,
             sandbox: Some(SandboxFileConfig {
                 hardening: Some(HardeningMode::Enforce),
                 limits: Some(SandboxLimitsConfig {
@@ -1222,6 +1803,7 @@ max_execution_ms = 60000
             defaults: None,
             skills: None,
             context: None,
+            log_level: None,
         };
 
         let toml_str = toml::to_string(&config).unwrap();
@@ -0,0 +1,365 @@
+//! Configuration validation.
+//!
+//! A misconfigured provider otherwise only fails when it is first used.
+//! [`ActonAIConfig::validate`] checks the whole configuration up front and
+//! reports every problem at once.
+
+use crate::config::file::from_path;
+use crate::config::types::{parse_log_level, ActonAIConfig, NamedProviderConfig};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+/// A problem found by [`ActonAIConfig::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValidationError {
+    /// The configuration file could not be read or parsed
+    LoadFailed {
+        /// The configuration file
+        path: PathBuf,
+        /// Why it could not be loaded
+        reason: String,
+    },
+    /// `api_key_env` names an environment variable that is not set
+    MissingEnvVar {
+        /// Name of the provider
+        provider: String,
+        /// The unset variable
+        var: String,
+    },
+    /// `base_url` is not a valid URL
+    InvalidBaseUrl {
+        /// Name of the provider
+        provider: String,
+        /// The configured value
+        url: String,
+        /// Why it failed to parse
+        reason: String,
+    },
+    /// `timeout_secs` is zero
+    ZeroTimeout {
+        /// Name of the provider
+        provider: String,
+    },
+    /// `max_tokens` is zero
+    ZeroMaxTokens {
+        /// Name of the provider
+        provider: String,
+    },
+    /// `temperature` is outside the range the provider accepts
+    TemperatureOutOfRange {
+        /// Name of the provider
+        provider: String,
+        /// The configured temperature
+        temperature: f64,
+        /// Lowest accepted temperature
+        min: f64,
+        /// Highest accepted temperature
+        max: f64,
+    },
+    /// `rate_limit.requests_per_minute` is zero
+    ZeroRequestsPerMinute {
+        /// Name of the provider
+        provider: String,
+    },
+    /// `default_provider` or `fallback_provider` names no configured provider
+    UnknownProvider {
+        /// The referencing field
+        field: &'static str,
+        /// The referenced name
+        name: String,
+    },
+    /// `log_level` is not a known level
+    InvalidLogLevel {
+        /// The configured value
+        level: String,
+    },
+}
+
+impl fmt::Display for ConfigValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LoadFailed { path, reason } => {
+                write!(f, "cannot load '{}': {}", path.display(), reason)
+            }
+            Self::MissingEnvVar { provider, var } => write!(
+                f,
+                "provider '{provider}': environment variable '{var}' from api_key_env is not set"
+            ),
+            Self::InvalidBaseUrl {
+                provider,
+                url,
+                reason,
+            } => write!(
+                f,
+                "provider '{provider}': base_url '{url}' is not a valid URL ({reason})"
+            ),
+            Self::ZeroTimeout { provider } => {
+                write!(
+                    f,
+                    "provider '{provider}': timeout_secs must be greater than 0"
+                )
+            }
+            Self::ZeroMaxTokens { provider } => {
+                write!(
+                    f,
+                    "provider '{provider}': max_tokens must be greater than 0"
+                )
+            }
+            Self::TemperatureOutOfRange {
+                provider,
+                temperature,
+                min,
+                max,
+            } => write!(
+                f,
+                "provider '{provider}': temperature {temperature} is outside {min}..={max}"
+            ),
+            Self::ZeroRequestsPerMinute { provider } => write!(
+                f,
+                "provider '{provider}': rate_limit.requests_per_minute must be greater than 0"
+            ),
+            Self::UnknownProvider { field, name } => {
+                write!(f, "{field} '{name}' is not a configured provider")
+            }
+            Self::InvalidLogLevel { level } => write!(
+                f,
+                "log_level '{level}' is not one of trace, debug, info, warn, error"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigValidationError {}
+
+/// Temperatures accepted by each provider type.
+fn temperature_range(provider_type: &str) -> RangeInclusive<f64> {
+    match provider_type.to_lowercase().as_str() {
+        "anthropic" | "cohere" => 0.0..=1.0,
+        "mistral" => 0.0..=1.5,
+        _ => 0.0..=2.0,
+    }
+}
+
+impl ActonAIConfig {
+    /// Checks the configuration for problems that would otherwise only
+    /// surface when a provider is first used.
+    ///
+    /// For every provider this checks that the `api_key_env` variable is
+    /// set, `base_url` parses as a URL, `timeout_secs`, `max_tokens`, and
+    /// `rate_limit.requests_per_minute` are non-zero, and `temperature` is
+    /// within the range the provider type accepts. It also checks that
+    /// `default_provider` and `fallback_provider` name configured providers
+    /// and that `log_level` is a known level.
+    ///
+    /// Returns every problem found, ordered by provider name; an empty list
+    /// means the configuration is valid.
+    #[must_use]
+    pub fn validate(&self) -> Vec<ConfigValidationError> {
+        let mut names: Vec<&String> = self.providers.keys().collect();
+        names.sort();
+
+        let mut errors = Vec::new();
+        for name in names {
+            validate_provider(name, &self.providers[name], &mut errors);
+        }
+
+        for (field, reference) in [
+            ("default_provider", &self.default_provider),
+            ("fallback_provider", &self.fallback_provider),
+        ] {
+            if let Some(name) = reference {
+                if !self.providers.contains_key(name) {
+                    errors.push(ConfigValidationError::UnknownProvider {
+                        field,
+                        name: name.clone(),
+                    });
+                }
+            }
+        }
+
+        if let Some(level) = &self.log_level {
+            if parse_log_level(level).is_none() {
+                errors.push(ConfigValidationError::InvalidLogLevel {
+                    level: level.clone(),
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+fn validate_provider(
+    name: &str,
+    config: &NamedProviderConfig,
+    errors: &mut Vec<ConfigValidationError>,
+) {
+    let provider = || name.to_string();
+
+    if let Some(var) = &config.api_key_env {
+        if !std::env::var(var).is_ok_and(|value| !value.is_empty()) {
+            errors.push(ConfigValidationError::MissingEnvVar {
+                provider: provider(),
+                var: var.clone(),
+            });
+        }
+    }
+
+    if let Some(url) = &config.base_url {
+        if let Err(e) = url::Url::parse(url) {
+            errors.push(ConfigValidationError::InvalidBaseUrl {
+                provider: provider(),
+                url: url.clone(),
+                reason: e.to_string(),
+            });
+        }
+    }
+
+    if config.timeout_secs == Some(0) {
+        errors.push(ConfigValidationError::ZeroTimeout {
+            provider: provider(),
+        });
+    }
+
+    if config.max_tokens == Some(0) {
+        errors.push(ConfigValidationError::ZeroMaxTokens {
+            provider: provider(),
+        });
+    }
+
+    if let Some(temperature) = config.temperature {
+        let range = temperature_range(&config.provider_type);
+        if !range.contains(&temperature) {
+            errors.push(ConfigValidationError::TemperatureOutOfRange {
+                provider: provider(),
+                temperature,
+                min: *range.start(),
+                max: *range.end(),
+            });
+        }
+    }
+
+    if config
+        .rate_limit
+        .as_ref()
+        .is_some_and(|limit| limit.requests_per_minute == 0)
+    {
+        errors.push(ConfigValidationError::ZeroRequestsPerMinute {
+            provider: provider(),
+        });
+    }
+}
+
+/// Loads and validates the configuration file at `path`.
+///
+/// Intended for CI scripts: a file that cannot be read or parsed yields a
+/// single [`ConfigValidationError::LoadFailed`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use acton_ai::config::validate_file;
+/// use std::path::Path;
+///
+/// let errors = validate_file(Path::new("acton-ai.toml"));
+/// for error in &errors {
+///     eprintln!("{error}");
+/// }
+/// std::process::exit(i32::from(!errors.is_empty()));
+/// ```
+#[must_use]
+pub fn validate_file(path: &Path) -> Vec<ConfigValidationError> {
+    match from_path(path) {
+        Ok(config) => config.validate(),
+        Err(e) => vec![ConfigValidationError::LoadFailed {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RateLimitFileConfig;
+
+    #[test]
+    fn valid_config_has_no_errors() {
+        let config = ActonAIConfig::new()
+            .with_provider(
+                "local",
+                NamedProviderConfig::ollama("qwen2.5:7b").with_temperature(0.7),
+            )
+            .with_default_provider("local");
+
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn reports_every_problem() {
+        let config = ActonAIConfig::new()
+            .with_provider(
+                "claude",
+                NamedProviderConfig::anthropic("claude-sonnet-4-20250514")
+                    .with_api_key_env("ACTON_AI_TEST_UNSET_KEY_VAR")
+                    .with_temperature(1.5)
+                    .with_max_tokens(0),
+            )
+            .with_provider(
+                "local",
+                NamedProviderConfig::ollama("qwen2.5:7b")
+                    .with_base_url("not a url")
+                    .with_timeout_secs(0)
+                    .with_rate_limit(RateLimitFileConfig::new(0, 1000)),
+            )
+            .with_default_provider("missing");
+        let config = ActonAIConfig {
+            log_level: Some("loud".to_string()),
+            ..config
+        };
+
+        let errors = config.validate();
+        assert_eq!(errors.len(), 8, "{errors:?}");
+        assert!(errors.contains(&ConfigValidationError::MissingEnvVar {
+            provider: "claude".to_string(),
+            var: "ACTON_AI_TEST_UNSET_KEY_VAR".to_string(),
+        }));
+        assert!(
+            errors.contains(&ConfigValidationError::TemperatureOutOfRange {
+                provider: "claude".to_string(),
+                temperature: 1.5,
+                min: 0.0,
+                max: 1.0,
+            })
+        );
+        assert!(errors.contains(&ConfigValidationError::ZeroTimeout {
+            provider: "local".to_string(),
+        }));
+        assert_eq!(
+            errors[6].to_string(),
+            "default_provider 'missing' is not a configured provider"
+        );
+        assert!(errors.contains(&ConfigValidationError::InvalidLogLevel {
+            level: "loud".to_string(),
+        }));
+    }
+
+    #[test]
+    fn temperature_range_depends_on_provider() {
+        let mut openai = NamedProviderConfig::openai("gpt-4o").with_temperature(1.5);
+        openai.api_key_env = None;
+        let config = ActonAIConfig::new().with_provider("gpt", openai);
+
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn validate_file_reports_unreadable_file() {
+        let errors = validate_file(Path::new("/nonexistent/acton-ai.toml"));
+        assert!(matches!(
+            errors.as_slice(),
+            [ConfigValidationError::LoadFailed { .. }]
+        ));
+    }
+}
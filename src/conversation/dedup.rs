@@ -0,0 +1,120 @@
+//! Suppression of repeated identical turns.
+//!
+//! When the same user message arrives twice within the configured window
+//! (a double-click, a client retry), the second send is answered from the
+//! first response instead of calling the LLM again.
+
+use crate::stream::CollectedResponse;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// Counters reported by [`Conversation::dedup_stats`](super::Conversation::dedup_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeduplicationStats {
+    /// Sends answered from the cache
+    pub hits: u64,
+    /// Sends that went to the LLM
+    pub misses: u64,
+    /// LLM calls avoided by answering from the cache
+    pub saved_api_calls: u64,
+}
+
+/// Per-conversation cache of recent responses keyed by user content.
+#[derive(Debug)]
+pub(super) struct MessageDeduplication {
+    window: Duration,
+    seen: HashMap<u64, (Instant, CollectedResponse)>,
+    stats: DeduplicationStats,
+}
+
+impl MessageDeduplication {
+    pub(super) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            seen: HashMap::new(),
+            stats: DeduplicationStats::default(),
+        }
+    }
+
+    /// Returns the cached response for `content` if it was seen within the
+    /// window, counting a hit or a miss.
+    pub(super) fn lookup(&mut self, content: &str) -> Option<CollectedResponse> {
+        self.lookup_at(content, Instant::now())
+    }
+
+    /// Caches `response` as the answer to `content`.
+    pub(super) fn record(&mut self, content: &str, response: CollectedResponse) {
+        self.seen
+            .insert(content_hash(content), (Instant::now(), response));
+    }
+
+    pub(super) fn stats(&self) -> DeduplicationStats {
+        self.stats
+    }
+
+    fn lookup_at(&mut self, content: &str, now: Instant) -> Option<CollectedResponse> {
+        let window = self.window;
+        self.seen
+            .retain(|_, (seen_at, _)| now.duration_since(*seen_at) < window);
+
+        match self.seen.get(&content_hash(content)) {
+            Some((_, response)) => {
+                self.stats.hits += 1;
+                self.stats.saved_api_calls += 1;
+                Some(response.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::StopReason;
+
+    fn response(text: &str) -> CollectedResponse {
+        CollectedResponse::new(text.to_string(), StopReason::MaxTokens, 42)
+    }
+
+    #[test]
+    fn repeated_content_hits_cache() {
+        let mut dedup = MessageDeduplication::new(Duration::from_secs(5));
+        assert!(dedup.lookup("hello").is_none());
+        dedup.record("hello", response("hi there"));
+
+        let cached = dedup.lookup("hello").unwrap();
+        assert_eq!(cached, response("hi there"));
+        assert!(dedup.lookup("goodbye").is_none());
+
+        assert_eq!(
+            dedup.stats(),
+            DeduplicationStats {
+                hits: 1,
+                misses: 2,
+                saved_api_calls: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn entries_expire_after_window() {
+        let mut dedup = MessageDeduplication::new(Duration::from_secs(5));
+        dedup.record("hello", response("hi there"));
+
+        let later = Instant::now() + Duration::from_secs(6);
+        assert!(dedup.lookup_at("hello", later).is_none());
+        assert!(dedup.seen.is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+//! Conversation transcripts as Markdown, JSON, or CSV.
+//!
+//! [`Conversation::export`](super::Conversation::export) renders the system
+//! prompt and history in an [`ExportFormat`], and
+//! [`ConversationBuilder::restore_from_export`](super::ConversationBuilder::restore_from_export)
+//! reads it back. Only roles, contents, and timestamps are exported; tool
+//! call details are not.
+
+use crate::error::ActonAIError;
+use crate::messages::{Message, MessageRole};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// Text format of an exported conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExportFormat {
+    /// A transcript with a `**User:**` / `**Assistant:**` header before each
+    /// message body
+    Markdown,
+    /// A pretty-printed JSON array of `{role, content, timestamp}` objects
+    Json,
+    /// RFC 4180 CSV with `role,content,timestamp` columns
+    Csv,
+}
+
+/// One message in the JSON format.
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportedMessage {
+    role: MessageRole,
+    content: String,
+    timestamp: Option<String>,
+}
+
+const CSV_HEADER: [&str; 3] = ["role", "content", "timestamp"];
+
+/// Renders `messages` in `format`.
+pub(super) fn export_messages(messages: &[Message], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Markdown => {
+            let mut out = messages
+                .iter()
+                .map(|message| format!("**{}:**\n\n{}", role_label(message.role), message.content))
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            out.push('\n');
+            out
+        }
+        ExportFormat::Json => {
+            let exported: Vec<ExportedMessage> = messages
+                .iter()
+                .map(|message| ExportedMessage {
+                    role: message.role,
+                    content: message.content.clone(),
+                    timestamp: message.timestamp.map(format_timestamp),
+                })
+                .collect();
+            serde_json::to_string_pretty(&exported).unwrap_or_default()
+        }
+        ExportFormat::Csv => {
+            let mut out = format!("{}\r\n", CSV_HEADER.join(","));
+            for message in messages {
+                let timestamp = message.timestamp.map(format_timestamp).unwrap_or_default();
+                out.push_str(&format!(
+                    "{},{},{}\r\n",
+                    message.role,
+                    csv_field(&message.content),
+                    timestamp
+                ));
+            }
+            out
+        }
+    }
+}
+
+/// Parses messages exported in `format`.
+///
+/// # Errors
+///
+/// Returns a configuration error if `data` is not valid `format` output.
+pub(super) fn import_messages(
+    format: ExportFormat,
+    data: &str,
+) -> Result<Vec<Message>, ActonAIError> {
+    match format {
+        ExportFormat::Markdown => import_markdown(data),
+        ExportFormat::Json => {
+            let exported: Vec<ExportedMessage> =
+                serde_json::from_str(data).map_err(|e| invalid_export(e.to_string()))?;
+            exported
+                .into_iter()
+                .map(|message| {
+                    let timestamp = message
+                        .timestamp
+                        .as_deref()
+                        .map(parse_timestamp)
+                        .transpose()?;
+                    Ok(imported_message(message.role, message.content, timestamp))
+                })
+                .collect()
+        }
+        ExportFormat::Csv => {
+            let mut records = parse_csv(data)?.into_iter();
+            if records.next().as_deref() != Some(&CSV_HEADER.map(String::from)[..]) {
+                return Err(invalid_export(format!(
+                    "CSV header must be '{}'",
+                    CSV_HEADER.join(",")
+                )));
+            }
+            records
+                .map(|record| {
+                    let [role, content, timestamp] =
+                        <[String; 3]>::try_from(record).map_err(|r| {
+                            invalid_export(format!("CSV record has {} fields, expected 3", r.len()))
+                        })?;
+                    let timestamp = Some(timestamp.as_str())
+                        .filter(|t| !t.is_empty())
+                        .map(parse_timestamp)
+                        .transpose()?;
+                    Ok(imported_message(parse_role(&role)?, content, timestamp))
+                })
+                .collect()
+        }
+    }
+}
+
+fn import_markdown(data: &str) -> Result<Vec<Message>, ActonAIError> {
+    let mut messages = Vec::new();
+    let mut current: Option<(MessageRole, Vec<&str>)> = None;
+    for line in data.lines() {
+        if let Some(role) = markdown_header_role(line) {
+            messages.extend(current.take().map(markdown_message));
+            current = Some((role, Vec::new()));
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        } else if !line.trim().is_empty() {
+            return Err(invalid_export("Markdown text before the first role header"));
+        }
+    }
+    messages.extend(current.map(markdown_message));
+    Ok(messages)
+}
+
+fn markdown_message((role, lines): (MessageRole, Vec<&str>)) -> Message {
+    let content = lines.join("\n").trim_matches('\n').to_string();
+    imported_message(role, content, None)
+}
+
+fn markdown_header_role(line: &str) -> Option<MessageRole> {
+    let label = line.trim_end().strip_prefix("**")?.strip_suffix(":**")?;
+    [
+        MessageRole::System,
+        MessageRole::User,
+        MessageRole::Assistant,
+        MessageRole::Tool,
+    ]
+    .into_iter()
+    .find(|role| role_label(*role) == label)
+}
+
+fn role_label(role: MessageRole) -> &'static str {
+    match role {
+        MessageRole::System => "System",
+        MessageRole::User => "User",
+        MessageRole::Assistant => "Assistant",
+        MessageRole::Tool => "Tool",
+    }
+}
+
+fn parse_role(role: &str) -> Result<MessageRole, ActonAIError> {
+    serde_json::from_value(serde_json::Value::String(role.to_string()))
+        .map_err(|_| invalid_export(format!("unknown role '{role}'")))
+}
+
+fn imported_message(role: MessageRole, content: String, timestamp: Option<SystemTime>) -> Message {
+    Message {
+        role,
+        content,
+        tool_calls: None,
+        tool_call_id: None,
+        timestamp,
+        is_injected: false,
+    }
+}
+
+fn format_timestamp(timestamp: SystemTime) -> String {
+    DateTime::<Utc>::from(timestamp).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn parse_timestamp(timestamp: &str) -> Result<SystemTime, ActonAIError> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(SystemTime::from)
+        .map_err(|e| invalid_export(format!("invalid timestamp '{timestamp}': {e}")))
+}
+
+/// Quotes a CSV field when it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits RFC 4180 CSV into records of fields.
+fn parse_csv(data: &str) -> Result<Vec<Vec<String>>, ActonAIError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = data.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(invalid_export("unterminated quoted CSV field"));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+fn invalid_export(reason: impl Into<String>) -> ActonAIError {
+    ActonAIError::configuration("export", reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sample() -> Vec<Message> {
+        let at = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        vec![
+            Message::system("Be brief."),
+            Message::user("Hi, \"bot\"").with_timestamp(at),
+            Message::assistant("Hello!\n\nHow can I help?"),
+        ]
+    }
+
+    #[test]
+    fn markdown_renders_role_headers() {
+        let markdown = export_messages(&sample(), ExportFormat::Markdown);
+        assert!(markdown.starts_with("**System:**\n\nBe brief.\n\n**User:**\n\nHi, \"bot\""));
+        assert!(markdown.ends_with("**Assistant:**\n\nHello!\n\nHow can I help?\n"));
+    }
+
+    #[test]
+    fn markdown_round_trips_without_timestamps() {
+        let markdown = export_messages(&sample(), ExportFormat::Markdown);
+        let messages = import_messages(ExportFormat::Markdown, &markdown).unwrap();
+
+        let expected: Vec<Message> = sample()
+            .into_iter()
+            .map(|m| Message {
+                timestamp: None,
+                ..m
+            })
+            .collect();
+        assert_eq!(messages, expected);
+    }
+
+    #[test]
+    fn json_includes_role_content_and_timestamp() {
+        let json = export_messages(&sample(), ExportFormat::Json);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value[1]["role"], "user");
+        assert_eq!(value[1]["content"], "Hi, \"bot\"");
+        assert_eq!(value[1]["timestamp"], "2023-11-14T22:13:20.123Z");
+        assert!(value[2]["timestamp"].is_null());
+        assert_eq!(
+            import_messages(ExportFormat::Json, &json).unwrap(),
+            sample()
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_round_trips() {
+        let csv = export_messages(&sample(), ExportFormat::Csv);
+
+        assert!(csv.starts_with("role,content,timestamp\r\nsystem,Be brief.,\r\n"));
+        assert!(csv.contains("user,\"Hi, \"\"bot\"\"\",2023-11-14T22:13:20.123Z\r\n"));
+        assert_eq!(import_messages(ExportFormat::Csv, &csv).unwrap(), sample());
+    }
+
+    #[test]
+    fn invalid_exports_are_rejected() {
+        assert!(import_messages(ExportFormat::Markdown, "no header")
+            .unwrap_err()
+            .is_configuration());
+        assert!(import_messages(ExportFormat::Json, "{}").is_err());
+        assert!(import_messages(ExportFormat::Csv, "a,b,c\r\n").is_err());
+        assert!(
+            import_messages(ExportFormat::Csv, "role,content,timestamp\r\nrobot,hi,\r\n").is_err()
+        );
+        assert!(import_messages(ExportFormat::Csv, "role,content,timestamp\r\nuser,\"hi").is_err());
+    }
+}
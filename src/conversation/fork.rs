@@ -0,0 +1,349 @@
+//! A/B testing of system prompts over a shared history.
+//!
+//! A [`ConversationFork`] copies a conversation's history into two variant
+//! conversations that differ only in their system prompt, sends the same
+//! messages to both, and compares the answers. The winning variant can be
+//! written back to the original conversation.
+
+use super::{Conversation, ConversationInspector};
+use crate::error::ActonAIError;
+use crate::messages::Message;
+use crate::stream::CollectedResponse;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+/// One side of a [`ConversationFork`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WinnerVariant {
+    /// The variant set with [`ConversationFork::variant_a`]
+    A,
+    /// The variant set with [`ConversationFork::variant_b`]
+    B,
+}
+
+/// Scores the sentiment of a response, typically in `-1.0..=1.0`.
+pub type SentimentScorer = Arc<dyn Fn(&str) -> f32 + Send + Sync>;
+
+/// Cheap textual comparison of two responses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResponseDiff {
+    /// Length of A divided by length of B, in characters (infinite when only
+    /// B is empty, 1.0 when both are)
+    pub length_ratio: f32,
+    /// Shared words over all distinct words of both responses (1.0 when both
+    /// are empty)
+    pub common_words_ratio: f32,
+    /// Sentiment of A minus sentiment of B, when the fork has a
+    /// [scorer](ConversationFork::with_sentiment_scorer)
+    pub sentiment_diff: Option<f32>,
+}
+
+impl ResponseDiff {
+    /// Compares two response texts.
+    #[must_use]
+    pub fn between(a: &str, b: &str) -> Self {
+        let (len_a, len_b) = (a.chars().count(), b.chars().count());
+        let length_ratio = match (len_a, len_b) {
+            (0, 0) => 1.0,
+            (_, 0) => f32::INFINITY,
+            (a, b) => a as f32 / b as f32,
+        };
+
+        let words_a = vocabulary(a);
+        let words_b = vocabulary(b);
+        let union = words_a.union(&words_b).count();
+        let common_words_ratio = if union == 0 {
+            1.0
+        } else {
+            words_a.intersection(&words_b).count() as f32 / union as f32
+        };
+
+        Self {
+            length_ratio,
+            common_words_ratio,
+            sentiment_diff: None,
+        }
+    }
+}
+
+/// Lowercased alphanumeric words of `text`.
+fn vocabulary(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Both variants' answers to one message.
+#[derive(Debug, Clone)]
+pub struct ForkResult {
+    /// The message sent to both variants
+    pub input: String,
+    /// Variant A's response
+    pub a: CollectedResponse,
+    /// Variant B's response
+    pub b: CollectedResponse,
+    /// Comparison of the two responses
+    pub diff: ResponseDiff,
+}
+
+/// Picks the better variant from the results collected so far.
+///
+/// Implemented for closures taking `&[ForkResult]`.
+pub trait ForkEvaluator: Send + Sync {
+    /// Returns the winning variant.
+    fn evaluate(&self, results: &[ForkResult]) -> WinnerVariant;
+}
+
+impl<F> ForkEvaluator for F
+where
+    F: Fn(&[ForkResult]) -> WinnerVariant + Send + Sync,
+{
+    fn evaluate(&self, results: &[ForkResult]) -> WinnerVariant {
+        self(results)
+    }
+}
+
+/// Runs two system prompts side by side over a copy of a conversation.
+///
+/// The variant conversations are built on the first
+/// [`send_to_both`](Self::send_to_both); the base conversation is untouched
+/// until [`promote`](Self::promote).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut fork = ConversationFork::new(&conv)
+///     .variant_a("You are concise.")
+///     .variant_b("You are thorough and cite sources.");
+///
+/// let result = fork.send_to_both("Explain borrowing in Rust").await?;
+/// println!("length ratio: {}", result.diff.length_ratio);
+///
+/// let winner = fork.evaluate(Box::new(|results: &[ForkResult]| {
+///     if results.iter().all(|r| r.a.text.len() < r.b.text.len()) {
+///         WinnerVariant::A
+///     } else {
+///         WinnerVariant::B
+///     }
+/// }));
+/// fork.promote(winner, &conv);
+/// ```
+pub struct ConversationFork {
+    base: Conversation,
+    history: Vec<Message>,
+    prompt_a: Option<String>,
+    prompt_b: Option<String>,
+    variants: Option<(Conversation, Conversation)>,
+    results: Vec<ForkResult>,
+    sentiment: Option<SentimentScorer>,
+}
+
+impl ConversationFork {
+    /// Forks `base` at its current history.
+    ///
+    /// Both variants start with the base system prompt until replaced.
+    #[must_use]
+    pub fn new(base: &Conversation) -> Self {
+        let prompt = base.system_prompt();
+        Self {
+            base: base.clone(),
+            history: base.history(),
+            prompt_a: prompt.clone(),
+            prompt_b: prompt,
+            variants: None,
+            results: Vec::new(),
+            sentiment: None,
+        }
+    }
+
+    /// Sets variant A's system prompt.
+    ///
+    /// Has no effect once the variants have been built by
+    /// [`send_to_both`](Self::send_to_both).
+    #[must_use]
+    pub fn variant_a(mut self, system_prompt: &str) -> Self {
+        self.prompt_a = Some(system_prompt.to_string());
+        self
+    }
+
+    /// Sets variant B's system prompt.
+    ///
+    /// Has no effect once the variants have been built by
+    /// [`send_to_both`](Self::send_to_both).
+    #[must_use]
+    pub fn variant_b(mut self, system_prompt: &str) -> Self {
+        self.prompt_b = Some(system_prompt.to_string());
+        self
+    }
+
+    /// Fills [`ResponseDiff::sentiment_diff`] using `scorer`.
+    #[must_use]
+    pub fn with_sentiment_scorer(mut self, scorer: SentimentScorer) -> Self {
+        self.sentiment = Some(scorer);
+        self
+    }
+
+    /// Returns the system prompt of `variant`.
+    #[must_use]
+    pub fn system_prompt(&self, variant: WinnerVariant) -> Option<&str> {
+        match variant {
+            WinnerVariant::A => self.prompt_a.as_deref(),
+            WinnerVariant::B => self.prompt_b.as_deref(),
+        }
+    }
+
+    /// Returns the results of every [`send_to_both`](Self::send_to_both) so far.
+    #[must_use]
+    pub fn results(&self) -> &[ForkResult] {
+        &self.results
+    }
+
+    /// Sends `content` to both variants concurrently.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error if either variant's request fails.
+    pub async fn send_to_both(&mut self, content: &str) -> Result<ForkResult, ActonAIError> {
+        let (a, b) = self.variants().await;
+        let (a, b) = futures::future::join(a.send(content), b.send(content)).await;
+        let (a, b) = (a?, b?);
+
+        let mut diff = ResponseDiff::between(&a.text, &b.text);
+        if let Some(scorer) = &self.sentiment {
+            diff.sentiment_diff = Some(scorer(&a.text) - scorer(&b.text));
+        }
+
+        let result = ForkResult {
+            input: content.to_string(),
+            a,
+            b,
+            diff,
+        };
+        self.results.push(result.clone());
+        Ok(result)
+    }
+
+    /// Asks `eval` to pick a winner from the results so far.
+    #[must_use]
+    pub fn evaluate(&self, eval: Box<dyn ForkEvaluator>) -> WinnerVariant {
+        eval.evaluate(&self.results)
+    }
+
+    /// Writes the winning variant back to `base`.
+    ///
+    /// `base` adopts the variant's system prompt and, if the variant has
+    /// been sent to, its history. Like
+    /// [`Conversation::set_system_prompt`], this is fire-and-forget.
+    pub fn promote(&self, winner: WinnerVariant, base: &Conversation) {
+        match self.system_prompt(winner) {
+            Some(prompt) => base.set_system_prompt(prompt),
+            None => base.clear_system_prompt(),
+        }
+
+        if let Some((a, b)) = &self.variants {
+            let variant = match winner {
+                WinnerVariant::A => a,
+                WinnerVariant::B => b,
+            };
+            base.apply_inspection(ConversationInspector::new(variant.history()));
+        }
+    }
+
+    /// Returns the variant conversations, building them on first use.
+    async fn variants(&mut self) -> (Conversation, Conversation) {
+        if let Some((a, b)) = &self.variants {
+            return (a.clone(), b.clone());
+        }
+        let a = self.build_variant(self.prompt_a.clone()).await;
+        let b = self.build_variant(self.prompt_b.clone()).await;
+        self.variants = Some((a.clone(), b.clone()));
+        (a, b)
+    }
+
+    async fn build_variant(&self, system_prompt: Option<String>) -> Conversation {
+        let mut builder = self
+            .base
+            .runtime
+            .conversation()
+            .restore(self.history.clone());
+        if let Some(prompt) = system_prompt {
+            builder = builder.system(prompt);
+        }
+        builder.build().await
+    }
+}
+
+impl fmt::Debug for ConversationFork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConversationFork")
+            .field("history_len", &self.history.len())
+            .field("prompt_a", &self.prompt_a)
+            .field("prompt_b", &self.prompt_b)
+            .field("built", &self.variants.is_some())
+            .field("results", &self.results.len())
+            .field("sentiment", &self.sentiment.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::StopReason;
+
+    fn response(text: &str) -> CollectedResponse {
+        CollectedResponse {
+            text: text.to_string(),
+            stop_reason: StopReason::EndTurn,
+            token_count: 0,
+            tool_calls: Vec::new(),
+            is_json: false,
+            thinking: None,
+            token_usage: None,
+        }
+    }
+
+    #[test]
+    fn diff_compares_length_and_vocabulary() {
+        let diff = ResponseDiff::between("The cat sat.", "the dog sat");
+        assert!((diff.length_ratio - 12.0 / 11.0).abs() < 1e-6);
+        // {the, sat} shared of {the, cat, sat, dog}
+        assert!((diff.common_words_ratio - 0.5).abs() < 1e-6);
+        assert_eq!(diff.sentiment_diff, None);
+    }
+
+    #[test]
+    fn diff_handles_empty_responses() {
+        let both = ResponseDiff::between("", "");
+        assert_eq!(both.length_ratio, 1.0);
+        assert_eq!(both.common_words_ratio, 1.0);
+
+        let only_b_empty = ResponseDiff::between("hi", "");
+        assert!(only_b_empty.length_ratio.is_infinite());
+        assert_eq!(only_b_empty.common_words_ratio, 0.0);
+    }
+
+    #[test]
+    fn closures_are_evaluators() {
+        let shorter = |results: &[ForkResult]| {
+            let a: usize = results.iter().map(|r| r.a.text.len()).sum();
+            let b: usize = results.iter().map(|r| r.b.text.len()).sum();
+            if a <= b {
+                WinnerVariant::A
+            } else {
+                WinnerVariant::B
+            }
+        };
+        let results = vec![ForkResult {
+            input: "q".to_string(),
+            a: response("a long answer"),
+            b: response("short"),
+            diff: ResponseDiff::between("a long answer", "short"),
+        }];
+
+        let eval: Box<dyn ForkEvaluator> = Box::new(shorter);
+        assert_eq!(eval.evaluate(&results), WinnerVariant::B);
+    }
+}
@@ -0,0 +1,221 @@
+//! Non-destructive editing and annotation of conversation history.
+//!
+//! A [`ConversationInspector`] works on a copy of a history. Edits are
+//! applied in call order, and indices always refer to the history as it
+//! stands after the preceding edits. Out-of-range indices are ignored so a
+//! chain of edits never panics halfway through.
+
+use crate::messages::Message;
+
+/// A message plus any metadata attached by [`ConversationInspector::annotate`].
+#[derive(Debug, Clone)]
+struct InspectedMessage {
+    message: Message,
+    metadata: Option<serde_json::Value>,
+}
+
+/// Edits and annotates a conversation history without re-running it.
+///
+/// Created via [`Conversation::inspect`](super::Conversation::inspect) or
+/// [`ConversationInspector::new`]; written back with
+/// [`Conversation::apply_inspection`](super::Conversation::apply_inspection).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut inspector = conv.inspect();
+/// inspector
+///     .annotate(1, serde_json::json!({"importance": "high"}))
+///     .redact(2, "[email removed]")
+///     .inject(0, Message::system("Summary of earlier context..."));
+///
+/// for (index, metadata) in inspector.annotations() {
+///     println!("{index}: {metadata}");
+/// }
+///
+/// conv.apply_inspection(inspector);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConversationInspector {
+    entries: Vec<InspectedMessage>,
+}
+
+impl ConversationInspector {
+    /// Creates an inspector over `history`.
+    #[must_use]
+    pub fn new(history: Vec<Message>) -> Self {
+        Self {
+            entries: history
+                .into_iter()
+                .map(|message| InspectedMessage {
+                    message,
+                    metadata: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Attaches `metadata` to the message at `index`, replacing any earlier
+    /// annotation.
+    pub fn annotate(&mut self, index: usize, metadata: serde_json::Value) -> &mut Self {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.metadata = Some(metadata);
+        }
+        self
+    }
+
+    /// Replaces the content of the message at `index`.
+    ///
+    /// Role, tool calls, and tool call ID are kept, so tool-result pairing
+    /// stays intact after redacting PII from a tool response.
+    pub fn redact(&mut self, index: usize, replacement: &str) -> &mut Self {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.message.content = replacement.to_string();
+        }
+        self
+    }
+
+    /// Removes the message at `index`, along with its annotation.
+    pub fn remove(&mut self, index: usize) -> &mut Self {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+        }
+        self
+    }
+
+    /// Inserts `message` at `index`, shifting later messages back.
+    ///
+    /// An `index` equal to the history length appends.
+    pub fn inject(&mut self, index: usize, message: Message) -> &mut Self {
+        if index <= self.entries.len() {
+            self.entries.insert(
+                index,
+                InspectedMessage {
+                    message,
+                    metadata: None,
+                },
+            );
+        }
+        self
+    }
+
+    /// Returns the message at `index`.
+    #[must_use]
+    pub fn message(&self, index: usize) -> Option<&Message> {
+        self.entries.get(index).map(|entry| &entry.message)
+    }
+
+    /// Returns the metadata attached to the message at `index`.
+    #[must_use]
+    pub fn metadata(&self, index: usize) -> Option<&serde_json::Value> {
+        self.entries
+            .get(index)
+            .and_then(|entry| entry.metadata.as_ref())
+    }
+
+    /// Returns `(index, metadata)` for every annotated message, in order.
+    #[must_use]
+    pub fn annotations(&self) -> Vec<(usize, &serde_json::Value)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| entry.metadata.as_ref().map(|m| (i, m)))
+            .collect()
+    }
+
+    /// Returns the number of messages in the edited history.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the edited history is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the edited history.
+    ///
+    /// Annotations are inspection-only and are not carried into the
+    /// returned messages.
+    #[must_use]
+    pub fn build(self) -> Vec<Message> {
+        self.entries
+            .into_iter()
+            .map(|entry| entry.message)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::MessageRole;
+
+    fn history() -> Vec<Message> {
+        vec![
+            Message::user("my email is a@b.c"),
+            Message::assistant("Noted."),
+            Message::user("thanks"),
+        ]
+    }
+
+    #[test]
+    fn build_without_edits_returns_history() {
+        let built = ConversationInspector::new(history()).build();
+        let contents: Vec<_> = built.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["my email is a@b.c", "Noted.", "thanks"]);
+    }
+
+    #[test]
+    fn edits_apply_in_order() {
+        let mut inspector = ConversationInspector::new(history());
+        inspector
+            .redact(0, "[redacted]")
+            .remove(1)
+            .inject(0, Message::system("context"));
+
+        let built = inspector.build();
+        assert_eq!(built.len(), 3);
+        assert_eq!(built[0].role, MessageRole::System);
+        assert_eq!(built[1].content, "[redacted]");
+        assert_eq!(built[1].role, MessageRole::User);
+        assert_eq!(built[2].content, "thanks");
+    }
+
+    #[test]
+    fn annotations_follow_their_message() {
+        let mut inspector = ConversationInspector::new(history());
+        inspector
+            .annotate(2, serde_json::json!({"importance": "high"}))
+            .remove(0);
+
+        assert_eq!(
+            inspector.metadata(1),
+            Some(&serde_json::json!({"importance": "high"}))
+        );
+        assert_eq!(inspector.annotations().len(), 1);
+        assert_eq!(inspector.annotations()[0].0, 1);
+    }
+
+    #[test]
+    fn out_of_range_indices_are_ignored() {
+        let mut inspector = ConversationInspector::new(history());
+        inspector
+            .annotate(9, serde_json::json!(1))
+            .redact(9, "x")
+            .remove(9)
+            .inject(9, Message::user("lost"));
+
+        assert_eq!(inspector.len(), 3);
+        assert!(inspector.annotations().is_empty());
+    }
+
+    #[test]
+    fn inject_at_len_appends() {
+        let mut inspector = ConversationInspector::new(history());
+        inspector.inject(3, Message::user("last"));
+        assert_eq!(inspector.message(3).unwrap().content, "last");
+    }
+}
@@ -0,0 +1,3000 @@
+//! Managed conversation abstraction for multi-turn interactions.
+//!
+//! This module provides the [`Conversation`] type which handles automatic history
+//! management, reducing boilerplate in multi-turn conversation scenarios.
+//!
+//! `Conversation` is an actor-based wrapper: a [`ConversationActor`] owns the history,
+//! the mailbox serializes sends, and the public [`Conversation`] handle is
+//! `Clone + Send + 'static` with all methods taking `&self`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use acton_ai::prelude::*;
+//!
+//! let runtime = ActonAI::builder()
+//!     .app_name("my-app")
+//!     .ollama("qwen2.5:7b")
+//!     .with_builtins()
+//!     .launch()
+//!     .await?;
+//!
+//! let conv = runtime.conversation()
+//!     .system("You are a helpful assistant.")
+//!     .build()
+//!     .await;
+//!
+//! // History is automatically managed
+//! let response = conv.send("What is Rust?").await?;
+//! println!("Assistant: {}", response.text);
+//!
+//! let response = conv.send("How does ownership work?").await?;
+//! println!("Assistant: {}", response.text);
+//! ```
+
+use crate::error::{ActonAIError, ResultExt};
+use crate::facade::ActonAI;
+use crate::memory::{
+    query_store, ConversationCreated, ConversationLoaded, CreateConversation, EmbeddingProvider,
+    LoadConversation, MessageSaved, SaveMessage, StoreConversationSummary,
+};
+use crate::messages::{Message, MessageRole, ToolDefinition};
+use crate::prompt::{build_stream_collector, CancellationToken, StreamCollectorSession};
+use crate::stream::CollectedResponse;
+use crate::tools::registry::{get_tool_schema_versions, ToolSchemaVersions};
+use crate::tools::versioning::ToolSchemaVersion;
+use crate::types::{AgentId, ConversationId};
+use acton_reactive::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+use tokio::sync::{mpsc, watch};
+
+mod dedup;
+mod export;
+mod fork;
+mod inspector;
+#[cfg(feature = "websocket")]
+mod websocket;
+
+pub use dedup::DeduplicationStats;
+pub use export::ExportFormat;
+pub use fork::{
+    ConversationFork, ForkEvaluator, ForkResult, ResponseDiff, SentimentScorer, WinnerVariant,
+};
+pub use inspector::ConversationInspector;
+#[cfg(feature = "websocket")]
+pub use websocket::{
+    ConversationWebSocketAdapter, WebSocketClientMessage, WebSocketServerMessage, WebSocketStream,
+};
+
+use dedup::MessageDeduplication;
+
+/// Type alias for input mapper functions used in [`ChatConfig`].
+type InputMapperFn = Box<dyn FnMut(&str) -> String + Send>;
+
+/// Default system prompt used by [`Conversation::run_chat`] when no system prompt is set.
+///
+/// This prompt provides sensible defaults for a general-purpose chat assistant with
+/// tool access and exit detection.
+pub const DEFAULT_SYSTEM_PROMPT: &str = "\
+You are a helpful assistant in a terminal. You have tools. Use them correctly.
+
+RULES:
+1. Call `list_skills` exactly once, at the very first turn of the conversation. If the conversation history already contains a `list_skills` result, do NOT call it again.
+2. If the user's request matches one of the skills returned by `list_skills`, call `activate_skill` with that skill's name first, then follow the instructions it returns.
+3. Use a tool when the answer depends on information you cannot know from general knowledge: file contents, directory listings, current web pages, shell output, or exact arithmetic. In those cases, call the tool — do not guess.
+4. For general-knowledge questions (definitions, concepts, common facts), answer directly. Do not call a tool just to call one.
+5. Call one tool per turn. Wait for its result before deciding the next action.
+6. Use the exact argument names the tool expects. Use file paths the user gave you or that a previous tool returned. Do not invent field names or paths.
+7. If a tool returns an error, say in one short sentence what failed and stop. Do not retry the same call with the same arguments.
+8. Before writing files, editing files, or running shell commands that change state, state in one short sentence what you are about to do, then call the tool.
+
+RESPONSE STYLE:
+- Be concise. One or two short paragraphs is usually enough.
+- When you are not sure, say so plainly. Do not invent file contents, command output, or facts.
+- After a tool returns a result, answer the user's original question using that result. Do not re-run a tool whose result is already in the conversation.
+
+ENDING:
+Call `exit_conversation` with a short friendly farewell when the user clearly wants to leave. Triggers include: bye, goodbye, quit, exit, stop, done, that's all, thanks that's it, see ya, later, no more questions, I'm good.";
+
+// =========================================================================
+// ChatConfig (unchanged from original)
+// =========================================================================
+
+/// Configuration for the chat loop.
+///
+/// Created via [`ChatConfig::new()`] and passed to
+/// [`Conversation::run_chat_with()`](Conversation::run_chat_with).
+///
+/// For custom token handling (e.g., sending to a websocket), use the lower-level
+/// [`Conversation::send_streaming()`] API in your own loop instead.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use acton_ai::prelude::*;
+///
+/// conv.run_chat_with(
+///     ChatConfig::new()
+///         .user_prompt(">>> ")
+///         .assistant_prompt("AI: ")
+///         .map_input(|s| format!("[context] {}", s))
+/// ).await?;
+/// ```
+pub struct ChatConfig {
+    /// The prompt shown before user input (default: "You: ")
+    user_prompt: String,
+    /// The prefix shown before assistant responses (default: "Assistant: ")
+    assistant_prompt: String,
+    /// Optional function to transform user input before sending
+    input_mapper: Option<InputMapperFn>,
+}
+
+impl Default for ChatConfig {
+    fn default() -> Self {
+        Self {
+            user_prompt: "You: ".to_string(),
+            assistant_prompt: "Assistant: ".to_string(),
+            input_mapper: None,
+        }
+    }
+}
+
+impl ChatConfig {
+    /// Creates a new chat configuration with default settings.
+    ///
+    /// Default settings:
+    /// - User prompt: "You: "
+    /// - Assistant prompt: "Assistant: "
+    /// - Tokens printed to stdout
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let config = ChatConfig::new()
+    ///     .user_prompt(">>> ");
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the prompt shown before user input.
+    ///
+    /// Default: "You: "
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// ChatConfig::new().user_prompt(">>> ")
+    /// ```
+    #[must_use]
+    pub fn user_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.user_prompt = prompt.into();
+        self
+    }
+
+    /// Sets the prefix shown before assistant responses.
+    ///
+    /// Default: "Assistant: "
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// ChatConfig::new().assistant_prompt("AI: ")
+    /// ```
+    #[must_use]
+    pub fn assistant_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.assistant_prompt = prompt.into();
+        self
+    }
+
+    /// Sets a function to transform user input before sending to the LLM.
+    ///
+    /// Use this to add context, preprocess input, or inject system information.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// ChatConfig::new()
+    ///     .map_input(|s| format!("[User context: admin] {}", s))
+    /// ```
+    #[must_use]
+    pub fn map_input<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&str) -> String + Send + 'static,
+    {
+        self.input_mapper = Some(Box::new(f));
+        self
+    }
+}
+
+impl std::fmt::Debug for ChatConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChatConfig")
+            .field("user_prompt", &self.user_prompt)
+            .field("assistant_prompt", &self.assistant_prompt)
+            .field("has_input_mapper", &self.input_mapper.is_some())
+            .finish()
+    }
+}
+
+// =========================================================================
+// Summarization
+// =========================================================================
+
+/// Default instruction used to summarize a conversation before it is stored.
+pub const DEFAULT_SUMMARY_PROMPT: &str = "\
+Summarize the following conversation for long-term memory. Capture the user's goals, \
+decisions that were made, facts the user shared about themselves, and any open questions. \
+Write plain prose without preamble.";
+
+/// Configuration for conversation summaries written by
+/// [`ConversationBuilder::with_summarization_memory`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let conv = runtime.conversation()
+///     .with_summarization_memory(store, Box::new(provider))
+///     .summarization_config(SummarizationConfig {
+///         max_summary_tokens: 128,
+///         ..SummarizationConfig::default()
+///     })
+///     .build()
+///     .await;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummarizationConfig {
+    /// Upper bound on the summary length, in estimated tokens (default: 256)
+    pub max_summary_tokens: usize,
+    /// System prompt instructing the LLM how to summarize
+    pub summary_prompt: String,
+}
+
+impl Default for SummarizationConfig {
+    fn default() -> Self {
+        Self {
+            max_summary_tokens: 256,
+            summary_prompt: DEFAULT_SUMMARY_PROMPT.to_string(),
+        }
+    }
+}
+
+/// Rough characters-per-token ratio used to cap summary length.
+const SUMMARY_CHARS_PER_TOKEN: usize = 4;
+
+/// Where and how a conversation's summary is stored.
+struct Summarization {
+    store: ActorHandle,
+    embedding_provider: Box<dyn EmbeddingProvider>,
+    config: SummarizationConfig,
+    agent_id: AgentId,
+}
+
+impl Summarization {
+    /// Summarizes `history` with a brief LLM call, embeds the summary, and
+    /// sends it to the memory store. Empty histories are skipped.
+    async fn summarize_and_store(
+        &self,
+        runtime: &ActonAI,
+        conversation_id: &ConversationId,
+        history: &[Message],
+    ) -> Result<(), ActonAIError> {
+        let content = summarize_history(runtime, &self.config, history).await?;
+        if content.is_empty() {
+            return Ok(());
+        }
+
+        let embedding = match self.embedding_provider.embed(&content).await {
+            Ok(embedding) => Some(embedding),
+            Err(e) => {
+                tracing::warn!(
+                    conversation_id = %conversation_id,
+                    provider = self.embedding_provider.name(),
+                    error = %e,
+                    "failed to embed conversation summary; storing without embedding"
+                );
+                None
+            }
+        };
+
+        self.store
+            .send(StoreConversationSummary {
+                agent_id: self.agent_id.clone(),
+                conversation_id: conversation_id.clone(),
+                content,
+                embedding,
+            })
+            .await;
+        Ok(())
+    }
+}
+
+/// How long a conversation waits for each memory store reply.
+const STORE_QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The memory store a conversation's history is saved to.
+struct Persistence {
+    store: ActorHandle,
+    /// Number of leading history messages already saved. Locked for the
+    /// whole save so concurrent saves never store a message twice.
+    saved: tokio::sync::Mutex<usize>,
+}
+
+impl Persistence {
+    /// Saves the messages of `history` not yet in the store, in order.
+    ///
+    /// Only appended messages are saved; if the history shrank (e.g., after
+    /// [`Conversation::clear`]), saving resumes from its new end.
+    async fn save_new(
+        &self,
+        runtime: &ActonAI,
+        conversation_id: &ConversationId,
+        history: &[Message],
+    ) -> Result<(), ActonAIError> {
+        let mut saved = self.saved.lock().await;
+        *saved = (*saved).min(history.len());
+
+        let mut actor_runtime = runtime.runtime().clone();
+        for message in &history[*saved..] {
+            let request = SaveMessage {
+                conversation_id: conversation_id.clone(),
+                message: message.clone(),
+            };
+            query_store::<MessageSaved>(
+                &mut actor_runtime,
+                &self.store,
+                request,
+                STORE_QUERY_TIMEOUT,
+            )
+            .await
+            .ok_or_else(|| {
+                ActonAIError::prompt_failed(format!(
+                    "memory store did not save a message of conversation {conversation_id}"
+                ))
+            })?;
+            *saved += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Loads the stored messages of `conversation_id`, or `None` if the store
+/// did not answer in time.
+async fn load_history(
+    runtime: &ActonAI,
+    store: &ActorHandle,
+    conversation_id: &ConversationId,
+) -> Option<Vec<Message>> {
+    let mut actor_runtime = runtime.runtime().clone();
+    let request = LoadConversation {
+        conversation_id: conversation_id.clone(),
+    };
+    query_store::<ConversationLoaded>(&mut actor_runtime, store, request, STORE_QUERY_TIMEOUT)
+        .await
+        .map(|loaded| loaded.messages)
+}
+
+/// Creates a conversation owned by `agent_id` in the store, returning its
+/// ID, or `None` if the store did not answer in time.
+async fn create_stored_conversation(
+    runtime: &ActonAI,
+    store: &ActorHandle,
+    agent_id: AgentId,
+) -> Option<ConversationId> {
+    let mut actor_runtime = runtime.runtime().clone();
+    let request = CreateConversation { agent_id };
+    query_store::<ConversationCreated>(&mut actor_runtime, store, request, STORE_QUERY_TIMEOUT)
+        .await
+        .map(|created| created.conversation_id)
+}
+
+/// Summarizes `history` with a brief LLM call, capped at
+/// `config.max_summary_tokens`. Returns an empty summary for histories
+/// without user or assistant turns.
+async fn summarize_history(
+    runtime: &ActonAI,
+    config: &SummarizationConfig,
+    history: &[Message],
+) -> Result<String, ActonAIError> {
+    let transcript = summary_transcript(history);
+    if transcript.is_empty() {
+        return Ok(String::new());
+    }
+
+    let system = format!(
+        "{}\nKeep the summary under {} tokens.",
+        config.summary_prompt, config.max_summary_tokens
+    );
+    let response = runtime
+        .prompt(transcript)
+        .system(system)
+        .collect()
+        .await
+        .context("summarizing conversation")?;
+    Ok(cap_summary(response.text.trim(), config.max_summary_tokens))
+}
+
+/// Renders the user and assistant turns of `history` as a plain transcript.
+fn summary_transcript(history: &[Message]) -> String {
+    history
+        .iter()
+        .filter(|m| matches!(m.role, MessageRole::User | MessageRole::Assistant))
+        .filter(|m| !m.content.trim().is_empty())
+        .map(|m| format!("{}: {}", m.role, m.content.trim()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Truncates `summary` to roughly `max_tokens` tokens on a char boundary.
+fn cap_summary(summary: &str, max_tokens: usize) -> String {
+    let max_chars = max_tokens.saturating_mul(SUMMARY_CHARS_PER_TOKEN);
+    summary.chars().take(max_chars).collect()
+}
+
+// =========================================================================
+// Exit tool definition (unchanged)
+// =========================================================================
+
+/// Creates the exit tool definition for conversation termination.
+fn exit_tool_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "exit_conversation".to_string(),
+        description: "Call this tool when the user wants to end the conversation, \
+                      say goodbye, or leave. Examples: 'bye', 'goodbye', 'I'm done', \
+                      'quit', 'exit', 'see ya', 'thanks, that's all'."
+            .to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "farewell": {
+                    "type": "string",
+                    "description": "A friendly farewell message to the user"
+                }
+            },
+            "required": ["farewell"]
+        }),
+    }
+}
+
+// =========================================================================
+// Actor messages
+// =========================================================================
+
+/// Wrapper → ConversationActor: initiate a send.
+#[derive(Clone, Debug)]
+struct ConvSend {
+    content: String,
+    token_target: Option<ActorHandle>,
+    /// Bypass deduplication for this send
+    force: bool,
+    result_tx: mpsc::Sender<Result<CollectedResponse, ActonAIError>>,
+}
+
+/// Async block → ConversationActor: LLM completed, add assistant message.
+#[derive(Clone, Debug)]
+struct ConvAddAssistant {
+    text: String,
+}
+
+/// A streamed token message sent from the conversation to a user-provided actor.
+///
+/// Register a handler for this message type on your actor to receive
+/// individual tokens as they are streamed from the LLM.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use acton_ai::prelude::*;
+///
+/// actor.mutate_on::<StreamToken>(|_actor, ctx| {
+///     print!("{}", ctx.message().text);
+///     std::io::stdout().flush().ok();
+///     Reply::ready()
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamToken {
+    /// The token text.
+    pub text: String,
+}
+
+/// Wrapper → ConversationActor: clear history (fire-and-forget).
+#[derive(Clone, Debug)]
+struct ConvClear;
+
+/// Wrapper → ConversationActor: replace history wholesale (fire-and-forget).
+#[derive(Clone, Debug)]
+struct ConvRollback {
+    history: Vec<Message>,
+}
+
+/// Wrapper → ConversationActor: replace the first `summarized_len` messages
+/// with a summary, keeping any added since.
+#[derive(Clone, Debug)]
+struct ConvCompress {
+    summary: String,
+    summarized_len: usize,
+    done_tx: mpsc::Sender<()>,
+}
+
+/// Wrapper → ConversationActor: update system prompt (fire-and-forget).
+#[derive(Clone, Debug)]
+struct ConvSetSystemPrompt {
+    prompt: Option<String>,
+}
+
+// =========================================================================
+// ConversationActor
+// =========================================================================
+
+/// Actor that owns the conversation history.
+///
+/// All mutations are serialized through the mailbox. The `Reply::pending`
+/// future blocks the mailbox during the LLM call, guaranteeing that
+/// `ConvAddAssistant` is processed before the next `ConvSend`.
+#[acton_actor]
+struct ConversationActor {
+    history: Vec<Message>,
+}
+
+/// Actor used internally by [`Conversation::run_chat_with`] to print tokens to stdout.
+#[derive(Default, Debug)]
+struct StdoutTokenPrinter;
+
+// =========================================================================
+// Handler configuration
+// =========================================================================
+
+/// Shared state captured by handler closures at build time.
+///
+/// Groups the shared state that `configure_handlers` needs, keeping the
+/// function signature clean.
+struct HandlerState {
+    runtime: ActonAI,
+    self_handle: ActorHandle,
+    history_tx: Arc<watch::Sender<Vec<Message>>>,
+    history_len: Arc<AtomicUsize>,
+    exit_requested: Arc<AtomicBool>,
+    exit_tool_enabled: Arc<AtomicBool>,
+    system_prompt_rx: watch::Receiver<Option<String>>,
+    system_prompt_tx: watch::Sender<Option<String>>,
+    /// Optional truncator applied to `history.clone()` before each LLM call.
+    /// `None` means unbounded history (explicit opt-out at build time).
+    context_window: Option<crate::memory::ContextWindow>,
+    /// Long-lived stream collector. Subscribes once at Conversation build
+    /// time and is reused by every turn so we don't stack dead broker
+    /// subscribers (acton-reactive's `UnsubscribeBroker` is a no-op).
+    stream_session: StreamCollectorSession,
+    /// Recent-response cache, when deduplication is enabled.
+    dedup: Option<Arc<Mutex<MessageDeduplication>>>,
+    /// Cancellation token of the send currently in flight.
+    pending_cancel: Arc<Mutex<Option<CancellationToken>>>,
+    /// Instruction injected before the user message of each send.
+    recurring_injection: Arc<watch::Sender<Option<String>>>,
+}
+
+/// Locks the deduplication cache, recovering from a poisoned lock.
+fn lock_dedup(dedup: &Mutex<MessageDeduplication>) -> MutexGuard<'_, MessageDeduplication> {
+    dedup
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Locks the in-flight send's cancellation token, recovering from a
+/// poisoned lock.
+fn lock_pending_cancel(
+    pending_cancel: &Mutex<Option<CancellationToken>>,
+) -> MutexGuard<'_, Option<CancellationToken>> {
+    pending_cancel
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Registers all message handlers on the `ConversationActor` builder.
+fn configure_handlers(builder: &mut ManagedActor<Idle, ConversationActor>, state: HandlerState) {
+    let HandlerState {
+        runtime,
+        self_handle,
+        history_tx,
+        history_len,
+        exit_requested,
+        exit_tool_enabled,
+        system_prompt_rx,
+        system_prompt_tx,
+        context_window,
+        stream_session,
+        dedup,
+        pending_cancel,
+        recurring_injection,
+    } = state;
+    // ----- ConvSend: push user msg, run LLM call, await it -----
+    {
+        let runtime = runtime.clone();
+        let self_handle = self_handle.clone();
+        let history_tx = history_tx.clone();
+        let history_len = history_len.clone();
+        let exit_requested = exit_requested.clone();
+        let exit_tool_enabled = exit_tool_enabled.clone();
+        let system_prompt_rx = system_prompt_rx.clone();
+        let context_window = context_window.clone();
+        let stream_session = stream_session.clone();
+
+        builder.mutate_on::<ConvSend>(move |actor, ctx| {
+            let msg = ctx.message().clone();
+
+            // A repeat of a recent message is answered from the cache and
+            // leaves history untouched.
+            if !msg.force {
+                if let Some(cached) = dedup
+                    .as_deref()
+                    .and_then(|dedup| lock_dedup(dedup).lookup(&msg.content))
+                {
+                    let result_tx = msg.result_tx;
+                    return Reply::pending(async move {
+                        let _ = result_tx.send(Ok(cached)).await;
+                    });
+                }
+            }
+
+            // Sync: push user message to history
+            actor
+                .model
+                .history
+                .push(Message::user(&msg.content).with_timestamp(SystemTime::now()));
+
+            // Update watch channel and atomic
+            let _ = history_tx.send(actor.model.history.clone());
+            history_len.store(actor.model.history.len(), Ordering::SeqCst);
+
+            // Clone everything for the async block
+            let history = actor.model.history.clone();
+            let history = fit_history_for_request(&context_window, history);
+            let system_prompt = system_prompt_rx.borrow().clone();
+            let injection = recurring_injection.borrow().clone();
+            let runtime = runtime.clone();
+            let exit_requested = exit_requested.clone();
+            let exit_tool_enabled_val = exit_tool_enabled.load(Ordering::SeqCst);
+            let result_tx = msg.result_tx;
+            let token_target = msg.token_target;
+            let self_handle = self_handle.clone();
+            let stream_session = stream_session.clone();
+            let dedup = dedup.clone();
+            let content = msg.content;
+            let cancellation = CancellationToken::new();
+            *lock_pending_cancel(&pending_cancel) = Some(cancellation.clone());
+            let pending_cancel = pending_cancel.clone();
+
+            // The LLM call runs in a spawned task because PromptBuilder
+            // contains non-Sync callbacks (FnMut). The spawned task only
+            // requires Send. JoinHandle is Send+Sync, satisfying the
+            // FutureBox = Pin<Box<dyn Future + Send + Sync>> requirement.
+            Reply::pending(async move {
+                let llm_result = tokio::spawn(async move {
+                    // Build prompt with full history
+                    let mut builder = runtime.continue_with(history);
+
+                    if let Some(ref system) = system_prompt {
+                        builder = builder.system(system);
+                    }
+
+                    if let Some(instruction) = injection {
+                        builder = builder.inject_instruction_before_last_user(instruction);
+                    }
+
+                    // Inject exit tool if enabled
+                    if exit_tool_enabled_val {
+                        let exit_flag = exit_requested.clone();
+                        builder = builder.with_tool_callback(
+                            exit_tool_definition(),
+                            move |_args| {
+                                let flag = exit_flag.clone();
+                                async move {
+                                    flag.store(true, Ordering::SeqCst);
+                                    Ok(serde_json::json!({"status": "goodbye"}))
+                                }
+                            },
+                            |_result| {},
+                        );
+                    }
+
+                    // Add token target if provided
+                    if let Some(target) = token_target {
+                        builder = builder.token_target(target);
+                    }
+
+                    builder = builder.cancel_on(cancellation);
+
+                    // Reuse the Conversation's long-lived stream collector
+                    // so every turn shares one broker subscription — avoids
+                    // stacking `Recipient channel is closed` spam as turns
+                    // accumulate.
+                    builder
+                        .collect_with_session(&stream_session)
+                        .await
+                        .context("sending conversation message")
+                })
+                .await;
+
+                lock_pending_cancel(&pending_cancel).take();
+
+                // Unwrap the JoinHandle result
+                let result = match llm_result {
+                    Ok(r) => r,
+                    Err(join_err) => Err(ActonAIError::prompt_failed(join_err.to_string())),
+                };
+
+                // On success, send assistant message back to actor
+                // (queued BEFORE result is sent to caller → FIFO guarantees ordering)
+                if let Ok(ref response) = result {
+                    if let Some(dedup) = dedup {
+                        lock_dedup(&dedup).record(&content, response.clone());
+                    }
+                    self_handle
+                        .send(ConvAddAssistant {
+                            text: response.text.clone(),
+                        })
+                        .await;
+                }
+
+                // Send result to caller
+                let _ = result_tx.send(result).await;
+            })
+        });
+    }
+
+    // ----- ConvAddAssistant: push assistant msg (sync only) -----
+    {
+        let history_tx = history_tx.clone();
+        let history_len = history_len.clone();
+
+        builder.mutate_on::<ConvAddAssistant>(move |actor, ctx| {
+            let text = &ctx.message().text;
+            actor
+                .model
+                .history
+                .push(Message::assistant(text).with_timestamp(SystemTime::now()));
+
+            let _ = history_tx.send(actor.model.history.clone());
+            history_len.store(actor.model.history.len(), Ordering::SeqCst);
+
+            Reply::ready()
+        });
+    }
+
+    // ----- ConvClear: clear history (sync only) -----
+    {
+        let history_tx = history_tx.clone();
+        let history_len = history_len.clone();
+
+        builder.mutate_on::<ConvClear>(move |actor, _ctx| {
+            actor.model.history.clear();
+
+            let _ = history_tx.send(actor.model.history.clone());
+            history_len.store(0, Ordering::SeqCst);
+
+            Reply::ready()
+        });
+    }
+
+    // ----- ConvRollback: replace history (sync only) -----
+    {
+        let history_tx = history_tx.clone();
+        let history_len = history_len.clone();
+
+        builder.mutate_on::<ConvRollback>(move |actor, ctx| {
+            actor.model.history = ctx.message().history.clone();
+
+            let _ = history_tx.send(actor.model.history.clone());
+            history_len.store(actor.model.history.len(), Ordering::SeqCst);
+
+            Reply::ready()
+        });
+    }
+
+    // ----- ConvCompress: swap the summarized prefix for its summary -----
+    {
+        let history_tx = history_tx.clone();
+        let history_len = history_len.clone();
+
+        builder.mutate_on::<ConvCompress>(move |actor, ctx| {
+            let msg = ctx.message();
+            let history = &mut actor.model.history;
+            let added_since = history.split_off(msg.summarized_len.min(history.len()));
+            let summary = Message::assistant(&msg.summary).with_timestamp(SystemTime::now());
+            *history = std::iter::once(summary).chain(added_since).collect();
+
+            let _ = history_tx.send(actor.model.history.clone());
+            history_len.store(actor.model.history.len(), Ordering::SeqCst);
+            let _ = msg.done_tx.try_send(());
+
+            Reply::ready()
+        });
+    }
+
+    // ----- ConvSetSystemPrompt: update watch channel (sync only) -----
+    builder.mutate_on::<ConvSetSystemPrompt>(move |_actor, ctx| {
+        let prompt = ctx.message().prompt.clone();
+        let _ = system_prompt_tx.send(prompt);
+
+        Reply::ready()
+    });
+}
+
+// =========================================================================
+// Conversation — thin, Clone + Send + 'static wrapper
+// =========================================================================
+
+/// A managed conversation with automatic history tracking.
+///
+/// `Conversation` is backed by an actor that serializes all operations through
+/// its mailbox. This makes `Conversation` `Clone + Send + 'static` with all
+/// methods taking `&self`, enabling safe sharing across tasks.
+///
+/// Each call to [`send`](Self::send) automatically:
+/// 1. Adds the user message to history
+/// 2. Sends the request to the LLM
+/// 3. Adds the assistant response to history
+/// 4. Returns the response
+///
+/// Create a `Conversation` using [`ActonAI::conversation()`](ActonAI::conversation)
+/// and [`ConversationBuilder::build().await`](ConversationBuilder::build).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let conv = runtime.conversation()
+///     .system("You are a helpful assistant.")
+///     .build()
+///     .await;
+///
+/// loop {
+///     let input = read_user_input();
+///     let response = conv.send(&input).await?;
+///     println!("{}", response.text);
+/// }
+/// ```
+pub struct Conversation {
+    /// Handle to the ConversationActor.
+    handle: ActorHandle,
+    /// The ActonAI runtime (cheaply cloned via Arc).
+    runtime: ActonAI,
+    /// Exit flag — set when the exit tool is called.
+    exit_requested: Arc<AtomicBool>,
+    /// Whether the exit tool is enabled.
+    exit_tool_enabled: Arc<AtomicBool>,
+    /// Broadcast receiver for history changes.
+    history_rx: watch::Receiver<Vec<Message>>,
+    /// Lock-free history length.
+    history_len: Arc<AtomicUsize>,
+    /// Broadcast receiver for system prompt changes.
+    system_prompt_rx: watch::Receiver<Option<String>>,
+    /// Cancellation token of the send currently in flight, shared with the
+    /// ConversationActor's ConvSend handler.
+    pending_cancel: Arc<Mutex<Option<CancellationToken>>>,
+    /// Instruction injected before each user message, shared with the
+    /// ConversationActor's ConvSend handler.
+    recurring_injection: Arc<watch::Sender<Option<String>>>,
+    /// Long-lived stream collector shared with the ConversationActor's
+    /// ConvSend handler. Held here (and cloned through `Clone`) so the
+    /// session outlives the actor and is cleanly stopped when the last
+    /// `Conversation` clone drops.
+    stream_session: StreamCollectorSession,
+    /// Identifies this conversation in stored summaries.
+    id: ConversationId,
+    /// Summary-to-memory settings, when enabled.
+    summarization: Option<Arc<Summarization>>,
+    /// Recent-response cache shared with the actor, when enabled.
+    dedup: Option<Arc<Mutex<MessageDeduplication>>>,
+    /// Conversation this one was branched from, if any.
+    parent_id: Option<ConversationId>,
+    /// Number of parent messages the branch started with.
+    branch_point: Option<usize>,
+    /// Summary settings for [`summarize`](Self::summarize).
+    summary_config: Arc<SummarizationConfig>,
+    /// History length that triggers summarization before a send.
+    auto_summarize_at: Option<usize>,
+    /// Memory store the history is saved to, when persistent.
+    persistence: Option<Arc<Persistence>>,
+}
+
+// Compile-time assertion: Conversation is Clone + Send + 'static.
+const _: () = {
+    #[allow(dead_code)]
+    fn assert_clone_send_static<T: Clone + Send + 'static>() {}
+    #[allow(dead_code)]
+    fn assert_conversation() {
+        assert_clone_send_static::<Conversation>();
+    }
+};
+
+impl Clone for Conversation {
+    fn clone(&self) -> Self {
+        Self {
+            handle: self.handle.clone(),
+            runtime: self.runtime.clone(),
+            exit_requested: self.exit_requested.clone(),
+            exit_tool_enabled: self.exit_tool_enabled.clone(),
+            history_rx: self.history_rx.clone(),
+            history_len: self.history_len.clone(),
+            system_prompt_rx: self.system_prompt_rx.clone(),
+            pending_cancel: self.pending_cancel.clone(),
+            recurring_injection: self.recurring_injection.clone(),
+            stream_session: self.stream_session.clone(),
+            id: self.id.clone(),
+            summarization: self.summarization.clone(),
+            dedup: self.dedup.clone(),
+            parent_id: self.parent_id.clone(),
+            branch_point: self.branch_point,
+            summary_config: self.summary_config.clone(),
+            auto_summarize_at: self.auto_summarize_at,
+            persistence: self.persistence.clone(),
+        }
+    }
+}
+
+impl Conversation {
+    /// Sends a message and receives a response, automatically managing history.
+    ///
+    /// This is the primary method for interacting with a conversation. It:
+    /// 1. Adds the user message to the conversation history
+    /// 2. Sends the request to the LLM with the full history
+    /// 3. Adds the assistant's response to history
+    /// 4. Returns the collected response
+    ///
+    /// If builtins were configured with [`with_builtins`](crate::ActonAIBuilder::with_builtins),
+    /// they are automatically available to the LLM.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let response = conv.send("What is the capital of France?").await?;
+    /// println!("{}", response.text);  // "Paris"
+    ///
+    /// // Conversation remembers context
+    /// let response = conv.send("What about Germany?").await?;
+    /// println!("{}", response.text);  // "Berlin"
+    /// ```
+    pub async fn send(
+        &self,
+        content: impl Into<String>,
+    ) -> Result<CollectedResponse, ActonAIError> {
+        self.dispatch(content.into(), None, false).await
+    }
+
+    /// Like [`send`](Self::send), but always calls the LLM even when
+    /// [deduplication](ConversationBuilder::with_deduplication) would have
+    /// answered from the cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails.
+    pub async fn send_forced(
+        &self,
+        content: impl Into<String>,
+    ) -> Result<CollectedResponse, ActonAIError> {
+        self.dispatch(content.into(), None, true).await
+    }
+
+    /// Sends a message with streaming tokens delivered to a user-provided actor.
+    ///
+    /// The `token_handle` actor must have a handler registered for [`StreamToken`]
+    /// messages. Tokens are delivered in order as they arrive from the LLM.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Create a token-handling actor
+    /// let mut printer = runtime.runtime().clone().new_actor::<MyPrinter>();
+    /// printer.mutate_on::<StreamToken>(|_actor, ctx| {
+    ///     print!("{}", ctx.message().text);
+    ///     Reply::ready()
+    /// });
+    /// let printer_handle = printer.start().await;
+    ///
+    /// let response = conv.send_streaming("Tell me a story", &printer_handle).await?;
+    /// ```
+    pub async fn send_streaming(
+        &self,
+        content: impl Into<String>,
+        token_handle: &ActorHandle,
+    ) -> Result<CollectedResponse, ActonAIError> {
+        self.dispatch(content.into(), Some(token_handle.clone()), false)
+            .await
+    }
+
+    /// Sends `messages` one after another and returns their responses in
+    /// order.
+    ///
+    /// Each message completes, including any tool rounds, before the next is
+    /// sent, so later messages see the earlier exchanges in history. A failed
+    /// message does not stop the batch.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let responses = conv
+    ///     .send_batch(vec![
+    ///         "Summarize the README.".to_string(),
+    ///         "Now list its open questions.".to_string(),
+    ///     ])
+    ///     .await;
+    /// ```
+    pub async fn send_batch(
+        &self,
+        messages: Vec<String>,
+    ) -> Vec<Result<CollectedResponse, ActonAIError>> {
+        self.send_batch_with_progress(messages, |_, _| {}).await
+    }
+
+    /// Like [`send_batch`](Self::send_batch), calling `on_progress` with the
+    /// index and response of each message that succeeds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let total = questions.len();
+    /// let responses = conv
+    ///     .send_batch_with_progress(questions, |index, _response| {
+    ///         println!("answered {}/{total}", index + 1);
+    ///     })
+    ///     .await;
+    /// ```
+    pub async fn send_batch_with_progress(
+        &self,
+        messages: Vec<String>,
+        on_progress: impl Fn(usize, &CollectedResponse),
+    ) -> Vec<Result<CollectedResponse, ActonAIError>> {
+        let mut results = Vec::with_capacity(messages.len());
+        for (index, content) in messages.into_iter().enumerate() {
+            let result = self.send(content).await;
+            if let Ok(response) = &result {
+                on_progress(index, response);
+            }
+            results.push(result);
+        }
+        results
+    }
+
+    /// Sends each of `messages` to its own [branch](Self::branch) of the
+    /// current history, concurrently, and returns their responses in order.
+    ///
+    /// The messages do not see each other and this conversation's history
+    /// is unchanged, which makes this useful for comparing alternative
+    /// continuations.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let alternatives = conv
+    ///     .send_batch_parallel(vec![
+    ///         "Answer formally.".to_string(),
+    ///         "Answer casually.".to_string(),
+    ///     ])
+    ///     .await;
+    /// ```
+    pub async fn send_batch_parallel(
+        &self,
+        messages: Vec<String>,
+    ) -> Vec<Result<CollectedResponse, ActonAIError>> {
+        let from_index = self.len();
+        let sends = messages
+            .into_iter()
+            .map(|content| async move { self.branch(from_index).await.send(content).await });
+        futures::future::join_all(sends).await
+    }
+
+    /// Sends a [`ConvSend`] to the actor and waits for its result.
+    async fn dispatch(
+        &self,
+        content: String,
+        token_target: Option<ActorHandle>,
+        force: bool,
+    ) -> Result<CollectedResponse, ActonAIError> {
+        if self
+            .auto_summarize_at
+            .is_some_and(|threshold| self.len() >= threshold)
+        {
+            if let Err(e) = self.summarize_in_place().await {
+                tracing::warn!(
+                    conversation_id = %self.id,
+                    error = %e,
+                    "failed to summarize conversation; sending full history"
+                );
+            }
+        }
+
+        let (tx, mut rx) = mpsc::channel(1);
+        self.handle
+            .send(ConvSend {
+                content,
+                token_target,
+                force,
+                result_tx: tx,
+            })
+            .await;
+
+        let result = rx.recv().await.unwrap_or_else(|| {
+            Err(ActonAIError::prompt_failed(
+                "conversation actor dropped".to_string(),
+            ))
+        });
+
+        if result.is_ok() {
+            if let Err(e) = self.persist_now().await {
+                tracing::warn!(
+                    conversation_id = %self.id,
+                    error = %e,
+                    "failed to persist conversation"
+                );
+            }
+        }
+        result
+    }
+
+    /// Saves every message not yet in the memory store.
+    ///
+    /// [`send`](Self::send) already saves after each successful exchange;
+    /// use this after changing the history directly (e.g., with
+    /// [`apply_inspection`](Self::apply_inspection)) or before shutting
+    /// down. Does nothing unless the runtime had an
+    /// [attached memory store](ActonAI::attach_memory_store) when the
+    /// conversation was built.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store does not confirm a save in time; the
+    /// unsaved messages are retried on the next save.
+    pub async fn persist_now(&self) -> Result<(), ActonAIError> {
+        match &self.persistence {
+            Some(persistence) => {
+                persistence
+                    .save_new(&self.runtime, &self.id, &self.history())
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Cancels the send currently in flight, if any.
+    ///
+    /// The cancelled [`send`](Self::send) returns an error (see
+    /// [`ActonAIError::is_cancelled`]) and its message stays in history
+    /// without a reply. Sends queued behind it are unaffected.
+    pub fn cancel_pending(&self) {
+        if let Some(token) = lock_pending_cancel(&self.pending_cancel).take() {
+            token.cancel();
+        }
+    }
+
+    /// Returns deduplication counters.
+    ///
+    /// All zeros unless the conversation was built with
+    /// [`with_deduplication`](ConversationBuilder::with_deduplication).
+    #[must_use]
+    pub fn dedup_stats(&self) -> DeduplicationStats {
+        self.dedup
+            .as_deref()
+            .map(|dedup| lock_dedup(dedup).stats())
+            .unwrap_or_default()
+    }
+
+    /// Summarizes the conversation history with the LLM.
+    ///
+    /// Uses the summary prompt set with
+    /// [`ConversationBuilder::summary_prompt`] (or
+    /// [`DEFAULT_SUMMARY_PROMPT`]). The history is left unchanged; see
+    /// [`summarize_in_place`](Self::summarize_in_place) to replace it.
+    /// Returns an empty summary when there are no user or assistant turns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summarization LLM call fails.
+    pub async fn summarize(&self) -> Result<String, ActonAIError> {
+        summarize_history(&self.runtime, &self.summary_config, &self.history()).await
+    }
+
+    /// Summarizes the history and replaces it with the summary.
+    ///
+    /// The history becomes a single assistant message holding the summary;
+    /// the system prompt is kept and still applies. Messages added while the
+    /// summary was being written are kept after it. Returns the summary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summarization LLM call fails; the history is
+    /// left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if conv.len() > 40 {
+    ///     conv.summarize_in_place().await?;
+    /// }
+    /// ```
+    pub async fn summarize_in_place(&self) -> Result<String, ActonAIError> {
+        let history = self.history();
+        let summary = summarize_history(&self.runtime, &self.summary_config, &history).await?;
+        if summary.is_empty() {
+            return Ok(summary);
+        }
+
+        let (done_tx, mut done_rx) = mpsc::channel(1);
+        self.handle
+            .send(ConvCompress {
+                summary: summary.clone(),
+                summarized_len: history.len(),
+                done_tx,
+            })
+            .await;
+        let _ = done_rx.recv().await;
+        Ok(summary)
+    }
+
+    /// Returns a snapshot of the conversation history.
+    ///
+    /// This is useful for:
+    /// - Serializing the conversation for persistence
+    /// - Inspecting the conversation for debugging
+    /// - Building custom UIs that display history
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// for message in conv.history() {
+    ///     println!("{}: {}", message.role, message.content);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn history(&self) -> Vec<Message> {
+        self.history_rx.borrow().clone()
+    }
+
+    /// Clears the conversation history.
+    ///
+    /// This resets the conversation to a fresh state while keeping the system
+    /// prompt. Use this to start a new topic without creating a new `Conversation`.
+    ///
+    /// The clear is sent as a fire-and-forget message to the actor and will be
+    /// processed after any in-flight sends complete.
+    ///
+    /// With [`with_summarization_memory`](ConversationBuilder::with_summarization_memory),
+    /// the history being cleared is summarized and stored in the background.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// conv.send("Topic A discussion...").await?;
+    /// conv.clear();  // Start fresh
+    /// conv.send("Topic B discussion...").await?;
+    /// ```
+    pub fn clear(&self) {
+        let handle = self.handle.clone();
+        let pending = self.summarization.clone().map(|summarization| {
+            (
+                summarization,
+                self.history(),
+                self.runtime.clone(),
+                self.id.clone(),
+            )
+        });
+        tokio::spawn(async move {
+            handle.send(ConvClear).await;
+            if let Some((summarization, history, runtime, id)) = pending {
+                if let Err(e) = summarization
+                    .summarize_and_store(&runtime, &id, &history)
+                    .await
+                {
+                    tracing::warn!(
+                        conversation_id = %id,
+                        error = %e,
+                        "failed to summarize conversation before clearing"
+                    );
+                }
+            }
+        });
+    }
+
+    /// Closes the conversation, stopping its actor.
+    ///
+    /// With [`with_summarization_memory`](ConversationBuilder::with_summarization_memory),
+    /// the history is summarized and stored first, tagged with
+    /// [`CONVERSATION_SUMMARY_TAG`](crate::memory::CONVERSATION_SUMMARY_TAG)
+    /// and this conversation's [`ConversationId`].
+    ///
+    /// Other clones of this `Conversation` can no longer send once it is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the summarization LLM call fails. The actor is
+    /// stopped either way.
+    pub async fn close(&self) -> Result<(), ActonAIError> {
+        let summarized = match &self.summarization {
+            Some(summarization) => {
+                summarization
+                    .summarize_and_store(&self.runtime, &self.id, &self.history())
+                    .await
+            }
+            None => Ok(()),
+        };
+
+        let _ = self.handle.stop().await;
+        summarized
+    }
+
+    /// Creates a [`ConversationInspector`] over a snapshot of the current history.
+    ///
+    /// Edits made through the inspector do not touch the conversation until
+    /// passed to [`apply_inspection`](Self::apply_inspection).
+    #[must_use]
+    pub fn inspect(&self) -> ConversationInspector {
+        ConversationInspector::new(self.history())
+    }
+
+    /// Replaces the conversation history with the inspector's result.
+    ///
+    /// Like [`clear`](Self::clear), this is fire-and-forget and is processed
+    /// after any in-flight sends complete.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut inspector = conv.inspect();
+    /// inspector.redact(0, "[redacted]").remove(3);
+    /// conv.apply_inspection(inspector);
+    /// ```
+    pub fn apply_inspection(&self, inspector: ConversationInspector) {
+        let handle = self.handle.clone();
+        let history = inspector.build();
+        tokio::spawn(async move {
+            handle.send(ConvRollback { history }).await;
+        });
+    }
+
+    /// Starts a new conversation from the first `from_index` messages of
+    /// this one.
+    ///
+    /// The branch has its own actor and history, shares this conversation's
+    /// runtime and system prompt, and records this conversation as its
+    /// [parent](Self::parent_id). Sends to either conversation do not affect
+    /// the other. An index past the end copies the whole history.
+    ///
+    /// Use [`ConversationBuilder::branch_from`] to configure the branch
+    /// before building it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// conv.send("Suggest a name for a Rust web framework").await?;
+    ///
+    /// // Explore another direction from the same question
+    /// let alt = conv.branch(1).await;
+    /// alt.send("Make it sound more playful").await?;
+    /// ```
+    pub async fn branch(&self, from_index: usize) -> Conversation {
+        self.runtime
+            .conversation()
+            .branch_from(self, from_index)
+            .build()
+            .await
+    }
+
+    /// Returns the ID of this conversation.
+    ///
+    /// Pass it to [`ActonAI::conversation_from_id`] to resume a persisted
+    /// conversation in a later session.
+    #[must_use]
+    pub fn id(&self) -> ConversationId {
+        self.id.clone()
+    }
+
+    /// Returns the ID of the conversation this one was branched from.
+    #[must_use]
+    pub fn parent_id(&self) -> Option<&ConversationId> {
+        self.parent_id.as_ref()
+    }
+
+    /// Returns how many of the parent's messages this branch started with.
+    #[must_use]
+    pub fn branch_point(&self) -> Option<usize> {
+        self.branch_point
+    }
+
+    /// Captures the history, system prompt, and branch provenance.
+    ///
+    /// The snapshot is serializable, so branches can be saved (for example
+    /// alongside other data in a `MemoryStore`) and rebuilt later with
+    /// [`ConversationBuilder::restore_snapshot`].
+    #[must_use]
+    pub fn history_snapshot(&self) -> ConversationSnapshot {
+        ConversationSnapshot {
+            id: self.id.clone(),
+            parent_id: self.parent_id.clone(),
+            branch_point: self.branch_point,
+            system_prompt: self.system_prompt(),
+            history: self.history(),
+        }
+    }
+
+    /// Renders the system prompt and history as a transcript.
+    ///
+    /// The system prompt, when set, comes first as a system message. Only
+    /// roles, contents, and (for JSON and CSV) the times messages were
+    /// added are included. Read a transcript back with
+    /// [`ConversationBuilder::restore_from_export`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// std::fs::write("transcript.md", conv.export(ExportFormat::Markdown))?;
+    /// ```
+    #[must_use]
+    pub fn export(&self, format: ExportFormat) -> String {
+        let mut messages: Vec<Message> = self
+            .system_prompt()
+            .map(Message::system)
+            .into_iter()
+            .collect();
+        messages.extend(self.history());
+        export::export_messages(&messages, format)
+    }
+
+    /// Returns the number of messages in the conversation history.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.history_len.load(Ordering::SeqCst)
+    }
+
+    /// Returns true if the conversation history is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.history_len.load(Ordering::SeqCst) == 0
+    }
+
+    /// Returns the system prompt, if set.
+    #[must_use]
+    pub fn system_prompt(&self) -> Option<String> {
+        self.system_prompt_rx.borrow().clone()
+    }
+
+    /// Sets or updates the system prompt.
+    ///
+    /// This can be used to change the assistant's behavior mid-conversation.
+    /// The change is sent as a fire-and-forget message and will take effect
+    /// on the next [`send`](Self::send) call.
+    pub fn set_system_prompt(&self, prompt: impl Into<String>) {
+        let handle = self.handle.clone();
+        let prompt = prompt.into();
+        tokio::spawn(async move {
+            handle
+                .send(ConvSetSystemPrompt {
+                    prompt: Some(prompt),
+                })
+                .await;
+        });
+    }
+
+    /// Clears the system prompt.
+    pub fn clear_system_prompt(&self) {
+        let handle = self.handle.clone();
+        tokio::spawn(async move {
+            handle.send(ConvSetSystemPrompt { prompt: None }).await;
+        });
+    }
+
+    /// Sets an instruction to inject before the user message of every
+    /// subsequent send, or stops injecting with `None`.
+    ///
+    /// See [`PromptBuilder::inject_instruction_before_last_user`](crate::prompt::PromptBuilder::inject_instruction_before_last_user).
+    /// The instruction is sent with each request but never added to history.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// conv.set_recurring_injection(Some(
+    ///     "Reminder: never run commands outside the project directory.".to_string(),
+    /// ));
+    /// ```
+    pub fn set_recurring_injection(&self, content: Option<String>) {
+        self.recurring_injection.send_replace(content);
+    }
+
+    /// Returns `true` if the exit tool has been called.
+    ///
+    /// Use this to check if the conversation should end. The exit flag
+    /// is set when the LLM calls the `exit_conversation` tool, which
+    /// it does when the user indicates they want to leave.
+    ///
+    /// This method is only meaningful when the conversation was built
+    /// with [`with_exit_tool`](ConversationBuilder::with_exit_tool).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// loop {
+    ///     let response = conv.send(&input).await?;
+    ///
+    ///     if conv.should_exit() {
+    ///         println!("Goodbye!");
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn should_exit(&self) -> bool {
+        self.exit_requested.load(Ordering::SeqCst)
+    }
+
+    /// Clears the exit flag.
+    ///
+    /// Use this if you want to reset the conversation state and continue
+    /// after the exit tool was called. For example, you might ask for
+    /// confirmation before actually exiting.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// if conv.should_exit() {
+    ///     println!("Are you sure you want to leave?");
+    ///     let confirmation = read_input();
+    ///     if confirmation != "yes" {
+    ///         conv.clear_exit();
+    ///         continue;
+    ///     }
+    /// }
+    /// ```
+    pub fn clear_exit(&self) {
+        self.exit_requested.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns a clone of the exit flag `Arc<AtomicBool>`.
+    ///
+    /// This is useful if you need to share the exit state with other
+    /// components or check it from a different context.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let exit_flag = conv.exit_requested();
+    ///
+    /// // Check from another context
+    /// if exit_flag.load(Ordering::SeqCst) {
+    ///     // Handle exit
+    /// }
+    /// ```
+    #[must_use]
+    pub fn exit_requested(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.exit_requested)
+    }
+
+    /// Returns whether the exit tool is enabled for this conversation.
+    #[must_use]
+    pub fn is_exit_tool_enabled(&self) -> bool {
+        self.exit_tool_enabled.load(Ordering::SeqCst)
+    }
+
+    /// Runs a complete terminal chat loop.
+    ///
+    /// This method provides a minimal API for building terminal chat applications.
+    /// It handles:
+    /// - Reading input from stdin with "You: " prompt
+    /// - Sending to the LLM and streaming the response
+    /// - Printing "Assistant: " prefix before responses
+    /// - Streaming tokens to stdout
+    /// - Checking `should_exit()` and breaking when true
+    /// - Breaking on EOF (Ctrl+D)
+    ///
+    /// The exit tool is automatically enabled since the chat loop depends on it
+    /// for graceful termination. If no system prompt is set, a sensible default
+    /// is used (see [`DEFAULT_SYSTEM_PROMPT`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an LLM request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// ActonAI::builder()
+    ///     .app_name("chat")
+    ///     .ollama("qwen2.5:7b")
+    ///     .with_builtins()
+    ///     .launch()
+    ///     .await?
+    ///     .conversation()
+    ///     .run_chat()
+    ///     .await?;
+    /// ```
+    pub async fn run_chat(&self) -> Result<(), ActonAIError> {
+        self.run_chat_with(ChatConfig::default()).await
+    }
+
+    /// Runs a complete terminal chat loop with custom configuration.
+    ///
+    /// Like [`run_chat`](Self::run_chat), but allows customization of prompts,
+    /// input transformation, and token handling via [`ChatConfig`].
+    ///
+    /// The exit tool is automatically enabled since the chat loop depends on it
+    /// for graceful termination. If no system prompt is set, a sensible default
+    /// is used (see [`DEFAULT_SYSTEM_PROMPT`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an LLM request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// conv.run_chat_with(
+    ///     ChatConfig::new()
+    ///         .user_prompt(">>> ")
+    ///         .assistant_prompt("AI: ")
+    ///         .map_input(|s| format!("[context] {}", s))
+    /// ).await?;
+    /// ```
+    pub async fn run_chat_with(&self, mut config: ChatConfig) -> Result<(), ActonAIError> {
+        use std::io::{BufRead, Write};
+
+        // Auto-enable exit tool since chat loop depends on it
+        self.exit_tool_enabled.store(true, Ordering::SeqCst);
+
+        // Use default system prompt if none is set
+        if self.system_prompt_rx.borrow().is_none() {
+            self.handle
+                .send(ConvSetSystemPrompt {
+                    prompt: Some(DEFAULT_SYSTEM_PROMPT.to_string()),
+                })
+                .await;
+        }
+
+        // Create a temporary token actor for stdout printing
+        let mut actor_runtime = self.runtime.runtime().clone();
+        let mut token_actor = actor_runtime.new_actor::<StdoutTokenPrinter>();
+        token_actor.mutate_on::<StreamToken>(|_actor, ctx| {
+            print!("{}", ctx.message().text);
+            std::io::stdout().flush().ok();
+            Reply::ready()
+        });
+        let token_handle = token_actor.start().await;
+
+        let stdin = std::io::stdin();
+
+        let result = loop {
+            // Print user prompt and flush
+            print!("{}", config.user_prompt);
+            std::io::stdout().flush().ok();
+
+            // Read input line
+            let mut input = String::new();
+            if stdin.lock().read_line(&mut input).unwrap_or(0) == 0 {
+                break Ok(()); // EOF
+            }
+
+            let input = input.trim();
+            if input.is_empty() {
+                continue;
+            }
+
+            // Apply input mapper if present
+            let content = match config.input_mapper.as_mut() {
+                Some(mapper) => mapper(input),
+                None => input.to_string(),
+            };
+
+            // Print assistant prompt
+            print!("{}", config.assistant_prompt);
+            std::io::stdout().flush().ok();
+
+            // Stream response to stdout via the token actor
+            match self.send_streaming(&content, &token_handle).await {
+                Ok(_) => {
+                    println!();
+                }
+                Err(e) => {
+                    break Err(e);
+                }
+            }
+
+            // Check for exit
+            if self.should_exit() {
+                break Ok(());
+            }
+        };
+
+        // Stop the temporary token actor
+        let _ = token_handle.stop().await;
+
+        result
+    }
+}
+
+impl std::fmt::Debug for Conversation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Conversation")
+            .field("history_len", &self.history_len.load(Ordering::SeqCst))
+            .field(
+                "has_system_prompt",
+                &self.system_prompt_rx.borrow().is_some(),
+            )
+            .field(
+                "exit_tool_enabled",
+                &self.exit_tool_enabled.load(Ordering::SeqCst),
+            )
+            .field(
+                "exit_requested",
+                &self.exit_requested.load(Ordering::SeqCst),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+/// Serializable state of a [`Conversation`], from
+/// [`Conversation::history_snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConversationSnapshot {
+    /// The conversation the snapshot was taken from
+    pub id: ConversationId,
+    /// The conversation it was branched from, if any
+    pub parent_id: Option<ConversationId>,
+    /// Number of parent messages the branch started with
+    pub branch_point: Option<usize>,
+    /// The system prompt
+    pub system_prompt: Option<String>,
+    /// The full message history
+    pub history: Vec<Message>,
+}
+
+// =========================================================================
+// ConversationBuilder
+// =========================================================================
+
+/// How long [`ConversationBuilder::build`] waits for the tool registry.
+const TOOL_VERSION_QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Warns about tool schemas a restored history was recorded against that
+/// no longer match the registry, and about tools the registry does not know.
+fn warn_outdated_tools(
+    current: &ToolSchemaVersions,
+    recorded: &[ToolSchemaVersion],
+    history: &[Message],
+) {
+    let current_hash = |name: &str| {
+        current
+            .versions
+            .iter()
+            .find(|v| v.tool_name == name)
+            .map(|v| v.schema_hash.as_str())
+    };
+
+    for old in recorded {
+        if current_hash(&old.tool_name).is_some_and(|hash| hash != old.schema_hash) {
+            tracing::warn!(
+                tool = %old.tool_name,
+                recorded_version = old.version,
+                "restored conversation uses an outdated tool schema"
+            );
+        }
+    }
+
+    let mut unknown: Vec<&str> = history
+        .iter()
+        .filter_map(|m| m.tool_calls.as_ref())
+        .flatten()
+        .map(|call| call.name.as_str())
+        .filter(|name| current_hash(name).is_none())
+        .collect();
+    unknown.sort_unstable();
+    unknown.dedup();
+    for name in unknown {
+        tracing::warn!(tool = %name, "restored conversation calls a tool that is not registered");
+    }
+}
+
+/// Builder for creating a [`Conversation`].
+///
+/// Created via [`ActonAI::conversation()`](ActonAI::conversation).
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let conv = runtime.conversation()
+///     .system("You are a helpful assistant.")
+///     .restore(saved_history)
+///     .build()
+///     .await;
+/// ```
+pub struct ConversationBuilder {
+    runtime: ActonAI,
+    system_prompt: Option<String>,
+    history: Vec<Message>,
+    /// Whether to enable the built-in exit tool
+    exit_tool_enabled: bool,
+    /// Override for the per-turn context window. `Some(None)` = explicit
+    /// opt-out; `Some(Some(cw))` = explicit override; `None` = inherit
+    /// from the runtime at [`build`](Self::build).
+    context_window_override: Option<Option<crate::memory::ContextWindow>>,
+    /// Memory store and embedder for conversation summaries
+    summarization_memory: Option<(ActorHandle, Box<dyn EmbeddingProvider>)>,
+    /// Summary settings used with `summarization_memory`
+    summarization_config: SummarizationConfig,
+    /// Agent that owns stored summaries
+    agent_id: Option<AgentId>,
+    /// Window within which repeated user messages are answered from cache
+    dedup_window: Option<Duration>,
+    /// Tool schema versions the restored history was recorded with
+    restored_tool_versions: Vec<ToolSchemaVersion>,
+    /// Tool registry to check the restored history against at build time
+    tool_version_registry: Option<ActorHandle>,
+    /// History length that triggers summarization before a send
+    auto_summarize_at: Option<usize>,
+    /// Parent conversation and branch point, for branches
+    branch: Option<(ConversationId, usize)>,
+    /// Persistent identity, set with `with_id`
+    id: Option<ConversationId>,
+    /// Whether to persist to the runtime's memory store. `None` = persist
+    /// if the runtime has one.
+    persistence: Option<bool>,
+}
+
+impl ConversationBuilder {
+    /// Creates a new conversation builder.
+    pub(crate) fn new(runtime: ActonAI) -> Self {
+        Self {
+            runtime,
+            system_prompt: None,
+            history: Vec::new(),
+            exit_tool_enabled: false,
+            context_window_override: None,
+            summarization_memory: None,
+            summarization_config: SummarizationConfig::default(),
+            agent_id: None,
+            dedup_window: None,
+            restored_tool_versions: Vec::new(),
+            tool_version_registry: None,
+            auto_summarize_at: None,
+            branch: None,
+            id: None,
+            persistence: None,
+        }
+    }
+
+    /// Sets the system prompt for the conversation.
+    ///
+    /// The system prompt provides context and instructions to the LLM
+    /// about how to respond. It's applied to every message in the conversation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .system("You are a concise assistant. Answer in one sentence.")
+    ///     .build()
+    ///     .await;
+    /// ```
+    #[must_use]
+    pub fn system(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    /// Sets the system prompt from the contents of a text or markdown file.
+    ///
+    /// See [`PromptBuilder::system_from_file`](crate::prompt::PromptBuilder::system_from_file).
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the path is not allowed or the file
+    /// cannot be read.
+    pub fn system_from_file(self, path: impl AsRef<std::path::Path>) -> Result<Self, ActonAIError> {
+        let prompt = crate::system_prompt::read_system_prompt_file(path.as_ref())?;
+        Ok(self.system(prompt))
+    }
+
+    /// Restores conversation history from a previous session.
+    ///
+    /// Use this to continue a conversation that was previously saved or
+    /// to inject context from an external source.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Load saved history
+    /// let saved: Vec<Message> = load_from_database();
+    ///
+    /// let conv = runtime.conversation()
+    ///     .system("Be helpful.")
+    ///     .restore(saved)
+    ///     .build()
+    ///     .await;
+    /// ```
+    #[must_use]
+    pub fn restore(mut self, messages: impl IntoIterator<Item = Message>) -> Self {
+        self.history = messages.into_iter().collect();
+        self
+    }
+
+    /// Resumes the conversation with the given ID.
+    ///
+    /// When the runtime has an
+    /// [attached memory store](ActonAI::attach_memory_store),
+    /// [`build`](Self::build) loads the stored history of `id` (replacing
+    /// any [restored](Self::restore) history if some was stored) and the
+    /// conversation keeps saving to it. `id` must come from
+    /// [`Conversation::id`] of a conversation persisted to the same store;
+    /// without this call, `build` creates a new conversation in the store.
+    /// Without a store, the ID only identifies the conversation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .with_id(saved_id)
+    ///     .build()
+    ///     .await;
+    /// ```
+    #[must_use]
+    pub fn with_id(mut self, id: ConversationId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Starts the conversation as a branch of `parent`.
+    ///
+    /// Copies the first `index` messages of the parent's history (all of
+    /// them if `index` is past the end), its system prompt, and whether its
+    /// exit tool is enabled. Later builder calls can override any of these.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let branch = runtime.conversation()
+    ///     .branch_from(&conv, 4)
+    ///     .system("Answer as a skeptic.")
+    ///     .build()
+    ///     .await;
+    /// assert_eq!(branch.branch_point(), Some(4));
+    /// ```
+    #[must_use]
+    pub fn branch_from(mut self, parent: &Conversation, index: usize) -> Self {
+        let mut history = parent.history();
+        history.truncate(index);
+        self.branch = Some((parent.id.clone(), history.len()));
+        self.history = history;
+        self.system_prompt = parent.system_prompt();
+        self.exit_tool_enabled = parent.is_exit_tool_enabled();
+        self
+    }
+
+    /// Restores a conversation saved with [`Conversation::history_snapshot`].
+    ///
+    /// Sets the history, system prompt, and branch provenance. The restored
+    /// conversation gets a new ID.
+    #[must_use]
+    pub fn restore_snapshot(mut self, snapshot: ConversationSnapshot) -> Self {
+        self.history = snapshot.history;
+        self.system_prompt = snapshot.system_prompt;
+        self.branch = snapshot.parent_id.zip(snapshot.branch_point);
+        self
+    }
+
+    /// Restores a transcript produced by [`Conversation::export`].
+    ///
+    /// A leading system message becomes the system prompt; the remaining
+    /// messages become the history.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if `data` is not valid `format` output.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let data = std::fs::read_to_string("transcript.json")?;
+    /// let conv = runtime.conversation()
+    ///     .restore_from_export(ExportFormat::Json, &data)?
+    ///     .build()
+    ///     .await;
+    /// ```
+    pub fn restore_from_export(
+        mut self,
+        format: ExportFormat,
+        data: &str,
+    ) -> Result<Self, ActonAIError> {
+        let mut messages = export::import_messages(format, data)?;
+        if messages
+            .first()
+            .is_some_and(|m| m.role == MessageRole::System)
+        {
+            self.system_prompt = Some(messages.remove(0).content);
+        }
+        self.history = messages;
+        Ok(self)
+    }
+
+    /// Overrides the per-turn context window for this conversation.
+    ///
+    /// When unset, the conversation inherits
+    /// [`ActonAI::context_window`](crate::facade::ActonAI::context_window).
+    #[must_use]
+    pub fn context_window(mut self, window: crate::memory::ContextWindow) -> Self {
+        self.context_window_override = Some(Some(window));
+        self
+    }
+
+    /// Disables per-turn history truncation for this conversation.
+    ///
+    /// Overrides any runtime-wide context window; the full history is shipped
+    /// on every call.
+    #[must_use]
+    pub fn without_context_window(mut self) -> Self {
+        self.context_window_override = Some(None);
+        self
+    }
+
+    /// Enables the built-in exit tool for this conversation.
+    ///
+    /// When enabled, an `exit_conversation` tool is automatically available
+    /// to the LLM. When the LLM calls this tool (typically when the user
+    /// wants to end the conversation), the conversation's exit flag is set.
+    ///
+    /// Check the flag with [`Conversation::should_exit`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .system("Be helpful. Use exit_conversation when the user says goodbye.")
+    ///     .with_exit_tool()
+    ///     .build()
+    ///     .await;
+    ///
+    /// loop {
+    ///     let input = read_input();
+    ///     let response = conv.send(&input).await?;
+    ///
+    ///     if conv.should_exit() {
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_exit_tool(mut self) -> Self {
+        self.exit_tool_enabled = true;
+        self
+    }
+
+    /// Explicitly disables the exit tool for this conversation.
+    ///
+    /// By default, [`run_chat`](Conversation::run_chat) auto-enables the exit tool.
+    /// Use this method if you want to build a conversation that will be used
+    /// with `run_chat` but you want to handle exit logic differently.
+    ///
+    /// Note: If you later call [`run_chat`](Conversation::run_chat) or
+    /// [`run_chat_with`](Conversation::run_chat_with), the exit tool will
+    /// still be auto-enabled since the chat loop depends on it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .system("You are helpful.")
+    ///     .without_exit_tool()
+    ///     .build()
+    ///     .await;
+    ///
+    /// // Manual control over conversation
+    /// loop {
+    ///     let response = conv.send(&input).await?;
+    ///     if custom_exit_condition(&response) {
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn without_exit_tool(mut self) -> Self {
+        self.exit_tool_enabled = false;
+        self
+    }
+
+    /// Saves and loads the conversation's history through the runtime's
+    /// memory store.
+    ///
+    /// This is the default when the runtime has a store, set up with
+    /// [`ActonAIBuilder::with_memory_store`](crate::facade::ActonAIBuilder::with_memory_store)
+    /// or [`ActonAI::attach_memory_store`]. Calling it also makes
+    /// [`build`](Self::build) warn when there is no store, instead of
+    /// silently keeping the history in memory only. Combine with
+    /// [`with_id`](Self::with_id) to resume a stored conversation.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .with_persistence()
+    ///     .build()
+    ///     .await;
+    /// let id = conv.id();
+    /// ```
+    #[must_use]
+    pub fn with_persistence(mut self) -> Self {
+        self.persistence = Some(true);
+        self
+    }
+
+    /// Keeps the conversation out of the runtime's memory store, even if
+    /// the runtime has one.
+    #[must_use]
+    pub fn without_persistence(mut self) -> Self {
+        self.persistence = Some(false);
+        self
+    }
+
+    /// Stores a summary of the conversation in `store` when it is closed or cleared.
+    ///
+    /// `store` must be an initialized [`MemoryStore`](crate::memory::MemoryStore).
+    /// The summary is produced by a brief LLM call, embedded with
+    /// `embedding_provider`, and saved via
+    /// [`StoreConversationSummary`]. Load it back with
+    /// [`LoadConversationSummaries`](crate::memory::LoadConversationSummaries).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .agent_id(agent_id.clone())
+    ///     .with_summarization_memory(store.clone(), Box::new(StubEmbeddingProvider::default()))
+    ///     .build()
+    ///     .await;
+    ///
+    /// conv.send("Remember that I prefer dark mode.").await?;
+    /// conv.close().await?;
+    /// ```
+    #[must_use]
+    pub fn with_summarization_memory(
+        mut self,
+        store: ActorHandle,
+        embedding_provider: Box<dyn EmbeddingProvider>,
+    ) -> Self {
+        self.summarization_memory = Some((store, embedding_provider));
+        self
+    }
+
+    /// Overrides the default [`SummarizationConfig`].
+    ///
+    /// Applies to [`Conversation::summarize`] and to summaries stored with
+    /// [`with_summarization_memory`](Self::with_summarization_memory).
+    #[must_use]
+    pub fn summarization_config(mut self, config: SummarizationConfig) -> Self {
+        self.summarization_config = config;
+        self
+    }
+
+    /// Sets the instruction used to summarize the conversation.
+    ///
+    /// Shorthand for overriding [`SummarizationConfig::summary_prompt`].
+    #[must_use]
+    pub fn summary_prompt(mut self, prompt: &str) -> Self {
+        self.summarization_config.summary_prompt = prompt.to_string();
+        self
+    }
+
+    /// Summarizes the history in place before a send once it holds at least
+    /// `threshold_messages` messages.
+    ///
+    /// Keeps long conversations within the context window; see
+    /// [`Conversation::summarize_in_place`]. If summarizing fails, the send
+    /// goes ahead with the full history.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .auto_summarize_at(40)
+    ///     .build()
+    ///     .await;
+    /// ```
+    #[must_use]
+    pub fn auto_summarize_at(mut self, threshold_messages: usize) -> Self {
+        self.auto_summarize_at = Some(threshold_messages);
+        self
+    }
+
+    /// Sets the agent that owns this conversation's stored summaries.
+    ///
+    /// Defaults to a freshly generated [`AgentId`].
+    #[must_use]
+    pub fn agent_id(mut self, agent_id: AgentId) -> Self {
+        self.agent_id = Some(agent_id);
+        self
+    }
+
+    /// Answers repeated identical user messages from cache.
+    ///
+    /// A message whose content matches one sent within `window` gets the
+    /// earlier response back unchanged (text, `token_count`, `stop_reason`)
+    /// without calling the LLM or adding to history. Use
+    /// [`Conversation::send_forced`] to bypass the cache for one send, and
+    /// [`Conversation::dedup_stats`] to see how often it was used.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .with_deduplication(Duration::from_secs(10))
+    ///     .build()
+    ///     .await;
+    /// ```
+    #[must_use]
+    pub fn with_deduplication(mut self, window: Duration) -> Self {
+        self.dedup_window = Some(window);
+        self
+    }
+
+    /// Sets the tool schema versions the restored history was recorded with.
+    ///
+    /// Typically loaded with
+    /// [`LoadConversationToolVersions`](crate::memory::LoadConversationToolVersions).
+    /// Only used together with [`validate_tool_versions`](Self::validate_tool_versions).
+    #[must_use]
+    pub fn restored_tool_versions(
+        mut self,
+        versions: impl IntoIterator<Item = ToolSchemaVersion>,
+    ) -> Self {
+        self.restored_tool_versions = versions.into_iter().collect();
+        self
+    }
+
+    /// Checks the restored history against the tool registry at build time.
+    ///
+    /// [`build`](Self::build) logs a warning for every recorded tool schema
+    /// version that no longer matches the registry, and for every tool called
+    /// in the restored history that the registry has never seen. The
+    /// conversation is built either way.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation()
+    ///     .restore(saved_history)
+    ///     .restored_tool_versions(saved_versions)
+    ///     .validate_tool_versions(registry_handle)
+    ///     .build()
+    ///     .await;
+    /// ```
+    #[must_use]
+    pub fn validate_tool_versions(mut self, registry: ActorHandle) -> Self {
+        self.tool_version_registry = Some(registry);
+        self
+    }
+
+    /// Builds the conversation by spawning a [`ConversationActor`].
+    ///
+    /// After calling this, you can use [`Conversation::send`] to interact
+    /// with the LLM. The returned `Conversation` is `Clone + Send + 'static`.
+    pub async fn build(self) -> Conversation {
+        if let Some(registry) = &self.tool_version_registry {
+            let mut actor_runtime = self.runtime.runtime().clone();
+            let query = get_tool_schema_versions(&mut actor_runtime, registry);
+            match tokio::time::timeout(TOOL_VERSION_QUERY_TIMEOUT, query).await {
+                Ok(current) => {
+                    warn_outdated_tools(&current, &self.restored_tool_versions, &self.history);
+                }
+                Err(_) => tracing::warn!("tool registry did not report schema versions in time"),
+            }
+        }
+
+        let mut initial_history = self.history;
+        let mut id = self.id;
+        let mut persistence = None;
+        let store = match self.persistence {
+            Some(false) => None,
+            Some(true) => {
+                let store = self.runtime.memory_store().cloned();
+                if store.is_none() {
+                    tracing::warn!(
+                        "conversation persistence requested but the runtime has no memory store"
+                    );
+                }
+                store
+            }
+            None => self.runtime.memory_store().cloned(),
+        };
+        if let Some(store) = store {
+            let mut saved = 0;
+            match &id {
+                Some(id) => match load_history(&self.runtime, &store, id).await {
+                    Some(messages) if !messages.is_empty() => {
+                        saved = messages.len();
+                        initial_history = messages;
+                    }
+                    Some(_) => {}
+                    None => tracing::warn!(
+                        conversation_id = %id,
+                        "memory store did not load the conversation in time"
+                    ),
+                },
+                None => {
+                    let agent_id = self.agent_id.clone().unwrap_or_default();
+                    id = create_stored_conversation(&self.runtime, &store, agent_id).await;
+                }
+            }
+            if id.is_some() {
+                persistence = Some(Arc::new(Persistence {
+                    store,
+                    saved: tokio::sync::Mutex::new(saved),
+                }));
+            } else {
+                tracing::warn!(
+                    "memory store did not create the conversation in time; not persisting it"
+                );
+            }
+        }
+
+        // Resolve the context window: explicit builder override wins, else
+        // inherit whatever the runtime was launched with.
+        let context_window = match self.context_window_override {
+            Some(override_value) => override_value,
+            None => self.runtime.context_window().cloned(),
+        };
+
+        // Create watch channels with initial values
+        let (history_tx, history_rx) = watch::channel(initial_history.clone());
+        let (system_prompt_tx, system_prompt_rx) = watch::channel(self.system_prompt);
+
+        let history_len = Arc::new(AtomicUsize::new(initial_history.len()));
+        let exit_requested = Arc::new(AtomicBool::new(false));
+        let exit_tool_enabled = Arc::new(AtomicBool::new(self.exit_tool_enabled));
+
+        // One long-lived stream collector per Conversation. Subscribes
+        // once and is reused by every turn, so the broker never
+        // accumulates dead subscribers across turns.
+        let stream_session = build_stream_collector(&self.runtime).await;
+
+        let dedup = self
+            .dedup_window
+            .map(|window| Arc::new(Mutex::new(MessageDeduplication::new(window))));
+        let pending_cancel = Arc::new(Mutex::new(None));
+        let recurring_injection = Arc::new(watch::Sender::new(None));
+
+        // Create the actor
+        let mut actor_runtime = self.runtime.runtime().clone();
+        let mut actor_builder = actor_runtime.new_actor::<ConversationActor>();
+
+        // Set initial history in the actor model
+        actor_builder.model.history = initial_history;
+
+        let actor_handle = actor_builder.handle().clone();
+
+        // Configure all message handlers
+        configure_handlers(
+            &mut actor_builder,
+            HandlerState {
+                runtime: self.runtime.clone(),
+                self_handle: actor_handle.clone(),
+                history_tx: Arc::new(history_tx),
+                history_len: history_len.clone(),
+                exit_requested: exit_requested.clone(),
+                exit_tool_enabled: exit_tool_enabled.clone(),
+                system_prompt_rx: system_prompt_rx.clone(),
+                system_prompt_tx,
+                context_window,
+                stream_session: stream_session.clone(),
+                dedup: dedup.clone(),
+                pending_cancel: pending_cancel.clone(),
+                recurring_injection: recurring_injection.clone(),
+            },
+        );
+
+        // Start the actor
+        let started = actor_builder.start().await;
+        self.runtime.track_actor(&started);
+
+        let summarization = self
+            .summarization_memory
+            .map(|(store, embedding_provider)| {
+                Arc::new(Summarization {
+                    store,
+                    embedding_provider,
+                    config: self.summarization_config.clone(),
+                    agent_id: self.agent_id.unwrap_or_default(),
+                })
+            });
+
+        Conversation {
+            handle: actor_handle,
+            runtime: self.runtime,
+            exit_requested,
+            exit_tool_enabled,
+            history_rx,
+            history_len,
+            system_prompt_rx,
+            pending_cancel,
+            recurring_injection,
+            stream_session,
+            id: id.unwrap_or_default(),
+            summarization,
+            dedup,
+            parent_id: self.branch.as_ref().map(|(id, _)| id.clone()),
+            branch_point: self.branch.map(|(_, index)| index),
+            summary_config: Arc::new(self.summarization_config),
+            auto_summarize_at: self.auto_summarize_at,
+            persistence,
+        }
+    }
+
+    /// Builds the conversation and immediately runs the chat loop.
+    ///
+    /// This is a convenience method that combines [`build()`](Self::build)
+    /// and [`Conversation::run_chat()`](Conversation::run_chat) into a single call.
+    ///
+    /// The exit tool is automatically enabled and a default system prompt
+    /// is used if none was set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an LLM request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// // Minimal chat - all defaults
+    /// ActonAI::builder()
+    ///     .app_name("chat")
+    ///     .ollama("qwen2.5:7b")
+    ///     .with_builtins()
+    ///     .launch()
+    ///     .await?
+    ///     .conversation()
+    ///     .run_chat()
+    ///     .await?;
+    ///
+    /// // Or with custom system prompt
+    /// runtime.conversation()
+    ///     .system("You are a coding assistant.")
+    ///     .run_chat()
+    ///     .await?;
+    /// ```
+    pub async fn run_chat(self) -> Result<(), ActonAIError> {
+        self.build().await.run_chat().await
+    }
+
+    /// Builds the conversation and immediately runs the chat loop with custom config.
+    ///
+    /// This is a convenience method that combines [`build()`](Self::build)
+    /// and [`Conversation::run_chat_with()`](Conversation::run_chat_with) into a single call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an LLM request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// runtime.conversation()
+    ///     .system("You are helpful.")
+    ///     .run_chat_with(
+    ///         ChatConfig::new()
+    ///             .user_prompt(">>> ")
+    ///             .assistant_prompt("AI: ")
+    ///     )
+    ///     .await?;
+    /// ```
+    pub async fn run_chat_with(self, config: ChatConfig) -> Result<(), ActonAIError> {
+        self.build().await.run_chat_with(config).await
+    }
+}
+
+impl std::fmt::Debug for ConversationBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConversationBuilder")
+            .field("has_system_prompt", &self.system_prompt.is_some())
+            .field("history_len", &self.history.len())
+            .field("exit_tool_enabled", &self.exit_tool_enabled)
+            .field("has_summarization", &self.summarization_memory.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Applies the optional context-window truncator to `history` before the LLM
+/// call. Emits a `warn!` when messages are actually dropped so operators can
+/// see truncation happening in `-v` output. Returns the history to send.
+fn fit_history_for_request(
+    cw: &Option<crate::memory::ContextWindow>,
+    history: Vec<Message>,
+) -> Vec<Message> {
+    let Some(cw) = cw else {
+        return history;
+    };
+    let before_len = history.len();
+    let fitted = cw.fit_messages(&history);
+    if fitted.len() < before_len {
+        let before_tok = cw.estimate_total_tokens(&history);
+        let after_tok = cw.estimate_total_tokens(&fitted);
+        tracing::warn!(
+            dropped_messages = before_len - fitted.len(),
+            dropped_tokens = before_tok.saturating_sub(after_tok),
+            kept_messages = fitted.len(),
+            kept_tokens = after_tok,
+            max_tokens = cw.config().max_tokens,
+            "truncated conversation history to fit context window",
+        );
+    }
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_tool_definition_has_required_fields() {
+        let def = exit_tool_definition();
+        assert_eq!(def.name, "exit_conversation");
+        assert!(def.description.contains("goodbye"));
+
+        // Check schema has farewell property
+        let props = def.input_schema.get("properties").unwrap();
+        assert!(props.get("farewell").is_some());
+
+        // Check farewell is required
+        let required = def.input_schema.get("required").unwrap();
+        let required_arr = required.as_array().unwrap();
+        assert!(required_arr.iter().any(|v| v.as_str() == Some("farewell")));
+    }
+
+    #[test]
+    fn summarization_config_defaults() {
+        let config = SummarizationConfig::default();
+        assert_eq!(config.max_summary_tokens, 256);
+        assert_eq!(config.summary_prompt, DEFAULT_SUMMARY_PROMPT);
+    }
+
+    #[test]
+    fn summary_transcript_keeps_user_and_assistant_turns() {
+        let history = vec![
+            Message::system("ignored"),
+            Message::user("I prefer dark mode"),
+            Message::assistant("Noted."),
+            Message::user("   "),
+        ];
+        assert_eq!(
+            summary_transcript(&history),
+            "user: I prefer dark mode\nassistant: Noted."
+        );
+        assert!(summary_transcript(&[]).is_empty());
+    }
+
+    #[test]
+    fn cap_summary_limits_length_on_char_boundary() {
+        assert_eq!(cap_summary("short", 10), "short");
+        assert_eq!(cap_summary("abcdefghij", 2), "abcdefgh");
+        assert_eq!(cap_summary("ééééé", 1), "éééé");
+    }
+
+    #[test]
+    fn exit_flag_atomic_operations() {
+        let flag = Arc::new(AtomicBool::new(false));
+        assert!(!flag.load(Ordering::SeqCst));
+
+        flag.store(true, Ordering::SeqCst);
+        assert!(flag.load(Ordering::SeqCst));
+
+        flag.store(false, Ordering::SeqCst);
+        assert!(!flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn chat_config_default_values() {
+        let config = ChatConfig::new();
+        assert_eq!(config.user_prompt, "You: ");
+        assert_eq!(config.assistant_prompt, "Assistant: ");
+        assert!(config.input_mapper.is_none());
+    }
+
+    #[test]
+    fn chat_config_custom_user_prompt() {
+        let config = ChatConfig::new().user_prompt(">>> ");
+        assert_eq!(config.user_prompt, ">>> ");
+    }
+
+    #[test]
+    fn chat_config_custom_assistant_prompt() {
+        let config = ChatConfig::new().assistant_prompt("AI: ");
+        assert_eq!(config.assistant_prompt, "AI: ");
+    }
+
+    #[test]
+    fn chat_config_with_input_mapper() {
+        let mut config = ChatConfig::new().map_input(|s| format!("[test] {}", s));
+
+        let mapper = config.input_mapper.as_mut().unwrap();
+        assert_eq!(mapper("hello"), "[test] hello");
+    }
+
+    #[test]
+    fn chat_config_debug_impl() {
+        let config = ChatConfig::new()
+            .user_prompt("test> ")
+            .map_input(|s| s.to_string());
+
+        let debug = format!("{:?}", config);
+        assert!(debug.contains("test> "));
+        assert!(debug.contains("has_input_mapper"));
+    }
+
+    #[test]
+    fn chat_config_chaining() {
+        let config = ChatConfig::new()
+            .user_prompt("U> ")
+            .assistant_prompt("A> ")
+            .map_input(|s| s.to_uppercase());
+
+        assert_eq!(config.user_prompt, "U> ");
+        assert_eq!(config.assistant_prompt, "A> ");
+        assert!(config.input_mapper.is_some());
+    }
+
+    #[test]
+    fn default_system_prompt_is_sensible() {
+        assert!(DEFAULT_SYSTEM_PROMPT.contains("helpful"));
+        assert!(DEFAULT_SYSTEM_PROMPT.contains("exit_conversation"));
+    }
+
+    #[test]
+    fn fit_history_none_passes_through_unchanged() {
+        let history = vec![
+            Message::user("one"),
+            Message::assistant("two"),
+            Message::user("three"),
+        ];
+        let out = fit_history_for_request(&None, history.clone());
+        assert_eq!(out.len(), history.len());
+    }
+
+    #[test]
+    fn fit_history_truncates_when_over_budget() {
+        use crate::memory::{ContextWindow, ContextWindowConfig, TruncationStrategy};
+
+        let cfg = ContextWindowConfig {
+            max_tokens: 50,
+            truncation_strategy: TruncationStrategy::KeepRecent,
+            reserved_for_response: 10,
+            tokens_per_char: 0.25,
+            importance_weight: 1.0,
+            dedup_threshold: None,
+            hybrid_search_alpha: 0.7,
+            accurate_token_count: false,
+            model_name: None,
+        };
+        let cw = Some(ContextWindow::new(cfg));
+
+        // Three fat messages, total ~150+ tokens; budget 40.
+        let history = vec![
+            Message::user("a".repeat(120)),
+            Message::assistant("b".repeat(120)),
+            Message::user("new"),
+        ];
+
+        let out = fit_history_for_request(&cw, history);
+        assert!(out.len() < 3, "expected truncation");
+        assert_eq!(
+            out.last().unwrap().content,
+            "new",
+            "newest message must survive",
+        );
+    }
+
+    #[tokio::test]
+    async fn branch_copies_history_prefix_and_records_parent() {
+        let runtime = ActonAI::builder()
+            .app_name("branch-test")
+            .ollama("test-model")
+            .launch()
+            .await
+            .unwrap();
+        let parent = runtime
+            .conversation()
+            .system("Be brief.")
+            .restore(vec![
+                Message::user("one"),
+                Message::assistant("two"),
+                Message::user("three"),
+            ])
+            .build()
+            .await;
+
+        let branch = parent.branch(2).await;
+
+        assert_eq!(branch.history(), parent.history()[..2].to_vec());
+        assert_eq!(branch.system_prompt().as_deref(), Some("Be brief."));
+        assert_eq!(branch.parent_id(), Some(&parent.id));
+        assert_eq!(branch.branch_point(), Some(2));
+        assert_eq!(parent.parent_id(), None);
+        assert_eq!(parent.len(), 3);
+
+        // Snapshots round-trip through JSON and restore the branch
+        let snapshot = branch.history_snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: ConversationSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, snapshot);
+
+        let rebuilt = runtime
+            .conversation()
+            .restore_snapshot(restored)
+            .build()
+            .await;
+        assert_eq!(rebuilt.history(), branch.history());
+        assert_eq!(rebuilt.parent_id(), Some(&parent.id));
+        assert_eq!(rebuilt.branch_point(), Some(2));
+
+        // An index past the end copies everything
+        assert_eq!(parent.branch(10).await.branch_point(), Some(3));
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn persisted_conversation_restores_by_id() {
+        use crate::memory::{InitMemoryStore, MemoryStore, PersistenceConfig};
+
+        let runtime = ActonAI::builder()
+            .app_name("persist-test")
+            .ollama("test-model")
+            .launch()
+            .await
+            .unwrap();
+        let store = MemoryStore::spawn(&mut runtime.runtime().clone()).await;
+        store
+            .send(InitMemoryStore {
+                config: PersistenceConfig::in_memory(),
+            })
+            .await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        runtime.attach_memory_store(store.clone()).unwrap();
+        assert!(runtime.attach_memory_store(store).is_err());
+
+        let conv = runtime
+            .conversation()
+            .restore(vec![Message::user("one"), Message::assistant("two")])
+            .build()
+            .await;
+        conv.persist_now().await.unwrap();
+        // Saving again stores nothing twice
+        conv.persist_now().await.unwrap();
+
+        let resumed = runtime.conversation_from_id(conv.id()).await;
+        assert_eq!(resumed.id(), conv.id());
+        assert_eq!(resumed.history(), conv.history());
+
+        let fresh = runtime.conversation().build().await;
+        assert_ne!(fresh.id(), conv.id());
+        assert!(runtime.conversation_from_id(fresh.id()).await.is_empty());
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn launch_memory_store_persists_unless_opted_out() {
+        use crate::memory::PersistenceConfig;
+
+        let runtime = ActonAI::builder()
+            .app_name("persist-launch-test")
+            .ollama("test-model")
+            .with_memory_store(PersistenceConfig::in_memory())
+            .launch()
+            .await
+            .unwrap();
+        assert!(runtime.memory_store().is_some());
+
+        let conv = runtime
+            .conversation()
+            .with_persistence()
+            .restore(vec![Message::user("one"), Message::assistant("two")])
+            .build()
+            .await;
+        conv.persist_now().await.unwrap();
+        let resumed = runtime.conversation_from_id(conv.id()).await;
+        assert_eq!(resumed.history(), conv.history());
+
+        let transient = runtime
+            .conversation()
+            .without_persistence()
+            .restore(vec![Message::user("three")])
+            .build()
+            .await;
+        transient.persist_now().await.unwrap();
+        assert!(runtime
+            .conversation_from_id(transient.id())
+            .await
+            .is_empty());
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn export_round_trips_system_prompt_and_history() {
+        let runtime = ActonAI::builder()
+            .app_name("export-test")
+            .ollama("test-model")
+            .launch()
+            .await
+            .unwrap();
+        let conv = runtime
+            .conversation()
+            .system("Be brief.")
+            .restore(vec![Message::user("Hi"), Message::assistant("Hello!")])
+            .build()
+            .await;
+
+        let markdown = conv.export(ExportFormat::Markdown);
+        assert!(markdown.starts_with("**System:**\n\nBe brief.\n\n**User:**\n\nHi"));
+
+        for format in [
+            ExportFormat::Markdown,
+            ExportFormat::Json,
+            ExportFormat::Csv,
+        ] {
+            let restored = runtime
+                .conversation()
+                .restore_from_export(format, &conv.export(format))
+                .unwrap()
+                .build()
+                .await;
+            assert_eq!(restored.system_prompt().as_deref(), Some("Be brief."));
+            assert_eq!(restored.history(), conv.history());
+        }
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn summarize_in_place_replaces_history_with_summary() {
+        use crate::prompt::test_server::{launch_against, serve_responses};
+
+        let (base_url, server) = serve_responses(vec![Ok(2)]).await;
+        let runtime = launch_against(base_url).await;
+        let conv = runtime
+            .conversation()
+            .system("Be brief.")
+            .summary_prompt("Summarize tersely.")
+            .restore(vec![
+                Message::user("one"),
+                Message::assistant("two"),
+                Message::user("three"),
+                Message::assistant("four"),
+            ])
+            .build()
+            .await;
+
+        let summary = conv.summarize_in_place().await.unwrap();
+
+        assert_eq!(summary, "tok tok");
+        assert_eq!(conv.len(), 1);
+        assert_eq!(conv.history()[0].role, MessageRole::Assistant);
+        assert_eq!(conv.history()[0].content, "tok tok");
+        assert_eq!(conv.system_prompt().as_deref(), Some("Be brief."));
+
+        let bodies = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        let system = body["messages"][0]["content"].as_str().unwrap();
+        assert!(system.starts_with("Summarize tersely."));
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn send_batch_keeps_context_and_reports_progress() {
+        use crate::prompt::test_server::{launch_against, serve_responses};
+
+        let (base_url, server) = serve_responses(vec![Ok(1), Ok(2)]).await;
+        let runtime = launch_against(base_url).await;
+        let conv = runtime.conversation().build().await;
+
+        let progress = Mutex::new(Vec::new());
+        let results = conv
+            .send_batch_with_progress(vec!["first".to_string(), "second".to_string()], |i, r| {
+                progress.lock().unwrap().push((i, r.text.clone()));
+            })
+            .await;
+
+        let texts: Vec<String> = results.into_iter().map(|r| r.unwrap().text).collect();
+        assert_eq!(texts, vec!["tok ", "tok tok "]);
+        assert_eq!(
+            progress.into_inner().unwrap(),
+            vec![(0, "tok ".to_string()), (1, "tok tok ".to_string())]
+        );
+        assert_eq!(conv.len(), 4);
+
+        let bodies = server.await.unwrap();
+        assert!(bodies[1].contains("first"));
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn recurring_injection_is_sent_but_not_recorded() {
+        use crate::prompt::test_server::{launch_against, serve_responses};
+
+        let (base_url, server) = serve_responses(vec![Ok(1), Ok(1)]).await;
+        let runtime = launch_against(base_url).await;
+        let conv = runtime.conversation().build().await;
+
+        conv.set_recurring_injection(Some("Stay on task.".to_string()));
+        conv.send("first").await.unwrap();
+        conv.set_recurring_injection(None);
+        conv.send("second").await.unwrap();
+
+        assert_eq!(conv.len(), 4);
+        assert!(conv.history().iter().all(|m| !m.is_injected));
+
+        let bodies = server.await.unwrap();
+        let first: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(first["messages"][0]["content"], "Stay on task.");
+        assert_eq!(first["messages"][1]["content"], "first");
+        assert!(!bodies[1].contains("Stay on task."));
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn send_batch_parallel_leaves_history_unchanged() {
+        use crate::prompt::test_server::{launch_against, serve_responses};
+
+        let (base_url, server) = serve_responses(vec![Ok(1), Ok(1)]).await;
+        let runtime = launch_against(base_url).await;
+        let conv = runtime
+            .conversation()
+            .restore(vec![Message::user("hi"), Message::assistant("hello")])
+            .build()
+            .await;
+
+        let results = conv
+            .send_batch_parallel(vec!["formal".to_string(), "casual".to_string()])
+            .await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(conv.len(), 2);
+
+        let bodies = server.await.unwrap();
+        for body in bodies {
+            assert!(body.contains("hello"));
+            assert_ne!(body.contains("formal"), body.contains("casual"));
+        }
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn auto_summarize_runs_before_send_at_threshold() {
+        use crate::prompt::test_server::{launch_against, serve_responses};
+
+        let (base_url, server) = serve_responses(vec![Ok(1), Ok(1)]).await;
+        let runtime = launch_against(base_url).await;
+        let conv = runtime
+            .conversation()
+            .auto_summarize_at(3)
+            .restore(vec![
+                Message::user("one"),
+                Message::assistant("two"),
+                Message::user("three"),
+            ])
+            .build()
+            .await;
+
+        conv.send("four").await.unwrap();
+
+        // Summary, then the new exchange
+        assert_eq!(conv.len(), 3);
+        assert_eq!(conv.history()[1].content, "four");
+        assert_eq!(server.await.unwrap().len(), 2);
+
+        runtime.shutdown().await.unwrap();
+    }
+}
@@ -0,0 +1,843 @@
+//! Serving conversations over WebSocket.
+//!
+//! [`ConversationWebSocketAdapter`] drives one [`Conversation`] from a
+//! WebSocket connection using a small JSON protocol:
+//!
+//! | Direction | Message |
+//! |-----------|---------|
+//! | client → server | `{"type": "message", "content": "..."}` |
+//! | client → server | `{"type": "clear"}` |
+//! | client → server | `{"type": "set_system", "prompt": "..."}` |
+//! | server → client | `{"type": "token", "text": "..."}` |
+//! | server → client | `{"type": "tool_started", "name": "..."}` |
+//! | server → client | `{"type": "tool_done", "name": "...", "result": ...}` |
+//! | server → client | `{"type": "done", "full_text": "..."}` |
+//! | server → client | `{"type": "error", "message": "..."}` |
+//!
+//! Tokens are streamed as they arrive. Tool events are not real-time: they
+//! are reported once the turn completes, in the order the tools ran, just
+//! before `done`.
+//!
+//! The WebSocket layer is a minimal server-side RFC 6455 implementation:
+//! text messages (including fragmented ones), ping/pong, and close. Binary
+//! messages are ignored.
+//!
+//! The opening handshake must be a `GET` with `Upgrade: websocket`,
+//! `Connection: Upgrade` and `Sec-WebSocket-Version: 13`. Browsers always
+//! send an `Origin` header, so connections carrying one are refused unless
+//! the origin is allowed; this stops other sites from opening a
+//! conversation with a visitor's browser. Clients that send no `Origin`
+//! (anything but a browser) are accepted.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let runtime = ActonAI::builder().ollama("qwen2.5:7b").launch().await?;
+//!
+//! let factory_runtime = runtime.clone();
+//! let server = runtime
+//!     .conversation_websocket_server(
+//!         "127.0.0.1:8080".parse()?,
+//!         vec!["http://localhost:3000".to_string()],
+//!         move || factory_runtime.conversation().system("You are a helpful assistant."),
+//!     )
+//!     .await?;
+//! server.await?;
+//! ```
+
+use super::{Conversation, ConversationBuilder, StreamToken};
+use crate::facade::ActonAI;
+use crate::stream::CollectedResponse;
+use acton_reactive::prelude::*;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// GUID appended to the client key when computing `Sec-WebSocket-Accept`.
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// Largest opening handshake accepted.
+const MAX_HANDSHAKE_LEN: usize = 8 * 1024;
+
+/// Largest client message accepted, across all fragments.
+const MAX_MESSAGE_LEN: usize = 1024 * 1024;
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xA;
+
+/// A message sent by a WebSocket client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSocketClientMessage {
+    /// A user turn
+    Message {
+        /// The user's message
+        content: String,
+    },
+    /// Clears the conversation history
+    Clear,
+    /// Replaces the system prompt
+    SetSystem {
+        /// The new system prompt
+        prompt: String,
+    },
+}
+
+/// A message sent to a WebSocket client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSocketServerMessage {
+    /// A streamed token
+    Token {
+        /// The token text
+        text: String,
+    },
+    /// A tool was called during the turn (sent after the turn completes)
+    ToolStarted {
+        /// The tool name
+        name: String,
+    },
+    /// A tool call finished (sent after the turn completes)
+    ToolDone {
+        /// The tool name
+        name: String,
+        /// The tool's result, or `{"error": "..."}` if it failed
+        result: serde_json::Value,
+    },
+    /// The turn is complete
+    Done {
+        /// The full response text
+        full_text: String,
+    },
+    /// The turn or the client message failed
+    Error {
+        /// What went wrong
+        message: String,
+    },
+}
+
+impl WebSocketServerMessage {
+    /// Returns the tool and completion events for a finished turn.
+    fn for_response(response: &CollectedResponse) -> Vec<Self> {
+        let mut messages = Vec::with_capacity(response.tool_calls.len() * 2 + 1);
+        for call in &response.tool_calls {
+            messages.push(Self::ToolStarted {
+                name: call.name.clone(),
+            });
+            messages.push(Self::ToolDone {
+                name: call.name.clone(),
+                result: match &call.result {
+                    Ok(value) => value.clone(),
+                    Err(error) => serde_json::json!({ "error": error }),
+                },
+            });
+        }
+        messages.push(Self::Done {
+            full_text: response.text.clone(),
+        });
+        messages
+    }
+}
+
+/// A server-side WebSocket connection over `S`.
+///
+/// Created by [`accept`](Self::accept) or
+/// [`accept_with_origins`](Self::accept_with_origins), which perform the
+/// opening handshake.
+#[derive(Debug)]
+pub struct WebSocketStream<S> {
+    inner: BufReader<S>,
+}
+
+impl<S> WebSocketStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    /// Completes the WebSocket opening handshake on `stream`, refusing every
+    /// request that carries an `Origin` header.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream fails or the client's request is not
+    /// a WebSocket upgrade.
+    pub async fn accept(stream: S) -> io::Result<Self> {
+        Self::accept_with_origins(stream, &[]).await
+    }
+
+    /// Completes the WebSocket opening handshake on `stream`, accepting
+    /// requests whose `Origin` is in `allowed_origins` (`"*"` allows any)
+    /// or that carry no `Origin`.
+    ///
+    /// Refused requests get a `400` (or `403` for the origin) response.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream fails, the client's request is not a
+    /// valid WebSocket upgrade, or its origin is not allowed.
+    pub async fn accept_with_origins(stream: S, allowed_origins: &[String]) -> io::Result<Self> {
+        let mut stream = BufReader::new(stream);
+        let request = read_handshake(&mut stream).await?;
+        let key = match check_upgrade(&request, allowed_origins) {
+            Ok(key) => key,
+            Err(error) => {
+                let status = if error.kind() == io::ErrorKind::PermissionDenied {
+                    "403 Forbidden"
+                } else {
+                    "400 Bad Request"
+                };
+                let response =
+                    format!("HTTP/1.1 {status}\r\nConnection: close\r\nContent-Length: 0\r\n\r\n");
+                stream.write_all(response.as_bytes()).await?;
+                stream.flush().await?;
+                return Err(error);
+            }
+        };
+
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\r\n",
+            accept_key(key)
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.flush().await?;
+
+        Ok(Self { inner: stream })
+    }
+}
+
+/// Serves a [`Conversation`] over a WebSocket connection.
+#[derive(Debug, Clone, Copy)]
+pub struct ConversationWebSocketAdapter;
+
+/// Frames queued for the writer task.
+#[derive(Debug)]
+enum Outgoing {
+    Text(String),
+    Pong(Vec<u8>),
+    Close,
+}
+
+impl Outgoing {
+    fn message(message: &WebSocketServerMessage) -> Self {
+        // Serializing these enums cannot fail
+        Self::Text(serde_json::to_string(message).unwrap_or_default())
+    }
+}
+
+/// A client message decoded from the wire.
+#[derive(Debug)]
+enum Incoming {
+    Text(String),
+    Ping(Vec<u8>),
+    Close,
+}
+
+/// Forwards streamed tokens and turn events to the writer task.
+///
+/// Routing turn events through the same mailbox as [`StreamToken`] keeps
+/// `done` behind the last token of its turn.
+#[derive(Default, Debug)]
+struct WebSocketForwarder {
+    tx: Option<mpsc::UnboundedSender<Outgoing>>,
+}
+
+/// Internal message queueing a server message behind pending tokens.
+#[derive(Clone, Debug)]
+struct ForwardServerMessage(WebSocketServerMessage);
+
+impl ConversationWebSocketAdapter {
+    /// Runs the protocol until the client closes the connection.
+    ///
+    /// Turns are handled one at a time; messages sent while a turn is in
+    /// progress are queued.
+    pub async fn serve<S>(conversation: Conversation, socket: WebSocketStream<S>)
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (reader, mut writer) = tokio::io::split(socket.inner);
+        let mut reader = MessageReader::new(reader);
+        let (out_tx, mut out_rx) = mpsc::unbounded_channel::<Outgoing>();
+        let (cmd_tx, mut cmd_rx) = mpsc::unbounded_channel::<WebSocketClientMessage>();
+
+        let writer_task = tokio::spawn(async move {
+            while let Some(outgoing) = out_rx.recv().await {
+                let (opcode, payload) = match &outgoing {
+                    Outgoing::Text(text) => (OPCODE_TEXT, text.as_bytes()),
+                    Outgoing::Pong(data) => (OPCODE_PONG, data.as_slice()),
+                    Outgoing::Close => (OPCODE_CLOSE, &[][..]),
+                };
+                if write_frame(&mut writer, opcode, payload).await.is_err()
+                    || matches!(outgoing, Outgoing::Close)
+                {
+                    break;
+                }
+            }
+            let _ = writer.shutdown().await;
+        });
+
+        let reader_out = out_tx.clone();
+        let reader_task = tokio::spawn(async move {
+            loop {
+                match reader.next().await {
+                    Ok(Incoming::Text(text)) => match serde_json::from_str(&text) {
+                        Ok(message) => {
+                            if cmd_tx.send(message).is_err() {
+                                break;
+                            }
+                        }
+                        Err(e) => {
+                            let _ = reader_out.send(Outgoing::message(
+                                &WebSocketServerMessage::Error {
+                                    message: format!("invalid message: {e}"),
+                                },
+                            ));
+                        }
+                    },
+                    Ok(Incoming::Ping(data)) => {
+                        let _ = reader_out.send(Outgoing::Pong(data));
+                    }
+                    Ok(Incoming::Close) => {
+                        let _ = reader_out.send(Outgoing::Close);
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::debug!(error = %e, "WebSocket connection ended");
+                        let _ = reader_out.send(Outgoing::Close);
+                        break;
+                    }
+                }
+            }
+        });
+
+        let mut actor_runtime = conversation.runtime.runtime().clone();
+        let mut forwarder = actor_runtime.new_actor::<WebSocketForwarder>();
+        forwarder.model.tx = Some(out_tx);
+        forwarder.mutate_on::<StreamToken>(|actor, ctx| {
+            if let Some(tx) = &actor.model.tx {
+                let _ = tx.send(Outgoing::message(&WebSocketServerMessage::Token {
+                    text: ctx.message().text.clone(),
+                }));
+            }
+            Reply::ready()
+        });
+        forwarder.mutate_on::<ForwardServerMessage>(|actor, ctx| {
+            if let Some(tx) = &actor.model.tx {
+                let _ = tx.send(Outgoing::message(&ctx.message().0));
+            }
+            Reply::ready()
+        });
+        let forwarder = forwarder.start().await;
+
+        while let Some(message) = cmd_rx.recv().await {
+            match message {
+                WebSocketClientMessage::Message { content } => {
+                    let events = match conversation.send_streaming(content, &forwarder).await {
+                        Ok(response) => WebSocketServerMessage::for_response(&response),
+                        Err(e) => vec![WebSocketServerMessage::Error {
+                            message: e.to_string(),
+                        }],
+                    };
+                    for event in events {
+                        forwarder.send(ForwardServerMessage(event)).await;
+                    }
+                }
+                WebSocketClientMessage::Clear => conversation.clear(),
+                WebSocketClientMessage::SetSystem { prompt } => {
+                    conversation.set_system_prompt(prompt);
+                }
+            }
+        }
+
+        let _ = forwarder.stop().await;
+        let _ = reader_task.await;
+        let _ = writer_task.await;
+    }
+}
+
+impl ActonAI {
+    /// Accepts WebSocket connections on `addr`, serving each with its own
+    /// conversation built from `factory`.
+    ///
+    /// Browser connections are accepted only from `allowed_origins` (for
+    /// example `"https://chat.example.com"`; `"*"` allows any origin);
+    /// clients that send no `Origin` header are always accepted. The
+    /// returned task runs until aborted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `addr` cannot be bound.
+    pub async fn conversation_websocket_server<F>(
+        &self,
+        addr: SocketAddr,
+        allowed_origins: Vec<String>,
+        factory: F,
+    ) -> io::Result<JoinHandle<()>>
+    where
+        F: Fn() -> ConversationBuilder + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind(addr).await?;
+        tracing::info!(addr = %listener.local_addr()?, "Conversation WebSocket server listening");
+        let factory = Arc::new(factory);
+        let allowed_origins: Arc<[String]> = allowed_origins.into();
+
+        Ok(tokio::spawn(async move {
+            loop {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to accept WebSocket connection");
+                        continue;
+                    }
+                };
+                let factory = Arc::clone(&factory);
+                let allowed_origins = Arc::clone(&allowed_origins);
+                tokio::spawn(async move {
+                    match WebSocketStream::accept_with_origins(stream, &allowed_origins).await {
+                        Ok(socket) => {
+                            let conversation = factory().build().await;
+                            ConversationWebSocketAdapter::serve(conversation, socket).await;
+                        }
+                        Err(e) => {
+                            tracing::warn!(peer = %peer, error = %e, "WebSocket handshake failed");
+                        }
+                    }
+                });
+            }
+        }))
+    }
+}
+
+/// Computes `Sec-WebSocket-Accept` for a client key.
+fn accept_key(key: &str) -> String {
+    let digest = ring::digest::digest(
+        &ring::digest::SHA1_FOR_LEGACY_USE_ONLY,
+        format!("{key}{ACCEPT_GUID}").as_bytes(),
+    );
+    BASE64.encode(digest.as_ref())
+}
+
+/// Reads the HTTP upgrade request, up to and including the blank line.
+async fn read_handshake<S: AsyncRead + Unpin>(stream: &mut BufReader<S>) -> io::Result<String> {
+    let mut request = Vec::new();
+    while !request.ends_with(b"\r\n\r\n") {
+        let remaining = MAX_HANDSHAKE_LEN - request.len();
+        if remaining == 0 {
+            return Err(invalid_data("handshake too large"));
+        }
+        let read = (&mut *stream)
+            .take(remaining as u64)
+            .read_until(b'\n', &mut request)
+            .await?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+    }
+    String::from_utf8(request).map_err(|_| invalid_data("handshake is not UTF-8"))
+}
+
+/// Checks that `request` is a WebSocket upgrade from an allowed origin,
+/// returning its `Sec-WebSocket-Key`.
+fn check_upgrade<'a>(request: &'a str, allowed_origins: &[String]) -> io::Result<&'a str> {
+    let request_line = request.lines().next().unwrap_or_default();
+    let mut parts = request_line.split(' ');
+    if parts.next() != Some("GET") {
+        return Err(invalid_data("handshake is not a GET request"));
+    }
+    if parts.nth(1) != Some("HTTP/1.1") {
+        return Err(invalid_data("handshake is not HTTP/1.1"));
+    }
+    if !header_has_token(request, "upgrade", "websocket") {
+        return Err(invalid_data("missing Upgrade: websocket header"));
+    }
+    if !header_has_token(request, "connection", "upgrade") {
+        return Err(invalid_data("missing Connection: Upgrade header"));
+    }
+    if header_value(request, "sec-websocket-version") != Some("13") {
+        return Err(invalid_data("unsupported Sec-WebSocket-Version"));
+    }
+    if let Some(origin) = header_value(request, "origin") {
+        let allowed = allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(origin));
+        if !allowed {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("origin '{origin}' is not allowed"),
+            ));
+        }
+    }
+    header_value(request, "sec-websocket-key")
+        .ok_or_else(|| invalid_data("missing Sec-WebSocket-Key header"))
+}
+
+/// Returns whether the comma-separated header `name` lists `token`
+/// (case-insensitive).
+fn header_has_token(request: &str, name: &str, token: &str) -> bool {
+    header_value(request, name).is_some_and(|value| {
+        value
+            .split(',')
+            .any(|item| item.trim().eq_ignore_ascii_case(token))
+    })
+}
+
+/// Returns the value of `name` (case-insensitive) from an HTTP request head.
+fn header_value<'a>(request: &'a str, name: &str) -> Option<&'a str> {
+    request.lines().skip(1).find_map(|line| {
+        let (header, value) = line.split_once(':')?;
+        header
+            .trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim())
+    })
+}
+
+/// Decodes client messages from the read half of a connection.
+///
+/// Control frames may arrive between the fragments of a message, so the
+/// partial message is kept across calls to [`next`](Self::next).
+#[derive(Debug)]
+struct MessageReader<R> {
+    reader: R,
+    /// Opcode and payload of a fragmented message in progress
+    fragmented: Option<(u8, Vec<u8>)>,
+}
+
+impl<R: AsyncRead + Unpin> MessageReader<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            fragmented: None,
+        }
+    }
+
+    /// Reads frames until a complete text message, ping, or close arrives.
+    async fn next(&mut self) -> io::Result<Incoming> {
+        let reader = &mut self.reader;
+        loop {
+            let first = reader.read_u8().await?;
+            let second = reader.read_u8().await?;
+            let fin = first & 0x80 != 0;
+            let opcode = first & 0x0F;
+
+            let len = match second & 0x7F {
+                126 => u64::from(reader.read_u16().await?),
+                127 => reader.read_u64().await?,
+                len => u64::from(len),
+            };
+            let len = usize::try_from(len)
+                .ok()
+                .filter(|len| *len <= MAX_MESSAGE_LEN)
+                .ok_or_else(|| invalid_data("frame too large"))?;
+
+            let mask = if second & 0x80 != 0 {
+                let mut mask = [0u8; 4];
+                reader.read_exact(&mut mask).await?;
+                Some(mask)
+            } else {
+                None
+            };
+            let mut payload = vec![0u8; len];
+            reader.read_exact(&mut payload).await?;
+            if let Some(mask) = mask {
+                for (i, byte) in payload.iter_mut().enumerate() {
+                    *byte ^= mask[i % 4];
+                }
+            }
+
+            let (message_opcode, data) = match opcode {
+                OPCODE_PING => return Ok(Incoming::Ping(payload)),
+                OPCODE_PONG => continue,
+                OPCODE_CLOSE => return Ok(Incoming::Close),
+                OPCODE_TEXT | OPCODE_BINARY => {
+                    if fin {
+                        (opcode, payload)
+                    } else {
+                        self.fragmented = Some((opcode, payload));
+                        continue;
+                    }
+                }
+                OPCODE_CONTINUATION => {
+                    let (first_opcode, mut data) = self
+                        .fragmented
+                        .take()
+                        .ok_or_else(|| invalid_data("continuation without a message"))?;
+                    if data.len() + payload.len() > MAX_MESSAGE_LEN {
+                        return Err(invalid_data("message too large"));
+                    }
+                    data.extend_from_slice(&payload);
+                    if fin {
+                        (first_opcode, data)
+                    } else {
+                        self.fragmented = Some((first_opcode, data));
+                        continue;
+                    }
+                }
+                other => return Err(invalid_data(&format!("unknown opcode {other:#x}"))),
+            };
+
+            if message_opcode == OPCODE_TEXT {
+                return String::from_utf8(data)
+                    .map(Incoming::Text)
+                    .map_err(|_| invalid_data("text message is not UTF-8"));
+            }
+            // Binary messages are not part of the protocol
+        }
+    }
+}
+
+/// Writes a single unmasked frame.
+async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    opcode: u8,
+    payload: &[u8],
+) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= usize::from(u16::MAX) => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    writer.write_all(&frame).await?;
+    writer.flush().await
+}
+
+fn invalid_data(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::StopReason;
+    use crate::stream::ExecutedToolCall;
+    use serde_json::json;
+
+    /// Encodes a masked client frame.
+    fn client_frame(fin: bool, opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [1u8, 2, 3, 4];
+        let mut frame = vec![if fin { 0x80 | opcode } else { opcode }];
+        assert!(payload.len() < 126);
+        frame.push(0x80 | payload.len() as u8);
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        frame
+    }
+
+    #[test]
+    fn accept_key_matches_rfc_example() {
+        assert_eq!(
+            accept_key("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn header_lookup_is_case_insensitive() {
+        let request = "GET / HTTP/1.1\r\nHost: x\r\nsec-websocket-KEY:  abc \r\n\r\n";
+        assert_eq!(header_value(request, "Sec-WebSocket-Key"), Some("abc"));
+        assert_eq!(header_value(request, "Upgrade"), None);
+    }
+
+    #[test]
+    fn protocol_messages_use_type_tags() {
+        let parsed: WebSocketClientMessage =
+            serde_json::from_str(r#"{"type": "set_system", "prompt": "Be brief."}"#).unwrap();
+        assert_eq!(
+            parsed,
+            WebSocketClientMessage::SetSystem {
+                prompt: "Be brief.".to_string()
+            }
+        );
+
+        let token = serde_json::to_value(WebSocketServerMessage::Token {
+            text: "Hi".to_string(),
+        })
+        .unwrap();
+        assert_eq!(token, json!({"type": "token", "text": "Hi"}));
+    }
+
+    #[test]
+    fn response_events_report_tools_then_done() {
+        let response = CollectedResponse::with_tool_calls(
+            "It is 4.".to_string(),
+            StopReason::EndTurn,
+            3,
+            vec![
+                ExecutedToolCall::success("1", "calculate", json!({}), json!({"result": 4})),
+                ExecutedToolCall::error("2", "bash", json!({}), "denied"),
+            ],
+        );
+
+        let events = WebSocketServerMessage::for_response(&response);
+        assert_eq!(events.len(), 5);
+        assert_eq!(
+            events[1],
+            WebSocketServerMessage::ToolDone {
+                name: "calculate".to_string(),
+                result: json!({"result": 4}),
+            }
+        );
+        assert_eq!(
+            events[3],
+            WebSocketServerMessage::ToolDone {
+                name: "bash".to_string(),
+                result: json!({"error": "denied"}),
+            }
+        );
+        assert_eq!(
+            events[4],
+            WebSocketServerMessage::Done {
+                full_text: "It is 4.".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn handshake_then_fragmented_text_and_ping() {
+        let (mut client, server) = tokio::io::duplex(4096);
+        client
+            .write_all(
+                b"GET /chat HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\n\
+                  Connection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+                  Sec-WebSocket-Version: 13\r\n\r\n",
+            )
+            .await
+            .unwrap();
+
+        let socket = WebSocketStream::accept(server).await.unwrap();
+        let mut reader = MessageReader::new(socket.inner);
+        let mut response = vec![0u8; 129];
+        client.read_exact(&mut response).await.unwrap();
+        let response = String::from_utf8(response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 101"));
+        assert!(response.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+
+        client
+            .write_all(&client_frame(false, OPCODE_TEXT, b"hel"))
+            .await
+            .unwrap();
+        client
+            .write_all(&client_frame(true, OPCODE_PING, b"p"))
+            .await
+            .unwrap();
+        client
+            .write_all(&client_frame(true, OPCODE_CONTINUATION, b"lo"))
+            .await
+            .unwrap();
+        client
+            .write_all(&client_frame(true, OPCODE_CLOSE, b""))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            reader.next().await.unwrap(),
+            Incoming::Ping(data) if data == b"p"
+        ));
+        assert!(matches!(
+            reader.next().await.unwrap(),
+            Incoming::Text(text) if text == "hello"
+        ));
+        assert!(matches!(reader.next().await.unwrap(), Incoming::Close));
+    }
+
+    #[tokio::test]
+    async fn server_frames_are_unmasked() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        write_frame(&mut server, OPCODE_TEXT, b"ok").await.unwrap();
+
+        let mut frame = [0u8; 4];
+        client.read_exact(&mut frame).await.unwrap();
+        assert_eq!(frame, [0x81, 2, b'o', b'k']);
+    }
+
+    async fn handshake_error(request: &[u8], allowed_origins: &[String]) -> (io::Error, String) {
+        let (mut client, server) = tokio::io::duplex(16 * 1024);
+        client.write_all(request).await.unwrap();
+        let err = WebSocketStream::accept_with_origins(server, allowed_origins)
+            .await
+            .unwrap_err();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        (err, response)
+    }
+
+    const UPGRADE_HEADERS: &str = "Host: localhost\r\nUpgrade: websocket\r\n\
+        Connection: keep-alive, Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\
+        Sec-WebSocket-Version: 13\r\n";
+
+    #[tokio::test]
+    async fn rejects_non_get_handshake() {
+        let request = format!("POST /chat HTTP/1.1\r\n{UPGRADE_HEADERS}\r\n");
+        let (err, response) = handshake_error(request.as_bytes(), &[]).await;
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(response.starts_with("HTTP/1.1 400"));
+    }
+
+    #[tokio::test]
+    async fn rejects_handshake_without_upgrade_header() {
+        let request = b"GET / HTTP/1.1\r\nHost: x\r\nConnection: Upgrade\r\n\
+            Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n";
+        let (err, _) = handshake_error(request, &[]).await;
+        assert!(err.to_string().contains("Upgrade"));
+    }
+
+    #[tokio::test]
+    async fn origin_must_be_allowed() {
+        let request =
+            format!("GET / HTTP/1.1\r\n{UPGRADE_HEADERS}Origin: https://evil.example\r\n\r\n");
+        let allowed = vec!["https://chat.example".to_string()];
+        let (err, response) = handshake_error(request.as_bytes(), &allowed).await;
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(response.starts_with("HTTP/1.1 403"));
+
+        let request =
+            format!("GET / HTTP/1.1\r\n{UPGRADE_HEADERS}Origin: https://chat.example\r\n\r\n");
+        let (mut client, server) = tokio::io::duplex(4096);
+        client.write_all(request.as_bytes()).await.unwrap();
+        assert!(WebSocketStream::accept_with_origins(server, &allowed)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_handshake() {
+        let request = format!(
+            "GET / HTTP/1.1\r\nX-Padding: {}\r\n\r\n",
+            "a".repeat(MAX_HANDSHAKE_LEN)
+        );
+        let (mut client, server) = tokio::io::duplex(2 * MAX_HANDSHAKE_LEN);
+        client.write_all(request.as_bytes()).await.unwrap();
+        let err = WebSocketStream::accept(server).await.unwrap_err();
+        assert!(err.to_string().contains("too large"));
+    }
+
+    #[tokio::test]
+    async fn rejects_request_without_key() {
+        let request = b"GET / HTTP/1.1\r\nHost: x\r\nUpgrade: websocket\r\n\
+            Connection: Upgrade\r\nSec-WebSocket-Version: 13\r\n\r\n";
+        let (err, _) = handshake_error(request, &[]).await;
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("Sec-WebSocket-Key"));
+    }
+}
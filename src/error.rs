@@ -3,10 +3,13 @@
 //! This module contains all error types used throughout the framework.
 //! Each error type implements Display, Debug, Clone, PartialEq, Eq, and std::error::Error.
 //!
-//! No external error crates (anyhow, thiserror, eyre) are used.
+//! No external error crates (anyhow, thiserror, eyre) are used. The optional
+//! `anyhow` feature only adds `ToolError::from_anyhow`.
 
-use crate::types::AgentId;
+use crate::types::{AgentId, CorrelationId, ToolName};
+use chrono::{DateTime, Utc};
 use std::fmt;
+use std::time::Duration;
 
 /// Errors that can occur in the Kernel actor.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -470,6 +473,8 @@ impl std::error::Error for MultiAgentError {}
 pub struct ActonAIError {
     /// The specific error that occurred
     pub kind: ActonAIErrorKind,
+    /// Operations the error propagated through, innermost first
+    contexts: Vec<ErrorContext>,
 }
 
 /// Specific high-level API error types.
@@ -504,13 +509,23 @@ pub enum ActonAIErrorKind {
     },
     /// Runtime was shut down
     RuntimeShutdown,
+    /// The prompt or one of its rounds ran out of time
+    Timeout {
+        /// How long the operation ran before it was abandoned
+        elapsed: Duration,
+    },
+    /// The prompt was cancelled through its cancellation token
+    Cancelled,
 }
 
 impl ActonAIError {
     /// Creates a new ActonAIError with the given kind.
     #[must_use]
     pub fn new(kind: ActonAIErrorKind) -> Self {
-        Self { kind }
+        Self {
+            kind,
+            contexts: Vec::new(),
+        }
     }
 
     /// Creates a configuration error.
@@ -560,6 +575,18 @@ impl ActonAIError {
         Self::new(ActonAIErrorKind::RuntimeShutdown)
     }
 
+    /// Creates a timeout error.
+    #[must_use]
+    pub fn timeout(elapsed: Duration) -> Self {
+        Self::new(ActonAIErrorKind::Timeout { elapsed })
+    }
+
+    /// Creates a cancelled error.
+    #[must_use]
+    pub fn cancelled() -> Self {
+        Self::new(ActonAIErrorKind::Cancelled)
+    }
+
     /// Returns true if this error indicates a configuration problem.
     #[must_use]
     pub fn is_configuration(&self) -> bool {
@@ -571,6 +598,44 @@ impl ActonAIError {
     pub fn is_runtime_shutdown(&self) -> bool {
         matches!(self.kind, ActonAIErrorKind::RuntimeShutdown)
     }
+
+    /// Returns true if this error indicates a timeout.
+    #[must_use]
+    pub fn is_timeout(&self) -> bool {
+        matches!(self.kind, ActonAIErrorKind::Timeout { .. })
+    }
+
+    /// Returns true if this error indicates the prompt was cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.kind, ActonAIErrorKind::Cancelled)
+    }
+
+    /// Records that the error propagated through `operation`.
+    #[must_use]
+    pub fn with_context(self, operation: &str) -> Self {
+        self.with_error_context(ErrorContext::new(operation))
+    }
+
+    /// Records that the error propagated through `operation` on behalf of
+    /// `agent_id`.
+    #[must_use]
+    pub fn with_agent_context(self, agent_id: &AgentId, operation: &str) -> Self {
+        self.with_error_context(ErrorContext::new(operation).with_agent_id(agent_id.clone()))
+    }
+
+    /// Appends `context` to the breadcrumb chain.
+    #[must_use]
+    pub fn with_error_context(mut self, context: ErrorContext) -> Self {
+        self.contexts.push(context);
+        self
+    }
+
+    /// Returns the operations the error propagated through, innermost first.
+    #[must_use]
+    pub fn contexts(&self) -> &[ErrorContext] {
+        &self.contexts
+    }
 }
 
 impl fmt::Display for ActonAIError {
@@ -594,11 +659,144 @@ impl fmt::Display for ActonAIError {
             ActonAIErrorKind::RuntimeShutdown => {
                 write!(f, "runtime has been shut down")
             }
+            ActonAIErrorKind::Timeout { elapsed } => {
+                write!(
+                    f,
+                    "timed out after {}ms; raise the timeout or simplify the prompt",
+                    elapsed.as_millis()
+                )
+            }
+            ActonAIErrorKind::Cancelled => {
+                write!(f, "prompt was cancelled")
+            }
         }
     }
 }
 
-impl std::error::Error for ActonAIError {}
+impl std::error::Error for ActonAIError {
+    /// Returns the outermost [`ErrorContext`], if any.
+    ///
+    /// Use [`ActonAIError::contexts`] for the whole chain.
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.contexts
+            .last()
+            .map(|context| context as &(dyn std::error::Error + 'static))
+    }
+}
+
+/// One breadcrumb in an [`ActonAIError`]'s context chain.
+///
+/// Records the operation that was in progress when the error passed
+/// through, with whatever identifiers were at hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// What was being done, e.g. "loading memories"
+    pub operation: String,
+    /// The agent the operation ran for
+    pub agent_id: Option<AgentId>,
+    /// The LLM request the operation belonged to
+    pub correlation_id: Option<CorrelationId>,
+    /// The tool being executed
+    pub tool_name: Option<ToolName>,
+    /// When the context was recorded
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ErrorContext {
+    /// Creates a context for `operation`, timestamped now.
+    #[must_use]
+    pub fn new(operation: impl Into<String>) -> Self {
+        Self {
+            operation: operation.into(),
+            agent_id: None,
+            correlation_id: None,
+            tool_name: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Sets the agent ID.
+    #[must_use]
+    pub fn with_agent_id(mut self, agent_id: AgentId) -> Self {
+        self.agent_id = Some(agent_id);
+        self
+    }
+
+    /// Sets the correlation ID.
+    #[must_use]
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Sets the tool name.
+    #[must_use]
+    pub fn with_tool_name(mut self, tool_name: ToolName) -> Self {
+        self.tool_name = Some(tool_name);
+        self
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "while {}", self.operation)?;
+        if let Some(agent_id) = &self.agent_id {
+            write!(f, " (agent '{}')", agent_id)?;
+        }
+        if let Some(correlation_id) = &self.correlation_id {
+            write!(f, " (correlation '{}')", correlation_id)?;
+        }
+        if let Some(tool_name) = &self.tool_name {
+            write!(f, " (tool '{}')", tool_name)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ErrorContext {}
+
+/// Adds context to the error of a `Result`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use acton_ai::prelude::*;
+///
+/// let memories = load_memories().await.context("loading memories")?;
+/// ```
+pub trait ResultExt<T> {
+    /// Records that the error propagated through `operation`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the original error with `operation` appended to its contexts.
+    fn context(self, operation: &str) -> Result<T, ActonAIError>;
+
+    /// Like [`context`](Self::context), building the context only on error.
+    ///
+    /// # Errors
+    ///
+    /// Returns the original error with the built context appended.
+    fn with_error_context<F>(self, context: F) -> Result<T, ActonAIError>
+    where
+        F: FnOnce() -> ErrorContext;
+}
+
+impl<T, E> ResultExt<T> for Result<T, E>
+where
+    E: Into<ActonAIError>,
+{
+    fn context(self, operation: &str) -> Result<T, ActonAIError> {
+        self.map_err(|e| e.into().with_context(operation))
+    }
+
+    fn with_error_context<F>(self, context: F) -> Result<T, ActonAIError>
+    where
+        F: FnOnce() -> ErrorContext,
+    {
+        self.map_err(|e| e.into().with_error_context(context()))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -709,6 +907,39 @@ mod tests {
     }
 
     // ActonAIError tests
+    #[test]
+    fn acton_ai_error_context_chain() {
+        let agent_id = AgentId::new();
+        let result: Result<(), ActonAIError> = Err(ActonAIError::provider_error("rate limited"));
+        let error = result
+            .context("streaming LLM response")
+            .map_err(|e| e.with_agent_context(&agent_id, "handling user prompt"))
+            .unwrap_err();
+
+        let operations: Vec<&str> = error
+            .contexts()
+            .iter()
+            .map(|c| c.operation.as_str())
+            .collect();
+        assert_eq!(
+            operations,
+            ["streaming LLM response", "handling user prompt"]
+        );
+        assert_eq!(error.contexts()[1].agent_id, Some(agent_id.clone()));
+
+        let source = std::error::Error::source(&error).unwrap().to_string();
+        assert!(source.contains("handling user prompt"));
+        assert!(source.contains(&agent_id.to_string()));
+        assert!(error.to_string().contains("rate limited"));
+    }
+
+    #[test]
+    fn acton_ai_error_without_context_has_no_source() {
+        let error = ActonAIError::runtime_shutdown();
+        assert!(error.contexts().is_empty());
+        assert!(std::error::Error::source(&error).is_none());
+    }
+
     #[test]
     fn acton_ai_error_configuration_display() {
         let error = ActonAIError::configuration("app_name", "cannot be empty");
@@ -762,6 +993,24 @@ mod tests {
         assert!(message.contains("shut down"));
     }
 
+    #[test]
+    fn acton_ai_error_timeout_display() {
+        let error = ActonAIError::timeout(Duration::from_millis(1500));
+
+        assert!(error.is_timeout());
+        assert!(error.to_string().contains("1500ms"));
+        assert!(!ActonAIError::runtime_shutdown().is_timeout());
+    }
+
+    #[test]
+    fn acton_ai_error_cancelled_display() {
+        let error = ActonAIError::cancelled();
+
+        assert!(error.is_cancelled());
+        assert!(error.to_string().contains("cancelled"));
+        assert!(!error.is_timeout());
+    }
+
     #[test]
     fn acton_ai_error_is_configuration() {
         let error = ActonAIError::configuration("field", "reason");
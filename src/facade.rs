@@ -71,66 +71,47 @@
 //! }
 //! ```
 
+use crate::agent::UseMemoryStore;
 use crate::config::{self, ActonAIConfig, SandboxFileConfig};
-use crate::conversation::ConversationBuilder;
+use crate::conversation::{ChatConfig, Conversation, ConversationBuilder};
 use crate::error::{ActonAIError, ActonAIErrorKind};
-use crate::kernel::{Kernel, KernelConfig};
-use crate::llm::{LLMProvider, ProviderConfig};
-use crate::messages::Message;
-use crate::prompt::PromptBuilder;
+use crate::kernel::{
+    Kernel, KernelConfig, LogLevel, LoggingConfig, DEFAULT_SHUTDOWN_DRAIN_TIMEOUT,
+};
+use crate::llm::{
+    LLMProvider, ProviderConfig, RateLimitConfig, RetryPolicy, SamplingParams, SetFallbackProvider,
+    UpdateRateLimit, UpdateSamplingParams,
+};
+use crate::memory::{
+    EmbeddingCache, EmbeddingCacheStats, EmbeddingProvider, MemoryStore, PersistenceConfig,
+};
+use crate::messages::{KernelShuttingDown, Message};
+use crate::prompt::{PromptBuilder, PromptTemplate, TemplateLibrary};
 use crate::tools::builtins::BuiltinTools;
+use crate::tools::rate_limit::ToolRateLimit;
 use crate::tools::sandbox::{ProcessSandboxConfig, ProcessSandboxFactory, SandboxFactory};
+use crate::types::ConversationId;
 use acton_reactive::prelude::*;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::Notify;
 
 /// The default provider name used when registering single providers.
 pub const DEFAULT_PROVIDER_NAME: &str = "default";
 
-/// High-level facade for interacting with ActonAI.
-///
-/// `ActonAI` encapsulates the runtime, kernel, and LLM providers, providing
-/// a simplified API for common operations. It handles all the actor setup
-/// and subscription management automatically.
-///
-/// # Single Provider Example
-///
-/// ```rust,ignore
-/// let runtime = ActonAI::builder()
-///     .app_name("my-app")
-///     .ollama("llama3.2")
-///     .launch()
-///     .await?;
-///
-/// runtime
-///     .prompt("Hello!")
-///     .on_token(|t| print!("{t}"))
-///     .collect()
-///     .await?;
-/// ```
-///
-/// # Multi-Provider Example
-///
-/// ```rust,ignore
-/// let runtime = ActonAI::builder()
-///     .app_name("my-app")
-///     .provider_named("claude", ProviderConfig::anthropic("sk-..."))
-///     .provider_named("local", ProviderConfig::ollama("qwen2.5:7b"))
-///     .default_provider("local")
-///     .launch()
-///     .await?;
-///
-/// // Use specific provider
-/// runtime.prompt("Complex task").provider("claude").collect().await?;
-/// ```
 /// Internal state shared via `Arc`.
 pub(crate) struct ActonAIInner {
     /// The underlying actor runtime
     pub(crate) runtime: ActorRuntime,
     /// Named LLM provider handles
     pub(crate) providers: HashMap<String, ActorHandle>,
+    /// Default prompt retry policies, by provider name
+    pub(crate) retry_policies: HashMap<String, RetryPolicy>,
+    /// Default prompt time limits, by provider name
+    pub(crate) prompt_timeouts: HashMap<String, Duration>,
     /// The name of the default provider
     pub(crate) default_provider: String,
     /// Built-in tools (if enabled)
@@ -163,8 +144,109 @@ pub(crate) struct ActonAIInner {
     pub(crate) context_window: Option<crate::memory::ContextWindow>,
     /// Whether the runtime has been shut down
     pub(crate) is_shutdown: AtomicBool,
+    /// The kernel actor spawned at launch
+    pub(crate) kernel: ActorHandle,
+    /// How long [`ActonAI::shutdown`] waits for in-flight requests.
+    pub(crate) shutdown_drain_timeout: Duration,
+    /// Embedding provider for memory features (if configured)
+    pub(crate) embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
+    /// The cache wrapping `embedding_provider`, kept for its stats
+    pub(crate) embedding_cache: Option<Arc<EmbeddingCache>>,
+    /// Named prompt templates
+    pub(crate) templates: TemplateLibrary,
+    /// Number of prompt requests currently executing
+    pub(crate) in_flight: AtomicUsize,
+    /// Notified whenever `in_flight` drops to zero
+    pub(crate) drained: Notify,
+    /// Memory store used to persist conversations (if attached)
+    pub(crate) memory_store: OnceLock<ActorHandle>,
+    /// Other actors spawned on this runtime, counted at shutdown
+    pub(crate) tracked_actors: Mutex<Vec<ActorHandle>>,
 }
 
+/// Outcome of [`ActonAI::shutdown_timeout`] and
+/// [`ActonAI::shutdown_gracefully`].
+#[derive(Debug)]
+pub enum ShutdownResult {
+    /// All actors stopped within the timeout.
+    Clean,
+    /// The timeout elapsed before every actor stopped.
+    Timeout {
+        /// Actors still running: the kernel, providers, memory store,
+        /// conversations, and any added with
+        /// [`ActonAI::track_actor`]
+        actors_still_running: usize,
+    },
+    /// The runtime reported an error while stopping.
+    Error(ActonAIError),
+}
+
+impl ShutdownResult {
+    /// Returns true if every actor stopped within the timeout.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        matches!(self, Self::Clean)
+    }
+}
+
+/// Marks one request as in flight until dropped.
+///
+/// Held by [`PromptBuilder`] for the duration of a request so shutdown can
+/// drain outstanding work.
+pub(crate) struct InFlightRequest {
+    inner: Arc<ActonAIInner>,
+}
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        if self.inner.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.inner.drained.notify_waiters();
+        }
+    }
+}
+
+/// High-level facade for interacting with ActonAI.
+///
+/// `ActonAI` encapsulates the runtime, kernel, and LLM providers, providing
+/// a simplified API for common operations. It handles all the actor setup
+/// and subscription management automatically.
+///
+/// # Single Provider Example
+///
+/// ```rust,ignore
+/// let runtime = ActonAI::builder()
+///     .app_name("my-app")
+///     .ollama("llama3.2")
+///     .launch()
+///     .await?;
+///
+/// runtime
+///     .prompt("Hello!")
+///     .on_token(|t| print!("{t}"))
+///     .collect()
+///     .await?;
+/// ```
+///
+/// # Multi-Provider Example
+///
+/// ```rust,ignore
+/// let runtime = ActonAI::builder()
+///     .app_name("my-app")
+///     .provider_named("claude", ProviderConfig::anthropic("sk-..."))
+///     .provider_named("local", ProviderConfig::ollama("qwen2.5:7b"))
+///     .default_provider("local")
+///     .launch()
+///     .await?;
+///
+/// // Use specific provider
+/// runtime.prompt("Complex task").provider("claude").collect().await?;
+/// ```
+///
+/// # Sharing
+///
+/// `ActonAI` is a cheap handle to shared state, so clones can be handed to
+/// async tasks or stored in a web server's app state. Shut a shared runtime
+/// down with [`shutdown_shared`](Self::shutdown_shared).
 pub struct ActonAI {
     pub(crate) inner: Arc<ActonAIInner>,
 }
@@ -240,6 +322,42 @@ impl ActonAI {
         builder
     }
 
+    /// Creates a prompt builder whose message is `template` rendered with
+    /// `vars`.
+    ///
+    /// Otherwise identical to [`prompt`](Self::prompt).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let review = runtime.templates().get("code_review").expect("template");
+    /// let vars = HashMap::from([("language", "Rust"), ("code", code.as_str())]);
+    ///
+    /// runtime.prompt_from_template(review, &vars).collect().await?;
+    /// ```
+    #[must_use]
+    pub fn prompt_from_template(
+        &self,
+        template: &PromptTemplate,
+        vars: &HashMap<&str, &str>,
+    ) -> PromptBuilder {
+        let mut builder = PromptBuilder::from_template(self.clone(), template, vars);
+        if self.inner.auto_builtins && self.inner.builtins.is_some() {
+            builder = builder.use_builtins();
+        }
+        builder = self.inject_skill_tools(builder);
+        builder
+    }
+
+    /// Returns the prompt templates available to this runtime.
+    ///
+    /// Holds the templates in [`TemplateLibrary::default_dir`], if any,
+    /// plus those added with [`ActonAIBuilder::with_templates`].
+    #[must_use]
+    pub fn templates(&self) -> &TemplateLibrary {
+        &self.inner.templates
+    }
+
     /// Returns a reference to the underlying actor runtime.
     ///
     /// This provides an escape hatch for advanced use cases that need
@@ -294,6 +412,87 @@ impl ActonAI {
         self.inner.providers.get(name).cloned()
     }
 
+    /// Replaces the default sampling parameters of the named provider.
+    ///
+    /// Takes effect for prompts sent afterwards, without restarting the
+    /// provider. Per-prompt sampling (such as
+    /// [`PromptBuilder::sampling`](crate::prompt::PromptBuilder::sampling))
+    /// still overrides the new defaults field by field.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if no provider has the given name.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// runtime
+    ///     .set_provider_sampling("default", SamplingParams::new().with_temperature(0.2))
+    ///     .await?;
+    /// ```
+    pub async fn set_provider_sampling(
+        &self,
+        provider_name: &str,
+        params: SamplingParams,
+    ) -> Result<(), ActonAIError> {
+        let handle = self.require_provider(provider_name)?;
+        handle.send(UpdateSamplingParams { params }).await;
+        Ok(())
+    }
+
+    /// Replaces the rate limit of the named provider.
+    ///
+    /// Takes effect for requests the provider handles afterwards, without
+    /// restarting it.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if no provider has the given name.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// runtime
+    ///     .update_provider_rate_limit("claude", RateLimitConfig::new(40, 50_000))
+    ///     .await?;
+    /// ```
+    pub async fn update_provider_rate_limit(
+        &self,
+        provider_name: &str,
+        config: RateLimitConfig,
+    ) -> Result<(), ActonAIError> {
+        let handle = self.require_provider(provider_name)?;
+        handle.send(UpdateRateLimit { config }).await;
+        Ok(())
+    }
+
+    /// Returns the handle of the named provider, or a configuration error
+    /// listing the available providers.
+    fn require_provider(&self, provider_name: &str) -> Result<ActorHandle, ActonAIError> {
+        self.provider_handle_named(provider_name).ok_or_else(|| {
+            ActonAIError::configuration(
+                "provider",
+                format!(
+                    "provider '{}' not found; available: {}",
+                    provider_name,
+                    self.provider_names().collect::<Vec<_>>().join(", ")
+                ),
+            )
+        })
+    }
+
+    /// Returns the default prompt retry policy of the named provider, if
+    /// it has one.
+    pub(crate) fn provider_retry_policy(&self, name: &str) -> Option<RetryPolicy> {
+        self.inner.retry_policies.get(name).copied()
+    }
+
+    /// Returns the default prompt time limit of the named provider, if it
+    /// has one.
+    pub(crate) fn provider_prompt_timeout(&self, name: &str) -> Option<Duration> {
+        self.inner.prompt_timeouts.get(name).copied()
+    }
+
     /// Returns the name of the default provider.
     #[must_use]
     pub fn default_provider_name(&self) -> &str {
@@ -376,6 +575,54 @@ impl ActonAI {
         self.inner.sandbox_factory.as_ref()
     }
 
+    /// Returns the embedding provider, if configured.
+    ///
+    /// Set with [`with_embedding_provider`](ActonAIBuilder::with_embedding_provider);
+    /// wrapped in an [`EmbeddingCache`] when
+    /// [`with_embedding_cache`](ActonAIBuilder::with_embedding_cache) was used.
+    #[must_use]
+    pub fn embedding_provider(&self) -> Option<&Arc<dyn EmbeddingProvider>> {
+        self.inner.embedding_provider.as_ref()
+    }
+
+    /// Returns the memory store conversations are persisted to, if one was
+    /// set up with
+    /// [`with_memory_store`](ActonAIBuilder::with_memory_store) or
+    /// [attached](Self::attach_memory_store).
+    #[must_use]
+    pub fn memory_store(&self) -> Option<&ActorHandle> {
+        self.inner.memory_store.get()
+    }
+
+    /// Attaches an initialized [`MemoryStore`](crate::memory::MemoryStore)
+    /// for conversations to persist to.
+    ///
+    /// Spawn the store on [`runtime`](Self::runtime) and send it
+    /// [`InitMemoryStore`](crate::memory::InitMemoryStore) first.
+    /// Conversations built afterwards are created in the store and save each
+    /// exchange to it; resume one later with
+    /// [`conversation_from_id`](Self::conversation_from_id). Unlike
+    /// [`with_memory_store`](ActonAIBuilder::with_memory_store), the store
+    /// is not handed to the kernel's memory-enabled agents.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if a memory store is already attached.
+    pub fn attach_memory_store(&self, store: ActorHandle) -> Result<(), ActonAIError> {
+        self.inner.memory_store.set(store).map_err(|_| {
+            ActonAIError::configuration("memory_store", "a memory store is already attached")
+        })
+    }
+
+    /// Returns the embedding cache's counters, if a cache is configured.
+    #[must_use]
+    pub fn embedding_cache_stats(&self) -> Option<EmbeddingCacheStats> {
+        self.inner
+            .embedding_cache
+            .as_ref()
+            .map(|cache| cache.stats())
+    }
+
     /// Returns whether built-in tools are enabled.
     #[must_use]
     pub fn has_builtins(&self) -> bool {
@@ -432,7 +679,8 @@ impl ActonAI {
     /// Registers the `list_skills` / `activate_skill` tools on `builder` when
     /// a skill registry is configured. No-op otherwise.
     ///
-    /// Called from both [`prompt`](Self::prompt) and
+    /// Called from [`prompt`](Self::prompt),
+    /// [`prompt_from_template`](Self::prompt_from_template) and
     /// [`continue_with`](Self::continue_with) so every `PromptBuilder` the
     /// facade hands out — including the ones [`Conversation`] rebuilds per
     /// turn — has the skill tools available without per-call wiring.
@@ -485,15 +733,91 @@ impl ActonAI {
         ConversationBuilder::new(self.clone())
     }
 
+    /// Builds a conversation restored from the attached memory store.
+    ///
+    /// Shorthand for `conversation().with_id(id).build()`; see
+    /// [`ConversationBuilder::with_id`]. Without an
+    /// [attached store](Self::attach_memory_store) the conversation starts
+    /// empty and is not persisted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let conv = runtime.conversation_from_id(saved_id).await;
+    /// let response = conv.send("Where were we?").await?;
+    /// ```
+    pub async fn conversation_from_id(&self, id: ConversationId) -> Conversation {
+        self.conversation().with_id(id).build().await
+    }
+
+    /// Runs a terminal chat loop in a new conversation.
+    ///
+    /// Uses `system_prompt`, or [`DEFAULT_SYSTEM_PROMPT`](crate::conversation::DEFAULT_SYSTEM_PROMPT)
+    /// when `None`, and enables the exit tool. See
+    /// [`Conversation::run_chat`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an LLM request fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// ActonAI::builder()
+    ///     .ollama("qwen2.5")
+    ///     .launch()
+    ///     .await?
+    ///     .run_chat(None)
+    ///     .await?;
+    /// ```
+    pub async fn run_chat(&self, system_prompt: Option<&str>) -> Result<(), ActonAIError> {
+        self.run_chat_with(system_prompt, ChatConfig::default()).await
+    }
+
+    /// Like [`run_chat`](Self::run_chat), with prompts, input transformation,
+    /// and token handling customized by `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an LLM request fails.
+    pub async fn run_chat_with(
+        &self,
+        system_prompt: Option<&str>,
+        config: ChatConfig,
+    ) -> Result<(), ActonAIError> {
+        let mut builder = self.conversation().with_exit_tool();
+        if let Some(prompt) = system_prompt {
+            builder = builder.system(prompt);
+        }
+        builder.run_chat_with(config).await
+    }
+
+    /// Returns the number of prompt requests currently executing.
+    #[must_use]
+    pub fn in_flight_requests(&self) -> usize {
+        self.inner.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Registers a request as in flight until the returned guard is dropped.
+    pub(crate) fn begin_request(&self) -> InFlightRequest {
+        self.inner.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlightRequest {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+
     /// Shuts down the runtime gracefully.
     ///
-    /// This stops all actors and releases resources.
+    /// New requests are rejected immediately. In-flight requests get up to
+    /// the kernel's [`shutdown_drain_timeout`](KernelConfig::shutdown_drain_timeout)
+    /// to finish, then all actors are stopped and resources released.
     ///
     /// # Errors
     ///
     /// Returns an error if the shutdown fails.
     pub async fn shutdown(self) -> Result<(), ActonAIError> {
         self.inner.is_shutdown.store(true, Ordering::SeqCst);
+        self.drain(self.inner.shutdown_drain_timeout).await;
         // Get the runtime clone for shutdown. The Arc may still be shared,
         // so we clone the ActorRuntime (which is itself cheap to clone).
         let mut runtime = self.inner.runtime.clone();
@@ -502,6 +826,157 @@ impl ActonAI {
             .await
             .map_err(|e| ActonAIError::launch_failed(e.to_string()))
     }
+
+    /// Shuts down the runtime if this is its last handle.
+    ///
+    /// Other clones (including those held by conversations and in-flight
+    /// prompts) keep the runtime alive, in which case this handle is simply
+    /// dropped. Returns whether the runtime was shut down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the shutdown fails.
+    pub async fn shutdown_shared(self) -> Result<bool, ActonAIError> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(inner) => {
+                let runtime = Self {
+                    inner: Arc::new(inner),
+                };
+                runtime.shutdown().await.map(|()| true)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Stops all actors, giving up after `timeout`.
+    ///
+    /// Unlike [`shutdown`](Self::shutdown), in-flight requests are not
+    /// drained first. If actors are still running when `timeout` elapses,
+    /// the shutdown is aborted and [`ShutdownResult::Timeout`] reports how
+    /// many were still alive.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// match runtime.shutdown_timeout(Duration::from_secs(5)).await {
+    ///     ShutdownResult::Clean => {}
+    ///     ShutdownResult::Timeout { actors_still_running } => {
+    ///         eprintln!("{actors_still_running} actors did not stop in time");
+    ///     }
+    ///     ShutdownResult::Error(e) => eprintln!("shutdown failed: {e}"),
+    /// }
+    /// ```
+    pub async fn shutdown_timeout(self, timeout: Duration) -> ShutdownResult {
+        self.inner.is_shutdown.store(true, Ordering::SeqCst);
+        self.stop_actors(timeout).await
+    }
+
+    /// Shuts down in two phases: drain, then stop.
+    ///
+    /// New requests are rejected immediately and a [`KernelShuttingDown`]
+    /// event is broadcast. In-flight requests get up to `drain_timeout` to
+    /// finish; actors are then stopped as in
+    /// [`shutdown_timeout`](Self::shutdown_timeout) with `force_timeout`.
+    pub async fn shutdown_gracefully(
+        self,
+        drain_timeout: Duration,
+        force_timeout: Duration,
+    ) -> ShutdownResult {
+        self.inner.is_shutdown.store(true, Ordering::SeqCst);
+        self.drain(drain_timeout).await;
+        self.stop_actors(force_timeout).await
+    }
+
+    /// Announces shutdown, cancels requests queued in the providers, and
+    /// waits up to `timeout` for in-flight requests.
+    async fn drain(&self, timeout: Duration) {
+        let pending_requests = self.in_flight_requests();
+        self.inner
+            .runtime
+            .broker()
+            .broadcast(KernelShuttingDown { pending_requests })
+            .await;
+
+        let drains = self.inner.providers.values().map(|provider| {
+            let mut runtime = self.inner.runtime.clone();
+            async move { LLMProvider::drain_queue(&mut runtime, provider).await.len() }
+        });
+        let cancelled: usize = futures::future::join_all(drains).await.into_iter().sum();
+        if cancelled > 0 {
+            tracing::info!(cancelled, "Cancelled queued LLM requests for shutdown");
+        }
+        let pending_requests = self.in_flight_requests();
+        if pending_requests == 0 {
+            return;
+        }
+
+        let drained = async {
+            loop {
+                let notified = self.inner.drained.notified();
+                if self.in_flight_requests() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        };
+        if tokio::time::timeout(timeout, drained).await.is_err() {
+            tracing::warn!(
+                remaining = self.in_flight_requests(),
+                "Drain timeout elapsed with requests still in flight"
+            );
+        }
+    }
+
+    /// Includes `handle` in the actors counted by
+    /// [`ShutdownResult::Timeout`].
+    ///
+    /// Conversations register themselves; use this for tool actors and other
+    /// actors spawned on [`runtime`](Self::runtime).
+    pub fn track_actor(&self, handle: &ActorHandle) {
+        let mut actors = self
+            .inner
+            .tracked_actors
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        actors.retain(|actor| !actor.tracker().is_empty());
+        actors.push(handle.clone());
+    }
+
+    /// Runs `shutdown_all` with a deadline, aborting it once the deadline
+    /// passes.
+    async fn stop_actors(&self, timeout: Duration) -> ShutdownResult {
+        let mut runtime = self.inner.runtime.clone();
+        let mut shutdown = tokio::spawn(async move { runtime.shutdown_all().await });
+
+        match tokio::time::timeout(timeout, &mut shutdown).await {
+            Ok(Ok(Ok(()))) => ShutdownResult::Clean,
+            Ok(Ok(Err(e))) => ShutdownResult::Error(ActonAIError::launch_failed(e.to_string())),
+            Ok(Err(e)) => ShutdownResult::Error(ActonAIError::launch_failed(e.to_string())),
+            Err(_) => {
+                shutdown.abort();
+                ShutdownResult::Timeout {
+                    actors_still_running: self.running_actor_count(),
+                }
+            }
+        }
+    }
+
+    /// Counts the actors whose tasks have not finished: the kernel,
+    /// providers, memory store, and tracked actors.
+    fn running_actor_count(&self) -> usize {
+        let tracked = self
+            .inner
+            .tracked_actors
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone();
+        std::iter::once(&self.inner.kernel)
+            .chain(self.inner.providers.values())
+            .chain(self.inner.memory_store.get())
+            .chain(&tracked)
+            .filter(|handle| !handle.tracker().is_empty())
+            .count()
+    }
 }
 
 /// Configuration for built-in tools.
@@ -567,6 +1042,8 @@ pub struct ActonAIBuilder {
     providers: HashMap<String, ProviderConfig>,
     /// The name of the default provider
     default_provider_name: Option<String>,
+    /// The provider that takes over while the default provider's circuit is open
+    fallback_provider_name: Option<String>,
     builtins: BuiltinToolsConfig,
     auto_builtins: bool,
     sandbox_mode: SandboxMode,
@@ -594,6 +1071,32 @@ pub struct ActonAIBuilder {
     /// during [`apply_config`](Self::apply_config). At launch the entry for the
     /// resolved default provider wins over the global `[context] max_tokens`.
     context_window_per_provider: HashMap<String, usize>,
+    /// Override for [`KernelConfig::shutdown_drain_timeout`].
+    shutdown_drain_timeout: Option<Duration>,
+    /// Per-tool rate limits applied to the builtins at launch.
+    tool_rate_limits: HashMap<String, ToolRateLimit>,
+    /// Embedding provider set via
+    /// [`with_embedding_provider`](Self::with_embedding_provider).
+    embedding_provider: Option<Box<dyn EmbeddingProvider>>,
+    /// Capacity of the [`EmbeddingCache`] wrapping the embedding provider.
+    embedding_cache_entries: Option<usize>,
+    /// Templates added on top of those loaded from
+    /// [`TemplateLibrary::default_dir`].
+    templates: TemplateLibrary,
+    /// Logging settings replacing the kernel's defaults.
+    logging: Option<LoggingConfig>,
+    /// Problems found by [`ActonAIConfig::validate`] in applied
+    /// configurations, reported together by [`launch`](Self::launch).
+    config_errors: Vec<config::ConfigValidationError>,
+    /// Kernel log level from the applied configuration's `log_level`.
+    /// Ignored when [`with_logging`](Self::with_logging) was called.
+    config_log_level: Option<LogLevel>,
+    /// Database for the memory store spawned at launch, set via
+    /// [`with_memory_store`](Self::with_memory_store).
+    memory_store_config: Option<PersistenceConfig>,
+    /// Whether [`launch`](Self::launch) fails when the memory store cannot
+    /// be initialized.
+    memory_store_required: bool,
 }
 
 impl ActonAIBuilder {
@@ -606,6 +1109,42 @@ impl ActonAIBuilder {
         self
     }
 
+    /// Configures the kernel's logging.
+    ///
+    /// By default the kernel logs to journald under the
+    /// [`app_name`](Self::app_name). `config` replaces those settings,
+    /// including the app name, and can turn on
+    /// [audit logging of LLM requests](LoggingConfig::with_provider_request_logging).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .ollama("qwen2.5:7b")
+    ///     .with_logging(
+    ///         LoggingConfig::new()
+    ///             .with_app_name("my-app")
+    ///             .with_level(LogLevel::Debug)
+    ///             .with_provider_request_logging(true),
+    ///     )
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn with_logging(mut self, config: LoggingConfig) -> Self {
+        self.logging = Some(config);
+        self
+    }
+
+    /// Sets how long [`ActonAI::shutdown`] waits for in-flight requests.
+    ///
+    /// Defaults to [`DEFAULT_SHUTDOWN_DRAIN_TIMEOUT`] (30 seconds).
+    #[must_use]
+    pub fn shutdown_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_drain_timeout = Some(timeout);
+        self
+    }
+
     // =========================================================================
     // Multi-Provider API (new)
     // =========================================================================
@@ -654,6 +1193,33 @@ impl ActonAIBuilder {
         self
     }
 
+    /// Sets the provider that receives the default provider's requests while
+    /// its circuit breaker is open.
+    ///
+    /// Has no effect unless the default provider was configured with
+    /// [`ProviderConfig::with_circuit_breaker`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .provider_named(
+    ///         "cloud",
+    ///         ProviderConfig::anthropic("sk-...")
+    ///             .with_circuit_breaker(CircuitBreakerConfig::default()),
+    ///     )
+    ///     .provider_named("local", ProviderConfig::ollama("qwen2.5:7b"))
+    ///     .default_provider("cloud")
+    ///     .fallback_provider("local")
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn fallback_provider(mut self, name: impl Into<String>) -> Self {
+        self.fallback_provider_name = Some(name.into());
+        self
+    }
+
     /// Sets the framework-wide default cap on agentic tool-call rounds.
     ///
     /// Takes precedence over a `[defaults] max_tool_rounds` value loaded from
@@ -704,9 +1270,12 @@ impl ActonAIBuilder {
 
     /// Loads provider configurations from a config file.
     ///
-    /// This searches for configuration in the following order:
-    /// 1. `./acton-ai.toml` (project-local)
-    /// 2. `~/.config/acton-ai/config.toml` (XDG config)
+    /// This merges configuration from the following layers, later ones
+    /// taking precedence (see [`config::load_with_env_overrides`]):
+    /// 1. `~/.config/acton-ai/config.toml` (XDG config)
+    /// 2. `./acton-ai.toml` (project-local)
+    /// 3. The `ACTON_AI_DEFAULT_PROVIDER` and `ACTON_AI_LOG_LEVEL` environment
+    ///    variables
     ///
     /// If no config file is found, this is a no-op (returns Ok).
     /// Providers loaded from config are merged with any already registered.
@@ -725,7 +1294,7 @@ impl ActonAIBuilder {
     ///     .await?;
     /// ```
     pub fn from_config(self) -> Result<Self, ActonAIError> {
-        let config = config::load()?;
+        let config = config::load_with_env_overrides()?;
         self.apply_config(config)
     }
 
@@ -749,6 +1318,40 @@ impl ActonAIBuilder {
         self.apply_config(config)
     }
 
+    /// Loads per-provider rate limits from a standalone TOML file (see
+    /// [`config::load_rate_limits`]).
+    ///
+    /// Each limit replaces the rate limit of the already registered provider
+    /// of the same name, so call this after registering providers. Limits
+    /// for unregistered providers are ignored with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .from_config_file("/etc/acton-ai/config.toml")?
+    ///     .with_rate_limit_file(Path::new("/etc/acton-ai/rate-limits.toml"))?
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    pub fn with_rate_limit_file(mut self, path: &Path) -> Result<Self, ActonAIError> {
+        for (name, limit) in config::load_rate_limits(path)? {
+            match self.providers.get_mut(&name) {
+                Some(provider) => provider.rate_limit = limit.to_rate_limit_config(),
+                None => tracing::warn!(
+                    provider = %name,
+                    path = %path.display(),
+                    "Ignoring rate limit for unregistered provider"
+                ),
+            }
+        }
+        Ok(self)
+    }
+
     /// Attempts to load from config file, ignoring errors if no config exists.
     ///
     /// This is useful when config files are optional. Parse errors are still
@@ -772,7 +1375,11 @@ impl ActonAIBuilder {
     ///
     /// This is useful when you've already loaded the configuration and want
     /// to apply it to the builder without going through file loading again.
+    /// The configuration is [validated](ActonAIConfig::validate) here, and
+    /// any problems are returned together by [`launch`](Self::launch).
     pub fn apply_config(mut self, config: ActonAIConfig) -> Result<Self, ActonAIError> {
+        self.config_errors.extend(config.validate());
+
         // Convert and add each provider
         for (name, provider_config) in config.providers {
             if let Some(tokens) = provider_config.context_window_tokens {
@@ -786,6 +1393,9 @@ impl ActonAIBuilder {
         if self.default_provider_name.is_none() {
             self.default_provider_name = config.default_provider;
         }
+        if self.fallback_provider_name.is_none() {
+            self.fallback_provider_name = config.fallback_provider;
+        }
 
         // Apply sandbox configuration if present and no programmatic sandbox was set
         if let Some(sandbox_config) = config.sandbox {
@@ -813,6 +1423,10 @@ impl ActonAIBuilder {
             self.context_config = Some(context_cfg);
         }
 
+        if let Some(level) = config.log_level.as_deref().and_then(config::parse_log_level) {
+            self.config_log_level = Some(level);
+        }
+
         Ok(self)
     }
 
@@ -843,95 +1457,212 @@ impl ActonAIBuilder {
     /// ```rust,ignore
     /// let runtime = ActonAI::builder()
     ///     .app_name("my-app")
-    ///     .ollama("llama3.2")
+    ///     .ollama("llama3.2")
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn ollama(self, model: impl Into<String>) -> Self {
+        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::ollama(model))
+    }
+
+    /// Configures for Ollama with a custom URL and model.
+    ///
+    /// Use this when Ollama is running on a non-default address.
+    /// Registers as "default" provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .app_name("my-app")
+    ///     .ollama_at("http://192.168.1.100:11434/v1", "llama3.2")
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn ollama_at(self, base_url: impl Into<String>, model: impl Into<String>) -> Self {
+        self.provider_named(
+            DEFAULT_PROVIDER_NAME,
+            ProviderConfig::openai_compatible(base_url, model),
+        )
+    }
+
+    /// Configures for Anthropic Claude with the specified API key.
+    ///
+    /// Uses the default Claude model. Registers as "default" provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .app_name("my-app")
+    ///     .anthropic("sk-ant-...")
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn anthropic(self, api_key: impl Into<String>) -> Self {
+        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::anthropic(api_key))
+    }
+
+    /// Configures for Anthropic Claude with a specific model.
+    ///
+    /// Registers as "default" provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .app_name("my-app")
+    ///     .anthropic_model("sk-ant-...", "claude-3-haiku-20240307")
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn anthropic_model(self, api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        self.provider_named(
+            DEFAULT_PROVIDER_NAME,
+            ProviderConfig::anthropic(api_key).with_model(model),
+        )
+    }
+
+    /// Configures for OpenAI with the specified API key.
+    ///
+    /// Uses the default GPT model. Registers as "default" provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .app_name("my-app")
+    ///     .openai("sk-...")
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn openai(self, api_key: impl Into<String>) -> Self {
+        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::openai(api_key))
+    }
+
+    /// Configures for OpenAI with a specific model.
+    ///
+    /// Registers as "default" provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .app_name("my-app")
+    ///     .openai_model("sk-...", "gpt-4-turbo")
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn openai_model(self, api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        self.provider_named(
+            DEFAULT_PROVIDER_NAME,
+            ProviderConfig::openai(api_key).with_model(model),
+        )
+    }
+
+    /// Configures for Cohere with the specified API key.
+    ///
+    /// Uses the default Command R+ model. Registers as "default" provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .app_name("my-app")
+    ///     .cohere("co-...")
     ///     .launch()
     ///     .await?;
     /// ```
     #[must_use]
-    pub fn ollama(self, model: impl Into<String>) -> Self {
-        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::ollama(model))
+    pub fn cohere(self, api_key: impl Into<String>) -> Self {
+        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::cohere(api_key))
     }
 
-    /// Configures for Ollama with a custom URL and model.
+    /// Configures for Google Gemini with the specified API key.
     ///
-    /// Use this when Ollama is running on a non-default address.
-    /// Registers as "default" provider.
+    /// Uses the default Gemini Flash model. Registers as "default" provider.
     ///
     /// # Example
     ///
     /// ```rust,ignore
     /// let runtime = ActonAI::builder()
     ///     .app_name("my-app")
-    ///     .ollama_at("http://192.168.1.100:11434/v1", "llama3.2")
+    ///     .gemini("AIza...")
     ///     .launch()
     ///     .await?;
     /// ```
     #[must_use]
-    pub fn ollama_at(self, base_url: impl Into<String>, model: impl Into<String>) -> Self {
-        self.provider_named(
-            DEFAULT_PROVIDER_NAME,
-            ProviderConfig::openai_compatible(base_url, model),
-        )
+    pub fn gemini(self, api_key: impl Into<String>) -> Self {
+        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::gemini(api_key))
     }
 
-    /// Configures for Anthropic Claude with the specified API key.
+    /// Configures for Google Gemini with a specific model.
     ///
-    /// Uses the default Claude model. Registers as "default" provider.
+    /// Registers as "default" provider.
     ///
     /// # Example
     ///
     /// ```rust,ignore
     /// let runtime = ActonAI::builder()
     ///     .app_name("my-app")
-    ///     .anthropic("sk-ant-...")
+    ///     .gemini_model("AIza...", "gemini-1.5-pro")
     ///     .launch()
     ///     .await?;
     /// ```
     #[must_use]
-    pub fn anthropic(self, api_key: impl Into<String>) -> Self {
-        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::anthropic(api_key))
+    pub fn gemini_model(self, api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        self.provider_named(
+            DEFAULT_PROVIDER_NAME,
+            ProviderConfig::gemini(api_key).with_model(model),
+        )
     }
 
-    /// Configures for Anthropic Claude with a specific model.
+    /// Configures for Mistral AI with the specified API key.
     ///
-    /// Registers as "default" provider.
+    /// Uses the default Mistral Large model. Registers as "default" provider.
     ///
     /// # Example
     ///
     /// ```rust,ignore
     /// let runtime = ActonAI::builder()
     ///     .app_name("my-app")
-    ///     .anthropic_model("sk-ant-...", "claude-3-haiku-20240307")
+    ///     .mistral("mistral-key")
     ///     .launch()
     ///     .await?;
     /// ```
     #[must_use]
-    pub fn anthropic_model(self, api_key: impl Into<String>, model: impl Into<String>) -> Self {
-        self.provider_named(
-            DEFAULT_PROVIDER_NAME,
-            ProviderConfig::anthropic(api_key).with_model(model),
-        )
+    pub fn mistral(self, api_key: impl Into<String>) -> Self {
+        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::mistral(api_key))
     }
 
-    /// Configures for OpenAI with the specified API key.
+    /// Configures for Mistral AI with a specific model.
     ///
-    /// Uses the default GPT model. Registers as "default" provider.
+    /// Registers as "default" provider.
     ///
     /// # Example
     ///
     /// ```rust,ignore
     /// let runtime = ActonAI::builder()
     ///     .app_name("my-app")
-    ///     .openai("sk-...")
+    ///     .mistral_model("mistral-key", "mistral-small-latest")
     ///     .launch()
     ///     .await?;
     /// ```
     #[must_use]
-    pub fn openai(self, api_key: impl Into<String>) -> Self {
-        self.provider_named(DEFAULT_PROVIDER_NAME, ProviderConfig::openai(api_key))
+    pub fn mistral_model(self, api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        self.provider_named(
+            DEFAULT_PROVIDER_NAME,
+            ProviderConfig::mistral(api_key).with_model(model),
+        )
     }
 
-    /// Configures for OpenAI with a specific model.
+    /// Configures for an Azure OpenAI deployment.
     ///
     /// Registers as "default" provider.
     ///
@@ -940,15 +1671,20 @@ impl ActonAIBuilder {
     /// ```rust,ignore
     /// let runtime = ActonAI::builder()
     ///     .app_name("my-app")
-    ///     .openai_model("sk-...", "gpt-4-turbo")
+    ///     .azure_openai("my-resource", "gpt-4o-prod", "azure-key")
     ///     .launch()
     ///     .await?;
     /// ```
     #[must_use]
-    pub fn openai_model(self, api_key: impl Into<String>, model: impl Into<String>) -> Self {
+    pub fn azure_openai(
+        self,
+        resource: impl Into<String>,
+        deployment: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Self {
         self.provider_named(
             DEFAULT_PROVIDER_NAME,
-            ProviderConfig::openai(api_key).with_model(model),
+            ProviderConfig::openai_azure(resource, deployment, api_key),
         )
     }
 
@@ -983,9 +1719,14 @@ impl ActonAIBuilder {
     /// - `list_directory`: List directory contents
     /// - `glob`: Find files matching glob patterns
     /// - `grep`: Search file contents with regex
+    /// - `git`: Read repository history, diffs and status
     /// - `bash`: Execute shell commands
     /// - `calculate`: Evaluate mathematical expressions
     /// - `web_fetch`: Fetch content from URLs
+    /// - `fetch_rss`: Fetch recent items from RSS and Atom feeds
+    /// - `sql_query`: Run read-only SQL against libSQL databases
+    /// - `json_query`: Query and transform JSON with jq-style expressions
+    /// - `database`: Run SQL against libSQL databases (read-only by default)
     ///
     /// When using this method, builtins are automatically enabled on every prompt
     /// created via [`prompt()`](ActonAI::prompt), [`continue_with()`](ActonAI::continue_with),
@@ -1084,6 +1825,30 @@ impl ActonAIBuilder {
         self
     }
 
+    /// Sets per-tool rate limits for the built-in tools.
+    ///
+    /// Applied at launch via [`BuiltinTools::with_rate_limits`]. Has no
+    /// effect unless builtins are enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .ollama("qwen2.5:7b")
+    ///     .with_builtins()
+    ///     .with_tool_rate_limits(HashMap::from([(
+    ///         "bash".to_string(),
+    ///         ToolRateLimit::new(10, 100, 2),
+    ///     )]))
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn with_tool_rate_limits(mut self, limits: HashMap<String, ToolRateLimit>) -> Self {
+        self.tool_rate_limits.extend(limits);
+        self
+    }
+
     /// Stages skill paths to be loaded into a shared
     /// [`SkillRegistry`](crate::skills::SkillRegistry) at launch.
     ///
@@ -1174,6 +1939,83 @@ impl ActonAIBuilder {
         self
     }
 
+    /// Adds prompt templates to the runtime.
+    ///
+    /// They are merged over the templates loaded from
+    /// [`TemplateLibrary::default_dir`], replacing any with the same name.
+    #[must_use]
+    pub fn with_templates(mut self, templates: TemplateLibrary) -> Self {
+        self.templates.merge(templates);
+        self
+    }
+
+    /// Sets the embedding provider used for memory features.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .ollama("qwen2.5:7b")
+    ///     .with_embedding_provider(OpenAIEmbeddingProvider::new(api_key))
+    ///     .with_embedding_cache(10_000)
+    ///     .launch()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn with_embedding_provider(mut self, provider: impl EmbeddingProvider + 'static) -> Self {
+        self.embedding_provider = Some(Box::new(provider));
+        self
+    }
+
+    /// Spawns a [`MemoryStore`] on `config`'s database at launch and
+    /// attaches it to the runtime.
+    ///
+    /// Conversations then persist to the store (see
+    /// [`ActonAI::attach_memory_store`]), and agents configured with
+    /// [`AgentConfig::with_memory`](crate::agent::AgentConfig::with_memory)
+    /// that register with the runtime's kernel draw memories from it. If
+    /// the store cannot be initialized, launch logs a warning and continues
+    /// without it; use
+    /// [`with_memory_store_required`](Self::with_memory_store_required) to
+    /// fail instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let runtime = ActonAI::builder()
+    ///     .ollama("qwen2.5:7b")
+    ///     .with_memory_store(PersistenceConfig::new("agent.db"))
+    ///     .launch()
+    ///     .await?;
+    /// assert!(runtime.memory_store().is_some());
+    /// ```
+    #[must_use]
+    pub fn with_memory_store(mut self, config: PersistenceConfig) -> Self {
+        self.memory_store_config = Some(config);
+        self.memory_store_required = false;
+        self
+    }
+
+    /// Like [`with_memory_store`](Self::with_memory_store), but
+    /// [`launch`](Self::launch) fails if the store cannot be initialized.
+    #[must_use]
+    pub fn with_memory_store_required(mut self, config: PersistenceConfig) -> Self {
+        self.memory_store_config = Some(config);
+        self.memory_store_required = true;
+        self
+    }
+
+    /// Wraps the embedding provider in an [`EmbeddingCache`] holding up to
+    /// `max_entries` embeddings, so identical texts are embedded once.
+    ///
+    /// Has no effect unless an embedding provider is set with
+    /// [`with_embedding_provider`](Self::with_embedding_provider).
+    #[must_use]
+    pub fn with_embedding_cache(mut self, max_entries: usize) -> Self {
+        self.embedding_cache_entries = Some(max_entries);
+        self
+    }
+
     /// Launches the ActonAI runtime with the configured settings.
     ///
     /// This spawns the actor runtime, kernel, and LLM providers.
@@ -1181,10 +2023,14 @@ impl ActonAIBuilder {
     /// # Errors
     ///
     /// Returns an error if:
+    /// - An applied configuration failed [validation](ActonAIConfig::validate)
     /// - No provider is configured
     /// - Default provider is specified but doesn't exist
     /// - Multiple providers exist but no default is specified
     /// - The runtime fails to launch
+    /// - A memory store set with
+    ///   [`with_memory_store_required`](Self::with_memory_store_required)
+    ///   fails to initialize
     ///
     /// # Example
     ///
@@ -1196,6 +2042,21 @@ impl ActonAIBuilder {
     ///     .await?;
     /// ```
     pub async fn launch(mut self) -> Result<ActonAI, ActonAIError> {
+        // Report every configuration problem at once rather than failing on
+        // first use of each provider
+        if !self.config_errors.is_empty() {
+            let problems: Vec<String> =
+                self.config_errors.iter().map(ToString::to_string).collect();
+            return Err(ActonAIError::configuration(
+                "config",
+                format!(
+                    "{} problem(s) found: {}",
+                    problems.len(),
+                    problems.join("; ")
+                ),
+            ));
+        }
+
         // Validate we have at least one provider
         if self.providers.is_empty() {
             return Err(ActonAIError::new(ActonAIErrorKind::Configuration {
@@ -1208,6 +2069,7 @@ impl ActonAIBuilder {
         // context-window resolution (the HashMap gets consumed in the spawn
         // loop below).
         let default_provider_name = self.resolve_default_provider_name()?;
+        self.validate_fallback_provider_name(&default_provider_name)?;
         let default_provider_model = self
             .providers
             .get(&default_provider_name)
@@ -1220,16 +2082,81 @@ impl ActonAIBuilder {
         let mut runtime = ActonApp::launch_async().await;
 
         // Spawn the kernel with the app name for logging
-        let kernel_config = KernelConfig::default().with_app_name(&app_name);
-        let _kernel = Kernel::spawn_with_config(&mut runtime, kernel_config).await;
+        let shutdown_drain_timeout = self
+            .shutdown_drain_timeout
+            .unwrap_or(DEFAULT_SHUTDOWN_DRAIN_TIMEOUT);
+        let mut kernel_config = KernelConfig::default()
+            .with_app_name(&app_name)
+            .with_shutdown_drain_timeout(shutdown_drain_timeout);
+        if let Some(logging) = self.logging.take() {
+            kernel_config = kernel_config.with_logging(logging);
+        } else if let (Some(level), Some(logging)) =
+            (self.config_log_level, kernel_config.logging.as_mut())
+        {
+            logging.level = level;
+        }
+        let kernel = Kernel::spawn_with_config(&mut runtime, kernel_config).await;
+
+        // Spawn the memory store, degrading to no persistence unless required
+        let memory_store = OnceLock::new();
+        if let Some(config) = self.memory_store_config.take() {
+            match MemoryStore::spawn_initialized(&mut runtime, config).await {
+                Ok(store) => {
+                    kernel
+                        .send(UseMemoryStore {
+                            store: store.clone(),
+                        })
+                        .await;
+                    let _ = memory_store.set(store);
+                }
+                Err(e) if self.memory_store_required => {
+                    return Err(ActonAIError::configuration(
+                        "memory_store",
+                        format!("failed to initialize memory store: {e}"),
+                    ));
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        error = %e,
+                        "memory store failed to initialize; continuing without persistence"
+                    );
+                }
+            }
+        }
 
         // Spawn all LLM providers
         let mut providers = HashMap::new();
+        let mut retry_policies = HashMap::new();
+        let mut prompt_timeouts = HashMap::new();
         for (name, config) in self.providers {
-            let handle = LLMProvider::spawn(&mut runtime, config).await;
+            if let Some(policy) = config.retry_policy {
+                retry_policies.insert(name.clone(), policy);
+            }
+            if let Some(timeout) = config.default_prompt_timeout {
+                prompt_timeouts.insert(name.clone(), timeout);
+            }
+            let handle =
+                LLMProvider::spawn_with_drain_timeout(&mut runtime, config, shutdown_drain_timeout)
+                    .await;
             providers.insert(name, handle);
         }
 
+        // Route the default provider's requests to the fallback while its
+        // circuit is open
+        if let Some(fallback_name) = self.fallback_provider_name {
+            if let (Some(primary), Some(fallback)) = (
+                providers.get(&default_provider_name),
+                providers.get(&fallback_name),
+            ) {
+                primary
+                    .send(SetFallbackProvider {
+                        name: fallback_name,
+                        handle: fallback.clone(),
+                    })
+                    .await;
+            }
+        }
+
         // Initialize sandbox if configured.
         //
         // Ownership note: the produced factory is retained on
@@ -1267,7 +2194,8 @@ impl ActonAIBuilder {
                     })
                 })?)
             }
-        };
+        }
+        .map(|tools| tools.with_rate_limits(self.tool_rate_limits));
 
         let default_max_tool_rounds = self
             .default_max_tool_rounds
@@ -1300,10 +2228,30 @@ impl ActonAIBuilder {
             &default_provider_model,
         );
 
+        let (embedding_provider, embedding_cache) =
+            match (self.embedding_provider.take(), self.embedding_cache_entries) {
+                (Some(provider), Some(max_entries)) => {
+                    let cache = Arc::new(EmbeddingCache::boxed(provider, max_entries));
+                    let provider: Arc<dyn EmbeddingProvider> = cache.clone();
+                    (Some(provider), Some(cache))
+                }
+                (Some(provider), None) => (Some(Arc::from(provider)), None),
+                (None, Some(_)) => {
+                    tracing::warn!("embedding cache configured without an embedding provider");
+                    (None, None)
+                }
+                (None, None) => (None, None),
+            };
+
+        let mut templates = TemplateLibrary::load_default();
+        templates.merge(std::mem::take(&mut self.templates));
+
         Ok(ActonAI {
             inner: Arc::new(ActonAIInner {
                 runtime,
                 providers,
+                retry_policies,
+                prompt_timeouts,
                 default_provider: default_provider_name,
                 builtins,
                 auto_builtins: self.auto_builtins,
@@ -1312,6 +2260,15 @@ impl ActonAIBuilder {
                 default_max_tool_rounds,
                 context_window,
                 is_shutdown: AtomicBool::new(false),
+                kernel,
+                shutdown_drain_timeout,
+                embedding_provider,
+                embedding_cache,
+                templates,
+                in_flight: AtomicUsize::new(0),
+                drained: Notify::new(),
+                memory_store,
+                tracked_actors: Mutex::new(Vec::new()),
             }),
         })
     }
@@ -1357,6 +2314,32 @@ impl ActonAIBuilder {
         }))
     }
 
+    /// Checks that the fallback provider, if set, exists and is not the
+    /// default provider.
+    fn validate_fallback_provider_name(
+        &self,
+        default_provider: &str,
+    ) -> Result<(), ActonAIError> {
+        let Some(ref name) = self.fallback_provider_name else {
+            return Ok(());
+        };
+        let reason = if name == default_provider {
+            format!("fallback provider '{name}' is also the default provider")
+        } else if !self.providers.contains_key(name) {
+            format!(
+                "fallback provider '{}' not found; available providers: {}",
+                name,
+                self.providers.keys().cloned().collect::<Vec<_>>().join(", ")
+            )
+        } else {
+            return Ok(());
+        };
+        Err(ActonAIError::new(ActonAIErrorKind::Configuration {
+            field: "fallback_provider".to_string(),
+            reason,
+        }))
+    }
+
     /// Returns whether auto-builtins is currently enabled.
     ///
     /// This is useful for testing or debugging.
@@ -1417,6 +2400,11 @@ fn resolve_context_window(
         truncation_strategy: strategy,
         reserved_for_response,
         tokens_per_char: default_cfg.tokens_per_char,
+        importance_weight: default_cfg.importance_weight,
+        dedup_threshold: default_cfg.dedup_threshold,
+        hybrid_search_alpha: default_cfg.hybrid_search_alpha,
+        accurate_token_count: default_cfg.accurate_token_count,
+        model_name: Some(default_provider_model.to_string()),
     };
 
     let estimator: Arc<dyn TokenEstimator> =
@@ -1532,6 +2520,12 @@ mod tests {
         assert!(err.to_string().contains("provider"));
     }
 
+    #[test]
+    fn builder_with_logging_stores_config() {
+        let builder = ActonAI::builder().with_logging(LoggingConfig::disabled());
+        assert_eq!(builder.logging, Some(LoggingConfig::disabled()));
+    }
+
     #[test]
     fn with_builtins_enables_auto_builtins() {
         let builder = ActonAI::builder().with_builtins();
@@ -1568,6 +2562,68 @@ mod tests {
         assert!(builder.providers.contains_key("local"));
     }
 
+    #[test]
+    fn with_rate_limit_file_overrides_registered_providers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rate-limits.toml");
+        std::fs::write(
+            &path,
+            "[providers.claude]\nrequests_per_minute = 40\ntokens_per_minute = 50000\n\n\
+             [providers.unused]\nrequests_per_minute = 1\ntokens_per_minute = 1\n",
+        )
+        .unwrap();
+
+        let builder = ActonAI::builder()
+            .provider_named("claude", ProviderConfig::anthropic("sk-test"))
+            .provider_named("local", ProviderConfig::ollama("qwen2.5:7b"))
+            .with_rate_limit_file(&path)
+            .unwrap();
+
+        assert_eq!(
+            builder.providers["claude"].rate_limit,
+            RateLimitConfig::new(40, 50_000)
+        );
+        assert_eq!(
+            builder.providers["local"].rate_limit,
+            ProviderConfig::ollama("qwen2.5:7b").rate_limit
+        );
+        assert!(!builder.providers.contains_key("unused"));
+        assert!(ActonAI::builder()
+            .with_rate_limit_file(&dir.path().join("missing.toml"))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn update_provider_rate_limit_reaches_running_provider() {
+        let mut runtime = ActonAI::builder()
+            .ollama("test")
+            .launch()
+            .await
+            .expect("launch");
+        let updated = RateLimitConfig::new(40, 50_000);
+
+        runtime
+            .update_provider_rate_limit(runtime.default_provider_name(), updated)
+            .await
+            .unwrap();
+
+        let provider = runtime.provider_handle();
+        let reply: Option<crate::llm::EffectiveRateLimit> = crate::memory::query_store(
+            runtime.runtime_mut(),
+            &provider,
+            crate::llm::GetEffectiveRateLimit,
+            Duration::from_secs(1),
+        )
+        .await;
+        assert_eq!(reply.map(|r| r.config), Some(updated));
+
+        assert!(runtime
+            .update_provider_rate_limit("missing", updated)
+            .await
+            .unwrap_err()
+            .is_configuration());
+    }
+
     #[test]
     fn builder_default_provider_sets_name() {
         let builder = ActonAI::builder()
@@ -1577,6 +2633,22 @@ mod tests {
         assert_eq!(builder.default_provider_name, Some("claude".to_string()));
     }
 
+    #[test]
+    fn validate_fallback_provider() {
+        let builder = ActonAI::builder()
+            .provider_named("cloud", ProviderConfig::anthropic("sk-test"))
+            .provider_named("local", ProviderConfig::ollama("qwen2.5:7b"));
+        assert!(builder.validate_fallback_provider_name("cloud").is_ok());
+
+        let builder = builder.fallback_provider("local");
+        assert!(builder.validate_fallback_provider_name("cloud").is_ok());
+        assert!(builder.validate_fallback_provider_name("local").is_err());
+
+        let builder = builder.fallback_provider("missing");
+        let err = builder.validate_fallback_provider_name("cloud").unwrap_err();
+        assert!(err.to_string().contains("fallback provider 'missing' not found"));
+    }
+
     #[test]
     fn resolve_default_single_provider() {
         let builder = ActonAI::builder().provider_named("only-one", ProviderConfig::ollama("test"));
@@ -1665,6 +2737,91 @@ mod tests {
         assert_eq!(runtime.prompt("hi").current_max_tool_rounds(), 42);
     }
 
+    #[tokio::test]
+    async fn memory_store_failure_is_fatal_only_when_required() {
+        let dir = tempfile::tempdir().unwrap();
+        let unopenable =
+            || PersistenceConfig::new(dir.path().join("missing/memory.db").display().to_string());
+
+        let runtime = ActonAI::builder()
+            .ollama("test")
+            .with_memory_store(unopenable())
+            .launch()
+            .await
+            .expect("launch");
+        assert!(runtime.memory_store().is_none());
+        runtime.shutdown().await.unwrap();
+
+        let err = ActonAI::builder()
+            .ollama("test")
+            .with_memory_store_required(unopenable())
+            .launch()
+            .await
+            .unwrap_err();
+        assert!(err.is_configuration());
+        assert!(err.to_string().contains("memory store"));
+    }
+
+    #[tokio::test]
+    async fn builder_templates_are_available_to_prompts() {
+        let runtime = ActonAI::builder()
+            .ollama("test")
+            .with_templates(
+                TemplateLibrary::new().with_template("greet", PromptTemplate::new("Hi {{name}}")),
+            )
+            .launch()
+            .await
+            .expect("launch");
+
+        let template = runtime.templates().get("greet").expect("template");
+        assert_eq!(template.render(&HashMap::from([("name", "Ada")])), "Hi Ada");
+    }
+
+    #[tokio::test]
+    async fn shutdown_shared_waits_for_last_clone() {
+        let runtime = ActonAI::builder()
+            .ollama("test")
+            .launch()
+            .await
+            .expect("launch");
+        let clone = runtime.clone();
+
+        assert!(!runtime.shutdown_shared().await.unwrap());
+        assert!(!clone.is_shutdown());
+        assert!(clone.shutdown_shared().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn builder_with_embedding_cache_wraps_provider() {
+        let runtime = ActonAI::builder()
+            .ollama("test")
+            .with_embedding_provider(crate::memory::StubEmbeddingProvider::default())
+            .with_embedding_cache(16)
+            .launch()
+            .await
+            .expect("launch");
+
+        let provider = runtime.embedding_provider().expect("embedding provider");
+        provider.embed("same text").await.unwrap();
+        provider.embed("same text").await.unwrap();
+
+        let stats = runtime.embedding_cache_stats().expect("cache stats");
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn builder_without_embedding_provider_has_no_cache() {
+        let runtime = ActonAI::builder()
+            .ollama("test")
+            .with_embedding_cache(16)
+            .launch()
+            .await
+            .expect("launch");
+
+        assert!(runtime.embedding_provider().is_none());
+        assert!(runtime.embedding_cache_stats().is_none());
+    }
+
     #[tokio::test]
     async fn toml_defaults_max_tool_rounds_is_applied() {
         let config = crate::config::ActonAIConfig::new()
@@ -1689,6 +2846,31 @@ mod tests {
         assert_eq!(runtime.default_max_tool_rounds(), 33);
     }
 
+    #[tokio::test]
+    async fn launch_reports_all_config_validation_errors() {
+        let config = crate::config::ActonAIConfig::new()
+            .with_provider(
+                "ollama",
+                crate::config::NamedProviderConfig::ollama("test")
+                    .with_timeout_secs(0)
+                    .with_max_tokens(0),
+            )
+            .with_default_provider("ollama");
+
+        let err = ActonAI::builder()
+            .apply_config(config)
+            .expect("apply_config")
+            .launch()
+            .await
+            .expect_err("invalid config should not launch");
+
+        assert!(err.is_configuration());
+        let message = err.to_string();
+        assert!(message.contains("2 problem(s)"), "{message}");
+        assert!(message.contains("timeout_secs"), "{message}");
+        assert!(message.contains("max_tokens"), "{message}");
+    }
+
     #[tokio::test]
     async fn builder_max_tool_rounds_overrides_toml_defaults() {
         // Builder wins: user explicitly set 7 in code, config says 33.
@@ -1817,4 +2999,59 @@ mod tests {
         let registry = runtime.skills().expect("registry");
         assert_eq!(registry.len(), 1);
     }
+
+    #[tokio::test]
+    async fn shutdown_timeout_is_clean_when_idle() {
+        let runtime = ActonAI::builder()
+            .ollama("test")
+            .launch()
+            .await
+            .expect("launch");
+
+        let result = runtime.shutdown_timeout(Duration::from_secs(10)).await;
+        assert!(result.is_clean(), "unexpected result: {result:?}");
+    }
+
+    #[tokio::test]
+    async fn running_actor_count_includes_conversations() {
+        let runtime = ActonAI::builder()
+            .ollama("test")
+            .launch()
+            .await
+            .expect("launch");
+        assert_eq!(runtime.running_actor_count(), 2);
+
+        let _conversation = runtime.conversation().build().await;
+        assert_eq!(runtime.running_actor_count(), 3);
+
+        let result = runtime.shutdown_timeout(Duration::from_secs(10)).await;
+        assert!(result.is_clean(), "unexpected result: {result:?}");
+    }
+
+    #[tokio::test]
+    async fn shutdown_gracefully_waits_for_in_flight_requests() {
+        let runtime = ActonAI::builder()
+            .ollama("test")
+            .launch()
+            .await
+            .expect("launch");
+
+        let guard = runtime.begin_request();
+        assert_eq!(runtime.in_flight_requests(), 1);
+
+        let observer = runtime.clone();
+        let shutdown = tokio::spawn(runtime.shutdown_gracefully(
+            Duration::from_secs(10),
+            Duration::from_secs(10),
+        ));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(observer.is_shutdown());
+        assert!(!shutdown.is_finished());
+
+        drop(guard);
+        let result = shutdown.await.expect("join");
+        assert!(result.is_clean(), "unexpected result: {result:?}");
+        assert_eq!(observer.in_flight_requests(), 0);
+    }
 }
@@ -4,16 +4,33 @@
 //! Acton-AI system. It manages agent lifecycles, routes inter-agent
 //! communication, and handles agent failures through supervision.
 
+use crate::agent::{
+    Agent, AgentConfig, AgentMetrics, AgentState, InitAgent, UseMemoryStore, UseToolThrottle,
+};
 use crate::kernel::discovery::CapabilityRegistry;
 use crate::kernel::logging::init_and_store_logging;
 use crate::kernel::KernelConfig;
+use crate::llm::{CacheUsage, TokenUsage};
+use crate::memory::query_store;
 use crate::messages::{
-    AgentMessage, AgentSpawned, AnnounceCapabilities, CapableAgentFound, DelegateTask,
-    FindCapableAgent, GetAgentStatus, IncomingAgentMessage, IncomingTask, RouteMessage, SpawnAgent,
-    StopAgent, SystemEvent,
+    AgentMessage, AgentMetricsResponse, AgentSpawned, AllAgentMetrics, AnnounceCapabilities,
+    BroadcastComplete, BroadcastMessage, CapableAgentFound, DelegateTask, FindBestCapableAgent,
+    FindCapableAgent, GetAgentStatus, GetAllAgentMetrics, Heartbeat, HeartbeatAck,
+    IncomingAgentMessage, IncomingTask, LLMStreamUsage, PauseAgent, ResumeAgent, RouteMessage,
+    SpawnAgent, StopAgent, SystemEvent, TaskResult, WatchdogTriggered,
 };
+use crate::tools::actor::{ExecuteToolDirect, ToolActorResponse};
+use crate::tools::registry::UnregisterAllForAgent;
+use crate::types::AgentId;
 use acton_reactive::prelude::*;
-use std::collections::HashMap;
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// How long [`Kernel::broadcast`] waits for the kernel to reply.
+const BROADCAST_REPLY_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Metrics collected by the Kernel.
 #[derive(Debug, Clone, Default)]
@@ -24,6 +41,48 @@ pub struct KernelMetrics {
     pub agents_stopped: usize,
     /// Total number of messages routed
     pub messages_routed: usize,
+    /// Latest metrics reported by each agent
+    pub agent_metrics: HashMap<AgentId, AgentMetrics>,
+    /// Number of times the watchdog found an agent unresponsive
+    pub watchdog_trigger_count: usize,
+    /// Tool calls currently waiting for a free slot
+    pub queued_tool_calls: usize,
+    /// Most tool calls that have run at once
+    pub peak_concurrent_tool_calls: usize,
+    /// Prompt cache usage reported by all providers
+    pub prompt_cache: CacheUsage,
+    /// Input tokens reported by all providers for streamed responses
+    pub input_tokens: u64,
+    /// Output tokens reported by all providers for streamed responses
+    pub output_tokens: u64,
+}
+
+impl KernelMetrics {
+    /// Returns the sum of the latest metrics reported by all agents.
+    #[must_use]
+    pub fn total_agent_metrics(&self) -> AgentMetrics {
+        self.agent_metrics.values().cloned().sum()
+    }
+
+    /// Estimates the input tokens saved by prompt caching across all agents.
+    ///
+    /// See [`CacheUsage::estimated_tokens_saved`].
+    #[must_use]
+    pub fn estimated_cache_tokens_saved(&self) -> f64 {
+        self.prompt_cache.estimated_tokens_saved()
+    }
+
+    /// Adds prompt cache usage reported by a provider.
+    fn record_prompt_cache(&mut self, cache_write_tokens: u64, cache_read_tokens: u64) {
+        self.prompt_cache.cache_creation_tokens += cache_write_tokens;
+        self.prompt_cache.cache_read_tokens += cache_read_tokens;
+    }
+
+    /// Adds the token usage of a streamed response.
+    fn record_token_usage(&mut self, usage: &TokenUsage) {
+        self.input_tokens = self.input_tokens.saturating_add(usage.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(usage.output_tokens);
+    }
 }
 
 /// Message to initialize the kernel with configuration.
@@ -49,8 +108,103 @@ pub struct Kernel {
     pub shutting_down: bool,
     /// Registry of agent capabilities for discovery
     pub capability_registry: CapabilityRegistry,
+    /// Configurations of registered agents, used to restart them
+    pub agent_configs: HashMap<String, AgentConfig>,
+    /// Heartbeats awaiting an acknowledgement (AgentId -> sent at)
+    pub pending_heartbeats: HashMap<String, Instant>,
+    /// Last state each agent reported in a heartbeat acknowledgement
+    pub agent_states: HashMap<AgentId, AgentState>,
+    /// Periodic task driving the watchdog
+    watchdog_task: Option<tokio::task::JoinHandle<()>>,
+    /// Slots limiting concurrent tool calls; None when unlimited
+    tool_slots: Option<Arc<Semaphore>>,
+    /// Tool calls waiting for a free slot
+    tool_call_queue: VecDeque<ScheduleToolCall>,
+    /// Slots held by running tool calls, by (correlation ID, tool call ID)
+    tool_calls_in_flight: HashMap<(String, String), OwnedSemaphorePermit>,
+    /// Memory store handed to agents configured with a memory limit
+    memory_store: Option<ActorHandle>,
 }
 
+impl Kernel {
+    /// Takes slots for queued tool calls while any are free, returning the
+    /// calls to dispatch.
+    fn dequeue_tool_calls(&mut self) -> Vec<ScheduleToolCall> {
+        let mut ready = Vec::new();
+        if let Some(slots) = &self.tool_slots {
+            while !self.tool_call_queue.is_empty() {
+                let Ok(permit) = Arc::clone(slots).try_acquire_owned() else {
+                    break;
+                };
+                let Some(request) = self.tool_call_queue.pop_front() else {
+                    break;
+                };
+                let key = (
+                    request.call.correlation_id.to_string(),
+                    request.call.tool_call_id.clone(),
+                );
+                self.tool_calls_in_flight.insert(key, permit);
+                ready.push(request);
+            }
+        } else {
+            ready.extend(self.tool_call_queue.drain(..));
+        }
+
+        self.metrics.queued_tool_calls = self.tool_call_queue.len();
+        self.metrics.peak_concurrent_tool_calls = self
+            .metrics
+            .peak_concurrent_tool_calls
+            .max(self.tool_calls_in_flight.len());
+        ready
+    }
+}
+
+/// Asks the kernel to send `call` to `tool` once a tool call slot is free
+/// (see [`KernelConfig::max_concurrent_tool_calls`]).
+///
+/// Registered agents send their tool calls this way when the kernel limits
+/// concurrent tool calls.
+#[acton_message]
+pub struct ScheduleToolCall {
+    /// The tool actor to execute the call
+    pub tool: ActorHandle,
+    /// The call to execute
+    pub call: ExecuteToolDirect,
+}
+
+/// Registers a running agent with the kernel.
+///
+/// Registered agents receive routed messages and are watched by the
+/// heartbeat watchdog. `config` is used to respawn the agent when
+/// [`KernelConfig::agent_watchdog_restart`] is enabled; tool actors
+/// registered with the original agent are not carried over.
+#[acton_message]
+pub struct RegisterAgent {
+    /// The ID the agent was initialized with
+    pub agent_id: AgentId,
+    /// The running agent
+    pub handle: ActorHandle,
+    /// The configuration the agent was initialized with
+    pub config: AgentConfig,
+}
+
+/// Frees the slot of a throttled tool call that has not responded within
+/// [`KernelConfig::tool_call_timeout`].
+#[acton_message]
+struct ToolCallTimedOut {
+    correlation_id: String,
+    tool_call_id: String,
+}
+
+/// Sends a heartbeat to every registered agent.
+#[acton_message]
+struct SendHeartbeats;
+
+/// Fires the watchdog for heartbeats that have not been acknowledged in
+/// time.
+#[acton_message]
+struct CheckHeartbeats;
+
 impl Kernel {
     /// Spawns the Kernel actor with default configuration.
     ///
@@ -68,6 +222,60 @@ impl Kernel {
         Self::spawn_with_config(runtime, KernelConfig::default()).await
     }
 
+    /// Asks `kernel` to spawn an agent defined in YAML (see
+    /// [`AgentConfig::from_yaml`]).
+    ///
+    /// # Returns
+    ///
+    /// The ID assigned to the agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `yaml` is not a valid agent definition; nothing is
+    /// sent to the kernel in that case.
+    pub async fn spawn_agent_from_yaml(
+        kernel: &ActorHandle,
+        yaml: &str,
+    ) -> Result<AgentId, serde_yaml::Error> {
+        let config = AgentConfig::from_yaml(yaml)?;
+        let agent_id = config.agent_id();
+        kernel
+            .send(SpawnAgent {
+                config: config.with_id(agent_id.clone()),
+            })
+            .await;
+        Ok(agent_id)
+    }
+
+    /// Sends `message` to every agent registered with `kernel` but its
+    /// sender.
+    ///
+    /// `message.to` is ignored. Sends a [`BroadcastMessage`] and waits for
+    /// the kernel's [`BroadcastComplete`]; returns `None` if the kernel does
+    /// not reply, for example because it has already stopped.
+    pub async fn broadcast(
+        runtime: &mut ActorRuntime,
+        kernel: &ActorHandle,
+        message: AgentMessage,
+    ) -> Option<BroadcastComplete> {
+        let broadcast = BroadcastMessage::from(message);
+        query_store(runtime, kernel, broadcast, BROADCAST_REPLY_TIMEOUT).await
+    }
+
+    /// Sends `message` to every agent registered with `kernel` that
+    /// announced `capability`, except its sender.
+    ///
+    /// See [`broadcast`](Self::broadcast).
+    pub async fn broadcast_to_capable(
+        runtime: &mut ActorRuntime,
+        kernel: &ActorHandle,
+        capability: &str,
+        message: AgentMessage,
+    ) -> Option<BroadcastComplete> {
+        let broadcast = BroadcastMessage::from(message).with_capability(capability);
+        query_store(runtime, kernel, broadcast, BROADCAST_REPLY_TIMEOUT).await
+    }
+
     /// Spawns the Kernel actor with the given configuration.
     ///
     /// If logging is configured, this will automatically initialize file-based logging
@@ -126,6 +334,9 @@ impl Kernel {
                 Reply::ready()
             })
             .before_stop(|actor| {
+                if let Some(task) = &actor.model.watchdog_task {
+                    task.abort();
+                }
                 tracing::info!(
                     active_agents = actor.model.agents.len(),
                     total_spawned = actor.model.metrics.agents_spawned,
@@ -139,6 +350,19 @@ impl Kernel {
 
         let handle = builder.start().await;
 
+        // Agents report their metrics at the end of each turn
+        handle.subscribe::<AgentMetricsResponse>().await;
+        // Providers report prompt cache usage as system events
+        handle.subscribe::<SystemEvent>().await;
+        // Providers report the token usage of each streamed response
+        handle.subscribe::<LLMStreamUsage>().await;
+        // Agents report delegated task results for routing to the delegator
+        handle.subscribe::<TaskResult>().await;
+        // Tool responses free throttled tool call slots
+        if config.max_concurrent_tool_calls.is_some() {
+            handle.subscribe::<ToolActorResponse>().await;
+        }
+
         // Initialize kernel with config
         handle
             .send(InitKernel {
@@ -163,9 +387,142 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Kernel>) {
             "Kernel configured"
         );
 
+        if let Some(previous) = actor.model.watchdog_task.take() {
+            previous.abort();
+        }
+        actor.model.tool_slots = actor
+            .model
+            .config
+            .max_concurrent_tool_calls
+            .map(|max| Arc::new(Semaphore::new(max.get())));
+
+        let interval = actor.model.config.heartbeat_interval;
+        if !interval.is_zero() {
+            actor.model.watchdog_task = Some(spawn_watchdog(
+                interval,
+                actor.model.config.heartbeat_timeout,
+                actor.handle().clone(),
+            ));
+        }
+
         Reply::ready()
     });
 
+    // Handle agent registration
+    builder.mutate_on::<RegisterAgent>(|actor, envelope| {
+        let msg = envelope.message();
+        let agent_id_str = msg.agent_id.to_string();
+        tracing::info!(agent_id = %msg.agent_id, "Registering agent");
+
+        actor
+            .model
+            .agents
+            .insert(agent_id_str.clone(), msg.handle.clone());
+        actor.model.agent_configs.insert(
+            agent_id_str.clone(),
+            msg.config.clone().with_id(msg.agent_id.clone()),
+        );
+        actor.model.pending_heartbeats.remove(&agent_id_str);
+
+        // Give memory-enabled agents the kernel's memory store
+        let memory_store = actor
+            .model
+            .memory_store
+            .clone()
+            .filter(|_| msg.config.memory_limit.is_some());
+        // Route the agent's tool calls through the kernel's queue
+        let kernel = actor
+            .model
+            .tool_slots
+            .is_some()
+            .then(|| actor.handle().clone());
+        if memory_store.is_none() && kernel.is_none() {
+            return Reply::ready();
+        }
+
+        let agent = msg.handle.clone();
+        Reply::pending(async move {
+            if let Some(store) = memory_store {
+                agent.send(UseMemoryStore { store }).await;
+            }
+            if let Some(kernel) = kernel {
+                agent.send(UseToolThrottle { kernel }).await;
+            }
+        })
+    });
+
+    // Hand the memory store to memory-enabled agents, now and as they
+    // register
+    builder.mutate_on::<UseMemoryStore>(|actor, envelope| {
+        let store = envelope.message().store.clone();
+        actor.model.memory_store = Some(store.clone());
+        tracing::info!("Kernel memory store attached");
+
+        let agents: Vec<ActorHandle> = actor
+            .model
+            .agent_configs
+            .iter()
+            .filter(|(_, config)| config.memory_limit.is_some())
+            .filter_map(|(agent_id, _)| actor.model.agents.get(agent_id).cloned())
+            .collect();
+        Reply::pending(async move {
+            for agent in agents {
+                agent
+                    .send(UseMemoryStore {
+                        store: store.clone(),
+                    })
+                    .await;
+            }
+        })
+    });
+
+    // Queue tool calls, dispatching as many as there are free slots
+    builder.mutate_on::<ScheduleToolCall>(|actor, envelope| {
+        actor
+            .model
+            .tool_call_queue
+            .push_back(envelope.message().clone());
+        let ready = actor.model.dequeue_tool_calls();
+        let timeout = actor.model.config.tool_call_timeout;
+        let kernel = actor.handle().clone();
+
+        Reply::pending(dispatch_tool_calls(ready, kernel, timeout))
+    });
+
+    // A finished tool call frees its slot for the next queued one
+    builder.mutate_on::<ToolActorResponse>(|actor, envelope| {
+        let msg = envelope.message();
+        let key = (msg.correlation_id.to_string(), msg.tool_call_id.clone());
+        if actor.model.tool_calls_in_flight.remove(&key).is_none() {
+            return Reply::ready();
+        }
+        let ready = actor.model.dequeue_tool_calls();
+        let timeout = actor.model.config.tool_call_timeout;
+        let kernel = actor.handle().clone();
+
+        Reply::pending(dispatch_tool_calls(ready, kernel, timeout))
+    });
+
+    // A tool call that never responded frees its slot so the queue keeps
+    // moving
+    builder.mutate_on::<ToolCallTimedOut>(|actor, envelope| {
+        let msg = envelope.message();
+        let key = (msg.correlation_id.clone(), msg.tool_call_id.clone());
+        if actor.model.tool_calls_in_flight.remove(&key).is_none() {
+            return Reply::ready();
+        }
+        tracing::warn!(
+            correlation_id = %msg.correlation_id,
+            tool_call_id = %msg.tool_call_id,
+            "Tool call did not respond in time; freeing its slot"
+        );
+        let ready = actor.model.dequeue_tool_calls();
+        let timeout = actor.model.config.tool_call_timeout;
+        let kernel = actor.handle().clone();
+
+        Reply::pending(dispatch_tool_calls(ready, kernel, timeout))
+    });
+
     // Handle SpawnAgent requests
     builder.mutate_on::<SpawnAgent>(|actor, envelope| {
         let config = envelope.message().config.clone();
@@ -223,6 +580,11 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Kernel>) {
         let agent_id = &envelope.message().agent_id;
         let agent_id_str = agent_id.to_string();
 
+        actor.model.metrics.agent_metrics.remove(agent_id);
+        actor.model.agent_configs.remove(&agent_id_str);
+        actor.model.pending_heartbeats.remove(&agent_id_str);
+        actor.model.agent_states.remove(agent_id);
+
         if let Some(handle) = actor.model.agents.remove(&agent_id_str) {
             tracing::info!(agent_id = %agent_id, "Stopping agent");
 
@@ -306,6 +668,192 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Kernel>) {
         }
     });
 
+    // Forward pause and resume requests to the agent
+    builder.act_on::<PauseAgent>(|actor, envelope| {
+        let msg = envelope.message().clone();
+        match actor.model.agents.get(&msg.agent_id.to_string()) {
+            Some(handle) => {
+                let handle = handle.clone();
+                Reply::pending(async move {
+                    handle.send(msg).await;
+                })
+            }
+            None => {
+                tracing::warn!(agent_id = %msg.agent_id, "Agent not found for pause request");
+                Reply::ready()
+            }
+        }
+    });
+
+    builder.act_on::<ResumeAgent>(|actor, envelope| {
+        let msg = envelope.message().clone();
+        match actor.model.agents.get(&msg.agent_id.to_string()) {
+            Some(handle) => {
+                let handle = handle.clone();
+                Reply::pending(async move {
+                    handle.send(msg).await;
+                })
+            }
+            None => {
+                tracing::warn!(agent_id = %msg.agent_id, "Agent not found for resume request");
+                Reply::ready()
+            }
+        }
+    });
+
+    // Record metrics reported by agents
+    builder.mutate_on::<AgentMetricsResponse>(|actor, envelope| {
+        let msg = envelope.message();
+        actor
+            .model
+            .metrics
+            .agent_metrics
+            .insert(msg.agent_id.clone(), msg.metrics.clone());
+        Reply::ready()
+    });
+
+    // Aggregate prompt cache usage reported by providers and release the
+    // tools of stopped agents
+    builder.mutate_on::<SystemEvent>(|actor, envelope| match envelope.message() {
+        SystemEvent::PromptCacheUsed {
+            cache_write_tokens,
+            cache_read_tokens,
+            ..
+        } => {
+            actor
+                .model
+                .metrics
+                .record_prompt_cache(*cache_write_tokens, *cache_read_tokens);
+            Reply::ready()
+        }
+        SystemEvent::AgentStopped { id, .. } => {
+            let broker = actor.broker().clone();
+            let agent_id = id.clone();
+            Reply::pending(async move {
+                broker.broadcast(UnregisterAllForAgent { agent_id }).await;
+            })
+        }
+        _ => Reply::ready(),
+    });
+
+    // Aggregate token usage reported by providers
+    builder.mutate_on::<LLMStreamUsage>(|actor, envelope| {
+        actor
+            .model
+            .metrics
+            .record_token_usage(&envelope.message().usage);
+        Reply::ready()
+    });
+
+    // Handle GetAllAgentMetrics requests
+    builder.act_on::<GetAllAgentMetrics>(|actor, envelope| {
+        let reply = envelope.reply_envelope();
+        let mut agents: Vec<AgentMetricsResponse> = actor
+            .model
+            .metrics
+            .agent_metrics
+            .iter()
+            .map(|(agent_id, metrics)| AgentMetricsResponse {
+                agent_id: agent_id.clone(),
+                metrics: metrics.clone(),
+            })
+            .collect();
+        agents.sort_by_key(|agent| agent.agent_id.to_string());
+
+        Reply::pending(async move {
+            reply.send(AllAgentMetrics { agents }).await;
+        })
+    });
+
+    // Watchdog: ping every registered agent not already awaiting an ack
+    builder.mutate_on::<SendHeartbeats>(|actor, _envelope| {
+        let now = Instant::now();
+        let mut targets = Vec::new();
+        for (agent_id, handle) in &actor.model.agents {
+            if !actor.model.pending_heartbeats.contains_key(agent_id) {
+                actor.model.pending_heartbeats.insert(agent_id.clone(), now);
+                targets.push(handle.clone());
+            }
+        }
+
+        let kernel = actor.handle().clone();
+        Reply::pending(async move {
+            for target in targets {
+                kernel
+                    .create_envelope(Some(target.reply_address()))
+                    .send(Heartbeat)
+                    .await;
+            }
+        })
+    });
+
+    // Watchdog: an acknowledged heartbeat clears the pending entry
+    builder.mutate_on::<HeartbeatAck>(|actor, envelope| {
+        let msg = envelope.message();
+        tracing::trace!(
+            agent_id = %msg.agent_id,
+            state = %msg.state,
+            "Heartbeat acknowledged"
+        );
+        actor
+            .model
+            .pending_heartbeats
+            .remove(&msg.agent_id.to_string());
+        actor
+            .model
+            .agent_states
+            .insert(msg.agent_id.clone(), msg.state);
+        Reply::ready()
+    });
+
+    // Watchdog: fire for heartbeats that were not acknowledged in time
+    builder.mutate_on::<CheckHeartbeats>(|actor, _envelope| {
+        let timeout = actor.model.config.heartbeat_timeout;
+        let overdue: Vec<String> = actor
+            .model
+            .pending_heartbeats
+            .iter()
+            .filter(|(_, sent_at)| sent_at.elapsed() >= timeout)
+            .map(|(agent_id, _)| agent_id.clone())
+            .collect();
+        if overdue.is_empty() {
+            return Reply::ready();
+        }
+
+        let restart = actor.model.config.agent_watchdog_restart;
+        let mut triggered = Vec::new();
+        let mut restarts = Vec::new();
+        for agent_id_str in overdue {
+            actor.model.pending_heartbeats.remove(&agent_id_str);
+            let Ok(agent_id) = AgentId::parse(&agent_id_str) else {
+                continue;
+            };
+            let reason = format!("no heartbeat acknowledgement within {timeout:?}");
+            tracing::warn!(agent_id = %agent_id, reason = %reason, "Agent watchdog triggered");
+            actor.model.metrics.watchdog_trigger_count += 1;
+
+            if restart {
+                let handle = actor.model.agents.remove(&agent_id_str);
+                if let Some(config) = actor.model.agent_configs.remove(&agent_id_str) {
+                    restarts.push((agent_id.clone(), handle, config));
+                }
+            }
+            triggered.push(WatchdogTriggered { agent_id, reason });
+        }
+
+        let broker = actor.broker().clone();
+        let kernel = actor.handle().clone();
+        let mut runtime = actor.runtime().clone();
+        Reply::pending(async move {
+            for event in triggered {
+                broker.broadcast(event).await;
+            }
+            for (agent_id, old_handle, config) in restarts {
+                restart_agent(&mut runtime, &kernel, agent_id, old_handle, config, timeout).await;
+            }
+        })
+    });
+
     // Handle ChildTerminated events for supervised agents
     builder.mutate_on::<ChildTerminated>(|_actor, envelope| {
         let msg = envelope.message();
@@ -355,6 +903,70 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Kernel>) {
         }
     });
 
+    // Handle BroadcastMessage - fan out to every registered agent
+    builder.mutate_on::<BroadcastMessage>(|actor, envelope| {
+        let msg = envelope.message();
+        let reply = envelope.reply_envelope();
+
+        let excluded: HashSet<String> = msg.exclude.iter().map(ToString::to_string).collect();
+        let capable: Option<HashSet<String>> = msg.capability.as_deref().map(|capability| {
+            actor
+                .model
+                .capability_registry
+                .find_all_capable_agents(capability)
+                .iter()
+                .map(ToString::to_string)
+                .collect()
+        });
+        let targets: Vec<ActorHandle> = actor
+            .model
+            .agents
+            .iter()
+            .filter(|(agent_id, _)| !excluded.contains(*agent_id))
+            .filter(|(agent_id, _)| capable.as_ref().is_none_or(|c| c.contains(*agent_id)))
+            .map(|(_, handle)| handle.clone())
+            .collect();
+
+        tracing::debug!(
+            from = %msg.from,
+            capability = ?msg.capability,
+            recipients = targets.len(),
+            "Broadcasting agent message"
+        );
+
+        actor.model.metrics.messages_routed += targets.len();
+
+        let incoming = IncomingAgentMessage {
+            from: msg.from.clone(),
+            content: msg.content.clone(),
+            metadata: msg.metadata.clone(),
+        };
+        let kernel = actor.handle().clone();
+        Reply::pending(async move {
+            let mut sends: FuturesUnordered<_> = targets
+                .into_iter()
+                .map(|target| {
+                    let envelope = kernel.create_envelope(Some(target.reply_address()));
+                    let incoming = incoming.clone();
+                    async move { envelope.try_send(incoming).await.is_ok() }
+                })
+                .collect();
+
+            let mut complete = BroadcastComplete {
+                sent_count: 0,
+                failed_count: 0,
+            };
+            while let Some(delivered) = sends.next().await {
+                if delivered {
+                    complete.sent_count += 1;
+                } else {
+                    complete.failed_count += 1;
+                }
+            }
+            reply.send(complete).await;
+        })
+    });
+
     // Handle DelegateTask - route to target agent
     builder.try_mutate_on::<DelegateTask, (), crate::error::MultiAgentError>(|actor, envelope| {
         let msg = envelope.message();
@@ -363,6 +975,12 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Kernel>) {
         if let Some(target_handle) = actor.model.agents.get(&to_str) {
             let handle = target_handle.clone();
             let incoming = IncomingTask::from_delegate(msg);
+            // The delegating agent tracks the task and keeps its callback
+            let delegator = actor
+                .model
+                .agents
+                .get(&msg.from.to_string())
+                .map(|delegator| (delegator.clone(), msg.clone()));
 
             tracing::info!(
                 from = %msg.from,
@@ -375,6 +993,9 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Kernel>) {
             actor.model.metrics.messages_routed += 1;
 
             Reply::try_pending(async move {
+                if let Some((delegator, delegation)) = delegator {
+                    delegator.send(delegation).await;
+                }
                 handle.send(incoming).await;
                 Ok(())
             })
@@ -386,14 +1007,37 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Kernel>) {
         }
     });
 
+    // Handle TaskResult - route to the delegating agent
+    builder.mutate_on::<TaskResult>(|actor, envelope| {
+        let msg = envelope.message();
+
+        let Some(handle) = actor.model.agents.get(&msg.to.to_string()) else {
+            tracing::debug!(
+                to = %msg.to,
+                task_id = %msg.task_id,
+                "Delegating agent not registered for task result"
+            );
+            return Reply::ready();
+        };
+
+        actor.model.metrics.messages_routed += 1;
+
+        let handle = handle.clone();
+        let result = msg.clone();
+        Reply::pending(async move {
+            handle.send(result).await;
+        })
+    });
+
     // Handle AnnounceCapabilities - update capability registry
     builder.mutate_on::<AnnounceCapabilities>(|actor, envelope| {
         let msg = envelope.message();
 
-        actor
-            .model
-            .capability_registry
-            .register(msg.agent_id.clone(), msg.capabilities.clone());
+        actor.model.capability_registry.register_with_scores(
+            msg.agent_id.clone(),
+            msg.capabilities.clone(),
+            msg.scores.clone().unwrap_or_default(),
+        );
 
         tracing::info!(
             agent_id = %msg.agent_id,
@@ -430,6 +1074,114 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, Kernel>) {
             reply.send(response).await;
         })
     });
+
+    // Handle FindBestCapableAgent - pick the highest scored agent
+    builder.act_on::<FindBestCapableAgent>(|actor, envelope| {
+        let msg = envelope.message();
+        let reply = envelope.reply_envelope();
+
+        let states = &actor.model.agent_states;
+        let agent_id = actor
+            .model
+            .capability_registry
+            .find_best_capable_agent_weighted(&msg.capability, |agent_id, score| {
+                match states.get(agent_id) {
+                    Some(state) if msg.prefer_idle => state.weighted_score(score),
+                    _ => score,
+                }
+            });
+
+        tracing::debug!(
+            capability = %msg.capability,
+            prefer_idle = msg.prefer_idle,
+            found = ?agent_id,
+            "Best capability search"
+        );
+
+        let response = CapableAgentFound {
+            correlation_id: msg.correlation_id.clone(),
+            agent_id,
+            capability: msg.capability.clone(),
+        };
+
+        Reply::pending(async move {
+            reply.send(response).await;
+        })
+    });
+}
+
+/// Spawns the task that drives the watchdog: every `interval` it has the
+/// kernel send heartbeats, then checks for missing acknowledgements
+/// `timeout` later.
+fn spawn_watchdog(
+    interval: Duration,
+    timeout: Duration,
+    kernel: ActorHandle,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let start = tokio::time::Instant::now() + interval;
+        let mut ticks = tokio::time::interval_at(start, interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticks.tick().await;
+            kernel.send(SendHeartbeats).await;
+            tokio::time::sleep(timeout).await;
+            kernel.send(CheckHeartbeats).await;
+        }
+    })
+}
+
+/// Sends dequeued tool calls to their tool actors, arming a timeout for each
+/// that frees its slot if the tool never responds.
+async fn dispatch_tool_calls(ready: Vec<ScheduleToolCall>, kernel: ActorHandle, timeout: Duration) {
+    for request in ready {
+        let timed_out = ToolCallTimedOut {
+            correlation_id: request.call.correlation_id.to_string(),
+            tool_call_id: request.call.tool_call_id.clone(),
+        };
+        request.tool.send(request.call).await;
+        let kernel = kernel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+            kernel.send(timed_out).await;
+        });
+    }
+}
+
+/// Replaces an unresponsive agent with a fresh one built from `config`.
+async fn restart_agent(
+    runtime: &mut ActorRuntime,
+    kernel: &ActorHandle,
+    agent_id: AgentId,
+    old_handle: Option<ActorHandle>,
+    config: AgentConfig,
+    stop_timeout: Duration,
+) {
+    if let Some(old_handle) = old_handle {
+        // A hung agent may never finish stopping
+        let _ = tokio::time::timeout(stop_timeout, old_handle.stop()).await;
+    }
+
+    let handle = match kernel.supervise(Agent::create(runtime)).await {
+        Ok(handle) => handle,
+        Err(e) => {
+            tracing::error!(agent_id = %agent_id, error = %e, "Failed to restart agent");
+            return;
+        }
+    };
+    handle
+        .send(InitAgent {
+            config: config.clone(),
+        })
+        .await;
+    kernel
+        .send(RegisterAgent {
+            agent_id: agent_id.clone(),
+            handle,
+            config,
+        })
+        .await;
+    tracing::info!(agent_id = %agent_id, "Agent restarted by watchdog");
 }
 
 #[cfg(test)]
@@ -442,5 +1194,80 @@ mod tests {
         assert_eq!(metrics.agents_spawned, 0);
         assert_eq!(metrics.agents_stopped, 0);
         assert_eq!(metrics.messages_routed, 0);
+        assert!(metrics.agent_metrics.is_empty());
+        assert_eq!(metrics.watchdog_trigger_count, 0);
+        assert_eq!(metrics.input_tokens, 0);
+        assert_eq!(metrics.output_tokens, 0);
+    }
+
+    #[tokio::test]
+    async fn tool_calls_queue_beyond_the_limit() {
+        let mut kernel = Kernel {
+            tool_slots: Some(Arc::new(Semaphore::new(2))),
+            ..Kernel::default()
+        };
+        let correlation_id = crate::types::CorrelationId::new();
+        for i in 0..3 {
+            kernel.tool_call_queue.push_back(ScheduleToolCall {
+                tool: ActorHandle::default(),
+                call: ExecuteToolDirect::new(
+                    correlation_id.clone(),
+                    format!("call_{i}"),
+                    serde_json::json!({}),
+                ),
+            });
+        }
+
+        let ready = kernel.dequeue_tool_calls();
+        assert_eq!(ready.len(), 2);
+        assert_eq!(kernel.metrics.queued_tool_calls, 1);
+        assert_eq!(kernel.metrics.peak_concurrent_tool_calls, 2);
+        assert!(kernel.dequeue_tool_calls().is_empty());
+
+        kernel
+            .tool_calls_in_flight
+            .remove(&(correlation_id.to_string(), "call_0".to_string()));
+        let ready = kernel.dequeue_tool_calls();
+        assert_eq!(ready.len(), 1);
+        assert_eq!(ready[0].call.tool_call_id, "call_2");
+        assert_eq!(kernel.metrics.queued_tool_calls, 0);
+        assert_eq!(kernel.metrics.peak_concurrent_tool_calls, 2);
+    }
+
+    #[test]
+    fn total_agent_metrics_sums_agents() {
+        let mut metrics = KernelMetrics::default();
+        for calls in [2, 3] {
+            metrics.agent_metrics.insert(
+                AgentId::new(),
+                AgentMetrics {
+                    total_llm_calls: calls,
+                    ..AgentMetrics::default()
+                },
+            );
+        }
+        assert_eq!(metrics.total_agent_metrics().total_llm_calls, 5);
+    }
+
+    #[test]
+    fn prompt_cache_usage_accumulates() {
+        let mut metrics = KernelMetrics::default();
+        metrics.record_prompt_cache(1_000, 0);
+        metrics.record_prompt_cache(0, 2_000);
+        metrics.record_prompt_cache(0, 2_000);
+
+        assert_eq!(metrics.prompt_cache.cache_creation_tokens, 1_000);
+        assert_eq!(metrics.prompt_cache.cache_read_tokens, 4_000);
+        assert!((metrics.estimated_cache_tokens_saved() - 3_350.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn token_usage_accumulates() {
+        let mut metrics = KernelMetrics::default();
+        metrics.record_token_usage(&TokenUsage::new(100, 20));
+        metrics.record_token_usage(&TokenUsage::new(50, 5).with_cache_read_tokens(30));
+
+        assert_eq!(metrics.input_tokens, 150);
+        assert_eq!(metrics.output_tokens, 25);
     }
 }
@@ -4,6 +4,38 @@
 
 use crate::kernel::logging::LoggingConfig;
 use serde::{Deserialize, Serialize};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Default time [`ActonAI::shutdown`](crate::facade::ActonAI::shutdown) waits
+/// for in-flight requests before stopping actors.
+pub const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn default_shutdown_drain_timeout() -> Duration {
+    DEFAULT_SHUTDOWN_DRAIN_TIMEOUT
+}
+
+/// Default time between watchdog heartbeats.
+pub const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+fn default_heartbeat_interval() -> Duration {
+    DEFAULT_HEARTBEAT_INTERVAL
+}
+
+/// Default time an agent has to answer a heartbeat.
+pub const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn default_heartbeat_timeout() -> Duration {
+    DEFAULT_HEARTBEAT_TIMEOUT
+}
+
+/// Default time a throttled tool call may hold its slot without a response.
+pub const DEFAULT_TOOL_CALL_TIMEOUT: Duration = Duration::from_secs(300);
+
+fn default_tool_call_timeout() -> Duration {
+    DEFAULT_TOOL_CALL_TIMEOUT
+}
 
 /// Configuration for the Kernel actor.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -14,8 +46,34 @@ pub struct KernelConfig {
     pub enable_metrics: bool,
     /// Default system prompt for agents without one specified.
     pub default_system_prompt: Option<String>,
+    /// File to read the default system prompt from; overrides
+    /// `default_system_prompt` when set.
+    #[serde(default)]
+    pub default_system_prompt_file: Option<PathBuf>,
     /// File logging configuration. None disables file logging.
     pub logging: Option<LoggingConfig>,
+    /// How long shutdown waits for in-flight requests to finish.
+    #[serde(default = "default_shutdown_drain_timeout")]
+    pub shutdown_drain_timeout: Duration,
+    /// How often the watchdog sends a heartbeat to each registered agent.
+    /// Zero disables the watchdog.
+    #[serde(default = "default_heartbeat_interval")]
+    pub heartbeat_interval: Duration,
+    /// How long an agent has to answer a heartbeat before the watchdog
+    /// fires.
+    #[serde(default = "default_heartbeat_timeout")]
+    pub heartbeat_timeout: Duration,
+    /// Whether the watchdog restarts agents that miss a heartbeat.
+    #[serde(default)]
+    pub agent_watchdog_restart: bool,
+    /// Maximum number of tool calls running at once across all registered
+    /// agents. None means unlimited.
+    #[serde(default)]
+    pub max_concurrent_tool_calls: Option<NonZeroUsize>,
+    /// How long a throttled tool call holds its slot before the kernel frees
+    /// it, in case the tool actor never responds.
+    #[serde(default = "default_tool_call_timeout")]
+    pub tool_call_timeout: Duration,
 }
 
 impl KernelConfig {
@@ -46,6 +104,29 @@ impl KernelConfig {
         self
     }
 
+    /// Sets a file to read the default system prompt from.
+    #[must_use]
+    pub fn with_default_system_prompt_file(mut self, path: PathBuf) -> Self {
+        self.default_system_prompt_file = Some(path);
+        self
+    }
+
+    /// Returns the default system prompt, reading
+    /// `default_system_prompt_file` if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `default_system_prompt_file` is set but cannot be
+    /// read.
+    pub fn resolve_default_system_prompt(
+        &self,
+    ) -> Result<Option<String>, crate::error::ActonAIError> {
+        match &self.default_system_prompt_file {
+            Some(path) => crate::system_prompt::read_system_prompt_file(path).map(Some),
+            None => Ok(self.default_system_prompt.clone()),
+        }
+    }
+
     /// Sets the logging configuration.
     #[must_use]
     pub fn with_logging(mut self, config: LoggingConfig) -> Self {
@@ -60,6 +141,50 @@ impl KernelConfig {
         self
     }
 
+    /// Sets how long shutdown waits for in-flight requests to finish.
+    #[must_use]
+    pub fn with_shutdown_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_drain_timeout = timeout;
+        self
+    }
+
+    /// Sets how often the watchdog sends heartbeats; zero disables it.
+    #[must_use]
+    pub fn with_heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.heartbeat_interval = interval;
+        self
+    }
+
+    /// Sets how long an agent has to answer a heartbeat.
+    #[must_use]
+    pub fn with_heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.heartbeat_timeout = timeout;
+        self
+    }
+
+    /// Sets whether the watchdog restarts agents that miss a heartbeat.
+    #[must_use]
+    pub fn with_agent_watchdog_restart(mut self, restart: bool) -> Self {
+        self.agent_watchdog_restart = restart;
+        self
+    }
+
+    /// Limits how many tool calls registered agents may run at once; further
+    /// calls queue in the kernel until a running one responds.
+    #[must_use]
+    pub fn with_max_concurrent_tool_calls(mut self, max: NonZeroUsize) -> Self {
+        self.max_concurrent_tool_calls = Some(max);
+        self
+    }
+
+    /// Sets how long a throttled tool call holds its slot without a
+    /// response.
+    #[must_use]
+    pub fn with_tool_call_timeout(mut self, timeout: Duration) -> Self {
+        self.tool_call_timeout = timeout;
+        self
+    }
+
     /// Sets the application name for log files.
     ///
     /// If logging is not yet configured, creates a default logging config
@@ -82,7 +207,14 @@ impl Default for KernelConfig {
             max_agents: 100,
             enable_metrics: true,
             default_system_prompt: None,
+            default_system_prompt_file: None,
             logging: Some(LoggingConfig::default()),
+            shutdown_drain_timeout: DEFAULT_SHUTDOWN_DRAIN_TIMEOUT,
+            heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
+            heartbeat_timeout: DEFAULT_HEARTBEAT_TIMEOUT,
+            agent_watchdog_restart: false,
+            max_concurrent_tool_calls: None,
+            tool_call_timeout: DEFAULT_TOOL_CALL_TIMEOUT,
         }
     }
 }
@@ -99,6 +231,50 @@ mod tests {
         assert!(config.enable_metrics);
         assert!(config.default_system_prompt.is_none());
         assert!(config.logging.is_some());
+        assert_eq!(config.shutdown_drain_timeout, Duration::from_secs(30));
+    }
+
+    #[test]
+    fn with_shutdown_drain_timeout_sets_value() {
+        let config = KernelConfig::new().with_shutdown_drain_timeout(Duration::from_secs(5));
+        assert_eq!(config.shutdown_drain_timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn watchdog_defaults_and_builders() {
+        let config = KernelConfig::default();
+        assert_eq!(config.heartbeat_interval, Duration::from_secs(30));
+        assert_eq!(config.heartbeat_timeout, Duration::from_secs(10));
+        assert!(!config.agent_watchdog_restart);
+
+        let config = config
+            .with_heartbeat_interval(Duration::from_secs(5))
+            .with_heartbeat_timeout(Duration::from_secs(1))
+            .with_agent_watchdog_restart(true);
+        assert_eq!(config.heartbeat_interval, Duration::from_secs(5));
+        assert_eq!(config.heartbeat_timeout, Duration::from_secs(1));
+        assert!(config.agent_watchdog_restart);
+    }
+
+    #[test]
+    fn tool_calls_are_unlimited_by_default() {
+        assert_eq!(KernelConfig::default().max_concurrent_tool_calls, None);
+        assert_eq!(
+            KernelConfig::default().tool_call_timeout,
+            Duration::from_secs(300)
+        );
+        let config = KernelConfig::new()
+            .with_max_concurrent_tool_calls(NonZeroUsize::new(4).unwrap())
+            .with_tool_call_timeout(Duration::from_secs(5));
+        assert_eq!(config.max_concurrent_tool_calls, NonZeroUsize::new(4));
+        assert_eq!(config.tool_call_timeout, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn zero_tool_call_limit_is_rejected() {
+        let mut value = serde_json::to_value(KernelConfig::default()).unwrap();
+        value["max_concurrent_tool_calls"] = serde_json::json!(0);
+        assert!(serde_json::from_value::<KernelConfig>(value).is_err());
     }
 
     #[test]
@@ -124,6 +300,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn default_system_prompt_file_overrides_string() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "From file").unwrap();
+
+        let config = KernelConfig::new().with_default_system_prompt("Inline");
+        assert_eq!(
+            config.resolve_default_system_prompt().unwrap().as_deref(),
+            Some("Inline")
+        );
+
+        let config = config.with_default_system_prompt_file(file.path().to_path_buf());
+        assert_eq!(
+            config.resolve_default_system_prompt().unwrap().as_deref(),
+            Some("From file")
+        );
+    }
+
     #[test]
     fn with_logging_sets_config() {
         let logging_config = LoggingConfig::default()
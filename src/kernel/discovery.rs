@@ -1,11 +1,16 @@
 //! Agent capability registry for discovery.
 //!
 //! This module provides the `CapabilityRegistry` which tracks which agents
-//! have which capabilities, enabling agent discovery by capability.
+//! have which capabilities, enabling agent discovery by capability. Agents
+//! may also score how well-suited they are for each capability so that
+//! work can be routed to the best available agent.
 
 use crate::types::AgentId;
 use std::collections::{HashMap, HashSet};
 
+/// Score of a capability announced without one.
+pub const DEFAULT_CAPABILITY_SCORE: f32 = 1.0;
+
 /// Registry for tracking agent capabilities.
 ///
 /// This is a pure data structure used by the Kernel to track which agents
@@ -16,6 +21,8 @@ pub struct CapabilityRegistry {
     capability_to_agents: HashMap<String, HashSet<AgentId>>,
     /// Maps agent ID to set of capabilities that agent has
     agent_to_capabilities: HashMap<AgentId, HashSet<String>>,
+    /// Maps agent ID to its announced capability scores
+    agent_scores: HashMap<AgentId, HashMap<String, f32>>,
 }
 
 impl CapabilityRegistry {
@@ -25,16 +32,41 @@ impl CapabilityRegistry {
         Self {
             capability_to_agents: HashMap::new(),
             agent_to_capabilities: HashMap::new(),
+            agent_scores: HashMap::new(),
         }
     }
 
     /// Registers capabilities for an agent.
     ///
-    /// This replaces any existing capabilities for the agent.
+    /// This replaces any existing capabilities and scores for the agent.
     pub fn register(&mut self, agent_id: AgentId, capabilities: Vec<String>) {
+        self.register_with_scores(agent_id, capabilities, HashMap::new());
+    }
+
+    /// Registers capabilities for an agent along with how well-suited it is
+    /// for each (clamped to 0.0–1.0).
+    ///
+    /// Capabilities without a score get [`DEFAULT_CAPABILITY_SCORE`]; scores
+    /// for capabilities not in `capabilities` are ignored. This replaces any
+    /// existing capabilities and scores for the agent.
+    pub fn register_with_scores(
+        &mut self,
+        agent_id: AgentId,
+        capabilities: Vec<String>,
+        scores: HashMap<String, f32>,
+    ) {
         // Remove old capabilities
         self.unregister(&agent_id);
 
+        let scores: HashMap<String, f32> = scores
+            .into_iter()
+            .filter(|(cap, _)| capabilities.contains(cap))
+            .map(|(cap, score)| (cap, score.clamp(0.0, 1.0)))
+            .collect();
+        if !scores.is_empty() {
+            self.agent_scores.insert(agent_id.clone(), scores);
+        }
+
         // Add new capabilities
         for cap in &capabilities {
             self.capability_to_agents
@@ -49,6 +81,7 @@ impl CapabilityRegistry {
 
     /// Unregisters all capabilities for an agent.
     pub fn unregister(&mut self, agent_id: &AgentId) {
+        self.agent_scores.remove(agent_id);
         if let Some(caps) = self.agent_to_capabilities.remove(agent_id) {
             for cap in caps {
                 if let Some(agents) = self.capability_to_agents.get_mut(&cap) {
@@ -71,6 +104,54 @@ impl CapabilityRegistry {
             .and_then(|agents| agents.iter().next().cloned())
     }
 
+    /// Returns how well-suited an agent is for a capability.
+    ///
+    /// This is the announced score, [`DEFAULT_CAPABILITY_SCORE`] if the agent
+    /// announced the capability without one, or 0.0 if it does not have the
+    /// capability.
+    #[must_use]
+    pub fn score_agent(&self, agent_id: &AgentId, capability: &str) -> f32 {
+        if !self.has_capability(agent_id, capability) {
+            return 0.0;
+        }
+        self.agent_scores
+            .get(agent_id)
+            .and_then(|scores| scores.get(capability))
+            .copied()
+            .unwrap_or(DEFAULT_CAPABILITY_SCORE)
+    }
+
+    /// Finds the agent with the highest score for the specified capability.
+    #[must_use]
+    pub fn find_best_capable_agent(&self, capability: &str) -> Option<AgentId> {
+        self.find_best_capable_agent_weighted(capability, |_, score| score)
+    }
+
+    /// Finds the agent with the highest score for the specified capability
+    /// after `weight` adjusts each agent's score (e.g., by its current
+    /// load).
+    ///
+    /// Ties go to the agent with the smallest ID so results are stable.
+    #[must_use]
+    pub fn find_best_capable_agent_weighted(
+        &self,
+        capability: &str,
+        weight: impl Fn(&AgentId, f32) -> f32,
+    ) -> Option<AgentId> {
+        self.capability_to_agents
+            .get(capability)?
+            .iter()
+            .map(|agent_id| {
+                let score = weight(agent_id, self.score_agent(agent_id, capability));
+                (agent_id, score)
+            })
+            .max_by(|(a_id, a), (b_id, b)| {
+                a.total_cmp(b)
+                    .then_with(|| b_id.to_string().cmp(&a_id.to_string()))
+            })
+            .map(|(agent_id, _)| agent_id.clone())
+    }
+
     /// Finds all agents with the specified capability.
     #[must_use]
     pub fn find_all_capable_agents(&self, capability: &str) -> Vec<AgentId> {
@@ -217,6 +298,67 @@ mod tests {
         assert!(caps.contains(&"cap2".to_string()));
     }
 
+    #[test]
+    fn score_agent_uses_announced_or_default_score() {
+        let mut registry = CapabilityRegistry::new();
+        let agent_id = AgentId::new();
+
+        registry.register_with_scores(
+            agent_id.clone(),
+            vec!["translate".to_string(), "summarize".to_string()],
+            HashMap::from([
+                ("translate".to_string(), 0.3),
+                ("unannounced".to_string(), 0.9),
+            ]),
+        );
+
+        assert_eq!(registry.score_agent(&agent_id, "translate"), 0.3);
+        assert_eq!(
+            registry.score_agent(&agent_id, "summarize"),
+            DEFAULT_CAPABILITY_SCORE
+        );
+        assert_eq!(registry.score_agent(&agent_id, "unannounced"), 0.0);
+
+        registry.register(agent_id.clone(), vec!["translate".to_string()]);
+        assert_eq!(
+            registry.score_agent(&agent_id, "translate"),
+            DEFAULT_CAPABILITY_SCORE
+        );
+    }
+
+    #[test]
+    fn find_best_capable_agent_picks_highest_score() {
+        let mut registry = CapabilityRegistry::new();
+        let weak = AgentId::new();
+        let strong = AgentId::new();
+        let cap = "code_review".to_string();
+
+        registry.register_with_scores(
+            weak.clone(),
+            vec![cap.clone()],
+            HashMap::from([(cap.clone(), 0.4)]),
+        );
+        registry.register_with_scores(
+            strong.clone(),
+            vec![cap.clone()],
+            HashMap::from([(cap.clone(), 7.0)]),
+        );
+
+        assert_eq!(registry.score_agent(&strong, &cap), 1.0);
+        assert_eq!(registry.find_best_capable_agent(&cap), Some(strong.clone()));
+        assert_eq!(
+            registry.find_best_capable_agent_weighted(&cap, |agent_id, score| {
+                if *agent_id == strong {
+                    score / 4.0
+                } else {
+                    score
+                }
+            }),
+            Some(weak)
+        );
+        assert_eq!(registry.find_best_capable_agent("unknown"), None);
+    }
+
     #[test]
     fn get_capabilities_for_unknown_agent() {
         let registry = CapabilityRegistry::new();
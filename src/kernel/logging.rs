@@ -6,6 +6,7 @@
 //! journald socket, initialization becomes a silent no-op — callers can still
 //! install their own subscriber (e.g. stderr via `tracing-subscriber`).
 
+use crate::messages::LLMRequest;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -38,6 +39,10 @@ pub struct LoggingConfig {
     pub app_name: String,
     /// Minimum log level forwarded to journald.
     pub level: LogLevel,
+    /// Whether every [`LLMRequest`] is logged as JSON under
+    /// [`PROVIDER_REQUEST_LOG_TARGET`] for auditing.
+    #[serde(default)]
+    pub log_provider_requests: bool,
 }
 
 impl LoggingConfig {
@@ -69,6 +74,17 @@ impl LoggingConfig {
         self.level = level;
         self
     }
+
+    /// Sets whether every LLM request is logged for auditing.
+    ///
+    /// Requests are logged at info level under
+    /// [`PROVIDER_REQUEST_LOG_TARGET`] as JSON holding the messages, tools
+    /// and sampling parameters sent; provider credentials are never logged.
+    #[must_use]
+    pub fn with_provider_request_logging(mut self, enabled: bool) -> Self {
+        self.log_provider_requests = enabled;
+        self
+    }
 }
 
 impl Default for LoggingConfig {
@@ -77,6 +93,7 @@ impl Default for LoggingConfig {
             enabled: true,
             app_name: "acton-ai".to_string(),
             level: LogLevel::default(),
+            log_provider_requests: false,
         }
     }
 }
@@ -115,6 +132,37 @@ impl LogLevel {
 /// via this module's helpers. Subsequent calls no-op rather than racing.
 static LOGGING_INSTALLED: AtomicBool = AtomicBool::new(false);
 
+/// Set from [`LoggingConfig::log_provider_requests`] whenever a kernel
+/// initializes logging.
+static PROVIDER_REQUEST_LOGGING: AtomicBool = AtomicBool::new(false);
+
+/// Tracing target of the audit log of LLM requests.
+pub const PROVIDER_REQUEST_LOG_TARGET: &str = "acton_ai::provider_requests";
+
+/// Logs `request` under [`PROVIDER_REQUEST_LOG_TARGET`] if provider request
+/// logging is enabled.
+pub(crate) fn log_provider_request(model: &str, request: &LLMRequest) {
+    if !PROVIDER_REQUEST_LOGGING.load(Ordering::SeqCst) {
+        return;
+    }
+    match serde_json::to_string(request) {
+        Ok(json) => tracing::info!(
+            target: PROVIDER_REQUEST_LOG_TARGET,
+            correlation_id = %request.correlation_id,
+            agent_id = %request.agent_id,
+            model,
+            request = %json,
+            "LLM request"
+        ),
+        Err(e) => tracing::warn!(
+            target: PROVIDER_REQUEST_LOG_TARGET,
+            correlation_id = %request.correlation_id,
+            error = %e,
+            "failed to serialize LLM request for the audit log"
+        ),
+    }
+}
+
 /// Marks the global tracing subscriber as already installed so subsequent
 /// calls to [`init_and_store_logging`] become silent no-ops. The CLI bootstrap
 /// uses this when it composes its own subscriber that already includes a
@@ -252,6 +300,10 @@ pub fn init_journald_logging(config: &LoggingConfig) -> Result<bool, LoggingErro
 /// `Ok(false)` if logging is disabled, a subscriber was already installed,
 /// or journald is unavailable on this host.
 pub fn init_and_store_logging(config: &LoggingConfig) -> Result<bool, LoggingError> {
+    PROVIDER_REQUEST_LOGGING.store(
+        config.enabled && config.log_provider_requests,
+        Ordering::SeqCst,
+    );
     if LOGGING_INSTALLED.load(Ordering::SeqCst) {
         return Ok(false);
     }
@@ -268,6 +320,7 @@ mod tests {
         assert!(config.enabled);
         assert_eq!(config.app_name, "acton-ai");
         assert_eq!(config.level, LogLevel::Info);
+        assert!(!config.log_provider_requests);
     }
 
     #[test]
@@ -287,6 +340,16 @@ mod tests {
         assert_eq!(config.level, LogLevel::Debug);
     }
 
+    #[test]
+    fn provider_request_logging_defaults_off_when_deserialized() {
+        let json = r#"{"enabled":true,"app_name":"a","level":"Info"}"#;
+        let config: LoggingConfig = serde_json::from_str(json).unwrap();
+        assert!(!config.log_provider_requests);
+
+        let config = config.with_provider_request_logging(true);
+        assert!(config.log_provider_requests);
+    }
+
     #[test]
     fn logging_config_disabled() {
         let config = LoggingConfig::disabled();
@@ -3,16 +3,28 @@
 //! The Kernel is the central coordinator and supervisor of the entire
 //! Acton-AI system. It manages agent lifecycles and routes inter-agent
 //! communication.
+//!
+//! [`MultiAgentWorkflow`] builds on the kernel's capability routing to run
+//! multi-step workflows across agents.
 
 mod actor;
 mod config;
 mod discovery;
 mod logging;
+mod workflow;
 
-pub use actor::{InitKernel, Kernel, KernelMetrics};
-pub use config::KernelConfig;
-pub use discovery::CapabilityRegistry;
+pub use actor::{InitKernel, Kernel, KernelMetrics, RegisterAgent, ScheduleToolCall};
+pub use config::{
+    KernelConfig, DEFAULT_HEARTBEAT_INTERVAL, DEFAULT_HEARTBEAT_TIMEOUT,
+    DEFAULT_SHUTDOWN_DRAIN_TIMEOUT,
+};
+pub use discovery::{CapabilityRegistry, DEFAULT_CAPABILITY_SCORE};
+pub(crate) use logging::log_provider_request;
 pub use logging::{
     init_and_store_logging, init_journald_logging, journald_layer, mark_subscriber_installed,
-    LogLevel, LoggingConfig, LoggingError, LoggingErrorKind,
+    LogLevel, LoggingConfig, LoggingError, LoggingErrorKind, PROVIDER_REQUEST_LOG_TARGET,
+};
+pub use workflow::{
+    MultiAgentWorkflow, ResultTransformer, RunWorkflow, TaskSpec, WorkflowDefinition,
+    WorkflowFailed, WorkflowResult, WorkflowStep,
 };
@@ -0,0 +1,412 @@
+//! Multi-step workflows across agents.
+//!
+//! A [`WorkflowDefinition`] lists steps that run in order. Each step is a
+//! batch of [`TaskSpec`]s run either one after another
+//! ([`WorkflowStep::Sequential`]) or all at once
+//! ([`WorkflowStep::Parallel`]). For every task, the
+//! [`MultiAgentWorkflow`] actor asks the kernel for the best agent with the
+//! task's capability ([`FindBestCapableAgent`]), delegates the task to it
+//! through the kernel ([`DelegateTask`]), and waits for the agent's
+//! [`TaskCompleted`] or [`TaskFailed`].
+//!
+//! Each task receives `{"payload": <TaskSpec::payload>, "input": <previous
+//! output>}`. Within a sequential step the input is the previous task's
+//! output; every task of a parallel step gets the previous step's output.
+//! A sequential step's output is its last task's output, and a parallel
+//! step's output is an array of its tasks' outputs in order.
+
+use crate::error::MultiAgentError;
+use crate::messages::{
+    CapableAgentFound, DelegateTask, FindBestCapableAgent, TaskCompleted, TaskFailed,
+};
+use crate::types::{AgentId, TaskId};
+use acton_reactive::prelude::*;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+
+/// Transforms a task's result before it is passed on.
+pub type ResultTransformer = fn(Value) -> Value;
+
+/// One task of a workflow step.
+#[derive(Debug, Clone)]
+pub struct TaskSpec {
+    /// Capability the agent running the task must have
+    pub capability: String,
+    /// Task-specific input, sent alongside the previous output
+    pub payload: Value,
+    /// Applied to the task's result before it is passed on
+    pub transform: Option<ResultTransformer>,
+    /// How long the agent has to finish the task
+    pub deadline: Option<Duration>,
+}
+
+impl TaskSpec {
+    /// Creates a task for an agent with `capability`.
+    #[must_use]
+    pub fn new(capability: impl Into<String>, payload: Value) -> Self {
+        Self {
+            capability: capability.into(),
+            payload,
+            transform: None,
+            deadline: None,
+        }
+    }
+
+    /// Transforms the task's result before it is passed on.
+    #[must_use]
+    pub fn with_transform(mut self, transform: ResultTransformer) -> Self {
+        self.transform = Some(transform);
+        self
+    }
+
+    /// Fails the workflow if the task does not finish within `deadline`.
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+}
+
+/// A batch of tasks in a workflow.
+#[derive(Debug, Clone)]
+pub enum WorkflowStep {
+    /// Tasks run one after another, each fed the previous one's output
+    Sequential(Vec<TaskSpec>),
+    /// Tasks run concurrently, all fed the previous step's output
+    Parallel(Vec<TaskSpec>),
+}
+
+/// Steps of a workflow, run in order.
+///
+/// # Example
+///
+/// ```
+/// use acton_ai::kernel::{TaskSpec, WorkflowDefinition, WorkflowStep};
+/// use serde_json::json;
+///
+/// let workflow = WorkflowDefinition::new()
+///     .then(WorkflowStep::Sequential(vec![TaskSpec::new(
+///         "plan",
+///         json!({"topic": "actor models"}),
+///     )]))
+///     .then(WorkflowStep::Parallel(vec![
+///         TaskSpec::new("research", json!({"source": "papers"})),
+///         TaskSpec::new("research", json!({"source": "blogs"})),
+///     ]))
+///     .then(WorkflowStep::Sequential(vec![
+///         TaskSpec::new("write", json!({})),
+///         TaskSpec::new("review", json!({})),
+///     ]));
+/// assert_eq!(workflow.steps.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WorkflowDefinition {
+    /// The steps to run
+    pub steps: Vec<WorkflowStep>,
+}
+
+impl WorkflowDefinition {
+    /// Creates an empty workflow.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a step.
+    #[must_use]
+    pub fn then(mut self, step: WorkflowStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+}
+
+/// Outcome of a successful workflow.
+#[acton_message]
+pub struct WorkflowResult {
+    /// Output of each step, in order
+    pub step_results: Vec<Value>,
+    /// Time from start to the last step's completion
+    pub total_elapsed: Duration,
+}
+
+/// Sent instead of [`WorkflowResult`] when a workflow fails.
+#[acton_message]
+pub struct WorkflowFailed {
+    /// Why the workflow failed
+    pub error: MultiAgentError,
+}
+
+/// Request to run a workflow.
+///
+/// The workflow actor replies with [`WorkflowResult`] or
+/// [`WorkflowFailed`].
+#[acton_message]
+pub struct RunWorkflow {
+    /// The workflow to run
+    pub definition: WorkflowDefinition,
+}
+
+/// Replies the workflow actor is waiting for.
+#[derive(Debug, Default)]
+struct Waiters {
+    /// Agent searches by correlation ID
+    agents: HashMap<String, oneshot::Sender<Option<AgentId>>>,
+    /// Delegated tasks by task ID
+    tasks: HashMap<TaskId, oneshot::Sender<Result<Value, String>>>,
+}
+
+/// Actor that runs [`WorkflowDefinition`]s through the kernel.
+///
+/// Tasks can only be delegated to agents registered with the kernel
+/// ([`RegisterAgent`](super::RegisterAgent)) that announced the task's
+/// capability. Those agents report back by broadcasting [`TaskCompleted`] or
+/// [`TaskFailed`] for the delegated task ID.
+#[acton_actor]
+pub struct MultiAgentWorkflow {
+    /// ID tasks are delegated from
+    pub id: AgentId,
+    /// The kernel that selects agents and routes tasks
+    kernel: Option<ActorHandle>,
+    /// Pending agent searches and tasks
+    waiters: Arc<Mutex<Waiters>>,
+}
+
+impl MultiAgentWorkflow {
+    /// Spawns a workflow actor that coordinates agents through `kernel`.
+    ///
+    /// Send it [`RunWorkflow`] messages, or use [`run`](Self::run) to wait for
+    /// the outcome directly.
+    pub async fn spawn(runtime: &mut ActorRuntime, kernel: ActorHandle) -> ActorHandle {
+        let mut builder = runtime.new_actor::<Self>();
+        builder.model.kernel = Some(kernel);
+        configure_handlers(&mut builder);
+
+        let handle = builder.start().await;
+        handle.subscribe::<TaskCompleted>().await;
+        handle.subscribe::<TaskFailed>().await;
+        handle
+    }
+
+    /// Runs `definition` on a new workflow actor and waits for the outcome.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no agent has a task's capability, an agent fails a
+    /// task, or a task misses its deadline.
+    pub async fn run(
+        runtime: &mut ActorRuntime,
+        kernel: ActorHandle,
+        definition: WorkflowDefinition,
+    ) -> Result<WorkflowResult, MultiAgentError> {
+        let workflow = Self::spawn(runtime, kernel).await;
+
+        let (tx, rx) = oneshot::channel();
+        let mut receiver = runtime.new_actor::<WorkflowOutcomeReceiver>();
+        let sender = Arc::new(Mutex::new(Some(tx)));
+        let on_result = Arc::clone(&sender);
+        receiver.mutate_on::<WorkflowResult>(move |_actor, envelope| {
+            if let Some(tx) = lock(&on_result).take() {
+                let _ = tx.send(Ok(envelope.message().clone()));
+            }
+            Reply::ready()
+        });
+        let on_failure = Arc::clone(&sender);
+        receiver.mutate_on::<WorkflowFailed>(move |_actor, envelope| {
+            if let Some(tx) = lock(&on_failure).take() {
+                let _ = tx.send(Err(envelope.message().error.clone()));
+            }
+            Reply::ready()
+        });
+        let receiver = receiver.start().await;
+
+        receiver
+            .create_envelope(Some(workflow.reply_address()))
+            .send(RunWorkflow { definition })
+            .await;
+        let outcome = rx.await.unwrap_or_else(|_| {
+            Err(MultiAgentError::delegation_failed(
+                TaskId::new(),
+                "workflow stopped before finishing",
+            ))
+        });
+
+        let _ = receiver.stop().await;
+        let _ = workflow.stop().await;
+        outcome
+    }
+}
+
+/// Receives the outcome of [`MultiAgentWorkflow::run`].
+#[acton_actor]
+struct WorkflowOutcomeReceiver;
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Configures message handlers for the workflow actor.
+fn configure_handlers(builder: &mut ManagedActor<Idle, MultiAgentWorkflow>) {
+    builder.act_on::<RunWorkflow>(|actor, envelope| {
+        let reply = envelope.reply_envelope();
+        let Some(kernel) = actor.model.kernel.clone() else {
+            tracing::error!("Workflow actor has no kernel");
+            return Reply::ready();
+        };
+        let driver = WorkflowDriver {
+            id: actor.model.id.clone(),
+            kernel,
+            workflow: actor.handle().clone(),
+            waiters: Arc::clone(&actor.model.waiters),
+        };
+        let definition = envelope.message().definition.clone();
+
+        // Run detached: the driver waits on replies this actor must keep
+        // handling
+        tokio::spawn(async move {
+            match driver.run(&definition).await {
+                Ok(result) => reply.send(result).await,
+                Err(error) => {
+                    tracing::warn!(error = %error, "Workflow failed");
+                    reply.send(WorkflowFailed { error }).await;
+                }
+            }
+        });
+        Reply::ready()
+    });
+
+    builder.mutate_on::<CapableAgentFound>(|actor, envelope| {
+        let msg = envelope.message();
+        let waiter = lock(&actor.model.waiters)
+            .agents
+            .remove(&msg.correlation_id.to_string());
+        if let Some(waiter) = waiter {
+            let _ = waiter.send(msg.agent_id.clone());
+        }
+        Reply::ready()
+    });
+
+    builder.mutate_on::<TaskCompleted>(|actor, envelope| {
+        let msg = envelope.message();
+        let waiter = lock(&actor.model.waiters).tasks.remove(&msg.task_id);
+        if let Some(waiter) = waiter {
+            let _ = waiter.send(Ok(msg.result.clone()));
+        }
+        Reply::ready()
+    });
+
+    builder.mutate_on::<TaskFailed>(|actor, envelope| {
+        let msg = envelope.message();
+        let waiter = lock(&actor.model.waiters).tasks.remove(&msg.task_id);
+        if let Some(waiter) = waiter {
+            let _ = waiter.send(Err(msg.error.clone()));
+        }
+        Reply::ready()
+    });
+}
+
+/// Runs one workflow on behalf of a workflow actor.
+struct WorkflowDriver {
+    id: AgentId,
+    kernel: ActorHandle,
+    workflow: ActorHandle,
+    waiters: Arc<Mutex<Waiters>>,
+}
+
+impl WorkflowDriver {
+    async fn run(
+        &self,
+        definition: &WorkflowDefinition,
+    ) -> Result<WorkflowResult, MultiAgentError> {
+        let started = Instant::now();
+        let mut input = Value::Null;
+        let mut step_results = Vec::with_capacity(definition.steps.len());
+
+        for step in &definition.steps {
+            let output = match step {
+                WorkflowStep::Sequential(tasks) => {
+                    let mut output = input;
+                    for task in tasks {
+                        output = self.run_task(task, output).await?;
+                    }
+                    output
+                }
+                WorkflowStep::Parallel(tasks) => {
+                    let outputs = futures::future::try_join_all(
+                        tasks.iter().map(|task| self.run_task(task, input.clone())),
+                    )
+                    .await?;
+                    Value::Array(outputs)
+                }
+            };
+            step_results.push(output.clone());
+            input = output;
+        }
+
+        Ok(WorkflowResult {
+            step_results,
+            total_elapsed: started.elapsed(),
+        })
+    }
+
+    async fn run_task(&self, spec: &TaskSpec, input: Value) -> Result<Value, MultiAgentError> {
+        let agent_id = self
+            .find_agent(&spec.capability)
+            .await
+            .ok_or_else(|| MultiAgentError::no_capable_agent(&spec.capability))?;
+
+        let mut task = DelegateTask::new(
+            self.id.clone(),
+            agent_id,
+            spec.capability.clone(),
+            json!({"payload": spec.payload, "input": input}),
+        );
+        if let Some(deadline) = spec.deadline {
+            task = task.with_deadline(deadline);
+        }
+        let task_id = task.task_id.clone();
+
+        let (tx, rx) = oneshot::channel();
+        lock(&self.waiters).tasks.insert(task_id.clone(), tx);
+        self.kernel.send(task).await;
+
+        let outcome = match spec.deadline {
+            Some(deadline) => match tokio::time::timeout(deadline, rx).await {
+                Ok(outcome) => outcome,
+                Err(_) => {
+                    lock(&self.waiters).tasks.remove(&task_id);
+                    return Err(MultiAgentError::delegation_failed(
+                        task_id,
+                        "deadline exceeded",
+                    ));
+                }
+            },
+            None => rx.await,
+        };
+        let result = outcome
+            .map_err(|_| MultiAgentError::delegation_failed(task_id.clone(), "workflow stopped"))?
+            .map_err(|error| MultiAgentError::delegation_failed(task_id, error))?;
+
+        Ok(match spec.transform {
+            Some(transform) => transform(result),
+            None => result,
+        })
+    }
+
+    /// Asks the kernel for the best idle-weighted agent with `capability`.
+    async fn find_agent(&self, capability: &str) -> Option<AgentId> {
+        let request = FindBestCapableAgent::new(capability, true);
+        let (tx, rx) = oneshot::channel();
+        lock(&self.waiters)
+            .agents
+            .insert(request.correlation_id.to_string(), tx);
+
+        self.workflow
+            .create_envelope(Some(self.kernel.reply_address()))
+            .send(request)
+            .await;
+        rx.await.ok().flatten()
+    }
+}
@@ -69,6 +69,7 @@ pub mod memory;
 pub mod messages;
 pub mod prompt;
 pub mod stream;
+mod system_prompt;
 pub mod tools;
 pub mod types;
 
@@ -81,40 +82,45 @@ pub mod prelude {
         ActonAIConfig, ActonAIDefaults, NamedProviderConfig, RateLimitFileConfig,
     };
     pub use crate::conversation::{
-        ChatConfig, Conversation, ConversationBuilder, StreamToken, DEFAULT_SYSTEM_PROMPT,
+        ChatConfig, Conversation, ConversationBuilder, ConversationFork, ConversationInspector,
+        ConversationSnapshot, DeduplicationStats, ExportFormat, StreamToken, SummarizationConfig,
+        DEFAULT_SYSTEM_PROMPT,
     };
-    pub use crate::error::{ActonAIError, ActonAIErrorKind};
-    pub use crate::facade::{ActonAI, ActonAIBuilder, DEFAULT_PROVIDER_NAME};
+    pub use crate::error::{ActonAIError, ActonAIErrorKind, ErrorContext, ResultExt};
+    pub use crate::facade::{ActonAI, ActonAIBuilder, ShutdownResult, DEFAULT_PROVIDER_NAME};
     pub use crate::stream::{CollectedResponse, StreamAction, StreamHandler};
 
     // Low-level API (for advanced use cases)
     pub use crate::agent::{
-        Agent, AgentConfig, AgentState, DelegatedTask, DelegatedTaskState, DelegationTracker,
-        IncomingTaskInfo, InitAgent,
+        Agent, AgentConfig, AgentMetrics, AgentState, DelegatedTask, DelegatedTaskState,
+        DelegationTracker, IncomingTaskInfo, InitAgent, UseMemoryStore,
     };
     pub use crate::error::{AgentError, KernelError, MultiAgentError, MultiAgentErrorKind};
     pub use crate::kernel::{
         init_and_store_logging, init_journald_logging, journald_layer, mark_subscriber_installed,
         CapabilityRegistry, InitKernel, Kernel, KernelConfig, KernelMetrics, LogLevel,
-        LoggingConfig, LoggingError, LoggingErrorKind,
+        LoggingConfig, LoggingError, LoggingErrorKind, RegisterAgent,
     };
     pub use crate::llm::{
-        AnthropicClient, InitLLMProvider, LLMClient, LLMClientResponse, LLMError, LLMErrorKind,
-        LLMEventStream, LLMProvider, LLMStreamEvent, OpenAIClient, ProviderConfig, ProviderType,
-        RateLimitConfig, SamplingParams,
+        AnthropicClient, CircuitBreakerConfig, CircuitState, InitLLMProvider, LLMClient,
+        LLMClientResponse, LLMError, LLMErrorKind, LLMEventStream, LLMProvider, LLMStreamEvent,
+        OpenAIClient, ProviderConfig, ProviderType, RateLimitConfig, RetryPolicy, SamplingParams,
+        TokenUsage,
     };
     pub use crate::memory::{
         AgentStateSnapshot, ContextStats, ContextWindow, ContextWindowConfig,
-        ContextWindowResponse, Embedding, EmbeddingError, EmbeddingProvider, GetContextWindow,
+        ContextWindowResponse, Embedding, EmbeddingError, EmbeddingProvider, EncryptionAlgorithm,
+        EncryptionConfig, ExpiredMemoriesPruned, GetContextWindow, HybridSearchMemories,
         InitMemoryStore, LoadMemories, MemoriesLoaded, Memory, MemorySearchResults, MemoryStore,
-        MemoryStoreMetrics, MemoryStored, PersistenceConfig, PersistenceError, ScoredMemory,
-        SearchMemories, StoreMemory, StubEmbeddingProvider, TruncationStrategy,
+        MemoryStoreMetrics, MemoryStored, PersistenceConfig, PersistenceError,
+        PruneExpiredMemories, ScoredMemory, SearchMemories, StoreMemory, StubEmbeddingProvider,
+        TruncationStrategy,
     };
     pub use crate::messages::*;
     pub use crate::tools::builtins::BuiltinTools;
     pub use crate::tools::{
-        RegisterTool, ToolConfig, ToolDefinition, ToolError, ToolErrorKind, ToolExecutorTrait,
-        ToolRegistry,
+        RegisterTool, ToolCallLog, ToolCallRecord, ToolConfig, ToolDefinition, ToolError,
+        ToolErrorKind, ToolExecutorTrait, ToolRegistry, ToolResultExt,
     };
     pub use crate::types::{
         AgentId, ConversationId, CorrelationId, InvalidTaskId, MemoryId, MessageId, TaskId,
@@ -125,7 +131,9 @@ pub mod prelude {
     pub use acton_reactive::prelude::*;
 
     // Agent Skills
-    pub use crate::skills::{LoadedSkill, SkillInfo, SkillRegistry, SkillsError};
+    pub use crate::skills::{
+        LoadedSkill, SkillInfo, SkillParameter, SkillRegistry, SkillRegistryEvent, SkillsError,
+    };
     pub use crate::tools::builtins::{
         skill_tool_names, spawn_skill_tool_actors, ActivateSkillTool, ActivateSkillToolActor,
         ListSkillsTool, ListSkillsToolActor,
@@ -0,0 +1,338 @@
+//! Rate limits tuned from provider response headers.
+//!
+//! OpenAI-compatible APIs report their remaining quota in
+//! `x-ratelimit-*` headers and Anthropic in `anthropic-ratelimit-*`
+//! headers. When [`RateLimitConfig::adaptive`] is enabled, the provider
+//! feeds each response's headers to an [`AdaptiveRateLimiter`], which backs
+//! off before the API starts returning 429s and recovers once the quota
+//! refills.
+
+use crate::llm::config::RateLimitConfig;
+use reqwest::header::HeaderMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Remaining quota below this fraction of the limit lowers the effective rate.
+const LOW_WATERMARK: f64 = 0.2;
+
+/// Remaining quota above this fraction of the limit restores the configured rate.
+const HIGH_WATERMARK: f64 = 0.8;
+
+/// Rate-limit information reported by a single API response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitSnapshot {
+    /// Requests allowed in the current window
+    pub request_limit: Option<u32>,
+    /// Requests remaining in the current window
+    pub remaining_requests: Option<u32>,
+    /// Tokens allowed in the current window
+    pub token_limit: Option<u32>,
+    /// Tokens remaining in the current window
+    pub remaining_tokens: Option<u32>,
+    /// How long the API asked callers to wait
+    pub retry_after: Option<Duration>,
+}
+
+impl RateLimitSnapshot {
+    /// Parses the rate-limit headers of a response.
+    ///
+    /// Returns `None` if the response carries none of them.
+    #[must_use]
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |names: [&str; 2]| {
+            names.iter().find_map(|name| {
+                headers
+                    .get(*name)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|s| s.trim().parse::<u32>().ok())
+            })
+        };
+
+        let snapshot = Self {
+            request_limit: number([
+                "x-ratelimit-limit-requests",
+                "anthropic-ratelimit-requests-limit",
+            ]),
+            remaining_requests: number([
+                "x-ratelimit-remaining-requests",
+                "anthropic-ratelimit-requests-remaining",
+            ]),
+            token_limit: number([
+                "x-ratelimit-limit-tokens",
+                "anthropic-ratelimit-tokens-limit",
+            ]),
+            remaining_tokens: number([
+                "x-ratelimit-remaining-tokens",
+                "anthropic-ratelimit-tokens-remaining",
+            ]),
+            retry_after: headers
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.trim().parse::<f64>().ok())
+                .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                .map(Duration::from_secs_f64),
+        };
+
+        (snapshot != Self::default()).then_some(snapshot)
+    }
+}
+
+/// Holds the rate-limit headers of a client's most recent response.
+///
+/// Clients record into it after every request and the provider takes the
+/// snapshot once the request completes. Clones share the same slot.
+#[derive(Debug, Clone, Default)]
+pub struct RecentRateLimit {
+    slot: Arc<Mutex<Option<RateLimitSnapshot>>>,
+}
+
+impl RecentRateLimit {
+    /// Records the rate-limit headers of a response, if it has any.
+    pub fn record(&self, headers: &HeaderMap) {
+        if let Some(snapshot) = RateLimitSnapshot::from_headers(headers) {
+            *self.slot.lock().unwrap_or_else(PoisonError::into_inner) = Some(snapshot);
+        }
+    }
+
+    /// Takes the most recent snapshot, leaving the slot empty.
+    #[must_use]
+    pub fn take(&self) -> Option<RateLimitSnapshot> {
+        self.slot
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+/// Adjusts a [`RateLimitConfig`] from the quota the API reports.
+///
+/// The configured limits are the ceiling: when the remaining requests (or
+/// tokens) drop below 20% of the window's limit, the effective rate is
+/// halved, and once they are back above 80% the configured rate is
+/// restored. A `retry-after` header pauses requests until it has elapsed.
+#[derive(Debug, Clone)]
+pub struct AdaptiveRateLimiter {
+    configured: RateLimitConfig,
+    effective: RateLimitConfig,
+    paused_until: Option<Instant>,
+}
+
+impl AdaptiveRateLimiter {
+    /// Creates a limiter starting at the configured limits.
+    #[must_use]
+    pub fn new(configured: RateLimitConfig) -> Self {
+        Self {
+            configured,
+            effective: configured,
+            paused_until: None,
+        }
+    }
+
+    /// Returns the limits currently in force.
+    #[must_use]
+    pub fn effective(&self) -> RateLimitConfig {
+        self.effective
+    }
+
+    /// Returns the limits the limiter was configured with.
+    #[must_use]
+    pub fn configured(&self) -> RateLimitConfig {
+        self.configured
+    }
+
+    /// Returns true if a `retry-after` header is still in effect.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Updates the effective limits from `snapshot`.
+    ///
+    /// Returns true if the effective limits changed.
+    pub fn observe(&mut self, snapshot: &RateLimitSnapshot) -> bool {
+        if let Some(retry_after) = snapshot.retry_after {
+            self.paused_until = Some(Instant::now() + retry_after);
+        }
+
+        let requests_per_minute = adjust(
+            self.effective.requests_per_minute,
+            self.configured.requests_per_minute,
+            snapshot.remaining_requests,
+            snapshot.request_limit,
+        );
+        let tokens_per_minute = adjust(
+            self.effective.tokens_per_minute,
+            self.configured.tokens_per_minute,
+            snapshot.remaining_tokens,
+            snapshot.token_limit,
+        );
+
+        if requests_per_minute == self.effective.requests_per_minute
+            && tokens_per_minute == self.effective.tokens_per_minute
+        {
+            return false;
+        }
+
+        tracing::info!(
+            requests_per_minute_from = self.effective.requests_per_minute,
+            requests_per_minute_to = requests_per_minute,
+            tokens_per_minute_from = self.effective.tokens_per_minute,
+            tokens_per_minute_to = tokens_per_minute,
+            remaining_requests = ?snapshot.remaining_requests,
+            remaining_tokens = ?snapshot.remaining_tokens,
+            "Adjusted rate limit from provider headers"
+        );
+        self.effective.requests_per_minute = requests_per_minute;
+        self.effective.tokens_per_minute = tokens_per_minute;
+        true
+    }
+}
+
+/// Computes the new effective value of one limit.
+///
+/// `limit` falls back to the configured value when the API does not report
+/// the window size.
+fn adjust(current: u32, configured: u32, remaining: Option<u32>, limit: Option<u32>) -> u32 {
+    let Some(remaining) = remaining else {
+        return current;
+    };
+    let limit = limit.unwrap_or(configured);
+    if limit == 0 {
+        return current;
+    }
+
+    let fraction = f64::from(remaining) / f64::from(limit);
+    if fraction < LOW_WATERMARK {
+        (current / 2).max(1)
+    } else if fraction > HIGH_WATERMARK {
+        configured
+    } else {
+        current
+    }
+}
+
+/// Shared handle to an [`AdaptiveRateLimiter`].
+pub(crate) type SharedAdaptiveRateLimiter = Arc<Mutex<AdaptiveRateLimiter>>;
+
+/// Locks `limiter`, recovering from poisoning.
+pub(crate) fn lock_limiter(
+    limiter: &SharedAdaptiveRateLimiter,
+) -> std::sync::MutexGuard<'_, AdaptiveRateLimiter> {
+    limiter.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    fn snapshot(remaining: u32, limit: u32) -> RateLimitSnapshot {
+        RateLimitSnapshot {
+            request_limit: Some(limit),
+            remaining_requests: Some(remaining),
+            ..RateLimitSnapshot::default()
+        }
+    }
+
+    #[test]
+    fn parses_openai_headers() {
+        let parsed = RateLimitSnapshot::from_headers(&headers(&[
+            ("x-ratelimit-limit-requests", "500"),
+            ("x-ratelimit-remaining-requests", "499"),
+            ("x-ratelimit-remaining-tokens", "29000"),
+            ("retry-after", "2"),
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.request_limit, Some(500));
+        assert_eq!(parsed.remaining_requests, Some(499));
+        assert_eq!(parsed.remaining_tokens, Some(29_000));
+        assert_eq!(parsed.token_limit, None);
+        assert_eq!(parsed.retry_after, Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn parses_anthropic_headers() {
+        let parsed = RateLimitSnapshot::from_headers(&headers(&[
+            ("anthropic-ratelimit-requests-limit", "50"),
+            ("anthropic-ratelimit-requests-remaining", "3"),
+            ("anthropic-ratelimit-tokens-limit", "40000"),
+            ("anthropic-ratelimit-tokens-remaining", "39000"),
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.request_limit, Some(50));
+        assert_eq!(parsed.remaining_requests, Some(3));
+        assert_eq!(parsed.token_limit, Some(40_000));
+        assert_eq!(parsed.remaining_tokens, Some(39_000));
+    }
+
+    #[test]
+    fn responses_without_rate_limit_headers_are_ignored() {
+        let recent = RecentRateLimit::default();
+        recent.record(&headers(&[("content-type", "application/json")]));
+        assert!(recent.take().is_none());
+
+        recent.record(&headers(&[("x-ratelimit-remaining-requests", "1")]));
+        assert!(recent.take().is_some());
+        assert!(recent.take().is_none());
+    }
+
+    #[test]
+    fn lowers_when_quota_runs_low_and_restores_when_it_recovers() {
+        let mut limiter = AdaptiveRateLimiter::new(RateLimitConfig::new(50, 40_000));
+
+        assert!(limiter.observe(&snapshot(5, 50)));
+        assert_eq!(limiter.effective().requests_per_minute, 25);
+        assert!(limiter.observe(&snapshot(5, 50)));
+        assert_eq!(limiter.effective().requests_per_minute, 12);
+
+        // Between the watermarks nothing changes
+        assert!(!limiter.observe(&snapshot(25, 50)));
+        assert_eq!(limiter.effective().requests_per_minute, 12);
+
+        assert!(limiter.observe(&snapshot(45, 50)));
+        assert_eq!(limiter.effective(), limiter.configured());
+    }
+
+    #[test]
+    fn never_drops_below_one_request() {
+        let mut limiter = AdaptiveRateLimiter::new(RateLimitConfig::new(1, 40_000));
+        assert!(!limiter.observe(&snapshot(0, 50)));
+        assert_eq!(limiter.effective().requests_per_minute, 1);
+    }
+
+    #[test]
+    fn token_quota_adjusts_tokens_per_minute() {
+        let mut limiter = AdaptiveRateLimiter::new(RateLimitConfig::new(50, 40_000));
+        let low_tokens = RateLimitSnapshot {
+            remaining_tokens: Some(1_000),
+            ..RateLimitSnapshot::default()
+        };
+
+        assert!(limiter.observe(&low_tokens));
+        assert_eq!(limiter.effective().tokens_per_minute, 20_000);
+        assert_eq!(limiter.effective().requests_per_minute, 50);
+    }
+
+    #[test]
+    fn retry_after_pauses() {
+        let mut limiter = AdaptiveRateLimiter::new(RateLimitConfig::default());
+        assert!(!limiter.is_paused());
+
+        limiter.observe(&RateLimitSnapshot {
+            retry_after: Some(Duration::from_secs(30)),
+            ..RateLimitSnapshot::default()
+        });
+        assert!(limiter.is_paused());
+    }
+}
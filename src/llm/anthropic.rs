@@ -3,9 +3,12 @@
 //! HTTP client for communicating with the Anthropic Claude API,
 //! including streaming SSE response handling.
 
+use crate::llm::adaptive_rate_limit::{RateLimitSnapshot, RecentRateLimit};
 use crate::llm::client::{LLMClient, LLMClientResponse, LLMEventStream, LLMStreamEvent};
 use crate::llm::config::{ProviderConfig, SamplingParams};
 use crate::llm::error::LLMError;
+use crate::llm::prompt_cache::{CacheUsage, RecentCacheUsage};
+use crate::llm::usage::TokenUsage;
 use crate::messages::{Message, MessageRole, StopReason, ToolCall, ToolDefinition};
 use async_trait::async_trait;
 use futures::StreamExt;
@@ -20,6 +23,10 @@ pub struct AnthropicClient {
     client: Client,
     /// Configuration
     config: ProviderConfig,
+    /// Rate-limit headers of the most recent response
+    rate_limit: RecentRateLimit,
+    /// Prompt cache usage of the most recent response
+    cache_usage: RecentCacheUsage,
 }
 
 /// Request body for the Anthropic messages API.
@@ -28,10 +35,12 @@ struct MessagesRequest {
     model: String,
     max_tokens: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
-    system: Option<String>,
+    system: Option<ApiSystem>,
     messages: Vec<ApiMessage>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<ApiTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<serde_json::Value>,
     stream: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f64>,
@@ -41,6 +50,49 @@ struct MessagesRequest {
     top_p: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop_sequences: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking: Option<ApiThinking>,
+}
+
+/// Extended thinking settings in the API format.
+#[derive(Debug, Clone, Serialize)]
+struct ApiThinking {
+    #[serde(rename = "type")]
+    thinking_type: &'static str,
+    budget_tokens: u32,
+}
+
+/// System prompt in the API format: a plain string, or a text block when it
+/// carries a cache breakpoint.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum ApiSystem {
+    Text(String),
+    Blocks(Vec<SystemBlock>),
+}
+
+/// A system prompt text block.
+#[derive(Debug, Clone, Serialize)]
+struct SystemBlock {
+    #[serde(rename = "type")]
+    block_type: &'static str,
+    text: String,
+    cache_control: CacheControl,
+}
+
+/// Marks the end of a cacheable prompt prefix.
+#[derive(Debug, Clone, Serialize)]
+struct CacheControl {
+    #[serde(rename = "type")]
+    cache_type: &'static str,
+}
+
+impl CacheControl {
+    fn ephemeral() -> Self {
+        Self {
+            cache_type: "ephemeral",
+        }
+    }
 }
 
 /// A message in the API format.
@@ -83,6 +135,8 @@ struct ApiTool {
     name: String,
     description: String,
     input_schema: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<CacheControl>,
 }
 
 /// Response from the Anthropic messages API (non-streaming).
@@ -112,6 +166,14 @@ pub enum ResponseContentBlock {
         name: String,
         input: serde_json::Value,
     },
+    #[serde(rename = "thinking")]
+    Thinking {
+        thinking: String,
+        #[serde(default)]
+        signature: String,
+    },
+    #[serde(rename = "redacted_thinking")]
+    RedactedThinking { data: String },
 }
 
 /// Usage statistics from the API.
@@ -121,6 +183,23 @@ pub struct Usage {
     pub input_tokens: u32,
     /// Output tokens generated
     pub output_tokens: u32,
+    /// Input tokens written to the prompt cache
+    #[serde(default)]
+    pub cache_creation_input_tokens: u32,
+    /// Input tokens served from the prompt cache
+    #[serde(default)]
+    pub cache_read_input_tokens: u32,
+}
+
+impl Usage {
+    /// Returns the prompt cache portion of this usage.
+    #[must_use]
+    pub fn cache_usage(&self) -> CacheUsage {
+        CacheUsage {
+            cache_creation_tokens: u64::from(self.cache_creation_input_tokens),
+            cache_read_tokens: u64::from(self.cache_read_input_tokens),
+        }
+    }
 }
 
 /// Error response from the Anthropic API.
@@ -144,6 +223,8 @@ pub enum StreamEvent {
     MessageStart {
         /// Response ID
         id: String,
+        /// Input usage reported at the start of the stream
+        usage: Option<Usage>,
     },
     /// Content block started
     ContentBlockStart {
@@ -176,6 +257,8 @@ pub enum StreamEvent {
     MessageDelta {
         /// Stop reason
         stop_reason: Option<String>,
+        /// Output tokens reported in the delta's usage
+        output_tokens: Option<u32>,
     },
     /// Stream ended
     MessageStop,
@@ -205,6 +288,8 @@ struct RawStreamEvent {
     delta: Option<serde_json::Value>,
     #[serde(default)]
     error: Option<serde_json::Value>,
+    #[serde(default)]
+    usage: Option<serde_json::Value>,
 }
 
 impl AnthropicClient {
@@ -227,7 +312,12 @@ impl AnthropicClient {
             .build()
             .map_err(|e| LLMError::network(format!("failed to create HTTP client: {}", e)))?;
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            rate_limit: RecentRateLimit::default(),
+            cache_usage: RecentCacheUsage::default(),
+        })
     }
 
     /// Sends a messages request to the Anthropic API (non-streaming).
@@ -250,20 +340,7 @@ impl AnthropicClient {
         tools: Option<&[ToolDefinition]>,
         sampling: Option<&SamplingParams>,
     ) -> Result<MessagesResponse, LLMError> {
-        let (system, api_messages) = self.convert_messages(messages);
-
-        let request_body = MessagesRequest {
-            model: self.config.model.clone(),
-            max_tokens: self.config.max_tokens,
-            system,
-            messages: api_messages,
-            tools: tools.map(|t| self.convert_tools(t)),
-            stream: false,
-            temperature: sampling.and_then(|s| s.temperature),
-            top_k: sampling.and_then(|s| s.top_k),
-            top_p: sampling.and_then(|s| s.top_p),
-            stop_sequences: sampling.and_then(|s| s.stop_sequences.clone()),
-        };
+        let request_body = self.build_request(messages, tools, sampling, false);
 
         let response = self
             .client
@@ -275,8 +352,11 @@ impl AnthropicClient {
             .send()
             .await
             .map_err(|e| self.map_reqwest_error(e))?;
+        self.rate_limit.record(response.headers());
 
-        self.handle_response(response).await
+        let response = self.handle_response(response).await?;
+        self.cache_usage.record(response.usage.cache_usage());
+        Ok(response)
     }
 
     /// Sends a streaming messages request to the Anthropic API.
@@ -299,20 +379,7 @@ impl AnthropicClient {
         tools: Option<&[ToolDefinition]>,
         sampling: Option<&SamplingParams>,
     ) -> Result<impl futures::Stream<Item = Result<StreamEvent, LLMError>>, LLMError> {
-        let (system, api_messages) = self.convert_messages(messages);
-
-        let request_body = MessagesRequest {
-            model: self.config.model.clone(),
-            max_tokens: self.config.max_tokens,
-            system,
-            messages: api_messages,
-            tools: tools.map(|t| self.convert_tools(t)),
-            stream: true,
-            temperature: sampling.and_then(|s| s.temperature),
-            top_k: sampling.and_then(|s| s.top_k),
-            top_p: sampling.and_then(|s| s.top_p),
-            stop_sequences: sampling.and_then(|s| s.stop_sequences.clone()),
-        };
+        let request_body = self.build_request(messages, tools, sampling, true);
 
         let response = self
             .client
@@ -324,6 +391,7 @@ impl AnthropicClient {
             .send()
             .await
             .map_err(|e| self.map_reqwest_error(e))?;
+        self.rate_limit.record(response.headers());
 
         let status = response.status();
         if !status.is_success() {
@@ -331,13 +399,23 @@ impl AnthropicClient {
             return Err(error);
         }
 
+        let cache_usage = self.cache_usage.clone();
         let stream = response.bytes_stream().map(move |result| {
-            result
+            let events = result
                 .map_err(|e| LLMError::stream_error(format!("stream read error: {}", e)))
                 .and_then(|bytes| {
                     let text = String::from_utf8_lossy(&bytes);
                     Self::parse_sse_events(&text)
-                })
+                })?;
+            for event in &events {
+                if let StreamEvent::MessageStart {
+                    usage: Some(usage), ..
+                } = event
+                {
+                    cache_usage.record(usage.cache_usage());
+                }
+            }
+            Ok(events)
         });
 
         // Flatten the nested stream
@@ -349,6 +427,93 @@ impl AnthropicClient {
         }))
     }
 
+    /// Builds the request body, applying prompt caching breakpoints.
+    fn build_request(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+        stream: bool,
+    ) -> MessagesRequest {
+        let (system, api_messages) = self.convert_messages(messages);
+        let cache_system = sampling.and_then(|s| s.cache_system_prompt);
+        let cache_tools = sampling.and_then(|s| s.cache_tools) == Some(true);
+
+        let system = system.map(|text| {
+            let cached = cache_system
+                .unwrap_or_else(|| self.config.caches_system_prompt(text.chars().count()));
+            if cached {
+                ApiSystem::Blocks(vec![SystemBlock {
+                    block_type: "text",
+                    text,
+                    cache_control: CacheControl::ephemeral(),
+                }])
+            } else {
+                ApiSystem::Text(text)
+            }
+        });
+        // Without other tools, structured output is a forced tool call
+        // whose input is the response
+        let structured_schema = structured_output_schema(tools, sampling);
+        let tool_choice = structured_schema
+            .map(|_| serde_json::json!({ "type": "tool", "name": STRUCTURED_OUTPUT_TOOL }));
+        let tools = match structured_schema {
+            Some(schema) => Some(vec![ApiTool {
+                name: STRUCTURED_OUTPUT_TOOL.to_string(),
+                description: "Respond with output matching this schema".to_string(),
+                input_schema: schema.clone(),
+                cache_control: None,
+            }]),
+            None => tools.map(|t| self.convert_tools(t)),
+        };
+        let tools = tools.map(|mut tools| {
+            // The breakpoint on the last tool caches the whole tools array
+            if let Some(last) = tools.last_mut().filter(|_| cache_tools) {
+                last.cache_control = Some(CacheControl::ephemeral());
+            }
+            tools
+        });
+
+        let max_tokens = sampling
+            .and_then(|s| s.max_tokens)
+            .unwrap_or(self.config.max_tokens);
+        let thinking = sampling
+            .and_then(|s| s.extended_thinking)
+            .or(self.config.extended_thinking);
+        // Thinking counts against max_tokens, and the API rejects sampling
+        // changes while thinking
+        let (max_tokens, temperature, top_k) = match thinking {
+            Some(thinking) => (
+                max_tokens.saturating_add(thinking.budget_tokens),
+                None,
+                None,
+            ),
+            None => (
+                max_tokens,
+                sampling.and_then(|s| s.temperature),
+                sampling.and_then(|s| s.top_k),
+            ),
+        };
+
+        MessagesRequest {
+            model: self.config.model.clone(),
+            max_tokens,
+            system,
+            messages: api_messages,
+            tools,
+            tool_choice,
+            stream,
+            temperature,
+            top_k,
+            top_p: sampling.and_then(|s| s.top_p),
+            stop_sequences: sampling.and_then(|s| s.stop_sequences.clone()),
+            thinking: thinking.map(|thinking| ApiThinking {
+                thinking_type: "enabled",
+                budget_tokens: thinking.budget_tokens,
+            }),
+        }
+    }
+
     /// Converts internal messages to API format.
     fn convert_messages(&self, messages: &[Message]) -> (Option<String>, Vec<ApiMessage>) {
         let mut system = None;
@@ -413,6 +578,7 @@ impl AnthropicClient {
                 name: t.name.clone(),
                 description: t.description.clone(),
                 input_schema: t.input_schema.clone(),
+                cache_control: None,
             })
             .collect()
     }
@@ -514,11 +680,15 @@ impl AnthropicClient {
     fn convert_raw_event(raw: RawStreamEvent) -> Result<Option<StreamEvent>, LLMError> {
         match raw.event_type.as_str() {
             "message_start" => {
-                let id = raw
-                    .message
-                    .and_then(|m| m.get("id").and_then(|v| v.as_str().map(String::from)))
+                let message = raw.message.unwrap_or_default();
+                let id = message
+                    .get("id")
+                    .and_then(|v| v.as_str().map(String::from))
                     .unwrap_or_default();
-                Ok(Some(StreamEvent::MessageStart { id }))
+                let usage = message
+                    .get("usage")
+                    .and_then(|u| serde_json::from_value::<Usage>(u.clone()).ok());
+                Ok(Some(StreamEvent::MessageStart { id, usage }))
             }
             "content_block_start" => {
                 let index = raw.index.unwrap_or(0);
@@ -553,7 +723,11 @@ impl AnthropicClient {
                             .and_then(|v| v.as_str())
                             .unwrap_or("text_delta")
                             .to_string();
-                        let text = d.get("text").and_then(|v| v.as_str().map(String::from));
+                        // Thinking deltas carry their text in `thinking`
+                        let text = d
+                            .get("text")
+                            .or_else(|| d.get("thinking"))
+                            .and_then(|v| v.as_str().map(String::from));
                         let partial_json = d
                             .get("partial_json")
                             .and_then(|v| v.as_str().map(String::from));
@@ -577,7 +751,14 @@ impl AnthropicClient {
                     d.get("stop_reason")
                         .and_then(|v| v.as_str().map(String::from))
                 });
-                Ok(Some(StreamEvent::MessageDelta { stop_reason }))
+                let output_tokens = raw
+                    .usage
+                    .and_then(|u| u.get("output_tokens").and_then(serde_json::Value::as_u64))
+                    .map(|tokens| u32::try_from(tokens).unwrap_or(u32::MAX));
+                Ok(Some(StreamEvent::MessageDelta {
+                    stop_reason,
+                    output_tokens,
+                }))
             }
             "message_stop" => Ok(Some(StreamEvent::MessageStop)),
             "ping" => Ok(Some(StreamEvent::Ping)),
@@ -624,6 +805,9 @@ impl LLMClient for AnthropicClient {
         sampling: Option<&SamplingParams>,
     ) -> Result<LLMClientResponse, LLMError> {
         let response = self.send_messages(messages, tools, sampling).await?;
+        if structured_output_schema(tools, sampling).is_some() {
+            return Ok(structured_output_response(&response));
+        }
         Ok(LLMClientResponse {
             content: extract_text_content(&response),
             tool_calls: extract_tool_calls(&response),
@@ -632,6 +816,8 @@ impl LLMClient for AnthropicClient {
                 .as_ref()
                 .map(|s| parse_stop_reason(s))
                 .unwrap_or(StopReason::EndTurn),
+            cache_write_tokens: u64::from(response.usage.cache_creation_input_tokens),
+            cache_read_tokens: u64::from(response.usage.cache_read_input_tokens),
         })
     }
 
@@ -644,47 +830,145 @@ impl LLMClient for AnthropicClient {
         let stream = self
             .send_messages_streaming(messages, tools, sampling)
             .await?;
-        Ok(Box::pin(convert_anthropic_stream(stream)))
+        let structured = structured_output_schema(tools, sampling).is_some();
+        Ok(Box::pin(convert_anthropic_stream(stream, structured)))
     }
 
     fn provider_name(&self) -> &'static str {
         "anthropic"
     }
+
+    fn take_rate_limit_snapshot(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limit.take()
+    }
+
+    fn take_cache_usage(&self) -> Option<CacheUsage> {
+        self.cache_usage.take()
+    }
+}
+
+/// Name of the forced tool used for structured output.
+const STRUCTURED_OUTPUT_TOOL: &str = "structured_output";
+
+/// Returns the requested output schema when it is sent as a forced tool.
+///
+/// Requests that carry their own tools keep them, and rely on the prompt
+/// instructions for the final JSON answer instead.
+fn structured_output_schema<'a>(
+    tools: Option<&[ToolDefinition]>,
+    sampling: Option<&'a SamplingParams>,
+) -> Option<&'a serde_json::Value> {
+    if tools.is_some_and(|t| !t.is_empty()) {
+        return None;
+    }
+    sampling.and_then(|s| s.output_schema.as_ref())
+}
+
+/// Converts a forced structured output tool call into a text response.
+fn structured_output_response(response: &MessagesResponse) -> LLMClientResponse {
+    let content = response
+        .content
+        .iter()
+        .find_map(|block| match block {
+            ResponseContentBlock::ToolUse { input, .. } => Some(input.to_string()),
+            _ => None,
+        })
+        .unwrap_or_else(|| extract_text_content(response));
+    let stop_reason = match response.stop_reason.as_deref().map(parse_stop_reason) {
+        Some(StopReason::ToolUse) | None => StopReason::EndTurn,
+        Some(reason) => reason,
+    };
+    LLMClientResponse {
+        content,
+        tool_calls: Vec::new(),
+        stop_reason,
+        cache_write_tokens: u64::from(response.usage.cache_creation_input_tokens),
+        cache_read_tokens: u64::from(response.usage.cache_read_input_tokens),
+    }
 }
 
 /// Converts Anthropic stream events to unified LLMStreamEvent.
+///
+/// With `structured` set, the forced structured output tool's input is
+/// streamed as tokens and its `tool_use` stop ends the turn. The input
+/// usage of `message_start` is combined with the output tokens of
+/// `message_delta` into a single [`LLMStreamEvent::Usage`] sent before the
+/// end of the stream.
 fn convert_anthropic_stream(
     stream: impl futures::Stream<Item = Result<StreamEvent, LLMError>> + Send + 'static,
+    structured: bool,
 ) -> impl futures::Stream<Item = Result<LLMStreamEvent, LLMError>> + Send {
-    stream.filter_map(|result| async move {
-        match result {
-            Ok(event) => match event {
-                StreamEvent::MessageStart { id } => Some(Ok(LLMStreamEvent::Start { id })),
-                StreamEvent::ContentBlockDelta { text, .. } => {
-                    text.map(|t| Ok(LLMStreamEvent::Token { text: t }))
-                }
-                StreamEvent::MessageDelta { stop_reason } => stop_reason.map(|reason| {
-                    Ok(LLMStreamEvent::End {
-                        stop_reason: parse_stop_reason(&reason),
-                    })
-                }),
-                StreamEvent::MessageStop => Some(Ok(LLMStreamEvent::End {
-                    stop_reason: StopReason::EndTurn,
-                })),
-                StreamEvent::Error {
-                    error_type,
-                    message,
-                } => Some(Ok(LLMStreamEvent::Error {
-                    error_type,
-                    message,
-                })),
-                StreamEvent::Ping
-                | StreamEvent::ContentBlockStart { .. }
-                | StreamEvent::ContentBlockStop { .. } => None,
-            },
-            Err(e) => Some(Err(e)),
-        }
-    })
+    stream
+        .scan(None::<Usage>, move |start_usage, result| {
+            let events: Vec<Result<LLMStreamEvent, LLMError>> = match result {
+                Ok(event) => match event {
+                    StreamEvent::MessageStart { id, usage } => {
+                        *start_usage = usage;
+                        vec![Ok(LLMStreamEvent::Start { id })]
+                    }
+                    StreamEvent::ContentBlockDelta {
+                        delta_type, text, ..
+                    } if delta_type == "thinking_delta" => text
+                        .map(|t| Ok(LLMStreamEvent::ThinkingToken { text: t }))
+                        .into_iter()
+                        .collect(),
+                    StreamEvent::ContentBlockDelta {
+                        text, partial_json, ..
+                    } => text
+                        .or(partial_json.filter(|_| structured))
+                        .map(|t| Ok(LLMStreamEvent::Token { text: t }))
+                        .into_iter()
+                        .collect(),
+                    StreamEvent::MessageDelta {
+                        stop_reason,
+                        output_tokens,
+                    } => {
+                        let usage = output_tokens.map(|output_tokens| {
+                            Ok(LLMStreamEvent::Usage(stream_usage(
+                                start_usage.take(),
+                                output_tokens,
+                            )))
+                        });
+                        let end = stop_reason.map(|reason| {
+                            let stop_reason = match parse_stop_reason(&reason) {
+                                StopReason::ToolUse if structured => StopReason::EndTurn,
+                                other => other,
+                            };
+                            Ok(LLMStreamEvent::End { stop_reason })
+                        });
+                        usage.into_iter().chain(end).collect()
+                    }
+                    StreamEvent::MessageStop => vec![Ok(LLMStreamEvent::End {
+                        stop_reason: StopReason::EndTurn,
+                    })],
+                    StreamEvent::Error {
+                        error_type,
+                        message,
+                    } => vec![Ok(LLMStreamEvent::Error {
+                        error_type,
+                        message,
+                    })],
+                    StreamEvent::Ping
+                    | StreamEvent::ContentBlockStart { .. }
+                    | StreamEvent::ContentBlockStop { .. } => Vec::new(),
+                },
+                Err(e) => vec![Err(e)],
+            };
+            futures::future::ready(Some(futures::stream::iter(events)))
+        })
+        .flatten()
+}
+
+/// Combines the usage reported at the start of a stream with its final
+/// output token count.
+fn stream_usage(start_usage: Option<Usage>, output_tokens: u32) -> TokenUsage {
+    let mut usage = TokenUsage::new(0, u64::from(output_tokens));
+    if let Some(start) = start_usage {
+        usage.input_tokens = u64::from(start.input_tokens);
+        usage.cache_read_tokens = Some(u64::from(start.cache_read_input_tokens));
+        usage.cache_write_tokens = Some(u64::from(start.cache_creation_input_tokens));
+    }
+    usage
 }
 
 /// Converts an API stop reason string to our `StopReason` enum.
@@ -815,6 +1099,272 @@ mod tests {
         assert_eq!(api_tools[0].name, "calculator");
     }
 
+    #[test]
+    fn build_request_adds_cache_breakpoints() {
+        let client = AnthropicClient::new(ProviderConfig::new("test-key")).unwrap();
+        let messages = vec![Message::system("You are helpful"), Message::user("Hi")];
+        let tools = vec![
+            ToolDefinition {
+                name: "a".to_string(),
+                description: "A".to_string(),
+                input_schema: serde_json::json!({"type": "object"}),
+            },
+            ToolDefinition {
+                name: "b".to_string(),
+                description: "B".to_string(),
+                input_schema: serde_json::json!({"type": "object"}),
+            },
+        ];
+
+        let plain = client.build_request(&messages, Some(&tools), None, false);
+        let json = serde_json::to_value(&plain).unwrap();
+        assert_eq!(json["system"], "You are helpful");
+        assert!(json["tools"][1].get("cache_control").is_none());
+
+        let sampling = SamplingParams::new()
+            .with_cache_system_prompt(true)
+            .with_cache_tools(true);
+        let cached = client.build_request(&messages, Some(&tools), Some(&sampling), false);
+        let json = serde_json::to_value(&cached).unwrap();
+        assert_eq!(json["system"][0]["text"], "You are helpful");
+        assert_eq!(json["system"][0]["cache_control"]["type"], "ephemeral");
+        assert!(json["tools"][0].get("cache_control").is_none());
+        assert_eq!(json["tools"][1]["cache_control"]["type"], "ephemeral");
+    }
+
+    #[test]
+    fn build_request_caches_long_system_prompts_when_enabled() {
+        let config = ProviderConfig::new("test-key")
+            .enable_prompt_caching(true)
+            .with_cache_threshold_chars(20);
+        let client = AnthropicClient::new(config).unwrap();
+
+        let short = vec![Message::system("Be brief"), Message::user("Hi")];
+        let json = serde_json::to_value(client.build_request(&short, None, None, false)).unwrap();
+        assert_eq!(json["system"], "Be brief");
+
+        let long = vec![
+            Message::system("You review Rust pull requests carefully"),
+            Message::user("Hi"),
+        ];
+        let json = serde_json::to_value(client.build_request(&long, None, None, false)).unwrap();
+        assert_eq!(json["system"][0]["cache_control"]["type"], "ephemeral");
+
+        let opt_out = SamplingParams::new().with_cache_system_prompt(false);
+        let request = client.build_request(&long, None, Some(&opt_out), false);
+        let json = serde_json::to_value(request).unwrap();
+        assert!(json["system"].is_string());
+    }
+
+    #[test]
+    fn build_request_forces_structured_output_tool() {
+        let client = AnthropicClient::new(ProviderConfig::new("test-key")).unwrap();
+        let messages = vec![Message::user("Hi")];
+        let schema = serde_json::json!({"type": "object"});
+        let sampling = SamplingParams::new().with_output_schema(schema.clone());
+
+        let request = client.build_request(&messages, None, Some(&sampling), false);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["tools"][0]["name"], STRUCTURED_OUTPUT_TOOL);
+        assert_eq!(json["tools"][0]["input_schema"], schema);
+        assert_eq!(json["tool_choice"]["name"], STRUCTURED_OUTPUT_TOOL);
+
+        // Requests with their own tools keep them
+        let tools = vec![ToolDefinition {
+            name: "a".to_string(),
+            description: "A".to_string(),
+            input_schema: serde_json::json!({"type": "object"}),
+        }];
+        let request = client.build_request(&messages, Some(&tools), Some(&sampling), false);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["tools"][0]["name"], "a");
+        assert!(json.get("tool_choice").is_none());
+    }
+
+    #[test]
+    fn build_request_enables_extended_thinking() {
+        let config = ProviderConfig::new("test-key")
+            .with_max_tokens(1000)
+            .with_extended_thinking(2048);
+        let client = AnthropicClient::new(config).unwrap();
+        let messages = vec![Message::user("Hi")];
+        let sampling = SamplingParams::new().with_temperature(0.2);
+
+        let request = client.build_request(&messages, None, Some(&sampling), false);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["thinking"]["type"], "enabled");
+        assert_eq!(json["thinking"]["budget_tokens"], 2048);
+        assert_eq!(json["max_tokens"], 3048);
+        assert!(json.get("temperature").is_none());
+
+        let sampling = SamplingParams::new().with_thinking(4096);
+        let request = client.build_request(&messages, None, Some(&sampling), false);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["thinking"]["budget_tokens"], 4096);
+    }
+
+    #[tokio::test]
+    async fn convert_stream_separates_thinking_tokens() {
+        let text = concat!(
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"Let me see."}}"#,
+            "\n",
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"abc"}}"#,
+            "\n",
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"42"}}"#,
+        );
+        let events = AnthropicClient::parse_sse_events(text).unwrap();
+
+        let converted: Vec<_> =
+            convert_anthropic_stream(futures::stream::iter(events.into_iter().map(Ok)), false)
+                .map(Result::unwrap)
+                .collect()
+                .await;
+
+        assert_eq!(converted.len(), 2);
+        assert!(
+            matches!(&converted[0], LLMStreamEvent::ThinkingToken { text } if text == "Let me see.")
+        );
+        assert!(matches!(&converted[1], LLMStreamEvent::Token { text } if text == "42"));
+    }
+
+    #[tokio::test]
+    async fn convert_stream_emits_usage_before_end() {
+        let text = concat!(
+            r#"data: {"type":"message_start","message":{"id":"msg_1","usage":{"input_tokens":25,"output_tokens":1,"cache_read_input_tokens":10}}}"#,
+            "\n",
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#,
+            "\n",
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":15}}"#,
+        );
+        let events = AnthropicClient::parse_sse_events(text).unwrap();
+
+        let converted: Vec<_> =
+            convert_anthropic_stream(futures::stream::iter(events.into_iter().map(Ok)), false)
+                .map(Result::unwrap)
+                .collect()
+                .await;
+
+        assert_eq!(converted.len(), 4);
+        match &converted[2] {
+            LLMStreamEvent::Usage(usage) => {
+                assert_eq!(usage.input_tokens, 25);
+                assert_eq!(usage.output_tokens, 15);
+                assert_eq!(usage.cache_read_tokens, Some(10));
+                assert_eq!(usage.cache_write_tokens, Some(0));
+            }
+            other => panic!("Expected Usage, got {other:?}"),
+        }
+        assert!(matches!(
+            converted[3],
+            LLMStreamEvent::End {
+                stop_reason: StopReason::EndTurn
+            }
+        ));
+    }
+
+    #[test]
+    fn response_with_thinking_blocks_deserializes() {
+        let response: MessagesResponse = serde_json::from_value(serde_json::json!({
+            "id": "msg_1",
+            "model": "claude-3-7-sonnet-20250219",
+            "stop_reason": "end_turn",
+            "content": [
+                {"type": "thinking", "thinking": "Two plus two...", "signature": "sig"},
+                {"type": "redacted_thinking", "data": "opaque"},
+                {"type": "text", "text": "4"}
+            ],
+            "usage": {"input_tokens": 10, "output_tokens": 20}
+        }))
+        .unwrap();
+
+        assert_eq!(extract_text_content(&response), "4");
+    }
+
+    #[tokio::test]
+    async fn convert_stream_emits_structured_output_as_tokens() {
+        let events = vec![
+            Ok(StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta_type: "input_json_delta".to_string(),
+                text: None,
+                partial_json: Some("{\"a\":".to_string()),
+            }),
+            Ok(StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta_type: "input_json_delta".to_string(),
+                text: None,
+                partial_json: Some("1}".to_string()),
+            }),
+            Ok(StreamEvent::MessageDelta {
+                stop_reason: Some("tool_use".to_string()),
+                output_tokens: None,
+            }),
+        ];
+
+        let converted: Vec<_> = convert_anthropic_stream(futures::stream::iter(events), true)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        let text: String = converted
+            .iter()
+            .filter_map(|event| match event {
+                LLMStreamEvent::Token { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "{\"a\":1}");
+        assert!(matches!(
+            converted.last(),
+            Some(LLMStreamEvent::End {
+                stop_reason: StopReason::EndTurn
+            })
+        ));
+    }
+
+    #[test]
+    fn structured_output_response_uses_tool_input() {
+        let response = MessagesResponse {
+            id: "msg_123".to_string(),
+            model: "claude-3-sonnet".to_string(),
+            stop_reason: Some("tool_use".to_string()),
+            content: vec![ResponseContentBlock::ToolUse {
+                id: "tc_1".to_string(),
+                name: STRUCTURED_OUTPUT_TOOL.to_string(),
+                input: serde_json::json!({"answer": 42}),
+            }],
+            usage: Usage {
+                input_tokens: 10,
+                output_tokens: 5,
+                cache_creation_input_tokens: 0,
+                cache_read_input_tokens: 0,
+            },
+        };
+
+        let converted = structured_output_response(&response);
+
+        assert_eq!(converted.content, r#"{"answer":42}"#);
+        assert!(converted.tool_calls.is_empty());
+        assert_eq!(converted.stop_reason, StopReason::EndTurn);
+    }
+
+    #[test]
+    fn parse_sse_events_message_start_usage() {
+        let text = r#"data: {"type":"message_start","message":{"id":"msg_1","usage":{"input_tokens":10,"output_tokens":1,"cache_creation_input_tokens":0,"cache_read_input_tokens":2048}}}"#;
+
+        let events = AnthropicClient::parse_sse_events(text).unwrap();
+
+        match &events[0] {
+            StreamEvent::MessageStart { id, usage } => {
+                assert_eq!(id, "msg_1");
+                let cache = usage.as_ref().unwrap().cache_usage();
+                assert_eq!(cache.cache_read_tokens, 2048);
+                assert_eq!(cache.cache_creation_tokens, 0);
+            }
+            _ => panic!("Expected MessageStart"),
+        }
+    }
+
     #[test]
     fn parse_sse_events_text_delta() {
         let text = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hello"}}"#;
@@ -883,6 +1433,8 @@ mod tests {
             usage: Usage {
                 input_tokens: 10,
                 output_tokens: 5,
+                cache_creation_input_tokens: 0,
+                cache_read_input_tokens: 0,
             },
         };
 
@@ -908,6 +1460,8 @@ mod tests {
             usage: Usage {
                 input_tokens: 10,
                 output_tokens: 5,
+                cache_creation_input_tokens: 0,
+                cache_read_input_tokens: 0,
             },
         };
 
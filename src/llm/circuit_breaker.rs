@@ -0,0 +1,162 @@
+//! Per-provider circuit breaker.
+//!
+//! When a provider keeps failing with server errors or timeouts, sending it
+//! more requests only adds latency. A [`CircuitBreaker`] counts consecutive
+//! failures; once [`CircuitBreakerConfig::failure_threshold`] is reached the
+//! circuit opens and the provider rejects requests without calling the API.
+//! After the cool-down a single probe request is let through: success closes
+//! the circuit, failure opens it again.
+
+use crate::llm::config::CircuitBreakerConfig;
+use crate::llm::error::LLMError;
+use std::time::Instant;
+
+/// State of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are rejected until the cool-down has passed
+    Open,
+    /// One probe request is in flight to test recovery
+    HalfOpen,
+}
+
+/// Failure-counting state machine guarding a provider.
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Creates a closed circuit breaker.
+    #[must_use]
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+        }
+    }
+
+    /// Returns the current state.
+    #[must_use]
+    pub fn state(&self) -> CircuitState {
+        self.state
+    }
+
+    /// Returns the number of consecutive failures recorded.
+    #[must_use]
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    /// Returns true if a request may be sent now.
+    ///
+    /// An open circuit whose cool-down has passed moves to
+    /// [`CircuitState::HalfOpen`] and admits the caller as the probe; further
+    /// requests are rejected until the probe's outcome is recorded.
+    pub fn allow_request(&mut self) -> bool {
+        match self.state {
+            CircuitState::Closed => true,
+            CircuitState::HalfOpen => false,
+            CircuitState::Open => {
+                let cooled_down = self
+                    .opened_at
+                    .is_none_or(|at| at.elapsed() >= self.config.cooldown);
+                if cooled_down {
+                    self.state = CircuitState::HalfOpen;
+                }
+                cooled_down
+            }
+        }
+    }
+
+    /// Records the outcome of a request that was allowed through.
+    ///
+    /// Errors that do not [trip the circuit](LLMError::trips_circuit), such
+    /// as invalid requests, count as the provider being reachable.
+    pub fn record(&mut self, outcome: Result<(), &LLMError>) {
+        match outcome {
+            Err(error) if error.trips_circuit() => self.record_failure(),
+            _ => self.record_success(),
+        }
+    }
+
+    fn record_success(&mut self) {
+        self.state = CircuitState::Closed;
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+    }
+
+    fn record_failure(&mut self) {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if self.state == CircuitState::HalfOpen
+            || self.consecutive_failures >= self.config.failure_threshold
+        {
+            self.state = CircuitState::Open;
+            self.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn server_error() -> LLMError {
+        LLMError::api_error(503, "unavailable", None)
+    }
+
+    #[test]
+    fn opens_after_threshold() {
+        let mut breaker =
+            CircuitBreaker::new(CircuitBreakerConfig::new(2, Duration::from_secs(60)));
+
+        breaker.record(Err(&server_error()));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record(Err(&server_error()));
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(!breaker.allow_request());
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let mut breaker =
+            CircuitBreaker::new(CircuitBreakerConfig::new(2, Duration::from_secs(60)));
+
+        breaker.record(Err(&server_error()));
+        breaker.record(Ok(()));
+        breaker.record(Err(&server_error()));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record(Err(&LLMError::invalid_request("bad")));
+        assert_eq!(breaker.consecutive_failures(), 0);
+    }
+
+    #[test]
+    fn half_open_probe_closes_or_reopens() {
+        let mut breaker = CircuitBreaker::new(CircuitBreakerConfig::new(1, Duration::ZERO));
+
+        breaker.record(Err(&server_error()));
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Cool-down elapsed: one probe is admitted, the next caller waits
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(!breaker.allow_request());
+
+        breaker.record(Err(&server_error()));
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        assert!(breaker.allow_request());
+        breaker.record(Ok(()));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.allow_request());
+    }
+}
@@ -4,8 +4,11 @@
 //! LLM providers (Anthropic, OpenAI, Ollama, etc.) allowing the LLMProvider
 //! actor to work with any backend.
 
+use crate::llm::adaptive_rate_limit::RateLimitSnapshot;
 use crate::llm::config::SamplingParams;
 use crate::llm::error::LLMError;
+use crate::llm::prompt_cache::CacheUsage;
+use crate::llm::usage::TokenUsage;
 use crate::messages::{Message, StopReason, ToolCall, ToolDefinition};
 use async_trait::async_trait;
 use futures::Stream;
@@ -24,11 +27,18 @@ pub enum LLMStreamEvent {
         /// The text content of the token
         text: String,
     },
+    /// A token of the model's extended thinking trace was generated
+    ThinkingToken {
+        /// The text content of the token
+        text: String,
+    },
     /// A tool call was generated
     ToolCall {
         /// The tool call details
         tool_call: ToolCall,
     },
+    /// Token usage of the response, reported before the stream ends
+    Usage(TokenUsage),
     /// The stream has ended
     End {
         /// The reason the stream ended
@@ -52,6 +62,10 @@ pub struct LLMClientResponse {
     pub tool_calls: Vec<ToolCall>,
     /// The reason the model stopped generating
     pub stop_reason: StopReason,
+    /// Input tokens written to the prompt cache (Anthropic only)
+    pub cache_write_tokens: u64,
+    /// Input tokens served from the prompt cache (Anthropic only)
+    pub cache_read_tokens: u64,
 }
 
 /// Type alias for boxed stream of LLM events.
@@ -121,6 +135,21 @@ pub trait LLMClient: Send + Sync + std::fmt::Debug {
 
     /// Returns the name of this provider for logging/metrics.
     fn provider_name(&self) -> &'static str;
+
+    /// Takes the rate-limit headers reported by the most recent response.
+    ///
+    /// Used by adaptive rate limiting. Clients whose API reports no quota
+    /// headers keep the default, which returns `None`.
+    fn take_rate_limit_snapshot(&self) -> Option<RateLimitSnapshot> {
+        None
+    }
+
+    /// Takes the prompt cache usage reported by the most recent response.
+    ///
+    /// Clients without prompt caching keep the default, which returns `None`.
+    fn take_cache_usage(&self) -> Option<CacheUsage> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +181,8 @@ mod tests {
             content: "Hello".to_string(),
             tool_calls: vec![],
             stop_reason: StopReason::EndTurn,
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
         };
         let debug_str = format!("{:?}", response);
         assert!(debug_str.contains("Hello"));
@@ -164,6 +195,8 @@ mod tests {
             content: "Hello".to_string(),
             tool_calls: vec![],
             stop_reason: StopReason::EndTurn,
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
         };
         let cloned = response.clone();
         assert_eq!(cloned.content, "Hello");
@@ -195,6 +228,15 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn llm_stream_event_usage_variant() {
+        let event = LLMStreamEvent::Usage(TokenUsage::new(10, 5));
+        assert!(matches!(
+            event,
+            LLMStreamEvent::Usage(usage) if usage.total_tokens() == 15
+        ));
+    }
+
     #[test]
     fn llm_stream_event_tool_call_variant() {
         let tool_call = ToolCall {
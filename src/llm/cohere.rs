@@ -0,0 +1,771 @@
+//! Cohere API client.
+//!
+//! HTTP client for the Cohere v2 chat API (Command R / Command A models),
+//! including streaming SSE response handling and tool use.
+
+use crate::llm::client::{LLMClient, LLMClientResponse, LLMEventStream, LLMStreamEvent};
+use crate::llm::config::{ProviderConfig, SamplingParams};
+use crate::llm::error::LLMError;
+use crate::messages::{Message, MessageRole, StopReason, ToolCall, ToolDefinition};
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Client for the Cohere chat API.
+#[derive(Debug, Clone)]
+pub struct CohereClient {
+    /// HTTP client
+    client: Client,
+    /// Configuration
+    config: ProviderConfig,
+}
+
+/// Request body for the Cohere v2 chat API.
+#[derive(Debug, Clone, Serialize)]
+struct CohereChatRequest {
+    model: String,
+    messages: Vec<CohereMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<CohereTool>>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(rename = "p", skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    #[serde(rename = "k", skip_serializing_if = "Option::is_none")]
+    top_k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+}
+
+/// A request message in Cohere format.
+#[derive(Debug, Clone, Serialize)]
+struct CohereMessage {
+    role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<CohereToolCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+/// A tool definition in Cohere format.
+#[derive(Debug, Clone, Serialize)]
+struct CohereTool {
+    #[serde(rename = "type")]
+    tool_type: String,
+    function: CohereFunction,
+}
+
+/// A function definition in Cohere format.
+#[derive(Debug, Clone, Serialize)]
+struct CohereFunction {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+/// A tool call in Cohere format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CohereToolCall {
+    id: String,
+    #[serde(rename = "type")]
+    call_type: String,
+    function: CohereFunctionCall,
+}
+
+/// A function call in Cohere format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CohereFunctionCall {
+    name: String,
+    arguments: String,
+}
+
+/// Non-streaming response from the Cohere chat API.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereChatResponse {
+    #[allow(dead_code)]
+    id: String,
+    finish_reason: Option<String>,
+    message: CohereResponseMessage,
+}
+
+/// The assistant message in a non-streaming response.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereResponseMessage {
+    #[serde(default)]
+    content: Option<Vec<CohereContentBlock>>,
+    #[serde(default)]
+    tool_calls: Option<Vec<CohereToolCall>>,
+}
+
+/// A content block in a response message.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+/// A streaming event from the Cohere chat API.
+///
+/// Event types: `message-start`, `content-start`, `content-delta`,
+/// `content-end`, `tool-plan-delta`, `tool-call-start`, `tool-call-delta`,
+/// `tool-call-end`, `citation-start`, `citation-end`, and `message-end`.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereStreamEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    delta: Option<CohereStreamDelta>,
+}
+
+/// Delta payload of a streaming event.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereStreamDelta {
+    #[serde(default)]
+    message: Option<CohereDeltaMessage>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+/// Message fragment within a streaming delta.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereDeltaMessage {
+    #[serde(default)]
+    content: Option<CohereDeltaContent>,
+    #[serde(default)]
+    tool_calls: Option<CohereToolCallDelta>,
+}
+
+/// Text fragment within a streaming delta.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereDeltaContent {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+/// Tool call fragment within a streaming delta.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereToolCallDelta {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<CohereFunctionCallDelta>,
+}
+
+/// Function call fragment within a streaming delta.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereFunctionCallDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+/// Error response from the Cohere API.
+#[derive(Debug, Clone, Deserialize)]
+struct CohereErrorResponse {
+    message: String,
+}
+
+/// Accumulator for building tool calls from streaming deltas.
+#[derive(Debug, Clone, Default)]
+struct ToolCallAccumulator {
+    id: Option<String>,
+    name: Option<String>,
+    arguments: String,
+}
+
+/// State carried across streaming events.
+#[derive(Debug, Default)]
+struct StreamParseState {
+    tool_accumulators: HashMap<usize, ToolCallAccumulator>,
+    emitted_tool_calls: bool,
+}
+
+impl CohereClient {
+    /// Creates a new Cohere client with the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Provider configuration including API key and settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `LLMError::network` if the HTTP client cannot be created.
+    pub fn new(config: ProviderConfig) -> Result<Self, LLMError> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| LLMError::network(format!("failed to create HTTP client: {}", e)))?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Returns the chat endpoint URL.
+    fn chat_endpoint(&self) -> String {
+        format!("{}/v2/chat", self.config.base_url)
+    }
+
+    /// Converts internal messages to Cohere API format.
+    fn convert_messages(&self, messages: &[Message]) -> Vec<CohereMessage> {
+        messages
+            .iter()
+            .map(|msg| match msg.role {
+                MessageRole::System | MessageRole::User => CohereMessage {
+                    role: msg.role.to_string(),
+                    content: Some(msg.content.clone()),
+                    tool_calls: None,
+                    tool_call_id: None,
+                },
+                MessageRole::Assistant => {
+                    let tool_calls = msg.tool_calls.as_ref().map(|tcs| {
+                        tcs.iter()
+                            .map(|tc| CohereToolCall {
+                                id: tc.id.clone(),
+                                call_type: "function".to_string(),
+                                function: CohereFunctionCall {
+                                    name: tc.name.clone(),
+                                    arguments: tc.arguments.to_string(),
+                                },
+                            })
+                            .collect()
+                    });
+
+                    CohereMessage {
+                        role: "assistant".to_string(),
+                        content: if msg.content.is_empty() {
+                            None
+                        } else {
+                            Some(msg.content.clone())
+                        },
+                        tool_calls,
+                        tool_call_id: None,
+                    }
+                }
+                MessageRole::Tool => CohereMessage {
+                    role: "tool".to_string(),
+                    content: Some(msg.content.clone()),
+                    tool_calls: None,
+                    tool_call_id: msg.tool_call_id.clone(),
+                },
+            })
+            .collect()
+    }
+
+    /// Converts tool definitions to Cohere API format.
+    fn convert_tools(&self, tools: &[ToolDefinition]) -> Vec<CohereTool> {
+        tools
+            .iter()
+            .map(|t| CohereTool {
+                tool_type: "function".to_string(),
+                function: CohereFunction {
+                    name: t.name.clone(),
+                    description: t.description.clone(),
+                    parameters: t.input_schema.clone(),
+                },
+            })
+            .collect()
+    }
+
+    /// Builds the request body.
+    fn build_request_body(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+        stream: bool,
+    ) -> CohereChatRequest {
+        CohereChatRequest {
+            model: self.config.model.clone(),
+            messages: self.convert_messages(messages),
+            tools: tools.map(|t| self.convert_tools(t)),
+            stream,
+            max_tokens: Some(
+                sampling
+                    .and_then(|s| s.max_tokens)
+                    .unwrap_or(self.config.max_tokens),
+            ),
+            temperature: sampling.and_then(|s| s.temperature),
+            top_p: sampling.and_then(|s| s.top_p),
+            top_k: sampling.and_then(|s| s.top_k),
+            frequency_penalty: sampling.and_then(|s| s.frequency_penalty),
+            presence_penalty: sampling.and_then(|s| s.presence_penalty),
+            seed: sampling.and_then(|s| s.seed),
+            stop_sequences: sampling.and_then(|s| s.stop_sequences.clone()),
+        }
+    }
+
+    /// Sends the request and maps non-success statuses to errors.
+    async fn post(&self, request_body: &CohereChatRequest) -> Result<reqwest::Response, LLMError> {
+        let response = self
+            .client
+            .post(self.chat_endpoint())
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("content-type", "application/json")
+            .json(request_body)
+            .send()
+            .await
+            .map_err(|e| self.map_reqwest_error(e))?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        Ok(response)
+    }
+
+    /// Parses Cohere finish reason to internal format.
+    #[must_use]
+    pub fn parse_stop_reason(reason: Option<&str>) -> StopReason {
+        match reason {
+            Some("COMPLETE") => StopReason::EndTurn,
+            Some("MAX_TOKENS") => StopReason::MaxTokens,
+            Some("STOP_SEQUENCE") => StopReason::StopSequence,
+            Some("TOOL_CALL") => StopReason::ToolUse,
+            _ => StopReason::EndTurn,
+        }
+    }
+
+    /// Parses an error response from the API.
+    async fn parse_error_response(&self, response: reqwest::Response) -> LLMError {
+        let status = response.status();
+        let status_code = status.as_u16();
+
+        // Check for rate limit
+        if status_code == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(60);
+
+            return LLMError::rate_limited(Duration::from_secs(retry_after));
+        }
+
+        // Try to parse error body
+        let error_body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<CohereErrorResponse>(&error_body)
+            .map(|e| e.message)
+            .unwrap_or_else(|_| {
+                if error_body.is_empty() {
+                    status
+                        .canonical_reason()
+                        .unwrap_or("Unknown error")
+                        .to_string()
+                } else {
+                    error_body
+                }
+            });
+
+        match status_code {
+            401 | 403 => LLMError::authentication_failed(message),
+            400 | 422 => LLMError::invalid_request(message),
+            _ => LLMError::api_error(status_code, message, None),
+        }
+    }
+
+    /// Maps a reqwest error to an LLMError.
+    fn map_reqwest_error(&self, error: reqwest::Error) -> LLMError {
+        if error.is_timeout() {
+            LLMError::timeout(self.config.timeout)
+        } else {
+            LLMError::network(format!("request failed: {}", error))
+        }
+    }
+
+    /// Parses a single SSE line into a stream event.
+    ///
+    /// Returns `None` for `event:` lines, blank lines, and anything else
+    /// that does not carry a `data:` payload.
+    fn parse_sse_line(line: &str) -> Option<Result<CohereStreamEvent, LLMError>> {
+        let data = line.strip_prefix("data:")?.trim_start();
+
+        if data.is_empty() || data == "[DONE]" {
+            return None;
+        }
+
+        Some(
+            serde_json::from_str::<CohereStreamEvent>(data)
+                .map_err(|e| LLMError::parse_error(format!("failed to parse SSE event: {}", e))),
+        )
+    }
+
+    /// Applies one streaming event, appending the resulting LLM events to `out`.
+    fn apply_stream_event(
+        event: CohereStreamEvent,
+        state: &mut StreamParseState,
+        out: &mut VecDeque<Result<LLMStreamEvent, LLMError>>,
+    ) {
+        let index = event.index.unwrap_or(0);
+        let message = event.delta.as_ref().and_then(|d| d.message.as_ref());
+
+        match event.event_type.as_str() {
+            "message-start" => {
+                if let Some(id) = event.id {
+                    out.push_back(Ok(LLMStreamEvent::Start { id }));
+                }
+            }
+            "content-delta" => {
+                if let Some(text) = message
+                    .and_then(|m| m.content.as_ref())
+                    .and_then(|c| c.text.clone())
+                {
+                    if !text.is_empty() {
+                        out.push_back(Ok(LLMStreamEvent::Token { text }));
+                    }
+                }
+            }
+            "tool-call-start" | "tool-call-delta" => {
+                if let Some(delta) = message.and_then(|m| m.tool_calls.as_ref()) {
+                    let acc = state.tool_accumulators.entry(index).or_default();
+                    if let Some(ref id) = delta.id {
+                        acc.id = Some(id.clone());
+                    }
+                    if let Some(ref func) = delta.function {
+                        if let Some(ref name) = func.name {
+                            acc.name = Some(name.clone());
+                        }
+                        if let Some(ref args) = func.arguments {
+                            acc.arguments.push_str(args);
+                        }
+                    }
+                }
+            }
+            "tool-call-end" => {
+                if let Some(acc) = state.tool_accumulators.remove(&index) {
+                    Self::emit_tool_call(acc, state, out);
+                }
+            }
+            "message-end" => {
+                // Flush any tool calls whose tool-call-end never arrived
+                let mut pending: Vec<_> = state.tool_accumulators.drain().collect();
+                pending.sort_by_key(|(index, _)| *index);
+                for (_, acc) in pending {
+                    Self::emit_tool_call(acc, state, out);
+                }
+
+                let reason = event
+                    .delta
+                    .as_ref()
+                    .and_then(|d| d.finish_reason.as_deref());
+                let stop_reason = if state.emitted_tool_calls {
+                    StopReason::ToolUse
+                } else {
+                    Self::parse_stop_reason(reason)
+                };
+                out.push_back(Ok(LLMStreamEvent::End { stop_reason }));
+            }
+            _ => {}
+        }
+    }
+
+    /// Emits an accumulated tool call if it has both an ID and a name.
+    fn emit_tool_call(
+        acc: ToolCallAccumulator,
+        state: &mut StreamParseState,
+        out: &mut VecDeque<Result<LLMStreamEvent, LLMError>>,
+    ) {
+        if let (Some(id), Some(name)) = (acc.id, acc.name) {
+            let arguments: serde_json::Value =
+                serde_json::from_str(&acc.arguments).unwrap_or(serde_json::json!({}));
+            out.push_back(Ok(LLMStreamEvent::ToolCall {
+                tool_call: ToolCall {
+                    id,
+                    name,
+                    arguments,
+                },
+            }));
+            state.emitted_tool_calls = true;
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for CohereClient {
+    async fn send_request(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+    ) -> Result<LLMClientResponse, LLMError> {
+        let request_body = self.build_request_body(messages, tools, sampling, false);
+        let response = self.post(&request_body).await?;
+
+        let chat: CohereChatResponse = response
+            .json()
+            .await
+            .map_err(|e| LLMError::parse_error(format!("failed to parse response: {}", e)))?;
+
+        let content = chat
+            .message
+            .content
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|block| block.block_type == "text")
+            .filter_map(|block| block.text)
+            .collect::<String>();
+
+        let tool_calls = chat
+            .message
+            .tool_calls
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|tc| {
+                let arguments: serde_json::Value =
+                    serde_json::from_str(&tc.function.arguments).ok()?;
+                Some(ToolCall {
+                    id: tc.id,
+                    name: tc.function.name,
+                    arguments,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let stop_reason = if !tool_calls.is_empty() {
+            StopReason::ToolUse
+        } else {
+            Self::parse_stop_reason(chat.finish_reason.as_deref())
+        };
+
+        Ok(LLMClientResponse {
+            content,
+            tool_calls,
+            stop_reason,
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
+        })
+    }
+
+    async fn send_streaming_request(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+    ) -> Result<LLMEventStream, LLMError> {
+        let request_body = self.build_request_body(messages, tools, sampling, true);
+        let response = self.post(&request_body).await?;
+
+        // State carried through the unfold iteration. `buffer` holds a
+        // trailing partial line until the next chunk completes it.
+        struct StreamState<S> {
+            stream: S,
+            buffer: String,
+            parse: StreamParseState,
+            pending_events: VecDeque<Result<LLMStreamEvent, LLMError>>,
+        }
+
+        let event_stream = futures::stream::unfold(
+            StreamState {
+                stream: response.bytes_stream(),
+                buffer: String::new(),
+                parse: StreamParseState::default(),
+                pending_events: VecDeque::new(),
+            },
+            |mut state| async move {
+                loop {
+                    if let Some(event) = state.pending_events.pop_front() {
+                        return Some((event, state));
+                    }
+
+                    match state.stream.next().await? {
+                        Ok(bytes) => {
+                            state.buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+                            while let Some(newline) = state.buffer.find('\n') {
+                                let line: String = state.buffer.drain(..=newline).collect();
+                                match CohereClient::parse_sse_line(line.trim_end()) {
+                                    Some(Ok(event)) => CohereClient::apply_stream_event(
+                                        event,
+                                        &mut state.parse,
+                                        &mut state.pending_events,
+                                    ),
+                                    Some(Err(e)) => state.pending_events.push_back(Err(e)),
+                                    None => {}
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            return Some((
+                                Err(LLMError::stream_error(format!("stream read error: {}", e))),
+                                state,
+                            ));
+                        }
+                    }
+                }
+            },
+        );
+
+        Ok(Box::pin(event_stream))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "cohere"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_client() -> CohereClient {
+        CohereClient::new(ProviderConfig::cohere("test-key")).unwrap()
+    }
+
+    fn apply_lines(lines: &[&str]) -> Vec<LLMStreamEvent> {
+        let mut state = StreamParseState::default();
+        let mut out = VecDeque::new();
+        for line in lines {
+            if let Some(event) = CohereClient::parse_sse_line(line) {
+                CohereClient::apply_stream_event(event.unwrap(), &mut state, &mut out);
+            }
+        }
+        out.into_iter().map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn cohere_client_chat_endpoint() {
+        let client = create_test_client();
+        assert_eq!(client.chat_endpoint(), "https://api.cohere.com/v2/chat");
+    }
+
+    #[test]
+    fn cohere_convert_messages() {
+        let client = create_test_client();
+        let tool_calls = vec![ToolCall {
+            id: "tc_1".to_string(),
+            name: "search".to_string(),
+            arguments: serde_json::json!({"query": "rust"}),
+        }];
+        let messages = vec![
+            Message::system("Be brief."),
+            Message::user("Find Rust docs"),
+            Message::assistant_with_tools("", tool_calls),
+            Message::tool("tc_1", "docs.rs"),
+        ];
+
+        let api_messages = client.convert_messages(&messages);
+
+        assert_eq!(api_messages.len(), 4);
+        assert_eq!(api_messages[0].role, "system");
+        assert_eq!(api_messages[1].role, "user");
+        assert_eq!(api_messages[2].role, "assistant");
+        assert!(api_messages[2].content.is_none());
+        let calls = api_messages[2].tool_calls.as_ref().unwrap();
+        assert_eq!(calls[0].function.name, "search");
+        assert_eq!(calls[0].function.arguments, r#"{"query":"rust"}"#);
+        assert_eq!(api_messages[3].role, "tool");
+        assert_eq!(api_messages[3].tool_call_id, Some("tc_1".to_string()));
+    }
+
+    #[test]
+    fn cohere_request_uses_cohere_sampling_names() {
+        let client = create_test_client();
+        let sampling = SamplingParams::new().with_top_p(0.9).with_top_k(40);
+        let body = client.build_request_body(&[Message::user("hi")], None, Some(&sampling), false);
+
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["p"], 0.9);
+        assert_eq!(json["k"], 40);
+        assert_eq!(json["model"], "command-r-plus-08-2024");
+        assert!(json.get("tools").is_none());
+    }
+
+    #[test]
+    fn cohere_parse_stop_reason() {
+        assert_eq!(
+            CohereClient::parse_stop_reason(Some("COMPLETE")),
+            StopReason::EndTurn
+        );
+        assert_eq!(
+            CohereClient::parse_stop_reason(Some("MAX_TOKENS")),
+            StopReason::MaxTokens
+        );
+        assert_eq!(
+            CohereClient::parse_stop_reason(Some("STOP_SEQUENCE")),
+            StopReason::StopSequence
+        );
+        assert_eq!(
+            CohereClient::parse_stop_reason(Some("TOOL_CALL")),
+            StopReason::ToolUse
+        );
+        assert_eq!(CohereClient::parse_stop_reason(None), StopReason::EndTurn);
+    }
+
+    #[test]
+    fn cohere_parse_sse_ignores_event_lines() {
+        assert!(CohereClient::parse_sse_line("event: content-delta").is_none());
+        assert!(CohereClient::parse_sse_line("").is_none());
+    }
+
+    #[test]
+    fn cohere_stream_text_events() {
+        let events = apply_lines(&[
+            r#"data: {"id":"msg_1","type":"message-start","delta":{"message":{"role":"assistant"}}}"#,
+            r#"data: {"type":"content-delta","index":0,"delta":{"message":{"content":{"text":"Hello"}}}}"#,
+            r#"data: {"type":"message-end","delta":{"finish_reason":"COMPLETE"}}"#,
+        ]);
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(&events[0], LLMStreamEvent::Start { id } if id == "msg_1"));
+        assert!(matches!(&events[1], LLMStreamEvent::Token { text } if text == "Hello"));
+        assert!(matches!(
+            &events[2],
+            LLMStreamEvent::End { stop_reason } if *stop_reason == StopReason::EndTurn
+        ));
+    }
+
+    #[test]
+    fn cohere_stream_tool_call_events() {
+        let events = apply_lines(&[
+            r#"data: {"type":"tool-call-start","index":0,"delta":{"message":{"tool_calls":{"id":"call_1","type":"function","function":{"name":"search","arguments":""}}}}}"#,
+            r#"data: {"type":"tool-call-delta","index":0,"delta":{"message":{"tool_calls":{"function":{"arguments":"{\"query\":"}}}}}"#,
+            r#"data: {"type":"tool-call-delta","index":0,"delta":{"message":{"tool_calls":{"function":{"arguments":"\"rust\"}"}}}}}"#,
+            r#"data: {"type":"tool-call-end","index":0}"#,
+            r#"data: {"type":"message-end","delta":{"finish_reason":"TOOL_CALL"}}"#,
+        ]);
+
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            LLMStreamEvent::ToolCall { tool_call } => {
+                assert_eq!(tool_call.id, "call_1");
+                assert_eq!(tool_call.name, "search");
+                assert_eq!(tool_call.arguments, serde_json::json!({"query": "rust"}));
+            }
+            other => panic!("expected tool call, got {other:?}"),
+        }
+        assert!(matches!(
+            &events[1],
+            LLMStreamEvent::End { stop_reason } if *stop_reason == StopReason::ToolUse
+        ));
+    }
+
+    #[test]
+    fn cohere_client_implements_llm_client() {
+        let _boxed: Box<dyn LLMClient> = Box::new(create_test_client());
+    }
+
+    #[test]
+    fn cohere_client_provider_name() {
+        assert_eq!(create_test_client().provider_name(), "cohere");
+    }
+}
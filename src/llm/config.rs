@@ -3,25 +3,38 @@
 //! Configuration types for the LLM Provider actor including API settings,
 //! rate limiting, and retry behavior.
 
+use crate::llm::error::LLMError;
+use crate::llm::response_cache::CacheConfig;
+use crate::messages::{Message, MessageRole};
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
 use std::time::Duration;
 
 /// The type of LLM provider to use.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ProviderType {
     /// Anthropic Claude API
+    #[default]
     Anthropic,
     /// OpenAI-compatible API (including Ollama, vLLM, LocalAI, etc.)
     OpenAI {
         /// Base URL for the API (e.g., "http://localhost:11434/v1" for Ollama)
         base_url: String,
     },
-}
-
-impl Default for ProviderType {
-    fn default() -> Self {
-        Self::Anthropic
-    }
+    /// Cohere chat API (Command R / Command A models)
+    Cohere,
+    /// Google Gemini API
+    Gemini,
+    /// Mistral AI API
+    Mistral,
+    /// Azure OpenAI Service, addressed by resource and deployment
+    AzureOpenAI {
+        /// Azure resource name (the `{resource}` in `{resource}.openai.azure.com`)
+        resource: String,
+        /// Deployment name, which selects the model
+        deployment: String,
+    },
 }
 
 impl ProviderType {
@@ -48,6 +61,34 @@ impl ProviderType {
             base_url: "https://api.openai.com/v1".to_string(),
         }
     }
+
+    /// Creates an Azure OpenAI provider for the given resource and deployment.
+    #[must_use]
+    pub fn azure_openai(resource: impl Into<String>, deployment: impl Into<String>) -> Self {
+        Self::AzureOpenAI {
+            resource: resource.into(),
+            deployment: deployment.into(),
+        }
+    }
+}
+
+/// Anthropic extended thinking settings.
+///
+/// With extended thinking, Claude writes a reasoning trace before its
+/// answer. The trace is streamed separately from the answer and is not
+/// added to conversation history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtendedThinkingConfig {
+    /// Tokens Claude may spend on thinking (at least 1024)
+    pub budget_tokens: u32,
+}
+
+impl ExtendedThinkingConfig {
+    /// Creates a thinking configuration with the given token budget.
+    #[must_use]
+    pub fn new(budget_tokens: u32) -> Self {
+        Self { budget_tokens }
+    }
 }
 
 /// Sampling parameters for LLM text generation.
@@ -104,6 +145,43 @@ pub struct SamplingParams {
     /// - OpenAI: `stop` field
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stop_sequences: Option<Vec<String>>,
+
+    /// Marks the system prompt as cacheable.
+    ///
+    /// Supported by Anthropic (`cache_control` on the system block). Ignored
+    /// by other providers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_system_prompt: Option<bool>,
+
+    /// Marks the tool definitions as cacheable.
+    ///
+    /// Supported by Anthropic (`cache_control` on the last tool). Ignored by
+    /// other providers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_tools: Option<bool>,
+
+    /// Caps the response length, overriding [`ProviderConfig::max_tokens`].
+    ///
+    /// Supported by all providers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+
+    /// Requests a JSON response matching this JSON schema.
+    ///
+    /// - OpenAI-compatible: `response_format` (`json_schema`, or
+    ///   `json_object` for an empty schema)
+    /// - Anthropic: a forced tool call whose input is the response, when
+    ///   the request has no other tools
+    /// - Others: ignored; rely on instructions in the prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_schema: Option<serde_json::Value>,
+
+    /// Enables extended thinking, overriding
+    /// [`ProviderConfig::extended_thinking`].
+    ///
+    /// Supported by Anthropic (`thinking`). Ignored by other providers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_thinking: Option<ExtendedThinkingConfig>,
 }
 
 impl SamplingParams {
@@ -162,6 +240,41 @@ impl SamplingParams {
         self
     }
 
+    /// Enables or disables system prompt caching.
+    #[must_use]
+    pub fn with_cache_system_prompt(mut self, enabled: bool) -> Self {
+        self.cache_system_prompt = Some(enabled);
+        self
+    }
+
+    /// Enables or disables tool definition caching.
+    #[must_use]
+    pub fn with_cache_tools(mut self, enabled: bool) -> Self {
+        self.cache_tools = Some(enabled);
+        self
+    }
+
+    /// Sets the maximum response length.
+    #[must_use]
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Requests a JSON response matching `schema`.
+    #[must_use]
+    pub fn with_output_schema(mut self, schema: serde_json::Value) -> Self {
+        self.output_schema = Some(schema);
+        self
+    }
+
+    /// Enables extended thinking with a budget of `budget_tokens`.
+    #[must_use]
+    pub fn with_thinking(mut self, budget_tokens: u32) -> Self {
+        self.extended_thinking = Some(ExtendedThinkingConfig::new(budget_tokens));
+        self
+    }
+
     /// Returns true if no parameters are set.
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -172,6 +285,11 @@ impl SamplingParams {
             && self.presence_penalty.is_none()
             && self.seed.is_none()
             && self.stop_sequences.is_none()
+            && self.cache_system_prompt.is_none()
+            && self.cache_tools.is_none()
+            && self.max_tokens.is_none()
+            && self.output_schema.is_none()
+            && self.extended_thinking.is_none()
     }
 
     /// Merges two `SamplingParams`, with `overrides` taking precedence.
@@ -190,6 +308,14 @@ impl SamplingParams {
                 .stop_sequences
                 .clone()
                 .or_else(|| self.stop_sequences.clone()),
+            cache_system_prompt: overrides.cache_system_prompt.or(self.cache_system_prompt),
+            cache_tools: overrides.cache_tools.or(self.cache_tools),
+            max_tokens: overrides.max_tokens.or(self.max_tokens),
+            output_schema: overrides
+                .output_schema
+                .clone()
+                .or_else(|| self.output_schema.clone()),
+            extended_thinking: overrides.extended_thinking.or(self.extended_thinking),
         }
     }
 }
@@ -215,9 +341,46 @@ pub struct ProviderConfig {
     pub rate_limit: RateLimitConfig,
     /// Retry configuration
     pub retry: RetryConfig,
+    /// Circuit breaker configuration; `None` disables the breaker
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Response cache configuration; `None` disables caching
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_cache: Option<CacheConfig>,
     /// Default sampling parameters for this provider
     #[serde(default, skip_serializing_if = "SamplingParams::is_empty")]
     pub sampling: SamplingParams,
+    /// Default retry policy for prompts sent to this provider; `None`
+    /// disables prompt retries unless a prompt sets its own policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy: Option<RetryPolicy>,
+    /// Default time limit for prompts sent to this provider, covering
+    /// every tool round; `None` means no limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_prompt_timeout: Option<Duration>,
+    /// Whether system prompts longer than `cache_threshold_chars` are
+    /// cached with Anthropic prompt caching
+    #[serde(default)]
+    pub enable_prompt_caching: bool,
+    /// Length in characters above which a system prompt is cached when
+    /// `enable_prompt_caching` is set
+    #[serde(default = "default_cache_threshold_chars")]
+    pub cache_threshold_chars: usize,
+    /// Extended thinking for every request; `None` disables it unless a
+    /// prompt enables it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_thinking: Option<ExtendedThinkingConfig>,
+    /// Text placed before the system prompt of every request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_prefix: Option<String>,
+    /// Text placed after the system prompt of every request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt_suffix: Option<String>,
+}
+
+/// Roughly 1024 tokens, the shortest prefix Anthropic will cache.
+fn default_cache_threshold_chars() -> usize {
+    4096
 }
 
 impl ProviderConfig {
@@ -269,7 +432,16 @@ impl ProviderConfig {
             timeout: Duration::from_secs(120),
             rate_limit: RateLimitConfig::default(),
             retry: RetryConfig::default(),
+            circuit_breaker: None,
+            response_cache: None,
             sampling: SamplingParams::default(),
+            retry_policy: None,
+            default_prompt_timeout: None,
+            enable_prompt_caching: false,
+            cache_threshold_chars: default_cache_threshold_chars(),
+            extended_thinking: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
         }
     }
 
@@ -301,7 +473,16 @@ impl ProviderConfig {
             timeout: Duration::from_secs(300), // Longer timeout for local inference
             rate_limit: RateLimitConfig::new(1000, 1_000_000), // High limits for local
             retry: RetryConfig::default(),
+            circuit_breaker: None,
+            response_cache: None,
             sampling: SamplingParams::default(),
+            retry_policy: None,
+            default_prompt_timeout: None,
+            enable_prompt_caching: false,
+            cache_threshold_chars: default_cache_threshold_chars(),
+            extended_thinking: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
         }
     }
 
@@ -331,7 +512,189 @@ impl ProviderConfig {
             timeout: Duration::from_secs(120),
             rate_limit: RateLimitConfig::default(),
             retry: RetryConfig::default(),
+            circuit_breaker: None,
+            response_cache: None,
             sampling: SamplingParams::default(),
+            retry_policy: None,
+            default_prompt_timeout: None,
+            enable_prompt_caching: false,
+            cache_threshold_chars: default_cache_threshold_chars(),
+            extended_thinking: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
+        }
+    }
+
+    /// Creates a new provider configuration for Cohere.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - The Cohere API key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acton_ai::llm::{ProviderConfig, ProviderType};
+    ///
+    /// let config = ProviderConfig::cohere("co-...");
+    /// assert_eq!(config.provider_type, ProviderType::Cohere);
+    /// ```
+    #[must_use]
+    pub fn cohere(api_key: impl Into<String>) -> Self {
+        Self {
+            provider_type: ProviderType::Cohere,
+            api_key: api_key.into(),
+            model: "command-r-plus-08-2024".to_string(),
+            max_tokens: 4096,
+            base_url: "https://api.cohere.com".to_string(),
+            api_version: String::new(),
+            timeout: Duration::from_secs(120),
+            rate_limit: RateLimitConfig::default(),
+            retry: RetryConfig::default(),
+            circuit_breaker: None,
+            response_cache: None,
+            sampling: SamplingParams::default(),
+            retry_policy: None,
+            default_prompt_timeout: None,
+            enable_prompt_caching: false,
+            cache_threshold_chars: default_cache_threshold_chars(),
+            extended_thinking: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
+        }
+    }
+
+    /// Creates a new provider configuration for Google Gemini.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - The Google AI Studio API key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acton_ai::llm::{ProviderConfig, ProviderType};
+    ///
+    /// let config = ProviderConfig::gemini("AIza...");
+    /// assert_eq!(config.provider_type, ProviderType::Gemini);
+    /// ```
+    #[must_use]
+    pub fn gemini(api_key: impl Into<String>) -> Self {
+        Self {
+            provider_type: ProviderType::Gemini,
+            api_key: api_key.into(),
+            model: "gemini-2.0-flash".to_string(),
+            max_tokens: 8192,
+            base_url: "https://generativelanguage.googleapis.com".to_string(),
+            api_version: "v1beta".to_string(),
+            timeout: Duration::from_secs(120),
+            rate_limit: RateLimitConfig::default(),
+            retry: RetryConfig::default(),
+            circuit_breaker: None,
+            response_cache: None,
+            sampling: SamplingParams::default(),
+            retry_policy: None,
+            default_prompt_timeout: None,
+            enable_prompt_caching: false,
+            cache_threshold_chars: default_cache_threshold_chars(),
+            extended_thinking: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
+        }
+    }
+
+    /// Creates a new provider configuration for Mistral AI.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - The Mistral API key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acton_ai::llm::{ProviderConfig, ProviderType};
+    ///
+    /// let config = ProviderConfig::mistral("mistral-key");
+    /// assert_eq!(config.provider_type, ProviderType::Mistral);
+    /// ```
+    #[must_use]
+    pub fn mistral(api_key: impl Into<String>) -> Self {
+        Self {
+            provider_type: ProviderType::Mistral,
+            api_key: api_key.into(),
+            model: "mistral-large-latest".to_string(),
+            max_tokens: 4096,
+            base_url: "https://api.mistral.ai".to_string(),
+            api_version: String::new(),
+            timeout: Duration::from_secs(120),
+            rate_limit: RateLimitConfig::default(),
+            retry: RetryConfig::default(),
+            circuit_breaker: None,
+            response_cache: None,
+            sampling: SamplingParams::default(),
+            retry_policy: None,
+            default_prompt_timeout: None,
+            enable_prompt_caching: false,
+            cache_threshold_chars: default_cache_threshold_chars(),
+            extended_thinking: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
+        }
+    }
+
+    /// Creates a new provider configuration for an Azure OpenAI deployment.
+    ///
+    /// Requests go to
+    /// `https://{resource}.openai.azure.com/openai/deployments/{deployment}`
+    /// with the `api-version` query parameter taken from `api_version`.
+    ///
+    /// # Arguments
+    ///
+    /// * `resource` - The Azure OpenAI resource name
+    /// * `deployment` - The deployment name
+    /// * `api_key` - The resource's API key
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acton_ai::llm::ProviderConfig;
+    ///
+    /// let config = ProviderConfig::openai_azure("my-resource", "gpt-4o-prod", "key");
+    /// assert_eq!(
+    ///     config.base_url,
+    ///     "https://my-resource.openai.azure.com/openai/deployments/gpt-4o-prod"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn openai_azure(
+        resource: impl Into<String>,
+        deployment: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Self {
+        let resource = resource.into();
+        let deployment = deployment.into();
+        Self {
+            base_url: format!(
+                "https://{resource}.openai.azure.com/openai/deployments/{deployment}"
+            ),
+            model: deployment.clone(),
+            provider_type: ProviderType::azure_openai(resource, deployment),
+            api_key: api_key.into(),
+            max_tokens: 4096,
+            api_version: "2024-10-21".to_string(),
+            timeout: Duration::from_secs(120),
+            rate_limit: RateLimitConfig::default(),
+            retry: RetryConfig::default(),
+            circuit_breaker: None,
+            response_cache: None,
+            sampling: SamplingParams::default(),
+            retry_policy: None,
+            default_prompt_timeout: None,
+            enable_prompt_caching: false,
+            cache_threshold_chars: default_cache_threshold_chars(),
+            extended_thinking: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
         }
     }
 
@@ -363,7 +726,16 @@ impl ProviderConfig {
             timeout: Duration::from_secs(300),
             rate_limit: RateLimitConfig::new(1000, 1_000_000),
             retry: RetryConfig::default(),
+            circuit_breaker: None,
+            response_cache: None,
             sampling: SamplingParams::default(),
+            retry_policy: None,
+            default_prompt_timeout: None,
+            enable_prompt_caching: false,
+            cache_threshold_chars: default_cache_threshold_chars(),
+            extended_thinking: None,
+            system_prompt_prefix: None,
+            system_prompt_suffix: None,
         }
     }
 
@@ -430,6 +802,51 @@ impl ProviderConfig {
         self
     }
 
+    /// Sets the default retry policy for prompts sent to this provider.
+    ///
+    /// Prompts that don't set their own policy with
+    /// [`PromptBuilder::retry`](crate::prompt::PromptBuilder::retry) use
+    /// this one.
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Sets the default time limit for prompts sent to this provider.
+    ///
+    /// Unlike [`with_timeout`](Self::with_timeout), which bounds each HTTP request,
+    /// this bounds a whole prompt including its tool rounds. Prompts that
+    /// don't set their own limit with
+    /// [`PromptBuilder::timeout`](crate::prompt::PromptBuilder::timeout)
+    /// use this one.
+    #[must_use]
+    pub fn with_default_prompt_timeout(mut self, timeout: Duration) -> Self {
+        self.default_prompt_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables the circuit breaker with the given configuration.
+    ///
+    /// After `failure_threshold` consecutive server errors or timeouts the
+    /// provider rejects requests (or forwards them to its fallback provider)
+    /// until the cool-down has passed.
+    #[must_use]
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// Enables caching of complete responses.
+    ///
+    /// Identical requests (same messages, tools, model and sampling
+    /// parameters) are then answered from memory without calling the API.
+    #[must_use]
+    pub fn with_response_cache(mut self, cache: CacheConfig) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
     /// Sets the default sampling parameters for this provider.
     #[must_use]
     pub fn with_sampling(mut self, sampling: SamplingParams) -> Self {
@@ -465,6 +882,88 @@ impl ProviderConfig {
         self
     }
 
+    /// Enables or disables Anthropic prompt caching of system prompts longer
+    /// than `cache_threshold_chars`.
+    ///
+    /// Shorter prompts are sent uncached, as Anthropic does not cache them.
+    /// [`SamplingParams::cache_system_prompt`] overrides the threshold either
+    /// way. Ignored by providers without prompt caching.
+    #[must_use]
+    pub fn enable_prompt_caching(mut self, enabled: bool) -> Self {
+        self.enable_prompt_caching = enabled;
+        self
+    }
+
+    /// Sets the system prompt length, in characters, above which prompt
+    /// caching applies.
+    #[must_use]
+    pub fn with_cache_threshold_chars(mut self, chars: usize) -> Self {
+        self.cache_threshold_chars = chars;
+        self
+    }
+
+    /// Caches the system prompt by default, whatever its length.
+    ///
+    /// Prompts can override this with [`SamplingParams::cache_system_prompt`].
+    #[must_use]
+    pub fn with_system_prompt_caching(mut self) -> Self {
+        self.sampling.cache_system_prompt = Some(true);
+        self
+    }
+
+    /// Enables Anthropic extended thinking with a budget of `budget_tokens`
+    /// for every request.
+    ///
+    /// Ignored by providers without extended thinking.
+    #[must_use]
+    pub fn with_extended_thinking(mut self, budget_tokens: u32) -> Self {
+        self.extended_thinking = Some(ExtendedThinkingConfig::new(budget_tokens));
+        self
+    }
+
+    /// Places `prefix` before the system prompt of every request sent
+    /// through this provider, e.g. for policy text callers cannot change.
+    ///
+    /// Requests without a system message get one holding just the prefix
+    /// (and any suffix).
+    #[must_use]
+    pub fn with_system_prompt_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.system_prompt_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Places `suffix` after the system prompt of every request sent
+    /// through this provider.
+    ///
+    /// Requests without a system message get one holding just the suffix
+    /// (and any prefix).
+    #[must_use]
+    pub fn with_system_prompt_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.system_prompt_suffix = Some(suffix.into());
+        self
+    }
+
+    /// Wraps the first system message of `messages` in the configured
+    /// prefix and suffix, inserting a system message if there is none.
+    pub(crate) fn apply_system_prompt_affixes(&self, messages: &mut Vec<Message>) {
+        if self.system_prompt_prefix.is_none() && self.system_prompt_suffix.is_none() {
+            return;
+        }
+        let prefix = self.system_prompt_prefix.as_deref().unwrap_or_default();
+        let suffix = self.system_prompt_suffix.as_deref().unwrap_or_default();
+        match messages.iter_mut().find(|m| m.role == MessageRole::System) {
+            Some(system) => system.content = format!("{prefix}{}{suffix}", system.content),
+            None => messages.insert(0, Message::system(format!("{prefix}{suffix}"))),
+        }
+    }
+
+    /// Returns whether a system prompt of `system_prompt_len` characters is
+    /// cached when the prompt does not say either way.
+    #[must_use]
+    pub fn caches_system_prompt(&self, system_prompt_len: usize) -> bool {
+        self.enable_prompt_caching && system_prompt_len > self.cache_threshold_chars
+    }
+
     /// Returns the full API endpoint URL for messages.
     #[must_use]
     pub fn messages_endpoint(&self) -> String {
@@ -472,6 +971,21 @@ impl ProviderConfig {
     }
 }
 
+/// How [`RateLimitConfig::requests_per_minute`] is enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitAlgorithm {
+    /// At most `requests_per_minute` requests in each one-minute window
+    #[default]
+    SlidingWindow,
+    /// Up to `burst_capacity` requests at once, refilling at
+    /// `requests_per_minute / 60` requests per second
+    TokenBucket {
+        /// Requests that can be made back to back from a full bucket
+        burst_capacity: u32,
+    },
+}
+
 /// Rate limiting configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RateLimitConfig {
@@ -483,6 +997,12 @@ pub struct RateLimitConfig {
     pub queue_when_limited: bool,
     /// Maximum queue size (0 = unlimited)
     pub max_queue_size: usize,
+    /// Whether to tune the limits from the provider's rate-limit headers
+    #[serde(default)]
+    pub adaptive: bool,
+    /// How the request limit is enforced
+    #[serde(default)]
+    pub algorithm: RateLimitAlgorithm,
 }
 
 impl RateLimitConfig {
@@ -494,9 +1014,45 @@ impl RateLimitConfig {
             tokens_per_minute,
             queue_when_limited: true,
             max_queue_size: 100,
+            adaptive: false,
+            algorithm: RateLimitAlgorithm::SlidingWindow,
         }
     }
 
+    /// Enforces the request limit with a token bucket.
+    ///
+    /// Allows bursts of up to `burst_capacity` requests while keeping the
+    /// long-run average at `requests_per_minute`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use acton_ai::llm::{RateLimitAlgorithm, RateLimitConfig};
+    ///
+    /// let config = RateLimitConfig::new(60, 40_000).token_bucket(10);
+    /// assert_eq!(
+    ///     config.algorithm,
+    ///     RateLimitAlgorithm::TokenBucket { burst_capacity: 10 }
+    /// );
+    /// ```
+    #[must_use]
+    pub fn token_bucket(mut self, burst_capacity: u32) -> Self {
+        self.algorithm = RateLimitAlgorithm::TokenBucket { burst_capacity };
+        self
+    }
+
+    /// Enables or disables adaptive rate limiting.
+    ///
+    /// When enabled, the provider lowers `requests_per_minute` and
+    /// `tokens_per_minute` as the API reports its remaining quota running
+    /// low, and restores the configured values once the quota recovers.
+    /// The values set here remain the upper bound.
+    #[must_use]
+    pub fn adaptive(mut self, enabled: bool) -> Self {
+        self.adaptive = enabled;
+        self
+    }
+
     /// Disables queuing when rate limited (requests will fail immediately).
     #[must_use]
     pub fn without_queueing(mut self) -> Self {
@@ -520,6 +1076,8 @@ impl Default for RateLimitConfig {
             tokens_per_minute: 40_000,
             queue_when_limited: true,
             max_queue_size: 100,
+            adaptive: false,
+            algorithm: RateLimitAlgorithm::SlidingWindow,
         }
     }
 }
@@ -619,6 +1177,143 @@ impl Default for RetryConfig {
     }
 }
 
+/// Retry policy for prompts.
+///
+/// Where [`RetryConfig`] governs requests the provider queued itself, a
+/// policy makes [`PromptBuilder::collect`](crate::prompt::PromptBuilder::collect)
+/// resend a request that failed with a retriable [`LLMError`] (rate limit,
+/// network error, overloaded or unavailable service). Other errors are
+/// returned immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Factor applied to the delay after each retry
+    pub backoff_multiplier: f64,
+    /// Whether to randomize each delay
+    pub jitter: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a policy that makes up to `max_attempts` attempts.
+    #[must_use]
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the delay before the first retry.
+    #[must_use]
+    pub fn with_initial_backoff(mut self, duration: Duration) -> Self {
+        self.initial_backoff = duration;
+        self
+    }
+
+    /// Sets the backoff multiplier.
+    #[must_use]
+    pub fn with_backoff_multiplier(mut self, multiplier: f64) -> Self {
+        self.backoff_multiplier = multiplier;
+        self
+    }
+
+    /// Disables jitter.
+    #[must_use]
+    pub fn without_jitter(mut self) -> Self {
+        self.jitter = false;
+        self
+    }
+
+    /// Returns true if a request that failed on `attempt` (starting at 1)
+    /// with `error` should be sent again.
+    #[must_use]
+    pub fn should_retry(&self, attempt: u32, error: &LLMError) -> bool {
+        attempt < self.max_attempts && error.is_retriable()
+    }
+
+    /// Calculates the delay before retrying a request that failed on
+    /// `attempt` (starting at 1).
+    ///
+    /// A rate limit's [`LLMError::retry_after`] hint takes precedence over
+    /// the exponential backoff. Jitter scales the backoff by a random
+    /// factor between 0.5 and 1.
+    #[must_use]
+    pub fn backoff_for_attempt(&self, attempt: u32, error: &LLMError) -> Duration {
+        if let Some(retry_after) = error.retry_after() {
+            return retry_after;
+        }
+
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let factor = self.backoff_multiplier.max(1.0).powi(exponent);
+        let backoff = Duration::try_from_secs_f64(self.initial_backoff.as_secs_f64() * factor)
+            .unwrap_or(Duration::MAX);
+
+        if self.jitter {
+            let random = RandomState::new().hash_one(attempt) as f64 / u64::MAX as f64;
+            backoff.mul_f64(0.5 + random / 2.0)
+        } else {
+            backoff
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            backoff_multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+/// Circuit breaker configuration for a provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe request is let through
+    pub cooldown: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// Creates a new circuit breaker configuration.
+    #[must_use]
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            failure_threshold,
+            cooldown,
+        }
+    }
+
+    /// Sets the failure threshold.
+    #[must_use]
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    /// Sets the cool-down duration.
+    #[must_use]
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -693,6 +1388,59 @@ mod tests {
         assert_eq!(config.base_url, "https://api.openai.com/v1");
     }
 
+    #[test]
+    fn provider_config_cohere_creates_cohere_provider() {
+        let config = ProviderConfig::cohere("test-key");
+        assert_eq!(config.provider_type, ProviderType::Cohere);
+        assert_eq!(config.api_key, "test-key");
+        assert_eq!(config.model, "command-r-plus-08-2024");
+        assert_eq!(config.base_url, "https://api.cohere.com");
+    }
+
+    #[test]
+    fn provider_config_gemini_creates_gemini_provider() {
+        let config = ProviderConfig::gemini("test-key");
+        assert_eq!(config.provider_type, ProviderType::Gemini);
+        assert_eq!(config.api_key, "test-key");
+        assert_eq!(config.model, "gemini-2.0-flash");
+        assert_eq!(config.base_url, "https://generativelanguage.googleapis.com");
+        assert_eq!(config.api_version, "v1beta");
+    }
+
+    #[test]
+    fn provider_config_mistral_creates_mistral_provider() {
+        let config = ProviderConfig::mistral("test-key");
+        assert_eq!(config.provider_type, ProviderType::Mistral);
+        assert_eq!(config.api_key, "test-key");
+        assert_eq!(config.model, "mistral-large-latest");
+        assert_eq!(config.base_url, "https://api.mistral.ai");
+    }
+
+    #[test]
+    fn provider_config_response_cache_is_opt_in() {
+        let config = ProviderConfig::anthropic("test-key");
+        assert!(config.response_cache.is_none());
+
+        let config = config.with_response_cache(CacheConfig::new(10));
+        assert_eq!(config.response_cache.map(|c| c.max_entries), Some(10));
+    }
+
+    #[test]
+    fn provider_config_openai_azure_creates_azure_provider() {
+        let config = ProviderConfig::openai_azure("contoso", "gpt-4o-prod", "test-key");
+        assert_eq!(
+            config.provider_type,
+            ProviderType::azure_openai("contoso", "gpt-4o-prod")
+        );
+        assert_eq!(config.api_key, "test-key");
+        assert_eq!(config.model, "gpt-4o-prod");
+        assert_eq!(
+            config.base_url,
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o-prod"
+        );
+        assert_eq!(config.api_version, "2024-10-21");
+    }
+
     #[test]
     fn provider_config_openai_compatible_creates_custom_provider() {
         let config = ProviderConfig::openai_compatible("http://custom:8000/v1", "custom-model");
@@ -770,6 +1518,18 @@ mod tests {
         assert!(!config.queue_when_limited);
     }
 
+    #[test]
+    fn rate_limit_config_adaptive_defaults_off() {
+        assert!(!RateLimitConfig::default().adaptive);
+        assert!(RateLimitConfig::default().adaptive(true).adaptive);
+
+        let parsed: RateLimitConfig = serde_json::from_str(
+            r#"{"requests_per_minute":5,"tokens_per_minute":10,"queue_when_limited":true,"max_queue_size":1}"#,
+        )
+        .unwrap();
+        assert!(!parsed.adaptive);
+    }
+
     #[test]
     fn retry_config_default() {
         let config = RetryConfig::default();
@@ -811,6 +1571,56 @@ mod tests {
         assert_eq!(config.backoff_for_attempt(10), Duration::from_secs(5));
     }
 
+    #[test]
+    fn retry_policy_retries_only_retriable_errors() {
+        let policy = RetryPolicy::new(3);
+
+        assert!(policy.should_retry(1, &LLMError::network("reset")));
+        assert!(policy.should_retry(2, &LLMError::api_error(503, "unavailable", None)));
+        assert!(!policy.should_retry(3, &LLMError::network("reset")));
+        assert!(!policy.should_retry(1, &LLMError::api_error(401, "unauthorized", None)));
+    }
+
+    #[test]
+    fn retry_policy_backoff_grows_exponentially() {
+        let policy = RetryPolicy::new(5)
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_backoff_multiplier(3.0)
+            .without_jitter();
+        let error = LLMError::network("reset");
+
+        assert_eq!(
+            policy.backoff_for_attempt(1, &error),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            policy.backoff_for_attempt(3, &error),
+            Duration::from_millis(900)
+        );
+    }
+
+    #[test]
+    fn retry_policy_prefers_retry_after_hint() {
+        let policy = RetryPolicy::default();
+        let error = LLMError::rate_limited(Duration::from_secs(7));
+
+        assert_eq!(
+            policy.backoff_for_attempt(1, &error),
+            Duration::from_secs(7)
+        );
+    }
+
+    #[test]
+    fn retry_policy_jitter_stays_within_half_to_full_backoff() {
+        let policy = RetryPolicy::new(3).with_initial_backoff(Duration::from_secs(2));
+        let error = LLMError::network("reset");
+
+        for _ in 0..20 {
+            let delay = policy.backoff_for_attempt(1, &error);
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+
     #[test]
     fn provider_config_serialization_roundtrip() {
         let config = ProviderConfig::new("test-key").with_model("claude-3-haiku-20240307");
@@ -885,6 +1695,23 @@ mod tests {
         assert_eq!(merged.top_p, Some(0.8));
     }
 
+    #[test]
+    fn sampling_params_output_schema_merges_and_serializes() {
+        let schema = serde_json::json!({"type": "object"});
+        let base = SamplingParams::new().with_output_schema(schema.clone());
+        assert!(!base.is_empty());
+
+        let merged = base.merge_with(&SamplingParams::new().with_temperature(0.2));
+        assert_eq!(merged.output_schema, Some(schema.clone()));
+
+        let json = serde_json::to_value(&merged).unwrap();
+        assert_eq!(json["output_schema"], schema);
+        assert!(serde_json::to_value(SamplingParams::new())
+            .unwrap()
+            .get("output_schema")
+            .is_none());
+    }
+
     #[test]
     fn sampling_params_merge_both_empty() {
         let base = SamplingParams::default();
@@ -927,6 +1754,46 @@ mod tests {
         assert_eq!(config.sampling.temperature, Some(0.5));
     }
 
+    #[test]
+    fn prompt_caching_default_can_be_overridden_per_prompt() {
+        let config = ProviderConfig::new("test-key").with_system_prompt_caching();
+        assert_eq!(config.sampling.cache_system_prompt, Some(true));
+
+        let merged = config
+            .sampling
+            .merge_with(&SamplingParams::new().with_cache_system_prompt(false));
+        assert_eq!(merged.cache_system_prompt, Some(false));
+        assert_eq!(merged.cache_tools, None);
+    }
+
+    #[test]
+    fn extended_thinking_merges_per_prompt() {
+        let base = SamplingParams::new().with_thinking(1024);
+        assert!(!base.is_empty());
+
+        let merged = base.merge_with(&SamplingParams::new().with_thinking(4096));
+        assert_eq!(
+            merged.extended_thinking,
+            Some(ExtendedThinkingConfig::new(4096))
+        );
+        let merged = base.merge_with(&SamplingParams::new().with_temperature(1.0));
+        assert_eq!(merged.extended_thinking.unwrap().budget_tokens, 1024);
+    }
+
+    #[test]
+    fn prompt_caching_applies_above_threshold() {
+        let config = ProviderConfig::new("test-key");
+        assert!(!config.enable_prompt_caching);
+        assert_eq!(config.cache_threshold_chars, 4096);
+        assert!(!config.caches_system_prompt(10_000));
+
+        let config = config
+            .enable_prompt_caching(true)
+            .with_cache_threshold_chars(100);
+        assert!(!config.caches_system_prompt(100));
+        assert!(config.caches_system_prompt(101));
+    }
+
     #[test]
     fn provider_config_serialization_roundtrip_with_sampling() {
         let config = ProviderConfig::new("test-key")
@@ -938,4 +1805,33 @@ mod tests {
 
         assert_eq!(config, deserialized);
     }
+
+    #[test]
+    fn system_prompt_affixes_wrap_system_message() {
+        let config = ProviderConfig::ollama("test")
+            .with_system_prompt_prefix("POLICY. ")
+            .with_system_prompt_suffix(" END.")
+            .with_system_prompt_prefix("Policy: ");
+        let mut messages = vec![Message::system("Be brief."), Message::user("hi")];
+        config.apply_system_prompt_affixes(&mut messages);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].content, "Policy: Be brief. END.");
+        assert_eq!(messages[1].content, "hi");
+    }
+
+    #[test]
+    fn system_prompt_affixes_create_missing_system_message() {
+        let config = ProviderConfig::ollama("test").with_system_prompt_prefix("Policy.");
+        let mut messages = vec![Message::user("hi")];
+        config.apply_system_prompt_affixes(&mut messages);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, MessageRole::System);
+        assert_eq!(messages[0].content, "Policy.");
+
+        let mut untouched = vec![Message::user("hi")];
+        ProviderConfig::ollama("test").apply_system_prompt_affixes(&mut untouched);
+        assert_eq!(untouched, vec![Message::user("hi")]);
+    }
 }
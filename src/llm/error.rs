@@ -3,18 +3,19 @@
 //! Custom error types for LLM operations including network errors,
 //! rate limiting, API errors, and streaming errors.
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::time::Duration;
 
 /// Errors that can occur in the LLM provider.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LLMError {
     /// The specific error that occurred
     pub kind: LLMErrorKind,
 }
 
 /// Specific LLM error types.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LLMErrorKind {
     /// Network error when communicating with the API
     Network {
@@ -57,6 +58,8 @@ pub enum LLMErrorKind {
     },
     /// Provider is shutting down
     ShuttingDown,
+    /// Request was still queued when the provider shut down
+    Cancelled,
     /// Configuration error
     InvalidConfig {
         /// The configuration field that was invalid
@@ -74,6 +77,11 @@ pub enum LLMErrorKind {
         /// The timeout duration that was exceeded
         duration: Duration,
     },
+    /// The provider's circuit breaker is open after repeated failures
+    CircuitOpen {
+        /// The provider whose circuit is open
+        provider: String,
+    },
 }
 
 impl LLMError {
@@ -149,6 +157,12 @@ impl LLMError {
         Self::new(LLMErrorKind::ShuttingDown)
     }
 
+    /// Creates a cancelled error.
+    #[must_use]
+    pub fn cancelled() -> Self {
+        Self::new(LLMErrorKind::Cancelled)
+    }
+
     /// Creates an invalid config error.
     #[must_use]
     pub fn invalid_config(field: impl Into<String>, reason: impl Into<String>) -> Self {
@@ -172,6 +186,44 @@ impl LLMError {
         Self::new(LLMErrorKind::Timeout { duration })
     }
 
+    /// Creates a circuit open error.
+    #[must_use]
+    pub fn circuit_open(provider: impl Into<String>) -> Self {
+        Self::new(LLMErrorKind::CircuitOpen {
+            provider: provider.into(),
+        })
+    }
+
+    /// Returns true if the request was cancelled by a provider shutdown.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self.kind, LLMErrorKind::Cancelled)
+    }
+
+    /// Returns true if this error was raised by an open circuit breaker.
+    #[must_use]
+    pub fn is_circuit_open(&self) -> bool {
+        matches!(self.kind, LLMErrorKind::CircuitOpen { .. })
+    }
+
+    /// Returns true if this error counts against the circuit breaker.
+    ///
+    /// Only failures that suggest the provider itself is unhealthy count:
+    /// network errors, timeouts, overload, and 5xx responses.
+    #[must_use]
+    pub fn trips_circuit(&self) -> bool {
+        matches!(
+            self.kind,
+            LLMErrorKind::Network { .. }
+                | LLMErrorKind::ModelOverloaded { .. }
+                | LLMErrorKind::Timeout { .. }
+                | LLMErrorKind::ApiError {
+                    status_code: 500..=599,
+                    ..
+                }
+        )
+    }
+
     /// Returns true if this error is retriable.
     #[must_use]
     pub fn is_retriable(&self) -> bool {
@@ -252,6 +304,12 @@ impl fmt::Display for LLMError {
                     "LLM provider is shutting down; cannot accept new requests"
                 )
             }
+            LLMErrorKind::Cancelled => {
+                write!(
+                    f,
+                    "request cancelled before it was sent; the LLM provider shut down"
+                )
+            }
             LLMErrorKind::InvalidConfig { field, reason } => {
                 write!(f, "invalid configuration for '{}': {}", field, reason)
             }
@@ -265,6 +323,13 @@ impl fmt::Display for LLMError {
             LLMErrorKind::Timeout { duration } => {
                 write!(f, "request timed out after {} seconds", duration.as_secs())
             }
+            LLMErrorKind::CircuitOpen { provider } => {
+                write!(
+                    f,
+                    "circuit breaker for provider '{}' is open after repeated failures; retry after the cool-down",
+                    provider
+                )
+            }
         }
     }
 }
@@ -387,4 +452,31 @@ mod tests {
         let error = LLMError::model_overloaded("claude-3-opus-20240229");
         assert!(error.is_retriable());
     }
+
+    #[test]
+    fn circuit_open_error() {
+        let error = LLMError::circuit_open("claude");
+        assert!(error.is_circuit_open());
+        assert!(!error.is_retriable());
+        assert!(!error.trips_circuit());
+        assert!(error.to_string().contains("'claude'"));
+    }
+
+    #[test]
+    fn cancelled_error() {
+        let error = LLMError::cancelled();
+        assert!(error.is_cancelled());
+        assert!(!error.is_retriable());
+        assert!(!error.trips_circuit());
+        assert!(error.to_string().contains("cancelled"));
+    }
+
+    #[test]
+    fn only_provider_failures_trip_circuit() {
+        assert!(LLMError::api_error(503, "unavailable", None).trips_circuit());
+        assert!(LLMError::timeout(Duration::from_secs(5)).trips_circuit());
+        assert!(!LLMError::api_error(400, "bad", None).trips_circuit());
+        assert!(!LLMError::rate_limited(Duration::from_secs(5)).trips_circuit());
+        assert!(!LLMError::authentication_failed("bad key").trips_circuit());
+    }
 }
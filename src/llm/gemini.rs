@@ -0,0 +1,746 @@
+//! Google Gemini API client.
+//!
+//! HTTP client for the Gemini `generateContent` API, including streaming via
+//! `streamGenerateContent` and function calling.
+
+use crate::llm::client::{LLMClient, LLMClientResponse, LLMEventStream, LLMStreamEvent};
+use crate::llm::config::{ProviderConfig, SamplingParams};
+use crate::llm::error::LLMError;
+use crate::messages::{Message, MessageRole, StopReason, ToolCall, ToolDefinition};
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// JSON Schema keywords that Gemini's function declarations reject.
+const UNSUPPORTED_SCHEMA_KEYS: &[&str] = &["$schema", "additionalProperties"];
+
+/// Client for the Google Gemini API.
+#[derive(Debug, Clone)]
+pub struct GeminiClient {
+    /// HTTP client
+    client: Client,
+    /// Configuration
+    config: ProviderConfig,
+}
+
+/// Request body for `generateContent` and `streamGenerateContent`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiRequest {
+    contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<GeminiTool>>,
+    generation_config: GeminiGenerationConfig,
+}
+
+/// A turn of the conversation in Gemini format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GeminiContent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    role: Option<String>,
+    #[serde(default)]
+    parts: Vec<GeminiPart>,
+}
+
+/// One part of a turn: text, a function call, or a function response.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiPart {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    function_call: Option<GeminiFunctionCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    function_response: Option<GeminiFunctionResponse>,
+}
+
+/// A function call requested by the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GeminiFunctionCall {
+    name: String,
+    #[serde(default)]
+    args: serde_json::Value,
+}
+
+/// The result of a function call, sent back to the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GeminiFunctionResponse {
+    name: String,
+    response: serde_json::Value,
+}
+
+/// A tool in Gemini format.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiTool {
+    function_declarations: Vec<GeminiFunctionDeclaration>,
+}
+
+/// A function declaration in Gemini format.
+#[derive(Debug, Clone, Serialize)]
+struct GeminiFunctionDeclaration {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+/// Generation settings in Gemini format.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiGenerationConfig {
+    max_output_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_sequences: Option<Vec<String>>,
+}
+
+/// A full response, or one chunk of a streamed response.
+///
+/// Each streamed chunk has the same shape as a non-streaming response and
+/// carries only the parts generated since the previous chunk.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiResponse {
+    #[serde(default)]
+    candidates: Vec<GeminiCandidate>,
+    #[serde(default)]
+    response_id: Option<String>,
+}
+
+/// A candidate completion.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiCandidate {
+    #[serde(default)]
+    content: Option<GeminiContent>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+/// Error response from the Gemini API.
+#[derive(Debug, Clone, Deserialize)]
+struct GeminiErrorResponse {
+    error: GeminiErrorBody,
+}
+
+/// Body of a Gemini error response.
+#[derive(Debug, Clone, Deserialize)]
+struct GeminiErrorBody {
+    message: String,
+}
+
+/// State carried across streamed chunks.
+#[derive(Debug, Default)]
+struct StreamParseState {
+    started: bool,
+    tool_calls: usize,
+}
+
+impl GeminiClient {
+    /// Creates a new Gemini client with the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Provider configuration including API key and settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `LLMError::network` if the HTTP client cannot be created.
+    pub fn new(config: ProviderConfig) -> Result<Self, LLMError> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| LLMError::network(format!("failed to create HTTP client: {}", e)))?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Returns the URL for `method` on the configured model.
+    fn endpoint(&self, method: &str) -> String {
+        format!(
+            "{}/{}/models/{}:{}",
+            self.config.base_url, self.config.api_version, self.config.model, method
+        )
+    }
+
+    /// Converts internal messages to Gemini contents plus a system instruction.
+    ///
+    /// System messages are merged into the system instruction. Tool results
+    /// are sent as `functionResponse` parts named after the call they answer.
+    fn convert_messages(
+        &self,
+        messages: &[Message],
+    ) -> (Vec<GeminiContent>, Option<GeminiContent>) {
+        let mut system = Vec::new();
+        let mut contents = Vec::new();
+        let mut call_names: HashMap<&str, &str> = HashMap::new();
+
+        for msg in messages {
+            match msg.role {
+                MessageRole::System => system.push(msg.content.clone()),
+                MessageRole::User => contents.push(GeminiContent {
+                    role: Some("user".to_string()),
+                    parts: vec![GeminiPart::text(&msg.content)],
+                }),
+                MessageRole::Assistant => {
+                    let mut parts = Vec::new();
+                    if !msg.content.is_empty() {
+                        parts.push(GeminiPart::text(&msg.content));
+                    }
+                    for tc in msg.tool_calls.iter().flatten() {
+                        call_names.insert(&tc.id, &tc.name);
+                        parts.push(GeminiPart {
+                            function_call: Some(GeminiFunctionCall {
+                                name: tc.name.clone(),
+                                args: tc.arguments.clone(),
+                            }),
+                            ..GeminiPart::default()
+                        });
+                    }
+                    contents.push(GeminiContent {
+                        role: Some("model".to_string()),
+                        parts,
+                    });
+                }
+                MessageRole::Tool => {
+                    let name = msg
+                        .tool_call_id
+                        .as_deref()
+                        .and_then(|id| call_names.get(id))
+                        .copied()
+                        .unwrap_or_default();
+                    contents.push(GeminiContent {
+                        role: Some("user".to_string()),
+                        parts: vec![GeminiPart {
+                            function_response: Some(GeminiFunctionResponse {
+                                name: name.to_string(),
+                                response: Self::function_response_body(&msg.content),
+                            }),
+                            ..GeminiPart::default()
+                        }],
+                    });
+                }
+            }
+        }
+
+        let system_instruction = (!system.is_empty()).then(|| GeminiContent {
+            role: None,
+            parts: vec![GeminiPart::text(&system.join("\n\n"))],
+        });
+        (contents, system_instruction)
+    }
+
+    /// Wraps a tool result as the JSON object Gemini expects.
+    ///
+    /// Results that are already JSON objects are passed through; anything
+    /// else is wrapped as `{"result": ...}`.
+    fn function_response_body(content: &str) -> serde_json::Value {
+        match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(value @ serde_json::Value::Object(_)) => value,
+            Ok(value) => serde_json::json!({ "result": value }),
+            Err(_) => serde_json::json!({ "result": content }),
+        }
+    }
+
+    /// Converts tool definitions to Gemini API format.
+    fn convert_tools(&self, tools: &[ToolDefinition]) -> Vec<GeminiTool> {
+        vec![GeminiTool {
+            function_declarations: tools
+                .iter()
+                .map(|t| GeminiFunctionDeclaration {
+                    name: t.name.clone(),
+                    description: t.description.clone(),
+                    parameters: Self::sanitize_schema(&t.input_schema),
+                })
+                .collect(),
+        }]
+    }
+
+    /// Removes JSON Schema keywords Gemini rejects, at every nesting level.
+    fn sanitize_schema(schema: &serde_json::Value) -> serde_json::Value {
+        match schema {
+            serde_json::Value::Object(map) => map
+                .iter()
+                .filter(|(key, _)| !UNSUPPORTED_SCHEMA_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), Self::sanitize_schema(value)))
+                .collect(),
+            serde_json::Value::Array(items) => items.iter().map(Self::sanitize_schema).collect(),
+            other => other.clone(),
+        }
+    }
+
+    /// Builds the request body.
+    fn build_request_body(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+    ) -> GeminiRequest {
+        let (contents, system_instruction) = self.convert_messages(messages);
+        GeminiRequest {
+            contents,
+            system_instruction,
+            tools: tools.map(|t| self.convert_tools(t)),
+            generation_config: GeminiGenerationConfig {
+                max_output_tokens: sampling
+                    .and_then(|s| s.max_tokens)
+                    .unwrap_or(self.config.max_tokens),
+                temperature: sampling.and_then(|s| s.temperature),
+                top_p: sampling.and_then(|s| s.top_p),
+                top_k: sampling.and_then(|s| s.top_k),
+                frequency_penalty: sampling.and_then(|s| s.frequency_penalty),
+                presence_penalty: sampling.and_then(|s| s.presence_penalty),
+                seed: sampling.and_then(|s| s.seed),
+                stop_sequences: sampling.and_then(|s| s.stop_sequences.clone()),
+            },
+        }
+    }
+
+    /// Sends the request to `url` and maps non-success statuses to errors.
+    async fn post(
+        &self,
+        url: String,
+        request_body: &GeminiRequest,
+    ) -> Result<reqwest::Response, LLMError> {
+        let response = self
+            .client
+            .post(url)
+            .header("x-goog-api-key", &self.config.api_key)
+            .header("content-type", "application/json")
+            .json(request_body)
+            .send()
+            .await
+            .map_err(|e| self.map_reqwest_error(e))?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        Ok(response)
+    }
+
+    /// Parses Gemini finish reason to internal format.
+    ///
+    /// Safety and recitation blocks end the turn like a normal stop.
+    #[must_use]
+    pub fn parse_stop_reason(reason: Option<&str>) -> StopReason {
+        match reason {
+            Some("MAX_TOKENS") => StopReason::MaxTokens,
+            _ => StopReason::EndTurn,
+        }
+    }
+
+    /// Parses an error response from the API.
+    async fn parse_error_response(&self, response: reqwest::Response) -> LLMError {
+        let status = response.status();
+        let status_code = status.as_u16();
+
+        // Check for rate limit
+        if status_code == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(60);
+
+            return LLMError::rate_limited(Duration::from_secs(retry_after));
+        }
+
+        // Try to parse error body
+        let error_body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<GeminiErrorResponse>(&error_body)
+            .map(|e| e.error.message)
+            .unwrap_or_else(|_| {
+                if error_body.is_empty() {
+                    status
+                        .canonical_reason()
+                        .unwrap_or("Unknown error")
+                        .to_string()
+                } else {
+                    error_body
+                }
+            });
+
+        match status_code {
+            401 | 403 => LLMError::authentication_failed(message),
+            400 | 404 => LLMError::invalid_request(message),
+            _ => LLMError::api_error(status_code, message, None),
+        }
+    }
+
+    /// Maps a reqwest error to an LLMError.
+    fn map_reqwest_error(&self, error: reqwest::Error) -> LLMError {
+        if error.is_timeout() {
+            LLMError::timeout(self.config.timeout)
+        } else {
+            LLMError::network(format!("request failed: {}", error))
+        }
+    }
+
+    /// Parses a single SSE line into a response chunk.
+    ///
+    /// Returns `None` for blank lines and anything else that does not carry
+    /// a `data:` payload.
+    fn parse_sse_line(line: &str) -> Option<Result<GeminiResponse, LLMError>> {
+        let data = line.strip_prefix("data:")?.trim_start();
+
+        if data.is_empty() {
+            return None;
+        }
+
+        Some(
+            serde_json::from_str::<GeminiResponse>(data)
+                .map_err(|e| LLMError::parse_error(format!("failed to parse SSE event: {}", e))),
+        )
+    }
+
+    /// Applies one streamed chunk, appending the resulting LLM events to `out`.
+    fn apply_stream_chunk(
+        chunk: GeminiResponse,
+        state: &mut StreamParseState,
+        out: &mut VecDeque<Result<LLMStreamEvent, LLMError>>,
+    ) {
+        if !state.started {
+            state.started = true;
+            let id = chunk
+                .response_id
+                .clone()
+                .unwrap_or_else(|| "gemini".to_string());
+            out.push_back(Ok(LLMStreamEvent::Start { id }));
+        }
+
+        let Some(candidate) = chunk.candidates.into_iter().next() else {
+            return;
+        };
+
+        for part in candidate.content.map(|c| c.parts).unwrap_or_default() {
+            if let Some(text) = part.text.filter(|t| !t.is_empty()) {
+                out.push_back(Ok(LLMStreamEvent::Token { text }));
+            }
+            if let Some(call) = part.function_call {
+                state.tool_calls += 1;
+                out.push_back(Ok(LLMStreamEvent::ToolCall {
+                    tool_call: Self::tool_call(call, state.tool_calls),
+                }));
+            }
+        }
+
+        if let Some(reason) = candidate.finish_reason {
+            let stop_reason = if state.tool_calls > 0 {
+                StopReason::ToolUse
+            } else {
+                Self::parse_stop_reason(Some(&reason))
+            };
+            out.push_back(Ok(LLMStreamEvent::End { stop_reason }));
+        }
+    }
+
+    /// Converts a function call to a tool call.
+    ///
+    /// Gemini does not assign call IDs, so the `n`th call of a response is
+    /// given the ID `call_n`.
+    fn tool_call(call: GeminiFunctionCall, n: usize) -> ToolCall {
+        ToolCall {
+            id: format!("call_{n}"),
+            name: call.name,
+            arguments: if call.args.is_null() {
+                serde_json::json!({})
+            } else {
+                call.args
+            },
+        }
+    }
+}
+
+impl GeminiPart {
+    fn text(text: &str) -> Self {
+        Self {
+            text: Some(text.to_string()),
+            ..Self::default()
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for GeminiClient {
+    async fn send_request(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+    ) -> Result<LLMClientResponse, LLMError> {
+        let request_body = self.build_request_body(messages, tools, sampling);
+        let response = self
+            .post(self.endpoint("generateContent"), &request_body)
+            .await?;
+
+        let parsed: GeminiResponse = response
+            .json()
+            .await
+            .map_err(|e| LLMError::parse_error(format!("failed to parse response: {}", e)))?;
+
+        let candidate = parsed
+            .candidates
+            .into_iter()
+            .next()
+            .ok_or_else(|| LLMError::parse_error("response contained no candidates"))?;
+
+        let mut content = String::new();
+        let mut tool_calls = Vec::new();
+        for part in candidate.content.map(|c| c.parts).unwrap_or_default() {
+            if let Some(text) = part.text {
+                content.push_str(&text);
+            }
+            if let Some(call) = part.function_call {
+                tool_calls.push(Self::tool_call(call, tool_calls.len() + 1));
+            }
+        }
+
+        let stop_reason = if !tool_calls.is_empty() {
+            StopReason::ToolUse
+        } else {
+            Self::parse_stop_reason(candidate.finish_reason.as_deref())
+        };
+
+        Ok(LLMClientResponse {
+            content,
+            tool_calls,
+            stop_reason,
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
+        })
+    }
+
+    async fn send_streaming_request(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+    ) -> Result<LLMEventStream, LLMError> {
+        let request_body = self.build_request_body(messages, tools, sampling);
+        let url = format!("{}?alt=sse", self.endpoint("streamGenerateContent"));
+        let response = self.post(url, &request_body).await?;
+
+        // State carried through the unfold iteration. `buffer` holds a
+        // trailing partial line until the next chunk completes it.
+        struct StreamState<S> {
+            stream: S,
+            buffer: String,
+            parse: StreamParseState,
+            pending_events: VecDeque<Result<LLMStreamEvent, LLMError>>,
+        }
+
+        let event_stream = futures::stream::unfold(
+            StreamState {
+                stream: response.bytes_stream(),
+                buffer: String::new(),
+                parse: StreamParseState::default(),
+                pending_events: VecDeque::new(),
+            },
+            |mut state| async move {
+                loop {
+                    if let Some(event) = state.pending_events.pop_front() {
+                        return Some((event, state));
+                    }
+
+                    match state.stream.next().await? {
+                        Ok(bytes) => {
+                            state.buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+                            while let Some(newline) = state.buffer.find('\n') {
+                                let line: String = state.buffer.drain(..=newline).collect();
+                                match GeminiClient::parse_sse_line(line.trim_end()) {
+                                    Some(Ok(chunk)) => GeminiClient::apply_stream_chunk(
+                                        chunk,
+                                        &mut state.parse,
+                                        &mut state.pending_events,
+                                    ),
+                                    Some(Err(e)) => state.pending_events.push_back(Err(e)),
+                                    None => {}
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            return Some((
+                                Err(LLMError::stream_error(format!("stream read error: {}", e))),
+                                state,
+                            ));
+                        }
+                    }
+                }
+            },
+        );
+
+        Ok(Box::pin(event_stream))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "gemini"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_client() -> GeminiClient {
+        GeminiClient::new(ProviderConfig::gemini("test-key")).unwrap()
+    }
+
+    fn apply_lines(lines: &[&str]) -> Vec<LLMStreamEvent> {
+        let mut state = StreamParseState::default();
+        let mut out = VecDeque::new();
+        for line in lines {
+            if let Some(chunk) = GeminiClient::parse_sse_line(line) {
+                GeminiClient::apply_stream_chunk(chunk.unwrap(), &mut state, &mut out);
+            }
+        }
+        out.into_iter().map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn gemini_client_endpoints() {
+        let client = create_test_client();
+        assert_eq!(
+            client.endpoint("generateContent"),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent"
+        );
+    }
+
+    #[test]
+    fn gemini_convert_messages() {
+        let client = create_test_client();
+        let tool_calls = vec![ToolCall {
+            id: "tc_1".to_string(),
+            name: "search".to_string(),
+            arguments: serde_json::json!({"query": "rust"}),
+        }];
+        let messages = vec![
+            Message::system("Be brief."),
+            Message::user("Find Rust docs"),
+            Message::assistant_with_tools("", tool_calls),
+            Message::tool("tc_1", "docs.rs"),
+        ];
+
+        let (contents, system) = client.convert_messages(&messages);
+
+        assert_eq!(system.unwrap().parts[0].text.as_deref(), Some("Be brief."));
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[0].role.as_deref(), Some("user"));
+        assert_eq!(contents[1].role.as_deref(), Some("model"));
+        let call = contents[1].parts[0].function_call.as_ref().unwrap();
+        assert_eq!(call.name, "search");
+        assert_eq!(call.args, serde_json::json!({"query": "rust"}));
+        let response = contents[2].parts[0].function_response.as_ref().unwrap();
+        assert_eq!(response.name, "search");
+        assert_eq!(response.response, serde_json::json!({"result": "docs.rs"}));
+    }
+
+    #[test]
+    fn gemini_request_uses_generation_config() {
+        let client = create_test_client();
+        let sampling = SamplingParams::new().with_top_p(0.9).with_top_k(40);
+        let tools = [ToolDefinition {
+            name: "search".to_string(),
+            description: "Searches".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {"q": {"type": "string"}}
+            }),
+        }];
+        let body = client.build_request_body(&[Message::user("hi")], Some(&tools), Some(&sampling));
+
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["generationConfig"]["topP"], 0.9);
+        assert_eq!(json["generationConfig"]["topK"], 40);
+        assert_eq!(json["generationConfig"]["maxOutputTokens"], 8192);
+        assert!(json.get("systemInstruction").is_none());
+        let parameters = &json["tools"][0]["functionDeclarations"][0]["parameters"];
+        assert!(parameters.get("additionalProperties").is_none());
+        assert_eq!(parameters["properties"]["q"]["type"], "string");
+    }
+
+    #[test]
+    fn gemini_parse_stop_reason() {
+        assert_eq!(
+            GeminiClient::parse_stop_reason(Some("STOP")),
+            StopReason::EndTurn
+        );
+        assert_eq!(
+            GeminiClient::parse_stop_reason(Some("MAX_TOKENS")),
+            StopReason::MaxTokens
+        );
+        assert_eq!(
+            GeminiClient::parse_stop_reason(Some("SAFETY")),
+            StopReason::EndTurn
+        );
+        assert_eq!(GeminiClient::parse_stop_reason(None), StopReason::EndTurn);
+    }
+
+    #[test]
+    fn gemini_stream_text_events() {
+        let events = apply_lines(&[
+            r#"data: {"candidates":[{"content":{"role":"model","parts":[{"text":"Hel"}]}}],"responseId":"resp_1"}"#,
+            "",
+            r#"data: {"candidates":[{"content":{"role":"model","parts":[{"text":"lo"}]},"finishReason":"STOP"}]}"#,
+        ]);
+
+        assert_eq!(events.len(), 4);
+        assert!(matches!(&events[0], LLMStreamEvent::Start { id } if id == "resp_1"));
+        assert!(matches!(&events[1], LLMStreamEvent::Token { text } if text == "Hel"));
+        assert!(matches!(&events[2], LLMStreamEvent::Token { text } if text == "lo"));
+        assert!(matches!(
+            &events[3],
+            LLMStreamEvent::End { stop_reason } if *stop_reason == StopReason::EndTurn
+        ));
+    }
+
+    #[test]
+    fn gemini_stream_function_call_events() {
+        let events = apply_lines(&[
+            r#"data: {"candidates":[{"content":{"role":"model","parts":[{"functionCall":{"name":"search","args":{"query":"rust"}}}]},"finishReason":"STOP"}]}"#,
+        ]);
+
+        assert_eq!(events.len(), 3);
+        match &events[1] {
+            LLMStreamEvent::ToolCall { tool_call } => {
+                assert_eq!(tool_call.id, "call_1");
+                assert_eq!(tool_call.name, "search");
+                assert_eq!(tool_call.arguments, serde_json::json!({"query": "rust"}));
+            }
+            other => panic!("expected tool call, got {other:?}"),
+        }
+        assert!(matches!(
+            &events[2],
+            LLMStreamEvent::End { stop_reason } if *stop_reason == StopReason::ToolUse
+        ));
+    }
+
+    #[test]
+    fn gemini_client_implements_llm_client() {
+        let _boxed: Box<dyn LLMClient> = Box::new(create_test_client());
+    }
+
+    #[test]
+    fn gemini_client_provider_name() {
+        assert_eq!(create_test_client().provider_name(), "gemini");
+    }
+}
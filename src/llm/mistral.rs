@@ -0,0 +1,800 @@
+//! Mistral AI API client.
+//!
+//! HTTP client for the Mistral chat completions API, including streaming SSE
+//! response handling and function calling.
+//!
+//! The wire format resembles OpenAI's, with differences that break the
+//! OpenAI client: tool call IDs must be exactly nine alphanumeric
+//! characters, tool results carry the function name, arguments may arrive as
+//! a JSON object rather than a string, streamed tool calls arrive whole, and
+//! the final chunk carries `finish_reason` before the `[DONE]` marker.
+
+use crate::llm::client::{LLMClient, LLMClientResponse, LLMEventStream, LLMStreamEvent};
+use crate::llm::config::{ProviderConfig, SamplingParams};
+use crate::llm::error::LLMError;
+use crate::messages::{Message, MessageRole, StopReason, ToolCall, ToolDefinition};
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::Client;
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Length of the tool call IDs Mistral accepts.
+const TOOL_CALL_ID_LEN: usize = 9;
+
+/// Client for the Mistral AI API.
+#[derive(Debug, Clone)]
+pub struct MistralClient {
+    /// HTTP client
+    client: Client,
+    /// Configuration
+    config: ProviderConfig,
+}
+
+/// Request body for the Mistral chat completions API.
+#[derive(Debug, Clone, Serialize)]
+struct MistralChatRequest {
+    model: String,
+    messages: Vec<MistralMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<MistralTool>>,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    random_seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop: Option<Vec<String>>,
+}
+
+/// A request message in Mistral format.
+#[derive(Debug, Clone, Serialize)]
+struct MistralMessage {
+    role: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_calls: Option<Vec<MistralToolCall>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+/// A tool definition in Mistral format.
+#[derive(Debug, Clone, Serialize)]
+struct MistralTool {
+    #[serde(rename = "type")]
+    tool_type: String,
+    function: MistralFunction,
+}
+
+/// A function definition in Mistral format.
+#[derive(Debug, Clone, Serialize)]
+struct MistralFunction {
+    name: String,
+    description: String,
+    parameters: serde_json::Value,
+}
+
+/// A tool call in Mistral format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MistralToolCall {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(rename = "type", default = "function_type")]
+    call_type: String,
+    function: MistralFunctionCall,
+}
+
+fn function_type() -> String {
+    "function".to_string()
+}
+
+/// A function call in Mistral format.
+///
+/// Requests send `arguments` as a JSON string; responses may return either
+/// a string or an object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MistralFunctionCall {
+    name: String,
+    arguments: serde_json::Value,
+}
+
+impl MistralFunctionCall {
+    /// Returns the arguments as a JSON value, parsing them if sent as a string.
+    fn parsed_arguments(&self) -> serde_json::Value {
+        match &self.arguments {
+            serde_json::Value::String(s) if s.trim().is_empty() => serde_json::json!({}),
+            serde_json::Value::String(s) => {
+                serde_json::from_str(s).unwrap_or_else(|_| serde_json::json!({}))
+            }
+            other => other.clone(),
+        }
+    }
+}
+
+/// Non-streaming response, and each streaming chunk.
+#[derive(Debug, Clone, Deserialize)]
+struct MistralChatResponse {
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    choices: Vec<MistralChoice>,
+}
+
+/// A choice in a response or chunk.
+#[derive(Debug, Clone, Deserialize)]
+struct MistralChoice {
+    #[serde(default, alias = "delta")]
+    message: Option<MistralResponseMessage>,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+/// The assistant message of a response, or its delta in a chunk.
+#[derive(Debug, Clone, Deserialize)]
+struct MistralResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    tool_calls: Option<Vec<MistralToolCall>>,
+}
+
+/// Error response from the Mistral API.
+///
+/// Validation errors (422) use `detail`; other errors use `message`.
+#[derive(Debug, Clone, Deserialize)]
+struct MistralErrorResponse {
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    detail: Option<serde_json::Value>,
+}
+
+/// A parsed `data:` line of the stream.
+#[derive(Debug)]
+enum MistralSseData {
+    Chunk(MistralChatResponse),
+    Done,
+}
+
+/// State carried across streaming chunks.
+#[derive(Debug, Default)]
+struct StreamParseState {
+    started: bool,
+    ended: bool,
+    emitted_tool_calls: bool,
+}
+
+impl MistralClient {
+    /// Creates a new Mistral client with the given configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Provider configuration including API key and settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `LLMError::network` if the HTTP client cannot be created.
+    pub fn new(config: ProviderConfig) -> Result<Self, LLMError> {
+        let client = Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .map_err(|e| LLMError::network(format!("failed to create HTTP client: {}", e)))?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Returns the chat completions endpoint URL.
+    fn chat_endpoint(&self) -> String {
+        format!("{}/v1/chat/completions", self.config.base_url)
+    }
+
+    /// Converts a tool call ID to one Mistral accepts.
+    ///
+    /// IDs that are already nine alphanumeric characters pass through;
+    /// others, such as IDs issued by another provider, are replaced by a
+    /// stable nine-character ID derived from them, so a tool result still
+    /// matches its call.
+    fn tool_call_id(id: &str) -> String {
+        if id.len() == TOOL_CALL_ID_LEN && id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return id.to_string();
+        }
+
+        const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        digest(&SHA256, id.as_bytes())
+            .as_ref()
+            .iter()
+            .take(TOOL_CALL_ID_LEN)
+            .map(|byte| ALPHABET[usize::from(*byte) % ALPHABET.len()] as char)
+            .collect()
+    }
+
+    /// Converts internal messages to Mistral API format.
+    fn convert_messages(&self, messages: &[Message]) -> Vec<MistralMessage> {
+        let mut call_names: HashMap<&str, &str> = HashMap::new();
+
+        messages
+            .iter()
+            .map(|msg| match msg.role {
+                MessageRole::System | MessageRole::User => MistralMessage {
+                    role: msg.role.to_string(),
+                    content: Some(msg.content.clone()),
+                    tool_calls: None,
+                    tool_call_id: None,
+                    name: None,
+                },
+                MessageRole::Assistant => {
+                    let tool_calls = msg.tool_calls.as_ref().map(|tcs| {
+                        tcs.iter()
+                            .map(|tc| {
+                                call_names.insert(&tc.id, &tc.name);
+                                MistralToolCall {
+                                    id: Some(Self::tool_call_id(&tc.id)),
+                                    call_type: function_type(),
+                                    function: MistralFunctionCall {
+                                        name: tc.name.clone(),
+                                        arguments: serde_json::Value::String(
+                                            tc.arguments.to_string(),
+                                        ),
+                                    },
+                                }
+                            })
+                            .collect()
+                    });
+
+                    MistralMessage {
+                        role: "assistant".to_string(),
+                        content: if msg.content.is_empty() {
+                            None
+                        } else {
+                            Some(msg.content.clone())
+                        },
+                        tool_calls,
+                        tool_call_id: None,
+                        name: None,
+                    }
+                }
+                MessageRole::Tool => MistralMessage {
+                    role: "tool".to_string(),
+                    content: Some(msg.content.clone()),
+                    tool_calls: None,
+                    name: msg
+                        .tool_call_id
+                        .as_deref()
+                        .and_then(|id| call_names.get(id))
+                        .map(|name| (*name).to_string()),
+                    tool_call_id: msg.tool_call_id.as_deref().map(Self::tool_call_id),
+                },
+            })
+            .collect()
+    }
+
+    /// Converts tool definitions to Mistral API format.
+    fn convert_tools(&self, tools: &[ToolDefinition]) -> Vec<MistralTool> {
+        tools
+            .iter()
+            .map(|t| MistralTool {
+                tool_type: function_type(),
+                function: MistralFunction {
+                    name: t.name.clone(),
+                    description: t.description.clone(),
+                    parameters: t.input_schema.clone(),
+                },
+            })
+            .collect()
+    }
+
+    /// Builds the request body.
+    fn build_request_body(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+        stream: bool,
+    ) -> MistralChatRequest {
+        MistralChatRequest {
+            model: self.config.model.clone(),
+            messages: self.convert_messages(messages),
+            tools: tools.map(|t| self.convert_tools(t)),
+            stream,
+            max_tokens: Some(
+                sampling
+                    .and_then(|s| s.max_tokens)
+                    .unwrap_or(self.config.max_tokens),
+            ),
+            temperature: sampling.and_then(|s| s.temperature),
+            top_p: sampling.and_then(|s| s.top_p),
+            frequency_penalty: sampling.and_then(|s| s.frequency_penalty),
+            presence_penalty: sampling.and_then(|s| s.presence_penalty),
+            random_seed: sampling.and_then(|s| s.seed),
+            stop: sampling.and_then(|s| s.stop_sequences.clone()),
+        }
+    }
+
+    /// Sends the request and maps non-success statuses to errors.
+    async fn post(&self, request_body: &MistralChatRequest) -> Result<reqwest::Response, LLMError> {
+        let response = self
+            .client
+            .post(self.chat_endpoint())
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("content-type", "application/json")
+            .json(request_body)
+            .send()
+            .await
+            .map_err(|e| self.map_reqwest_error(e))?;
+
+        if !response.status().is_success() {
+            return Err(self.parse_error_response(response).await);
+        }
+
+        Ok(response)
+    }
+
+    /// Parses Mistral finish reason to internal format.
+    #[must_use]
+    pub fn parse_stop_reason(reason: Option<&str>) -> StopReason {
+        match reason {
+            Some("stop") => StopReason::EndTurn,
+            Some("length" | "model_length") => StopReason::MaxTokens,
+            Some("tool_calls") => StopReason::ToolUse,
+            _ => StopReason::EndTurn,
+        }
+    }
+
+    /// Parses an error response from the API.
+    async fn parse_error_response(&self, response: reqwest::Response) -> LLMError {
+        let status = response.status();
+        let status_code = status.as_u16();
+
+        // Check for rate limit
+        if status_code == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(60);
+
+            return LLMError::rate_limited(Duration::from_secs(retry_after));
+        }
+
+        // Try to parse error body
+        let error_body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<MistralErrorResponse>(&error_body)
+            .ok()
+            .and_then(|e| e.message.or_else(|| e.detail.map(|d| d.to_string())))
+            .unwrap_or_else(|| {
+                if error_body.is_empty() {
+                    status
+                        .canonical_reason()
+                        .unwrap_or("Unknown error")
+                        .to_string()
+                } else {
+                    error_body
+                }
+            });
+
+        match status_code {
+            401 | 403 => LLMError::authentication_failed(message),
+            400 | 422 => LLMError::invalid_request(message),
+            _ => LLMError::api_error(status_code, message, None),
+        }
+    }
+
+    /// Maps a reqwest error to an LLMError.
+    fn map_reqwest_error(&self, error: reqwest::Error) -> LLMError {
+        if error.is_timeout() {
+            LLMError::timeout(self.config.timeout)
+        } else {
+            LLMError::network(format!("request failed: {}", error))
+        }
+    }
+
+    /// Parses a single SSE line.
+    ///
+    /// Returns `None` for blank lines and anything else that does not carry
+    /// a `data:` payload.
+    fn parse_sse_line(line: &str) -> Option<Result<MistralSseData, LLMError>> {
+        let data = line.strip_prefix("data:")?.trim_start();
+
+        if data.is_empty() {
+            return None;
+        }
+        if data == "[DONE]" {
+            return Some(Ok(MistralSseData::Done));
+        }
+
+        Some(
+            serde_json::from_str::<MistralChatResponse>(data)
+                .map(MistralSseData::Chunk)
+                .map_err(|e| LLMError::parse_error(format!("failed to parse SSE chunk: {}", e))),
+        )
+    }
+
+    /// Applies one SSE payload, appending the resulting LLM events to `out`.
+    ///
+    /// The stream ends at the first chunk with a `finish_reason`, which may
+    /// also carry the last text. `[DONE]` only ends the stream if no such
+    /// chunk was seen.
+    fn apply_sse_data(
+        data: MistralSseData,
+        state: &mut StreamParseState,
+        out: &mut VecDeque<Result<LLMStreamEvent, LLMError>>,
+    ) {
+        if state.ended {
+            return;
+        }
+
+        let chunk = match data {
+            MistralSseData::Chunk(chunk) => chunk,
+            MistralSseData::Done => {
+                let stop_reason = if state.emitted_tool_calls {
+                    StopReason::ToolUse
+                } else {
+                    StopReason::EndTurn
+                };
+                out.push_back(Ok(LLMStreamEvent::End { stop_reason }));
+                state.ended = true;
+                return;
+            }
+        };
+
+        if !state.started {
+            if let Some(id) = chunk.id {
+                out.push_back(Ok(LLMStreamEvent::Start { id }));
+            }
+            state.started = true;
+        }
+
+        for choice in chunk.choices {
+            if let Some(delta) = choice.message {
+                if let Some(text) = delta.content.filter(|t| !t.is_empty()) {
+                    out.push_back(Ok(LLMStreamEvent::Token { text }));
+                }
+                for tc in delta.tool_calls.into_iter().flatten() {
+                    let Some(id) = tc.id.clone() else {
+                        continue;
+                    };
+                    out.push_back(Ok(LLMStreamEvent::ToolCall {
+                        tool_call: ToolCall {
+                            id,
+                            arguments: tc.function.parsed_arguments(),
+                            name: tc.function.name,
+                        },
+                    }));
+                    state.emitted_tool_calls = true;
+                }
+            }
+
+            if let Some(reason) = choice.finish_reason.as_deref() {
+                if reason == "error" {
+                    out.push_back(Ok(LLMStreamEvent::Error {
+                        error_type: "error".to_string(),
+                        message: "Mistral ended the stream with an error".to_string(),
+                    }));
+                }
+                let stop_reason = if state.emitted_tool_calls {
+                    StopReason::ToolUse
+                } else {
+                    Self::parse_stop_reason(Some(reason))
+                };
+                out.push_back(Ok(LLMStreamEvent::End { stop_reason }));
+                state.ended = true;
+                return;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for MistralClient {
+    async fn send_request(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+    ) -> Result<LLMClientResponse, LLMError> {
+        let request_body = self.build_request_body(messages, tools, sampling, false);
+        let response = self.post(&request_body).await?;
+
+        let chat: MistralChatResponse = response
+            .json()
+            .await
+            .map_err(|e| LLMError::parse_error(format!("failed to parse response: {}", e)))?;
+
+        let choice = chat
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| LLMError::parse_error("response contained no choices"))?;
+        let message = choice.message;
+
+        let content = message
+            .as_ref()
+            .and_then(|m| m.content.clone())
+            .unwrap_or_default();
+
+        let tool_calls = message
+            .and_then(|m| m.tool_calls)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|tc| {
+                Some(ToolCall {
+                    id: tc.id.clone()?,
+                    arguments: tc.function.parsed_arguments(),
+                    name: tc.function.name,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let stop_reason = if !tool_calls.is_empty() {
+            StopReason::ToolUse
+        } else {
+            Self::parse_stop_reason(choice.finish_reason.as_deref())
+        };
+
+        Ok(LLMClientResponse {
+            content,
+            tool_calls,
+            stop_reason,
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
+        })
+    }
+
+    async fn send_streaming_request(
+        &self,
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        sampling: Option<&SamplingParams>,
+    ) -> Result<LLMEventStream, LLMError> {
+        let request_body = self.build_request_body(messages, tools, sampling, true);
+        let response = self.post(&request_body).await?;
+
+        // State carried through the unfold iteration. `buffer` holds a
+        // trailing partial line until the next chunk completes it.
+        struct StreamState<S> {
+            stream: S,
+            buffer: String,
+            parse: StreamParseState,
+            pending_events: VecDeque<Result<LLMStreamEvent, LLMError>>,
+        }
+
+        let event_stream = futures::stream::unfold(
+            StreamState {
+                stream: response.bytes_stream(),
+                buffer: String::new(),
+                parse: StreamParseState::default(),
+                pending_events: VecDeque::new(),
+            },
+            |mut state| async move {
+                loop {
+                    if let Some(event) = state.pending_events.pop_front() {
+                        return Some((event, state));
+                    }
+
+                    match state.stream.next().await? {
+                        Ok(bytes) => {
+                            state.buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+                            while let Some(newline) = state.buffer.find('\n') {
+                                let line: String = state.buffer.drain(..=newline).collect();
+                                match MistralClient::parse_sse_line(line.trim_end()) {
+                                    Some(Ok(data)) => MistralClient::apply_sse_data(
+                                        data,
+                                        &mut state.parse,
+                                        &mut state.pending_events,
+                                    ),
+                                    Some(Err(e)) => state.pending_events.push_back(Err(e)),
+                                    None => {}
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            return Some((
+                                Err(LLMError::stream_error(format!("stream read error: {}", e))),
+                                state,
+                            ));
+                        }
+                    }
+                }
+            },
+        );
+
+        Ok(Box::pin(event_stream))
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "mistral"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_client() -> MistralClient {
+        MistralClient::new(ProviderConfig::mistral("test-key")).unwrap()
+    }
+
+    fn apply_lines(lines: &[&str]) -> Vec<LLMStreamEvent> {
+        let mut state = StreamParseState::default();
+        let mut out = VecDeque::new();
+        for line in lines {
+            if let Some(data) = MistralClient::parse_sse_line(line) {
+                MistralClient::apply_sse_data(data.unwrap(), &mut state, &mut out);
+            }
+        }
+        out.into_iter().map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn mistral_client_chat_endpoint() {
+        let client = create_test_client();
+        assert_eq!(
+            client.chat_endpoint(),
+            "https://api.mistral.ai/v1/chat/completions"
+        );
+    }
+
+    #[test]
+    fn mistral_tool_call_ids_are_nine_alphanumerics() {
+        assert_eq!(MistralClient::tool_call_id("D681PevKs"), "D681PevKs");
+
+        let converted = MistralClient::tool_call_id("toolu_01A09q90qw90lq917835lq9");
+        assert_eq!(converted.len(), 9);
+        assert!(converted.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(
+            converted,
+            MistralClient::tool_call_id("toolu_01A09q90qw90lq917835lq9")
+        );
+    }
+
+    #[test]
+    fn mistral_convert_messages() {
+        let client = create_test_client();
+        let tool_calls = vec![ToolCall {
+            id: "call_1".to_string(),
+            name: "search".to_string(),
+            arguments: serde_json::json!({"query": "rust"}),
+        }];
+        let messages = vec![
+            Message::system("Be brief."),
+            Message::user("Find Rust docs"),
+            Message::assistant_with_tools("", tool_calls),
+            Message::tool("call_1", "docs.rs"),
+        ];
+
+        let api_messages = client.convert_messages(&messages);
+
+        assert_eq!(api_messages.len(), 4);
+        assert_eq!(api_messages[0].role, "system");
+        assert_eq!(api_messages[1].role, "user");
+        assert!(api_messages[2].content.is_none());
+        let call = &api_messages[2].tool_calls.as_ref().unwrap()[0];
+        assert_eq!(call.function.arguments, r#"{"query":"rust"}"#);
+        assert_eq!(call.id, Some(MistralClient::tool_call_id("call_1")));
+        assert_eq!(api_messages[3].role, "tool");
+        assert_eq!(api_messages[3].tool_call_id, call.id);
+        assert_eq!(api_messages[3].name, Some("search".to_string()));
+    }
+
+    #[test]
+    fn mistral_request_uses_random_seed() {
+        let client = create_test_client();
+        let sampling = SamplingParams::new().with_seed(7).with_top_k(40);
+        let body = client.build_request_body(&[Message::user("hi")], None, Some(&sampling), false);
+
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["random_seed"], 7);
+        assert_eq!(json["model"], "mistral-large-latest");
+        assert!(json.get("seed").is_none());
+        assert!(json.get("top_k").is_none());
+    }
+
+    #[test]
+    fn mistral_parse_stop_reason() {
+        assert_eq!(
+            MistralClient::parse_stop_reason(Some("stop")),
+            StopReason::EndTurn
+        );
+        assert_eq!(
+            MistralClient::parse_stop_reason(Some("model_length")),
+            StopReason::MaxTokens
+        );
+        assert_eq!(
+            MistralClient::parse_stop_reason(Some("tool_calls")),
+            StopReason::ToolUse
+        );
+        assert_eq!(MistralClient::parse_stop_reason(None), StopReason::EndTurn);
+    }
+
+    #[test]
+    fn mistral_function_call_accepts_object_arguments() {
+        let call: MistralToolCall = serde_json::from_str(
+            r#"{"id":"D681PevKs","function":{"name":"search","arguments":{"query":"rust"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            call.function.parsed_arguments(),
+            serde_json::json!({"query": "rust"})
+        );
+    }
+
+    #[test]
+    fn mistral_stream_final_chunk_carries_text_and_finish_reason() {
+        let events = apply_lines(&[
+            r#"data: {"id":"cmpl_1","choices":[{"index":0,"delta":{"role":"assistant","content":""},"finish_reason":null}]}"#,
+            r#"data: {"id":"cmpl_1","choices":[{"index":0,"delta":{"content":"Hello"},"finish_reason":null}]}"#,
+            r#"data: {"id":"cmpl_1","choices":[{"index":0,"delta":{"content":"!"},"finish_reason":"stop"}],"usage":{"prompt_tokens":5,"completion_tokens":2}}"#,
+            "data: [DONE]",
+        ]);
+
+        assert_eq!(events.len(), 4);
+        assert!(matches!(&events[0], LLMStreamEvent::Start { id } if id == "cmpl_1"));
+        assert!(matches!(&events[1], LLMStreamEvent::Token { text } if text == "Hello"));
+        assert!(matches!(&events[2], LLMStreamEvent::Token { text } if text == "!"));
+        assert!(matches!(
+            &events[3],
+            LLMStreamEvent::End { stop_reason } if *stop_reason == StopReason::EndTurn
+        ));
+    }
+
+    #[test]
+    fn mistral_stream_tool_call_events() {
+        let events = apply_lines(&[
+            r#"data: {"id":"cmpl_2","choices":[{"index":0,"delta":{"tool_calls":[{"id":"D681PevKs","function":{"name":"search","arguments":"{\"query\": \"rust\"}"}}]},"finish_reason":"tool_calls"}]}"#,
+            "data: [DONE]",
+        ]);
+
+        assert_eq!(events.len(), 3);
+        match &events[1] {
+            LLMStreamEvent::ToolCall { tool_call } => {
+                assert_eq!(tool_call.id, "D681PevKs");
+                assert_eq!(tool_call.name, "search");
+                assert_eq!(tool_call.arguments, serde_json::json!({"query": "rust"}));
+            }
+            other => panic!("expected tool call, got {other:?}"),
+        }
+        assert!(matches!(
+            &events[2],
+            LLMStreamEvent::End { stop_reason } if *stop_reason == StopReason::ToolUse
+        ));
+    }
+
+    #[test]
+    fn mistral_stream_done_without_finish_reason_ends_stream() {
+        let events = apply_lines(&[
+            r#"data: {"id":"cmpl_3","choices":[{"index":0,"delta":{"content":"Hi"}}]}"#,
+            "data: [DONE]",
+        ]);
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(
+            &events[2],
+            LLMStreamEvent::End { stop_reason } if *stop_reason == StopReason::EndTurn
+        ));
+    }
+
+    #[test]
+    fn mistral_client_implements_llm_client() {
+        let _boxed: Box<dyn LLMClient> = Box::new(create_test_client());
+    }
+
+    #[test]
+    fn mistral_client_provider_name() {
+        assert_eq!(create_test_client().provider_name(), "mistral");
+    }
+}
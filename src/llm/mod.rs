@@ -1,21 +1,46 @@
 //! LLM provider module.
 //!
 //! This module contains the LLM Provider actor implementation, API clients for
-//! Anthropic and OpenAI-compatible endpoints, and streaming message handling
-//! for token-by-token responses.
+//! Anthropic, Cohere, Gemini, and OpenAI-compatible endpoints, and streaming message
+//! handling for token-by-token responses.
 
+mod adaptive_rate_limit;
 mod anthropic;
+mod circuit_breaker;
 mod client;
+mod cohere;
 mod config;
 mod error;
+mod gemini;
+mod mistral;
 mod openai;
+mod prompt_cache;
 mod provider;
+mod response_cache;
 mod streaming;
+mod usage;
 
+pub use adaptive_rate_limit::{AdaptiveRateLimiter, RateLimitSnapshot, RecentRateLimit};
 pub use anthropic::AnthropicClient;
+pub use circuit_breaker::{CircuitBreaker, CircuitState};
 pub use client::{LLMClient, LLMClientResponse, LLMEventStream, LLMStreamEvent};
-pub use config::{ProviderConfig, ProviderType, RateLimitConfig, SamplingParams};
+pub use cohere::CohereClient;
+pub use config::{
+    CircuitBreakerConfig, ExtendedThinkingConfig, ProviderConfig, ProviderType,
+    RateLimitAlgorithm, RateLimitConfig, RetryPolicy, SamplingParams,
+};
 pub use error::{LLMError, LLMErrorKind};
+pub use gemini::GeminiClient;
+pub use mistral::MistralClient;
 pub use openai::OpenAIClient;
-pub use provider::{InitLLMProvider, LLMProvider};
+pub use prompt_cache::{CacheUsage, RecentCacheUsage};
+pub use provider::{
+    CacheStatsReport, CircuitStateReport, DrainQueue, EffectiveRateLimit, GetCacheStats,
+    GetCircuitState, GetEffectiveRateLimit, InitLLMProvider, LLMProvider, PreloadSystemPrompt,
+    QueueDrained, SetFallbackProvider, UpdateRateLimit, UpdateSamplingParams,
+};
+pub use response_cache::{
+    CacheConfig, CacheStats, CachedResponse, ResponseCache, ResponseCacheKey,
+};
 pub use streaming::{ActiveStream, StreamAccumulator};
+pub use usage::TokenUsage;
@@ -1,16 +1,19 @@
 //! OpenAI-compatible API client.
 //!
 //! HTTP client for communicating with OpenAI-compatible APIs including
-//! OpenAI, Ollama, vLLM, LocalAI, and other compatible endpoints.
+//! OpenAI, Azure OpenAI, Ollama, vLLM, LocalAI, and other compatible endpoints.
 
+use crate::llm::adaptive_rate_limit::{RateLimitSnapshot, RecentRateLimit};
 use crate::llm::client::{LLMClient, LLMClientResponse, LLMEventStream, LLMStreamEvent};
 use crate::llm::config::{ProviderConfig, SamplingParams};
 use crate::llm::error::LLMError;
+use crate::llm::usage::TokenUsage;
 use crate::messages::{Message, MessageRole, StopReason, ToolCall, ToolDefinition};
 use async_trait::async_trait;
 use futures::StreamExt;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
 use std::time::Duration;
 
 /// Client for OpenAI-compatible APIs (OpenAI, Ollama, vLLM, LocalAI, etc.).
@@ -26,6 +29,10 @@ pub struct OpenAIClient {
     model: String,
     /// Maximum tokens to generate
     max_tokens: u32,
+    /// Azure `api-version` query parameter; set only for Azure OpenAI
+    azure_api_version: Option<String>,
+    /// Rate-limit headers of the most recent response
+    rate_limit: RecentRateLimit,
 }
 
 /// Request body for OpenAI chat completions API.
@@ -50,6 +57,10 @@ struct ChatCompletionRequest {
     seed: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<serde_json::Value>,
 }
 
 /// A message in OpenAI format.
@@ -118,6 +129,40 @@ struct ChatCompletionChoice {
 struct ChatCompletionChunk {
     id: String,
     choices: Vec<ChatCompletionChunkChoice>,
+    /// Usage of the whole response, sent in the final chunk
+    #[serde(default)]
+    usage: Option<ChatCompletionUsage>,
+}
+
+/// Token usage reported in the final streaming chunk.
+#[derive(Debug, Clone, Deserialize)]
+struct ChatCompletionUsage {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    #[serde(default)]
+    prompt_tokens_details: Option<PromptTokensDetails>,
+}
+
+/// Breakdown of the prompt tokens of a response.
+#[derive(Debug, Clone, Deserialize)]
+struct PromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+impl ChatCompletionUsage {
+    /// Converts the reported usage into a [`TokenUsage`].
+    fn token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.prompt_tokens,
+            output_tokens: self.completion_tokens,
+            cache_read_tokens: self
+                .prompt_tokens_details
+                .as_ref()
+                .and_then(|details| details.cached_tokens),
+            cache_write_tokens: None,
+        }
+    }
 }
 
 /// A choice in a streaming chunk.
@@ -212,6 +257,8 @@ impl OpenAIClient {
             api_key,
             model: config.model.clone(),
             max_tokens: config.max_tokens,
+            azure_api_version: None,
+            rate_limit: RecentRateLimit::default(),
         })
     }
 
@@ -241,6 +288,25 @@ impl OpenAIClient {
         Self::new("https://api.openai.com/v1".to_string(), config)
     }
 
+    /// Creates a client for an Azure OpenAI deployment.
+    ///
+    /// Uses `config.base_url` as the deployment URL, authenticates with the
+    /// `api-key` header and adds `config.api_version` as the `api-version`
+    /// query parameter on every request.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Provider configuration from [`ProviderConfig::openai_azure`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be created.
+    pub fn for_azure(config: &ProviderConfig) -> Result<Self, LLMError> {
+        let mut client = Self::new(config.base_url.clone(), config)?;
+        client.azure_api_version = Some(config.api_version.clone());
+        Ok(client)
+    }
+
     /// Returns the chat completions endpoint URL.
     fn chat_completions_endpoint(&self) -> String {
         format!("{}/chat/completions", self.base_url)
@@ -335,7 +401,12 @@ impl OpenAIClient {
             .header("content-type", "application/json")
             .json(request_body);
 
-        if let Some(ref api_key) = self.api_key {
+        if let Some(ref api_version) = self.azure_api_version {
+            request = request.query(&[("api-version", api_version)]);
+            if let Some(ref api_key) = self.api_key {
+                request = request.header("api-key", api_key);
+            }
+        } else if let Some(ref api_key) = self.api_key {
             request = request.header("Authorization", format!("Bearer {}", api_key));
         }
 
@@ -416,7 +487,11 @@ impl LLMClient for OpenAIClient {
 
         let request_body = ChatCompletionRequest {
             model: self.model.clone(),
-            max_tokens: Some(self.max_tokens),
+            max_tokens: Some(
+                sampling
+                    .and_then(|s| s.max_tokens)
+                    .unwrap_or(self.max_tokens),
+            ),
             messages: api_messages,
             tools: tools.map(|t| self.convert_tools(t)),
             stream: false,
@@ -426,6 +501,10 @@ impl LLMClient for OpenAIClient {
             presence_penalty: sampling.and_then(|s| s.presence_penalty),
             seed: sampling.and_then(|s| s.seed),
             stop: sampling.and_then(|s| s.stop_sequences.clone()),
+            response_format: sampling
+                .and_then(|s| s.output_schema.as_ref())
+                .map(response_format),
+            stream_options: None,
         };
 
         let request = self.build_request(&request_body)?;
@@ -434,6 +513,7 @@ impl LLMClient for OpenAIClient {
             .send()
             .await
             .map_err(|e| LLMError::network(format!("request failed: {}", e)))?;
+        self.rate_limit.record(response.headers());
 
         if !response.status().is_success() {
             return Err(self.parse_error_response(response).await);
@@ -482,6 +562,8 @@ impl LLMClient for OpenAIClient {
             content,
             tool_calls,
             stop_reason,
+            cache_write_tokens: 0,
+            cache_read_tokens: 0,
         })
     }
 
@@ -491,13 +573,15 @@ impl LLMClient for OpenAIClient {
         tools: Option<&[ToolDefinition]>,
         sampling: Option<&SamplingParams>,
     ) -> Result<LLMEventStream, LLMError> {
-        use std::collections::{HashMap, VecDeque};
-
         let api_messages = self.convert_messages(messages);
 
         let request_body = ChatCompletionRequest {
             model: self.model.clone(),
-            max_tokens: Some(self.max_tokens),
+            max_tokens: Some(
+                sampling
+                    .and_then(|s| s.max_tokens)
+                    .unwrap_or(self.max_tokens),
+            ),
             messages: api_messages,
             tools: tools.map(|t| self.convert_tools(t)),
             stream: true,
@@ -507,6 +591,10 @@ impl LLMClient for OpenAIClient {
             presence_penalty: sampling.and_then(|s| s.presence_penalty),
             seed: sampling.and_then(|s| s.seed),
             stop: sampling.and_then(|s| s.stop_sequences.clone()),
+            response_format: sampling
+                .and_then(|s| s.output_schema.as_ref())
+                .map(response_format),
+            stream_options: Some(serde_json::json!({ "include_usage": true })),
         };
 
         let request = self.build_request(&request_body)?;
@@ -515,160 +603,212 @@ impl LLMClient for OpenAIClient {
             .send()
             .await
             .map_err(|e| LLMError::network(format!("request failed: {}", e)))?;
+        self.rate_limit.record(response.headers());
 
         if !response.status().is_success() {
             return Err(self.parse_error_response(response).await);
         }
 
-        let stream = response.bytes_stream();
+        Ok(Box::pin(convert_openai_stream(response.bytes_stream())))
+    }
 
-        // State carried through the unfold iteration.
-        // This eliminates the need for Arc<Mutex<>> by owning state directly.
-        struct StreamState<S> {
-            stream: S,
-            tool_accumulators: HashMap<usize, ToolCallAccumulator>,
-            pending_events: VecDeque<Result<LLMStreamEvent, LLMError>>,
+    fn provider_name(&self) -> &'static str {
+        if self.azure_api_version.is_some() {
+            "azure-openai"
+        } else {
+            "openai"
         }
+    }
+
+    fn take_rate_limit_snapshot(&self) -> Option<RateLimitSnapshot> {
+        self.rate_limit.take()
+    }
+}
+
+/// State carried through the unfold iteration of [`convert_openai_stream`].
+struct StreamState<S> {
+    stream: S,
+    tool_accumulators: HashMap<usize, ToolCallAccumulator>,
+    pending_events: VecDeque<Result<LLMStreamEvent, LLMError>>,
+    /// Stop reason held back until the usage chunk or `[DONE]` arrives
+    pending_end: Option<StopReason>,
+}
+
+impl<S> StreamState<S> {
+    /// Queues the events of a parsed chunk.
+    fn apply_chunk(&mut self, chunk: ChatCompletionChunk) {
+        for choice in chunk.choices {
+            // Handle content delta
+            if let Some(content) = choice.delta.content {
+                if !content.is_empty() {
+                    self.pending_events
+                        .push_back(Ok(LLMStreamEvent::Token { text: content }));
+                }
+            }
 
-        let event_stream = futures::stream::unfold(
-            StreamState {
-                stream,
-                tool_accumulators: HashMap::new(),
-                pending_events: VecDeque::new(),
-            },
-            |mut state| async move {
-                loop {
-                    // Return any pending events first
-                    if let Some(event) = state.pending_events.pop_front() {
-                        return Some((event, state));
+            // Handle tool call deltas
+            if let Some(tool_deltas) = choice.delta.tool_calls {
+                for delta in tool_deltas {
+                    let acc = self.tool_accumulators.entry(delta.index).or_default();
+
+                    if let Some(id) = delta.id {
+                        acc.id = Some(id);
                     }
 
-                    // Get next chunk from stream
-                    let result = state.stream.next().await?;
-
-                    match result {
-                        Ok(bytes) => {
-                            let text = String::from_utf8_lossy(&bytes);
-                            let mut first_id = None;
-
-                            for line in text.lines() {
-                                if let Some(chunk_result) = OpenAIClient::parse_sse_line(line) {
-                                    match chunk_result {
-                                        Ok(chunk) => {
-                                            // Capture first ID for Start event
-                                            if first_id.is_none() && !chunk.id.is_empty() {
-                                                first_id = Some(chunk.id.clone());
-                                            }
-
-                                            for choice in chunk.choices {
-                                                // Handle content delta
-                                                if let Some(content) = choice.delta.content {
-                                                    if !content.is_empty() {
-                                                        state.pending_events.push_back(Ok(
-                                                            LLMStreamEvent::Token { text: content },
-                                                        ));
-                                                    }
-                                                }
-
-                                                // Handle tool call deltas
-                                                if let Some(tool_deltas) = choice.delta.tool_calls {
-                                                    for delta in tool_deltas {
-                                                        let acc = state
-                                                            .tool_accumulators
-                                                            .entry(delta.index)
-                                                            .or_default();
-
-                                                        if let Some(id) = delta.id {
-                                                            acc.id = Some(id);
-                                                        }
-
-                                                        if let Some(ref func) = delta.function {
-                                                            if let Some(ref name) = func.name {
-                                                                acc.name = Some(name.clone());
-                                                            }
-                                                            if let Some(ref args) = func.arguments {
-                                                                acc.arguments.push_str(args);
-                                                            }
-                                                        }
-                                                    }
-                                                }
-
-                                                // Handle finish reason
-                                                if let Some(ref reason) = choice.finish_reason {
-                                                    // Emit any accumulated tool calls
-                                                    let mut emitted_tool_calls = false;
-                                                    for acc in state.tool_accumulators.values() {
-                                                        if let (Some(id), Some(name)) =
-                                                            (&acc.id, &acc.name)
-                                                        {
-                                                            let arguments: serde_json::Value =
-                                                                serde_json::from_str(
-                                                                    &acc.arguments,
-                                                                )
-                                                                .unwrap_or(serde_json::json!({}));
-
-                                                            state.pending_events.push_back(Ok(
-                                                                LLMStreamEvent::ToolCall {
-                                                                    tool_call: ToolCall {
-                                                                        id: id.clone(),
-                                                                        name: name.clone(),
-                                                                        arguments,
-                                                                    },
-                                                                },
-                                                            ));
-                                                            emitted_tool_calls = true;
-                                                        }
-                                                    }
-
-                                                    // If tool calls were emitted, force ToolUse
-                                                    // stop reason even if the provider (e.g. Ollama)
-                                                    // sent "stop" instead of "tool_calls".
-                                                    let stop_reason = if emitted_tool_calls {
-                                                        StopReason::ToolUse
-                                                    } else {
-                                                        OpenAIClient::parse_stop_reason(Some(
-                                                            reason,
-                                                        ))
-                                                    };
-
-                                                    state.pending_events.push_back(Ok(
-                                                        LLMStreamEvent::End { stop_reason },
-                                                    ));
-                                                }
-                                            }
-                                        }
-                                        Err(e) => {
-                                            state.pending_events.push_back(Err(e));
+                    if let Some(ref func) = delta.function {
+                        if let Some(ref name) = func.name {
+                            acc.name = Some(name.clone());
+                        }
+                        if let Some(ref args) = func.arguments {
+                            acc.arguments.push_str(args);
+                        }
+                    }
+                }
+            }
+
+            // Handle finish reason
+            if let Some(ref reason) = choice.finish_reason {
+                // Emit any accumulated tool calls
+                let mut emitted_tool_calls = false;
+                for acc in self.tool_accumulators.values() {
+                    if let (Some(id), Some(name)) = (&acc.id, &acc.name) {
+                        let arguments: serde_json::Value =
+                            serde_json::from_str(&acc.arguments).unwrap_or(serde_json::json!({}));
+
+                        self.pending_events.push_back(Ok(LLMStreamEvent::ToolCall {
+                            tool_call: ToolCall {
+                                id: id.clone(),
+                                name: name.clone(),
+                                arguments,
+                            },
+                        }));
+                        emitted_tool_calls = true;
+                    }
+                }
+
+                // If tool calls were emitted, force ToolUse
+                // stop reason even if the provider (e.g. Ollama)
+                // sent "stop" instead of "tool_calls".
+                let stop_reason = if emitted_tool_calls {
+                    StopReason::ToolUse
+                } else {
+                    OpenAIClient::parse_stop_reason(Some(reason))
+                };
+
+                // The usage chunk follows the finish reason, so the end
+                // of the stream waits for it
+                self.pending_end = Some(stop_reason);
+            }
+        }
+
+        if let Some(usage) = chunk.usage {
+            self.pending_events
+                .push_back(Ok(LLMStreamEvent::Usage(usage.token_usage())));
+            self.flush_end();
+        }
+    }
+
+    /// Queues the held-back end of the stream, if any.
+    fn flush_end(&mut self) {
+        if let Some(stop_reason) = self.pending_end.take() {
+            self.pending_events
+                .push_back(Ok(LLMStreamEvent::End { stop_reason }));
+        }
+    }
+}
+
+/// Converts the SSE byte stream of a chat completion into unified events.
+///
+/// The end of the stream is sent after the final `usage` chunk, or at
+/// `[DONE]` for servers that report no usage.
+fn convert_openai_stream<S, B, E>(
+    stream: S,
+) -> impl futures::Stream<Item = Result<LLMStreamEvent, LLMError>> + Send
+where
+    S: futures::Stream<Item = Result<B, E>> + Send + Unpin + 'static,
+    B: AsRef<[u8]>,
+    E: std::fmt::Display,
+{
+    futures::stream::unfold(
+        StreamState {
+            stream,
+            tool_accumulators: HashMap::new(),
+            pending_events: VecDeque::new(),
+            pending_end: None,
+        },
+        |mut state| async move {
+            loop {
+                // Return any pending events first
+                if let Some(event) = state.pending_events.pop_front() {
+                    return Some((event, state));
+                }
+
+                // Get next chunk from stream, ending it if the server closed
+                // the connection without sending `[DONE]`
+                let Some(result) = state.stream.next().await else {
+                    state.flush_end();
+                    let event = state.pending_events.pop_front()?;
+                    return Some((event, state));
+                };
+
+                match result {
+                    Ok(bytes) => {
+                        let text = String::from_utf8_lossy(bytes.as_ref());
+                        let mut first_id = None;
+
+                        for line in text.lines() {
+                            if line.trim() == "data: [DONE]" {
+                                state.flush_end();
+                                continue;
+                            }
+                            if let Some(chunk_result) = OpenAIClient::parse_sse_line(line) {
+                                match chunk_result {
+                                    Ok(chunk) => {
+                                        // Capture first ID for Start event
+                                        if first_id.is_none() && !chunk.id.is_empty() {
+                                            first_id = Some(chunk.id.clone());
                                         }
+                                        state.apply_chunk(chunk);
+                                    }
+                                    Err(e) => {
+                                        state.pending_events.push_back(Err(e));
                                     }
                                 }
                             }
-
-                            // Add Start event at the front if we have an ID
-                            if let Some(id) = first_id {
-                                state
-                                    .pending_events
-                                    .push_front(Ok(LLMStreamEvent::Start { id }));
-                            }
-
-                            // Loop continues to return first pending event (or get next chunk if none)
                         }
-                        Err(e) => {
-                            return Some((
-                                Err(LLMError::stream_error(format!("stream read error: {}", e))),
-                                state,
-                            ));
+
+                        // Add Start event at the front if we have an ID
+                        if let Some(id) = first_id {
+                            state
+                                .pending_events
+                                .push_front(Ok(LLMStreamEvent::Start { id }));
                         }
+
+                        // Loop continues to return first pending event (or get next chunk if none)
+                    }
+                    Err(e) => {
+                        return Some((
+                            Err(LLMError::stream_error(format!("stream read error: {}", e))),
+                            state,
+                        ));
                     }
                 }
-            },
-        );
-
-        Ok(Box::pin(event_stream))
-    }
+            }
+        },
+    )
+}
 
-    fn provider_name(&self) -> &'static str {
-        "openai"
+/// Builds the `response_format` for a requested output schema.
+///
+/// An empty schema (`{}`) asks for any JSON object; anything else is sent
+/// as a `json_schema` format.
+fn response_format(schema: &serde_json::Value) -> serde_json::Value {
+    match schema.as_object() {
+        Some(object) if object.is_empty() => serde_json::json!({ "type": "json_object" }),
+        _ => serde_json::json!({
+            "type": "json_schema",
+            "json_schema": { "name": "response", "schema": schema },
+        }),
     }
 }
 
@@ -706,6 +846,36 @@ mod tests {
         assert_eq!(client.model, "gpt-4o");
     }
 
+    #[test]
+    fn openai_client_for_azure() {
+        let config = ProviderConfig::openai_azure("contoso", "gpt-4o-prod", "azure-key");
+        let client = OpenAIClient::for_azure(&config).unwrap();
+        assert_eq!(client.provider_name(), "azure-openai");
+
+        let body = ChatCompletionRequest {
+            model: client.model.clone(),
+            messages: Vec::new(),
+            max_tokens: None,
+            tools: None,
+            stream: false,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            seed: None,
+            stop: None,
+            response_format: None,
+            stream_options: None,
+        };
+        let request = client.build_request(&body).unwrap().build().unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://contoso.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(request.headers()["api-key"], "azure-key");
+        assert!(request.headers().get("Authorization").is_none());
+    }
+
     #[test]
     fn openai_client_chat_completions_endpoint() {
         let client = create_test_client();
@@ -715,6 +885,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn openai_response_format_uses_json_schema() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "answer": { "type": "string" } }
+        });
+        let format = response_format(&schema);
+        assert_eq!(format["type"], "json_schema");
+        assert_eq!(format["json_schema"]["name"], "response");
+        assert_eq!(format["json_schema"]["schema"], schema);
+    }
+
+    #[test]
+    fn openai_response_format_empty_schema_is_json_object() {
+        let format = response_format(&serde_json::json!({}));
+        assert_eq!(format, serde_json::json!({ "type": "json_object" }));
+    }
+
     #[test]
     fn openai_convert_user_message() {
         let client = create_test_client();
@@ -882,6 +1070,87 @@ mod tests {
         );
     }
 
+    async fn convert_lines(lines: &[&str]) -> Vec<LLMStreamEvent> {
+        let chunks: Vec<Result<String, std::io::Error>> =
+            lines.iter().map(|line| Ok(format!("{line}\n"))).collect();
+        convert_openai_stream(futures::stream::iter(chunks))
+            .map(Result::unwrap)
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn openai_stream_emits_usage_before_end() {
+        let events = convert_lines(&[
+            r#"data: {"id":"chatcmpl-1","choices":[{"index":0,"delta":{"content":"Hi"}}]}"#,
+            r#"data: {"id":"chatcmpl-1","choices":[{"index":0,"delta":{},"finish_reason":"stop"}]}"#,
+            r#"data: {"id":"chatcmpl-1","choices":[],"usage":{"prompt_tokens":20,"completion_tokens":4,"prompt_tokens_details":{"cached_tokens":8}}}"#,
+            "data: [DONE]",
+        ])
+        .await;
+
+        let usage = events
+            .iter()
+            .position(|event| matches!(event, LLMStreamEvent::Usage(_)))
+            .expect("usage event");
+        let end = events
+            .iter()
+            .position(|event| matches!(event, LLMStreamEvent::End { .. }))
+            .expect("end event");
+        assert!(usage < end);
+        assert_eq!(end, events.len() - 1);
+        match &events[usage] {
+            LLMStreamEvent::Usage(usage) => {
+                assert_eq!(usage.input_tokens, 20);
+                assert_eq!(usage.output_tokens, 4);
+                assert_eq!(usage.cache_read_tokens, Some(8));
+                assert_eq!(usage.cache_write_tokens, None);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn openai_stream_without_usage_ends_at_done() {
+        let events = convert_lines(&[
+            r#"data: {"id":"chatcmpl-1","choices":[{"index":0,"delta":{"content":"Hi"}}]}"#,
+            r#"data: {"id":"chatcmpl-1","choices":[{"index":0,"delta":{},"finish_reason":"length"}]}"#,
+            "data: [DONE]",
+        ])
+        .await;
+
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, LLMStreamEvent::Usage(_))));
+        assert!(matches!(
+            events.last(),
+            Some(LLMStreamEvent::End {
+                stop_reason: StopReason::MaxTokens
+            })
+        ));
+    }
+
+    #[test]
+    fn openai_streaming_request_asks_for_usage() {
+        let body = ChatCompletionRequest {
+            model: "gpt-4o".to_string(),
+            messages: Vec::new(),
+            max_tokens: None,
+            tools: None,
+            stream: true,
+            temperature: None,
+            top_p: None,
+            frequency_penalty: None,
+            presence_penalty: None,
+            seed: None,
+            stop: None,
+            response_format: None,
+            stream_options: Some(serde_json::json!({ "include_usage": true })),
+        };
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["stream_options"]["include_usage"], true);
+    }
+
     #[test]
     fn openai_client_implements_llm_client() {
         let config = ProviderConfig::ollama("llama3.2");
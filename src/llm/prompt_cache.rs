@@ -0,0 +1,104 @@
+//! Prompt cache usage reported by Anthropic.
+//!
+//! With prompt caching enabled, Anthropic reports how many input tokens were
+//! written to the cache and how many were served from it. Cache writes cost
+//! 25% more than regular input tokens and cache reads 90% less, so
+//! [`CacheUsage::estimated_tokens_saved`] expresses the net effect in
+//! regular input tokens.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Price of a cache write relative to a regular input token.
+const CACHE_WRITE_MULTIPLIER: f64 = 1.25;
+
+/// Price of a cache read relative to a regular input token.
+const CACHE_READ_MULTIPLIER: f64 = 0.1;
+
+/// Cache token counts of a single response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheUsage {
+    /// Input tokens written to the cache
+    pub cache_creation_tokens: u64,
+    /// Input tokens served from the cache
+    pub cache_read_tokens: u64,
+}
+
+impl CacheUsage {
+    /// Returns true if no tokens were written to or read from the cache.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache_creation_tokens == 0 && self.cache_read_tokens == 0
+    }
+
+    /// Estimates the saving from caching, in regular input tokens.
+    ///
+    /// Negative when cache writes outweigh reads, as on the first request
+    /// with a new prompt.
+    #[must_use]
+    pub fn estimated_tokens_saved(&self) -> f64 {
+        let read = self.cache_read_tokens as f64;
+        let written = self.cache_creation_tokens as f64;
+        read * (1.0 - CACHE_READ_MULTIPLIER) - written * (CACHE_WRITE_MULTIPLIER - 1.0)
+    }
+}
+
+/// Holds the cache usage of a client's most recent response.
+///
+/// Clients record into it when a response reports usage and the provider
+/// takes it once the request completes. Clones share the same slot.
+#[derive(Debug, Clone, Default)]
+pub struct RecentCacheUsage {
+    slot: Arc<Mutex<Option<CacheUsage>>>,
+}
+
+impl RecentCacheUsage {
+    /// Records the cache usage of a response, if it touched the cache.
+    pub fn record(&self, usage: CacheUsage) {
+        if !usage.is_empty() {
+            *self.slot.lock().unwrap_or_else(PoisonError::into_inner) = Some(usage);
+        }
+    }
+
+    /// Takes the most recent usage, leaving the slot empty.
+    #[must_use]
+    pub fn take(&self) -> Option<CacheUsage> {
+        self.slot
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn savings_weigh_reads_against_writes() {
+        let write = CacheUsage {
+            cache_creation_tokens: 1_000,
+            cache_read_tokens: 0,
+        };
+        assert!((write.estimated_tokens_saved() + 250.0).abs() < 1e-9);
+
+        let read = CacheUsage {
+            cache_creation_tokens: 0,
+            cache_read_tokens: 1_000,
+        };
+        assert!((read.estimated_tokens_saved() - 900.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_usage_is_not_recorded() {
+        let recent = RecentCacheUsage::default();
+        recent.record(CacheUsage::default());
+        assert!(recent.take().is_none());
+
+        recent.record(CacheUsage {
+            cache_creation_tokens: 0,
+            cache_read_tokens: 5,
+        });
+        assert_eq!(recent.take().map(|u| u.cache_read_tokens), Some(5));
+        assert!(recent.take().is_none());
+    }
+}
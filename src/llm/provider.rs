@@ -3,26 +3,163 @@
 //! The LLM Provider actor manages API calls to language models with
 //! rate limiting, retry logic, and streaming support.
 
+use crate::kernel::{log_provider_request, DEFAULT_SHUTDOWN_DRAIN_TIMEOUT};
+use crate::llm::adaptive_rate_limit::{
+    lock_limiter, AdaptiveRateLimiter, SharedAdaptiveRateLimiter,
+};
 use crate::llm::anthropic::AnthropicClient;
+use crate::llm::circuit_breaker::{CircuitBreaker, CircuitState};
 use crate::llm::client::{LLMClient, LLMStreamEvent};
-use crate::llm::config::{ProviderConfig, ProviderType, SamplingParams};
+use crate::llm::cohere::CohereClient;
+use crate::llm::config::{
+    ProviderConfig, ProviderType, RateLimitAlgorithm, RateLimitConfig, SamplingParams,
+};
+use crate::llm::error::LLMError;
+use crate::llm::gemini::GeminiClient;
+use crate::llm::mistral::MistralClient;
 use crate::llm::openai::OpenAIClient;
+use crate::llm::prompt_cache::CacheUsage;
+use crate::llm::response_cache::{CacheStats, CachedResponse, ResponseCache, ResponseCacheKey};
 use crate::llm::streaming::StreamAccumulator;
+use crate::llm::usage::TokenUsage;
 use crate::messages::{
-    LLMRequest, LLMResponse, LLMStreamEnd, LLMStreamStart, LLMStreamToken, LLMStreamToolCall,
-    StopReason, SystemEvent,
+    CancelStream, LLMRequest, LLMStreamEnd, LLMStreamError, LLMStreamStart, LLMStreamThinkingToken,
+    LLMStreamToken, LLMStreamToolCall, LLMStreamUsage, Message, StopReason, SystemEvent,
 };
+use crate::types::CorrelationId;
 use acton_reactive::prelude::*;
 use futures::StreamExt;
-use std::collections::VecDeque;
-use std::sync::Arc;
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// How long [`LLMProvider::drain_queue`] waits for the provider to reply.
+const DRAIN_REPLY_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// Message to initialize the LLM Provider with configuration.
 #[acton_message]
 pub struct InitLLMProvider {
     /// Provider configuration
     pub config: ProviderConfig,
+    /// How long in-flight streams may run after a [`DrainQueue`] before
+    /// they are cancelled
+    pub shutdown_drain_timeout: Duration,
+}
+
+/// Puts the provider into its draining state for shutdown.
+///
+/// Queued requests are removed and their callers receive
+/// [`LLMError::cancelled`]; new requests are rejected with
+/// [`LLMError::shutting_down`]. Streams already in flight may finish until
+/// the provider's `shutdown_drain_timeout` elapses, after which they are
+/// cancelled. The provider replies with [`QueueDrained`].
+#[acton_message]
+pub struct DrainQueue;
+
+/// Reply to [`DrainQueue`].
+#[acton_message]
+pub struct QueueDrained {
+    /// The requests that were still queued, in queue order
+    pub requests: Vec<LLMRequest>,
+}
+
+/// Request for the rate limit the provider is currently enforcing.
+///
+/// The provider replies with [`EffectiveRateLimit`].
+#[acton_message]
+pub struct GetEffectiveRateLimit;
+
+/// Reply to [`GetEffectiveRateLimit`].
+#[acton_message]
+pub struct EffectiveRateLimit {
+    /// The limits in force; below the configured ones while adaptive rate
+    /// limiting is backing off
+    pub config: RateLimitConfig,
+}
+
+/// Sets the provider that receives requests while this provider's circuit
+/// breaker is open.
+#[acton_message]
+pub struct SetFallbackProvider {
+    /// Name of the fallback provider, for logging
+    pub name: String,
+    /// Handle of the fallback provider
+    pub handle: ActorHandle,
+}
+
+/// Replaces the provider's default sampling parameters.
+///
+/// Requests handled after this message use the new defaults; per-request
+/// sampling still overrides them field by field.
+#[acton_message]
+pub struct UpdateSamplingParams {
+    /// The new default sampling parameters
+    pub params: SamplingParams,
+}
+
+/// Replaces the provider's rate limit.
+///
+/// Requests handled after this message are limited by the new
+/// configuration; adaptive rate limiting restarts from the new limits.
+#[acton_message]
+pub struct UpdateRateLimit {
+    /// The new rate limit
+    pub config: RateLimitConfig,
+}
+
+/// Request for the provider's circuit breaker state.
+///
+/// The provider replies with [`CircuitStateReport`].
+#[acton_message]
+pub struct GetCircuitState;
+
+/// Reply to [`GetCircuitState`].
+#[acton_message]
+pub struct CircuitStateReport {
+    /// The circuit state, or `None` if the provider has no circuit breaker
+    pub state: Option<CircuitState>,
+}
+
+/// Request for the provider's response cache counters.
+///
+/// The provider replies with [`CacheStatsReport`].
+#[acton_message]
+pub struct GetCacheStats;
+
+/// Reply to [`GetCacheStats`].
+#[acton_message]
+pub struct CacheStatsReport {
+    /// The counters, or `None` if the provider has no response cache
+    pub stats: Option<CacheStats>,
+}
+
+/// Message asking the provider to write a system prompt to the prompt cache.
+///
+/// See [`LLMProvider::preload_system_prompt`].
+#[acton_message]
+pub struct PreloadSystemPrompt {
+    /// The system prompt to cache
+    pub prompt: String,
+}
+
+/// Internal message carrying prompt cache usage from a completed request.
+#[acton_message]
+struct RecordCacheUsage {
+    usage: CacheUsage,
+}
+
+/// Internal message reporting how a request sent to the API ended.
+#[acton_message]
+struct RecordRequestOutcome {
+    error: Option<LLMError>,
+}
+
+/// Internal message storing a completed response in the response cache.
+#[acton_message]
+struct StoreCachedResponse {
+    key: ResponseCacheKey,
+    response: CachedResponse,
 }
 
 /// Internal message for processing queued requests.
@@ -58,13 +195,17 @@ struct RateLimiterState {
     tokens_in_window: u32,
     /// Start of the current window
     window_start: Option<Instant>,
+    /// Token bucket level as of `bucket_updated_at`; the bucket starts full
+    bucket_level: Option<f64>,
+    /// When `bucket_level` was last refilled
+    bucket_updated_at: Option<Instant>,
     /// If rate limited, when we can retry
     rate_limited_until: Option<Instant>,
 }
 
 impl RateLimiterState {
     /// Checks if a request can be made now.
-    fn can_make_request(&self, config: &ProviderConfig) -> bool {
+    fn can_make_request(&self, limit: &RateLimitConfig) -> bool {
         // Check if we're in a rate limit window
         if let Some(until) = self.rate_limited_until {
             if Instant::now() < until {
@@ -72,6 +213,10 @@ impl RateLimiterState {
             }
         }
 
+        if let RateLimitAlgorithm::TokenBucket { burst_capacity } = limit.algorithm {
+            return self.bucket_tokens(limit.requests_per_minute, burst_capacity) >= 1.0;
+        }
+
         // Check if window has expired (reset counters)
         if let Some(start) = self.window_start {
             if start.elapsed() >= Duration::from_secs(60) {
@@ -82,13 +227,63 @@ impl RateLimiterState {
         }
 
         // Check rate limits
-        self.requests_in_window < config.rate_limit.requests_per_minute
+        self.requests_in_window < limit.requests_per_minute
+    }
+
+    /// Returns the tokens in the bucket now, refilled at
+    /// `requests_per_minute / 60` per second up to `burst_capacity`.
+    fn bucket_tokens(&self, requests_per_minute: u32, burst_capacity: u32) -> f64 {
+        let capacity = f64::from(burst_capacity.max(1));
+        match (self.bucket_level, self.bucket_updated_at) {
+            (Some(level), Some(updated_at)) => {
+                let refill =
+                    updated_at.elapsed().as_secs_f64() * f64::from(requests_per_minute) / 60.0;
+                (level + refill).min(capacity)
+            }
+            _ => capacity,
+        }
+    }
+
+    /// Returns how long until the next request is allowed.
+    ///
+    /// For a token bucket this is the time to refill one token; for the
+    /// sliding window it is the rest of the current window.
+    fn retry_after(&self, limit: &RateLimitConfig) -> Duration {
+        let paused = self.rate_limited_until.map_or(Duration::ZERO, |until| {
+            until.saturating_duration_since(Instant::now())
+        });
+
+        let wait = match limit.algorithm {
+            RateLimitAlgorithm::TokenBucket { burst_capacity } => {
+                let missing = 1.0 - self.bucket_tokens(limit.requests_per_minute, burst_capacity);
+                if missing <= 0.0 {
+                    Duration::ZERO
+                } else if limit.requests_per_minute == 0 {
+                    Duration::from_secs(60)
+                } else {
+                    Duration::from_secs_f64(missing * 60.0 / f64::from(limit.requests_per_minute))
+                }
+            }
+            RateLimitAlgorithm::SlidingWindow => {
+                self.window_start.map_or(Duration::ZERO, |start| {
+                    Duration::from_secs(60).saturating_sub(start.elapsed())
+                })
+            }
+        };
+
+        paused.max(wait)
     }
 
     /// Records a request being made.
-    fn record_request(&mut self, estimated_tokens: u32) {
+    fn record_request(&mut self, limit: &RateLimitConfig, estimated_tokens: u32) {
         let now = Instant::now();
 
+        if let RateLimitAlgorithm::TokenBucket { burst_capacity } = limit.algorithm {
+            let level = self.bucket_tokens(limit.requests_per_minute, burst_capacity);
+            self.bucket_level = Some((level - 1.0).max(0.0));
+            self.bucket_updated_at = Some(now);
+        }
+
         // Reset window if expired
         if let Some(start) = self.window_start {
             if start.elapsed() >= Duration::from_secs(60) {
@@ -132,14 +327,30 @@ pub struct LLMProvider {
     queue: VecDeque<PendingRequest>,
     /// Rate limiter state
     rate_limiter: RateLimiterState,
+    /// Header-driven limit tuning, present when `rate_limit.adaptive` is set
+    adaptive: Option<SharedAdaptiveRateLimiter>,
+    /// Failure tracking, present when `circuit_breaker` is configured
+    circuit: Option<CircuitBreaker>,
+    /// Provider that takes requests while the circuit is open
+    fallback: Option<(String, ActorHandle)>,
+    /// Complete responses, present when `response_cache` is configured
+    response_cache: Option<ResponseCache>,
     /// Active streams (for future correlation-based stream management)
     _streams: StreamAccumulator,
+    /// In-flight streams and those asked to stop via [`CancelStream`]
+    cancellations: StreamCancellations,
     /// Whether the provider is shutting down
     shutting_down: bool,
+    /// How long in-flight streams may run once draining starts
+    shutdown_drain_timeout: Duration,
     /// Metrics
     metrics: ProviderMetrics,
 }
 
+/// Temporary actor that receives the [`QueueDrained`] reply.
+#[acton_actor]
+struct DrainReplyReceiver;
+
 /// Metrics for the LLM Provider.
 #[derive(Debug, Clone, Default)]
 pub struct ProviderMetrics {
@@ -153,11 +364,141 @@ pub struct ProviderMetrics {
     pub _rate_limits_hit: u64,
     /// Total tokens used (input + output, to be populated from API response usage)
     pub _tokens_used: u64,
+    /// Input tokens written to the prompt cache
+    pub cache_creation_tokens: u64,
+    /// Input tokens served from the prompt cache
+    pub cache_read_tokens: u64,
+}
+
+impl ProviderMetrics {
+    /// Returns the cumulative prompt cache usage.
+    #[must_use]
+    pub fn cache_usage(&self) -> CacheUsage {
+        CacheUsage {
+            cache_creation_tokens: self.cache_creation_tokens,
+            cache_read_tokens: self.cache_read_tokens,
+        }
+    }
 }
 
 impl LLMProvider {
+    /// Returns the rate limit currently enforced.
+    ///
+    /// With adaptive rate limiting this may be below the configured limit;
+    /// otherwise it is the configured limit. An unconfigured provider
+    /// reports the default.
+    #[must_use]
+    pub fn current_effective_rate_limit(&self) -> RateLimitConfig {
+        match (&self.adaptive, &self.config) {
+            (Some(adaptive), _) => lock_limiter(adaptive).effective(),
+            (None, Some(config)) => config.rate_limit,
+            (None, None) => RateLimitConfig::default(),
+        }
+    }
+
+    /// Returns the circuit breaker state, if the provider has one.
+    #[must_use]
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.circuit.as_ref().map(CircuitBreaker::state)
+    }
+
+    /// Returns the response cache counters, if the provider has a cache.
+    #[must_use]
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.response_cache.as_ref().map(ResponseCache::stats)
+    }
+
+    /// Writes `prompt` to the Anthropic prompt cache ahead of use.
+    ///
+    /// The provider sends a one-token request with `prompt` as a cached
+    /// system prompt, so the first real request that starts with the same
+    /// system prompt reads it from the cache. The request runs in the
+    /// background and counts against the rate limit. Providers without
+    /// prompt caching ignore it.
+    pub async fn preload_system_prompt(provider: &ActorHandle, prompt: &str) {
+        provider
+            .send(PreloadSystemPrompt {
+                prompt: prompt.to_string(),
+            })
+            .await;
+    }
+
+    /// Drains `provider` for shutdown and returns the requests it had queued.
+    ///
+    /// Sends [`DrainQueue`] and waits for the reply. Callers of the returned
+    /// requests have already been sent [`LLMError::cancelled`]. Returns an
+    /// empty list if the provider does not reply, for example because it
+    /// has already stopped.
+    pub async fn drain_queue(
+        runtime: &mut ActorRuntime,
+        provider: &ActorHandle,
+    ) -> Vec<LLMRequest> {
+        let slot: Arc<Mutex<Vec<LLMRequest>>> = Arc::default();
+        let received = Arc::new(Notify::new());
+
+        let mut receiver = runtime.new_actor::<DrainReplyReceiver>();
+        let slot_for_handler = slot.clone();
+        let received_signal = received.clone();
+        receiver.mutate_on::<QueueDrained>(move |_actor, envelope| {
+            if let Ok(mut slot) = slot_for_handler.lock() {
+                *slot = envelope.message().requests.clone();
+            }
+            received_signal.notify_one();
+            Reply::ready()
+        });
+        let receiver = receiver.start().await;
+
+        receiver
+            .create_envelope(Some(provider.reply_address()))
+            .send(DrainQueue)
+            .await;
+        let replied = tokio::time::timeout(DRAIN_REPLY_TIMEOUT, received.notified())
+            .await
+            .is_ok();
+        let _ = receiver.stop().await;
+
+        if !replied {
+            tracing::warn!("LLM provider did not acknowledge drain request");
+            return Vec::new();
+        }
+        slot.lock()
+            .map(|mut guard| std::mem::take(&mut *guard))
+            .unwrap_or_default()
+    }
+
+    /// Returns the cache key of `request`, if the provider has a cache.
+    fn response_cache_key(
+        &self,
+        request: &LLMRequest,
+        sampling: Option<&SamplingParams>,
+    ) -> Option<ResponseCacheKey> {
+        let config = self.config.as_ref()?;
+        self.response_cache.as_ref()?;
+        Some(ResponseCacheKey::new(
+            &request.messages,
+            request.tools.as_deref(),
+            &config.model,
+            sampling,
+        ))
+    }
+
+    /// Checks the effective rate limit and any `retry-after` pause.
+    fn can_make_request(&self) -> bool {
+        if let Some(adaptive) = &self.adaptive {
+            if lock_limiter(adaptive).is_paused() {
+                return false;
+            }
+        }
+        self.rate_limiter
+            .can_make_request(&self.current_effective_rate_limit())
+    }
+
     /// Spawns the LLM Provider actor with the given configuration.
     ///
+    /// In-flight streams get [`DEFAULT_SHUTDOWN_DRAIN_TIMEOUT`] to finish
+    /// once the provider is drained; see
+    /// [`spawn_with_drain_timeout`](Self::spawn_with_drain_timeout).
+    ///
     /// # Arguments
     ///
     /// * `runtime` - The ActorRuntime
@@ -167,6 +508,16 @@ impl LLMProvider {
     ///
     /// The ActorHandle for the started provider.
     pub async fn spawn(runtime: &mut ActorRuntime, config: ProviderConfig) -> ActorHandle {
+        Self::spawn_with_drain_timeout(runtime, config, DEFAULT_SHUTDOWN_DRAIN_TIMEOUT).await
+    }
+
+    /// Spawns the LLM Provider actor, giving in-flight streams
+    /// `shutdown_drain_timeout` to finish after a [`DrainQueue`].
+    pub async fn spawn_with_drain_timeout(
+        runtime: &mut ActorRuntime,
+        config: ProviderConfig,
+        shutdown_drain_timeout: Duration,
+    ) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<LLMProvider>("llm_provider".to_string());
 
         // Store config for initialization
@@ -192,6 +543,7 @@ impl LLMProvider {
                     requests_total = actor.model.metrics.requests_total,
                     requests_success = actor.model.metrics.requests_success,
                     requests_failed = actor.model.metrics.requests_failed,
+                    cache_read_tokens = actor.model.metrics.cache_read_tokens,
                     "LLM Provider shutting down"
                 );
                 Reply::ready()
@@ -206,6 +558,7 @@ impl LLMProvider {
         handle
             .send(InitLLMProvider {
                 config: provider_config,
+                shutdown_drain_timeout,
             })
             .await;
 
@@ -218,6 +571,7 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
     // Handle initialization
     builder.mutate_on::<InitLLMProvider>(|actor, envelope| {
         let config = envelope.message().config.clone();
+        actor.model.shutdown_drain_timeout = envelope.message().shutdown_drain_timeout;
 
         // Create the appropriate client based on provider type
         let client_result: Result<Arc<dyn LLMClient>, crate::llm::error::LLMError> =
@@ -227,12 +581,30 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
                 }
                 ProviderType::OpenAI { base_url } => OpenAIClient::new(base_url.clone(), &config)
                     .map(|c| Arc::new(c) as Arc<dyn LLMClient>),
+                ProviderType::Cohere => {
+                    CohereClient::new(config.clone()).map(|c| Arc::new(c) as Arc<dyn LLMClient>)
+                }
+                ProviderType::Gemini => {
+                    GeminiClient::new(config.clone()).map(|c| Arc::new(c) as Arc<dyn LLMClient>)
+                }
+                ProviderType::Mistral => {
+                    MistralClient::new(config.clone()).map(|c| Arc::new(c) as Arc<dyn LLMClient>)
+                }
+                ProviderType::AzureOpenAI { .. } => {
+                    OpenAIClient::for_azure(&config).map(|c| Arc::new(c) as Arc<dyn LLMClient>)
+                }
             };
 
         match client_result {
             Ok(client) => {
                 let provider_name = client.provider_name();
                 actor.model.client = Some(client);
+                actor.model.adaptive = config
+                    .rate_limit
+                    .adaptive
+                    .then(|| Arc::new(Mutex::new(AdaptiveRateLimiter::new(config.rate_limit))));
+                actor.model.circuit = config.circuit_breaker.map(CircuitBreaker::new);
+                actor.model.response_cache = config.response_cache.map(ResponseCache::new);
                 actor.model.config = Some(config);
                 actor.model.shutting_down = false;
                 tracing::info!(provider = %provider_name, "LLM Provider configured");
@@ -266,8 +638,65 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
                 ));
             };
 
+            log_provider_request(&config.model, &request);
+
+            // Streaming is enabled by default
+            let streaming = config.rate_limit.queue_when_limited;
+
+            let merged_sampling = request_sampling(config, &request);
+
+            // Wrap the system prompt in the provider's mandatory text before
+            // hashing it, as queued requests are stored already wrapped. A
+            // fallback provider gets the original and applies its own.
+            let fallback_request = actor.model.fallback.is_some().then(|| request.clone());
+            let mut request = request;
+            config.apply_system_prompt_affixes(&mut request.messages);
+
+            // Answer repeated requests from the response cache
+            let mut cache_key = actor
+                .model
+                .response_cache_key(&request, merged_sampling.as_ref());
+            if let (Some(cache), Some(key)) = (actor.model.response_cache.as_mut(), &cache_key) {
+                if let Some(cached) = cache.get(key) {
+                    tracing::debug!(
+                        correlation_id = %correlation_id,
+                        "Response served from cache"
+                    );
+                    let broker = actor.broker().clone();
+                    return Reply::try_pending(async move {
+                        replay_cached_response(&broker, &request, cached, streaming).await;
+                        Ok(())
+                    });
+                }
+            }
+
+            // Reject or reroute while the circuit is open
+            if let Some(circuit) = actor.model.circuit.as_mut() {
+                if !circuit.allow_request() {
+                    let provider_name = actor
+                        .model
+                        .client
+                        .as_ref()
+                        .map_or("unknown", |c| c.provider_name());
+                    if let Some((fallback_name, fallback)) = actor.model.fallback.clone() {
+                        tracing::warn!(
+                            correlation_id = %correlation_id,
+                            provider = %provider_name,
+                            fallback = %fallback_name,
+                            "Circuit open, routing request to fallback provider"
+                        );
+                        let request = fallback_request.unwrap_or(request);
+                        return Reply::try_pending(async move {
+                            fallback.send(request).await;
+                            Ok(())
+                        });
+                    }
+                    return Reply::try_err(LLMError::circuit_open(provider_name));
+                }
+            }
+
             // Check rate limits
-            if !actor.model.rate_limiter.can_make_request(config) {
+            if !actor.model.can_make_request() {
                 if config.rate_limit.queue_when_limited {
                     // Check queue size
                     if actor.model.queue.len() >= config.rate_limit.max_queue_size {
@@ -298,50 +727,46 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
 
                     return Reply::try_ok(());
                 } else {
+                    let limit = actor.model.current_effective_rate_limit();
                     return Reply::try_err(crate::llm::error::LLMError::rate_limited(
-                        Duration::from_secs(60),
+                        actor.model.rate_limiter.retry_after(&limit),
                     ));
                 }
             }
 
             // Process the request
             let client = actor.model.client.clone();
+            let adaptive = actor.model.adaptive.clone();
             let broker = actor.broker().clone();
-            let streaming = actor
-                .model
-                .config
-                .as_ref()
-                .map(|c| c.rate_limit.queue_when_limited) // Streaming is enabled by default
-                .unwrap_or(true);
-
-            // Merge provider-level sampling defaults with per-request overrides
-            let merged_sampling = {
-                let base = &config.sampling;
-                match &request.sampling {
-                    Some(overrides) => Some(base.merge_with(overrides)),
-                    None if !base.is_empty() => Some(base.clone()),
-                    _ => None,
-                }
-            };
+            let provider_handle = actor.handle().clone();
+            let cancellations = actor.model.cancellations.clone();
 
             // Record the request
+            let limit = actor.model.current_effective_rate_limit();
             actor
                 .model
                 .rate_limiter
-                .record_request(estimate_tokens(&request));
+                .record_request(&limit, estimate_tokens(&request));
             actor.model.metrics.requests_total += 1;
 
             // Spawn the request processing to avoid Sync requirements
             if let Some(client) = client {
                 tokio::spawn(async move {
-                    if streaming {
-                        process_streaming_request(
+                    let outcome = if streaming {
+                        cancellations.start(&request.correlation_id);
+                        let outcome = process_streaming_request(
                             &client,
                             &request,
                             &broker,
                             merged_sampling.as_ref(),
+                            &cancellations,
                         )
                         .await;
+                        // A cancelled stream is partial; never cache it
+                        if cancellations.finish(&request.correlation_id) {
+                            cache_key = None;
+                        }
+                        outcome
                     } else {
                         process_non_streaming_request(
                             &client,
@@ -349,8 +774,11 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
                             &broker,
                             merged_sampling.as_ref(),
                         )
-                        .await;
-                    }
+                        .await
+                    };
+                    observe_rate_limit(&client, adaptive.as_ref());
+                    forward_cache_usage(&client, &provider_handle).await;
+                    report_outcome(&provider_handle, cache_key, outcome).await;
                 });
             }
 
@@ -368,26 +796,42 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
             // Update metrics
             actor.model.metrics.requests_failed += 1;
 
-            // Broadcast rate limit event if applicable
-            if let Some(retry_after) = error.retry_after() {
-                let broker = actor.broker().clone();
-                let provider_name = actor
-                    .model
-                    .client
-                    .as_ref()
-                    .map(|c| c.provider_name().to_string())
-                    .unwrap_or_else(|| "unknown".to_string());
-                return Box::pin(async move {
+            let broker = actor.broker().clone();
+            let correlation_id = correlation_id.clone();
+            let error = error.clone();
+            let provider_name = actor
+                .model
+                .client
+                .as_ref()
+                .map(|c| c.provider_name().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+
+            Box::pin(async move {
+                // Broadcast rate limit event if applicable
+                if let Some(retry_after) = error.retry_after() {
                     broker
                         .broadcast(SystemEvent::RateLimitHit {
                             provider: provider_name,
                             retry_after_secs: retry_after.as_secs(),
                         })
                         .await;
-                });
-            }
+                }
 
-            Box::pin(async {})
+                // End the caller's stream so it does not wait for a response
+                // that will never come
+                broker
+                    .broadcast(LLMStreamError {
+                        correlation_id: correlation_id.clone(),
+                        error,
+                    })
+                    .await;
+                broker
+                    .broadcast(LLMStreamEnd {
+                        correlation_id,
+                        stop_reason: StopReason::EndTurn,
+                    })
+                    .await;
+            })
         });
 
     // Handle queue processing
@@ -397,38 +841,46 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
         };
 
         // Process queued requests if rate limit allows
-        while actor.model.rate_limiter.can_make_request(config) {
+        while actor.model.can_make_request() {
             if let Some(pending) = actor.model.queue.pop_front() {
                 let client = actor.model.client.clone();
+                let adaptive = actor.model.adaptive.clone();
                 let broker = actor.broker().clone();
+                let provider_handle = actor.handle().clone();
+                let cancellations = actor.model.cancellations.clone();
                 let request = pending.request;
 
-                // Merge provider-level sampling defaults with per-request overrides
-                let merged_sampling = {
-                    let base = &config.sampling;
-                    match &request.sampling {
-                        Some(overrides) => Some(base.merge_with(overrides)),
-                        None if !base.is_empty() => Some(base.clone()),
-                        _ => None,
-                    }
-                };
+                let merged_sampling = request_sampling(config, &request);
+                let mut cache_key = actor
+                    .model
+                    .response_cache_key(&request, merged_sampling.as_ref());
 
+                let limit = actor.model.current_effective_rate_limit();
                 actor
                     .model
                     .rate_limiter
-                    .record_request(estimate_tokens(&request));
+                    .record_request(&limit, estimate_tokens(&request));
                 actor.model.metrics.requests_total += 1;
 
                 // Spawn the request processing
                 tokio::spawn(async move {
                     if let Some(client) = client {
-                        process_streaming_request(
+                        cancellations.start(&request.correlation_id);
+                        let outcome = process_streaming_request(
                             &client,
                             &request,
                             &broker,
                             merged_sampling.as_ref(),
+                            &cancellations,
                         )
                         .await;
+                        // A cancelled stream is partial; never cache it
+                        if cancellations.finish(&request.correlation_id) {
+                            cache_key = None;
+                        }
+                        observe_rate_limit(&client, adaptive.as_ref());
+                        forward_cache_usage(&client, &provider_handle).await;
+                        report_outcome(&provider_handle, cache_key, outcome).await;
                     }
                 });
             } else {
@@ -439,6 +891,232 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
         Reply::ready()
     });
 
+    builder.mutate_on::<RecordCacheUsage>(|actor, envelope| {
+        let usage = envelope.message().usage;
+        let metrics = &mut actor.model.metrics;
+        metrics.cache_creation_tokens += usage.cache_creation_tokens;
+        metrics.cache_read_tokens += usage.cache_read_tokens;
+
+        tracing::info!(
+            cache_creation_tokens = usage.cache_creation_tokens,
+            cache_read_tokens = usage.cache_read_tokens,
+            estimated_tokens_saved = usage.estimated_tokens_saved(),
+            total_estimated_tokens_saved = metrics.cache_usage().estimated_tokens_saved(),
+            "Prompt cache used"
+        );
+
+        let provider = actor
+            .model
+            .client
+            .as_ref()
+            .map_or("unknown", |client| client.provider_name())
+            .to_string();
+        let broker = actor.broker().clone();
+        Reply::pending(async move {
+            broker
+                .broadcast(SystemEvent::PromptCacheUsed {
+                    provider,
+                    cache_write_tokens: usage.cache_creation_tokens,
+                    cache_read_tokens: usage.cache_read_tokens,
+                })
+                .await;
+        })
+    });
+
+    builder.mutate_on::<RecordRequestOutcome>(|actor, envelope| {
+        let Some(circuit) = actor.model.circuit.as_mut() else {
+            return Reply::ready();
+        };
+
+        let before = circuit.state();
+        circuit.record(envelope.message().error.as_ref().map_or(Ok(()), Err));
+        let after = circuit.state();
+        if before != after {
+            let provider = actor
+                .model
+                .client
+                .as_ref()
+                .map_or("unknown", |c| c.provider_name());
+            if after == CircuitState::Open {
+                tracing::warn!(
+                    provider = %provider,
+                    consecutive_failures = circuit.consecutive_failures(),
+                    "Circuit breaker opened"
+                );
+            } else {
+                tracing::info!(provider = %provider, state = ?after, "Circuit breaker state changed");
+            }
+        }
+        Reply::ready()
+    });
+
+    builder.mutate_on::<StoreCachedResponse>(|actor, envelope| {
+        if let Some(cache) = actor.model.response_cache.as_mut() {
+            let msg = envelope.message();
+            cache.insert(msg.key.clone(), msg.response.clone());
+        }
+        Reply::ready()
+    });
+
+    builder.act_on::<GetCacheStats>(|actor, envelope| {
+        let reply = envelope.reply_envelope();
+        let stats = actor.model.cache_stats();
+
+        Reply::pending(async move {
+            reply.send(CacheStatsReport { stats }).await;
+        })
+    });
+
+    builder.mutate_on::<PreloadSystemPrompt>(|actor, envelope| {
+        let Some(client) = actor.model.client.clone() else {
+            tracing::warn!("Cannot preload system prompt: provider has no client");
+            return Reply::ready();
+        };
+        let is_anthropic = actor
+            .model
+            .config
+            .as_ref()
+            .is_some_and(|config| config.provider_type == ProviderType::Anthropic);
+        if !is_anthropic {
+            tracing::debug!(
+                provider = client.provider_name(),
+                "Provider has no prompt caching; not preloading system prompt"
+            );
+            return Reply::ready();
+        }
+
+        let prompt = envelope.message().prompt.clone();
+        let limit = actor.model.current_effective_rate_limit();
+        actor
+            .model
+            .rate_limiter
+            .record_request(&limit, (prompt.len() / 4) as u32);
+        actor.model.metrics.requests_total += 1;
+
+        let provider_handle = actor.handle().clone();
+        tokio::spawn(async move {
+            let messages = [Message::system(prompt), Message::user("Reply with OK.")];
+            let sampling = SamplingParams::new()
+                .with_max_tokens(1)
+                .with_cache_system_prompt(true);
+            let error = client
+                .send_request(&messages, None, Some(&sampling))
+                .await
+                .err();
+            match &error {
+                None => forward_cache_usage(&client, &provider_handle).await,
+                Some(e) => tracing::warn!(error = %e, "Failed to preload system prompt"),
+            }
+            provider_handle.send(RecordRequestOutcome { error }).await;
+        });
+        Reply::ready()
+    });
+
+    builder.mutate_on::<DrainQueue>(|actor, envelope| {
+        actor.model.shutting_down = true;
+        let requests: Vec<LLMRequest> = actor
+            .model
+            .queue
+            .drain(..)
+            .map(|pending| pending.request)
+            .collect();
+        tracing::info!(
+            cancelled = requests.len(),
+            "LLM Provider draining for shutdown"
+        );
+
+        // Give in-flight streams until the drain timeout, then stop them
+        let cancellations = actor.model.cancellations.clone();
+        let timeout = actor.model.shutdown_drain_timeout;
+        tokio::spawn(async move {
+            if tokio::time::timeout(timeout, cancellations.wait_idle())
+                .await
+                .is_err()
+            {
+                let cancelled = cancellations.cancel_all();
+                tracing::warn!(
+                    cancelled,
+                    "Drain timeout elapsed; cancelling in-flight streams"
+                );
+            }
+        });
+
+        let broker = actor.broker().clone();
+        let reply = envelope.reply_envelope();
+        Reply::pending(async move {
+            for request in &requests {
+                broker
+                    .broadcast(LLMStreamError {
+                        correlation_id: request.correlation_id.clone(),
+                        error: LLMError::cancelled(),
+                    })
+                    .await;
+                broker
+                    .broadcast(LLMStreamEnd {
+                        correlation_id: request.correlation_id.clone(),
+                        stop_reason: StopReason::EndTurn,
+                    })
+                    .await;
+            }
+            reply.send(QueueDrained { requests }).await;
+        })
+    });
+
+    builder.act_on::<CancelStream>(|actor, envelope| {
+        let correlation_id = &envelope.message().correlation_id;
+        if actor.model.cancellations.cancel(correlation_id) {
+            tracing::debug!(correlation_id = %correlation_id, "Stream cancelled");
+        }
+        Reply::ready()
+    });
+
+    builder.mutate_on::<SetFallbackProvider>(|actor, envelope| {
+        let msg = envelope.message();
+        actor.model.fallback = Some((msg.name.clone(), msg.handle.clone()));
+        Reply::ready()
+    });
+
+    builder.mutate_on::<UpdateSamplingParams>(|actor, envelope| {
+        if let Some(config) = actor.model.config.as_mut() {
+            config.sampling = envelope.message().params.clone();
+        }
+        Reply::ready()
+    });
+
+    builder.mutate_on::<UpdateRateLimit>(|actor, envelope| {
+        let rate_limit = envelope.message().config;
+        if let Some(config) = actor.model.config.as_mut() {
+            config.rate_limit = rate_limit;
+            actor.model.adaptive = rate_limit
+                .adaptive
+                .then(|| Arc::new(Mutex::new(AdaptiveRateLimiter::new(rate_limit))));
+            tracing::info!(
+                requests_per_minute = rate_limit.requests_per_minute,
+                tokens_per_minute = rate_limit.tokens_per_minute,
+                "Rate limit updated"
+            );
+        }
+        Reply::ready()
+    });
+
+    builder.act_on::<GetCircuitState>(|actor, envelope| {
+        let reply = envelope.reply_envelope();
+        let state = actor.model.circuit_state();
+
+        Reply::pending(async move {
+            reply.send(CircuitStateReport { state }).await;
+        })
+    });
+
+    builder.act_on::<GetEffectiveRateLimit>(|actor, envelope| {
+        let reply = envelope.reply_envelope();
+        let config = actor.model.current_effective_rate_limit();
+
+        Reply::pending(async move {
+            reply.send(EffectiveRateLimit { config }).await;
+        })
+    });
+
     // Handle retry after rate limit
     builder.mutate_on::<RetryAfterRateLimit>(|actor, envelope| {
         let msg = envelope.message();
@@ -471,13 +1149,187 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, LLMProvider>) {
     });
 }
 
+/// Feeds the client's latest rate-limit headers to the adaptive limiter.
+fn observe_rate_limit(client: &Arc<dyn LLMClient>, adaptive: Option<&SharedAdaptiveRateLimiter>) {
+    if let (Some(adaptive), Some(snapshot)) = (adaptive, client.take_rate_limit_snapshot()) {
+        lock_limiter(adaptive).observe(&snapshot);
+    }
+}
+
+/// Sends the client's latest prompt cache usage back to the provider.
+async fn forward_cache_usage(client: &Arc<dyn LLMClient>, provider: &ActorHandle) {
+    if let Some(usage) = client.take_cache_usage() {
+        provider.send(RecordCacheUsage { usage }).await;
+    }
+}
+
+/// Reports a finished request back to the provider.
+///
+/// Feeds the circuit breaker and, when `cache_key` is set, stores a
+/// successful response in the response cache.
+async fn report_outcome(
+    provider: &ActorHandle,
+    cache_key: Option<ResponseCacheKey>,
+    outcome: Result<CachedResponse, LLMError>,
+) {
+    let error = match outcome {
+        Ok(response) => {
+            if let Some(key) = cache_key {
+                provider.send(StoreCachedResponse { key, response }).await;
+            }
+            None
+        }
+        Err(e) => Some(e),
+    };
+    provider.send(RecordRequestOutcome { error }).await;
+}
+
+/// Broadcasts a cached response as if it had just been received.
+///
+/// Streaming requests get the same start, token, tool call and end events
+/// as a live stream, with the whole text in a single token.
+async fn replay_cached_response(
+    broker: &ActorHandle,
+    request: &LLMRequest,
+    cached: CachedResponse,
+    streaming: bool,
+) {
+    let correlation_id = &request.correlation_id;
+
+    if streaming {
+        broker
+            .broadcast(LLMStreamStart {
+                correlation_id: correlation_id.clone(),
+            })
+            .await;
+        if !cached.content.is_empty() {
+            broker
+                .broadcast(LLMStreamToken {
+                    correlation_id: correlation_id.clone(),
+                    token: cached.content.clone(),
+                })
+                .await;
+        }
+        for tool_call in &cached.tool_calls {
+            broker
+                .broadcast(LLMStreamToolCall {
+                    correlation_id: correlation_id.clone(),
+                    tool_call: tool_call.clone(),
+                })
+                .await;
+        }
+        broker
+            .broadcast(LLMStreamEnd {
+                correlation_id: correlation_id.clone(),
+                stop_reason: cached.stop_reason,
+            })
+            .await;
+    }
+
+    broker
+        .broadcast(cached.to_response(correlation_id.clone()))
+        .await;
+}
+
+/// Merges provider-level sampling defaults with the request's overrides,
+/// including its `max_tokens` cap.
+fn request_sampling(config: &ProviderConfig, request: &LLMRequest) -> Option<SamplingParams> {
+    let overrides = match request.max_tokens {
+        Some(max_tokens) => Some(SamplingParams {
+            max_tokens: Some(max_tokens),
+            ..request.sampling.clone().unwrap_or_default()
+        }),
+        None => request.sampling.clone(),
+    };
+
+    let base = &config.sampling;
+    match overrides {
+        Some(overrides) => Some(base.merge_with(&overrides)),
+        None if !base.is_empty() => Some(base.clone()),
+        None => None,
+    }
+}
+
+/// Streams in flight and the ones asked to stop, shared with request tasks.
+#[derive(Debug, Clone, Default)]
+struct StreamCancellations {
+    inner: Arc<Mutex<CancellationState>>,
+    /// Signalled whenever a stream finishes
+    finished: Arc<Notify>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    active: HashSet<CorrelationId>,
+    cancelled: HashSet<CorrelationId>,
+}
+
+impl StreamCancellations {
+    /// Marks a stream as in flight.
+    fn start(&self, id: &CorrelationId) {
+        self.lock().active.insert(id.clone());
+    }
+
+    /// Asks an in-flight stream to stop. Returns false if it is not in flight.
+    fn cancel(&self, id: &CorrelationId) -> bool {
+        let mut state = self.lock();
+        state.active.contains(id) && state.cancelled.insert(id.clone())
+    }
+
+    /// Returns true if the stream was asked to stop.
+    fn is_cancelled(&self, id: &CorrelationId) -> bool {
+        self.lock().cancelled.contains(id)
+    }
+
+    /// Forgets a finished stream, returning whether it was cancelled.
+    fn finish(&self, id: &CorrelationId) -> bool {
+        let cancelled = {
+            let mut state = self.lock();
+            state.active.remove(id);
+            state.cancelled.remove(id)
+        };
+        self.finished.notify_waiters();
+        cancelled
+    }
+
+    /// Asks every in-flight stream to stop, returning how many were asked.
+    fn cancel_all(&self) -> usize {
+        let mut state = self.lock();
+        let active: Vec<CorrelationId> = state.active.iter().cloned().collect();
+        active
+            .into_iter()
+            .filter(|id| state.cancelled.insert(id.clone()))
+            .count()
+    }
+
+    /// Waits until no streams are in flight.
+    async fn wait_idle(&self) {
+        loop {
+            let finished = self.finished.notified();
+            if self.lock().active.is_empty() {
+                return;
+            }
+            finished.await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CancellationState> {
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
 /// Processes a streaming request using the unified LLMClient trait.
+///
+/// Returns the complete response, or the error that ended the request.
 async fn process_streaming_request(
     client: &Arc<dyn LLMClient>,
     request: &LLMRequest,
     broker: &ActorHandle,
     sampling: Option<&SamplingParams>,
-) {
+    cancellations: &StreamCancellations,
+) -> Result<CachedResponse, LLMError> {
     let correlation_id = &request.correlation_id;
     let provider_name = client.provider_name();
 
@@ -500,8 +1352,14 @@ async fn process_streaming_request(
             let mut accumulated_text = String::new();
             let mut tool_calls = Vec::new();
             let mut stop_reason = StopReason::EndTurn;
+            let mut usage: Option<TokenUsage> = None;
+            let mut error = None;
 
             while let Some(result) = stream.next().await {
+                if cancellations.is_cancelled(correlation_id) {
+                    stop_reason = StopReason::StopSequence;
+                    break;
+                }
                 match result {
                     Ok(event) => {
                         match event {
@@ -519,6 +1377,14 @@ async fn process_streaming_request(
                                     })
                                     .await;
                             }
+                            LLMStreamEvent::ThinkingToken { text } => {
+                                broker
+                                    .broadcast(LLMStreamThinkingToken {
+                                        correlation_id: correlation_id.clone(),
+                                        token: text,
+                                    })
+                                    .await;
+                            }
                             LLMStreamEvent::ToolCall { tool_call } => {
                                 // Broadcast tool call
                                 broker
@@ -530,6 +1396,16 @@ async fn process_streaming_request(
 
                                 tool_calls.push(tool_call);
                             }
+                            LLMStreamEvent::Usage(reported) => {
+                                broker
+                                    .broadcast(LLMStreamUsage {
+                                        correlation_id: correlation_id.clone(),
+                                        usage: reported,
+                                    })
+                                    .await;
+
+                                *usage.get_or_insert_with(TokenUsage::default) += reported;
+                            }
                             LLMStreamEvent::End {
                                 stop_reason: reason,
                             } => {
@@ -546,6 +1422,7 @@ async fn process_streaming_request(
                                     message = %message,
                                     "Stream error"
                                 );
+                                error = Some(LLMError::stream_error(message));
                                 break;
                             }
                         }
@@ -556,12 +1433,33 @@ async fn process_streaming_request(
                             error = %e,
                             "Stream error"
                         );
+                        error = Some(e);
                         break;
                     }
                 }
             }
 
-            // Send stream end
+            if let Some(usage) = usage {
+                tracing::info!(
+                    correlation_id = %correlation_id,
+                    provider = provider_name,
+                    input_tokens = usage.input_tokens,
+                    output_tokens = usage.output_tokens,
+                    cache_read_tokens = usage.cache_read_tokens,
+                    cache_write_tokens = usage.cache_write_tokens,
+                    "Stream token usage"
+                );
+            }
+
+            // Report the failure, then send stream end
+            if let Some(ref error) = error {
+                broker
+                    .broadcast(LLMStreamError {
+                        correlation_id: correlation_id.clone(),
+                        error: error.clone(),
+                    })
+                    .await;
+            }
             broker
                 .broadcast(LLMStreamEnd {
                     correlation_id: correlation_id.clone(),
@@ -570,18 +1468,16 @@ async fn process_streaming_request(
                 .await;
 
             // Also broadcast the complete response for non-streaming consumers
+            let response = CachedResponse {
+                content: accumulated_text,
+                tool_calls,
+                stop_reason,
+            };
             broker
-                .broadcast(LLMResponse {
-                    correlation_id: correlation_id.clone(),
-                    content: accumulated_text,
-                    tool_calls: if tool_calls.is_empty() {
-                        None
-                    } else {
-                        Some(tool_calls)
-                    },
-                    stop_reason,
-                })
+                .broadcast(response.to_response(correlation_id.clone()))
                 .await;
+
+            error.map_or(Ok(response), Err)
         }
         Err(e) => {
             tracing::error!(
@@ -601,23 +1497,33 @@ async fn process_streaming_request(
             }
 
             // Send stream end with error
+            broker
+                .broadcast(LLMStreamError {
+                    correlation_id: correlation_id.clone(),
+                    error: e.clone(),
+                })
+                .await;
             broker
                 .broadcast(LLMStreamEnd {
                     correlation_id: correlation_id.clone(),
                     stop_reason: StopReason::EndTurn,
                 })
                 .await;
+
+            Err(e)
         }
     }
 }
 
 /// Processes a non-streaming request using the unified LLMClient trait.
+///
+/// Returns the complete response, or the error that ended the request.
 async fn process_non_streaming_request(
     client: &Arc<dyn LLMClient>,
     request: &LLMRequest,
     broker: &ActorHandle,
     sampling: Option<&SamplingParams>,
-) {
+) -> Result<CachedResponse, LLMError> {
     let correlation_id = &request.correlation_id;
     let provider_name = client.provider_name();
 
@@ -629,18 +1535,15 @@ async fn process_non_streaming_request(
         .await
     {
         Ok(response) => {
+            let response = CachedResponse {
+                content: response.content,
+                tool_calls: response.tool_calls,
+                stop_reason: response.stop_reason,
+            };
             broker
-                .broadcast(LLMResponse {
-                    correlation_id: correlation_id.clone(),
-                    content: response.content,
-                    tool_calls: if response.tool_calls.is_empty() {
-                        None
-                    } else {
-                        Some(response.tool_calls)
-                    },
-                    stop_reason: response.stop_reason,
-                })
+                .broadcast(response.to_response(correlation_id.clone()))
                 .await;
+            Ok(response)
         }
         Err(e) => {
             tracing::error!(
@@ -658,6 +1561,7 @@ async fn process_non_streaming_request(
                     })
                     .await;
             }
+            Err(e)
         }
     }
 }
@@ -679,20 +1583,52 @@ mod tests {
         let state = RateLimiterState::default();
         let config = ProviderConfig::new("test-key");
 
-        assert!(state.can_make_request(&config));
+        assert!(state.can_make_request(&config.rate_limit));
     }
 
     #[test]
     fn rate_limiter_tracks_requests() {
         let mut state = RateLimiterState::default();
-        let _config = ProviderConfig::new("test-key");
+        let config = ProviderConfig::new("test-key");
 
-        state.record_request(100);
+        state.record_request(&config.rate_limit, 100);
 
         assert_eq!(state.requests_in_window, 1);
         assert_eq!(state.tokens_in_window, 100);
     }
 
+    #[test]
+    fn token_bucket_allows_bursts_then_waits_for_refill() {
+        let mut state = RateLimiterState::default();
+        let limit = RateLimitConfig::new(60, 40_000).token_bucket(3);
+
+        for _ in 0..3 {
+            assert!(state.can_make_request(&limit));
+            state.record_request(&limit, 10);
+        }
+
+        assert!(!state.can_make_request(&limit));
+        // One request per second at 60 requests per minute
+        let retry_after = state.retry_after(&limit);
+        assert!(retry_after > Duration::from_millis(900), "{retry_after:?}");
+        assert!(retry_after <= Duration::from_secs(1), "{retry_after:?}");
+    }
+
+    #[test]
+    fn token_bucket_refills_over_time() {
+        let limit = RateLimitConfig::new(60, 40_000).token_bucket(2);
+        let state = RateLimiterState {
+            bucket_level: Some(0.0),
+            bucket_updated_at: Some(Instant::now() - Duration::from_secs(5)),
+            ..RateLimiterState::default()
+        };
+
+        // Five seconds refill five tokens, capped at the burst capacity
+        assert!((state.bucket_tokens(60, 2) - 2.0).abs() < f64::EPSILON);
+        assert!(state.can_make_request(&limit));
+        assert_eq!(state.retry_after(&limit), Duration::ZERO);
+    }
+
     #[test]
     fn rate_limiter_blocks_when_limited() {
         let mut state = RateLimiterState::default();
@@ -700,7 +1636,7 @@ mod tests {
 
         state.record_rate_limit(Duration::from_secs(60));
 
-        assert!(!state.can_make_request(&config));
+        assert!(!state.can_make_request(&config.rate_limit));
     }
 
     #[test]
@@ -709,10 +1645,39 @@ mod tests {
         let config = ProviderConfig::new("test-key");
 
         state.record_rate_limit(Duration::from_secs(60));
-        assert!(!state.can_make_request(&config));
+        assert!(!state.can_make_request(&config.rate_limit));
 
         state.clear_rate_limit();
-        assert!(state.can_make_request(&config));
+        assert!(state.can_make_request(&config.rate_limit));
+    }
+
+    #[test]
+    fn effective_rate_limit_follows_adaptive_limiter() {
+        use crate::llm::adaptive_rate_limit::RateLimitSnapshot;
+
+        let mut provider = LLMProvider::default();
+        assert_eq!(
+            provider.current_effective_rate_limit(),
+            RateLimitConfig::default()
+        );
+
+        let rate_limit = RateLimitConfig::new(40, 40_000).adaptive(true);
+        provider.config = Some(ProviderConfig::new("test-key").with_rate_limit(rate_limit));
+        assert_eq!(provider.current_effective_rate_limit(), rate_limit);
+
+        let adaptive = Arc::new(Mutex::new(AdaptiveRateLimiter::new(rate_limit)));
+        lock_limiter(&adaptive).observe(&RateLimitSnapshot {
+            request_limit: Some(40),
+            remaining_requests: Some(2),
+            ..RateLimitSnapshot::default()
+        });
+        provider.adaptive = Some(adaptive);
+
+        assert_eq!(
+            provider.current_effective_rate_limit().requests_per_minute,
+            20
+        );
+        assert!(provider.can_make_request());
     }
 
     #[test]
@@ -726,6 +1691,7 @@ mod tests {
             messages: vec![Message::user("Hello world")], // 11 chars
             tools: None,
             sampling: None,
+            max_tokens: None,
         };
 
         let tokens = estimate_tokens(&request);
@@ -741,5 +1707,221 @@ mod tests {
         assert_eq!(metrics.requests_failed, 0);
         assert_eq!(metrics._rate_limits_hit, 0);
         assert_eq!(metrics._tokens_used, 0);
+        assert!(metrics.cache_usage().is_empty());
+    }
+
+    #[test]
+    fn request_max_tokens_overrides_config_sampling() {
+        use crate::types::AgentId;
+
+        let config = ProviderConfig::ollama("llama3.2")
+            .with_sampling(SamplingParams::default().with_temperature(0.2));
+        let mut request = LLMRequest {
+            correlation_id: CorrelationId::new(),
+            agent_id: AgentId::new(),
+            messages: vec![],
+            tools: None,
+            sampling: None,
+            max_tokens: Some(5),
+        };
+
+        let sampling = request_sampling(&config, &request).unwrap();
+        assert_eq!(sampling.max_tokens, Some(5));
+        assert_eq!(sampling.temperature, Some(0.2));
+        assert_eq!(config.max_tokens, 4096);
+
+        request.max_tokens = None;
+        assert_eq!(
+            request_sampling(&config, &request).unwrap().max_tokens,
+            None
+        );
+    }
+
+    #[test]
+    fn request_sampling_keeps_provider_temperature_when_request_has_none() {
+        use crate::types::AgentId;
+
+        let config = ProviderConfig::ollama("llama3.2")
+            .with_sampling(SamplingParams::default().with_temperature(0.7));
+        let request = LLMRequest {
+            correlation_id: CorrelationId::new(),
+            agent_id: AgentId::new(),
+            messages: vec![],
+            tools: None,
+            sampling: Some(SamplingParams::default().with_top_p(0.9)),
+            max_tokens: None,
+        };
+
+        let sampling = request_sampling(&config, &request).unwrap();
+        assert_eq!(sampling.temperature, Some(0.7));
+        assert_eq!(sampling.top_p, Some(0.9));
+    }
+
+    #[test]
+    fn stream_cancellation_only_applies_to_active_streams() {
+        let cancellations = StreamCancellations::default();
+        let id = CorrelationId::new();
+
+        assert!(!cancellations.cancel(&id));
+
+        cancellations.start(&id);
+        assert!(cancellations.cancel(&id));
+        assert!(cancellations.is_cancelled(&id));
+        assert!(cancellations.finish(&id));
+        assert!(!cancellations.is_cancelled(&id));
+    }
+
+    #[tokio::test]
+    async fn cancel_all_stops_active_streams_and_wakes_idle_waiters() {
+        let cancellations = StreamCancellations::default();
+        let first = CorrelationId::new();
+        let second = CorrelationId::new();
+        cancellations.start(&first);
+        cancellations.start(&second);
+
+        assert_eq!(cancellations.cancel_all(), 2);
+        assert_eq!(cancellations.cancel_all(), 0);
+
+        let waiter = cancellations.clone();
+        let idle = tokio::spawn(async move { waiter.wait_idle().await });
+        cancellations.finish(&first);
+        cancellations.finish(&second);
+        tokio::time::timeout(Duration::from_secs(1), idle)
+            .await
+            .expect("idle")
+            .expect("join");
+    }
+
+    #[tokio::test]
+    async fn drain_queue_returns_queued_requests() {
+        let mut runtime = ActonApp::launch_async().await;
+        // One request per minute: the first is sent, the rest are queued
+        let config = ProviderConfig::openai_compatible("http://127.0.0.1:9/v1", "test")
+            .with_rate_limit(RateLimitConfig::new(1, 1_000_000));
+        let provider =
+            LLMProvider::spawn_with_drain_timeout(&mut runtime, config, Duration::ZERO).await;
+
+        let queued = LLMRequest::simple("second");
+        provider.send(LLMRequest::simple("first")).await;
+        provider.send(queued.clone()).await;
+
+        let drained = LLMProvider::drain_queue(&mut runtime, &provider).await;
+        assert_eq!(drained.len(), 1);
+        assert_eq!(drained[0].correlation_id, queued.correlation_id);
+
+        // Draining twice finds nothing left
+        assert!(LLMProvider::drain_queue(&mut runtime, &provider)
+            .await
+            .is_empty());
+
+        runtime.shutdown_all().await.expect("shutdown");
+    }
+
+    #[tokio::test]
+    async fn requests_carry_system_prompt_affixes() {
+        let mut runtime = ActonApp::launch_async().await;
+        let config = ProviderConfig::openai_compatible("http://127.0.0.1:9/v1", "test")
+            .with_rate_limit(RateLimitConfig::new(1, 1_000_000))
+            .with_system_prompt_prefix("[policy] ")
+            .with_system_prompt_suffix(" [/policy]");
+        let provider =
+            LLMProvider::spawn_with_drain_timeout(&mut runtime, config, Duration::ZERO).await;
+
+        provider.send(LLMRequest::simple("first")).await;
+        provider
+            .send(LLMRequest::with_system("Be brief.", "second"))
+            .await;
+        provider.send(LLMRequest::simple("third")).await;
+
+        let drained = LLMProvider::drain_queue(&mut runtime, &provider).await;
+        assert_eq!(drained.len(), 2);
+        let system: Vec<_> = drained[0]
+            .messages
+            .iter()
+            .filter(|m| m.role == crate::messages::MessageRole::System)
+            .collect();
+        assert_eq!(system.len(), 1);
+        assert_eq!(system[0].content, "[policy] Be brief. [/policy]");
+        assert_eq!(drained[1].messages[0].content, "[policy]  [/policy]");
+        assert_eq!(drained[1].messages[1].content, "third");
+
+        runtime.shutdown_all().await.expect("shutdown");
+    }
+
+    #[tokio::test]
+    async fn queued_and_direct_requests_share_cache_entries() {
+        let config = ProviderConfig::openai_compatible("http://127.0.0.1:9/v1", "test")
+            .with_rate_limit(RateLimitConfig::new(1, 1_000_000))
+            .with_response_cache(crate::llm::response_cache::CacheConfig::new(8))
+            .with_system_prompt_prefix("[policy] ");
+        let request = LLMRequest::with_system("Be brief.", "question");
+
+        // Queue the request behind a first one and take it back wrapped
+        let mut runtime = ActonApp::launch_async().await;
+        let queueing =
+            LLMProvider::spawn_with_drain_timeout(&mut runtime, config.clone(), Duration::ZERO)
+                .await;
+        queueing.send(LLMRequest::simple("first")).await;
+        queueing.send(request.clone()).await;
+        let drained = LLMProvider::drain_queue(&mut runtime, &queueing).await;
+        assert_eq!(drained.len(), 1);
+
+        // Cache a response under the key the queue would store it with
+        let key = ResponseCacheKey::new(
+            &drained[0].messages,
+            drained[0].tools.as_deref(),
+            &config.model,
+            request_sampling(&config, &drained[0]).as_ref(),
+        );
+        let provider =
+            LLMProvider::spawn_with_drain_timeout(&mut runtime, config, Duration::ZERO).await;
+        provider
+            .send(StoreCachedResponse {
+                key,
+                response: CachedResponse {
+                    content: "cached".to_string(),
+                    tool_calls: Vec::new(),
+                    stop_reason: StopReason::EndTurn,
+                },
+            })
+            .await;
+
+        // The same request sent directly finds that entry
+        provider.send(request).await;
+        let report: Option<CacheStatsReport> = crate::memory::query_store(
+            &mut runtime,
+            &provider,
+            GetCacheStats,
+            Duration::from_secs(1),
+        )
+        .await;
+        let stats = report.and_then(|r| r.stats).expect("cache stats");
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 0);
+
+        runtime.shutdown_all().await.expect("shutdown");
+    }
+
+    #[tokio::test]
+    async fn rate_limit_updates_without_restart() {
+        let mut runtime = ActonApp::launch_async().await;
+        let config = ProviderConfig::openai_compatible("http://127.0.0.1:9/v1", "test")
+            .with_rate_limit(RateLimitConfig::new(1, 1_000_000));
+        let provider =
+            LLMProvider::spawn_with_drain_timeout(&mut runtime, config, Duration::ZERO).await;
+
+        let updated = RateLimitConfig::new(40, 50_000);
+        provider.send(UpdateRateLimit { config: updated }).await;
+
+        let reply: Option<EffectiveRateLimit> = crate::memory::query_store(
+            &mut runtime,
+            &provider,
+            GetEffectiveRateLimit,
+            Duration::from_secs(1),
+        )
+        .await;
+        assert_eq!(reply.map(|r| r.config), Some(updated));
+
+        runtime.shutdown_all().await.expect("shutdown");
     }
 }
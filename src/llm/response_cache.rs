@@ -0,0 +1,307 @@
+//! Opt-in caching of complete LLM responses.
+//!
+//! Tests and batch jobs often send the same prompt many times. With a
+//! [`CacheConfig`] set on the provider, a [`ResponseCache`] answers repeated
+//! requests from memory. Requests are identified by a SHA-256 of the
+//! messages, tool definitions, model and sampling parameters, so any change
+//! to the prompt is a miss. The least recently used entry is evicted once
+//! `max_entries` is reached, and entries older than the TTL are ignored.
+
+use crate::llm::config::SamplingParams;
+use crate::messages::{LLMResponse, Message, StopReason, ToolCall, ToolDefinition};
+use crate::types::CorrelationId;
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Response cache configuration for a provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Maximum number of cached responses
+    pub max_entries: usize,
+    /// How long a cached response stays valid; `None` keeps it until evicted
+    pub ttl: Option<Duration>,
+}
+
+impl CacheConfig {
+    /// Creates a cache configuration holding up to `max_entries` responses
+    /// with no TTL.
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            ttl: None,
+        }
+    }
+
+    /// Sets how long a cached response stays valid.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self::new(128)
+    }
+}
+
+/// Counters of a [`ResponseCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests answered from the cache
+    pub hits: u64,
+    /// Requests that had to call the API
+    pub misses: u64,
+    /// Entries dropped to stay within `max_entries`
+    pub evictions: u64,
+}
+
+/// A response as stored in the cache, without its correlation ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    /// The generated content
+    pub content: String,
+    /// Tool calls requested by the LLM
+    pub tool_calls: Vec<ToolCall>,
+    /// The reason the model stopped generating
+    pub stop_reason: StopReason,
+}
+
+impl CachedResponse {
+    /// Builds the [`LLMResponse`] answering `correlation_id`.
+    #[must_use]
+    pub fn to_response(&self, correlation_id: CorrelationId) -> LLMResponse {
+        LLMResponse {
+            correlation_id,
+            content: self.content.clone(),
+            tool_calls: if self.tool_calls.is_empty() {
+                None
+            } else {
+                Some(self.tool_calls.clone())
+            },
+            stop_reason: self.stop_reason,
+        }
+    }
+}
+
+/// Identifies a request in a [`ResponseCache`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResponseCacheKey([u8; 32]);
+
+impl ResponseCacheKey {
+    /// Hashes everything that determines a request's response.
+    #[must_use]
+    pub fn new(
+        messages: &[Message],
+        tools: Option<&[ToolDefinition]>,
+        model: &str,
+        sampling: Option<&SamplingParams>,
+    ) -> Self {
+        // When a message was sent does not change the response
+        let messages: Vec<Message> = messages
+            .iter()
+            .map(|message| Message {
+                timestamp: None,
+                ..message.clone()
+            })
+            .collect();
+        let input = serde_json::json!({
+            "messages": messages,
+            "tools": tools,
+            "model": model,
+            "sampling": sampling,
+        });
+        let hash = digest(&SHA256, input.to_string().as_bytes());
+        let mut key = [0; 32];
+        key.copy_from_slice(hash.as_ref());
+        Self(key)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    response: CachedResponse,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+/// LRU cache of complete responses.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    config: CacheConfig,
+    entries: HashMap<ResponseCacheKey, CacheEntry>,
+    /// Incremented on every access to order entries by recency
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl ResponseCache {
+    /// Creates an empty cache.
+    #[must_use]
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Returns the hit, miss and eviction counts.
+    #[must_use]
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Returns the number of cached responses, including expired ones not
+    /// yet looked up.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if nothing is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Looks up `key`, counting a hit or a miss.
+    ///
+    /// An expired entry is removed and counts as a miss.
+    pub fn get(&mut self, key: &ResponseCacheKey) -> Option<CachedResponse> {
+        self.clock += 1;
+        let expired = match self.entries.get_mut(key) {
+            Some(entry) if !is_expired(entry, self.config.ttl) => {
+                entry.last_used = self.clock;
+                self.stats.hits += 1;
+                return Some(entry.response.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            self.entries.remove(key);
+        }
+        self.stats.misses += 1;
+        None
+    }
+
+    /// Stores `response` under `key`, evicting the least recently used entry
+    /// if the cache is full.
+    pub fn insert(&mut self, key: ResponseCacheKey, response: CachedResponse) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.config.max_entries {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+        }
+
+        self.clock += 1;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                response,
+                stored_at: Instant::now(),
+                last_used: self.clock,
+            },
+        );
+    }
+}
+
+fn is_expired(entry: &CacheEntry, ttl: Option<Duration>) -> bool {
+    ttl.is_some_and(|ttl| entry.stored_at.elapsed() >= ttl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(text: &str) -> ResponseCacheKey {
+        ResponseCacheKey::new(&[Message::user(text)], None, "model", None)
+    }
+
+    fn response(text: &str) -> CachedResponse {
+        CachedResponse {
+            content: text.to_string(),
+            tool_calls: Vec::new(),
+            stop_reason: StopReason::EndTurn,
+        }
+    }
+
+    #[test]
+    fn key_covers_every_input() {
+        let messages = [Message::user("hi")];
+        let base = ResponseCacheKey::new(&messages, None, "a", None);
+
+        assert_eq!(base, ResponseCacheKey::new(&messages, None, "a", None));
+        assert_ne!(base, ResponseCacheKey::new(&messages, None, "b", None));
+        assert_ne!(base, key("bye"));
+
+        let sampling = SamplingParams {
+            temperature: Some(0.2),
+            ..SamplingParams::default()
+        };
+        assert_ne!(
+            base,
+            ResponseCacheKey::new(&messages, None, "a", Some(&sampling))
+        );
+    }
+
+    #[test]
+    fn key_ignores_message_timestamps() {
+        let stamped = [Message::user("hi").with_timestamp(std::time::SystemTime::now())];
+        assert_eq!(
+            ResponseCacheKey::new(&stamped, None, "a", None),
+            ResponseCacheKey::new(&[Message::user("hi")], None, "a", None)
+        );
+    }
+
+    #[test]
+    fn get_counts_hits_and_misses() {
+        let mut cache = ResponseCache::new(CacheConfig::default());
+        assert!(cache.get(&key("q")).is_none());
+
+        cache.insert(key("q"), response("a"));
+        assert_eq!(cache.get(&key("q")), Some(response("a")));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 1, 0));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = ResponseCache::new(CacheConfig::new(2));
+        cache.insert(key("1"), response("1"));
+        cache.insert(key("2"), response("2"));
+        cache.get(&key("1"));
+
+        cache.insert(key("3"), response("3"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key("2")).is_none());
+        assert!(cache.get(&key("1")).is_some());
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn expired_entries_miss() {
+        let mut cache = ResponseCache::new(CacheConfig::new(4).with_ttl(Duration::ZERO));
+        cache.insert(key("q"), response("a"));
+
+        assert!(cache.get(&key("q")).is_none());
+        assert!(cache.is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Types for accumulating streaming tokens and managing active streams.
 
+use crate::llm::usage::TokenUsage;
 use crate::messages::{StopReason, ToolCall};
 use crate::types::CorrelationId;
 use std::collections::HashMap;
@@ -19,6 +20,8 @@ pub struct ActiveStream {
     pub started: bool,
     /// The stop reason when stream ends
     pub stop_reason: Option<StopReason>,
+    /// Token usage reported for the stream, if any
+    pub usage: Option<TokenUsage>,
 }
 
 impl ActiveStream {
@@ -31,6 +34,7 @@ impl ActiveStream {
             tool_calls: Vec::new(),
             started: false,
             stop_reason: None,
+            usage: None,
         }
     }
 
@@ -49,6 +53,11 @@ impl ActiveStream {
         self.tool_calls.push(tool_call);
     }
 
+    /// Adds reported token usage to the stream's total.
+    pub fn add_usage(&mut self, usage: TokenUsage) {
+        *self.usage.get_or_insert_with(TokenUsage::default) += usage;
+    }
+
     /// Marks the stream as ended with the given stop reason.
     pub fn mark_ended(&mut self, stop_reason: StopReason) {
         self.stop_reason = Some(stop_reason);
@@ -122,6 +131,13 @@ impl StreamAccumulator {
         }
     }
 
+    /// Adds reported token usage to the specified stream.
+    pub fn add_usage(&mut self, correlation_id: &CorrelationId, usage: TokenUsage) {
+        if let Some(stream) = self.get_stream_mut(correlation_id) {
+            stream.add_usage(usage);
+        }
+    }
+
     /// Ends a stream and returns it.
     pub fn end_stream(
         &mut self,
@@ -175,6 +191,7 @@ mod tests {
         assert!(stream.tool_calls.is_empty());
         assert!(!stream.started);
         assert!(stream.stop_reason.is_none());
+        assert!(stream.usage.is_none());
     }
 
     #[test]
@@ -339,6 +356,23 @@ mod tests {
         assert_eq!(stream.tool_calls[0].name, "calculator");
     }
 
+    #[test]
+    fn stream_accumulator_add_usage() {
+        let mut accumulator = StreamAccumulator::new();
+        let corr_id = CorrelationId::new();
+
+        accumulator.start_stream(&corr_id);
+        accumulator.append_token(&corr_id, "Hi");
+        accumulator.add_usage(&corr_id, TokenUsage::new(12, 3));
+        accumulator.add_usage(&corr_id, TokenUsage::new(0, 2));
+
+        let stream = accumulator
+            .end_stream(&corr_id, StopReason::EndTurn)
+            .unwrap();
+        assert_eq!(stream.content, "Hi");
+        assert_eq!(stream.usage, Some(TokenUsage::new(12, 5)));
+    }
+
     // Import ToolCall for tests
     use crate::messages::ToolCall;
 }
@@ -0,0 +1,103 @@
+//! Token usage reported by LLM providers.
+//!
+//! Providers report how many tokens a response consumed at the end of its
+//! stream. [`TokenUsage`] carries those counts through the streaming
+//! pipeline so callers can track cost per response, per conversation, or
+//! across the whole runtime.
+
+use serde::{Deserialize, Serialize};
+use std::ops::AddAssign;
+
+/// Token counts of a single LLM response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    /// Input tokens billed for the request
+    pub input_tokens: u64,
+    /// Output tokens generated for the response
+    pub output_tokens: u64,
+    /// Input tokens served from the prompt cache, if the provider reports them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_tokens: Option<u64>,
+    /// Input tokens written to the prompt cache, if the provider reports them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_tokens: Option<u64>,
+}
+
+impl TokenUsage {
+    /// Creates a usage record with input and output counts only.
+    #[must_use]
+    pub fn new(input_tokens: u64, output_tokens: u64) -> Self {
+        Self {
+            input_tokens,
+            output_tokens,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the number of input tokens served from the prompt cache.
+    #[must_use]
+    pub fn with_cache_read_tokens(mut self, tokens: u64) -> Self {
+        self.cache_read_tokens = Some(tokens);
+        self
+    }
+
+    /// Sets the number of input tokens written to the prompt cache.
+    #[must_use]
+    pub fn with_cache_write_tokens(mut self, tokens: u64) -> Self {
+        self.cache_write_tokens = Some(tokens);
+        self
+    }
+
+    /// Returns the sum of input and output tokens.
+    #[must_use]
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens.saturating_add(self.output_tokens)
+    }
+}
+
+impl AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input_tokens = self.input_tokens.saturating_add(other.input_tokens);
+        self.output_tokens = self.output_tokens.saturating_add(other.output_tokens);
+        self.cache_read_tokens = add_optional(self.cache_read_tokens, other.cache_read_tokens);
+        self.cache_write_tokens = add_optional(self.cache_write_tokens, other.cache_write_tokens);
+    }
+}
+
+/// Adds two optional counts, treating a missing count as zero unless both are missing.
+fn add_optional(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0).saturating_add(b.unwrap_or(0))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_tokens_sums_input_and_output() {
+        assert_eq!(TokenUsage::new(120, 30).total_tokens(), 150);
+    }
+
+    #[test]
+    fn add_assign_accumulates_counts() {
+        let mut usage = TokenUsage::new(100, 20);
+        usage += TokenUsage::new(50, 10).with_cache_read_tokens(40);
+
+        assert_eq!(usage.input_tokens, 150);
+        assert_eq!(usage.output_tokens, 30);
+        assert_eq!(usage.cache_read_tokens, Some(40));
+        assert_eq!(usage.cache_write_tokens, None);
+    }
+
+    #[test]
+    fn serde_omits_missing_cache_counts() {
+        let json = serde_json::to_value(TokenUsage::new(1, 2)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"input_tokens": 1, "output_tokens": 2})
+        );
+    }
+}
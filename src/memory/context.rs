@@ -28,6 +28,7 @@
 //! let fitted = window.fit_messages(&messages);
 //! ```
 
+use crate::error::ActonAIError;
 use crate::memory::Memory;
 use crate::messages::{Message, MessageRole};
 use std::sync::Arc;
@@ -54,6 +55,19 @@ pub enum TruncationStrategy {
     ///
     /// Best when both initial context and final state matter.
     KeepEnds,
+
+    /// Keep the system message + a window that advances in fixed steps.
+    ///
+    /// When the window fills, it advances by `stride` messages and keeps the
+    /// last `overlap` messages of the previous window for coherence. The
+    /// kept history only changes once per `stride` messages, which suits
+    /// very long conversations. `overlap` must be less than `stride`.
+    SlidingWindow {
+        /// Messages the window advances by each time it fills
+        stride: usize,
+        /// Messages of the previous window kept after advancing
+        overlap: usize,
+    },
 }
 
 // =============================================================================
@@ -79,8 +93,42 @@ pub struct ContextWindowConfig {
     /// - 0.33 for code (~3 chars per token)
     /// - 0.5 for non-Latin scripts (~2 chars per token)
     pub tokens_per_char: f32,
+
+    /// Share of a memory's search score taken by cosine similarity, from 0.0
+    /// to 1.0; the rest comes from the memory's importance.
+    ///
+    /// The default of 1.0 ranks purely by similarity.
+    pub importance_weight: f32,
+
+    /// Cosine similarity at or above which a retrieved memory is dropped as a
+    /// duplicate of a higher-ranked one before injection. `None` keeps all.
+    pub dedup_threshold: Option<f32>,
+
+    /// Share of a hybrid search score taken by cosine similarity, from 0.0
+    /// to 1.0; the rest comes from full-text (BM25) relevance.
+    ///
+    /// Used when the context window is built from a query text as well as
+    /// a query embedding.
+    pub hybrid_search_alpha: f32,
+
+    /// Whether [`ContextWindow::new`] counts tokens with the tiktoken
+    /// tokenizer for [`model_name`](Self::model_name) instead of the
+    /// `tokens_per_char` heuristic.
+    ///
+    /// Exact counting costs a tokenizer pass per message; leave it off
+    /// where speed matters more than accuracy. Models without a known
+    /// encoding fall back to the heuristic.
+    pub accurate_token_count: bool,
+
+    /// Model whose tokenizer is used for accurate counting
+    /// ([`DEFAULT_TOKENIZER_MODEL`] if `None`).
+    pub model_name: Option<String>,
 }
 
+/// Model whose tokenizer (`cl100k_base`) counts tokens when
+/// [`ContextWindowConfig::model_name`] is unset.
+pub const DEFAULT_TOKENIZER_MODEL: &str = "gpt-4";
+
 impl Default for ContextWindowConfig {
     fn default() -> Self {
         Self {
@@ -88,6 +136,11 @@ impl Default for ContextWindowConfig {
             truncation_strategy: TruncationStrategy::KeepSystemAndRecent,
             reserved_for_response: 1024,
             tokens_per_char: 0.25, // ~4 chars per token average for English
+            importance_weight: 1.0,
+            dedup_threshold: None,
+            hybrid_search_alpha: 0.7,
+            accurate_token_count: false,
+            model_name: None,
         }
     }
 }
@@ -124,6 +177,77 @@ impl ContextWindowConfig {
         self.tokens_per_char = ratio;
         self
     }
+
+    /// Sets the importance weight, clamped to 0.0..=1.0.
+    #[must_use]
+    pub fn with_importance_weight(mut self, weight: f32) -> Self {
+        self.importance_weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Drops retrieved memories that are near-duplicates of one already
+    /// included.
+    #[must_use]
+    pub fn with_dedup_threshold(mut self, threshold: f32) -> Self {
+        self.dedup_threshold = Some(threshold);
+        self
+    }
+
+    /// Validates the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if a
+    /// [`TruncationStrategy::SlidingWindow`] has a zero `stride` or an
+    /// `overlap` not less than its `stride`.
+    pub fn validate(&self) -> Result<(), ActonAIError> {
+        if let TruncationStrategy::SlidingWindow { stride, overlap } = self.truncation_strategy {
+            if stride == 0 {
+                return Err(ActonAIError::configuration(
+                    "truncation_strategy",
+                    "sliding window stride must be greater than zero",
+                ));
+            }
+            if overlap >= stride {
+                return Err(ActonAIError::configuration(
+                    "truncation_strategy",
+                    format!(
+                        "sliding window overlap ({overlap}) must be less than stride ({stride})"
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the hybrid search alpha, clamped to 0.0..=1.0.
+    #[must_use]
+    pub fn with_hybrid_search_alpha(mut self, alpha: f32) -> Self {
+        self.hybrid_search_alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets whether tokens are counted with the model's tokenizer.
+    #[must_use]
+    pub fn with_accurate_token_count(mut self, accurate: bool) -> Self {
+        self.accurate_token_count = accurate;
+        self
+    }
+
+    /// Sets the model whose tokenizer is used for accurate counting.
+    #[must_use]
+    pub fn with_model_name(mut self, model: impl Into<String>) -> Self {
+        self.model_name = Some(model.into());
+        self
+    }
+
+    /// Returns the model whose tokenizer is used for accurate counting.
+    #[must_use]
+    pub fn tokenizer_model(&self) -> &str {
+        self.model_name
+            .as_deref()
+            .unwrap_or(DEFAULT_TOKENIZER_MODEL)
+    }
 }
 
 // =============================================================================
@@ -144,6 +268,10 @@ pub trait TokenEstimator: std::fmt::Debug + Send + Sync {
     fn estimate_string(&self, text: &str) -> usize;
     /// Short identifier for logging and diagnostics (e.g., "char-ratio", "cl100k_base").
     fn name(&self) -> &'static str;
+    /// Whether counts come from a real tokenizer rather than a heuristic.
+    fn is_exact(&self) -> bool {
+        false
+    }
 }
 
 /// Character-ratio token estimator. Approximates tokens as
@@ -170,8 +298,7 @@ impl Default for CharRatioEstimator {
 
 impl TokenEstimator for CharRatioEstimator {
     fn estimate_message(&self, message: &Message) -> usize {
-        let content_tokens =
-            (message.content.len() as f32 * self.tokens_per_char).ceil() as usize;
+        let content_tokens = (message.content.len() as f32 * self.tokens_per_char).ceil() as usize;
         content_tokens + ROLE_OVERHEAD_TOKENS
     }
 
@@ -218,6 +345,16 @@ impl TiktokenEstimator {
         }
     }
 
+    /// Resolves the tokenizer for the given model, or `None` if tiktoken
+    /// has no encoding for it.
+    #[must_use]
+    pub fn try_for_model(model: &str) -> Option<Self> {
+        tiktoken_rs::bpe_for_model(model).ok().map(|bpe| Self {
+            bpe,
+            encoding_name: resolve_encoding_name(model),
+        })
+    }
+
     /// Returns the name of the selected encoding.
     #[must_use]
     pub fn encoding_name(&self) -> &'static str {
@@ -237,6 +374,10 @@ impl TokenEstimator for TiktokenEstimator {
     fn name(&self) -> &'static str {
         self.encoding_name
     }
+
+    fn is_exact(&self) -> bool {
+        true
+    }
 }
 
 /// Best-effort mapping from model name to encoding name. Only used for
@@ -274,15 +415,50 @@ pub struct ContextWindow {
 }
 
 impl ContextWindow {
-    /// Creates a new context window manager with a char-ratio estimator
-    /// derived from `config.tokens_per_char`.
+    /// Creates a new context window manager.
+    ///
+    /// Counts tokens with a [`TiktokenEstimator`] for the config's model
+    /// when `accurate_token_count` is set and the model has a known
+    /// encoding, and otherwise with a char-ratio estimator derived from
+    /// `config.tokens_per_char`.
     #[must_use]
     pub fn new(config: ContextWindowConfig) -> Self {
-        let estimator: Arc<dyn TokenEstimator> =
-            Arc::new(CharRatioEstimator::new(config.tokens_per_char));
+        let accurate = if config.accurate_token_count {
+            let model = config.tokenizer_model();
+            let estimator = TiktokenEstimator::try_for_model(model);
+            if estimator.is_none() {
+                tracing::debug!(
+                    model,
+                    "No tokenizer for model; estimating tokens from characters"
+                );
+            }
+            estimator
+        } else {
+            None
+        };
+        let estimator: Arc<dyn TokenEstimator> = match accurate {
+            Some(tiktoken) => Arc::new(tiktoken),
+            None => Arc::new(CharRatioEstimator::new(config.tokens_per_char)),
+        };
         Self { config, estimator }
     }
 
+    /// Creates a context window with the default config and a
+    /// [`TruncationStrategy::SlidingWindow`] strategy.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if `overlap` is not less than `stride`.
+    pub fn sliding_window_with_overlap(
+        stride: usize,
+        overlap: usize,
+    ) -> Result<Self, ActonAIError> {
+        let config = ContextWindowConfig::default()
+            .with_strategy(TruncationStrategy::SlidingWindow { stride, overlap });
+        config.validate()?;
+        Ok(Self::new(config))
+    }
+
     /// Replaces the token estimator. Use to swap in
     /// [`TiktokenEstimator`] for model-accurate counts.
     #[must_use]
@@ -322,6 +498,24 @@ impl ContextWindow {
         messages.iter().map(|m| self.estimate_tokens(m)).sum()
     }
 
+    /// Counts the tokens in `messages`, including role framing, with the
+    /// tiktoken tokenizer for the config's model.
+    ///
+    /// Uses the tokenizer whatever the configured estimator; models
+    /// without a known encoding are counted with `cl100k_base`.
+    #[must_use]
+    pub fn token_count_accurate(&self, messages: &[Message]) -> usize {
+        let tokenizer = TiktokenEstimator::for_model(self.config.tokenizer_model());
+        messages.iter().map(|m| tokenizer.estimate_message(m)).sum()
+    }
+
+    /// Returns whether token counts are heuristic estimates rather than
+    /// tokenizer counts.
+    #[must_use]
+    pub fn is_estimated(&self) -> bool {
+        !self.estimator.is_exact()
+    }
+
     /// Returns the available tokens for context (after reserving for response).
     #[must_use]
     pub fn available_tokens(&self) -> usize {
@@ -357,6 +551,28 @@ impl ContextWindow {
                 self.truncate_keep_system_and_recent(messages, available)
             }
             TruncationStrategy::KeepEnds => self.truncate_keep_ends(messages, available),
+            TruncationStrategy::SlidingWindow { stride, overlap } => {
+                self.truncate_sliding_window(messages, available, stride, overlap)
+            }
+        }
+    }
+
+    /// Returns how many non-system messages a
+    /// [`TruncationStrategy::SlidingWindow`] has slid past for `messages`.
+    ///
+    /// Always 0 for the other strategies or when `messages` fit.
+    #[must_use]
+    pub fn window_position(&self, messages: &[Message]) -> usize {
+        let available = self.available_tokens();
+        match self.config.truncation_strategy {
+            TruncationStrategy::SlidingWindow { stride, overlap }
+                if self.estimate_total_tokens(messages) > available =>
+            {
+                let (system, rest) = split_system(messages);
+                let budget = available.saturating_sub(self.system_tokens(system));
+                self.sliding_window_start(rest, budget, stride, overlap)
+            }
+            _ => 0,
         }
     }
 
@@ -420,6 +636,57 @@ impl ContextWindow {
         result
     }
 
+    /// Truncation strategy: keep system message + a sliding window.
+    fn truncate_sliding_window(
+        &self,
+        messages: &[Message],
+        available: usize,
+        stride: usize,
+        overlap: usize,
+    ) -> Vec<Message> {
+        let (system, rest) = split_system(messages);
+        let system_tokens = self.system_tokens(system);
+        let mut result = Vec::new();
+        if let Some(system) = system.filter(|_| system_tokens <= available) {
+            result.push(system.clone());
+        }
+
+        let budget = available.saturating_sub(system_tokens);
+        let start = self.sliding_window_start(rest, budget, stride, overlap);
+        // A window still over budget is trimmed like KeepRecent
+        result.extend(self.truncate_keep_recent(&rest[start..], budget));
+        result
+    }
+
+    /// Finds where the sliding window starts in `rest`: the first window
+    /// boundary (a multiple of `stride`, less `overlap`) after which `rest`
+    /// fits in `budget`.
+    fn sliding_window_start(
+        &self,
+        rest: &[Message],
+        budget: usize,
+        stride: usize,
+        overlap: usize,
+    ) -> usize {
+        let stride = stride.max(1);
+        let mut start = 0;
+        let mut boundary = stride;
+        while self.estimate_total_tokens(&rest[start..]) > budget {
+            let next = boundary.saturating_sub(overlap).max(start + 1);
+            if next >= rest.len() {
+                break;
+            }
+            start = next;
+            boundary += stride;
+        }
+        start
+    }
+
+    /// Returns the tokens taken by the system message, if any.
+    fn system_tokens(&self, system: Option<&Message>) -> usize {
+        system.map_or(0, |m| self.estimate_tokens(m))
+    }
+
     /// Truncation strategy: keep first and last messages.
     fn truncate_keep_ends(&self, messages: &[Message], available: usize) -> Vec<Message> {
         if messages.is_empty() {
@@ -492,7 +759,11 @@ impl ContextWindow {
         let mut messages = Vec::new();
 
         // Build system message with memories
-        let system_content = build_system_with_memories(system_prompt, memories);
+        let memories = match self.config.dedup_threshold {
+            Some(threshold) => dedup_memories(memories, threshold),
+            None => memories.iter().collect(),
+        };
+        let system_content = build_system_with_memories(system_prompt, &memories);
         messages.push(Message::system(system_content));
 
         // Add conversation messages (skip any system messages as we built our own)
@@ -523,6 +794,8 @@ impl ContextWindow {
                 0.0
             },
             is_truncated: total_tokens > available,
+            window_position: self.window_position(messages),
+            is_estimated: self.is_estimated(),
         }
     }
 }
@@ -550,14 +823,28 @@ pub struct ContextStats {
     pub utilization_percent: f64,
     /// Whether the context was truncated.
     pub is_truncated: bool,
+    /// Non-system messages a [`TruncationStrategy::SlidingWindow`] has slid
+    /// past (see [`ContextWindow::window_position`]).
+    pub window_position: usize,
+    /// Whether `estimated_tokens` is a heuristic estimate rather than a
+    /// tokenizer count.
+    pub is_estimated: bool,
 }
 
 // =============================================================================
 // Helper Functions
 // =============================================================================
 
+/// Splits off the leading system message, if any.
+fn split_system(messages: &[Message]) -> (Option<&Message>, &[Message]) {
+    match messages.split_first() {
+        Some((first, rest)) if first.role == MessageRole::System => (Some(first), rest),
+        _ => (None, messages),
+    }
+}
+
 /// Builds a system prompt with injected memories.
-fn build_system_with_memories(system_prompt: &str, memories: &[Memory]) -> String {
+pub(crate) fn build_system_with_memories(system_prompt: &str, memories: &[&Memory]) -> String {
     if memories.is_empty() {
         return system_prompt.to_string();
     }
@@ -572,6 +859,27 @@ fn build_system_with_memories(system_prompt: &str, memories: &[Memory]) -> Strin
     content
 }
 
+/// Keeps each memory unless its embedding is at least `threshold` similar
+/// to one already kept. Memories without embeddings are always kept.
+fn dedup_memories(memories: &[Memory], threshold: f32) -> Vec<&Memory> {
+    let mut kept: Vec<&Memory> = Vec::with_capacity(memories.len());
+    for memory in memories {
+        let duplicate = memory.embedding.as_ref().is_some_and(|embedding| {
+            kept.iter()
+                .filter_map(|k| k.embedding.as_ref())
+                .any(|other| {
+                    embedding
+                        .cosine_similarity(other)
+                        .is_ok_and(|similarity| similarity >= threshold)
+                })
+        });
+        if !duplicate {
+            kept.push(memory);
+        }
+    }
+    kept
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -588,6 +896,8 @@ mod tests {
             content: content.to_string(),
             tool_calls: None,
             tool_call_id: None,
+            timestamp: None,
+            is_injected: false,
         }
     }
 
@@ -604,6 +914,30 @@ mod tests {
             TruncationStrategy::KeepSystemAndRecent
         );
         assert_eq!(config.reserved_for_response, 1024);
+        assert_eq!(config.importance_weight, 1.0);
+        assert!((config.hybrid_search_alpha - 0.7).abs() < 0.001);
+    }
+
+    #[test]
+    fn config_hybrid_search_alpha_is_clamped() {
+        assert_eq!(
+            ContextWindowConfig::default()
+                .with_hybrid_search_alpha(-0.5)
+                .hybrid_search_alpha,
+            0.0
+        );
+    }
+
+    #[test]
+    fn config_importance_weight_is_clamped() {
+        let config = ContextWindowConfig::default().with_importance_weight(0.7);
+        assert!((config.importance_weight - 0.7).abs() < 0.001);
+        assert_eq!(
+            ContextWindowConfig::default()
+                .with_importance_weight(1.5)
+                .importance_weight,
+            1.0
+        );
     }
 
     #[test]
@@ -691,6 +1025,11 @@ mod tests {
             truncation_strategy: TruncationStrategy::KeepRecent,
             reserved_for_response: 20,
             tokens_per_char: 0.25,
+            importance_weight: 1.0,
+            dedup_threshold: None,
+            hybrid_search_alpha: 0.7,
+            accurate_token_count: false,
+            model_name: None,
         };
         let window = ContextWindow::new(config);
 
@@ -717,6 +1056,11 @@ mod tests {
             truncation_strategy: TruncationStrategy::KeepSystemAndRecent,
             reserved_for_response: 20,
             tokens_per_char: 0.25,
+            importance_weight: 1.0,
+            dedup_threshold: None,
+            hybrid_search_alpha: 0.7,
+            accurate_token_count: false,
+            model_name: None,
         };
         let window = ContextWindow::new(config);
 
@@ -741,6 +1085,11 @@ mod tests {
             truncation_strategy: TruncationStrategy::KeepEnds,
             reserved_for_response: 10,
             tokens_per_char: 0.25,
+            importance_weight: 1.0,
+            dedup_threshold: None,
+            hybrid_search_alpha: 0.7,
+            accurate_token_count: false,
+            model_name: None,
         };
         let window = ContextWindow::new(config);
 
@@ -763,6 +1112,83 @@ mod tests {
         assert_eq!(fitted[1].content, "end");
     }
 
+    /// A window with room for the system message plus `fit` conversation
+    /// messages, all `"m00"`-style.
+    fn sliding_window(fit: usize, stride: usize, overlap: usize) -> ContextWindow {
+        let probe = ContextWindow::default();
+        let system = probe.estimate_tokens(&msg(MessageRole::System, "Be brief."));
+        let each = probe.estimate_tokens(&msg(MessageRole::User, "m00"));
+        let config = ContextWindowConfig::with_max_tokens(system + fit * each + 100)
+            .with_reserved_for_response(100)
+            .with_strategy(TruncationStrategy::SlidingWindow { stride, overlap });
+        config.validate().unwrap();
+        ContextWindow::new(config)
+    }
+
+    fn long_conversation(len: usize) -> Vec<Message> {
+        std::iter::once(msg(MessageRole::System, "Be brief."))
+            .chain((0..len).map(|i| {
+                let role = if i % 2 == 0 {
+                    MessageRole::User
+                } else {
+                    MessageRole::Assistant
+                };
+                msg(role, &format!("m{i:02}"))
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn fit_messages_sliding_window() {
+        let window = sliding_window(30, 10, 5);
+
+        // 100 messages need the window to start at 75 (8 strides less 5)
+        let fitted = window.fit_messages(&long_conversation(100));
+        assert_eq!(fitted.len(), 26);
+        assert_eq!(fitted[0].content, "Be brief.");
+        assert_eq!(fitted[1].content, "m75");
+        assert_eq!(fitted[25].content, "m99");
+        assert_eq!(
+            window
+                .get_context_stats(&long_conversation(100))
+                .window_position,
+            75
+        );
+
+        // The window stays put until it fills again
+        let fitted = window.fit_messages(&long_conversation(105));
+        assert_eq!(fitted[0].content, "Be brief.");
+        assert_eq!(fitted[1].content, "m75");
+        assert_eq!(fitted.len(), 31);
+
+        // Then advances a full stride, keeping 5 messages of the last window
+        let fitted = window.fit_messages(&long_conversation(106));
+        assert_eq!(fitted[0].content, "Be brief.");
+        assert_eq!(fitted[1].content, "m85");
+        assert_eq!(window.window_position(&long_conversation(106)), 85);
+    }
+
+    #[test]
+    fn sliding_window_trims_a_window_that_still_does_not_fit() {
+        let window = sliding_window(3, 10, 5);
+
+        let fitted = window.fit_messages(&long_conversation(12));
+        let contents: Vec<&str> = fitted.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["Be brief.", "m09", "m10", "m11"]);
+    }
+
+    #[test]
+    fn sliding_window_requires_overlap_below_stride() {
+        assert!(ContextWindow::sliding_window_with_overlap(10, 5).is_ok());
+        let err = ContextWindow::sliding_window_with_overlap(10, 10).unwrap_err();
+        assert!(err.is_configuration());
+        assert!(ContextWindow::sliding_window_with_overlap(0, 0).is_err());
+        assert_eq!(
+            ContextWindow::default().window_position(&long_conversation(100)),
+            0
+        );
+    }
+
     #[test]
     fn fit_messages_empty() {
         let window = ContextWindow::default();
@@ -806,6 +1232,31 @@ mod tests {
         assert!(context[0].content.contains("User is from Seattle"));
     }
 
+    #[test]
+    fn build_context_drops_duplicate_memories_above_threshold() {
+        use crate::memory::Embedding;
+
+        let agent_id = AgentId::new();
+        let embedded = |content: &str, values: Vec<f32>| {
+            Memory::with_embedding(agent_id.clone(), content, Embedding::new(values).unwrap())
+        };
+        let memories = vec![
+            embedded("User likes blue", vec![1.0, 0.0]),
+            embedded("User's favorite color is blue", vec![0.99, 0.05]),
+            embedded("User is from Seattle", vec![0.0, 1.0]),
+            Memory::new(agent_id.clone(), "User likes blue"),
+        ];
+
+        let all = ContextWindow::default().build_context("Hi", &memories, &[]);
+        assert!(all[0].content.contains("favorite color"));
+
+        let window = ContextWindow::new(ContextWindowConfig::default().with_dedup_threshold(0.95));
+        let context = window.build_context("Hi", &memories, &[]);
+        assert!(!context[0].content.contains("favorite color"));
+        assert!(context[0].content.contains("User is from Seattle"));
+        assert_eq!(context[0].content.matches("User likes blue").count(), 2);
+    }
+
     #[test]
     fn build_context_skips_conversation_system_messages() {
         let window = ContextWindow::default();
@@ -865,6 +1316,11 @@ mod tests {
             truncation_strategy: TruncationStrategy::KeepRecent,
             reserved_for_response: 5,
             tokens_per_char: 0.25,
+            importance_weight: 1.0,
+            dedup_threshold: None,
+            hybrid_search_alpha: 0.7,
+            accurate_token_count: false,
+            model_name: None,
         };
         let window = ContextWindow::new(config);
 
@@ -940,17 +1396,72 @@ mod tests {
             truncation_strategy: TruncationStrategy::KeepRecent,
             reserved_for_response: 10,
             tokens_per_char: 0.25,
+            importance_weight: 1.0,
+            dedup_threshold: None,
+            hybrid_search_alpha: 0.7,
+            accurate_token_count: false,
+            model_name: None,
         };
         let cw = ContextWindow::new(cfg);
 
         let messages = vec![
-            msg(MessageRole::User, &"old".repeat(60)),    // ~49 tokens — too big
+            msg(MessageRole::User, &"old".repeat(60)), // ~49 tokens — too big
             msg(MessageRole::Assistant, &"mid".repeat(60)),
-            msg(MessageRole::User, "new"),                 // tiny, should survive
+            msg(MessageRole::User, "new"), // tiny, should survive
         ];
 
         let fitted = cw.fit_messages(&messages);
         assert!(!fitted.is_empty(), "at least the newest must survive");
         assert_eq!(fitted.last().unwrap().content, "new");
     }
+
+    #[test]
+    fn accurate_token_count_uses_model_tokenizer() {
+        let cw = ContextWindow::new(ContextWindowConfig::default().with_accurate_token_count(true));
+        assert_eq!(cw.config().tokenizer_model(), DEFAULT_TOKENIZER_MODEL);
+        assert_eq!(cw.estimator_name(), "cl100k_base");
+        assert!(!cw.is_estimated());
+
+        let messages = vec![msg(MessageRole::User, "hello world")];
+        assert_eq!(
+            cw.estimate_total_tokens(&messages),
+            cw.token_count_accurate(&messages)
+        );
+        assert!(!cw.get_context_stats(&messages).is_estimated);
+        assert!(
+            ContextWindow::default()
+                .get_context_stats(&messages)
+                .is_estimated
+        );
+    }
+
+    #[test]
+    fn accurate_token_count_falls_back_to_heuristic_for_unknown_model() {
+        let config = ContextWindowConfig::default()
+            .with_accurate_token_count(true)
+            .with_model_name("qwen2.5:7b");
+        let cw = ContextWindow::new(config);
+        assert_eq!(cw.estimator_name(), "char-ratio");
+        assert!(cw.is_estimated());
+    }
+
+    #[test]
+    fn fit_messages_uses_accurate_counts_when_enabled() {
+        // "a " encodes to about one token per two characters, twice the
+        // heuristic's estimate.
+        let messages = vec![
+            msg(MessageRole::User, &"a ".repeat(100)),
+            msg(MessageRole::User, &"a ".repeat(100)),
+        ];
+        let config = ContextWindowConfig::with_max_tokens(200)
+            .with_strategy(TruncationStrategy::KeepRecent)
+            .with_reserved_for_response(0);
+
+        let heuristic = ContextWindow::new(config.clone());
+        assert_eq!(heuristic.fit_messages(&messages).len(), 2);
+
+        let accurate = ContextWindow::new(config.with_accurate_token_count(true));
+        assert!(accurate.token_count_accurate(&messages) > 200);
+        assert_eq!(accurate.fit_messages(&messages).len(), 1);
+    }
 }
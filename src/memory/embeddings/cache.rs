@@ -0,0 +1,273 @@
+//! In-memory caching of embeddings.
+//!
+//! Embedding the same text twice costs a second API call for the same
+//! vector. [`EmbeddingCache`] wraps any [`EmbeddingProvider`] and answers
+//! repeated texts from memory. Texts are identified by a SHA-256 of their
+//! UTF-8 bytes, so long texts do not stay in memory as keys. The least
+//! recently used entry is evicted once `max_entries` is reached.
+
+use super::{Embedding, EmbeddingError, EmbeddingProvider};
+use async_trait::async_trait;
+use ring::digest::{digest, SHA256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Counters of an [`EmbeddingCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddingCacheStats {
+    /// Texts answered from the cache
+    pub hits: u64,
+    /// Texts that had to call the inner provider
+    pub misses: u64,
+    /// Entries dropped to stay within `max_entries`
+    pub evictions: u64,
+    /// Number of cached embeddings
+    pub size: usize,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    embedding: Embedding,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<[u8; 32], CacheEntry>,
+    /// Incremented on every access to order entries by recency
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+/// LRU cache of embeddings in front of another [`EmbeddingProvider`].
+///
+/// # Example
+///
+/// ```rust
+/// use acton_ai::memory::{EmbeddingCache, EmbeddingProvider, StubEmbeddingProvider};
+///
+/// # tokio_test::block_on(async {
+/// let cache = EmbeddingCache::new(StubEmbeddingProvider::default(), 1000);
+/// cache.embed("User prefers dark mode").await.unwrap();
+/// cache.embed("User prefers dark mode").await.unwrap();
+///
+/// assert_eq!(cache.stats().hits, 1);
+/// assert_eq!(cache.stats().misses, 1);
+/// # });
+/// ```
+pub struct EmbeddingCache {
+    inner: Box<dyn EmbeddingProvider>,
+    max_entries: usize,
+    state: Mutex<CacheState>,
+}
+
+impl EmbeddingCache {
+    /// Wraps `provider`, caching up to `max_entries` embeddings.
+    #[must_use]
+    pub fn new(provider: impl EmbeddingProvider + 'static, max_entries: usize) -> Self {
+        Self::boxed(Box::new(provider), max_entries)
+    }
+
+    /// Wraps an already boxed provider.
+    pub(crate) fn boxed(provider: Box<dyn EmbeddingProvider>, max_entries: usize) -> Self {
+        Self {
+            inner: provider,
+            max_entries,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Returns the hit, miss and eviction counts and the current size.
+    #[must_use]
+    pub fn stats(&self) -> EmbeddingCacheStats {
+        let state = self.lock();
+        EmbeddingCacheStats {
+            hits: state.hits,
+            misses: state.misses,
+            evictions: state.evictions,
+            size: state.entries.len(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Looks up `key`, counting a hit or a miss.
+    fn get(&self, key: &[u8; 32]) -> Option<Embedding> {
+        let mut state = self.lock();
+        state.clock += 1;
+        let clock = state.clock;
+        if let Some(entry) = state.entries.get_mut(key) {
+            entry.last_used = clock;
+            let embedding = entry.embedding.clone();
+            state.hits += 1;
+            return Some(embedding);
+        }
+        state.misses += 1;
+        None
+    }
+
+    /// Stores `embedding` under `key`, evicting the least recently used
+    /// entry if the cache is full.
+    fn insert(&self, key: [u8; 32], embedding: Embedding) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut state = self.lock();
+        if !state.entries.contains_key(&key) && state.entries.len() >= self.max_entries {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| *key);
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+                state.evictions += 1;
+            }
+        }
+
+        state.clock += 1;
+        let last_used = state.clock;
+        state.entries.insert(
+            key,
+            CacheEntry {
+                embedding,
+                last_used,
+            },
+        );
+    }
+}
+
+impl std::fmt::Debug for EmbeddingCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddingCache")
+            .field("inner", &self.inner.name())
+            .field("max_entries", &self.max_entries)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+fn cache_key(text: &str) -> [u8; 32] {
+    let hash = digest(&SHA256, text.as_bytes());
+    let mut key = [0; 32];
+    key.copy_from_slice(hash.as_ref());
+    key
+}
+
+#[async_trait]
+impl EmbeddingProvider for EmbeddingCache {
+    async fn embed(&self, text: &str) -> Result<Embedding, EmbeddingError> {
+        let key = cache_key(text);
+        if let Some(embedding) = self.get(&key) {
+            return Ok(embedding);
+        }
+
+        let embedding = self.inner.embed(text).await?;
+        self.insert(key, embedding.clone());
+        Ok(embedding)
+    }
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::StubEmbeddingProvider;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Counts calls through to a stub provider.
+    #[derive(Debug, Default)]
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+        stub: StubEmbeddingProvider,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for CountingProvider {
+        async fn embed(&self, text: &str) -> Result<Embedding, EmbeddingError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.stub.embed(text).await
+        }
+
+        fn dimension(&self) -> usize {
+            self.stub.dimension()
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+    }
+
+    fn counting_cache(max_entries: usize) -> (EmbeddingCache, Arc<AtomicUsize>) {
+        let provider = CountingProvider::default();
+        let calls = Arc::clone(&provider.calls);
+        (EmbeddingCache::new(provider, max_entries), calls)
+    }
+
+    #[tokio::test]
+    async fn identical_text_calls_provider_once() {
+        let (cache, calls) = counting_cache(10);
+
+        let first = cache.embed("User prefers dark mode").await.unwrap();
+        let second = cache.embed("User prefers dark mode").await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            cache.stats(),
+            EmbeddingCacheStats {
+                hits: 1,
+                misses: 1,
+                evictions: 0,
+                size: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_used_text() {
+        let (cache, calls) = counting_cache(2);
+
+        cache.embed("a").await.unwrap();
+        cache.embed("b").await.unwrap();
+        cache.embed("a").await.unwrap();
+        cache.embed("c").await.unwrap();
+        cache.embed("a").await.unwrap();
+        cache.embed("b").await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        let stats = cache.stats();
+        assert_eq!(stats.evictions, 2);
+        assert_eq!(stats.size, 2);
+    }
+
+    #[tokio::test]
+    async fn zero_capacity_always_calls_provider() {
+        let (cache, calls) = counting_cache(0);
+
+        cache.embed("a").await.unwrap();
+        cache.embed("a").await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(cache.stats().size, 0);
+    }
+
+    #[test]
+    fn reports_inner_provider_identity() {
+        let cache = EmbeddingCache::new(StubEmbeddingProvider::new(64), 10);
+        assert_eq!(cache.dimension(), 64);
+        assert_eq!(cache.name(), "stub");
+    }
+}
@@ -4,8 +4,10 @@
 //! - [`Embedding`]: A vector embedding for semantic similarity
 //! - [`EmbeddingProvider`]: Trait for embedding generation services
 //! - [`StubEmbeddingProvider`]: Test implementation using deterministic hashing
+//! - [`OpenAIEmbeddingProvider`]: OpenAI-compatible `/embeddings` API implementation
 //! - [`Memory`]: A memory entry with optional embedding
 //! - [`ScoredMemory`]: A memory with its similarity score
+//! - [`RerankProvider`]: Trait for post-retrieval reranking services
 //!
 //! ## Example
 //!
@@ -22,9 +24,18 @@
 
 use crate::types::{AgentId, MemoryId};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+mod cache;
+mod openai;
+mod rerank;
+
+pub use cache::{EmbeddingCache, EmbeddingCacheStats};
+pub use openai::OpenAIEmbeddingProvider;
+pub use rerank::{CohereRerankProvider, RerankError, RerankProvider, Reranking};
+
 // =============================================================================
 // Embedding Error
 // =============================================================================
@@ -394,6 +405,21 @@ pub struct Memory {
     pub embedding: Option<Embedding>,
     /// When this memory was created (ISO 8601 format).
     pub created_at: String,
+    /// How much this memory matters when ranking search results, from 0.0
+    /// to 1.0.
+    #[serde(default = "default_importance")]
+    pub importance: f32,
+    /// When this memory expires. Expired memories are excluded from search
+    /// and deleted by [`PruneExpiredMemories`](crate::memory::PruneExpiredMemories).
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Importance given to memories stored without one.
+pub const DEFAULT_MEMORY_IMPORTANCE: f32 = 0.5;
+
+fn default_importance() -> f32 {
+    DEFAULT_MEMORY_IMPORTANCE
 }
 
 impl Memory {
@@ -411,6 +437,8 @@ impl Memory {
             content: content.into(),
             embedding: None,
             created_at: current_timestamp(),
+            importance: DEFAULT_MEMORY_IMPORTANCE,
+            expires_at: None,
         }
     }
 
@@ -433,8 +461,30 @@ impl Memory {
             content: content.into(),
             embedding: Some(embedding),
             created_at: current_timestamp(),
+            importance: DEFAULT_MEMORY_IMPORTANCE,
+            expires_at: None,
         }
     }
+
+    /// Sets the importance, clamped to 0.0..=1.0.
+    #[must_use]
+    pub fn with_importance(mut self, importance: f32) -> Self {
+        self.importance = importance.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets when this memory expires.
+    #[must_use]
+    pub fn with_expires_at(mut self, expires_at: DateTime<Utc>) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Returns true if this memory has expired as of `now`.
+    #[must_use]
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at < now)
+    }
 }
 
 /// A memory with its similarity score from search.
@@ -443,7 +493,46 @@ pub struct ScoredMemory {
     /// The memory entry.
     pub memory: Memory,
     /// Similarity score (typically 0.0 to 1.0, can be negative for opposite vectors).
+    ///
+    /// When ranked with an importance weight below 1.0 this is
+    /// [`ScoredMemory::weighted_score`], and for hybrid searches
+    /// [`ScoredMemory::hybrid_score`], rather than raw cosine similarity.
     pub score: f32,
+    /// Cosine similarity to the query embedding (0.0 if the memory has no
+    /// embedding).
+    pub vector_score: f32,
+    /// Full-text relevance to the query text, normalized to 0.0..=1.0 within
+    /// one search (0.0 if the search did not use text).
+    pub text_score: f32,
+}
+
+impl ScoredMemory {
+    /// Creates a vector-only result whose score is its cosine similarity.
+    #[must_use]
+    pub fn new(memory: Memory, similarity: f32) -> Self {
+        Self {
+            memory,
+            score: similarity,
+            vector_score: similarity,
+            text_score: 0.0,
+        }
+    }
+
+    /// Blends vector and text relevance:
+    /// `alpha * vector_score + (1 - alpha) * text_score`.
+    #[must_use]
+    pub fn hybrid_score(vector_score: f32, text_score: f32, alpha: f32) -> f32 {
+        alpha * vector_score + (1.0 - alpha) * text_score
+    }
+
+    /// Blends cosine similarity with a memory's importance:
+    /// `similarity * importance_weight + importance * (1 - importance_weight)`.
+    ///
+    /// An `importance_weight` of 1.0 ranks purely by similarity.
+    #[must_use]
+    pub fn weighted_score(similarity: f32, importance: f32, importance_weight: f32) -> f32 {
+        similarity * importance_weight + importance * (1.0 - importance_weight)
+    }
 }
 
 /// Returns the current timestamp in ISO 8601 format.
@@ -713,12 +802,52 @@ mod tests {
     fn scored_memory_creation() {
         let agent_id = AgentId::new();
         let memory = Memory::new(agent_id, "test");
-        let scored = ScoredMemory {
-            memory: memory.clone(),
-            score: 0.85,
-        };
+        let scored = ScoredMemory::new(memory.clone(), 0.85);
 
         assert_eq!(scored.memory.content, "test");
         assert!((scored.score - 0.85).abs() < 0.0001);
     }
+
+    #[test]
+    fn memory_importance_defaults_and_clamps() {
+        let memory = Memory::new(AgentId::new(), "test");
+        assert_eq!(memory.importance, DEFAULT_MEMORY_IMPORTANCE);
+        assert_eq!(memory.clone().with_importance(1.5).importance, 1.0);
+        assert_eq!(memory.with_importance(-0.5).importance, 0.0);
+    }
+
+    #[test]
+    fn memory_expiry() {
+        let now = Utc::now();
+        let memory = Memory::new(AgentId::new(), "test");
+        let later = now + chrono::Duration::seconds(1);
+        assert!(!memory.is_expired_at(now));
+        assert!(memory.with_expires_at(now).is_expired_at(later));
+    }
+
+    #[test]
+    fn memory_deserializes_without_lifecycle_fields() {
+        let memory = Memory::new(AgentId::new(), "test");
+        let mut json = serde_json::to_value(&memory).unwrap();
+        let object = json.as_object_mut().unwrap();
+        object.remove("importance");
+        object.remove("expires_at");
+
+        let restored: Memory = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.importance, DEFAULT_MEMORY_IMPORTANCE);
+        assert_eq!(restored.expires_at, None);
+    }
+
+    #[test]
+    fn hybrid_score_blends_vector_and_text() {
+        assert!((ScoredMemory::hybrid_score(0.5, 1.0, 0.7) - 0.65).abs() < 0.0001);
+        assert!((ScoredMemory::hybrid_score(0.5, 1.0, 1.0) - 0.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn weighted_score_blends_similarity_and_importance() {
+        assert!((ScoredMemory::weighted_score(0.9, 0.1, 1.0) - 0.9).abs() < 0.0001);
+        assert!((ScoredMemory::weighted_score(0.9, 0.1, 0.0) - 0.1).abs() < 0.0001);
+        assert!((ScoredMemory::weighted_score(0.6, 1.0, 0.5) - 0.8).abs() < 0.0001);
+    }
 }
@@ -0,0 +1,266 @@
+//! Embeddings from the OpenAI `/v1/embeddings` API.
+//!
+//! [`OpenAIEmbeddingProvider`] also works with OpenAI-compatible servers
+//! such as Ollama by pointing it at their base URL.
+
+use super::{Embedding, EmbeddingError, EmbeddingProvider};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Embedding provider backed by an OpenAI-compatible `/embeddings` endpoint.
+///
+/// The first embedding returned fixes the provider's dimension; any later
+/// embedding of a different dimension is rejected with
+/// [`EmbeddingError::DimensionMismatch`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use acton_ai::memory::{EmbeddingProvider, OpenAIEmbeddingProvider};
+///
+/// let provider = OpenAIEmbeddingProvider::new(api_key);
+/// let embedding = provider.embed("User prefers dark mode").await?;
+///
+/// // Ollama's OpenAI-compatible endpoint
+/// let local = OpenAIEmbeddingProvider::new("")
+///     .with_base_url("http://localhost:11434/v1")
+///     .with_model("nomic-embed-text")
+///     .with_dimension(768);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenAIEmbeddingProvider {
+    /// HTTP client
+    client: Client,
+    /// API key
+    api_key: String,
+    /// Embedding model name
+    model: String,
+    /// Base URL for the API, including the version prefix
+    base_url: String,
+    /// Dimension reported before the first embedding is returned
+    dimension: usize,
+    /// Dimension of the first embedding returned
+    observed_dimension: OnceLock<usize>,
+}
+
+/// Request body for the embeddings API.
+#[derive(Debug, Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a str,
+}
+
+/// Response from the embeddings API.
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+/// A single embedding in an embeddings response.
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+impl OpenAIEmbeddingProvider {
+    /// Default OpenAI embedding model.
+    pub const DEFAULT_MODEL: &'static str = "text-embedding-3-small";
+
+    /// Dimension of [`Self::DEFAULT_MODEL`] embeddings.
+    pub const DEFAULT_DIMENSION: usize = 1536;
+
+    /// Creates a provider for the OpenAI API with the default model.
+    #[must_use]
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            api_key: api_key.into(),
+            model: Self::DEFAULT_MODEL.to_string(),
+            base_url: "https://api.openai.com/v1".to_string(),
+            dimension: Self::DEFAULT_DIMENSION,
+            observed_dimension: OnceLock::new(),
+        }
+    }
+
+    /// Sets the embedding model.
+    ///
+    /// Also set [`with_dimension`](Self::with_dimension) if the model's
+    /// dimension differs from the default.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Sets the base URL for an OpenAI-compatible server (e.g.,
+    /// "http://localhost:11434/v1").
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Sets the dimension reported before the first embedding is returned.
+    #[must_use]
+    pub fn with_dimension(mut self, dimension: usize) -> Self {
+        self.dimension = dimension;
+        self
+    }
+
+    /// Returns the embeddings endpoint URL.
+    fn embeddings_endpoint(&self) -> String {
+        format!("{}/embeddings", self.base_url)
+    }
+
+    fn failed(&self, message: impl Into<String>) -> EmbeddingError {
+        EmbeddingError::GenerationFailed {
+            provider: self.name().to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Builds an embedding from a response, checking that its dimension
+    /// matches the first one this provider returned.
+    fn embedding_from_response(
+        &self,
+        response: EmbeddingResponse,
+    ) -> Result<Embedding, EmbeddingError> {
+        let values = response
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| self.failed("response contained no embeddings"))?
+            .embedding;
+        let embedding = Embedding::new(values)?;
+
+        let expected = *self.observed_dimension.get_or_init(|| embedding.dimension());
+        if embedding.dimension() != expected {
+            return Err(EmbeddingError::DimensionMismatch {
+                expected,
+                actual: embedding.dimension(),
+            });
+        }
+        Ok(embedding)
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAIEmbeddingProvider {
+    async fn embed(&self, text: &str) -> Result<Embedding, EmbeddingError> {
+        let mut request = self
+            .client
+            .post(self.embeddings_endpoint())
+            .header("content-type", "application/json")
+            .json(&EmbeddingRequest {
+                model: &self.model,
+                input: text,
+            });
+        if !self.api_key.is_empty() {
+            request = request.header("Authorization", format!("Bearer {}", self.api_key));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| self.failed(format!("request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(self.failed(format!("HTTP {}: {}", status.as_u16(), body)));
+        }
+
+        let parsed: EmbeddingResponse = response
+            .json()
+            .await
+            .map_err(|e| self.failed(format!("failed to parse response: {}", e)))?;
+
+        self.embedding_from_response(parsed)
+    }
+
+    fn dimension(&self) -> usize {
+        self.observed_dimension
+            .get()
+            .copied()
+            .unwrap_or(self.dimension)
+    }
+
+    fn name(&self) -> &str {
+        "openai"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(values: Vec<f32>) -> EmbeddingResponse {
+        EmbeddingResponse {
+            data: vec![EmbeddingData { embedding: values }],
+        }
+    }
+
+    #[test]
+    fn defaults_to_text_embedding_3_small() {
+        let provider = OpenAIEmbeddingProvider::new("key");
+        assert_eq!(provider.model, "text-embedding-3-small");
+        assert_eq!(provider.dimension(), 1536);
+        assert_eq!(
+            provider.embeddings_endpoint(),
+            "https://api.openai.com/v1/embeddings"
+        );
+    }
+
+    #[test]
+    fn base_url_targets_compatible_servers() {
+        let provider = OpenAIEmbeddingProvider::new("")
+            .with_base_url("http://localhost:11434/v1")
+            .with_model("nomic-embed-text")
+            .with_dimension(768);
+        assert_eq!(
+            provider.embeddings_endpoint(),
+            "http://localhost:11434/v1/embeddings"
+        );
+        assert_eq!(provider.dimension(), 768);
+    }
+
+    #[test]
+    fn parses_embeddings_response_body() {
+        let body = r#"{"object":"list","data":[{"object":"embedding","index":0,"embedding":[0.1,0.2,0.3]}],"model":"m"}"#;
+        let parsed: EmbeddingResponse = serde_json::from_str(body).unwrap();
+        let embedding = OpenAIEmbeddingProvider::new("key")
+            .embedding_from_response(parsed)
+            .unwrap();
+        assert_eq!(embedding.values(), &[0.1, 0.2, 0.3]);
+    }
+
+    #[test]
+    fn rejects_dimension_changes_after_first_embedding() {
+        let provider = OpenAIEmbeddingProvider::new("key");
+        provider
+            .embedding_from_response(response(vec![0.1, 0.2]))
+            .unwrap();
+        assert_eq!(provider.dimension(), 2);
+
+        let err = provider
+            .embedding_from_response(response(vec![0.1, 0.2, 0.3]))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            EmbeddingError::DimensionMismatch {
+                expected: 2,
+                actual: 3
+            }
+        );
+    }
+
+    #[test]
+    fn empty_response_is_a_generation_failure() {
+        let err = OpenAIEmbeddingProvider::new("key")
+            .embedding_from_response(EmbeddingResponse { data: Vec::new() })
+            .unwrap_err();
+        assert!(matches!(err, EmbeddingError::GenerationFailed { .. }));
+    }
+}
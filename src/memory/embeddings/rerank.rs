@@ -0,0 +1,370 @@
+//! Post-retrieval reranking of memory search results.
+//!
+//! Vector similarity is a cheap first pass; a cross-encoder reranker scores
+//! each candidate against the query text and usually orders them better.
+//!
+//! - [`RerankProvider`]: Trait for reranking services
+//! - [`CohereRerankProvider`]: Cohere rerank API implementation
+//! - [`Reranking`]: Query + provider attached to a
+//!   [`SearchMemories`](crate::memory::SearchMemories) request
+
+use super::ScoredMemory;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+// =============================================================================
+// Rerank Error
+// =============================================================================
+
+/// Errors that can occur while reranking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RerankError {
+    /// Provider failed to rerank the documents.
+    RerankFailed {
+        /// The provider name.
+        provider: String,
+        /// Error message.
+        message: String,
+    },
+    /// Provider returned an index outside the submitted documents.
+    IndexOutOfRange {
+        /// The returned index.
+        index: usize,
+        /// Number of documents submitted.
+        len: usize,
+    },
+}
+
+impl fmt::Display for RerankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RerankFailed { provider, message } => {
+                write!(f, "rerank provider '{}' failed: {}", provider, message)
+            }
+            Self::IndexOutOfRange { index, len } => {
+                write!(
+                    f,
+                    "rerank result index {} is out of range for {} documents",
+                    index, len
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for RerankError {}
+
+// =============================================================================
+// Rerank Provider Trait
+// =============================================================================
+
+/// Trait for reranking services.
+///
+/// Given a query and candidate documents, returns `(index, relevance_score)`
+/// pairs ordered from most to least relevant. Indices refer to positions in
+/// `documents`; providers may omit documents they consider irrelevant.
+#[async_trait]
+pub trait RerankProvider: Send + Sync + fmt::Debug {
+    /// Scores `documents` against `query`, most relevant first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RerankError::RerankFailed` if the provider cannot rerank.
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+    ) -> Result<Vec<(usize, f32)>, RerankError>;
+
+    /// Returns the name/identifier of this provider.
+    fn name(&self) -> &str;
+}
+
+/// A reranking pass attached to a memory search.
+///
+/// The query text is needed because rerankers score text against text, not
+/// embeddings.
+#[derive(Debug, Clone)]
+pub struct Reranking {
+    /// The query text to rerank against
+    pub query: String,
+    /// The provider that scores the candidates
+    pub provider: Arc<dyn RerankProvider>,
+}
+
+impl Reranking {
+    /// Creates a reranking pass for `query` using `provider`.
+    #[must_use]
+    pub fn new(query: impl Into<String>, provider: Arc<dyn RerankProvider>) -> Self {
+        Self {
+            query: query.into(),
+            provider,
+        }
+    }
+
+    /// Reorders `results` by rerank relevance.
+    ///
+    /// Each returned memory's `score` is replaced with the reranker's
+    /// relevance score. Candidates the provider omits are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the provider fails or returns an unknown index.
+    pub async fn apply(
+        &self,
+        results: Vec<ScoredMemory>,
+    ) -> Result<Vec<ScoredMemory>, RerankError> {
+        if results.is_empty() {
+            return Ok(results);
+        }
+
+        let documents: Vec<&str> = results.iter().map(|r| r.memory.content.as_str()).collect();
+        let ranking = self.provider.rerank(&self.query, &documents).await?;
+
+        let len = results.len();
+        let mut slots: Vec<Option<ScoredMemory>> = results.into_iter().map(Some).collect();
+        ranking
+            .into_iter()
+            .filter_map(|(index, score)| match slots.get_mut(index) {
+                Some(slot) => slot
+                    .take()
+                    .map(|memory| Ok(ScoredMemory { score, ..memory })),
+                None => Some(Err(RerankError::IndexOutOfRange { index, len })),
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// Cohere Rerank Provider
+// =============================================================================
+
+/// Reranker backed by the Cohere rerank API.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use acton_ai::memory::{CohereRerankProvider, Reranking, SearchMemories};
+/// use std::sync::Arc;
+///
+/// let reranker = Arc::new(CohereRerankProvider::new(api_key));
+/// store.send(SearchMemories {
+///     agent_id,
+///     query_embedding,
+///     limit: 10,
+///     min_similarity: None,
+///     rerank_with: Some(Reranking::new("dark mode preference", reranker)),
+///     importance_weight: None,
+/// }).await;
+/// ```
+#[derive(Debug, Clone)]
+pub struct CohereRerankProvider {
+    /// HTTP client
+    client: Client,
+    /// API key
+    api_key: String,
+    /// Rerank model name
+    model: String,
+    /// Base URL for the API
+    base_url: String,
+}
+
+/// Request body for the Cohere rerank API.
+#[derive(Debug, Serialize)]
+struct CohereRerankRequest<'a> {
+    model: &'a str,
+    query: &'a str,
+    documents: &'a [&'a str],
+}
+
+/// Response from the Cohere rerank API.
+#[derive(Debug, Deserialize)]
+struct CohereRerankResponse {
+    results: Vec<CohereRerankResult>,
+}
+
+/// A single scored document in a rerank response.
+#[derive(Debug, Deserialize)]
+struct CohereRerankResult {
+    index: usize,
+    relevance_score: f32,
+}
+
+impl CohereRerankProvider {
+    /// Default Cohere rerank model.
+    pub const DEFAULT_MODEL: &'static str = "rerank-v3.5";
+
+    /// Creates a reranker with the default model.
+    #[must_use]
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            api_key: api_key.into(),
+            model: Self::DEFAULT_MODEL.to_string(),
+            base_url: "https://api.cohere.com".to_string(),
+        }
+    }
+
+    /// Sets the rerank model.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// Sets the base URL for the API.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Returns the rerank endpoint URL.
+    fn rerank_endpoint(&self) -> String {
+        format!("{}/v2/rerank", self.base_url)
+    }
+
+    fn failed(&self, message: impl Into<String>) -> RerankError {
+        RerankError::RerankFailed {
+            provider: self.name().to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl RerankProvider for CohereRerankProvider {
+    async fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+    ) -> Result<Vec<(usize, f32)>, RerankError> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let response = self
+            .client
+            .post(self.rerank_endpoint())
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("content-type", "application/json")
+            .json(&CohereRerankRequest {
+                model: &self.model,
+                query,
+                documents,
+            })
+            .send()
+            .await
+            .map_err(|e| self.failed(format!("request failed: {}", e)))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(self.failed(format!("HTTP {}: {}", status.as_u16(), body)));
+        }
+
+        let parsed: CohereRerankResponse = response
+            .json()
+            .await
+            .map_err(|e| self.failed(format!("failed to parse response: {}", e)))?;
+
+        let mut ranking: Vec<(usize, f32)> = parsed
+            .results
+            .into_iter()
+            .map(|r| (r.index, r.relevance_score))
+            .collect();
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(ranking)
+    }
+
+    fn name(&self) -> &str {
+        "cohere"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::Memory;
+    use crate::types::AgentId;
+
+    /// Ranks documents by how many times they contain the query.
+    #[derive(Debug)]
+    struct CountingReranker;
+
+    #[async_trait]
+    impl RerankProvider for CountingReranker {
+        async fn rerank(
+            &self,
+            query: &str,
+            documents: &[&str],
+        ) -> Result<Vec<(usize, f32)>, RerankError> {
+            let mut ranking: Vec<(usize, f32)> = documents
+                .iter()
+                .enumerate()
+                .map(|(i, d)| (i, d.matches(query).count() as f32))
+                .collect();
+            ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+            Ok(ranking)
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+    }
+
+    #[derive(Debug)]
+    struct BadIndexReranker;
+
+    #[async_trait]
+    impl RerankProvider for BadIndexReranker {
+        async fn rerank(&self, _: &str, _: &[&str]) -> Result<Vec<(usize, f32)>, RerankError> {
+            Ok(vec![(7, 1.0)])
+        }
+
+        fn name(&self) -> &str {
+            "bad-index"
+        }
+    }
+
+    fn scored(content: &str, score: f32) -> ScoredMemory {
+        ScoredMemory::new(Memory::new(AgentId::new(), content.to_string()), score)
+    }
+
+    #[tokio::test]
+    async fn reranking_reorders_and_rescores() {
+        let reranking = Reranking::new("rust", Arc::new(CountingReranker));
+        let results = vec![
+            scored("python", 0.9),
+            scored("rust rust", 0.5),
+            scored("rust", 0.7),
+        ];
+
+        let reranked = reranking.apply(results).await.unwrap();
+
+        let contents: Vec<_> = reranked.iter().map(|r| r.memory.content.as_str()).collect();
+        assert_eq!(contents, vec!["rust rust", "rust", "python"]);
+        assert_eq!(reranked[0].score, 2.0);
+    }
+
+    #[tokio::test]
+    async fn reranking_rejects_unknown_index() {
+        let reranking = Reranking::new("q", Arc::new(BadIndexReranker));
+        let err = reranking.apply(vec![scored("a", 0.1)]).await.unwrap_err();
+        assert_eq!(err, RerankError::IndexOutOfRange { index: 7, len: 1 });
+    }
+
+    #[test]
+    fn cohere_rerank_provider_defaults() {
+        let provider = CohereRerankProvider::new("co-key");
+        assert_eq!(provider.model, "rerank-v3.5");
+        assert_eq!(
+            provider.rerank_endpoint(),
+            "https://api.cohere.com/v2/rerank"
+        );
+        assert_eq!(provider.name(), "cohere");
+    }
+}
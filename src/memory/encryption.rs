@@ -0,0 +1,250 @@
+//! At-rest encryption for persisted conversation and memory content.
+//!
+//! When a [`PersistenceConfig`](super::PersistenceConfig) carries an
+//! [`EncryptionConfig`], the [`MemoryStore`](super::MemoryStore) seals the
+//! `content` column of every message and memory before it reaches libSQL and
+//! opens it again on the way out. Each row gets a fresh random nonce, stored
+//! alongside the ciphertext as `base64(nonce || ciphertext)`.
+//!
+//! Only the `content` columns are encrypted. Identifiers, timestamps, roles,
+//! tool-call metadata and embedding vectors stay in plaintext so that
+//! indexing and vector search keep working.
+//!
+//! ## Key management
+//!
+//! - Never hard-code keys or commit them to configuration files. Load them at
+//!   startup from a secret manager, KMS, or an environment variable injected
+//!   by your deployment tooling.
+//! - Use a key produced by a CSPRNG (32 random bytes). Do not derive keys
+//!   from passwords without a proper KDF such as Argon2.
+//! - Rotate keys with [`ReencryptAll`](super::ReencryptAll), which rewrites
+//!   every row inside a single transaction so a failed rotation leaves the
+//!   database readable with the old key.
+//! - Losing the key means losing the data: there is no recovery path.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use acton_ai::memory::{EncryptionAlgorithm, EncryptionConfig, PersistenceConfig};
+//!
+//! let key: [u8; 32] = load_key_from_secret_manager();
+//! let config = PersistenceConfig::new("agents.db").with_encryption(key);
+//!
+//! // Or pick the cipher explicitly:
+//! let config = PersistenceConfig::new("agents.db").with_encryption_config(
+//!     EncryptionConfig::new(key).with_algorithm(EncryptionAlgorithm::ChaCha20Poly1305),
+//! );
+//! ```
+
+use crate::memory::error::PersistenceError;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::fmt;
+
+/// The AEAD cipher used to encrypt stored content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EncryptionAlgorithm {
+    /// AES-256 in Galois/Counter Mode.
+    #[default]
+    AesGcm256,
+    /// ChaCha20 stream cipher with Poly1305 authenticator.
+    ChaCha20Poly1305,
+}
+
+impl EncryptionAlgorithm {
+    fn aead(self) -> &'static aead::Algorithm {
+        match self {
+            Self::AesGcm256 => &aead::AES_256_GCM,
+            Self::ChaCha20Poly1305 => &aead::CHACHA20_POLY1305,
+        }
+    }
+}
+
+impl fmt::Display for EncryptionAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AesGcm256 => write!(f, "aes-256-gcm"),
+            Self::ChaCha20Poly1305 => write!(f, "chacha20-poly1305"),
+        }
+    }
+}
+
+/// Encryption settings for persisted content.
+///
+/// The key is never printed: the `Debug` implementation redacts it so the
+/// config can be logged safely alongside the rest of
+/// [`PersistenceConfig`](super::PersistenceConfig).
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptionConfig {
+    /// The 256-bit symmetric key
+    pub key: [u8; 32],
+    /// The cipher used with the key
+    pub algorithm: EncryptionAlgorithm,
+}
+
+impl EncryptionConfig {
+    /// Creates an encryption config using [`EncryptionAlgorithm::AesGcm256`].
+    #[must_use]
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            key,
+            algorithm: EncryptionAlgorithm::default(),
+        }
+    }
+
+    /// Sets the cipher.
+    #[must_use]
+    pub fn with_algorithm(mut self, algorithm: EncryptionAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    fn sealing_key(&self) -> Result<LessSafeKey, PersistenceError> {
+        let unbound = UnboundKey::new(self.algorithm.aead(), &self.key)
+            .map_err(|_| PersistenceError::encryption_failed("invalid key for cipher"))?;
+        Ok(LessSafeKey::new(unbound))
+    }
+
+    /// Encrypts `plaintext`, returning `base64(nonce || ciphertext)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the system RNG fails or the key is rejected.
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, PersistenceError> {
+        let key = self.sealing_key()?;
+
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce_bytes)
+            .map_err(|_| PersistenceError::encryption_failed("failed to generate nonce"))?;
+
+        let mut in_out = plaintext.as_bytes().to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce_bytes),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| PersistenceError::encryption_failed("seal operation failed"))?;
+
+        let mut sealed = Vec::with_capacity(NONCE_LEN + in_out.len());
+        sealed.extend_from_slice(&nonce_bytes);
+        sealed.extend_from_slice(&in_out);
+        Ok(BASE64.encode(sealed))
+    }
+
+    /// Decrypts a value previously produced by [`encrypt`](Self::encrypt).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is not valid base64, is too short to
+    /// contain a nonce, was encrypted with a different key or cipher, or has
+    /// been tampered with.
+    pub fn decrypt(&self, encoded: &str) -> Result<String, PersistenceError> {
+        let sealed = BASE64
+            .decode(encoded)
+            .map_err(|e| PersistenceError::decryption_failed(format!("invalid base64: {e}")))?;
+        if sealed.len() < NONCE_LEN {
+            return Err(PersistenceError::decryption_failed(
+                "ciphertext shorter than nonce",
+            ));
+        }
+
+        let (nonce_bytes, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce_bytes)
+            .map_err(|_| PersistenceError::decryption_failed("malformed nonce"))?;
+
+        let key = self.sealing_key()?;
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = key
+            .open_in_place(nonce, Aad::empty(), &mut in_out)
+            .map_err(|_| {
+                PersistenceError::decryption_failed(
+                    "authentication failed; wrong key or corrupted data",
+                )
+            })?;
+
+        String::from_utf8(plaintext.to_vec())
+            .map_err(|e| PersistenceError::decryption_failed(e.to_string()))
+    }
+}
+
+impl fmt::Debug for EncryptionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptionConfig")
+            .field("key", &"<redacted>")
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn aes_roundtrip() {
+        let config = EncryptionConfig::new(KEY);
+        let sealed = config.encrypt("secret message").unwrap();
+        assert_ne!(sealed, "secret message");
+        assert_eq!(config.decrypt(&sealed).unwrap(), "secret message");
+    }
+
+    #[test]
+    fn chacha_roundtrip() {
+        let config =
+            EncryptionConfig::new(KEY).with_algorithm(EncryptionAlgorithm::ChaCha20Poly1305);
+        let sealed = config.encrypt("secret message").unwrap();
+        assert_eq!(config.decrypt(&sealed).unwrap(), "secret message");
+    }
+
+    #[test]
+    fn each_encryption_uses_fresh_nonce() {
+        let config = EncryptionConfig::new(KEY);
+        let a = config.encrypt("same").unwrap();
+        let b = config.encrypt("same").unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn wrong_key_fails_to_decrypt() {
+        let sealed = EncryptionConfig::new(KEY).encrypt("secret").unwrap();
+        let result = EncryptionConfig::new([8u8; 32]).decrypt(&sealed);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn wrong_algorithm_fails_to_decrypt() {
+        let sealed = EncryptionConfig::new(KEY).encrypt("secret").unwrap();
+        let result = EncryptionConfig::new(KEY)
+            .with_algorithm(EncryptionAlgorithm::ChaCha20Poly1305)
+            .decrypt(&sealed);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn tampered_ciphertext_fails_to_decrypt() {
+        let config = EncryptionConfig::new(KEY);
+        let mut sealed = BASE64.decode(config.encrypt("secret").unwrap()).unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0xff;
+        assert!(config.decrypt(&BASE64.encode(sealed)).is_err());
+    }
+
+    #[test]
+    fn short_input_fails_to_decrypt() {
+        let config = EncryptionConfig::new(KEY);
+        assert!(config.decrypt(&BASE64.encode([0u8; 4])).is_err());
+        assert!(config.decrypt("not base64!").is_err());
+    }
+
+    #[test]
+    fn debug_redacts_key() {
+        let debug = format!("{:?}", EncryptionConfig::new(KEY));
+        assert!(debug.contains("redacted"));
+        assert!(!debug.contains('7'));
+    }
+}
@@ -25,6 +25,13 @@ pub enum PersistenceErrorKind {
         /// Error message from database
         message: String,
     },
+    /// Failed to connect to a remote libSQL database
+    RemoteConnectionFailed {
+        /// URL of the remote database
+        url: String,
+        /// Why the connection failed
+        reason: String,
+    },
     /// Failed to initialize schema
     SchemaInit {
         /// Error message from database
@@ -85,6 +92,16 @@ pub enum PersistenceErrorKind {
         /// Error message
         message: String,
     },
+    /// Encrypting content failed
+    EncryptionFailed {
+        /// Error message
+        message: String,
+    },
+    /// Decrypting content failed
+    DecryptionFailed {
+        /// Error message
+        message: String,
+    },
 }
 
 impl PersistenceError {
@@ -121,6 +138,15 @@ impl PersistenceError {
         })
     }
 
+    /// Creates a remote connection failed error.
+    #[must_use]
+    pub fn remote_connection_failed(url: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::new(PersistenceErrorKind::RemoteConnectionFailed {
+            url: url.into(),
+            reason: reason.into(),
+        })
+    }
+
     /// Creates a schema initialization error.
     #[must_use]
     pub fn schema_init(message: impl Into<String>) -> Self {
@@ -208,15 +234,32 @@ impl PersistenceError {
         })
     }
 
+    /// Creates an encryption failed error.
+    #[must_use]
+    pub fn encryption_failed(message: impl Into<String>) -> Self {
+        Self::new(PersistenceErrorKind::EncryptionFailed {
+            message: message.into(),
+        })
+    }
+
+    /// Creates a decryption failed error.
+    #[must_use]
+    pub fn decryption_failed(message: impl Into<String>) -> Self {
+        Self::new(PersistenceErrorKind::DecryptionFailed {
+            message: message.into(),
+        })
+    }
+
     /// Returns true if this error is retriable.
     ///
-    /// Connection errors and transaction failures are typically transient
-    /// and may succeed on retry.
+    /// Connection errors (local or remote) and transaction failures are
+    /// typically transient and may succeed on retry.
     #[must_use]
     pub fn is_retriable(&self) -> bool {
         matches!(
             *self.kind,
             PersistenceErrorKind::ConnectionError { .. }
+                | PersistenceErrorKind::RemoteConnectionFailed { .. }
                 | PersistenceErrorKind::TransactionFailed { .. }
         )
     }
@@ -248,6 +291,13 @@ impl fmt::Display for PersistenceError {
                     path, message
                 )
             }
+            PersistenceErrorKind::RemoteConnectionFailed { url, reason } => {
+                write!(
+                    f,
+                    "failed to connect to remote database at '{}': {}; check the URL and auth token",
+                    url, reason
+                )
+            }
             PersistenceErrorKind::SchemaInit { message } => {
                 write!(
                     f,
@@ -296,6 +346,16 @@ impl fmt::Display for PersistenceError {
             PersistenceErrorKind::VectorSearchFailed { message } => {
                 write!(f, "vector search failed: {}", message)
             }
+            PersistenceErrorKind::EncryptionFailed { message } => {
+                write!(f, "failed to encrypt content: {}", message)
+            }
+            PersistenceErrorKind::DecryptionFailed { message } => {
+                write!(
+                    f,
+                    "failed to decrypt content: {}; check the configured encryption key",
+                    message
+                )
+            }
         }
     }
 }
@@ -314,6 +374,15 @@ mod tests {
         assert!(msg.contains("permission denied"));
     }
 
+    #[test]
+    fn persistence_error_remote_connection_display() {
+        let error = PersistenceError::remote_connection_failed("libsql://db.turso.io", "401");
+        let msg = error.to_string();
+        assert!(msg.contains("libsql://db.turso.io"));
+        assert!(msg.contains("auth token"));
+        assert!(error.is_retriable());
+    }
+
     #[test]
     fn persistence_error_connection_is_retriable() {
         let error = PersistenceError::connection_error("timeout");
@@ -429,4 +498,13 @@ mod tests {
         assert!(msg.contains("vector search"));
         assert!(msg.contains("index corrupted"));
     }
+
+    #[test]
+    fn persistence_error_decryption_failed_display() {
+        let error = PersistenceError::decryption_failed("authentication failed");
+        let msg = error.to_string();
+        assert!(msg.contains("decrypt"));
+        assert!(msg.contains("authentication failed"));
+        assert!(!error.is_retriable());
+    }
 }
@@ -8,9 +8,11 @@
 //!
 //! - [`MemoryStore`]: Actor that manages all database operations asynchronously
 //! - [`PersistenceConfig`]: Configuration for database connections
+//! - [`EncryptionConfig`]: Optional at-rest encryption of stored content
 //! - [`AgentStateSnapshot`]: Serializable agent state for persistence
 //! - [`Embedding`]: Vector embeddings for semantic memory search
 //! - [`EmbeddingProvider`]: Trait for embedding generation services
+//! - [`RerankProvider`]: Trait for reranking memory search results
 //! - [`Memory`]: A memory entry with optional embedding
 //! - [`ContextWindow`]: Context window management for LLM interactions
 //!
@@ -44,6 +46,9 @@
 //!         agent_id: agent_id.clone(),
 //!         content: "User prefers dark mode".to_string(),
 //!         embedding: Some(embedding),
+//!         importance: None,
+//!         expires_at: None,
+//!         dedup_threshold: None,
 //!     }).await;
 //!
 //!     runtime.shutdown_all().await.unwrap();
@@ -52,6 +57,7 @@
 
 mod context;
 mod embeddings;
+mod encryption;
 mod error;
 pub mod persistence;
 mod store;
@@ -64,19 +70,40 @@ pub use context::{
 
 // Re-export embedding types
 pub use embeddings::{
-    Embedding, EmbeddingError, EmbeddingProvider, Memory, ScoredMemory, StubEmbeddingProvider,
+    CohereRerankProvider, Embedding, EmbeddingCache, EmbeddingCacheStats, EmbeddingError,
+    EmbeddingProvider, Memory, OpenAIEmbeddingProvider, RerankError, RerankProvider, Reranking,
+    ScoredMemory, StubEmbeddingProvider, DEFAULT_MEMORY_IMPORTANCE,
 };
 
+// Re-export encryption types
+pub use encryption::{EncryptionAlgorithm, EncryptionConfig};
+
 // Re-export error types
 pub use error::{PersistenceError, PersistenceErrorKind};
 
 // Re-export persistence types
 pub use persistence::{
-    count_memories_for_agent, delete_agent_state, delete_memories_for_agent, delete_memory,
-    load_memories_for_agent, save_memory, search_memories_by_embedding, AgentStateSnapshot,
+    count_memories_for_agent, delete_agent_state, delete_memories, delete_memories_for_agent,
+    delete_memory, hybrid_search_memories, load_memories_for_agent, prune_expired_memories,
+    migrate_agent_states, migrate_snapshot, replace_memories_for_agent, save_memories,
+    save_memory, search_memories_by_embedding, AgentStateSnapshot, MigrationFn,
     PersistenceConfig, SCHEMA_VERSION,
 };
 
+// Re-export tool schema version tagging
+pub use persistence::{load_conversation_tool_versions, save_conversation_tool_versions};
+
+// Re-export maintenance helpers
+pub use persistence::{
+    check_embedding_dimension, db_stats, vacuum, DbStats, VacuumConfig, VacuumMode, VacuumStats,
+};
+
+// Re-export encryption helpers
+pub use persistence::{
+    decrypt_memories, decrypt_messages, encrypt_memory, encrypt_message, reencrypt_all,
+    ReencryptionStats,
+};
+
 // Re-export session and heartbeat types
 pub use persistence::{
     complete_entry, create_heartbeat_entry, create_session, delete_session, list_due_entries,
@@ -84,6 +111,12 @@ pub use persistence::{
     update_entry_after_run, HeartbeatEntry, SessionInfo,
 };
 
+// Re-export conversation summary helpers
+pub use persistence::{
+    conversation_tag, load_conversation_summaries, save_conversation_summary,
+    summary_conversation_id, CONVERSATION_SUMMARY_TAG,
+};
+
 // Re-export memory graph types
 pub use persistence::{
     create_memory_relation, find_memories_by_tag, get_related_memories, tag_memory, MemoryRelation,
@@ -93,6 +126,12 @@ pub use persistence::{
 pub use store::{
     // Agent state messages
     AgentStateLoaded,
+    // Bulk memory messages
+    BulkDeleteMemories,
+    BulkDeleted,
+    BulkMemoryInput,
+    BulkStoreMemories,
+    BulkStored,
     // Context window messages
     ContextWindowResponse,
     // Conversation messages
@@ -104,14 +143,24 @@ pub use store::{
     DeleteAgentMemories,
     DeleteConversation,
     DeleteMemory,
+    ExpiredMemoriesPruned,
+    // Export and import messages
+    ExportAgentMemories,
+    ExportedMemories,
+    ExportedMemory,
     GetContextWindow,
     GetLatestConversation,
+    HybridSearchMemories,
+    ImportAgentMemories,
+    ImportMergeStrategy,
     InitMemoryStore,
     LatestConversationResponse,
     ListConversations,
     LoadAgentState,
     LoadConversation,
+    LoadConversationSummaries,
     LoadMemories,
+    MemoriesImported,
     MemoriesLoaded,
     MemorySearchResults,
     // Core store types
@@ -120,8 +169,24 @@ pub use store::{
     MemoryStored,
     // Message store messages
     MessageSaved,
+    PruneExpiredMemories,
+    // Encryption messages
+    ReencryptAll,
+    ReencryptionComplete,
     SaveAgentState,
     SaveMessage,
     SearchMemories,
+    StoreConversationSummary,
     StoreMemory,
+    // Tool schema version messages
+    ConversationToolVersions,
+    LoadConversationToolVersions,
+    TagConversationToolVersions,
+    // Maintenance messages
+    DbStatsResponse,
+    GetDbStats,
+    MemoryStoreVacuumed,
+    VacuumMemoryStore,
 };
+pub(crate) use context::build_system_with_memories;
+pub(crate) use store::query_store;
@@ -5,15 +5,29 @@
 //! async and use libSQL for database access.
 
 use crate::memory::embeddings::{Embedding, Memory, ScoredMemory};
+use crate::memory::encryption::EncryptionConfig;
 use crate::memory::error::PersistenceError;
 use crate::messages::{Message, MessageRole};
+use crate::tools::versioning::ToolSchemaVersion;
 use crate::types::{AgentId, ConversationId, MemoryId, MessageId};
+use chrono::{DateTime, SecondsFormat, Utc};
 use libsql::{Connection, Database};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 /// Database schema version for migrations.
-pub const SCHEMA_VERSION: u32 = 1;
+///
+/// Also the version of [`AgentStateSnapshot`]; bump it on breaking changes
+/// to either and register a migration for the snapshot transition in
+/// [`snapshot_migration`] if its format changed.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Upgrades a stored [`AgentStateSnapshot`]'s JSON from one version to the
+/// next.
+pub type MigrationFn =
+    Box<dyn Fn(serde_json::Value) -> Result<serde_json::Value, PersistenceError> + Send + Sync>;
 
 /// SQL statements for schema creation.
 const CREATE_SCHEMA: &str = r"
@@ -47,11 +61,28 @@ CREATE TABLE IF NOT EXISTS memories (
     agent_id TEXT NOT NULL,
     content TEXT NOT NULL,
     embedding BLOB,
-    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    importance REAL NOT NULL DEFAULT 0.5,
+    expires_at TEXT,
+    updated_at TEXT
 );
 
 CREATE INDEX IF NOT EXISTS idx_memories_agent_id ON memories(agent_id);
 
+CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(memory_id UNINDEXED, content);
+
+CREATE TRIGGER IF NOT EXISTS memories_fts_insert AFTER INSERT ON memories BEGIN
+    INSERT INTO memories_fts (memory_id, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS memories_fts_delete AFTER DELETE ON memories BEGIN
+    DELETE FROM memories_fts WHERE memory_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS memories_fts_update AFTER UPDATE OF content ON memories BEGIN
+    UPDATE memories_fts SET content = new.content WHERE memory_id = old.id;
+END;
+
 CREATE TABLE IF NOT EXISTS agent_state (
     agent_id TEXT PRIMARY KEY,
     state TEXT NOT NULL,
@@ -101,13 +132,101 @@ CREATE TABLE IF NOT EXISTS memory_tags (
 );
 
 CREATE INDEX IF NOT EXISTS idx_memory_tags_tag ON memory_tags(tag);
+
+CREATE TABLE IF NOT EXISTS store_metadata (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS conversation_tool_versions (
+    conversation_id TEXT NOT NULL,
+    tool_name TEXT NOT NULL,
+    schema_hash TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    PRIMARY KEY (conversation_id, tool_name)
+);
 ";
 
+/// How [`vacuum`] compacts the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VacuumMode {
+    /// `PRAGMA incremental_vacuum`: returns free pages to the OS without
+    /// rewriting the database
+    Incremental,
+    /// `VACUUM`: rewrites the whole database into the smallest file, blocking
+    /// other writers while it runs
+    Full,
+    /// Incremental, or full once fragmentation exceeds
+    /// [`VacuumConfig::min_fragmentation`]
+    Auto,
+}
+
+/// Vacuum settings for [`vacuum`] and periodic auto-vacuuming.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VacuumConfig {
+    /// How to vacuum
+    pub mode: VacuumMode,
+    /// Fragmentation ratio (free pages / total pages) above which
+    /// [`VacuumMode::Auto`] runs a full vacuum
+    pub min_fragmentation: f32,
+}
+
+impl VacuumConfig {
+    /// How often auto-vacuuming runs.
+    pub const AUTO_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+    /// Creates a config with the given mode and the default threshold.
+    #[must_use]
+    pub fn new(mode: VacuumMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the fragmentation ratio above which auto mode runs a full vacuum.
+    #[must_use]
+    pub fn with_min_fragmentation(mut self, ratio: f32) -> Self {
+        self.min_fragmentation = ratio;
+        self
+    }
+}
+
+impl Default for VacuumConfig {
+    fn default() -> Self {
+        Self {
+            mode: VacuumMode::Auto,
+            min_fragmentation: 0.25,
+        }
+    }
+}
+
+/// URL scheme of remote libSQL (Turso) databases.
+const REMOTE_SCHEME: &str = "libsql://";
+
+/// Environment variable naming a remote database for [`PersistenceConfig::from_env`].
+const DATABASE_URL_ENV: &str = "TURSO_DATABASE_URL";
+
+/// Environment variable holding the remote database's auth token.
+const AUTH_TOKEN_ENV: &str = "TURSO_AUTH_TOKEN";
+
 /// Configuration for the persistence layer.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// The auth token is never printed: the `Debug` implementation redacts it.
+#[derive(Clone, PartialEq)]
 pub struct PersistenceConfig {
-    /// Path to the database file
+    /// Path to the database file, or a `libsql://` URL for a remote database
     pub db_path: String,
+    /// Auth token for a remote database
+    pub auth_token: Option<String>,
+    /// Optional at-rest encryption for message and memory content
+    pub encryption: Option<EncryptionConfig>,
+    /// Periodic vacuuming, run every [`VacuumConfig::AUTO_INTERVAL`]
+    pub auto_vacuum: Option<VacuumConfig>,
+    /// How often the memory store prunes expired memories, if at all
+    pub memory_prune_interval: Option<Duration>,
+    /// Embedding dimension the store must hold, checked on initialization
+    pub embedding_dimension: Option<usize>,
 }
 
 impl PersistenceConfig {
@@ -116,17 +235,53 @@ impl PersistenceConfig {
     pub fn new(db_path: impl Into<String>) -> Self {
         Self {
             db_path: db_path.into(),
+            auth_token: None,
+            encryption: None,
+            auto_vacuum: None,
+            memory_prune_interval: None,
+            embedding_dimension: None,
         }
     }
 
-    /// Creates a config for an in-memory database (for testing).
+    /// Creates a config for a remote libSQL database such as Turso.
+    ///
+    /// Any scheme on `url` (for example `https://`) is replaced with
+    /// `libsql://`.
     #[must_use]
-    pub fn in_memory() -> Self {
+    pub fn remote(url: &str, auth_token: &str) -> Self {
+        let host = url.split_once("://").map_or(url, |(_, rest)| rest);
         Self {
-            db_path: ":memory:".to_string(),
+            auth_token: Some(auth_token.to_string()),
+            ..Self::new(format!("{REMOTE_SCHEME}{host}"))
+        }
+    }
+
+    /// Creates a config from the `TURSO_DATABASE_URL` and `TURSO_AUTH_TOKEN`
+    /// environment variables.
+    ///
+    /// Falls back to the default local database when `TURSO_DATABASE_URL` is
+    /// unset or empty.
+    #[must_use]
+    pub fn from_env() -> Self {
+        Self::from_env_values(
+            std::env::var(DATABASE_URL_ENV).ok(),
+            std::env::var(AUTH_TOKEN_ENV).ok(),
+        )
+    }
+
+    fn from_env_values(url: Option<String>, auth_token: Option<String>) -> Self {
+        match url.filter(|url| !url.is_empty()) {
+            Some(url) => Self::remote(&url, auth_token.as_deref().unwrap_or_default()),
+            None => Self::default(),
         }
     }
 
+    /// Creates a config for an in-memory database (for testing).
+    #[must_use]
+    pub fn in_memory() -> Self {
+        Self::new(":memory:")
+    }
+
     /// Creates a config for a specific agent's database.
     #[must_use]
     pub fn for_agent(agent_id: &AgentId, base_path: &Path) -> Self {
@@ -134,7 +289,50 @@ impl PersistenceConfig {
             .join(format!("{}.db", agent_id))
             .to_string_lossy()
             .to_string();
-        Self { db_path }
+        Self::new(db_path)
+    }
+
+    /// Enables AES-256-GCM encryption of stored message and memory content.
+    ///
+    /// See the [`encryption`](crate::memory::EncryptionConfig) docs for key
+    /// management guidance. The key should come from a secret manager or the
+    /// environment, never from source code.
+    #[must_use]
+    pub fn with_encryption(self, key: [u8; 32]) -> Self {
+        self.with_encryption_config(EncryptionConfig::new(key))
+    }
+
+    /// Enables encryption with an explicit [`EncryptionConfig`].
+    #[must_use]
+    pub fn with_encryption_config(mut self, encryption: EncryptionConfig) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Enables periodic vacuuming by the memory store.
+    #[must_use]
+    pub fn with_auto_vacuum(mut self, config: VacuumConfig) -> Self {
+        self.auto_vacuum = Some(config);
+        self
+    }
+
+    /// Enables periodic pruning of expired memories by the memory store.
+    #[must_use]
+    pub fn with_memory_pruning(mut self, interval: Duration) -> Self {
+        self.memory_prune_interval = Some(interval);
+        self
+    }
+
+    /// Requires stored embeddings to have `dimension` values.
+    ///
+    /// Pass the configured provider's
+    /// [`EmbeddingProvider::dimension`](crate::memory::EmbeddingProvider::dimension)
+    /// so that switching to a model with a different dimension fails at
+    /// startup instead of silently breaking similarity search.
+    #[must_use]
+    pub fn with_embedding_dimension(mut self, dimension: usize) -> Self {
+        self.embedding_dimension = Some(dimension);
+        self
     }
 
     /// Returns true if this is an in-memory database.
@@ -142,6 +340,18 @@ impl PersistenceConfig {
     pub fn is_in_memory(&self) -> bool {
         self.db_path == ":memory:"
     }
+
+    /// Returns true if this is a remote libSQL database.
+    #[must_use]
+    pub fn is_remote(&self) -> bool {
+        self.db_path.starts_with(REMOTE_SCHEME)
+    }
+
+    /// Returns true if stored content is encrypted.
+    #[must_use]
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
 }
 
 impl Default for PersistenceConfig {
@@ -150,9 +360,30 @@ impl Default for PersistenceConfig {
     }
 }
 
+impl fmt::Debug for PersistenceConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistenceConfig")
+            .field("db_path", &self.db_path)
+            .field(
+                "auth_token",
+                &self.auth_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("encryption", &self.encryption)
+            .field("auto_vacuum", &self.auto_vacuum)
+            .field("memory_prune_interval", &self.memory_prune_interval)
+            .field("embedding_dimension", &self.embedding_dimension)
+            .finish()
+    }
+}
+
 /// Agent state snapshot for persistence.
+///
+/// Stored snapshots from older versions are upgraded by
+/// [`migrate_snapshot`] when loaded.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AgentStateSnapshot {
+    /// Format version; [`save_agent_state`] always writes [`SCHEMA_VERSION`]
+    pub schema_version: u32,
     /// The agent ID
     pub agent_id: AgentId,
     /// Current conversation ID (if any)
@@ -163,6 +394,72 @@ pub struct AgentStateSnapshot {
     pub system_prompt: String,
 }
 
+/// Returns the migration from snapshot version `from` to `from + 1`, or
+/// `None` if that transition left the snapshot format unchanged.
+fn snapshot_migration(from: u32) -> Option<MigrationFn> {
+    match from {
+        1 => Some(Box::new(migrate_snapshot_v1_to_v2)),
+        _ => None,
+    }
+}
+
+/// Version 2 added `conversation_id`.
+fn migrate_snapshot_v1_to_v2(
+    mut value: serde_json::Value,
+) -> Result<serde_json::Value, PersistenceError> {
+    value
+        .as_object_mut()
+        .ok_or_else(|| {
+            PersistenceError::deserialization_failed("agent state snapshot is not a JSON object")
+        })?
+        .entry("conversation_id")
+        .or_insert(serde_json::Value::Null);
+    Ok(value)
+}
+
+/// Returns a stored snapshot's version; snapshots written before
+/// versioning are version 1.
+fn snapshot_version(value: &serde_json::Value) -> Result<u32, PersistenceError> {
+    match value.get("schema_version") {
+        None => Ok(1),
+        Some(version) => version
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                PersistenceError::deserialization_failed(format!(
+                    "invalid agent state schema_version: {version}"
+                ))
+            }),
+    }
+}
+
+/// Upgrades a stored snapshot to [`SCHEMA_VERSION`] and parses it.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot is from a newer version, a migration
+/// fails, or the upgraded JSON is not a valid snapshot.
+pub fn migrate_snapshot(
+    mut value: serde_json::Value,
+) -> Result<AgentStateSnapshot, PersistenceError> {
+    let stored = snapshot_version(&value)?;
+    if stored > SCHEMA_VERSION {
+        return Err(PersistenceError::deserialization_failed(format!(
+            "agent state schema_version {stored} is newer than supported version {SCHEMA_VERSION}"
+        )));
+    }
+    for from in stored..SCHEMA_VERSION {
+        if let Some(migrate) = snapshot_migration(from) {
+            value = migrate(value)?;
+        }
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("schema_version".to_string(), SCHEMA_VERSION.into());
+    }
+    serde_json::from_value(value)
+        .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))
+}
+
 /// Opens a database connection.
 ///
 /// # Arguments
@@ -177,6 +474,16 @@ pub struct AgentStateSnapshot {
 ///
 /// Returns an error if the database cannot be opened.
 pub async fn open_database(config: &PersistenceConfig) -> Result<Database, PersistenceError> {
+    if config.is_remote() {
+        return libsql::Builder::new_remote(
+            config.db_path.clone(),
+            config.auth_token.clone().unwrap_or_default(),
+        )
+        .build()
+        .await
+        .map_err(|e| PersistenceError::remote_connection_failed(&config.db_path, e.to_string()));
+    }
+
     let builder = if config.is_in_memory() {
         libsql::Builder::new_local(":memory:")
     } else {
@@ -201,9 +508,20 @@ pub async fn open_database(config: &PersistenceConfig) -> Result<Database, Persi
 ///
 /// Returns an error if the schema cannot be created.
 pub async fn initialize_schema(conn: &Connection) -> Result<(), PersistenceError> {
+    // Must precede table creation to take effect on a new database; existing
+    // databases pick it up on their next full VACUUM.
+    conn.execute_batch("PRAGMA auto_vacuum = INCREMENTAL;")
+        .await
+        .map_err(|e| PersistenceError::schema_init(e.to_string()))?;
+
     conn.execute_batch(CREATE_SCHEMA)
         .await
         .map_err(|e| PersistenceError::schema_init(e.to_string()))?;
+    migrate_memory_columns(conn).await?;
+    let migrated = migrate_agent_states(conn).await?;
+    if migrated > 0 {
+        tracing::info!(migrated, "Migrated stored agent state snapshots");
+    }
 
     // Set schema version
     conn.execute(
@@ -216,6 +534,185 @@ pub async fn initialize_schema(conn: &Connection) -> Result<(), PersistenceError
     Ok(())
 }
 
+/// Rewrites stored agent state snapshots older than [`SCHEMA_VERSION`] in
+/// the current format.
+///
+/// # Returns
+///
+/// The number of snapshots migrated.
+///
+/// # Errors
+///
+/// Returns an error if a query fails or a snapshot cannot be migrated.
+pub async fn migrate_agent_states(conn: &Connection) -> Result<usize, PersistenceError> {
+    let mut rows = conn
+        .query("SELECT agent_id, state FROM agent_state", ())
+        .await
+        .map_err(|e| PersistenceError::query_failed("migrate_agent_states", e.to_string()))?;
+
+    let mut outdated = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| PersistenceError::query_failed("migrate_agent_states", e.to_string()))?
+    {
+        let agent_id: String = row
+            .get(0)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let state_json: String = row
+            .get(1)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let value: serde_json::Value = serde_json::from_str(&state_json)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        if snapshot_version(&value)? < SCHEMA_VERSION {
+            outdated.push((agent_id, value));
+        }
+    }
+
+    for (agent_id, value) in &outdated {
+        let snapshot = migrate_snapshot(value.clone())?;
+        let state_json = serde_json::to_string(&snapshot)
+            .map_err(|e| PersistenceError::serialization_failed(e.to_string()))?;
+        conn.execute(
+            "UPDATE agent_state SET state = ?2 WHERE agent_id = ?1",
+            [agent_id.clone(), state_json],
+        )
+        .await
+        .map_err(|e| PersistenceError::query_failed("migrate_agent_states", e.to_string()))?;
+    }
+
+    Ok(outdated.len())
+}
+
+/// Memory columns added after schema version 1, with their definitions.
+const MEMORY_COLUMN_MIGRATIONS: [(&str, &str); 3] = [
+    ("importance", "REAL NOT NULL DEFAULT 0.5"),
+    ("expires_at", "TEXT"),
+    ("updated_at", "TEXT"),
+];
+
+/// Adds [`MEMORY_COLUMN_MIGRATIONS`] to a `memories` table created by an
+/// older schema version.
+async fn migrate_memory_columns(conn: &Connection) -> Result<(), PersistenceError> {
+    let mut rows = conn
+        .query("PRAGMA table_info(memories)", ())
+        .await
+        .map_err(|e| PersistenceError::schema_init(e.to_string()))?;
+    let mut columns = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| PersistenceError::schema_init(e.to_string()))?
+    {
+        let name: String = row
+            .get(1)
+            .map_err(|e| PersistenceError::schema_init(e.to_string()))?;
+        columns.push(name);
+    }
+
+    for (column, definition) in MEMORY_COLUMN_MIGRATIONS {
+        if !columns.iter().any(|c| c == column) {
+            conn.execute(
+                &format!("ALTER TABLE memories ADD COLUMN {column} {definition}"),
+                (),
+            )
+            .await
+            .map_err(|e| PersistenceError::schema_init(e.to_string()))?;
+        }
+    }
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_memories_expires_at ON memories(expires_at)",
+        (),
+    )
+    .await
+    .map_err(|e| PersistenceError::schema_init(e.to_string()))?;
+
+    // Index memories stored before the full-text table existed
+    let indexed = query_u64(conn, "SELECT COUNT(*) FROM memories_fts").await?;
+    let stored = query_u64(conn, "SELECT COUNT(*) FROM memories").await?;
+    if indexed != stored {
+        conn.execute_batch(
+            "DELETE FROM memories_fts;
+             INSERT INTO memories_fts (memory_id, content) SELECT id, content FROM memories;",
+        )
+        .await
+        .map_err(|e| PersistenceError::schema_init(e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// `store_metadata` key recording the dimension of stored embeddings.
+const EMBEDDING_DIMENSION_KEY: &str = "embedding_dimension";
+
+/// Checks that `dimension` matches the embeddings already in the database,
+/// then records it for later checks.
+///
+/// The recorded dimension is compared when present; otherwise any stored
+/// embedding is measured.
+///
+/// # Errors
+///
+/// Returns an embedding dimension mismatch error if the stored dimension
+/// differs, or an error if a query fails.
+pub async fn check_embedding_dimension(
+    conn: &Connection,
+    dimension: usize,
+) -> Result<(), PersistenceError> {
+    let mut rows = conn
+        .query(
+            "SELECT value FROM store_metadata WHERE key = ?1",
+            [EMBEDDING_DIMENSION_KEY],
+        )
+        .await
+        .map_err(|e| PersistenceError::query_failed("check_embedding_dimension", e.to_string()))?;
+    let recorded =
+        match rows.next().await.map_err(|e| {
+            PersistenceError::query_failed("check_embedding_dimension", e.to_string())
+        })? {
+            Some(row) => {
+                let value: String = row
+                    .get(0)
+                    .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+                Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?,
+                )
+            }
+            None => None,
+        };
+
+    let stored = match recorded {
+        Some(recorded) => Some(recorded),
+        None => {
+            let bytes = query_u64(
+                conn,
+                "SELECT COALESCE(MAX(length(embedding)), 0) FROM memories WHERE embedding IS NOT NULL",
+            )
+            .await?;
+            usize::try_from(bytes / 4).ok().filter(|d| *d > 0)
+        }
+    };
+    if let Some(stored) = stored {
+        if stored != dimension {
+            return Err(PersistenceError::embedding_dimension_mismatch(
+                stored, dimension,
+            ));
+        }
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO store_metadata (key, value) VALUES (?1, ?2)",
+        [EMBEDDING_DIMENSION_KEY.to_string(), dimension.to_string()],
+    )
+    .await
+    .map_err(|e| PersistenceError::query_failed("check_embedding_dimension", e.to_string()))?;
+
+    Ok(())
+}
+
 /// Creates a new conversation record.
 ///
 /// # Arguments
@@ -314,7 +811,7 @@ pub async fn load_conversation_messages(
     let mut rows = conn
         .query(
             "SELECT role, content, tool_calls, tool_call_id FROM messages
-             WHERE conversation_id = ?1 ORDER BY created_at ASC",
+             WHERE conversation_id = ?1 ORDER BY created_at ASC, rowid ASC",
             [conversation_id.to_string()],
         )
         .await
@@ -351,6 +848,8 @@ pub async fn load_conversation_messages(
             content,
             tool_calls,
             tool_call_id,
+            timestamp: None,
+            is_injected: false,
         });
     }
 
@@ -413,7 +912,11 @@ pub async fn save_agent_state(
     conn: &Connection,
     snapshot: &AgentStateSnapshot,
 ) -> Result<(), PersistenceError> {
-    let state_json = serde_json::to_string(snapshot)
+    let snapshot = AgentStateSnapshot {
+        schema_version: SCHEMA_VERSION,
+        ..snapshot.clone()
+    };
+    let state_json = serde_json::to_string(&snapshot)
         .map_err(|e| PersistenceError::serialization_failed(e.to_string()))?;
 
     conn.execute(
@@ -436,7 +939,8 @@ pub async fn save_agent_state(
 ///
 /// # Returns
 ///
-/// The agent state snapshot if one exists, None otherwise.
+/// The agent state snapshot if one exists, None otherwise. Snapshots from
+/// older versions are migrated; see [`migrate_snapshot`].
 ///
 /// # Errors
 ///
@@ -461,9 +965,9 @@ pub async fn load_agent_state(
         let state_json: String = row
             .get(0)
             .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
-        let snapshot: AgentStateSnapshot = serde_json::from_str(&state_json)
+        let value: serde_json::Value = serde_json::from_str(&state_json)
             .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
-        Ok(Some(snapshot))
+        migrate_snapshot(value).map(Some)
     } else {
         Ok(None)
     }
@@ -577,81 +1081,329 @@ fn parse_message_role(s: &str) -> Result<MessageRole, PersistenceError> {
 }
 
 // =============================================================================
-// Memory Functions
+// Encryption
 // =============================================================================
 
-/// Saves a memory with optional embedding.
+/// Counts of rows rewritten by [`reencrypt_all`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReencryptionStats {
+    /// Number of message rows re-encrypted
+    pub messages: usize,
+    /// Number of memory rows re-encrypted
+    pub memories: usize,
+}
+
+/// Returns a copy of `message` with its content sealed under `encryption`.
 ///
-/// # Arguments
+/// Passes the message through unchanged when `encryption` is `None`.
 ///
-/// * `conn` - The database connection
-/// * `memory` - The memory to save
+/// # Errors
 ///
-/// # Returns
+/// Returns an error if encryption fails.
+pub fn encrypt_message(
+    message: &Message,
+    encryption: Option<&EncryptionConfig>,
+) -> Result<Message, PersistenceError> {
+    let mut message = message.clone();
+    if let Some(encryption) = encryption {
+        message.content = encryption.encrypt(&message.content)?;
+    }
+    Ok(message)
+}
+
+/// Decrypts the content of every message in place.
 ///
-/// The ID of the saved memory.
+/// No-op when `encryption` is `None`.
 ///
 /// # Errors
 ///
-/// Returns an error if the insert fails.
-pub async fn save_memory(conn: &Connection, memory: &Memory) -> Result<MemoryId, PersistenceError> {
-    let embedding_bytes = memory.embedding.as_ref().map(Embedding::to_bytes);
-
-    conn.execute(
-        "INSERT INTO memories (id, agent_id, content, embedding, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5)",
-        libsql::params![
-            memory.id.to_string(),
-            memory.agent_id.to_string(),
-            memory.content.clone(),
-            embedding_bytes,
-            memory.created_at.clone(),
-        ],
-    )
-    .await
-    .map_err(|e| PersistenceError::query_failed("save_memory", e.to_string()))?;
-
-    Ok(memory.id.clone())
+/// Returns an error if any message cannot be decrypted.
+pub fn decrypt_messages(
+    messages: &mut [Message],
+    encryption: Option<&EncryptionConfig>,
+) -> Result<(), PersistenceError> {
+    if let Some(encryption) = encryption {
+        for message in messages {
+            message.content = encryption.decrypt(&message.content)?;
+        }
+    }
+    Ok(())
 }
 
-/// Searches memories by embedding similarity.
+/// Returns a copy of `memory` with its content sealed under `encryption`.
 ///
-/// Uses in-application cosine similarity calculation since libSQL
-/// vector extension requires specific setup. Loads all memories with
-/// embeddings for the agent and computes similarity in Rust.
+/// Passes the memory through unchanged when `encryption` is `None`.
 ///
-/// # Arguments
+/// # Errors
 ///
-/// * `conn` - The database connection
-/// * `agent_id` - The agent to search within
-/// * `query_embedding` - The query embedding
-/// * `limit` - Maximum results to return
-/// * `min_similarity` - Optional minimum similarity threshold
+/// Returns an error if encryption fails.
+pub fn encrypt_memory(
+    memory: &Memory,
+    encryption: Option<&EncryptionConfig>,
+) -> Result<Memory, PersistenceError> {
+    let mut memory = memory.clone();
+    if let Some(encryption) = encryption {
+        memory.content = encryption.encrypt(&memory.content)?;
+    }
+    Ok(memory)
+}
+
+/// Decrypts the content of every memory in place.
 ///
-/// # Returns
+/// No-op when `encryption` is `None`.
+///
+/// # Errors
+///
+/// Returns an error if any memory cannot be decrypted.
+pub fn decrypt_memories<'a>(
+    memories: impl IntoIterator<Item = &'a mut Memory>,
+    encryption: Option<&EncryptionConfig>,
+) -> Result<(), PersistenceError> {
+    if let Some(encryption) = encryption {
+        for memory in memories {
+            memory.content = encryption.decrypt(&memory.content)?;
+        }
+    }
+    Ok(())
+}
+
+/// Re-encrypts every message and memory row from `old` to `new`.
 ///
-/// Memories ranked by similarity score (highest first).
+/// All rows are rewritten inside a single transaction: if any row fails to
+/// decrypt under `old`, nothing is committed and the database stays readable
+/// with the old key.
 ///
 /// # Errors
 ///
-/// Returns an error if the query fails.
-pub async fn search_memories_by_embedding(
+/// Returns an error if a row cannot be decrypted with `old`, or if the
+/// transaction cannot be started or committed.
+pub async fn reencrypt_all(
     conn: &Connection,
-    agent_id: &AgentId,
-    query_embedding: &Embedding,
-    limit: usize,
-    min_similarity: Option<f32>,
-) -> Result<Vec<ScoredMemory>, PersistenceError> {
-    // Load all memories with embeddings for this agent
-    let mut rows = conn
-        .query(
-            "SELECT id, content, embedding, created_at FROM memories
-             WHERE agent_id = ?1 AND embedding IS NOT NULL
-             ORDER BY created_at DESC",
-            [agent_id.to_string()],
-        )
+    old: &EncryptionConfig,
+    new: &EncryptionConfig,
+) -> Result<ReencryptionStats, PersistenceError> {
+    let tx = conn
+        .transaction()
         .await
-        .map_err(|e| PersistenceError::query_failed("search_memories", e.to_string()))?;
+        .map_err(|e| PersistenceError::transaction_failed(e.to_string()))?;
+
+    let mut stats = ReencryptionStats::default();
+    for (table, counter) in [
+        ("messages", &mut stats.messages),
+        ("memories", &mut stats.memories),
+    ] {
+        let mut rows = tx
+            .query(&format!("SELECT id, content FROM {table}"), ())
+            .await
+            .map_err(|e| PersistenceError::query_failed("reencrypt_all", e.to_string()))?;
+
+        let mut rewritten = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| PersistenceError::query_failed("reencrypt_all", e.to_string()))?
+        {
+            let id: String = row
+                .get(0)
+                .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+            let content: String = row
+                .get(1)
+                .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+            rewritten.push((id, new.encrypt(&old.decrypt(&content)?)?));
+        }
+
+        for (id, content) in rewritten {
+            tx.execute(
+                &format!("UPDATE {table} SET content = ?1 WHERE id = ?2"),
+                [content, id],
+            )
+            .await
+            .map_err(|e| PersistenceError::query_failed("reencrypt_all", e.to_string()))?;
+            *counter += 1;
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| PersistenceError::transaction_failed(e.to_string()))?;
+
+    Ok(stats)
+}
+
+// =============================================================================
+// Memory Functions
+// =============================================================================
+
+/// Saves a memory with optional embedding.
+///
+/// # Arguments
+///
+/// * `conn` - The database connection
+/// * `memory` - The memory to save
+///
+/// # Returns
+///
+/// The ID of the saved memory.
+///
+/// # Errors
+///
+/// Returns an error if the insert fails.
+pub async fn save_memory(conn: &Connection, memory: &Memory) -> Result<MemoryId, PersistenceError> {
+    insert_memory(conn, memory, "save_memory").await?;
+    Ok(memory.id.clone())
+}
+
+/// Saves several memories in a single transaction.
+///
+/// Either every memory is saved or, on error, none are.
+///
+/// # Arguments
+///
+/// * `conn` - The database connection
+/// * `memories` - The memories to save
+///
+/// # Returns
+///
+/// The number of memories saved.
+///
+/// # Errors
+///
+/// Returns an error if any insert or the commit fails.
+pub async fn save_memories(
+    conn: &Connection,
+    memories: &[Memory],
+) -> Result<usize, PersistenceError> {
+    let tx = conn
+        .transaction()
+        .await
+        .map_err(|e| PersistenceError::transaction_failed(e.to_string()))?;
+
+    for memory in memories {
+        insert_memory(&tx, memory, "save_memories").await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| PersistenceError::transaction_failed(e.to_string()))?;
+
+    Ok(memories.len())
+}
+
+/// Replaces all of an agent's memories in a single transaction.
+///
+/// The agent's existing memories are deleted and `memories` inserted; on
+/// error the agent's memories are left unchanged.
+///
+/// # Arguments
+///
+/// * `conn` - The database connection
+/// * `agent_id` - The agent whose memories to replace
+/// * `memories` - The memories to save in their place
+///
+/// # Returns
+///
+/// The number of memories saved.
+///
+/// # Errors
+///
+/// Returns an error if the delete, any insert, or the commit fails.
+pub async fn replace_memories_for_agent(
+    conn: &Connection,
+    agent_id: &AgentId,
+    memories: &[Memory],
+) -> Result<usize, PersistenceError> {
+    let tx = conn
+        .transaction()
+        .await
+        .map_err(|e| PersistenceError::transaction_failed(e.to_string()))?;
+
+    tx.execute(
+        "DELETE FROM memories WHERE agent_id = ?1",
+        [agent_id.to_string()],
+    )
+    .await
+    .map_err(|e| PersistenceError::query_failed("replace_memories_for_agent", e.to_string()))?;
+    for memory in memories {
+        insert_memory(&tx, memory, "replace_memories_for_agent").await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|e| PersistenceError::transaction_failed(e.to_string()))?;
+
+    Ok(memories.len())
+}
+
+/// Inserts one memory row, reporting failures as `operation`.
+async fn insert_memory(
+    conn: &Connection,
+    memory: &Memory,
+    operation: &str,
+) -> Result<(), PersistenceError> {
+    let embedding_bytes = memory.embedding.as_ref().map(Embedding::to_bytes);
+
+    conn.execute(
+        "INSERT INTO memories (id, agent_id, content, embedding, created_at, importance, expires_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        libsql::params![
+            memory.id.to_string(),
+            memory.agent_id.to_string(),
+            memory.content.clone(),
+            embedding_bytes,
+            memory.created_at.clone(),
+            f64::from(memory.importance),
+            memory.expires_at.map(format_expiry),
+        ],
+    )
+    .await
+    .map_err(|e| PersistenceError::query_failed(operation, e.to_string()))?;
+
+    Ok(())
+}
+
+/// Searches memories by embedding similarity.
+///
+/// Uses in-application cosine similarity calculation since libSQL
+/// vector extension requires specific setup. Loads all unexpired memories
+/// with embeddings for the agent and computes similarity in Rust.
+///
+/// # Arguments
+///
+/// * `conn` - The database connection
+/// * `agent_id` - The agent to search within
+/// * `query_embedding` - The query embedding
+/// * `limit` - Maximum results to return
+/// * `min_similarity` - Optional minimum similarity threshold, applied to
+///   cosine similarity before importance weighting
+/// * `importance_weight` - Share of the score taken by similarity; see
+///   [`ScoredMemory::weighted_score`]
+///
+/// # Returns
+///
+/// Memories ranked by score (highest first).
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn search_memories_by_embedding(
+    conn: &Connection,
+    agent_id: &AgentId,
+    query_embedding: &Embedding,
+    limit: usize,
+    min_similarity: Option<f32>,
+    importance_weight: f32,
+) -> Result<Vec<ScoredMemory>, PersistenceError> {
+    // Load all unexpired memories with embeddings for this agent
+    let mut rows = conn
+        .query(
+            "SELECT id, content, embedding, created_at, importance, expires_at FROM memories
+             WHERE agent_id = ?1 AND embedding IS NOT NULL
+               AND (expires_at IS NULL OR expires_at >= ?2)
+             ORDER BY created_at DESC",
+            [agent_id.to_string(), format_expiry(Utc::now())],
+        )
+        .await
+        .map_err(|e| PersistenceError::query_failed("search_memories", e.to_string()))?;
 
     let mut scored: Vec<ScoredMemory> = Vec::new();
     let threshold = min_similarity.unwrap_or(-1.0); // Default to include all
@@ -673,6 +1425,7 @@ pub async fn search_memories_by_embedding(
         let created_at: String = row
             .get(3)
             .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let (importance, expires_at) = memory_lifecycle(&row, 4)?;
 
         let memory_id = MemoryId::parse(&id_str)
             .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
@@ -692,8 +1445,12 @@ pub async fn search_memories_by_embedding(
                     content,
                     embedding: Some(embedding),
                     created_at,
+                    importance,
+                    expires_at,
                 },
-                score: similarity,
+                score: ScoredMemory::weighted_score(similarity, importance, importance_weight),
+                vector_score: similarity,
+                text_score: 0.0,
             });
         }
     }
@@ -711,6 +1468,190 @@ pub async fn search_memories_by_embedding(
     Ok(scored)
 }
 
+/// Searches memories by both embedding similarity and full-text relevance.
+///
+/// Each unexpired memory of the agent that has an embedding or matches
+/// `query_text` is scored with [`ScoredMemory::hybrid_score`]. Text scores
+/// are FTS5 BM25 relevance normalized so the best text match scores 1.0.
+/// Encrypted content is indexed as ciphertext, so text scores are 0.0 when
+/// the store is encrypted.
+///
+/// # Arguments
+///
+/// * `conn` - The database connection
+/// * `agent_id` - The agent to search within
+/// * `query_text` - The query text for full-text matching
+/// * `query_embedding` - The query embedding
+/// * `top_k` - Maximum results to return
+/// * `alpha` - Share of the score taken by vector similarity
+///
+/// # Returns
+///
+/// Memories ranked by combined score (highest first).
+///
+/// # Errors
+///
+/// Returns an error if a query fails.
+pub async fn hybrid_search_memories(
+    conn: &Connection,
+    agent_id: &AgentId,
+    query_text: &str,
+    query_embedding: &Embedding,
+    top_k: usize,
+    alpha: f32,
+) -> Result<Vec<ScoredMemory>, PersistenceError> {
+    let mut candidates =
+        search_memories_by_embedding(conn, agent_id, query_embedding, usize::MAX, None, 1.0)
+            .await?;
+
+    let text_matches = search_memories_by_text(conn, agent_id, query_text).await?;
+    let best = text_matches
+        .iter()
+        .map(|(_, relevance)| *relevance)
+        .fold(0.0_f32, f32::max);
+    for (memory, relevance) in text_matches {
+        let text_score = if best > 0.0 { relevance / best } else { 0.0 };
+        match candidates.iter_mut().find(|c| c.memory.id == memory.id) {
+            Some(candidate) => candidate.text_score = text_score,
+            None => candidates.push(ScoredMemory {
+                text_score,
+                ..ScoredMemory::new(memory, 0.0)
+            }),
+        }
+    }
+
+    for candidate in &mut candidates {
+        candidate.score =
+            ScoredMemory::hybrid_score(candidate.vector_score, candidate.text_score, alpha);
+    }
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates.truncate(top_k);
+
+    Ok(candidates)
+}
+
+/// Returns the agent's unexpired memories matching `query_text`, each with
+/// its BM25 relevance (higher is better).
+async fn search_memories_by_text(
+    conn: &Connection,
+    agent_id: &AgentId,
+    query_text: &str,
+) -> Result<Vec<(Memory, f32)>, PersistenceError> {
+    let Some(query) = fts_query(query_text) else {
+        return Ok(Vec::new());
+    };
+
+    let mut rows = conn
+        .query(
+            "SELECT m.id, m.content, m.embedding, m.created_at, m.importance, m.expires_at,
+                    -bm25(memories_fts)
+             FROM memories_fts f
+             INNER JOIN memories m ON m.id = f.memory_id
+             WHERE memories_fts MATCH ?1 AND m.agent_id = ?2
+               AND (m.expires_at IS NULL OR m.expires_at >= ?3)",
+            [query, agent_id.to_string(), format_expiry(Utc::now())],
+        )
+        .await
+        .map_err(|e| PersistenceError::query_failed("search_memories_by_text", e.to_string()))?;
+
+    let mut matches = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| PersistenceError::query_failed("search_memories_by_text", e.to_string()))?
+    {
+        let id: String = row
+            .get(0)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let content: String = row
+            .get(1)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let embedding_blob: Option<Vec<u8>> = row
+            .get(2)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let created_at: String = row
+            .get(3)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let (importance, expires_at) = memory_lifecycle(&row, 4)?;
+        let relevance: f64 = row
+            .get(6)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+
+        let embedding = embedding_blob
+            .filter(|blob| !blob.is_empty())
+            .map(|blob| Embedding::from_bytes(&blob))
+            .transpose()
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+
+        let memory = Memory {
+            id: MemoryId::parse(&id)
+                .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?,
+            agent_id: agent_id.clone(),
+            content,
+            embedding,
+            created_at,
+            importance,
+            expires_at,
+        };
+        matches.push((memory, relevance as f32));
+    }
+
+    Ok(matches)
+}
+
+/// Builds an FTS5 query matching any word of `text`, quoting each word so
+/// punctuation cannot be read as query syntax.
+fn fts_query(text: &str) -> Option<String> {
+    let terms: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{term}\""))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" OR "))
+}
+
+/// Finds the agent's unexpired memory most similar to `embedding`, if its
+/// cosine similarity is at least `threshold`.
+///
+/// # Errors
+///
+/// Returns an error if the search fails.
+pub async fn find_duplicate_memory(
+    conn: &Connection,
+    agent_id: &AgentId,
+    embedding: &Embedding,
+    threshold: f32,
+) -> Result<Option<ScoredMemory>, PersistenceError> {
+    let mut nearest =
+        search_memories_by_embedding(conn, agent_id, embedding, 1, Some(threshold), 1.0).await?;
+    Ok(nearest.pop())
+}
+
+/// Merges a duplicate into an existing memory, keeping the higher of the two
+/// importances and refreshing `updated_at`.
+///
+/// # Errors
+///
+/// Returns an error if the update fails.
+pub async fn merge_duplicate_memory(
+    conn: &Connection,
+    memory_id: &MemoryId,
+    importance: f32,
+) -> Result<(), PersistenceError> {
+    conn.execute(
+        "UPDATE memories SET importance = MAX(importance, ?2), updated_at = ?3 WHERE id = ?1",
+        libsql::params![
+            memory_id.to_string(),
+            f64::from(importance),
+            Utc::now().timestamp().to_string(),
+        ],
+    )
+    .await
+    .map_err(|e| PersistenceError::query_failed("merge_duplicate_memory", e.to_string()))?;
+
+    Ok(())
+}
+
 /// Loads all memories for an agent.
 ///
 /// # Arguments
@@ -733,11 +1674,11 @@ pub async fn load_memories_for_agent(
 ) -> Result<Vec<Memory>, PersistenceError> {
     let query = match limit {
         Some(l) => format!(
-            "SELECT id, content, embedding, created_at FROM memories
+            "SELECT id, content, embedding, created_at, importance, expires_at FROM memories
              WHERE agent_id = ?1 ORDER BY created_at DESC LIMIT {}",
             l
         ),
-        None => "SELECT id, content, embedding, created_at FROM memories
+        None => "SELECT id, content, embedding, created_at, importance, expires_at FROM memories
              WHERE agent_id = ?1 ORDER BY created_at DESC"
             .to_string(),
     };
@@ -766,6 +1707,7 @@ pub async fn load_memories_for_agent(
         let created_at: String = row
             .get(3)
             .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let (importance, expires_at) = memory_lifecycle(&row, 4)?;
 
         let memory_id = MemoryId::parse(&id_str)
             .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
@@ -784,6 +1726,8 @@ pub async fn load_memories_for_agent(
             content,
             embedding,
             created_at,
+            importance,
+            expires_at,
         });
     }
 
@@ -838,49 +1782,296 @@ pub async fn delete_memories_for_agent(
     Ok(())
 }
 
-/// Counts memories for an agent.
+/// Deletes memories by ID in a single statement.
 ///
 /// # Arguments
 ///
 /// * `conn` - The database connection
-/// * `agent_id` - The agent to count memories for
+/// * `memory_ids` - The memories to delete
 ///
 /// # Returns
 ///
-/// The number of memories for this agent.
+/// The number of memories deleted; IDs that do not exist are not counted.
 ///
 /// # Errors
 ///
-/// Returns an error if the query fails.
-pub async fn count_memories_for_agent(
+/// Returns an error if the delete fails.
+pub async fn delete_memories(
     conn: &Connection,
-    agent_id: &AgentId,
-) -> Result<usize, PersistenceError> {
-    let mut rows = conn
-        .query(
-            "SELECT COUNT(*) FROM memories WHERE agent_id = ?1",
-            [agent_id.to_string()],
-        )
-        .await
-        .map_err(|e| PersistenceError::query_failed("count_memories", e.to_string()))?;
-
-    if let Some(row) = rows
-        .next()
-        .await
-        .map_err(|e| PersistenceError::query_failed("count_memories", e.to_string()))?
-    {
-        let count: i64 = row
-            .get(0)
-            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
-        Ok(count as usize)
-    } else {
-        Ok(0)
+    memory_ids: &[MemoryId],
+) -> Result<u64, PersistenceError> {
+    if memory_ids.is_empty() {
+        return Ok(0);
     }
-}
 
-// =============================================================================
-// Session management
-// =============================================================================
+    let placeholders = (1..=memory_ids.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    conn.execute(
+        &format!("DELETE FROM memories WHERE id IN ({placeholders})"),
+        libsql::params_from_iter(memory_ids.iter().map(ToString::to_string)),
+    )
+    .await
+    .map_err(|e| PersistenceError::query_failed("delete_memories", e.to_string()))
+}
+
+/// Deletes memories whose expiry is before `now`.
+///
+/// # Arguments
+///
+/// * `conn` - The database connection
+/// * `agent_id` - The agent whose memories to prune, or `None` for all agents
+/// * `now` - The cutoff; memories expiring before it are deleted
+///
+/// # Returns
+///
+/// The number of memories deleted.
+///
+/// # Errors
+///
+/// Returns an error if the delete fails.
+pub async fn prune_expired_memories(
+    conn: &Connection,
+    agent_id: Option<&AgentId>,
+    now: DateTime<Utc>,
+) -> Result<u64, PersistenceError> {
+    let cutoff = format_expiry(now);
+    let deleted = match agent_id {
+        Some(agent_id) => {
+            conn.execute(
+                "DELETE FROM memories WHERE agent_id = ?1 AND expires_at < ?2",
+                [agent_id.to_string(), cutoff],
+            )
+            .await
+        }
+        None => {
+            conn.execute("DELETE FROM memories WHERE expires_at < ?1", [cutoff])
+                .await
+        }
+    }
+    .map_err(|e| PersistenceError::query_failed("prune_expired_memories", e.to_string()))?;
+
+    Ok(deleted)
+}
+
+/// Formats an expiry as fixed-width RFC 3339 UTC so stored values compare
+/// correctly as text.
+fn format_expiry(expires_at: DateTime<Utc>) -> String {
+    expires_at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Reads the `importance, expires_at` column pair starting at `idx`.
+fn memory_lifecycle(
+    row: &libsql::Row,
+    idx: i32,
+) -> Result<(f32, Option<DateTime<Utc>>), PersistenceError> {
+    let importance: f64 = row
+        .get(idx)
+        .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+    let expires_at: Option<String> = row
+        .get(idx + 1)
+        .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+    let expires_at = expires_at
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(&raw)
+                .map(|parsed| parsed.with_timezone(&Utc))
+                .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))
+        })
+        .transpose()?;
+
+    Ok((importance as f32, expires_at))
+}
+
+/// Counts memories for an agent.
+///
+/// # Arguments
+///
+/// * `conn` - The database connection
+/// * `agent_id` - The agent to count memories for
+///
+/// # Returns
+///
+/// The number of memories for this agent.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn count_memories_for_agent(
+    conn: &Connection,
+    agent_id: &AgentId,
+) -> Result<usize, PersistenceError> {
+    let mut rows = conn
+        .query(
+            "SELECT COUNT(*) FROM memories WHERE agent_id = ?1",
+            [agent_id.to_string()],
+        )
+        .await
+        .map_err(|e| PersistenceError::query_failed("count_memories", e.to_string()))?;
+
+    if let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| PersistenceError::query_failed("count_memories", e.to_string()))?
+    {
+        let count: i64 = row
+            .get(0)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        Ok(count as usize)
+    } else {
+        Ok(0)
+    }
+}
+
+// =============================================================================
+// Maintenance
+// =============================================================================
+
+/// Size and layout of the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DbStats {
+    /// Database size in bytes (`page_count * page_size`)
+    pub file_size_bytes: u64,
+    /// Total pages in the database
+    pub page_count: u64,
+    /// Pages allocated but unused
+    pub freelist_count: u64,
+    /// Schema version recorded in the database
+    pub schema_version: u32,
+    /// Number of stored memories
+    pub memory_count: u64,
+}
+
+impl DbStats {
+    /// Returns the fraction of pages that are free (0.0 for an empty
+    /// database).
+    #[must_use]
+    pub fn fragmentation_ratio(&self) -> f32 {
+        if self.page_count == 0 {
+            0.0
+        } else {
+            self.freelist_count as f32 / self.page_count as f32
+        }
+    }
+}
+
+/// Outcome of a [`vacuum`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VacuumStats {
+    /// The mode that ran (`Incremental` or `Full`, never `Auto`)
+    pub mode: VacuumMode,
+    /// Pages returned to the OS
+    pub pages_freed: u64,
+    /// How long the vacuum took
+    pub duration: Duration,
+}
+
+/// Runs a query returning a single integer, such as a pragma.
+async fn query_u64(conn: &Connection, sql: &str) -> Result<u64, PersistenceError> {
+    let mut rows = conn
+        .query(sql, ())
+        .await
+        .map_err(|e| PersistenceError::query_failed(sql, e.to_string()))?;
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| PersistenceError::query_failed(sql, e.to_string()))?
+    else {
+        return Ok(0);
+    };
+    let value: i64 = row
+        .get(0)
+        .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+    Ok(u64::try_from(value).unwrap_or(0))
+}
+
+/// Collects database size and fragmentation statistics.
+///
+/// # Errors
+///
+/// Returns an error if a pragma or count query fails.
+pub async fn db_stats(conn: &Connection) -> Result<DbStats, PersistenceError> {
+    let page_count = query_u64(conn, "PRAGMA page_count").await?;
+    let page_size = query_u64(conn, "PRAGMA page_size").await?;
+    let freelist_count = query_u64(conn, "PRAGMA freelist_count").await?;
+    let schema_version =
+        query_u64(conn, "SELECT COALESCE(MAX(version), 0) FROM schema_version").await?;
+    let memory_count = query_u64(conn, "SELECT COUNT(*) FROM memories").await?;
+
+    Ok(DbStats {
+        file_size_bytes: page_count * page_size,
+        page_count,
+        freelist_count,
+        schema_version: u32::try_from(schema_version).unwrap_or(u32::MAX),
+        memory_count,
+    })
+}
+
+/// Compacts the database.
+///
+/// [`VacuumMode::Auto`] runs a full vacuum when the fragmentation ratio
+/// exceeds `config.min_fragmentation`, and an incremental one otherwise.
+/// Incremental vacuuming only frees pages in databases created with
+/// incremental auto-vacuum, which [`initialize_schema`] enables for new
+/// databases.
+///
+/// # Errors
+///
+/// Returns an error if the stats queries or the vacuum itself fail.
+pub async fn vacuum(
+    conn: &Connection,
+    config: &VacuumConfig,
+) -> Result<VacuumStats, PersistenceError> {
+    let before = db_stats(conn).await?;
+    let mode = match config.mode {
+        VacuumMode::Auto if before.fragmentation_ratio() > config.min_fragmentation => {
+            VacuumMode::Full
+        }
+        VacuumMode::Auto => VacuumMode::Incremental,
+        mode => mode,
+    };
+
+    let started = Instant::now();
+    match mode {
+        VacuumMode::Full => {
+            conn.execute("VACUUM", ())
+                .await
+                .map_err(|e| PersistenceError::query_failed("vacuum", e.to_string()))?;
+        }
+        _ => {
+            // incremental_vacuum frees pages as its result rows are stepped
+            let mut rows = conn
+                .query("PRAGMA incremental_vacuum", ())
+                .await
+                .map_err(|e| PersistenceError::query_failed("incremental_vacuum", e.to_string()))?;
+            while rows
+                .next()
+                .await
+                .map_err(|e| PersistenceError::query_failed("incremental_vacuum", e.to_string()))?
+                .is_some()
+            {}
+        }
+    }
+    let duration = started.elapsed();
+
+    let after = db_stats(conn).await?;
+    let stats = VacuumStats {
+        mode,
+        pages_freed: before.page_count.saturating_sub(after.page_count),
+        duration,
+    };
+    tracing::info!(
+        mode = ?stats.mode,
+        pages_freed = stats.pages_freed,
+        duration_ms = stats.duration.as_millis() as u64,
+        "Database vacuumed"
+    );
+    Ok(stats)
+}
+
+// =============================================================================
+// Session management
+// =============================================================================
 
 /// Information about a named session.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1262,7 +2453,7 @@ pub async fn get_related_memories(
 ) -> Result<Vec<Memory>, PersistenceError> {
     let query = if let Some(rel_type) = relation_type {
         format!(
-            "SELECT m.id, m.agent_id, m.content, m.embedding, m.created_at
+            "SELECT m.id, m.agent_id, m.content, m.embedding, m.created_at, m.importance, m.expires_at
              FROM memories m
              INNER JOIN memory_relations r ON m.id = r.target_id
              WHERE r.source_id = '{memory_id}' AND r.relation_type = '{rel_type}'
@@ -1270,7 +2461,7 @@ pub async fn get_related_memories(
         )
     } else {
         format!(
-            "SELECT m.id, m.agent_id, m.content, m.embedding, m.created_at
+            "SELECT m.id, m.agent_id, m.content, m.embedding, m.created_at, m.importance, m.expires_at
              FROM memories m
              INNER JOIN memory_relations r ON m.id = r.target_id
              WHERE r.source_id = '{memory_id}'
@@ -1304,6 +2495,7 @@ pub async fn get_related_memories(
         let created_at: String = row
             .get(4)
             .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let (importance, expires_at) = memory_lifecycle(&row, 5)?;
 
         let embedding = embedding_blob
             .map(|blob| Embedding::from_bytes(&blob))
@@ -1316,6 +2508,8 @@ pub async fn get_related_memories(
             content,
             embedding,
             created_at,
+            importance,
+            expires_at,
         });
     }
 
@@ -1346,7 +2540,7 @@ pub async fn find_memories_by_tag(
 ) -> Result<Vec<Memory>, PersistenceError> {
     let mut rows = conn
         .query(
-            "SELECT m.id, m.agent_id, m.content, m.embedding, m.created_at
+            "SELECT m.id, m.agent_id, m.content, m.embedding, m.created_at, m.importance, m.expires_at
              FROM memories m
              INNER JOIN memory_tags t ON m.id = t.memory_id
              WHERE t.tag = ?1
@@ -1377,6 +2571,7 @@ pub async fn find_memories_by_tag(
         let created_at: String = row
             .get(4)
             .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let (importance, expires_at) = memory_lifecycle(&row, 5)?;
 
         let embedding = embedding_blob
             .map(|blob| Embedding::from_bytes(&blob))
@@ -1389,12 +2584,223 @@ pub async fn find_memories_by_tag(
             content,
             embedding,
             created_at,
+            importance,
+            expires_at,
         });
     }
 
     Ok(memories)
 }
 
+// =============================================================================
+// Conversation summaries
+// =============================================================================
+
+/// Tag applied to every memory that holds a conversation summary.
+pub const CONVERSATION_SUMMARY_TAG: &str = "conversation_summary";
+
+/// Prefix of the tag linking a summary memory to its conversation.
+const CONVERSATION_TAG_PREFIX: &str = "conversation:";
+
+/// Returns the tag that links a summary memory to `conversation_id`.
+#[must_use]
+pub fn conversation_tag(conversation_id: &ConversationId) -> String {
+    format!("{CONVERSATION_TAG_PREFIX}{conversation_id}")
+}
+
+/// Saves a conversation summary as a memory.
+///
+/// The memory is tagged with [`CONVERSATION_SUMMARY_TAG`] and with
+/// [`conversation_tag`] so the full conversation can be looked up again via
+/// [`summary_conversation_id`].
+///
+/// # Errors
+///
+/// Returns an error if the insert or tagging fails.
+pub async fn save_conversation_summary(
+    conn: &Connection,
+    memory: &Memory,
+    conversation_id: &ConversationId,
+) -> Result<MemoryId, PersistenceError> {
+    let memory_id = save_memory(conn, memory).await?;
+    let conversation = conversation_tag(conversation_id);
+    tag_memory(
+        conn,
+        &memory_id.to_string(),
+        &[CONVERSATION_SUMMARY_TAG, conversation.as_str()],
+    )
+    .await?;
+    Ok(memory_id)
+}
+
+/// Loads all conversation summaries stored for an agent, newest first.
+///
+/// # Errors
+///
+/// Returns an error if the query fails.
+pub async fn load_conversation_summaries(
+    conn: &Connection,
+    agent_id: &AgentId,
+) -> Result<Vec<Memory>, PersistenceError> {
+    let mut rows = conn
+        .query(
+            "SELECT m.id, m.content, m.embedding, m.created_at, m.importance, m.expires_at
+             FROM memories m
+             INNER JOIN memory_tags t ON m.id = t.memory_id
+             WHERE t.tag = ?1 AND m.agent_id = ?2
+             ORDER BY m.created_at DESC",
+            [CONVERSATION_SUMMARY_TAG.to_string(), agent_id.to_string()],
+        )
+        .await
+        .map_err(|e| {
+            PersistenceError::query_failed("load_conversation_summaries", e.to_string())
+        })?;
+
+    let mut memories = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| PersistenceError::query_failed("load_conversation_summaries", e.to_string()))?
+    {
+        let id: String = row
+            .get(0)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let content: String = row
+            .get(1)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let embedding_blob: Option<Vec<u8>> = row
+            .get(2)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let created_at: String = row
+            .get(3)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let (importance, expires_at) = memory_lifecycle(&row, 4)?;
+
+        let embedding = embedding_blob
+            .filter(|blob| !blob.is_empty())
+            .map(|blob| Embedding::from_bytes(&blob))
+            .transpose()
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+
+        memories.push(Memory {
+            id: MemoryId::parse(&id)
+                .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?,
+            agent_id: agent_id.clone(),
+            content,
+            embedding,
+            created_at,
+            importance,
+            expires_at,
+        });
+    }
+
+    Ok(memories)
+}
+
+/// Returns the conversation a summary memory was generated from, if any.
+///
+/// # Errors
+///
+/// Returns an error if the query fails or the stored ID is malformed.
+pub async fn summary_conversation_id(
+    conn: &Connection,
+    memory_id: &MemoryId,
+) -> Result<Option<ConversationId>, PersistenceError> {
+    let mut rows = conn
+        .query(
+            "SELECT tag FROM memory_tags WHERE memory_id = ?1 AND tag LIKE ?2 LIMIT 1",
+            [memory_id.to_string(), format!("{CONVERSATION_TAG_PREFIX}%")],
+        )
+        .await
+        .map_err(|e| PersistenceError::query_failed("summary_conversation_id", e.to_string()))?;
+
+    let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| PersistenceError::query_failed("summary_conversation_id", e.to_string()))?
+    else {
+        return Ok(None);
+    };
+
+    let tag: String = row
+        .get(0)
+        .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+    let id = tag.trim_start_matches(CONVERSATION_TAG_PREFIX);
+    ConversationId::parse(id)
+        .map(Some)
+        .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))
+}
+
+// =============================================================================
+// Tool schema versions
+// =============================================================================
+
+/// Tags a conversation with the tool schema versions it was recorded with.
+///
+/// Replaces any earlier entry for the same tool.
+pub async fn save_conversation_tool_versions(
+    conn: &Connection,
+    conversation_id: &ConversationId,
+    versions: &[ToolSchemaVersion],
+) -> Result<(), PersistenceError> {
+    for version in versions {
+        conn.execute(
+            "INSERT OR REPLACE INTO conversation_tool_versions
+             (conversation_id, tool_name, schema_hash, version) VALUES (?1, ?2, ?3, ?4)",
+            libsql::params![
+                conversation_id.to_string(),
+                version.tool_name.clone(),
+                version.schema_hash.clone(),
+                i64::from(version.version)
+            ],
+        )
+        .await
+        .map_err(|e| {
+            PersistenceError::query_failed("save_conversation_tool_versions", e.to_string())
+        })?;
+    }
+    Ok(())
+}
+
+/// Loads the tool schema versions a conversation was tagged with.
+pub async fn load_conversation_tool_versions(
+    conn: &Connection,
+    conversation_id: &ConversationId,
+) -> Result<Vec<ToolSchemaVersion>, PersistenceError> {
+    let op = "load_conversation_tool_versions";
+    let mut rows = conn
+        .query(
+            "SELECT tool_name, schema_hash, version FROM conversation_tool_versions
+             WHERE conversation_id = ?1 ORDER BY tool_name",
+            [conversation_id.to_string()],
+        )
+        .await
+        .map_err(|e| PersistenceError::query_failed(op, e.to_string()))?;
+
+    let mut versions = Vec::new();
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| PersistenceError::query_failed(op, e.to_string()))?
+    {
+        let tool_name: String = row
+            .get(0)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let schema_hash: String = row
+            .get(1)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        let version: i64 = row
+            .get(2)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        versions.push(ToolSchemaVersion {
+            tool_name,
+            schema_hash,
+            version: u32::try_from(version).unwrap_or(0),
+        });
+    }
+    Ok(versions)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1425,6 +2831,162 @@ mod tests {
     fn persistence_config_default() {
         let config = PersistenceConfig::default();
         assert_eq!(config.db_path, "acton-ai.db");
+        assert!(!config.is_encrypted());
+    }
+
+    #[test]
+    fn persistence_config_with_encryption() {
+        let config = PersistenceConfig::in_memory().with_encryption([1u8; 32]);
+        assert!(config.is_encrypted());
+        assert!(!format!("{config:?}").contains("[1, 1"));
+    }
+
+    #[test]
+    fn persistence_config_remote() {
+        let config = PersistenceConfig::remote("libsql://db-org.turso.io", "secret-token");
+        assert_eq!(config.db_path, "libsql://db-org.turso.io");
+        assert_eq!(config.auth_token.as_deref(), Some("secret-token"));
+        assert!(config.is_remote());
+        assert!(!config.is_in_memory());
+        assert!(!format!("{config:?}").contains("secret-token"));
+
+        let https = PersistenceConfig::remote("https://db-org.turso.io", "t");
+        assert_eq!(https.db_path, "libsql://db-org.turso.io");
+        assert!(!PersistenceConfig::new("agents.db").is_remote());
+    }
+
+    #[test]
+    fn persistence_config_from_env_values() {
+        let remote = PersistenceConfig::from_env_values(
+            Some("libsql://db-org.turso.io".to_string()),
+            Some("token".to_string()),
+        );
+        assert!(remote.is_remote());
+        assert_eq!(remote.auth_token.as_deref(), Some("token"));
+
+        assert_eq!(
+            PersistenceConfig::from_env_values(None, Some("token".to_string())),
+            PersistenceConfig::default()
+        );
+        assert_eq!(
+            PersistenceConfig::from_env_values(Some(String::new()), None),
+            PersistenceConfig::default()
+        );
+    }
+
+    #[test]
+    fn vacuum_config_defaults_to_auto() {
+        let config = VacuumConfig::default();
+        assert_eq!(config.mode, VacuumMode::Auto);
+        assert!((config.min_fragmentation - 0.25).abs() < f32::EPSILON);
+
+        let persistence = PersistenceConfig::in_memory()
+            .with_auto_vacuum(VacuumConfig::new(VacuumMode::Full).with_min_fragmentation(0.5));
+        assert_eq!(persistence.auto_vacuum.unwrap().mode, VacuumMode::Full);
+    }
+
+    #[test]
+    fn fragmentation_ratio_is_free_over_total() {
+        let stats = DbStats {
+            file_size_bytes: 0,
+            page_count: 0,
+            freelist_count: 0,
+            schema_version: SCHEMA_VERSION,
+            memory_count: 0,
+        };
+        assert_eq!(stats.fragmentation_ratio(), 0.0);
+
+        let stats = DbStats {
+            page_count: 40,
+            freelist_count: 10,
+            ..stats
+        };
+        assert!((stats.fragmentation_ratio() - 0.25).abs() < f32::EPSILON);
+    }
+
+    async fn memory_conn() -> Connection {
+        let db = open_database(&PersistenceConfig::in_memory())
+            .await
+            .unwrap();
+        let conn = db.connect().unwrap();
+        initialize_schema(&conn).await.unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    async fn encrypted_message_roundtrip() {
+        let conn = memory_conn().await;
+        let encryption = EncryptionConfig::new([3u8; 32]);
+        let conv_id = create_conversation(&conn, &AgentId::new()).await.unwrap();
+
+        let sealed = encrypt_message(&Message::user("top secret"), Some(&encryption)).unwrap();
+        save_message(&conn, &conv_id, &sealed).await.unwrap();
+
+        let mut loaded = load_conversation_messages(&conn, &conv_id).await.unwrap();
+        assert_ne!(loaded[0].content, "top secret");
+        decrypt_messages(&mut loaded, Some(&encryption)).unwrap();
+        assert_eq!(loaded[0].content, "top secret");
+    }
+
+    #[tokio::test]
+    async fn reencrypt_all_rotates_keys() {
+        let conn = memory_conn().await;
+        let old = EncryptionConfig::new([3u8; 32]);
+        let new = EncryptionConfig::new([4u8; 32])
+            .with_algorithm(crate::memory::EncryptionAlgorithm::ChaCha20Poly1305);
+        let agent_id = AgentId::new();
+        let conv_id = create_conversation(&conn, &agent_id).await.unwrap();
+
+        save_message(
+            &conn,
+            &conv_id,
+            &encrypt_message(&Message::user("hello"), Some(&old)).unwrap(),
+        )
+        .await
+        .unwrap();
+        save_memory(
+            &conn,
+            &encrypt_memory(&Memory::new(agent_id.clone(), "likes tea"), Some(&old)).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let stats = reencrypt_all(&conn, &old, &new).await.unwrap();
+        assert_eq!(stats.messages, 1);
+        assert_eq!(stats.memories, 1);
+
+        let mut messages = load_conversation_messages(&conn, &conv_id).await.unwrap();
+        assert!(decrypt_messages(&mut messages.clone(), Some(&old)).is_err());
+        decrypt_messages(&mut messages, Some(&new)).unwrap();
+        assert_eq!(messages[0].content, "hello");
+
+        let mut memories = load_memories_for_agent(&conn, &agent_id, None)
+            .await
+            .unwrap();
+        decrypt_memories(&mut memories, Some(&new)).unwrap();
+        assert_eq!(memories[0].content, "likes tea");
+    }
+
+    #[tokio::test]
+    async fn reencrypt_all_with_wrong_key_changes_nothing() {
+        let conn = memory_conn().await;
+        let old = EncryptionConfig::new([3u8; 32]);
+        let conv_id = create_conversation(&conn, &AgentId::new()).await.unwrap();
+        save_message(
+            &conn,
+            &conv_id,
+            &encrypt_message(&Message::user("hello"), Some(&old)).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let wrong = EncryptionConfig::new([9u8; 32]);
+        let result = reencrypt_all(&conn, &wrong, &EncryptionConfig::new([5u8; 32])).await;
+        assert!(result.is_err());
+
+        let mut messages = load_conversation_messages(&conn, &conv_id).await.unwrap();
+        decrypt_messages(&mut messages, Some(&old)).unwrap();
+        assert_eq!(messages[0].content, "hello");
     }
 
     #[test]
@@ -1449,6 +3011,7 @@ mod tests {
     #[test]
     fn agent_state_snapshot_serialization() {
         let snapshot = AgentStateSnapshot {
+            schema_version: SCHEMA_VERSION,
             agent_id: AgentId::new(),
             conversation_id: Some(ConversationId::new()),
             conversation: vec![Message::user("Hello")],
@@ -1462,4 +3025,563 @@ mod tests {
         assert_eq!(snapshot.system_prompt, deserialized.system_prompt);
         assert_eq!(snapshot.conversation.len(), deserialized.conversation.len());
     }
+
+    #[test]
+    fn v1_snapshot_migrates_without_conversation_id() {
+        let agent_id = AgentId::new();
+        let v1 = serde_json::json!({
+            "agent_id": agent_id,
+            "conversation": [],
+            "system_prompt": "You are helpful",
+        });
+
+        let snapshot = migrate_snapshot(v1).unwrap();
+        assert_eq!(snapshot.schema_version, SCHEMA_VERSION);
+        assert_eq!(snapshot.agent_id, agent_id);
+        assert!(snapshot.conversation_id.is_none());
+    }
+
+    #[test]
+    fn newer_snapshot_versions_are_rejected() {
+        let future = serde_json::json!({ "schema_version": SCHEMA_VERSION + 1 });
+        assert!(migrate_snapshot(future).is_err());
+    }
+
+    #[tokio::test]
+    async fn initialize_schema_migrates_stored_agent_states() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let v1 = serde_json::json!({
+            "agent_id": agent_id,
+            "conversation": [],
+            "system_prompt": "old",
+        });
+        conn.execute(
+            "INSERT INTO agent_state (agent_id, state, updated_at) VALUES (?1, ?2, datetime('now'))",
+            [agent_id.to_string(), v1.to_string()],
+        )
+        .await
+        .unwrap();
+
+        initialize_schema(&conn).await.unwrap();
+        assert_eq!(migrate_agent_states(&conn).await.unwrap(), 0);
+
+        let snapshot = load_agent_state(&conn, &agent_id).await.unwrap().unwrap();
+        assert_eq!(snapshot.schema_version, SCHEMA_VERSION);
+        assert_eq!(snapshot.system_prompt, "old");
+    }
+
+    #[tokio::test]
+    async fn conversation_summaries_roundtrip() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let conv_id = ConversationId::new();
+
+        save_memory(&conn, &Memory::new(agent_id.clone(), "unrelated"))
+            .await
+            .unwrap();
+        let summary = Memory::new(agent_id.clone(), "User asked about Rust ownership.");
+        let summary_id = save_conversation_summary(&conn, &summary, &conv_id)
+            .await
+            .unwrap();
+
+        let summaries = load_conversation_summaries(&conn, &agent_id).await.unwrap();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].content, "User asked about Rust ownership.");
+
+        let found = summary_conversation_id(&conn, &summary_id).await.unwrap();
+        assert_eq!(found, Some(conv_id));
+    }
+
+    #[tokio::test]
+    async fn conversation_summaries_scoped_to_agent() {
+        let conn = memory_conn().await;
+        let summary = Memory::new(AgentId::new(), "someone else's summary");
+        save_conversation_summary(&conn, &summary, &ConversationId::new())
+            .await
+            .unwrap();
+
+        let summaries = load_conversation_summaries(&conn, &AgentId::new())
+            .await
+            .unwrap();
+        assert!(summaries.is_empty());
+    }
+
+    #[tokio::test]
+    async fn vacuum_frees_deleted_pages() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let filler = "x".repeat(4096);
+        for _ in 0..50 {
+            save_memory(&conn, &Memory::new(agent_id.clone(), filler.clone()))
+                .await
+                .unwrap();
+        }
+        delete_memories_for_agent(&conn, &agent_id).await.unwrap();
+
+        let before = db_stats(&conn).await.unwrap();
+        assert_eq!(before.schema_version, SCHEMA_VERSION);
+        assert_eq!(before.memory_count, 0);
+        assert!(before.fragmentation_ratio() > 0.25, "{before:?}");
+
+        let stats = vacuum(&conn, &VacuumConfig::default()).await.unwrap();
+        assert_eq!(stats.mode, VacuumMode::Full);
+        assert!(stats.pages_freed > 0);
+
+        let after = db_stats(&conn).await.unwrap();
+        assert!(after.page_count < before.page_count);
+        assert_eq!(after.freelist_count, 0);
+    }
+
+    #[tokio::test]
+    async fn incremental_vacuum_runs_below_threshold() {
+        let conn = memory_conn().await;
+        let stats = vacuum(&conn, &VacuumConfig::default()).await.unwrap();
+        assert_eq!(stats.mode, VacuumMode::Incremental);
+        assert_eq!(stats.pages_freed, 0);
+    }
+
+    #[tokio::test]
+    async fn conversation_tool_versions_roundtrip() {
+        let conn = memory_conn().await;
+        let conv_id = ConversationId::new();
+        let v1 = ToolSchemaVersion {
+            tool_name: "search".to_string(),
+            schema_hash: "abc".to_string(),
+            version: 1,
+        };
+        save_conversation_tool_versions(&conn, &conv_id, std::slice::from_ref(&v1))
+            .await
+            .unwrap();
+
+        let v2 = ToolSchemaVersion {
+            schema_hash: "def".to_string(),
+            version: 2,
+            ..v1
+        };
+        save_conversation_tool_versions(&conn, &conv_id, std::slice::from_ref(&v2))
+            .await
+            .unwrap();
+
+        let loaded = load_conversation_tool_versions(&conn, &conv_id)
+            .await
+            .unwrap();
+        assert_eq!(loaded, vec![v2]);
+        assert!(
+            load_conversation_tool_versions(&conn, &ConversationId::new())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn memory_importance_and_expiry_roundtrip() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let expires_at = DateTime::parse_from_rfc3339("2100-01-01T00:00:00.250Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let memory = Memory::new(agent_id.clone(), "likes tea")
+            .with_importance(0.9)
+            .with_expires_at(expires_at);
+        save_memory(&conn, &memory).await.unwrap();
+
+        let loaded = load_memories_for_agent(&conn, &agent_id, None)
+            .await
+            .unwrap();
+        assert!((loaded[0].importance - 0.9).abs() < 0.0001);
+        assert_eq!(loaded[0].expires_at, Some(expires_at));
+    }
+
+    #[tokio::test]
+    async fn search_skips_expired_memories_and_weights_importance() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let query = Embedding::new(vec![1.0, 0.0]).unwrap();
+        let close = Embedding::new(vec![1.0, 0.1]).unwrap();
+        let far = Embedding::new(vec![0.6, 0.8]).unwrap();
+        let past = Utc::now() - chrono::Duration::hours(1);
+
+        for memory in [
+            Memory::with_embedding(agent_id.clone(), "close", close.clone()).with_importance(0.0),
+            Memory::with_embedding(agent_id.clone(), "far", far).with_importance(1.0),
+            Memory::with_embedding(agent_id.clone(), "expired", close).with_expires_at(past),
+        ] {
+            save_memory(&conn, &memory).await.unwrap();
+        }
+
+        let by_similarity = search_memories_by_embedding(&conn, &agent_id, &query, 10, None, 1.0)
+            .await
+            .unwrap();
+        let contents: Vec<&str> = by_similarity
+            .iter()
+            .map(|sm| sm.memory.content.as_str())
+            .collect();
+        assert_eq!(contents, vec!["close", "far"]);
+
+        let by_importance = search_memories_by_embedding(&conn, &agent_id, &query, 10, None, 0.5)
+            .await
+            .unwrap();
+        assert_eq!(by_importance[0].memory.content, "far");
+        assert!((by_importance[0].score - 0.8).abs() < 0.001);
+    }
+
+    #[tokio::test]
+    async fn prune_expired_memories_deletes_only_expired() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let other_agent = AgentId::new();
+        let now = Utc::now();
+        let past = now - chrono::Duration::minutes(5);
+        let future = now + chrono::Duration::minutes(5);
+
+        for memory in [
+            Memory::new(agent_id.clone(), "expired").with_expires_at(past),
+            Memory::new(agent_id.clone(), "fresh").with_expires_at(future),
+            Memory::new(agent_id.clone(), "permanent"),
+            Memory::new(other_agent.clone(), "other expired").with_expires_at(past),
+        ] {
+            save_memory(&conn, &memory).await.unwrap();
+        }
+
+        assert_eq!(
+            prune_expired_memories(&conn, Some(&agent_id), now)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(count_memories_for_agent(&conn, &agent_id).await.unwrap(), 2);
+        assert_eq!(prune_expired_memories(&conn, None, now).await.unwrap(), 1);
+        assert_eq!(
+            count_memories_for_agent(&conn, &other_agent).await.unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn bulk_saved_memories_match_individual_saves_in_search_recall() {
+        use crate::memory::{EmbeddingProvider, StubEmbeddingProvider};
+
+        let conn = memory_conn().await;
+        let provider = StubEmbeddingProvider::default();
+        let individual_agent = AgentId::new();
+        let bulk_agent = AgentId::new();
+
+        let mut bulk = Vec::new();
+        for i in 0..100 {
+            let content = format!("fact number {i}");
+            let embedding = provider.embed(&content).await.unwrap();
+            save_memory(
+                &conn,
+                &Memory::with_embedding(individual_agent.clone(), &content, embedding.clone()),
+            )
+            .await
+            .unwrap();
+            bulk.push(Memory::with_embedding(
+                bulk_agent.clone(),
+                content,
+                embedding,
+            ));
+        }
+        assert_eq!(save_memories(&conn, &bulk).await.unwrap(), 100);
+        assert_eq!(
+            count_memories_for_agent(&conn, &bulk_agent).await.unwrap(),
+            100
+        );
+
+        for query in ["fact number 7", "fact number 42", "fact number 99"] {
+            let embedding = provider.embed(query).await.unwrap();
+            let recall = |results: Vec<ScoredMemory>| {
+                results
+                    .into_iter()
+                    .map(|sm| sm.memory.content)
+                    .collect::<Vec<_>>()
+            };
+            let individual =
+                search_memories_by_embedding(&conn, &individual_agent, &embedding, 10, None, 1.0)
+                    .await
+                    .unwrap();
+            let bulk = search_memories_by_embedding(&conn, &bulk_agent, &embedding, 10, None, 1.0)
+                .await
+                .unwrap();
+            assert_eq!(recall(individual.clone()), recall(bulk));
+            assert_eq!(individual[0].memory.content, query);
+        }
+    }
+
+    #[tokio::test]
+    async fn duplicate_memories_merge_into_nearest() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let existing = Memory::with_embedding(
+            agent_id.clone(),
+            "likes tea",
+            Embedding::new(vec![1.0, 0.0]).unwrap(),
+        )
+        .with_importance(0.3);
+        save_memory(&conn, &existing).await.unwrap();
+
+        let near = Embedding::new(vec![0.98, 0.1]).unwrap();
+        let far = Embedding::new(vec![0.0, 1.0]).unwrap();
+        let found = find_duplicate_memory(&conn, &agent_id, &near, 0.95)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.memory.id, existing.id);
+        assert!(find_duplicate_memory(&conn, &agent_id, &far, 0.95)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(find_duplicate_memory(&conn, &AgentId::new(), &near, 0.95)
+            .await
+            .unwrap()
+            .is_none());
+
+        merge_duplicate_memory(&conn, &existing.id, 0.8)
+            .await
+            .unwrap();
+        merge_duplicate_memory(&conn, &existing.id, 0.1)
+            .await
+            .unwrap();
+        let loaded = load_memories_for_agent(&conn, &agent_id, None)
+            .await
+            .unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert!((loaded[0].importance - 0.8).abs() < 0.0001);
+        assert_eq!(
+            query_u64(
+                &conn,
+                "SELECT COUNT(*) FROM memories WHERE updated_at IS NOT NULL"
+            )
+            .await
+            .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn embedding_dimension_is_recorded_and_enforced() {
+        let conn = memory_conn().await;
+        check_embedding_dimension(&conn, 3).await.unwrap();
+        check_embedding_dimension(&conn, 3).await.unwrap();
+        let err = check_embedding_dimension(&conn, 4).await.unwrap_err();
+        assert_eq!(
+            err.kind(),
+            &crate::memory::PersistenceErrorKind::EmbeddingDimensionMismatch {
+                expected: 3,
+                actual: 4
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn embedding_dimension_check_measures_existing_embeddings() {
+        let conn = memory_conn().await;
+        let memory = Memory::with_embedding(
+            AgentId::new(),
+            "likes tea",
+            Embedding::new(vec![0.1, 0.2]).unwrap(),
+        );
+        save_memory(&conn, &memory).await.unwrap();
+
+        assert!(check_embedding_dimension(&conn, 384).await.is_err());
+        check_embedding_dimension(&conn, 2).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn save_memories_is_atomic() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let existing = Memory::new(agent_id.clone(), "existing");
+        save_memory(&conn, &existing).await.unwrap();
+
+        let batch = [Memory::new(agent_id.clone(), "new"), existing];
+        assert!(save_memories(&conn, &batch).await.is_err());
+        assert_eq!(count_memories_for_agent(&conn, &agent_id).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn replace_memories_for_agent_swaps_only_that_agent() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let other = AgentId::new();
+        save_memory(&conn, &Memory::new(agent_id.clone(), "old"))
+            .await
+            .unwrap();
+        save_memory(&conn, &Memory::new(other.clone(), "kept"))
+            .await
+            .unwrap();
+
+        let replacement = [
+            Memory::new(agent_id.clone(), "new 1"),
+            Memory::new(agent_id.clone(), "new 2"),
+        ];
+        assert_eq!(
+            replace_memories_for_agent(&conn, &agent_id, &replacement)
+                .await
+                .unwrap(),
+            2
+        );
+
+        let mut contents: Vec<String> = load_memories_for_agent(&conn, &agent_id, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.content)
+            .collect();
+        contents.sort();
+        assert_eq!(contents, ["new 1", "new 2"]);
+        assert_eq!(count_memories_for_agent(&conn, &other).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn delete_memories_removes_listed_ids() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let memories: Vec<Memory> = (0..3)
+            .map(|i| Memory::new(agent_id.clone(), format!("fact {i}")))
+            .collect();
+        save_memories(&conn, &memories).await.unwrap();
+
+        let ids = [
+            memories[0].id.clone(),
+            memories[2].id.clone(),
+            MemoryId::new(),
+        ];
+        assert_eq!(delete_memories(&conn, &ids).await.unwrap(), 2);
+        assert_eq!(delete_memories(&conn, &[]).await.unwrap(), 0);
+
+        let remaining = load_memories_for_agent(&conn, &agent_id, None)
+            .await
+            .unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].content, "fact 1");
+    }
+
+    #[tokio::test]
+    async fn initialize_schema_adds_memory_columns_to_old_databases() {
+        let db = open_database(&PersistenceConfig::in_memory())
+            .await
+            .unwrap();
+        let conn = db.connect().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE memories (
+                id TEXT PRIMARY KEY,
+                agent_id TEXT NOT NULL,
+                content TEXT NOT NULL,
+                embedding BLOB,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            INSERT INTO memories (id, agent_id, content)
+            VALUES ('mem_legacy', 'agent_legacy', 'old fact');",
+        )
+        .await
+        .unwrap();
+
+        initialize_schema(&conn).await.unwrap();
+        initialize_schema(&conn).await.unwrap();
+
+        let agent_id = AgentId::new();
+        save_memory(
+            &conn,
+            &Memory::new(agent_id.clone(), "new fact").with_importance(0.2),
+        )
+        .await
+        .unwrap();
+        let loaded = load_memories_for_agent(&conn, &agent_id, None)
+            .await
+            .unwrap();
+        assert!((loaded[0].importance - 0.2).abs() < 0.0001);
+        assert_eq!(
+            query_u64(
+                &conn,
+                "SELECT COUNT(*) FROM memories WHERE importance = 0.5"
+            )
+            .await
+            .unwrap(),
+            1
+        );
+        assert_eq!(
+            query_u64(
+                &conn,
+                "SELECT COUNT(*) FROM memories_fts WHERE memories_fts MATCH 'old'"
+            )
+            .await
+            .unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn full_text_index_follows_memory_changes() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let memory = Memory::new(agent_id.clone(), "meeting with Zaphod");
+        save_memory(&conn, &memory).await.unwrap();
+        let count = |query: &'static str| {
+            let conn = conn.clone();
+            async move { query_u64(&conn, query).await.unwrap() }
+        };
+        let zaphod = "SELECT COUNT(*) FROM memories_fts WHERE memories_fts MATCH 'zaphod'";
+
+        assert_eq!(count(zaphod).await, 1);
+
+        conn.execute(
+            "UPDATE memories SET content = 'meeting with Ford' WHERE id = ?1",
+            [memory.id.to_string()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(count(zaphod).await, 0);
+
+        delete_memory(&conn, &memory.id).await.unwrap();
+        assert_eq!(count("SELECT COUNT(*) FROM memories_fts").await, 0);
+    }
+
+    #[tokio::test]
+    async fn hybrid_search_ranks_exact_text_matches() {
+        let conn = memory_conn().await;
+        let agent_id = AgentId::new();
+        let query = Embedding::new(vec![1.0, 0.0]).unwrap();
+
+        for memory in [
+            Memory::with_embedding(
+                agent_id.clone(),
+                "Trillian lives in Islington",
+                Embedding::new(vec![0.0, 1.0]).unwrap(),
+            ),
+            Memory::with_embedding(
+                agent_id.clone(),
+                "The user likes towels",
+                Embedding::new(vec![0.8, 0.6]).unwrap(),
+            ),
+            Memory::new(agent_id.clone(), "Trillian is an astrophysicist")
+                .with_expires_at(Utc::now() - chrono::Duration::hours(1)),
+        ] {
+            save_memory(&conn, &memory).await.unwrap();
+        }
+
+        let vector_only = hybrid_search_memories(&conn, &agent_id, "Trillian?", &query, 10, 1.0)
+            .await
+            .unwrap();
+        assert_eq!(vector_only[0].memory.content, "The user likes towels");
+
+        let results = hybrid_search_memories(&conn, &agent_id, "Trillian?", &query, 10, 0.3)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].memory.content, "Trillian lives in Islington");
+        assert!((results[0].text_score - 1.0).abs() < 0.001);
+        assert!(results[0].vector_score.abs() < 0.001);
+        assert!((results[0].score - 0.7).abs() < 0.001);
+        assert_eq!(results[1].text_score, 0.0);
+        assert!((results[1].vector_score - 0.8).abs() < 0.001);
+
+        let top = hybrid_search_memories(&conn, &agent_id, "", &query, 1, 0.3)
+            .await
+            .unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].memory.content, "The user likes towels");
+    }
 }
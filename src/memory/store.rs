@@ -4,13 +4,24 @@
 //! spawning tokio tasks for database operations to avoid Sync constraints.
 
 use crate::memory::context::{ContextStats, ContextWindow, ContextWindowConfig};
-use crate::memory::embeddings::{Embedding, Memory, ScoredMemory};
+use crate::memory::embeddings::{Embedding, Memory, Reranking, ScoredMemory};
+use crate::memory::encryption::EncryptionConfig;
 use crate::memory::error::PersistenceError;
-use crate::memory::persistence::{self, AgentStateSnapshot, PersistenceConfig};
+use crate::memory::persistence::{
+    self, AgentStateSnapshot, DbStats, PersistenceConfig, ReencryptionStats, VacuumConfig,
+    VacuumStats,
+};
 use crate::messages::Message;
+use crate::tools::versioning::ToolSchemaVersion;
 use crate::types::{AgentId, ConversationId, MemoryId, MessageId};
 use acton_reactive::prelude::*;
+use chrono::{DateTime, Utc};
 use libsql::{Connection, Database};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
 
 // =============================================================================
 // Messages
@@ -111,6 +122,31 @@ pub struct DeleteConversation {
     pub conversation_id: ConversationId,
 }
 
+/// Request to tag a conversation with the tool schema versions it used.
+#[acton_message]
+pub struct TagConversationToolVersions {
+    /// The conversation to tag
+    pub conversation_id: ConversationId,
+    /// The tool schema versions in effect when it was recorded
+    pub versions: Vec<ToolSchemaVersion>,
+}
+
+/// Request the tool schema versions a conversation was tagged with.
+#[acton_message]
+pub struct LoadConversationToolVersions {
+    /// The conversation to look up
+    pub conversation_id: ConversationId,
+}
+
+/// Response to [`LoadConversationToolVersions`].
+#[acton_message]
+pub struct ConversationToolVersions {
+    /// The conversation looked up
+    pub conversation_id: ConversationId,
+    /// The tagged versions (empty if never tagged)
+    pub versions: Vec<ToolSchemaVersion>,
+}
+
 /// Request to list all conversations for an agent.
 #[acton_message]
 pub struct ListConversations {
@@ -138,15 +174,92 @@ pub struct StoreMemory {
     pub content: String,
     /// Optional pre-computed embedding for semantic search
     pub embedding: Option<Embedding>,
+    /// Importance from 0.0 to 1.0 (defaults to
+    /// [`DEFAULT_MEMORY_IMPORTANCE`](crate::memory::DEFAULT_MEMORY_IMPORTANCE))
+    pub importance: Option<f32>,
+    /// When the memory expires (never, if `None`)
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Cosine similarity at or above which the memory is merged into the
+    /// agent's nearest existing memory instead of stored (no dedup, if `None`)
+    pub dedup_threshold: Option<f32>,
 }
 
 /// Response with stored memory ID.
 #[acton_message]
 pub struct MemoryStored {
-    /// The ID of the stored memory
+    /// The ID of the stored memory, or of the existing memory a duplicate
+    /// was merged into
     pub memory_id: MemoryId,
 }
 
+/// One memory in a [`BulkStoreMemories`] request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BulkMemoryInput {
+    /// The content to store
+    pub content: String,
+    /// Optional pre-computed embedding for semantic search
+    pub embedding: Option<Embedding>,
+    /// Importance from 0.0 to 1.0 (defaults to
+    /// [`DEFAULT_MEMORY_IMPORTANCE`](crate::memory::DEFAULT_MEMORY_IMPORTANCE))
+    pub importance: Option<f32>,
+    /// When the memory expires (never, if `None`)
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl BulkMemoryInput {
+    /// Creates an input with only content.
+    #[must_use]
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            embedding: None,
+            importance: None,
+            expires_at: None,
+        }
+    }
+
+    /// Sets the embedding for semantic search.
+    #[must_use]
+    pub fn with_embedding(mut self, embedding: Embedding) -> Self {
+        self.embedding = Some(embedding);
+        self
+    }
+
+    /// Builds the memory this input describes.
+    fn into_memory(self, agent_id: AgentId) -> Memory {
+        let mut memory = match self.embedding {
+            Some(emb) => Memory::with_embedding(agent_id, self.content, emb),
+            None => Memory::new(agent_id, self.content),
+        };
+        if let Some(importance) = self.importance {
+            memory = memory.with_importance(importance);
+        }
+        memory.expires_at = self.expires_at;
+        memory
+    }
+}
+
+/// Request to store many memories for one agent in a single transaction.
+///
+/// Replies with [`BulkStored`].
+#[acton_message]
+pub struct BulkStoreMemories {
+    /// The agent these memories belong to
+    pub agent_id: AgentId,
+    /// The memories to store
+    pub memories: Vec<BulkMemoryInput>,
+}
+
+/// Response after a [`BulkStoreMemories`].
+#[acton_message]
+pub struct BulkStored {
+    /// Number of memories stored
+    pub count: usize,
+    /// Inputs that were not stored. The insert is atomic, so this holds every
+    /// input if the transaction failed.
+    pub failed: Vec<BulkMemoryInput>,
+}
+
 /// Request to search memories by semantic similarity.
 #[acton_message]
 pub struct SearchMemories {
@@ -158,6 +271,58 @@ pub struct SearchMemories {
     pub limit: usize,
     /// Minimum similarity threshold (0.0 to 1.0)
     pub min_similarity: Option<f32>,
+    /// Optional reranking pass applied to the similarity results
+    pub rerank_with: Option<Reranking>,
+    /// Share of the score taken by similarity versus importance (defaults to
+    /// [`ContextWindowConfig::importance_weight`])
+    pub importance_weight: Option<f32>,
+}
+
+/// Request to search memories by both semantic similarity and full-text
+/// relevance.
+///
+/// Each result's score is `alpha * vector_score + (1 - alpha) * text_score`,
+/// so exact matches the embedding misses (e.g., proper nouns) still rank.
+/// Replies with [`MemorySearchResults`].
+#[acton_message]
+pub struct HybridSearchMemories {
+    /// The agent to search within
+    pub agent_id: AgentId,
+    /// The query text for full-text matching
+    pub query_text: String,
+    /// The query embedding to match against
+    pub query_embedding: Embedding,
+    /// Maximum number of results
+    pub top_k: usize,
+    /// Share of the score taken by vector similarity, from 0.0 to 1.0
+    pub alpha: f32,
+}
+
+impl HybridSearchMemories {
+    /// Creates a hybrid search using
+    /// [`ContextWindowConfig::hybrid_search_alpha`]'s default alpha.
+    #[must_use]
+    pub fn new(
+        agent_id: AgentId,
+        query_text: impl Into<String>,
+        query_embedding: Embedding,
+        top_k: usize,
+    ) -> Self {
+        Self {
+            agent_id,
+            query_text: query_text.into(),
+            query_embedding,
+            top_k,
+            alpha: ContextWindowConfig::default().hybrid_search_alpha,
+        }
+    }
+
+    /// Sets the alpha, clamped to 0.0..=1.0.
+    #[must_use]
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha.clamp(0.0, 1.0);
+        self
+    }
 }
 
 /// Response with ranked memory results.
@@ -183,6 +348,170 @@ pub struct MemoriesLoaded {
     pub memories: Vec<Memory>,
 }
 
+/// Request to export all of an agent's memories, for backups or moving
+/// them to another database.
+///
+/// Replies with [`ExportedMemories`]. See also
+/// [`MemoryStore::export_json`].
+#[acton_message]
+pub struct ExportAgentMemories {
+    /// The agent whose memories to export
+    pub agent_id: AgentId,
+}
+
+/// One memory in an [`ExportedMemories`] document, decrypted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedMemory {
+    /// The memory's ID in the exporting database
+    pub memory_id: MemoryId,
+    /// The content
+    pub content: String,
+    /// The embedding values, if the memory has one
+    pub embedding: Option<Vec<f32>>,
+    /// Importance from 0.0 to 1.0
+    pub importance: f32,
+    /// When the memory was created
+    pub created_at: String,
+    /// When the memory expires (never, if `None`)
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ExportedMemory {
+    /// Captures `memory` for export.
+    fn from_memory(memory: Memory) -> Self {
+        Self {
+            memory_id: memory.id,
+            content: memory.content,
+            embedding: memory.embedding.map(|e| e.values().to_vec()),
+            importance: memory.importance,
+            created_at: memory.created_at,
+            expires_at: memory.expires_at,
+        }
+    }
+
+    /// Rebuilds the memory for `agent_id`, keeping the exported ID only if
+    /// `keep_id` is set.
+    fn into_memory(self, agent_id: AgentId, keep_id: bool) -> Result<Memory, PersistenceError> {
+        let embedding = self
+            .embedding
+            .map(Embedding::new)
+            .transpose()
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))?;
+        Ok(Memory {
+            id: if keep_id {
+                self.memory_id
+            } else {
+                MemoryId::new()
+            },
+            agent_id,
+            content: self.content,
+            embedding,
+            created_at: self.created_at,
+            importance: self.importance,
+            expires_at: self.expires_at,
+        })
+    }
+}
+
+/// An agent's exported memories.
+///
+/// Reply to [`ExportAgentMemories`]; serializes to JSON for storage.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct ExportedMemories {
+    /// The agent the memories were exported from
+    pub agent_id: AgentId,
+    /// The memories, newest first
+    pub memories: Vec<ExportedMemory>,
+    /// When the export was taken
+    pub exported_at: DateTime<Utc>,
+}
+
+impl ExportedMemories {
+    /// Serializes the export as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_json(&self) -> Result<String, PersistenceError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| PersistenceError::serialization_failed(e.to_string()))
+    }
+
+    /// Parses an export written by [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `json` is not a valid export.
+    pub fn from_json(json: &str) -> Result<Self, PersistenceError> {
+        serde_json::from_str(json)
+            .map_err(|e| PersistenceError::deserialization_failed(e.to_string()))
+    }
+}
+
+/// How [`ImportAgentMemories`] treats the agent's existing memories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportMergeStrategy {
+    /// Delete the agent's existing memories, then insert the imported ones
+    /// with their original IDs
+    ReplaceAll,
+    /// Skip imported memories whose content the agent already has
+    MergeByContent,
+    /// Insert every imported memory alongside the existing ones
+    AppendAll,
+}
+
+/// Request to import exported memories into an agent.
+///
+/// The memories are stored for `agent_id`, which need not be the agent they
+/// were exported from. Only [`ImportMergeStrategy::ReplaceAll`] keeps the
+/// exported memory IDs; the other strategies assign new ones. The import is
+/// atomic. Replies with [`MemoriesImported`].
+#[acton_message]
+pub struct ImportAgentMemories {
+    /// The agent to import the memories into
+    pub agent_id: AgentId,
+    /// The exported memories
+    pub data: ExportedMemories,
+    /// How to treat the agent's existing memories
+    pub merge_strategy: ImportMergeStrategy,
+}
+
+/// Response after an [`ImportAgentMemories`].
+#[acton_message]
+pub struct MemoriesImported {
+    /// Number of memories stored
+    pub imported: usize,
+    /// Number of memories skipped as duplicates
+    pub skipped: usize,
+}
+
+/// Request to store a conversation summary as a tagged memory.
+///
+/// The memory is tagged with
+/// [`CONVERSATION_SUMMARY_TAG`](super::persistence::CONVERSATION_SUMMARY_TAG)
+/// and linked to `conversation_id`. Replies with [`MemoryStored`].
+#[acton_message]
+pub struct StoreConversationSummary {
+    /// The agent this summary belongs to
+    pub agent_id: AgentId,
+    /// The conversation that was summarized
+    pub conversation_id: ConversationId,
+    /// The summary text
+    pub content: String,
+    /// Optional pre-computed embedding for semantic search
+    pub embedding: Option<Embedding>,
+}
+
+/// Request to load all conversation summaries for an agent.
+///
+/// Replies with [`MemoriesLoaded`], newest first.
+#[acton_message]
+pub struct LoadConversationSummaries {
+    /// The agent to load summaries for
+    pub agent_id: AgentId,
+}
+
 /// Request to delete a memory.
 #[acton_message]
 pub struct DeleteMemory {
@@ -197,11 +526,126 @@ pub struct DeleteAgentMemories {
     pub agent_id: AgentId,
 }
 
+/// Request to delete several memories by ID in one statement.
+///
+/// Replies with [`BulkDeleted`].
+#[acton_message]
+pub struct BulkDeleteMemories {
+    /// The memories to delete
+    pub memory_ids: Vec<MemoryId>,
+}
+
+/// Response after a [`BulkDeleteMemories`].
+#[acton_message]
+pub struct BulkDeleted {
+    /// Number of memories deleted
+    pub count: usize,
+}
+
+/// Request to delete an agent's expired memories.
+///
+/// Replies with [`ExpiredMemoriesPruned`].
+#[acton_message]
+pub struct PruneExpiredMemories {
+    /// The agent whose expired memories to delete
+    pub agent_id: AgentId,
+}
+
+/// Response after a [`PruneExpiredMemories`].
+#[acton_message]
+pub struct ExpiredMemoriesPruned {
+    /// Number of memories deleted
+    pub count: u64,
+}
+
+/// Request to rotate the encryption key for all stored content.
+///
+/// Every message and memory row is decrypted with `old_config` and
+/// re-encrypted with `new_config` inside a single transaction. On success
+/// the store adopts `new_config` for subsequent reads and writes and replies
+/// with [`ReencryptionComplete`].
+#[acton_message]
+pub struct ReencryptAll {
+    /// The configuration the rows are currently encrypted with
+    pub old_config: EncryptionConfig,
+    /// The configuration to re-encrypt the rows with
+    pub new_config: EncryptionConfig,
+}
+
+/// Response after a successful [`ReencryptAll`].
+#[acton_message]
+pub struct ReencryptionComplete {
+    /// Counts of rewritten rows
+    pub stats: ReencryptionStats,
+}
+
+/// Request to compact the database.
+///
+/// Replies with [`MemoryStoreVacuumed`].
+#[acton_message]
+pub struct VacuumMemoryStore {
+    /// How to vacuum
+    pub config: VacuumConfig,
+}
+
+/// Response after a successful [`VacuumMemoryStore`].
+#[acton_message]
+pub struct MemoryStoreVacuumed {
+    /// What the vacuum did
+    pub stats: VacuumStats,
+}
+
+/// Request database size and fragmentation statistics.
+///
+/// Replies with [`DbStatsResponse`].
+#[acton_message]
+pub struct GetDbStats;
+
+/// Response to [`GetDbStats`].
+#[acton_message]
+pub struct DbStatsResponse {
+    /// Current database statistics
+    pub stats: DbStats,
+}
+
+/// Internal message to record the latest measured fragmentation.
+#[acton_message]
+struct RecordFragmentation {
+    /// Free pages / total pages
+    ratio: f32,
+}
+
+/// Internal message to count a duplicate merged instead of stored.
+#[acton_message]
+struct RecordDuplicateMemory;
+
+/// Internal message to count memories stored by an import.
+#[acton_message]
+struct RecordImportedMemories {
+    count: usize,
+}
+
+/// Internal message to count memories deleted by a prune.
+#[acton_message]
+struct RecordPrunedMemories {
+    /// Number of memories deleted
+    count: u64,
+}
+
+/// Internal message to adopt a new encryption config after key rotation.
+#[acton_message]
+struct SetEncryption {
+    /// The encryption config now protecting stored content
+    encryption: EncryptionConfig,
+}
+
 /// Internal message to set the database connection after async initialization.
 #[acton_message]
 struct SetConnection {
     /// The initialized database connection
     conn: Connection,
+    /// The configuration the connection was opened with, if not already set
+    config: Option<PersistenceConfig>,
 }
 
 /// Request optimized context window.
@@ -219,6 +663,18 @@ pub struct GetContextWindow {
     pub max_tokens: usize,
     /// Number of memories to retrieve
     pub memory_limit: usize,
+    /// Similarity at or above which retrieved memories are dropped as
+    /// duplicates (see [`ContextWindowConfig::dedup_threshold`])
+    pub dedup_threshold: Option<f32>,
+    /// Share of memory scores taken by similarity versus importance
+    /// (defaults to [`ContextWindowConfig::importance_weight`])
+    pub importance_weight: Option<f32>,
+    /// Query text for full-text memory matching. When set alongside
+    /// `query_embedding`, memories are retrieved by hybrid search.
+    pub query_text: Option<String>,
+    /// Share of hybrid search scores taken by similarity versus text
+    /// relevance (defaults to [`ContextWindowConfig::hybrid_search_alpha`])
+    pub hybrid_search_alpha: Option<f32>,
 }
 
 /// Response with optimized context.
@@ -255,6 +711,12 @@ pub struct MemoryStoreMetrics {
     pub memory_searches: u64,
     /// Number of context windows built
     pub context_windows_built: u64,
+    /// Fraction of database pages that were free at the last measurement
+    pub fragmentation_ratio: f32,
+    /// Number of expired memories pruned
+    pub memories_pruned: u64,
+    /// Number of stored memories merged into an existing duplicate
+    pub duplicates_prevented: u64,
 }
 
 // =============================================================================
@@ -274,9 +736,46 @@ pub struct MemoryStore {
     pub shutting_down: bool,
     /// Metrics
     pub metrics: MemoryStoreMetrics,
+    /// Periodic vacuum task, when auto-vacuum is configured
+    auto_vacuum_task: Option<tokio::task::JoinHandle<()>>,
+    /// Periodic expired-memory pruning task, when configured
+    memory_prune_task: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl MemoryStore {
+    /// Returns the configured encryption settings, if any.
+    fn encryption(&self) -> Option<EncryptionConfig> {
+        self.config.as_ref().and_then(|c| c.encryption.clone())
+    }
+
+    /// Exports all of `agent_id`'s memories from `store` as pretty-printed
+    /// JSON.
+    ///
+    /// The JSON is an [`ExportedMemories`] document that
+    /// [`ExportedMemories::from_json`] reads back for an
+    /// [`ImportAgentMemories`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store does not reply within 30 seconds
+    /// (it logs the underlying failure) or serialization fails.
+    pub async fn export_json(
+        runtime: &mut ActorRuntime,
+        store: &ActorHandle,
+        agent_id: AgentId,
+    ) -> Result<String, PersistenceError> {
+        let request = ExportAgentMemories { agent_id };
+        query_store::<ExportedMemories>(runtime, store, request, EXPORT_TIMEOUT)
+            .await
+            .ok_or_else(|| {
+                PersistenceError::query_failed(
+                    "export_agent_memories",
+                    "memory store did not reply",
+                )
+            })?
+            .to_json()
+    }
+
     /// Spawns the Memory Store actor.
     ///
     /// # Arguments
@@ -300,6 +799,12 @@ impl MemoryStore {
                 Reply::ready()
             })
             .before_stop(|actor| {
+                if let Some(task) = &actor.model.auto_vacuum_task {
+                    task.abort();
+                }
+                if let Some(task) = &actor.model.memory_prune_task {
+                    task.abort();
+                }
                 tracing::info!(
                     conversations_created = actor.model.metrics.conversations_created,
                     messages_saved = actor.model.metrics.messages_saved,
@@ -313,6 +818,83 @@ impl MemoryStore {
 
         builder.start().await
     }
+
+    /// Opens the database described by `config`, then spawns a Memory Store
+    /// using it.
+    ///
+    /// Unlike sending [`InitMemoryStore`], initialization failures are
+    /// returned instead of only logged, and the store is ready for requests
+    /// as soon as this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened, its schema cannot
+    /// be initialized, or its embeddings do not match
+    /// [`PersistenceConfig::embedding_dimension`].
+    pub async fn spawn_initialized(
+        runtime: &mut ActorRuntime,
+        config: PersistenceConfig,
+    ) -> Result<ActorHandle, PersistenceError> {
+        let (_db, conn) = initialize_database(&config).await?;
+        let store = Self::spawn(runtime).await;
+        store
+            .send(SetConnection {
+                conn,
+                config: Some(config),
+            })
+            .await;
+        Ok(store)
+    }
+}
+
+/// How long [`MemoryStore::export_json`] waits for the export.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Temporary actor that receives a single reply from the store.
+#[acton_actor]
+struct StoreReplyReceiver;
+
+/// Sends `request` to `store` and waits up to `timeout` for its `R` reply.
+///
+/// Returns `None` on timeout; the store logs failures instead of replying,
+/// so a missing reply usually means the request failed.
+pub(crate) async fn query_store<R>(
+    runtime: &mut ActorRuntime,
+    store: &ActorHandle,
+    request: impl ActonMessage,
+    timeout: Duration,
+) -> Option<R>
+where
+    R: ActonMessage + Clone + Send + Sync + 'static,
+{
+    let slot: Arc<Mutex<Option<R>>> = Arc::new(Mutex::new(None));
+    let received = Arc::new(Notify::new());
+
+    let mut receiver = runtime.new_actor::<StoreReplyReceiver>();
+    let slot_for_handler = slot.clone();
+    let received_signal = received.clone();
+    receiver.mutate_on::<R>(move |_actor, envelope| {
+        if let Ok(mut slot) = slot_for_handler.lock() {
+            *slot = Some(envelope.message().clone());
+        }
+        received_signal.notify_one();
+        Reply::ready()
+    });
+    let receiver = receiver.start().await;
+
+    receiver
+        .create_envelope(Some(store.reply_address()))
+        .send(request)
+        .await;
+    let replied = tokio::time::timeout(timeout, received.notified())
+        .await
+        .is_ok();
+    let _ = receiver.stop().await;
+
+    if !replied {
+        return None;
+    }
+    slot.lock().ok().and_then(|mut guard| guard.take())
 }
 
 /// Configures message handlers for the Memory Store actor.
@@ -322,14 +904,46 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
     configure_message_handlers(builder);
     configure_state_handlers(builder);
     configure_memory_handlers(builder);
+    configure_encryption_handlers(builder);
+    configure_maintenance_handlers(builder);
+    configure_tool_version_handlers(builder);
 }
 
 /// Configures the initialization handler.
 fn configure_init_handler(builder: &mut ManagedActor<Idle, MemoryStore>) {
     // Handle SetConnection (internal message for async init completion)
     builder.mutate_on::<SetConnection>(|actor, envelope| {
-        actor.model.connection = Some(envelope.message().conn.clone());
+        let conn = envelope.message().conn.clone();
+        if let Some(config) = &envelope.message().config {
+            actor.model.config = Some(config.clone());
+        }
+        actor.model.connection = Some(conn.clone());
         tracing::info!("Memory Store connection established");
+
+        let auto_vacuum = actor.model.config.as_ref().and_then(|c| c.auto_vacuum);
+        if let Some(vacuum_config) = auto_vacuum {
+            if let Some(previous) = actor.model.auto_vacuum_task.take() {
+                previous.abort();
+            }
+            actor.model.auto_vacuum_task = Some(spawn_auto_vacuum(
+                conn.clone(),
+                vacuum_config,
+                actor.handle().clone(),
+            ));
+        }
+
+        let prune_interval = actor
+            .model
+            .config
+            .as_ref()
+            .and_then(|c| c.memory_prune_interval);
+        if let Some(interval) = prune_interval {
+            if let Some(previous) = actor.model.memory_prune_task.take() {
+                previous.abort();
+            }
+            actor.model.memory_prune_task =
+                Some(spawn_memory_pruning(conn, interval, actor.handle().clone()));
+        }
         Reply::ready()
     });
 
@@ -344,7 +958,7 @@ fn configure_init_handler(builder: &mut ManagedActor<Idle, MemoryStore>) {
             match initialize_database(&config).await {
                 Ok((_db, conn)) => {
                     // Send connection back to actor via message
-                    actor_handle.send(SetConnection { conn }).await;
+                    actor_handle.send(SetConnection { conn, config: None }).await;
                     tracing::info!(db_path = %config.db_path, "Memory Store initialized with database");
                 }
                 Err(e) => {
@@ -368,6 +982,9 @@ async fn initialize_database(
         .connect()
         .map_err(|e| PersistenceError::connection_error(e.to_string()))?;
     persistence::initialize_schema(&conn).await?;
+    if let Some(dimension) = config.embedding_dimension {
+        persistence::check_embedding_dimension(&conn, dimension).await?;
+    }
     Ok((db, conn))
 }
 
@@ -414,6 +1031,7 @@ fn configure_conversation_handlers(builder: &mut ManagedActor<Idle, MemoryStore>
         }
 
         let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
         let conversation_id = envelope.message().conversation_id.clone();
         let reply = envelope.reply_envelope();
         actor.model.metrics.conversations_loaded += 1;
@@ -424,7 +1042,14 @@ fn configure_conversation_handlers(builder: &mut ManagedActor<Idle, MemoryStore>
                 return;
             };
 
-            match persistence::load_conversation_messages(&conn, &conversation_id).await {
+            let loaded = persistence::load_conversation_messages(&conn, &conversation_id)
+                .await
+                .and_then(|mut messages| {
+                    persistence::decrypt_messages(&mut messages, encryption.as_ref())?;
+                    Ok(messages)
+                });
+
+            match loaded {
                 Ok(messages) => {
                     reply
                         .send(ConversationLoaded {
@@ -537,6 +1162,78 @@ fn configure_conversation_handlers(builder: &mut ManagedActor<Idle, MemoryStore>
     });
 }
 
+/// Configures handlers for tagging conversations with tool schema versions.
+fn configure_tool_version_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
+    builder.mutate_on::<TagConversationToolVersions>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting TagConversationToolVersions - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let msg = envelope.message();
+        let conversation_id = msg.conversation_id.clone();
+        let versions = msg.versions.clone();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            if let Err(e) =
+                persistence::save_conversation_tool_versions(&conn, &conversation_id, &versions)
+                    .await
+            {
+                tracing::error!(
+                    conversation_id = %conversation_id,
+                    error = %e,
+                    "Failed to tag conversation with tool versions"
+                );
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    builder.act_on::<LoadConversationToolVersions>(|actor, envelope| {
+        let conn = actor.model.connection.clone();
+        let conversation_id = envelope.message().conversation_id.clone();
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            match persistence::load_conversation_tool_versions(&conn, &conversation_id).await {
+                Ok(versions) => {
+                    reply
+                        .send(ConversationToolVersions {
+                            conversation_id,
+                            versions,
+                        })
+                        .await;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        conversation_id = %conversation_id,
+                        error = %e,
+                        "Failed to load conversation tool versions"
+                    );
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+}
+
 /// Configures message-related handlers.
 fn configure_message_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
     builder.mutate_on::<SaveMessage>(|actor, envelope| {
@@ -546,6 +1243,7 @@ fn configure_message_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
         }
 
         let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
         let msg = envelope.message();
         let conversation_id = msg.conversation_id.clone();
         let message = msg.message.clone();
@@ -558,7 +1256,12 @@ fn configure_message_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
                 return;
             };
 
-            match persistence::save_message(&conn, &conversation_id, &message).await {
+            let saved = match persistence::encrypt_message(&message, encryption.as_ref()) {
+                Ok(sealed) => persistence::save_message(&conn, &conversation_id, &sealed).await,
+                Err(e) => Err(e),
+            };
+
+            match saved {
                 Ok(message_id) => {
                     reply.send(MessageSaved { message_id }).await;
                 }
@@ -647,10 +1350,15 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
         }
 
         let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
         let msg = envelope.message();
         let agent_id = msg.agent_id.clone();
         let content = msg.content.clone();
         let embedding = msg.embedding.clone();
+        let importance = msg.importance;
+        let expires_at = msg.expires_at;
+        let dedup_threshold = msg.dedup_threshold;
+        let actor_handle = actor.handle().clone();
         let reply = envelope.reply_envelope();
         actor.model.metrics.memories_stored += 1;
 
@@ -660,12 +1368,35 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
                 return;
             };
 
-            let memory = match embedding {
+            let mut memory = match embedding {
                 Some(emb) => Memory::with_embedding(agent_id.clone(), content, emb),
                 None => Memory::new(agent_id.clone(), content),
             };
+            if let Some(importance) = importance {
+                memory = memory.with_importance(importance);
+            }
+            memory.expires_at = expires_at;
+
+            if let Some(threshold) = dedup_threshold {
+                match merge_if_duplicate(&conn, &memory, threshold).await {
+                    Ok(Some(memory_id)) => {
+                        actor_handle.send(RecordDuplicateMemory).await;
+                        reply.send(MemoryStored { memory_id }).await;
+                        return;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::warn!(agent_id = %agent_id, error = %e, "Memory deduplication failed; storing as new");
+                    }
+                }
+            }
+
+            let saved = match persistence::encrypt_memory(&memory, encryption.as_ref()) {
+                Ok(sealed) => persistence::save_memory(&conn, &sealed).await,
+                Err(e) => Err(e),
+            };
 
-            match persistence::save_memory(&conn, &memory).await {
+            match saved {
                 Ok(memory_id) => {
                     reply.send(MemoryStored { memory_id }).await;
                 }
@@ -680,21 +1411,20 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
         })
     });
 
-    // Handle search memories
-    builder.mutate_on::<SearchMemories>(|actor, envelope| {
+    // Handle bulk store memories
+    builder.mutate_on::<BulkStoreMemories>(|actor, envelope| {
         if actor.model.shutting_down {
-            tracing::warn!("Rejecting SearchMemories - store is shutting down");
+            tracing::warn!("Rejecting BulkStoreMemories - store is shutting down");
             return Reply::ready();
         }
 
         let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
         let msg = envelope.message();
         let agent_id = msg.agent_id.clone();
-        let query_embedding = msg.query_embedding.clone();
-        let limit = msg.limit;
-        let min_similarity = msg.min_similarity;
+        let inputs = msg.memories.clone();
         let reply = envelope.reply_envelope();
-        actor.model.metrics.memory_searches += 1;
+        actor.model.metrics.memories_stored += inputs.len() as u64;
 
         let handle = tokio::spawn(async move {
             let Some(conn) = conn else {
@@ -702,17 +1432,142 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
                 return;
             };
 
-            match persistence::search_memories_by_embedding(
-                &conn,
-                &agent_id,
-                &query_embedding,
-                limit,
-                min_similarity,
-            )
-            .await
-            {
-                Ok(results) => {
-                    reply.send(MemorySearchResults { results }).await;
+            let mut prepared = Vec::with_capacity(inputs.len());
+            let mut failed = Vec::new();
+            for input in inputs {
+                let memory = input.clone().into_memory(agent_id.clone());
+                match persistence::encrypt_memory(&memory, encryption.as_ref()) {
+                    Ok(sealed) => prepared.push((input, sealed)),
+                    Err(e) => {
+                        tracing::warn!(agent_id = %agent_id, error = %e, "Failed to encrypt memory");
+                        failed.push(input);
+                    }
+                }
+            }
+
+            let memories: Vec<Memory> = prepared.iter().map(|(_, m)| m.clone()).collect();
+            let count = match persistence::save_memories(&conn, &memories).await {
+                Ok(count) => count,
+                Err(e) => {
+                    tracing::error!(agent_id = %agent_id, error = %e, "Failed to bulk store memories");
+                    failed.extend(prepared.into_iter().map(|(input, _)| input));
+                    0
+                }
+            };
+
+            reply.send(BulkStored { count, failed }).await;
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    // Handle search memories
+    builder.mutate_on::<SearchMemories>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting SearchMemories - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
+        let msg = envelope.message();
+        let agent_id = msg.agent_id.clone();
+        let query_embedding = msg.query_embedding.clone();
+        let limit = msg.limit;
+        let min_similarity = msg.min_similarity;
+        let rerank_with = msg.rerank_with.clone();
+        let importance_weight = importance_weight_or_default(msg.importance_weight);
+        let reply = envelope.reply_envelope();
+        actor.model.metrics.memory_searches += 1;
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            let searched = persistence::search_memories_by_embedding(
+                &conn,
+                &agent_id,
+                &query_embedding,
+                limit,
+                min_similarity,
+                importance_weight,
+            )
+            .await
+            .and_then(|mut results| {
+                persistence::decrypt_memories(
+                    results.iter_mut().map(|sm| &mut sm.memory),
+                    encryption.as_ref(),
+                )?;
+                Ok(results)
+            });
+
+            match searched {
+                Ok(results) => {
+                    let results = match rerank_with {
+                        Some(reranking) => rerank_or_keep(&reranking, results).await,
+                        None => results,
+                    };
+                    reply.send(MemorySearchResults { results }).await;
+                }
+                Err(e) => {
+                    tracing::error!(agent_id = %agent_id, error = %e, "Failed to search memories");
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    // Handle hybrid search memories
+    builder.mutate_on::<HybridSearchMemories>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting HybridSearchMemories - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
+        let msg = envelope.message();
+        let agent_id = msg.agent_id.clone();
+        let query_text = msg.query_text.clone();
+        let query_embedding = msg.query_embedding.clone();
+        let top_k = msg.top_k;
+        let alpha = msg.alpha.clamp(0.0, 1.0);
+        let reply = envelope.reply_envelope();
+        actor.model.metrics.memory_searches += 1;
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            let searched = persistence::hybrid_search_memories(
+                &conn,
+                &agent_id,
+                &query_text,
+                &query_embedding,
+                top_k,
+                alpha,
+            )
+            .await
+            .and_then(|mut results| {
+                persistence::decrypt_memories(
+                    results.iter_mut().map(|sm| &mut sm.memory),
+                    encryption.as_ref(),
+                )?;
+                Ok(results)
+            });
+
+            match searched {
+                Ok(results) => {
+                    reply.send(MemorySearchResults { results }).await;
                 }
                 Err(e) => {
                     tracing::error!(agent_id = %agent_id, error = %e, "Failed to search memories");
@@ -733,6 +1588,7 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
         }
 
         let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
         let msg = envelope.message();
         let agent_id = msg.agent_id.clone();
         let limit = msg.limit;
@@ -744,7 +1600,14 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
                 return;
             };
 
-            match persistence::load_memories_for_agent(&conn, &agent_id, limit).await {
+            let loaded = persistence::load_memories_for_agent(&conn, &agent_id, limit)
+                .await
+                .and_then(|mut memories| {
+                    persistence::decrypt_memories(&mut memories, encryption.as_ref())?;
+                    Ok(memories)
+                });
+
+            match loaded {
                 Ok(memories) => {
                     reply.send(MemoriesLoaded { memories }).await;
                 }
@@ -759,6 +1622,197 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
         })
     });
 
+    // Handle export agent memories
+    builder.mutate_on::<ExportAgentMemories>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting ExportAgentMemories - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
+        let agent_id = envelope.message().agent_id.clone();
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            let loaded = persistence::load_memories_for_agent(&conn, &agent_id, None)
+                .await
+                .and_then(|mut memories| {
+                    persistence::decrypt_memories(&mut memories, encryption.as_ref())?;
+                    Ok(memories)
+                });
+
+            match loaded {
+                Ok(memories) => {
+                    let memories = memories
+                        .into_iter()
+                        .map(ExportedMemory::from_memory)
+                        .collect();
+                    reply
+                        .send(ExportedMemories {
+                            agent_id,
+                            memories,
+                            exported_at: Utc::now(),
+                        })
+                        .await;
+                }
+                Err(e) => {
+                    tracing::error!(agent_id = %agent_id, error = %e, "Failed to export memories");
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    // Handle import agent memories
+    builder.mutate_on::<ImportAgentMemories>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting ImportAgentMemories - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
+        let msg = envelope.message();
+        let agent_id = msg.agent_id.clone();
+        let exported = msg.data.memories.clone();
+        let strategy = msg.merge_strategy;
+        let actor_handle = actor.handle().clone();
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            match import_memories(&conn, encryption.as_ref(), &agent_id, exported, strategy).await {
+                Ok(imported) => {
+                    actor_handle
+                        .send(RecordImportedMemories {
+                            count: imported.imported,
+                        })
+                        .await;
+                    reply.send(imported).await;
+                }
+                Err(e) => {
+                    tracing::error!(agent_id = %agent_id, error = %e, "Failed to import memories");
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    // Handle store conversation summary
+    builder.mutate_on::<StoreConversationSummary>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting StoreConversationSummary - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
+        let msg = envelope.message();
+        let agent_id = msg.agent_id.clone();
+        let conversation_id = msg.conversation_id.clone();
+        let content = msg.content.clone();
+        let embedding = msg.embedding.clone();
+        let reply = envelope.reply_envelope();
+        actor.model.metrics.memories_stored += 1;
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            let memory = match embedding {
+                Some(emb) => Memory::with_embedding(agent_id.clone(), content, emb),
+                None => Memory::new(agent_id.clone(), content),
+            };
+
+            let saved = match persistence::encrypt_memory(&memory, encryption.as_ref()) {
+                Ok(sealed) => {
+                    persistence::save_conversation_summary(&conn, &sealed, &conversation_id).await
+                }
+                Err(e) => Err(e),
+            };
+
+            match saved {
+                Ok(memory_id) => {
+                    reply.send(MemoryStored { memory_id }).await;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        agent_id = %agent_id,
+                        conversation_id = %conversation_id,
+                        error = %e,
+                        "Failed to store conversation summary"
+                    );
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    // Handle load conversation summaries
+    builder.mutate_on::<LoadConversationSummaries>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting LoadConversationSummaries - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
+        let agent_id = envelope.message().agent_id.clone();
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            let loaded = persistence::load_conversation_summaries(&conn, &agent_id)
+                .await
+                .and_then(|mut memories| {
+                    persistence::decrypt_memories(&mut memories, encryption.as_ref())?;
+                    Ok(memories)
+                });
+
+            match loaded {
+                Ok(memories) => {
+                    reply.send(MemoriesLoaded { memories }).await;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        agent_id = %agent_id,
+                        error = %e,
+                        "Failed to load conversation summaries"
+                    );
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
     // Handle delete memory
     builder.mutate_on::<DeleteMemory>(|actor, envelope| {
         if actor.model.shutting_down {
@@ -811,6 +1865,90 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
         })
     });
 
+    // Handle bulk delete memories
+    builder.act_on::<BulkDeleteMemories>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting BulkDeleteMemories - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let memory_ids = envelope.message().memory_ids.clone();
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            match persistence::delete_memories(&conn, &memory_ids).await {
+                Ok(count) => {
+                    reply
+                        .send(BulkDeleted {
+                            count: usize::try_from(count).unwrap_or(usize::MAX),
+                        })
+                        .await;
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to bulk delete memories");
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    // Handle prune expired memories
+    builder.act_on::<PruneExpiredMemories>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting PruneExpiredMemories - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let actor_handle = actor.handle().clone();
+        let agent_id = envelope.message().agent_id.clone();
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            match run_memory_pruning(&conn, Some(&agent_id), &actor_handle).await {
+                Ok(count) => {
+                    reply.send(ExpiredMemoriesPruned { count }).await;
+                }
+                Err(e) => {
+                    tracing::error!(agent_id = %agent_id, error = %e, "Failed to prune expired memories");
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    builder.mutate_on::<RecordImportedMemories>(|actor, envelope| {
+        actor.model.metrics.memories_stored += envelope.message().count as u64;
+        Reply::ready()
+    });
+
+    builder.mutate_on::<RecordDuplicateMemory>(|actor, _envelope| {
+        actor.model.metrics.duplicates_prevented += 1;
+        Reply::ready()
+    });
+
+    builder.mutate_on::<RecordPrunedMemories>(|actor, envelope| {
+        actor.model.metrics.memories_pruned += envelope.message().count;
+        Reply::ready()
+    });
+
     // Handle get context window
     builder.mutate_on::<GetContextWindow>(|actor, envelope| {
         if actor.model.shutting_down {
@@ -819,6 +1957,7 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
         }
 
         let conn = actor.model.connection.clone();
+        let encryption = actor.model.encryption();
         let msg = envelope.message();
         let agent_id = msg.agent_id.clone();
         let system_prompt = msg.system_prompt.clone();
@@ -826,6 +1965,12 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
         let query_embedding = msg.query_embedding.clone();
         let max_tokens = msg.max_tokens;
         let memory_limit = msg.memory_limit;
+        let dedup_threshold = msg.dedup_threshold;
+        let importance_weight = importance_weight_or_default(msg.importance_weight);
+        let query_text = msg.query_text.clone();
+        let hybrid_search_alpha = msg
+            .hybrid_search_alpha
+            .unwrap_or_else(|| ContextWindowConfig::default().hybrid_search_alpha);
         let reply = envelope.reply_envelope();
         actor.model.metrics.context_windows_built += 1;
 
@@ -838,16 +1983,37 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
             // Retrieve relevant memories if query embedding provided
             let memories = match query_embedding {
                 Some(ref emb) => {
-                    match persistence::search_memories_by_embedding(
-                        &conn,
-                        &agent_id,
-                        emb,
-                        memory_limit,
-                        Some(0.0), // Include all matches
-                    )
-                    .await
-                    {
-                        Ok(results) => results.into_iter().map(|sm| sm.memory).collect(),
+                    let searched = match query_text {
+                        Some(ref text) => {
+                            persistence::hybrid_search_memories(
+                                &conn,
+                                &agent_id,
+                                text,
+                                emb,
+                                memory_limit,
+                                hybrid_search_alpha.clamp(0.0, 1.0),
+                            )
+                            .await
+                        }
+                        None => {
+                            persistence::search_memories_by_embedding(
+                                &conn,
+                                &agent_id,
+                                emb,
+                                memory_limit,
+                                Some(0.0), // Include all matches
+                                importance_weight,
+                            )
+                            .await
+                        }
+                    };
+                    match searched.and_then(|results| {
+                        let mut memories: Vec<Memory> =
+                            results.into_iter().map(|sm| sm.memory).collect();
+                        persistence::decrypt_memories(&mut memories, encryption.as_ref())?;
+                        Ok(memories)
+                    }) {
+                        Ok(memories) => memories,
                         Err(e) => {
                             tracing::warn!(error = %e, "Failed to retrieve memories for context");
                             Vec::new()
@@ -860,7 +2026,10 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
             let included_memories = memories.len();
 
             // Build context window
-            let config = ContextWindowConfig::with_max_tokens(max_tokens);
+            let mut config = ContextWindowConfig::with_max_tokens(max_tokens)
+                .with_importance_weight(importance_weight)
+                .with_hybrid_search_alpha(hybrid_search_alpha);
+            config.dedup_threshold = dedup_threshold;
             let window = ContextWindow::new(config);
 
             let messages = window.build_context(&system_prompt, &memories, &conversation);
@@ -881,6 +2050,289 @@ fn configure_memory_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
     });
 }
 
+/// Configures encryption key rotation handlers.
+fn configure_encryption_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
+    builder.mutate_on::<SetEncryption>(|actor, envelope| {
+        let encryption = envelope.message().encryption.clone();
+        if let Some(config) = actor.model.config.as_mut() {
+            config.encryption = Some(encryption);
+        }
+        tracing::info!("Memory Store encryption key rotated");
+        Reply::ready()
+    });
+
+    builder.mutate_on::<ReencryptAll>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting ReencryptAll - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let actor_handle = actor.handle().clone();
+        let msg = envelope.message();
+        let old_config = msg.old_config.clone();
+        let new_config = msg.new_config.clone();
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            match persistence::reencrypt_all(&conn, &old_config, &new_config).await {
+                Ok(stats) => {
+                    tracing::info!(
+                        messages = stats.messages,
+                        memories = stats.memories,
+                        "Re-encrypted stored content"
+                    );
+                    actor_handle
+                        .send(SetEncryption {
+                            encryption: new_config,
+                        })
+                        .await;
+                    reply.send(ReencryptionComplete { stats }).await;
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to re-encrypt stored content");
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+}
+
+/// Configures vacuum and database statistics handlers.
+fn configure_maintenance_handlers(builder: &mut ManagedActor<Idle, MemoryStore>) {
+    builder.mutate_on::<RecordFragmentation>(|actor, envelope| {
+        actor.model.metrics.fragmentation_ratio = envelope.message().ratio;
+        Reply::ready()
+    });
+
+    builder.act_on::<VacuumMemoryStore>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting VacuumMemoryStore - store is shutting down");
+            return Reply::ready();
+        }
+
+        let conn = actor.model.connection.clone();
+        let actor_handle = actor.handle().clone();
+        let config = envelope.message().config;
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            match run_vacuum(&conn, &config, &actor_handle).await {
+                Ok(stats) => {
+                    reply.send(MemoryStoreVacuumed { stats }).await;
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to vacuum database");
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+
+    builder.act_on::<GetDbStats>(|actor, envelope| {
+        let conn = actor.model.connection.clone();
+        let actor_handle = actor.handle().clone();
+        let reply = envelope.reply_envelope();
+
+        let handle = tokio::spawn(async move {
+            let Some(conn) = conn else {
+                tracing::error!("Memory Store not initialized");
+                return;
+            };
+
+            match persistence::db_stats(&conn).await {
+                Ok(stats) => {
+                    actor_handle
+                        .send(RecordFragmentation {
+                            ratio: stats.fragmentation_ratio(),
+                        })
+                        .await;
+                    reply.send(DbStatsResponse { stats }).await;
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "Failed to read database stats");
+                }
+            }
+        });
+
+        Reply::pending(async move {
+            let _ = handle.await;
+        })
+    });
+}
+
+/// Vacuums the database and records the resulting fragmentation.
+async fn run_vacuum(
+    conn: &Connection,
+    config: &VacuumConfig,
+    actor_handle: &ActorHandle,
+) -> Result<VacuumStats, PersistenceError> {
+    let stats = persistence::vacuum(conn, config).await?;
+    let after = persistence::db_stats(conn).await?;
+    actor_handle
+        .send(RecordFragmentation {
+            ratio: after.fragmentation_ratio(),
+        })
+        .await;
+    Ok(stats)
+}
+
+/// Spawns the periodic auto-vacuum task.
+fn spawn_auto_vacuum(
+    conn: Connection,
+    config: VacuumConfig,
+    actor_handle: ActorHandle,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(VacuumConfig::AUTO_INTERVAL);
+        // The first tick completes immediately; skip it so startup is not
+        // slowed by a vacuum
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_vacuum(&conn, &config, &actor_handle).await {
+                tracing::warn!(error = %e, "Auto-vacuum failed");
+            }
+        }
+    })
+}
+
+/// Stores `exported` for `agent_id` according to `strategy`, reporting how
+/// many memories were imported and how many were skipped as duplicates.
+///
+/// # Errors
+///
+/// Returns an error if an exported memory is invalid, encryption or
+/// decryption fails, or the database cannot be read or written.
+async fn import_memories(
+    conn: &Connection,
+    encryption: Option<&EncryptionConfig>,
+    agent_id: &AgentId,
+    exported: Vec<ExportedMemory>,
+    strategy: ImportMergeStrategy,
+) -> Result<MemoriesImported, PersistenceError> {
+    let total = exported.len();
+    let keep_ids = strategy == ImportMergeStrategy::ReplaceAll;
+    let mut memories = exported
+        .into_iter()
+        .map(|m| m.into_memory(agent_id.clone(), keep_ids))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if strategy == ImportMergeStrategy::MergeByContent {
+        let mut existing = persistence::load_memories_for_agent(conn, agent_id, None).await?;
+        persistence::decrypt_memories(&mut existing, encryption)?;
+        let mut seen: HashSet<String> = existing.into_iter().map(|m| m.content).collect();
+        memories.retain(|m| seen.insert(m.content.clone()));
+    }
+
+    let sealed = memories
+        .iter()
+        .map(|m| persistence::encrypt_memory(m, encryption))
+        .collect::<Result<Vec<_>, _>>()?;
+    let imported = match strategy {
+        ImportMergeStrategy::ReplaceAll => {
+            persistence::replace_memories_for_agent(conn, agent_id, &sealed).await?
+        }
+        ImportMergeStrategy::MergeByContent | ImportMergeStrategy::AppendAll => {
+            persistence::save_memories(conn, &sealed).await?
+        }
+    };
+
+    Ok(MemoriesImported {
+        imported,
+        skipped: total - imported,
+    })
+}
+
+/// Merges `memory` into the agent's nearest existing memory when their
+/// similarity reaches `threshold`, returning the existing memory's ID.
+///
+/// Memories without an embedding are never duplicates.
+async fn merge_if_duplicate(
+    conn: &Connection,
+    memory: &Memory,
+    threshold: f32,
+) -> Result<Option<MemoryId>, PersistenceError> {
+    let Some(embedding) = memory.embedding.as_ref() else {
+        return Ok(None);
+    };
+    let Some(existing) =
+        persistence::find_duplicate_memory(conn, &memory.agent_id, embedding, threshold).await?
+    else {
+        return Ok(None);
+    };
+    persistence::merge_duplicate_memory(conn, &existing.memory.id, memory.importance).await?;
+    Ok(Some(existing.memory.id))
+}
+
+/// Deletes expired memories and records the count in the store's metrics.
+async fn run_memory_pruning(
+    conn: &Connection,
+    agent_id: Option<&AgentId>,
+    actor_handle: &ActorHandle,
+) -> Result<u64, PersistenceError> {
+    let count = persistence::prune_expired_memories(conn, agent_id, Utc::now()).await?;
+    if count > 0 {
+        actor_handle.send(RecordPrunedMemories { count }).await;
+    }
+    Ok(count)
+}
+
+/// Spawns the periodic task that prunes every agent's expired memories.
+fn spawn_memory_pruning(
+    conn: Connection,
+    period: Duration,
+    actor_handle: ActorHandle,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_memory_pruning(&conn, None, &actor_handle).await {
+                tracing::warn!(error = %e, "Expired memory pruning failed");
+            }
+        }
+    })
+}
+
+/// Resolves a request's importance weight against the context window default.
+fn importance_weight_or_default(weight: Option<f32>) -> f32 {
+    weight.map_or(ContextWindowConfig::default().importance_weight, |w| {
+        w.clamp(0.0, 1.0)
+    })
+}
+
+/// Applies a reranking pass, falling back to similarity order on failure.
+async fn rerank_or_keep(reranking: &Reranking, results: Vec<ScoredMemory>) -> Vec<ScoredMemory> {
+    match reranking.apply(results.clone()).await {
+        Ok(reranked) => reranked,
+        Err(e) => {
+            tracing::warn!(
+                provider = reranking.provider.name(),
+                error = %e,
+                "Reranking failed; returning similarity order"
+            );
+            results
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -893,5 +2345,124 @@ mod tests {
         assert_eq!(metrics.conversations_loaded, 0);
         assert_eq!(metrics.state_saves, 0);
         assert_eq!(metrics.state_loads, 0);
+        assert_eq!(metrics.fragmentation_ratio, 0.0);
+        assert_eq!(metrics.memories_pruned, 0);
+        assert_eq!(metrics.duplicates_prevented, 0);
+    }
+
+    async fn spawn_in_memory_store(runtime: &mut ActorRuntime) -> ActorHandle {
+        let store = MemoryStore::spawn(runtime).await;
+        store
+            .send(InitMemoryStore {
+                config: PersistenceConfig::in_memory(),
+            })
+            .await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        store
+    }
+
+    async fn search(
+        runtime: &mut ActorRuntime,
+        store: &ActorHandle,
+        agent_id: &AgentId,
+        query: Embedding,
+    ) -> Vec<(MemoryId, String, f32)> {
+        let request = SearchMemories {
+            agent_id: agent_id.clone(),
+            query_embedding: query,
+            limit: 10,
+            min_similarity: None,
+            rerank_with: None,
+            importance_weight: None,
+        };
+        query_store::<MemorySearchResults>(runtime, store, request, Duration::from_secs(5))
+            .await
+            .expect("search")
+            .results
+            .into_iter()
+            .map(|r| (r.memory.id, r.memory.content, r.score))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn exported_memories_import_with_identical_search_results() {
+        use crate::memory::{EmbeddingProvider, StubEmbeddingProvider};
+
+        let mut runtime = ActonApp::launch_async().await;
+        let provider = StubEmbeddingProvider::default();
+        let agent_id = AgentId::new();
+        let source = spawn_in_memory_store(&mut runtime).await;
+
+        let mut inputs = Vec::new();
+        for content in ["the sky is blue", "grass is green", "snow is white"] {
+            let embedding = provider.embed(content).await.unwrap();
+            inputs.push(BulkMemoryInput::new(content).with_embedding(embedding));
+        }
+        let request = BulkStoreMemories {
+            agent_id: agent_id.clone(),
+            memories: inputs,
+        };
+        let stored: BulkStored =
+            query_store(&mut runtime, &source, request, Duration::from_secs(5))
+                .await
+                .expect("bulk store");
+        assert_eq!(stored.count, 3);
+
+        let json = MemoryStore::export_json(&mut runtime, &source, agent_id.clone())
+            .await
+            .unwrap();
+        let data = ExportedMemories::from_json(&json).unwrap();
+        assert_eq!(data.agent_id, agent_id);
+        assert_eq!(data.memories.len(), 3);
+
+        let target = spawn_in_memory_store(&mut runtime).await;
+        let import = |merge_strategy| ImportAgentMemories {
+            agent_id: agent_id.clone(),
+            data: data.clone(),
+            merge_strategy,
+        };
+        let imported: MemoriesImported = query_store(
+            &mut runtime,
+            &target,
+            import(ImportMergeStrategy::ReplaceAll),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("import");
+        assert_eq!((imported.imported, imported.skipped), (3, 0));
+
+        let query = provider.embed("grass is green").await.unwrap();
+        let expected = search(&mut runtime, &source, &agent_id, query.clone()).await;
+        assert_eq!(expected.len(), 3);
+        assert_eq!(
+            search(&mut runtime, &target, &agent_id, query.clone()).await,
+            expected
+        );
+
+        let merged: MemoriesImported = query_store(
+            &mut runtime,
+            &target,
+            import(ImportMergeStrategy::MergeByContent),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("merge");
+        assert_eq!((merged.imported, merged.skipped), (0, 3));
+
+        let appended: MemoriesImported = query_store(
+            &mut runtime,
+            &target,
+            import(ImportMergeStrategy::AppendAll),
+            Duration::from_secs(5),
+        )
+        .await
+        .expect("append");
+        assert_eq!((appended.imported, appended.skipped), (3, 0));
+        assert_eq!(
+            search(&mut runtime, &target, &agent_id, query).await.len(),
+            6
+        );
+
+        runtime.shutdown_all().await.unwrap();
     }
 }
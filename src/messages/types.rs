@@ -2,11 +2,14 @@
 //!
 //! All messages implement Send + Sync + Debug + Clone + 'static as required by acton-reactive.
 
-use crate::agent::AgentConfig;
-use crate::llm::SamplingParams;
+use crate::agent::{AgentConfig, AgentMetrics, AgentState};
+use crate::llm::{LLMError, SamplingParams, TokenUsage};
 use crate::types::{AgentId, CorrelationId, TaskId};
 use acton_reactive::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Instant, SystemTime};
 
 // =============================================================================
 // Kernel Messages
@@ -36,6 +39,30 @@ pub struct StopAgent {
     pub agent_id: AgentId,
 }
 
+/// Request to suspend an agent.
+///
+/// Only idle agents and agents executing tools can be paused. A paused
+/// agent queues the [`UserPrompt`]s it receives and runs them in order
+/// after a [`ResumeAgent`]; tool calls in flight when it was paused finish,
+/// but the agent waits for the resume before reasoning about their results.
+/// The kernel forwards this to the agent.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct PauseAgent {
+    /// The ID of the agent to pause
+    pub agent_id: AgentId,
+}
+
+/// Request to resume a paused agent.
+///
+/// The kernel forwards this to the agent.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct ResumeAgent {
+    /// The ID of the agent to resume
+    pub agent_id: AgentId,
+}
+
 /// Request for agent status.
 #[acton_message]
 #[derive(Serialize, Deserialize)]
@@ -56,6 +83,55 @@ pub struct AgentStatusResponse {
     pub conversation_length: usize,
 }
 
+/// Request for the metrics of every agent that has reported to the kernel.
+///
+/// Replied to with [`AllAgentMetrics`].
+#[acton_message]
+pub struct GetAllAgentMetrics;
+
+/// Response with per-agent metrics.
+#[acton_message]
+pub struct AllAgentMetrics {
+    /// The latest metrics reported by each agent
+    pub agents: Vec<AgentMetricsResponse>,
+}
+
+impl AllAgentMetrics {
+    /// Returns the sum of every agent's metrics.
+    #[must_use]
+    pub fn total(&self) -> AgentMetrics {
+        self.agents.iter().map(|a| a.metrics.clone()).sum()
+    }
+}
+
+/// Liveness check sent by the kernel's watchdog to each registered agent.
+///
+/// Agents reply with [`HeartbeatAck`].
+#[acton_message]
+pub struct Heartbeat;
+
+/// An agent's reply to a [`Heartbeat`].
+#[acton_message]
+pub struct HeartbeatAck {
+    /// The ID of the agent
+    pub agent_id: AgentId,
+    /// The current state of the agent
+    pub state: AgentState,
+    /// When the agent last sent or received an LLM message, if ever
+    pub last_activity: Option<Instant>,
+}
+
+/// Broadcast when an agent fails to answer a [`Heartbeat`] within the
+/// kernel's `heartbeat_timeout`.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct WatchdogTriggered {
+    /// The unresponsive agent
+    pub agent_id: AgentId,
+    /// Why the watchdog fired
+    pub reason: String,
+}
+
 /// Message routed between agents.
 #[acton_message]
 #[derive(Serialize, Deserialize)]
@@ -98,6 +174,28 @@ impl UserPrompt {
 #[derive(Serialize, Deserialize)]
 pub struct GetStatus;
 
+/// Request an agent's metrics (read-only).
+///
+/// Replied to with [`AgentMetricsResponse`].
+#[acton_message]
+pub struct GetAgentMetrics;
+
+/// An agent's metrics.
+///
+/// Sent in reply to [`GetAgentMetrics`], and broadcast by the agent after
+/// each completed turn so the kernel can aggregate it.
+#[acton_message]
+pub struct AgentMetricsResponse {
+    /// The agent the metrics belong to
+    pub agent_id: AgentId,
+    /// The agent's accumulated metrics
+    pub metrics: AgentMetrics,
+}
+
+/// Resets an agent's metrics to zero.
+#[acton_message]
+pub struct ResetAgentMetrics;
+
 /// Internal message to update agent state.
 #[acton_message]
 #[derive(Serialize, Deserialize)]
@@ -121,6 +219,13 @@ pub struct Message {
     pub tool_calls: Option<Vec<ToolCall>>,
     /// ID of the tool call this message responds to
     pub tool_call_id: Option<String>,
+    /// When the message was added to a conversation, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<SystemTime>,
+    /// Whether the message is an instruction injected by the framework
+    /// rather than something the assistant said
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_injected: bool,
 }
 
 impl Message {
@@ -132,6 +237,8 @@ impl Message {
             content: content.into(),
             tool_calls: None,
             tool_call_id: None,
+            timestamp: None,
+            is_injected: false,
         }
     }
 
@@ -143,6 +250,8 @@ impl Message {
             content: content.into(),
             tool_calls: None,
             tool_call_id: None,
+            timestamp: None,
+            is_injected: false,
         }
     }
 
@@ -154,6 +263,8 @@ impl Message {
             content: content.into(),
             tool_calls: Some(tool_calls),
             tool_call_id: None,
+            timestamp: None,
+            is_injected: false,
         }
     }
 
@@ -165,6 +276,8 @@ impl Message {
             content: content.into(),
             tool_calls: None,
             tool_call_id: Some(tool_call_id.into()),
+            timestamp: None,
+            is_injected: false,
         }
     }
 
@@ -176,8 +289,31 @@ impl Message {
             content: content.into(),
             tool_calls: None,
             tool_call_id: None,
+            timestamp: None,
+            is_injected: false,
         }
     }
+
+    /// Creates an instruction injected into the conversation as an
+    /// assistant message.
+    ///
+    /// Used as a reminder to keep the model on task, e.g., before the last
+    /// user message of a long tool-use chain; see
+    /// `PromptBuilder::inject_instruction_before_last_user`.
+    #[must_use]
+    pub fn injected_instruction(content: impl Into<String>) -> Self {
+        Self {
+            is_injected: true,
+            ..Self::assistant(content)
+        }
+    }
+
+    /// Records when the message was added to a conversation.
+    #[must_use]
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
 }
 
 /// The role of a message sender.
@@ -223,6 +359,10 @@ pub struct LLMRequest {
     pub tools: Option<Vec<ToolDefinition>>,
     /// Optional sampling parameters for this request
     pub sampling: Option<SamplingParams>,
+    /// Caps the response length for this request only, overriding the
+    /// provider's configured `max_tokens`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
 }
 
 impl LLMRequest {
@@ -247,6 +387,7 @@ impl LLMRequest {
             messages: vec![Message::user(content)],
             tools: None,
             sampling: None,
+            max_tokens: None,
         }
     }
 
@@ -273,6 +414,7 @@ impl LLMRequest {
             messages: vec![Message::system(system), Message::user(content)],
             tools: None,
             sampling: None,
+            max_tokens: None,
         }
     }
 
@@ -322,6 +464,7 @@ pub struct LLMRequestBuilder {
     messages: Vec<Message>,
     tools: Option<Vec<ToolDefinition>>,
     sampling: Option<SamplingParams>,
+    max_tokens: Option<u32>,
 }
 
 impl LLMRequestBuilder {
@@ -401,6 +544,13 @@ impl LLMRequestBuilder {
         self
     }
 
+    /// Caps the response length for this request only.
+    #[must_use]
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
     /// Builds the LLM request.
     ///
     /// IDs are auto-generated if not explicitly set.
@@ -412,6 +562,7 @@ impl LLMRequestBuilder {
             messages: self.messages,
             tools: self.tools,
             sampling: self.sampling,
+            max_tokens: self.max_tokens,
         }
     }
 }
@@ -466,6 +617,19 @@ pub struct LLMStreamToken {
     pub token: String,
 }
 
+/// A token of the model's extended thinking trace in a streaming response.
+///
+/// Broadcast before the response's [`LLMStreamToken`]s; thinking is not
+/// part of the response text.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct LLMStreamThinkingToken {
+    /// Correlation ID for this stream
+    pub correlation_id: CorrelationId,
+    /// The token text
+    pub token: String,
+}
+
 /// A tool call in a streaming response.
 #[acton_message]
 #[derive(Serialize, Deserialize)]
@@ -476,6 +640,19 @@ pub struct LLMStreamToolCall {
     pub tool_call: ToolCall,
 }
 
+/// Token usage of a streaming response, as reported by the provider.
+///
+/// Broadcast before the stream's [`LLMStreamEnd`]. Providers that report no
+/// usage never send it.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct LLMStreamUsage {
+    /// Correlation ID for this stream
+    pub correlation_id: CorrelationId,
+    /// Token counts of the response
+    pub usage: TokenUsage,
+}
+
 /// Indicates the end of a streaming LLM response.
 #[acton_message]
 #[derive(Serialize, Deserialize)]
@@ -486,6 +663,30 @@ pub struct LLMStreamEnd {
     pub stop_reason: StopReason,
 }
 
+/// Reports that a streaming LLM response failed.
+///
+/// Broadcast just before the stream's [`LLMStreamEnd`], so collectors can
+/// tell a failed stream from a completed one.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct LLMStreamError {
+    /// Correlation ID for this stream
+    pub correlation_id: CorrelationId,
+    /// The error that ended the stream
+    pub error: LLMError,
+}
+
+/// Asks the provider to stop generating a streaming response.
+///
+/// The provider stops forwarding events for the stream and ends it with
+/// [`StopReason::StopSequence`]. Does nothing if the stream already ended.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct CancelStream {
+    /// Correlation ID of the stream to cancel
+    pub correlation_id: CorrelationId,
+}
+
 /// Result of executing a tool call, broadcast after `PromptBuilder::collect`
 /// runs the tool. Consumers (e.g. the chat REPL) render this inline so the
 /// user can see tool success/failure in the same timeline as the
@@ -527,6 +728,20 @@ pub struct ToolDefinition {
     pub input_schema: serde_json::Value,
 }
 
+impl ToolDefinition {
+    /// Checks `args` against [`input_schema`](Self::input_schema).
+    ///
+    /// # Errors
+    ///
+    /// Returns every [`SchemaViolation`](crate::tools::SchemaViolation) found.
+    pub fn validate_against_schema(
+        &self,
+        args: &serde_json::Value,
+    ) -> Result<(), Vec<crate::tools::SchemaViolation>> {
+        crate::tools::schema::validate(&self.input_schema, args)
+    }
+}
+
 /// A tool call requested by the LLM.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ToolCall {
@@ -594,6 +809,26 @@ pub enum SystemEvent {
         /// Seconds until retry is allowed
         retry_after_secs: u64,
     },
+    /// A response was served partly from the prompt cache or wrote to it
+    PromptCacheUsed {
+        /// The provider that reported the usage
+        provider: String,
+        /// Input tokens written to the prompt cache
+        cache_write_tokens: u64,
+        /// Input tokens served from the prompt cache
+        cache_read_tokens: u64,
+    },
+}
+
+/// Broadcast when [`ActonAI`](crate::facade::ActonAI) starts draining
+/// in-flight requests ahead of shutdown.
+///
+/// New requests are rejected from this point on.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct KernelShuttingDown {
+    /// Requests still running when the drain phase started
+    pub pending_requests: usize,
 }
 
 // =============================================================================
@@ -637,6 +872,84 @@ impl AgentMessage {
     }
 }
 
+/// Message for every agent registered with the kernel.
+///
+/// The kernel delivers it as an [`IncomingAgentMessage`] to each registered
+/// agent not listed in `exclude` and replies with [`BroadcastComplete`].
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct BroadcastMessage {
+    /// The agent sending the message
+    pub from: AgentId,
+    /// The message content
+    pub content: String,
+    /// Optional metadata (JSON value for extensibility)
+    pub metadata: Option<serde_json::Value>,
+    /// Agents that should not receive the message
+    pub exclude: Vec<AgentId>,
+    /// When set, only agents with this capability receive the message
+    pub capability: Option<String>,
+}
+
+impl BroadcastMessage {
+    /// Creates a broadcast to every registered agent.
+    #[must_use]
+    pub fn new(from: AgentId, content: impl Into<String>) -> Self {
+        Self {
+            from,
+            content: content.into(),
+            metadata: None,
+            exclude: Vec::new(),
+            capability: None,
+        }
+    }
+
+    /// Adds metadata to the message.
+    #[must_use]
+    pub fn with_metadata(mut self, metadata: serde_json::Value) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Leaves `agent_id` out of the broadcast.
+    #[must_use]
+    pub fn excluding(mut self, agent_id: AgentId) -> Self {
+        self.exclude.push(agent_id);
+        self
+    }
+
+    /// Limits the broadcast to agents with `capability`.
+    #[must_use]
+    pub fn with_capability(mut self, capability: impl Into<String>) -> Self {
+        self.capability = Some(capability.into());
+        self
+    }
+}
+
+impl From<AgentMessage> for BroadcastMessage {
+    /// Broadcasts the message to every agent but its sender; `to` is ignored.
+    fn from(msg: AgentMessage) -> Self {
+        Self {
+            exclude: vec![msg.from.clone()],
+            from: msg.from,
+            content: msg.content,
+            metadata: msg.metadata,
+            capability: None,
+        }
+    }
+}
+
+/// Reply to a [`BroadcastMessage`].
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct BroadcastComplete {
+    /// Number of agents the message was delivered to
+    pub sent_count: usize,
+    /// Number of agents that could not receive it, such as agents that
+    /// stopped during the broadcast
+    pub failed_count: usize,
+}
+
 /// Request to delegate a task to another agent.
 ///
 /// The delegating agent creates this message to assign work to a specialist agent.
@@ -656,6 +969,9 @@ pub struct DelegateTask {
     pub payload: serde_json::Value,
     /// Optional deadline for the task
     pub deadline: Option<std::time::Duration>,
+    /// Callback the delegating agent runs with the task's [`TaskResult`]
+    #[serde(skip)]
+    pub callback: Option<TaskResultCallback>,
 }
 
 impl DelegateTask {
@@ -674,6 +990,7 @@ impl DelegateTask {
             task_type: task_type.into(),
             payload,
             deadline: None,
+            callback: None,
         }
     }
 
@@ -683,6 +1000,56 @@ impl DelegateTask {
         self.deadline = Some(deadline);
         self
     }
+
+    /// Runs `f` once with the task's [`TaskResult`] when the delegating
+    /// agent receives it.
+    ///
+    /// The delegating agent must be registered with the kernel, which
+    /// forwards the delegation to it.
+    #[must_use]
+    pub fn with_callback(mut self, f: impl Fn(TaskResult) + Send + 'static) -> Self {
+        self.callback = Some(TaskResultCallback::new(f));
+        self
+    }
+}
+
+/// One-shot callback run with the [`TaskResult`] of a delegated task.
+///
+/// Clones share the callback, which runs at most once.
+#[derive(Clone)]
+pub struct TaskResultCallback {
+    callback: Arc<Mutex<Option<TaskResultFn>>>,
+}
+
+/// Boxed callback held by a [`TaskResultCallback`].
+type TaskResultFn = Box<dyn Fn(TaskResult) + Send>;
+
+impl TaskResultCallback {
+    /// Wraps `f` as a one-shot callback.
+    #[must_use]
+    pub fn new(f: impl Fn(TaskResult) + Send + 'static) -> Self {
+        Self {
+            callback: Arc::new(Mutex::new(Some(Box::new(f)))),
+        }
+    }
+
+    /// Runs the callback with `result`, unless it has already run.
+    pub fn run(&self, result: TaskResult) {
+        let callback = self
+            .callback
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(callback) = callback {
+            callback(result);
+        }
+    }
+}
+
+impl std::fmt::Debug for TaskResultCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskResultCallback").finish_non_exhaustive()
+    }
 }
 
 /// Acknowledgment that a task was accepted.
@@ -715,6 +1082,80 @@ pub struct TaskFailed {
     pub error: String,
 }
 
+/// Tells an agent that it finished a task delegated to it.
+///
+/// The agent reports `result` to the delegating agent as a [`TaskResult`].
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct CompleteTask {
+    /// The task that finished
+    pub task_id: TaskId,
+    /// The task's output, or the error that ended it
+    pub result: Result<serde_json::Value, String>,
+}
+
+impl CompleteTask {
+    /// Reports a task that succeeded with `result`.
+    #[must_use]
+    pub fn success(task_id: TaskId, result: serde_json::Value) -> Self {
+        Self {
+            task_id,
+            result: Ok(result),
+        }
+    }
+
+    /// Reports a task that failed with `error`.
+    #[must_use]
+    pub fn failure(task_id: TaskId, error: impl Into<String>) -> Self {
+        Self {
+            task_id,
+            result: Err(error.into()),
+        }
+    }
+}
+
+/// Result of a delegated task, reported by the agent that performed it.
+///
+/// The agent broadcasts it once the task completes, and the kernel routes
+/// it to the delegating agent, which records it in its
+/// [`DelegationTracker`](crate::agent::DelegationTracker) and runs the
+/// task's [`DelegateTask::with_callback`] callback.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct TaskResult {
+    /// The task that finished
+    pub task_id: TaskId,
+    /// The agent that performed the task
+    pub from: AgentId,
+    /// The agent that delegated the task
+    pub to: AgentId,
+    /// The task's output, or the error that ended it
+    pub result: Result<serde_json::Value, String>,
+    /// Time from receiving the task to finishing it
+    pub elapsed: std::time::Duration,
+}
+
+impl TaskResult {
+    /// Returns true if the task succeeded.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Notification that a delegated task passed its deadline.
+///
+/// Sent to the agent that delegated the task after its
+/// [`DelegationTracker`](crate::agent::DelegationTracker) failed it.
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct TaskExpired {
+    /// The task that expired
+    pub task_id: TaskId,
+    /// The agent the task was delegated to
+    pub to_agent: AgentId,
+}
+
 /// Announcement of agent capabilities for discovery.
 ///
 /// Agents broadcast this message to announce what they can do.
@@ -726,6 +1167,9 @@ pub struct AnnounceCapabilities {
     pub agent_id: AgentId,
     /// List of capability strings (e.g., "code_review", "translation", "summarization")
     pub capabilities: Vec<String>,
+    /// How well-suited the agent is for each capability, from 0.0 to 1.0
+    #[serde(default)]
+    pub scores: Option<HashMap<String, f32>>,
 }
 
 impl AnnounceCapabilities {
@@ -735,8 +1179,17 @@ impl AnnounceCapabilities {
         Self {
             agent_id,
             capabilities,
+            scores: None,
         }
     }
+
+    /// Sets per-capability suitability scores (0.0–1.0) used for
+    /// load-balanced routing.
+    #[must_use]
+    pub fn with_scores(mut self, scores: HashMap<String, f32>) -> Self {
+        self.scores = Some(scores);
+        self
+    }
 }
 
 /// Request to find an agent with a specific capability.
@@ -760,6 +1213,35 @@ impl FindCapableAgent {
     }
 }
 
+/// Request to find the agent best suited for a capability.
+///
+/// The kernel picks the agent with the highest announced score; with
+/// `prefer_idle`, scores are first weighted by each agent's last known
+/// state (see [`AgentState::weighted_score`]). Replied to with
+/// [`CapableAgentFound`].
+#[acton_message]
+#[derive(Serialize, Deserialize)]
+pub struct FindBestCapableAgent {
+    /// The capability to search for
+    pub capability: String,
+    /// Whether to favor agents that are not busy
+    pub prefer_idle: bool,
+    /// Correlation ID for the response
+    pub correlation_id: CorrelationId,
+}
+
+impl FindBestCapableAgent {
+    /// Creates a new best-agent search request.
+    #[must_use]
+    pub fn new(capability: impl Into<String>, prefer_idle: bool) -> Self {
+        Self {
+            capability: capability.into(),
+            prefer_idle,
+            correlation_id: CorrelationId::new(),
+        }
+    }
+}
+
 /// Response to a capability search request.
 #[acton_message]
 #[derive(Serialize, Deserialize)]
@@ -840,6 +1322,42 @@ mod tests {
         assert_ne!(prompt1.correlation_id, prompt2.correlation_id);
     }
 
+    #[test]
+    fn broadcast_from_agent_message_excludes_sender() {
+        let from = AgentId::new();
+        let msg = AgentMessage::new(from.clone(), AgentId::new(), "hello")
+            .with_metadata(serde_json::json!({"urgent": true}));
+
+        let broadcast = BroadcastMessage::from(msg);
+
+        assert_eq!(broadcast.from, from);
+        assert_eq!(broadcast.content, "hello");
+        assert_eq!(broadcast.exclude, vec![from]);
+        assert!(broadcast.capability.is_none());
+        assert_eq!(broadcast.metadata.unwrap()["urgent"], true);
+    }
+
+    #[test]
+    fn task_result_callback_runs_once() {
+        let runs = Arc::new(Mutex::new(Vec::new()));
+        let recorded = runs.clone();
+        let callback = TaskResultCallback::new(move |result: TaskResult| {
+            recorded.lock().unwrap().push(result.task_id);
+        });
+        let result = TaskResult {
+            task_id: TaskId::new(),
+            from: AgentId::new(),
+            to: AgentId::new(),
+            result: Ok(serde_json::json!("done")),
+            elapsed: std::time::Duration::ZERO,
+        };
+
+        callback.clone().run(result.clone());
+        callback.run(result.clone());
+
+        assert_eq!(*runs.lock().unwrap(), vec![result.task_id]);
+    }
+
     #[test]
     fn message_user_creation() {
         let msg = Message::user("Hello, agent!");
@@ -881,6 +1399,20 @@ mod tests {
         assert_eq!(msg.tool_call_id, Some("tc_123".to_string()));
     }
 
+    #[test]
+    fn message_injected_instruction() {
+        let msg = Message::injected_instruction("Stay on task.");
+
+        assert_eq!(msg.role, MessageRole::Assistant);
+        assert!(msg.is_injected);
+        assert!(serde_json::to_string(&msg)
+            .unwrap()
+            .contains("\"is_injected\":true"));
+        assert!(!serde_json::to_string(&Message::user("hi"))
+            .unwrap()
+            .contains("is_injected"));
+    }
+
     #[test]
     fn message_role_display() {
         assert_eq!(MessageRole::System.to_string(), "system");
@@ -39,20 +39,30 @@
 //! ```
 
 use crate::conversation::StreamToken;
-use crate::error::ActonAIError;
+use crate::error::{ActonAIError, ErrorContext, ResultExt};
 use crate::facade::ActonAI;
-use crate::llm::SamplingParams;
+use crate::llm::{ExtendedThinkingConfig, LLMError, RetryPolicy, SamplingParams, TokenUsage};
 use crate::messages::{
-    LLMRequest, LLMStreamEnd, LLMStreamStart, LLMStreamToken, LLMStreamToolCall, Message,
-    StopReason, ToolCall, ToolDefinition,
+    CancelStream, LLMRequest, LLMStreamEnd, LLMStreamError, LLMStreamStart, LLMStreamThinkingToken,
+    LLMStreamToken, LLMStreamToolCall, LLMStreamUsage, Message, MessageRole, StopReason, ToolCall,
+    ToolDefinition,
 };
 use crate::stream::{CollectedResponse, ExecutedToolCall};
+use crate::tools::cache::execute_cached;
+use crate::tools::middleware::execute_with_hooks;
+use crate::tools::replay::{ToolCallLog, ToolCallReplayer};
 use crate::tools::ToolError;
 use crate::types::{AgentId, CorrelationId};
 use acton_reactive::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio::sync::Notify;
 
 /// Framework fallback for the agentic tool-call loop.
@@ -111,28 +121,81 @@ where
 /// `ProcessSandbox` actually reaches the bash/write_file/edit_file call
 /// sites — prior to this wiring the factory existed in memory but the
 /// prompt path skipped it entirely.
+///
+/// When `rate_limiter` is `Some`, calls over the limit fail with
+/// [`ToolError::rate_limited`] before reaching either path. Both paths run
+/// inside the tool's middleware and executor hooks, and are skipped when
+/// `result_cache` holds a result for the arguments.
 struct BuiltinToolExecutorAdapter {
     tool_name: String,
     executor: Arc<crate::tools::BoxedToolExecutor>,
+    middleware: crate::tools::ToolMiddlewareStack,
+    result_cache: Option<Arc<crate::tools::ToolResultCache>>,
     sandbox: Option<Arc<dyn crate::tools::sandbox::SandboxFactory>>,
+    rate_limiter: Option<Arc<crate::tools::ToolRateLimiter>>,
+    coercion: Option<crate::tools::CoercionRules>,
 }
 
 impl ToolExecutorFn for BuiltinToolExecutorAdapter {
     fn call(&self, args: serde_json::Value) -> ToolFuture {
+        let args = match &self.coercion {
+            Some(rules) => rules.apply(&self.tool_name, args),
+            None => args,
+        };
+        let permit = match self.rate_limiter.as_ref().map(|l| l.try_acquire()) {
+            Some(Err(retry_after_secs)) => {
+                let error = ToolError::rate_limited(&self.tool_name, retry_after_secs);
+                return Box::pin(async move { Err(error) });
+            }
+            Some(Ok(permit)) => Some(permit),
+            None => None,
+        };
+
+        let name = self.tool_name.clone();
+        let executor = Arc::clone(&self.executor);
+        let middleware = self.middleware.clone();
+        let cache = self.result_cache.clone();
         match self.sandbox.clone() {
-            Some(factory) => {
-                let name = self.tool_name.clone();
-                Box::pin(async move {
-                    let mut sandbox = factory.create().await?;
-                    let result = sandbox.execute(&name, args).await;
-                    sandbox.destroy();
-                    result
+            Some(factory) => Box::pin(async move {
+                let _permit = permit;
+                let (result, _) = execute_cached(cache.as_deref(), args, |args| {
+                    execute_with_hooks(&**executor, &middleware, &name, args, |args| async {
+                        let mut sandbox = factory.create().await?;
+                        let result = sandbox.execute(&name, args).await;
+                        sandbox.destroy();
+                        result
+                    })
                 })
-            }
-            None => {
-                let executor = Arc::clone(&self.executor);
-                Box::pin(async move { executor.execute(args).await })
-            }
+                .await;
+                result
+            }),
+            None => Box::pin(async move {
+                let _permit = permit;
+                let (result, _) = execute_cached(cache.as_deref(), args, |args| {
+                    execute_with_hooks(&**executor, &middleware, &name, args, |args| {
+                        executor.execute(args)
+                    })
+                })
+                .await;
+                result
+            }),
+        }
+    }
+}
+
+/// Adapter that answers tool calls from a recorded [`ToolCallLog`].
+///
+/// Calls with no matching record fall through to the tool's own executor.
+struct ReplayToolExecutorAdapter {
+    replayer: ToolCallReplayer,
+    inner: Arc<dyn ToolExecutorFn>,
+}
+
+impl ToolExecutorFn for ReplayToolExecutorAdapter {
+    fn call(&self, args: serde_json::Value) -> ToolFuture {
+        match self.replayer.replay(&args) {
+            Some(result) => Box::pin(async move { result }),
+            None => self.inner.call(args),
         }
     }
 }
@@ -166,6 +229,38 @@ impl Clone for ToolSpec {
     }
 }
 
+/// An example exchange shown to the LLM before the real prompt.
+///
+/// Added with [`PromptBuilder::few_shot`]; each example becomes a user
+/// message followed by an assistant message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FewShotExample {
+    /// The example user input
+    pub input: String,
+    /// The response the LLM should learn from
+    pub output: String,
+}
+
+impl FewShotExample {
+    /// Creates an example from an input and the expected output.
+    #[must_use]
+    pub fn new(input: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            input: input.into(),
+            output: output.into(),
+        }
+    }
+
+    /// Creates one example per `(input, output)` pair.
+    #[must_use]
+    pub fn from_qa_pairs(pairs: Vec<(String, String)>) -> Vec<Self> {
+        pairs
+            .into_iter()
+            .map(|(input, output)| Self::new(input, output))
+            .collect()
+    }
+}
+
 /// Type alias for wrapped start callback (shared across rounds).
 type WrappedStartCallback = Arc<std::sync::Mutex<StartCallback>>;
 
@@ -175,6 +270,52 @@ type WrappedTokenCallback = Arc<std::sync::Mutex<TokenCallback>>;
 /// Type alias for wrapped end callback (shared across rounds).
 type WrappedEndCallback = Arc<std::sync::Mutex<EndCallback>>;
 
+/// Aborts a prompt from another task.
+///
+/// Obtained from [`PromptBuilder::with_cancellation`]; clones share the same
+/// state, so cancelling any clone cancels them all.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationState>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    /// Creates a token that has not been cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token, aborting every prompt it was attached to.
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    /// Returns true once [`cancel`](Self::cancel) has been called.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Waits until the token is cancelled.
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
 /// A fluent builder for constructing and sending LLM prompts.
 ///
 /// Created via `ActonAI::prompt()`, this builder allows you to configure
@@ -210,10 +351,16 @@ pub struct PromptBuilder {
     system_prompt: Option<String>,
     /// Optional conversation history (replaces user_content when set)
     conversation_history: Option<Vec<Message>>,
+    /// Example exchanges placed before the history or user content
+    few_shot: Vec<FewShotExample>,
+    /// Reminder inserted before the last user message
+    injected_instruction: Option<String>,
     /// Callback for stream start
     on_start: Option<StartCallback>,
     /// Callback for each token
     on_token: Option<TokenCallback>,
+    /// Callback for each extended thinking token
+    on_thinking_token: Option<TokenCallback>,
     /// Callback for stream end
     on_end: Option<EndCallback>,
     /// Registered tools with inline executors
@@ -226,6 +373,18 @@ pub struct PromptBuilder {
     token_target: Option<ActorHandle>,
     /// Optional sampling parameters for this prompt
     sampling: Option<SamplingParams>,
+    /// Recorded tool calls to replay instead of executing
+    replay: Option<ToolCallLog>,
+    /// Maximum number of response tokens for this prompt
+    token_budget: Option<u32>,
+    /// Retry policy for this prompt (None = the provider's default)
+    retry_policy: Option<RetryPolicy>,
+    /// Time limit for the whole prompt (None = the provider's default)
+    timeout: Option<Duration>,
+    /// Time limit for each LLM round
+    round_timeout: Option<Duration>,
+    /// Token that aborts the prompt when cancelled
+    cancellation: Option<CancellationToken>,
 }
 
 impl PromptBuilder {
@@ -240,17 +399,39 @@ impl PromptBuilder {
             user_content,
             system_prompt: None,
             conversation_history: None,
+            few_shot: Vec::new(),
+            injected_instruction: None,
             on_start: None,
             on_token: None,
+            on_thinking_token: None,
             on_end: None,
             tools: Vec::new(),
             max_tool_rounds,
             provider_name: None,
             token_target: None,
             sampling: None,
+            replay: None,
+            token_budget: None,
+            retry_policy: None,
+            timeout: None,
+            round_timeout: None,
+            cancellation: None,
         }
     }
 
+    /// Creates a prompt builder whose content is `template` rendered with
+    /// `vars`.
+    ///
+    /// This is called internally by `ActonAI::prompt_from_template()`.
+    #[must_use]
+    pub(crate) fn from_template(
+        runtime: ActonAI,
+        template: &PromptTemplate,
+        vars: &HashMap<&str, &str>,
+    ) -> Self {
+        Self::new(runtime, template.render(vars))
+    }
+
     /// Returns the current `max_tool_rounds` value that will be enforced.
     #[must_use]
     pub fn current_max_tool_rounds(&self) -> usize {
@@ -277,6 +458,31 @@ impl PromptBuilder {
         self
     }
 
+    /// Sets the system prompt from the contents of a text or markdown file.
+    ///
+    /// The file is read synchronously and cached by path and modification
+    /// time. Paths are checked with the default
+    /// [`PathValidator`](crate::tools::security::PathValidator) rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if the path is not allowed or the file
+    /// cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// runtime
+    ///     .prompt("Review this diff")
+    ///     .system_from_file("prompts/reviewer.md")?
+    ///     .collect()
+    ///     .await?;
+    /// ```
+    pub fn system_from_file(self, path: impl AsRef<std::path::Path>) -> Result<Self, ActonAIError> {
+        let prompt = crate::system_prompt::read_system_prompt_file(path.as_ref())?;
+        Ok(self.system(prompt))
+    }
+
     /// Sets conversation history for multi-turn conversations.
     ///
     /// When set, this replaces the initial user content passed to `prompt()`.
@@ -315,6 +521,56 @@ impl PromptBuilder {
         self
     }
 
+    /// Adds example exchanges for in-context learning.
+    ///
+    /// Each example is sent as a user message followed by an assistant
+    /// message, after the system prompt and before the conversation
+    /// history or user content. Examples accumulate across calls.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use acton_ai::prompt::FewShotExample;
+    ///
+    /// let response = runtime
+    ///     .prompt("I waited an hour and the food was cold.")
+    ///     .system("Classify the sentiment of each review.")
+    ///     .few_shot([
+    ///         FewShotExample::new("Loved every bite!", "positive"),
+    ///         FewShotExample::new("Never coming back.", "negative"),
+    ///     ])
+    ///     .collect()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn few_shot(mut self, examples: impl IntoIterator<Item = FewShotExample>) -> Self {
+        self.few_shot.extend(examples);
+        self
+    }
+
+    /// Inserts `content` as an [injected](Message::injected_instruction)
+    /// assistant message immediately before the last user message.
+    ///
+    /// Restating instructions next to the latest input helps keep the model
+    /// on task in long tool-use chains and makes instructions smuggled into
+    /// earlier content less effective. The message is only sent with this
+    /// prompt; it is not part of any conversation history.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let response = runtime
+    ///     .continue_with(history)
+    ///     .inject_instruction_before_last_user("Only answer questions about the codebase.")
+    ///     .collect()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn inject_instruction_before_last_user(mut self, content: impl Into<String>) -> Self {
+        self.injected_instruction = Some(content.into());
+        self
+    }
+
     /// Sets a callback to be called when the stream starts.
     ///
     /// This is useful for displaying a "thinking" indicator or spinner.
@@ -360,6 +616,33 @@ impl PromptBuilder {
         self
     }
 
+    /// Sets a callback to be called for each extended thinking token.
+    ///
+    /// Thinking tokens arrive before the response's tokens and are only
+    /// produced when thinking is enabled with
+    /// [`with_thinking`](Self::with_thinking) or
+    /// `ProviderConfig::with_extended_thinking`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// runtime
+    ///     .prompt("Is 1009 prime?")
+    ///     .with_thinking(2048)
+    ///     .on_thinking_token(|t| eprint!("{t}"))
+    ///     .on_token(|t| print!("{t}"))
+    ///     .collect()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn on_thinking_token<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(&str) + Send + 'static,
+    {
+        self.on_thinking_token = Some(Box::new(f));
+        self
+    }
+
     /// Sets a callback to be called when the stream ends.
     ///
     /// The callback receives the stop reason indicating why the LLM
@@ -666,6 +949,89 @@ impl PromptBuilder {
         self
     }
 
+    /// Caches the system prompt with Anthropic prompt caching.
+    ///
+    /// Overrides the provider default set with
+    /// `ProviderConfig::with_system_prompt_caching` or
+    /// `ProviderConfig::enable_prompt_caching`. Ignored by providers without
+    /// prompt caching.
+    #[must_use]
+    pub fn cache_system_prompt(mut self) -> Self {
+        self.sampling
+            .get_or_insert_with(SamplingParams::default)
+            .cache_system_prompt = Some(true);
+        self
+    }
+
+    /// Enables Anthropic extended thinking with a budget of `budget_tokens`.
+    ///
+    /// Claude reasons before answering; the trace is streamed to
+    /// [`on_thinking_token`](Self::on_thinking_token) and returned in
+    /// [`CollectedResponse::thinking`], never in the response text or
+    /// conversation history. The budget is added to the response's
+    /// `max_tokens`, and temperature and top-k are not sent while thinking.
+    /// Ignored by providers without extended thinking.
+    #[must_use]
+    pub fn with_thinking(mut self, budget_tokens: u32) -> Self {
+        self.sampling
+            .get_or_insert_with(SamplingParams::default)
+            .extended_thinking = Some(ExtendedThinkingConfig::new(budget_tokens));
+        self
+    }
+
+    /// Caches the tool definitions with Anthropic prompt caching.
+    ///
+    /// Worthwhile for large tool sets reused across many prompts. Ignored by
+    /// providers without prompt caching.
+    #[must_use]
+    pub fn cache_tools(mut self) -> Self {
+        self.sampling
+            .get_or_insert_with(SamplingParams::default)
+            .cache_tools = Some(true);
+        self
+    }
+
+    /// Requests a JSON response matching a JSON schema.
+    ///
+    /// The system prompt gains an instruction to answer with JSON matching
+    /// the schema, and providers that support it enforce the format:
+    /// OpenAI-compatible providers through `response_format`, Anthropic
+    /// through a forced tool call (when the prompt has no other tools). An
+    /// empty schema (`{}`) asks for any JSON object.
+    ///
+    /// Parse the result with [`CollectedResponse::as_typed`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// #[derive(serde::Deserialize)]
+    /// struct Capital {
+    ///     city: String,
+    ///     country: String,
+    /// }
+    ///
+    /// let capital: Capital = runtime
+    ///     .prompt("What is the capital of France?")
+    ///     .output_schema(serde_json::json!({
+    ///         "type": "object",
+    ///         "properties": {
+    ///             "city": { "type": "string" },
+    ///             "country": { "type": "string" }
+    ///         },
+    ///         "required": ["city", "country"]
+    ///     }))
+    ///     .collect()
+    ///     .await?
+    ///     .as_typed()?;
+    /// ```
+    #[must_use]
+    pub fn output_schema(mut self, schema: serde_json::Value) -> Self {
+        self.sampling
+            .get_or_insert_with(SamplingParams::default)
+            .output_schema = Some(schema);
+        self
+    }
+
     /// Sets a target actor to receive [`StreamToken`] messages during streaming.
     ///
     /// When set, each token received from the LLM is forwarded as a [`StreamToken`]
@@ -720,7 +1086,11 @@ impl PromptBuilder {
                     let adapter = BuiltinToolExecutorAdapter {
                         tool_name: name.clone(),
                         executor,
+                        middleware: config.middleware.clone(),
+                        result_cache: builtins.result_cache(name),
                         sandbox,
+                        rate_limiter: builtins.rate_limiter(name),
+                        coercion: config.input_coercion.clone(),
                     };
                     self.tools.push(ToolSpec {
                         definition: config.definition.clone(),
@@ -733,6 +1103,168 @@ impl PromptBuilder {
         self
     }
 
+    /// Replays recorded tool results instead of executing the tools.
+    ///
+    /// Every registered tool that appears in `log` answers matching calls
+    /// (same tool name and arguments) with the recorded result. Calls that
+    /// don't match any record fall through to the tool's real executor.
+    /// Tools may be registered before or after this call. Replaces any
+    /// previously set log.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let log = ToolCallLog::load_from_file("fixtures/listing.json")?;
+    ///
+    /// let response = runtime
+    ///     .prompt("List files in the current directory")
+    ///     .use_builtins()
+    ///     .replay_tools(log)
+    ///     .collect()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn replay_tools(mut self, log: ToolCallLog) -> Self {
+        self.replay = Some(log);
+        self
+    }
+
+    /// Limits the response to at most `max_tokens` tokens.
+    ///
+    /// The limit is sent to the provider as this request's `max_tokens`,
+    /// without changing the provider's configuration for other requests.
+    /// It also applies across tool rounds: once the streamed tokens reach
+    /// the budget, the stream is cancelled and the response comes back
+    /// truncated, with [`StopReason::MaxTokens`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let response = runtime
+    ///     .prompt("Describe the solar system")
+    ///     .with_token_budget(50)
+    ///     .collect()
+    ///     .await?;
+    ///
+    /// if response.is_truncated() {
+    ///     println!("(response cut off at 50 tokens)");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_token_budget(mut self, max_tokens: u32) -> Self {
+        self.token_budget = Some(max_tokens);
+        self
+    }
+
+    /// Retries the request when it fails with a retriable error.
+    ///
+    /// Rate limits, network errors and overloaded or unavailable services
+    /// are retried with the policy's exponential backoff, waiting for the
+    /// provider's retry-after hint when it gives one. Other errors, such as
+    /// authentication failures and bad requests, are returned immediately.
+    /// Tokens streamed before a failure count against the
+    /// [token budget](Self::with_token_budget).
+    ///
+    /// While a retry policy applies, the stream callbacks and
+    /// [`token_target`](Self::token_target) get each attempt's events only
+    /// once it succeeds, so output from a failed attempt is never repeated.
+    ///
+    /// Overrides the provider's
+    /// [default policy](crate::llm::ProviderConfig::with_retry_policy).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use acton_ai::llm::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let response = runtime
+    ///     .prompt("Summarize the report")
+    ///     .retry(RetryPolicy::new(5).with_initial_backoff(Duration::from_secs(1)))
+    ///     .collect()
+    ///     .await?;
+    /// ```
+    #[must_use]
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Limits how long the whole prompt may take.
+    ///
+    /// The limit covers every round, including tool execution. When it
+    /// runs out, the in-flight stream is cancelled (ending with
+    /// [`StopReason::StopSequence`]) and `collect()` returns a timeout
+    /// error (see [`ActonAIError::is_timeout`]).
+    ///
+    /// Overrides the provider's
+    /// [default limit](crate::llm::ProviderConfig::with_default_prompt_timeout).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let result = runtime
+    ///     .prompt("Research this topic")
+    ///     .use_builtins()
+    ///     .timeout(Duration::from_secs(60))
+    ///     .collect()
+    ///     .await;
+    ///
+    /// if let Err(e) = &result {
+    ///     if e.is_timeout() {
+    ///         eprintln!("gave up after a minute");
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Limits how long each LLM round may take.
+    ///
+    /// A round is one streamed response; tool execution between rounds
+    /// doesn't count. A round that runs out of time is cancelled like a
+    /// [`timeout`](Self::timeout).
+    #[must_use]
+    pub fn round_timeout(mut self, timeout: Duration) -> Self {
+        self.round_timeout = Some(timeout);
+        self
+    }
+
+    /// Returns a token that aborts this prompt when cancelled.
+    ///
+    /// Cancelling stops the in-flight stream and makes `collect()` return
+    /// an error (see [`ActonAIError::is_cancelled`]), including between
+    /// tool rounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let (prompt, cancel) = runtime.prompt("Refactor the codebase").with_cancellation();
+    /// let task = tokio::spawn(prompt.collect());
+    ///
+    /// // Later, e.g. when the user presses Ctrl-C
+    /// cancel.cancel();
+    /// assert!(task.await?.unwrap_err().is_cancelled());
+    /// ```
+    #[must_use]
+    pub fn with_cancellation(self) -> (Self, CancellationToken) {
+        let token = CancellationToken::new();
+        (self.cancel_on(token.clone()), token)
+    }
+
+    /// Aborts this prompt when `token` is cancelled.
+    ///
+    /// Like [`with_cancellation`](Self::with_cancellation), but with a token
+    /// that may be shared by several prompts.
+    #[must_use]
+    pub fn cancel_on(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
     /// Sends the prompt and collects the complete response.
     ///
     /// This method:
@@ -776,45 +1308,226 @@ impl PromptBuilder {
         // lives across all calls — that avoids stacking dead subscribers
         // in the acton-reactive broker.
         let session = build_stream_collector(&self.runtime).await;
-        let result = self.collect_inner(&session).await;
+        let result = self.collect_with_cancellation(&session).await;
         session.shutdown().await;
         result
     }
 
-    /// Run `collect()`'s core tool/streaming loop against a caller-owned
-    /// [`StreamCollectorSession`]. Used by long-lived callers such as
-    /// [`crate::conversation::Conversation`] that want one persistent
-    /// subscriber for every turn instead of a fresh one per call.
-    pub(crate) async fn collect_with_session(
-        self,
-        session: &StreamCollectorSession,
-    ) -> Result<CollectedResponse, ActonAIError> {
-        if self.runtime.is_shutdown() {
-            return Err(ActonAIError::runtime_shutdown());
-        }
-        self.collect_inner(session).await
-    }
-
-    async fn collect_inner(
+    /// Like [`collect`](Self::collect), also writing each token to `writer`
+    /// as it arrives.
+    ///
+    /// Any [`on_token`](Self::on_token) callback is still called. The writer
+    /// is flushed once the response is complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`collect`](Self::collect), or a stream error
+    /// if writing fails; the response is still collected in that case.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// runtime
+    ///     .prompt("Tell me a story.")
+    ///     .collect_to_writer(tokio::io::stdout())
+    ///     .await?;
+    /// ```
+    pub async fn collect_to_writer<W>(
+        self,
+        mut writer: W,
+    ) -> Result<CollectedResponse, ActonAIError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let collect = self.collect_teeing_tokens(tx);
+        let write = async move {
+            let mut written = Ok(());
+            while let Some(Some(token)) = rx.recv().await {
+                if written.is_ok() {
+                    written = writer.write_all(token.as_bytes()).await;
+                }
+            }
+            written?;
+            writer.flush().await
+        };
+
+        let (response, written) = tokio::join!(collect, write);
+        let response = response?;
+        written.map_err(|e| ActonAIError::stream_error(format!("failed to write token: {e}")))?;
+        Ok(response)
+    }
+
+    /// Like [`collect_to_writer`](Self::collect_to_writer), for a blocking
+    /// [`std::io::Write`]. Writes happen on a blocking thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`collect`](Self::collect), or a stream error
+    /// if writing fails; the response is still collected in that case.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let file = std::fs::File::create("story.txt")?;
+    /// runtime
+    ///     .prompt("Tell me a story.")
+    ///     .collect_to_sync_writer(file)
+    ///     .await?;
+    /// ```
+    pub async fn collect_to_sync_writer<W>(
+        self,
+        mut writer: W,
+    ) -> Result<CollectedResponse, ActonAIError>
+    where
+        W: std::io::Write + Send + 'static,
+    {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<Option<String>>();
+        let write = tokio::task::spawn_blocking(move || {
+            let mut written = Ok(());
+            while let Some(Some(token)) = rx.blocking_recv() {
+                if written.is_ok() {
+                    written = writer.write_all(token.as_bytes());
+                }
+            }
+            written?;
+            writer.flush()
+        });
+
+        let response = self.collect_teeing_tokens(tx).await?;
+        write
+            .await
+            .map_err(std::io::Error::other)
+            .and_then(|written| written)
+            .map_err(|e| ActonAIError::stream_error(format!("failed to write token: {e}")))?;
+        Ok(response)
+    }
+
+    /// Collects the response, sending a copy of each token to `tx` and
+    /// `None` once the response is complete.
+    async fn collect_teeing_tokens(
+        mut self,
+        tx: tokio::sync::mpsc::UnboundedSender<Option<String>>,
+    ) -> Result<CollectedResponse, ActonAIError> {
+        let mut on_token = self.on_token.take();
+        let token_tx = tx.clone();
+        self.on_token = Some(Box::new(move |token: &str| {
+            if let Some(callback) = on_token.as_mut() {
+                callback(token);
+            }
+            let _ = token_tx.send(Some(token.to_string()));
+        }));
+
+        let result = self.collect().await;
+        let _ = tx.send(None);
+        result
+    }
+
+    /// Run `collect()`'s core tool/streaming loop against a caller-owned
+    /// [`StreamCollectorSession`]. Used by long-lived callers such as
+    /// [`crate::conversation::Conversation`] that want one persistent
+    /// subscriber for every turn instead of a fresh one per call.
+    pub(crate) async fn collect_with_session(
         self,
         session: &StreamCollectorSession,
     ) -> Result<CollectedResponse, ActonAIError> {
+        if self.runtime.is_shutdown() {
+            return Err(ActonAIError::runtime_shutdown());
+        }
+        self.collect_with_cancellation(session).await
+    }
+
+    /// Runs [`collect_with_timeout`](Self::collect_with_timeout) until the
+    /// prompt's cancellation token, if any, is cancelled.
+    async fn collect_with_cancellation(
+        self,
+        session: &StreamCollectorSession,
+    ) -> Result<CollectedResponse, ActonAIError> {
+        let Some(token) = self.cancellation.clone() else {
+            return self.collect_with_timeout(session).await;
+        };
+
+        tokio::select! {
+            result = self.collect_with_timeout(session) => result,
+            () = token.cancelled() => {
+                session.cancel_round().await;
+                Err(ActonAIError::cancelled())
+            }
+        }
+    }
+
+    /// Runs [`collect_inner`](Self::collect_inner) under the prompt's time
+    /// limit, cancelling the in-flight stream if it runs out.
+    async fn collect_with_timeout(
+        self,
+        session: &StreamCollectorSession,
+    ) -> Result<CollectedResponse, ActonAIError> {
+        let limit = self.timeout.or_else(|| {
+            let provider = self
+                .provider_name
+                .as_deref()
+                .unwrap_or(self.runtime.default_provider_name());
+            self.runtime.provider_prompt_timeout(provider)
+        });
+        let Some(limit) = limit else {
+            return self.collect_inner(session).await;
+        };
+
+        let started = Instant::now();
+        match tokio::time::timeout(limit, self.collect_inner(session)).await {
+            Ok(result) => result,
+            Err(_) => {
+                session.cancel_round().await;
+                Err(ActonAIError::timeout(started.elapsed()))
+            }
+        }
+    }
+
+    async fn collect_inner(
+        self,
+        session: &StreamCollectorSession,
+    ) -> Result<CollectedResponse, ActonAIError> {
+        let _in_flight = self.runtime.begin_request();
+
         // Destructure self to take ownership of all fields
         let PromptBuilder {
             runtime,
             user_content,
             system_prompt,
             conversation_history,
+            few_shot,
+            injected_instruction,
             on_start,
             on_token,
+            on_thinking_token,
             on_end,
             mut tools,
             max_tool_rounds,
             provider_name,
             token_target,
             sampling,
+            replay,
+            token_budget,
+            retry_policy,
+            timeout: _,
+            round_timeout,
+            cancellation: _,
         } = self;
 
+        // Route recorded tools through the replayer
+        if let Some(log) = replay {
+            for spec in &mut tools {
+                let name = &spec.definition.name;
+                if log.contains_tool(name) {
+                    let replayer = ToolCallReplayer::new(name.clone(), log.records().to_vec());
+                    spec.executor = Arc::new(ReplayToolExecutorAdapter {
+                        replayer,
+                        inner: Arc::clone(&spec.executor),
+                    });
+                }
+            }
+        }
+
         // Resolve the provider handle
         let provider_handle = if let Some(ref name) = provider_name {
             runtime.provider_handle_named(name).ok_or_else(|| {
@@ -830,18 +1543,38 @@ impl PromptBuilder {
         } else {
             runtime.provider_handle()
         };
+        let retry_policy = retry_policy.or_else(|| {
+            runtime.provider_retry_policy(
+                provider_name
+                    .as_deref()
+                    .unwrap_or(runtime.default_provider_name()),
+            )
+        });
+
+        // Ask for JSON in the system prompt as well, for providers that
+        // cannot enforce the schema themselves
+        let output_schema = sampling.as_ref().and_then(|s| s.output_schema.as_ref());
+        let is_json = output_schema.is_some();
+        let system_prompt = match output_schema {
+            Some(schema) => {
+                let instruction = structured_output_instruction(schema);
+                Some(match system_prompt {
+                    Some(prompt) => format!("{prompt}\n\n{instruction}"),
+                    None => instruction,
+                })
+            }
+            None => system_prompt,
+        };
 
         // Build the initial messages
-        let mut messages = Vec::new();
-        if let Some(ref system) = system_prompt {
-            messages.push(Message::system(system));
-        }
-
-        // Use conversation history if provided, otherwise use user_content
-        if let Some(history) = conversation_history {
-            messages.extend(history);
-        } else {
-            messages.push(Message::user(&user_content));
+        let mut messages = initial_messages(
+            system_prompt.as_deref(),
+            &few_shot,
+            conversation_history,
+            &user_content,
+        );
+        if let Some(instruction) = injected_instruction {
+            inject_before_last_user(&mut messages, instruction);
         }
 
         // Collect tool definitions
@@ -852,7 +1585,10 @@ impl PromptBuilder {
         // Track executed tool calls and total tokens
         let mut executed_tool_calls = Vec::new();
         let mut total_token_count = 0;
-        let mut final_text;
+        let mut token_usage: Option<TokenUsage> = None;
+        let mut final_text = String::new();
+        let mut thinking = String::new();
+        let mut final_stop_reason = StopReason::EndTurn;
         let mut rounds = 0;
 
         // Wrap callbacks in Arc<Mutex> for sharing across multiple rounds
@@ -860,9 +1596,11 @@ impl PromptBuilder {
             on_start.map(|f| Arc::new(std::sync::Mutex::new(f)));
         let on_token: Option<WrappedTokenCallback> =
             on_token.map(|f| Arc::new(std::sync::Mutex::new(f)));
+        let on_thinking_token: Option<WrappedTokenCallback> =
+            on_thinking_token.map(|f| Arc::new(std::sync::Mutex::new(f)));
         let on_end: Option<WrappedEndCallback> = on_end.map(|f| Arc::new(std::sync::Mutex::new(f)));
 
-        loop {
+        'rounds: loop {
             rounds += 1;
             if rounds > max_tool_rounds {
                 return Err(ActonAIError::prompt_failed(format!(
@@ -870,50 +1608,138 @@ impl PromptBuilder {
                 )));
             }
 
-            // Generate new IDs for this round
-            let correlation_id = CorrelationId::new();
-            let agent_id = AgentId::new();
-
-            // Create the request
-            let request = LLMRequest {
-                correlation_id: correlation_id.clone(),
-                agent_id,
-                messages: messages.clone(),
-                tools: if has_tools {
-                    Some(tool_definitions.clone())
-                } else {
-                    None
-                },
-                sampling: sampling.clone(),
-            };
+            // Send the round's request, resending it after retriable errors
+            let mut attempt = 0;
+            let (round_correlation_id, round) = loop {
+                attempt += 1;
+
+                // Tokens left in the budget for this attempt
+                let remaining_budget =
+                    token_budget.map(|budget| (budget as usize).saturating_sub(total_token_count));
+                if remaining_budget == Some(0) {
+                    final_stop_reason = StopReason::MaxTokens;
+                    break 'rounds;
+                }
 
-            // Collect stream response — reuses the caller-owned collector.
-            // Keep a clone so we can tag tool-result broadcasts with the
-            // round's correlation ID further down.
-            let round_correlation_id = correlation_id.clone();
-            let round_callbacks = StreamRoundCallbacks {
-                on_start: on_start.clone(),
-                on_token: on_token.clone(),
-                on_end: on_end.clone(),
-                token_target: token_target.clone(),
+                // Generate new IDs for this attempt
+                let correlation_id = CorrelationId::new();
+                let agent_id = AgentId::new();
+
+                // Create the request
+                let request = LLMRequest {
+                    correlation_id: correlation_id.clone(),
+                    agent_id,
+                    messages: messages.clone(),
+                    tools: if has_tools {
+                        Some(tool_definitions.clone())
+                    } else {
+                        None
+                    },
+                    sampling: sampling.clone(),
+                    max_tokens: remaining_budget.map(|remaining| remaining as u32),
+                };
+
+                // Collect stream response — reuses the caller-owned collector.
+                // Keep a clone so we can tag tool-result broadcasts with the
+                // round's correlation ID further down.
+                let round_correlation_id = correlation_id.clone();
+                let round_callbacks = StreamRoundCallbacks {
+                    on_start: on_start.clone(),
+                    on_token: on_token.clone(),
+                    on_thinking_token: on_thinking_token.clone(),
+                    on_end: on_end.clone(),
+                    token_target: token_target.clone(),
+                };
+                // Hold the attempt's events back while a retry may replace it
+                let held_events = retry_policy.map(|_| HeldStreamEvents::default());
+                let attempt_callbacks = match &held_events {
+                    Some(held) => held.recording(&round_callbacks),
+                    None => round_callbacks.clone(),
+                };
+                let round_budget = remaining_budget.map(|max_tokens| RoundTokenBudget {
+                    max_tokens,
+                    provider: provider_handle.clone(),
+                });
+                let round_started = Instant::now();
+                let round_future = run_stream_round(
+                    session,
+                    &provider_handle,
+                    &request,
+                    correlation_id,
+                    attempt_callbacks,
+                    round_budget,
+                );
+                let round_result = match round_timeout {
+                    Some(limit) => match tokio::time::timeout(limit, round_future).await {
+                        Ok(result) => result,
+                        Err(_) => {
+                            session.cancel_round().await;
+                            Err(ActonAIError::timeout(round_started.elapsed()))
+                        }
+                    },
+                    None => round_future.await,
+                };
+                let mut round = round_result.with_error_context(|| {
+                    ErrorContext::new("streaming LLM response")
+                        .with_correlation_id(round_correlation_id.clone())
+                })?;
+                total_token_count += round.token_count;
+                if let Some(usage) = round.usage {
+                    *token_usage.get_or_insert_with(TokenUsage::default) += usage;
+                }
+
+                let Some(error) = round.error.take() else {
+                    if let Some(held) = held_events {
+                        held.release(&round_callbacks).await;
+                    }
+                    break (round_correlation_id, round);
+                };
+
+                // Tokens streamed before a failure count against the budget
+                let budget_left =
+                    token_budget.is_none_or(|budget| total_token_count < budget as usize);
+                match retry_policy {
+                    Some(policy) if budget_left && policy.should_retry(attempt, &error) => {
+                        let delay = policy.backoff_for_attempt(attempt, &error);
+                        tracing::warn!(
+                            attempt,
+                            delay_ms = delay.as_millis() as u64,
+                            error = %error,
+                            "Retrying prompt after retriable error"
+                        );
+                        tokio::time::sleep(delay).await;
+                    }
+                    _ => {
+                        return Err(ActonAIError::provider_error(error.to_string())
+                            .with_error_context(
+                                ErrorContext::new("streaming LLM response")
+                                    .with_correlation_id(round_correlation_id),
+                            ));
+                    }
+                }
             };
-            let (text, stop_reason, token_count, tool_calls) = run_stream_round(
-                session,
-                &provider_handle,
-                &request,
-                correlation_id,
-                round_callbacks,
-            )
-            .await?;
+            let CollectorResultData {
+                buffer: text,
+                thinking: round_thinking,
+                stop_reason,
+                tool_calls,
+                ..
+            } = round;
+            let stop_reason = stop_reason.unwrap_or(StopReason::EndTurn);
+            thinking.push_str(&round_thinking);
 
             final_text = text.clone();
-            total_token_count += token_count;
 
             match stop_reason {
-                StopReason::EndTurn | StopReason::MaxTokens | StopReason::StopSequence => {
+                StopReason::EndTurn | StopReason::StopSequence => {
                     // Conversation complete
                     break;
                 }
+                StopReason::MaxTokens => {
+                    // Response cut off by the provider or the token budget
+                    final_stop_reason = StopReason::MaxTokens;
+                    break;
+                }
                 StopReason::ToolUse => {
                     if tool_calls.is_empty() {
                         // No tool calls but ToolUse stop reason - treat as complete
@@ -976,15 +1802,72 @@ impl PromptBuilder {
             }
         }
 
-        Ok(CollectedResponse::with_tool_calls(
+        let mut response = CollectedResponse::with_tool_calls(
             final_text,
-            StopReason::EndTurn,
+            final_stop_reason,
             total_token_count,
             executed_tool_calls,
-        ))
+        );
+        response.is_json = is_json;
+        response.thinking = (!thinking.is_empty()).then_some(thinking);
+        response.token_usage = token_usage;
+        Ok(response)
     }
 }
 
+/// Builds the system prompt instruction asking for JSON output.
+fn structured_output_instruction(schema: &serde_json::Value) -> String {
+    let is_any_object = schema.as_object().is_some_and(|o| o.is_empty());
+    if is_any_object {
+        return "Respond only with a valid JSON object, with no surrounding text or \
+                code fences."
+            .to_string();
+    }
+    let schema = serde_json::to_string_pretty(schema).unwrap_or_else(|_| schema.to_string());
+    format!(
+        "Respond only with valid JSON matching this JSON schema, with no surrounding \
+         text or code fences:\n{schema}"
+    )
+}
+
+/// Builds the messages a prompt starts with: the system prompt, the
+/// few-shot examples, then the conversation history if there is one or the
+/// user content otherwise.
+fn initial_messages(
+    system_prompt: Option<&str>,
+    few_shot: &[FewShotExample],
+    conversation_history: Option<Vec<Message>>,
+    user_content: &str,
+) -> Vec<Message> {
+    let mut messages = Vec::new();
+    if let Some(system) = system_prompt {
+        messages.push(Message::system(system));
+    }
+
+    for example in few_shot {
+        messages.push(Message::user(&example.input));
+        messages.push(Message::assistant(&example.output));
+    }
+
+    // Use conversation history if provided, otherwise use user_content
+    if let Some(history) = conversation_history {
+        messages.extend(history);
+    } else {
+        messages.push(Message::user(user_content));
+    }
+    messages
+}
+
+/// Inserts `instruction` as an injected message before the last user
+/// message, or at the end if there is none.
+fn inject_before_last_user(messages: &mut Vec<Message>, instruction: String) {
+    let index = messages
+        .iter()
+        .rposition(|m| m.role == MessageRole::User)
+        .unwrap_or(messages.len());
+    messages.insert(index, Message::injected_instruction(instruction));
+}
+
 /// Callbacks and token target that apply to a single stream round.
 ///
 /// Sent into the long-lived [`StreamCollectorSession`] via
@@ -1000,15 +1883,118 @@ impl PromptBuilder {
 pub(crate) struct StreamRoundCallbacks {
     pub(crate) on_start: Option<WrappedStartCallback>,
     pub(crate) on_token: Option<WrappedTokenCallback>,
+    pub(crate) on_thinking_token: Option<WrappedTokenCallback>,
     pub(crate) on_end: Option<WrappedEndCallback>,
     pub(crate) token_target: Option<ActorHandle>,
 }
 
+/// A stream callback invocation held back by [`HeldStreamEvents`].
+enum HeldStreamEvent {
+    Start,
+    Token(String),
+    ThinkingToken(String),
+    End(StopReason),
+}
+
+/// Stream events of an attempt that a retry may still replace.
+///
+/// While a retry policy applies, each attempt streams into callbacks from
+/// [`recording`](Self::recording), and [`release`](Self::release) passes
+/// the events on once the attempt succeeds. A failed attempt's events are
+/// dropped, so callers never see its output.
+#[derive(Clone, Default)]
+struct HeldStreamEvents {
+    events: Arc<std::sync::Mutex<Vec<HeldStreamEvent>>>,
+}
+
+impl HeldStreamEvents {
+    /// Returns callbacks recording an event for each hook `callbacks` has.
+    fn recording(&self, callbacks: &StreamRoundCallbacks) -> StreamRoundCallbacks {
+        let on_start = callbacks.on_start.as_ref().map(|_| {
+            let held = self.clone();
+            let callback: StartCallback = Box::new(move || held.push(HeldStreamEvent::Start));
+            Arc::new(std::sync::Mutex::new(callback))
+        });
+        let wants_tokens = callbacks.on_token.is_some() || callbacks.token_target.is_some();
+        let on_token = wants_tokens.then(|| {
+            let held = self.clone();
+            let callback: TokenCallback =
+                Box::new(move |token| held.push(HeldStreamEvent::Token(token.to_string())));
+            Arc::new(std::sync::Mutex::new(callback))
+        });
+        let on_thinking_token = callbacks.on_thinking_token.as_ref().map(|_| {
+            let held = self.clone();
+            let callback: TokenCallback = Box::new(move |token| {
+                held.push(HeldStreamEvent::ThinkingToken(token.to_string()));
+            });
+            Arc::new(std::sync::Mutex::new(callback))
+        });
+        let on_end = callbacks.on_end.as_ref().map(|_| {
+            let held = self.clone();
+            let callback: EndCallback =
+                Box::new(move |reason| held.push(HeldStreamEvent::End(reason)));
+            Arc::new(std::sync::Mutex::new(callback))
+        });
+
+        StreamRoundCallbacks {
+            on_start,
+            on_token,
+            on_thinking_token,
+            on_end,
+            token_target: None,
+        }
+    }
+
+    fn push(&self, event: HeldStreamEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+
+    /// Fires `callbacks` for every held event, in the order received.
+    async fn release(self, callbacks: &StreamRoundCallbacks) {
+        let events = self
+            .events
+            .lock()
+            .map(|mut events| std::mem::take(&mut *events))
+            .unwrap_or_default();
+        for event in events {
+            match event {
+                HeldStreamEvent::Start => {
+                    if let Some(Ok(mut f)) = callbacks.on_start.as_ref().map(|c| c.lock()) {
+                        f();
+                    }
+                }
+                HeldStreamEvent::Token(token) => {
+                    if let Some(Ok(mut f)) = callbacks.on_token.as_ref().map(|c| c.lock()) {
+                        f(&token);
+                    }
+                    if let Some(ref target) = callbacks.token_target {
+                        target.send(StreamToken { text: token }).await;
+                    }
+                }
+                HeldStreamEvent::ThinkingToken(token) => {
+                    if let Some(Ok(mut f)) = callbacks.on_thinking_token.as_ref().map(|c| c.lock())
+                    {
+                        f(&token);
+                    }
+                }
+                HeldStreamEvent::End(reason) => {
+                    if let Some(Ok(mut f)) = callbacks.on_end.as_ref().map(|c| c.lock()) {
+                        f(reason);
+                    }
+                }
+            }
+        }
+    }
+}
+
 impl std::fmt::Debug for StreamRoundCallbacks {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("StreamRoundCallbacks")
             .field("on_start", &self.on_start.is_some())
             .field("on_token", &self.on_token.is_some())
+            .field("on_thinking_token", &self.on_thinking_token.is_some())
             .field("on_end", &self.on_end.is_some())
             .field("token_target", &self.token_target.is_some())
             .finish()
@@ -1041,6 +2027,9 @@ struct StreamCollectorSessionInner {
     handle: std::sync::Mutex<Option<ActorHandle>>,
     completion: Arc<Notify>,
     result_container: Arc<std::sync::Mutex<Option<CollectorResultData>>>,
+    /// Correlation ID and provider of the round being collected, so a
+    /// timed-out round can be cancelled.
+    in_flight: std::sync::Mutex<Option<(CorrelationId, ActorHandle)>>,
 }
 
 impl StreamCollectorSession {
@@ -1059,6 +2048,24 @@ impl StreamCollectorSession {
     fn handle(&self) -> Option<ActorHandle> {
         self.inner.handle.lock().ok().and_then(|g| g.clone())
     }
+
+    /// Cancel the round being collected, if any.
+    ///
+    /// The provider ends the cancelled stream with
+    /// [`StopReason::StopSequence`]; its result is discarded because no
+    /// round waits for it any more.
+    pub(crate) async fn cancel_round(&self) {
+        let in_flight = self.inner.in_flight.lock().ok().and_then(|mut g| g.take());
+        if let Some((correlation_id, provider)) = in_flight {
+            provider.send(CancelStream { correlation_id }).await;
+        }
+    }
+
+    fn set_in_flight(&self, round: Option<(CorrelationId, ActorHandle)>) {
+        if let Ok(mut guard) = self.inner.in_flight.lock() {
+            *guard = round;
+        }
+    }
 }
 
 impl Drop for StreamCollectorSessionInner {
@@ -1078,7 +2085,7 @@ impl Drop for StreamCollectorSessionInner {
 }
 
 /// Build and start a long-lived `StreamCollector` actor subscribed to all
-/// four streaming event types. The caller drives individual rounds via
+/// six streaming event types. The caller drives individual rounds via
 /// [`run_stream_round`], which reuses this handle — and its subscriptions —
 /// for every round of every turn.
 pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollectorSession {
@@ -1088,6 +2095,8 @@ pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollector
     let result_container: Arc<std::sync::Mutex<Option<CollectorResultData>>> =
         Arc::new(std::sync::Mutex::new(None));
     let result_container_for_handler = result_container.clone();
+    let budget_result_container = result_container.clone();
+    let budget_completion_signal = completion.clone();
 
     let mut actor_runtime = runtime.runtime().clone();
     let mut collector = actor_runtime.new_actor::<StreamCollector>();
@@ -1114,6 +2123,28 @@ pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollector
         {
             return Reply::ready();
         }
+        // Past the budget: cancel the stream and end the round truncated.
+        if let Some(budget) = actor.model.budget.take() {
+            if actor.model.token_count >= budget.max_tokens {
+                actor.model.stop_reason = Some(StopReason::MaxTokens);
+                if let Some(ref callback) = actor.model.round.on_end {
+                    if let Ok(mut f) = callback.lock() {
+                        f(StopReason::MaxTokens);
+                    }
+                }
+                finish_round(&mut actor.model, &budget_result_container);
+                budget_completion_signal.notify_one();
+
+                let cancel = CancelStream {
+                    correlation_id: envelope.message().correlation_id.clone(),
+                };
+                return Reply::pending(async move {
+                    budget.provider.send(cancel).await;
+                });
+            }
+            actor.model.budget = Some(budget);
+        }
+
         let token = envelope.message().token.clone();
         actor.model.buffer.push_str(&token);
         actor.model.token_count += 1;
@@ -1133,6 +2164,24 @@ pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollector
         Reply::ready()
     });
 
+    // Thinking token — accumulate apart from the response text and fire the
+    // caller's callback.
+    collector.mutate_on::<LLMStreamThinkingToken>(move |actor, envelope| {
+        if actor.model.expected_correlation_id.as_ref()
+            != Some(&envelope.message().correlation_id)
+        {
+            return Reply::ready();
+        }
+        let token = &envelope.message().token;
+        actor.model.thinking.push_str(token);
+        if let Some(ref callback) = actor.model.round.on_thinking_token {
+            if let Ok(mut f) = callback.lock() {
+                f(token);
+            }
+        }
+        Reply::ready()
+    });
+
     // Stream tool call — accumulate into per-round state.
     collector.mutate_on::<LLMStreamToolCall>(move |actor, envelope| {
         if actor.model.expected_correlation_id.as_ref()
@@ -1147,6 +2196,29 @@ pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollector
         Reply::ready()
     });
 
+    // Stream usage — sum what the provider reports for the round.
+    collector.mutate_on::<LLMStreamUsage>(move |actor, envelope| {
+        if actor.model.expected_correlation_id.as_ref()
+            != Some(&envelope.message().correlation_id)
+        {
+            return Reply::ready();
+        }
+        *actor.model.usage.get_or_insert_with(TokenUsage::default) += envelope.message().usage;
+        Reply::ready()
+    });
+
+    // Stream error — keep the error for the round result; the stream end
+    // follows.
+    collector.mutate_on::<LLMStreamError>(move |actor, envelope| {
+        if actor.model.expected_correlation_id.as_ref()
+            != Some(&envelope.message().correlation_id)
+        {
+            return Reply::ready();
+        }
+        actor.model.error = Some(envelope.message().error.clone());
+        Reply::ready()
+    });
+
     // Stream end — take the accumulated state into the shared result slot
     // and signal completion so the caller can pick up the round result.
     collector.mutate_on::<LLMStreamEnd>(move |actor, envelope| {
@@ -1162,20 +2234,7 @@ pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollector
             }
         }
 
-        if let Ok(mut container) = result_container_for_handler.lock() {
-            *container = Some(CollectorResultData {
-                buffer: std::mem::take(&mut actor.model.buffer),
-                stop_reason: actor.model.stop_reason,
-                token_count: actor.model.token_count,
-                tool_calls: std::mem::take(&mut actor.model.tool_calls),
-            });
-        }
-        // Clear the correlation-ID filter and drop callbacks + target so
-        // late stray events from the just-finished round don't land in
-        // the next round's state.
-        actor.model.expected_correlation_id = None;
-        actor.model.round = StreamRoundCallbacks::default();
-
+        finish_round(&mut actor.model, &result_container_for_handler);
         completion_signal.notify_one();
         Reply::ready()
     });
@@ -1188,18 +2247,28 @@ pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollector
     collector.mutate_on::<ResetStreamRound>(move |actor, envelope| {
         let msg = envelope.message();
         actor.model.buffer.clear();
+        actor.model.thinking.clear();
         actor.model.token_count = 0;
         actor.model.stop_reason = None;
         actor.model.tool_calls.clear();
+        actor.model.usage = None;
+        actor.model.error = None;
         actor.model.expected_correlation_id = Some(msg.expected_id.clone());
         actor.model.round = msg.callbacks.clone();
+        actor.model.budget = msg.budget.clone();
         Reply::ready()
     });
 
     // Subscribe BEFORE starting so no broadcast can slip past us.
     collector.handle().subscribe::<LLMStreamStart>().await;
     collector.handle().subscribe::<LLMStreamToken>().await;
+    collector
+        .handle()
+        .subscribe::<LLMStreamThinkingToken>()
+        .await;
     collector.handle().subscribe::<LLMStreamToolCall>().await;
+    collector.handle().subscribe::<LLMStreamUsage>().await;
+    collector.handle().subscribe::<LLMStreamError>().await;
     collector.handle().subscribe::<LLMStreamEnd>().await;
 
     let handle = collector.start().await;
@@ -1208,6 +2277,7 @@ pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollector
             handle: std::sync::Mutex::new(Some(handle)),
             completion,
             result_container,
+            in_flight: std::sync::Mutex::new(None),
         }),
     }
 }
@@ -1216,14 +2286,18 @@ pub(crate) async fn build_stream_collector(runtime: &ActonAI) -> StreamCollector
 ///
 /// Installs `callbacks` for this round before firing the request so the
 /// collector's event handlers dispatch to the right hooks, then waits on
-/// the completion notify for the round's final result.
+/// the completion notify for the round's final result. With a `budget`,
+/// the collector cancels the stream once the round exceeds it. A provider
+/// failure is reported in the result's `error` rather than as an `Err`,
+/// so the caller can decide whether to retry.
 pub(crate) async fn run_stream_round(
     session: &StreamCollectorSession,
     provider_handle: &ActorHandle,
     request: &LLMRequest,
     correlation_id: CorrelationId,
     callbacks: StreamRoundCallbacks,
-) -> Result<(String, StopReason, usize, Vec<ToolCall>), ActonAIError> {
+    budget: Option<RoundTokenBudget>,
+) -> Result<CollectorResultData, ActonAIError> {
     // Resolve the collector's live actor handle. Returns an error if the
     // session was already shut down — defensive, but shouldn't happen on
     // any normal path.
@@ -1234,33 +2308,38 @@ pub(crate) async fn run_stream_round(
     // Ack: reset state and install the new round's correlation ID + callbacks.
     handle
         .send(ResetStreamRound {
-            expected_id: correlation_id,
+            expected_id: correlation_id.clone(),
             callbacks,
+            budget,
         })
         .await;
 
     // Fire the request.
+    session.set_in_flight(Some((correlation_id.clone(), provider_handle.clone())));
     provider_handle.send(request.clone()).await;
 
-    // Wait for the stream-end handler to fill the result slot.
-    session.inner.completion.notified().await;
-
-    let result = session
-        .inner
-        .result_container
-        .lock()
-        .ok()
-        .and_then(|mut guard| guard.take())
-        .ok_or_else(|| {
-            ActonAIError::prompt_failed("failed to retrieve collected stream data".to_string())
-        })?;
-
-    Ok((
-        result.buffer,
-        result.stop_reason.unwrap_or(StopReason::EndTurn),
-        result.token_count,
-        result.tool_calls,
-    ))
+    // Wait for the stream-end handler to fill the result slot. A round
+    // abandoned on timeout may still complete and leave its result behind;
+    // skip anything that isn't this round's.
+    let result = loop {
+        session.inner.completion.notified().await;
+
+        let result = session
+            .inner
+            .result_container
+            .lock()
+            .map_err(|_| {
+                ActonAIError::prompt_failed("failed to retrieve collected stream data".to_string())
+            })?
+            .take();
+        if let Some(result) = result {
+            if result.correlation_id.as_ref() == Some(&correlation_id) {
+                break result;
+            }
+        }
+    };
+    session.set_in_flight(None);
+    Ok(result)
 }
 
 /// Render a successful tool result as a single-line preview for the
@@ -1346,12 +2425,18 @@ async fn execute_tool_with_callback(
 struct StreamCollector {
     /// Accumulated response buffer for the current round
     buffer: String,
+    /// Accumulated extended thinking trace for the current round
+    thinking: String,
     /// Count of tokens received in the current round
     token_count: usize,
     /// Stop reason when the current round's stream ends
     stop_reason: Option<StopReason>,
     /// Accumulated tool calls from the current round
     tool_calls: Vec<ToolCall>,
+    /// Token usage reported for the current round
+    usage: Option<TokenUsage>,
+    /// Error that ended the current round, if it failed
+    error: Option<LLMError>,
     /// Correlation ID of the round currently being collected. Handlers
     /// ignore any event whose correlation ID doesn't match — protects the
     /// collector from stray events emitted by other concurrent streams
@@ -1360,6 +2445,45 @@ struct StreamCollector {
     /// Caller-supplied callbacks + token target for the current round.
     /// Swapped in at the start of each round by [`ResetStreamRound`].
     round: StreamRoundCallbacks,
+    /// Token budget for the current round, if any
+    budget: Option<RoundTokenBudget>,
+}
+
+/// Move the current round's state into the shared result slot.
+///
+/// Clears the correlation-ID filter and drops callbacks + target so late
+/// stray events from the just-finished round don't land in the next
+/// round's state.
+fn finish_round(
+    collector: &mut StreamCollector,
+    result_container: &std::sync::Mutex<Option<CollectorResultData>>,
+) {
+    if let Ok(mut container) = result_container.lock() {
+        *container = Some(CollectorResultData {
+            buffer: std::mem::take(&mut collector.buffer),
+            thinking: std::mem::take(&mut collector.thinking),
+            stop_reason: collector.stop_reason,
+            token_count: collector.token_count,
+            tool_calls: std::mem::take(&mut collector.tool_calls),
+            usage: collector.usage.take(),
+            error: collector.error.take(),
+            correlation_id: collector.expected_correlation_id.clone(),
+        });
+    }
+    collector.expected_correlation_id = None;
+    collector.round = StreamRoundCallbacks::default();
+    collector.budget = None;
+}
+
+/// Token limit for a single stream round.
+///
+/// When the round streams more than `max_tokens` tokens, the collector
+/// sends [`CancelStream`] to `provider` and ends the round with
+/// [`StopReason::MaxTokens`].
+#[derive(Clone, Debug)]
+pub(crate) struct RoundTokenBudget {
+    pub(crate) max_tokens: usize,
+    pub(crate) provider: ActorHandle,
 }
 
 /// Per-round reset message. Sent to the collector before starting each
@@ -1370,23 +2494,445 @@ struct StreamCollector {
 struct ResetStreamRound {
     expected_id: CorrelationId,
     callbacks: StreamRoundCallbacks,
+    budget: Option<RoundTokenBudget>,
 }
 
 /// Collected stream data returned from the actor.
 #[derive(Debug, Clone, Default)]
-struct CollectorResultData {
+pub(crate) struct CollectorResultData {
     /// Accumulated text from tokens
     buffer: String,
+    /// Accumulated extended thinking trace
+    thinking: String,
     /// Reason the stream stopped
     stop_reason: Option<StopReason>,
     /// Number of tokens received
     token_count: usize,
     /// Tool calls received during streaming
     tool_calls: Vec<ToolCall>,
+    /// Token usage reported by the provider
+    usage: Option<TokenUsage>,
+    /// Error reported by the provider, if the round failed
+    error: Option<LLMError>,
+    /// Correlation ID of the round the data belongs to
+    correlation_id: Option<CorrelationId>,
+}
+
+/// A prompt with `{{variable}}` placeholders.
+///
+/// Placeholders are filled by [`render`](Self::render):
+///
+/// - `{{name}}` is replaced by the variable's value, or left as-is when the
+///   variable is missing
+/// - `{{name?}}` renders as an empty string when the variable is missing
+/// - `{{name|default}}` renders `default` when the variable is missing
+/// - `{{#if name}}...{{/if}}` keeps its body only when the variable is set
+///   to a non-empty value; conditionals may be nested
+///
+/// Substituted values are inserted verbatim and never re-expanded.
+///
+/// # Example
+///
+/// ```
+/// use acton_ai::prompt::PromptTemplate;
+/// use std::collections::HashMap;
+///
+/// let template = PromptTemplate::new(
+///     "Review this {{language|Rust}} code.{{#if focus}} Focus on {{focus}}.{{/if}}",
+/// );
+///
+/// let vars = HashMap::from([("focus", "error handling")]);
+/// assert_eq!(
+///     template.render(&vars),
+///     "Review this Rust code. Focus on error handling."
+/// );
+/// assert_eq!(template.render(&HashMap::new()), "Review this Rust code.");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplate {
+    template: String,
+}
+
+impl PromptTemplate {
+    /// Creates a template from its source text.
+    #[must_use]
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+        }
+    }
+
+    /// Returns the template's source text.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.template
+    }
+
+    /// Renders the template with `vars`.
+    #[must_use]
+    pub fn render(&self, vars: &HashMap<&str, &str>) -> String {
+        let mut out = String::with_capacity(self.template.len());
+        render_template(&self.template, vars, &mut out);
+        out
+    }
+}
+
+/// One `{{...}}` tag found in a template.
+struct TemplateTag<'a> {
+    /// Text before the tag
+    before: &'a str,
+    /// The tag including its braces
+    raw: &'a str,
+    /// The tag's trimmed contents
+    tag: &'a str,
+    /// Text after the tag
+    after: &'a str,
+}
+
+/// Finds the first tag in `text`.
+fn next_tag(text: &str) -> Option<TemplateTag<'_>> {
+    let start = text.find("{{")?;
+    let end = text[start + 2..].find("}}")? + start + 4;
+    Some(TemplateTag {
+        before: &text[..start],
+        raw: &text[start..end],
+        tag: text[start + 2..end - 2].trim(),
+        after: &text[end..],
+    })
+}
+
+fn render_template(mut text: &str, vars: &HashMap<&str, &str>, out: &mut String) {
+    while let Some(TemplateTag {
+        before,
+        raw,
+        tag,
+        after,
+    }) = next_tag(text)
+    {
+        out.push_str(before);
+        text = after;
+
+        if let Some(name) = tag.strip_prefix("#if ") {
+            let (body, rest) = split_conditional(text);
+            if vars.get(name.trim()).is_some_and(|value| !value.is_empty()) {
+                render_template(body, vars, out);
+            }
+            text = rest;
+        } else if tag == "/if" {
+            // A stray closing tag has nothing to close
+        } else if let Some(name) = tag.strip_suffix('?') {
+            out.push_str(vars.get(name.trim()).copied().unwrap_or_default());
+        } else if let Some((name, default)) = tag.split_once('|') {
+            out.push_str(vars.get(name.trim()).copied().unwrap_or(default));
+        } else {
+            out.push_str(vars.get(tag).copied().unwrap_or(raw));
+        }
+    }
+    out.push_str(text);
+}
+
+/// Splits the text after an `{{#if}}` into the conditional's body and the
+/// text after its matching `{{/if}}`. An unclosed conditional runs to the
+/// end of the text.
+fn split_conditional(text: &str) -> (&str, &str) {
+    let mut depth = 0usize;
+    let mut scanned = 0;
+    while let Some(found) = next_tag(&text[scanned..]) {
+        let tag_start = scanned + found.before.len();
+        scanned = text.len() - found.after.len();
+        if found.tag.starts_with("#if ") {
+            depth += 1;
+        } else if found.tag == "/if" {
+            if depth == 0 {
+                return (&text[..tag_start], &text[scanned..]);
+            }
+            depth -= 1;
+        }
+    }
+    (text, "")
+}
+
+/// A template as stored in a template file.
+#[derive(Debug, Deserialize)]
+struct TemplateFileEntry {
+    template: String,
+}
+
+/// Named [`PromptTemplate`]s.
+///
+/// [`ActonAI`] loads the library in
+/// [`default_dir`](Self::default_dir) at launch; see
+/// [`ActonAI::templates`]. Template files are TOML tables of templates,
+/// keyed by name:
+///
+/// ```toml
+/// [code_review]
+/// template = "Review this {{language|Rust}} code:\n\n{{code}}"
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use acton_ai::prompt::{PromptTemplate, TemplateLibrary};
+/// use std::collections::HashMap;
+///
+/// let library = TemplateLibrary::new()
+///     .with_template("greeting", PromptTemplate::new("Hello, {{name|friend}}!"));
+///
+/// let greeting = library.get("greeting").unwrap();
+/// assert_eq!(greeting.render(&HashMap::new()), "Hello, friend!");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TemplateLibrary {
+    templates: HashMap<String, PromptTemplate>,
+}
+
+impl TemplateLibrary {
+    /// Creates an empty library.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a template, returning the one it replaced.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        template: PromptTemplate,
+    ) -> Option<PromptTemplate> {
+        self.templates.insert(name.into(), template)
+    }
+
+    /// Adds a template, replacing any with the same name.
+    #[must_use]
+    pub fn with_template(mut self, name: impl Into<String>, template: PromptTemplate) -> Self {
+        self.register(name, template);
+        self
+    }
+
+    /// Returns the template registered as `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.get(name)
+    }
+
+    /// Returns the names of all templates, sorted.
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.templates.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Returns the number of templates.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Returns true if the library has no templates.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Adds every template of `other`, replacing any with the same name.
+    pub fn merge(&mut self, other: TemplateLibrary) {
+        self.templates.extend(other.templates);
+    }
+
+    /// Parses templates from the contents of a template file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if `toml_str` is not a table of
+    /// templates.
+    pub fn from_toml_str(toml_str: &str) -> Result<Self, ActonAIError> {
+        Self::parse(toml_str)
+            .map_err(|e| ActonAIError::configuration("templates", format!("invalid TOML: {e}")))
+    }
+
+    fn parse(toml_str: &str) -> Result<Self, toml::de::Error> {
+        let entries: HashMap<String, TemplateFileEntry> = toml::from_str(toml_str)?;
+        Ok(Self {
+            templates: entries
+                .into_iter()
+                .map(|(name, entry)| (name, PromptTemplate::new(entry.template)))
+                .collect(),
+        })
+    }
+
+    /// Loads every `.toml` file in `dir`.
+    ///
+    /// Files are read in name order, so a template defined in several files
+    /// comes from the last one.
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if `dir` or one of its template files
+    /// cannot be read or parsed.
+    pub fn load_dir(dir: &Path) -> Result<Self, ActonAIError> {
+        let read_failed = |e: std::io::Error| {
+            ActonAIError::configuration(
+                "templates",
+                format!("failed to read '{}': {}", dir.display(), e),
+            )
+        };
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(read_failed)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        paths.sort();
+
+        let mut library = Self::new();
+        for path in paths {
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                ActonAIError::configuration(
+                    "templates",
+                    format!("failed to read '{}': {}", path.display(), e),
+                )
+            })?;
+            let templates = Self::parse(&contents).map_err(|e| {
+                ActonAIError::configuration(
+                    "templates",
+                    format!("failed to parse '{}': {}", path.display(), e),
+                )
+            })?;
+            library.merge(templates);
+        }
+        Ok(library)
+    }
+
+    /// Returns the directory templates are loaded from at launch.
+    ///
+    /// This is `~/.config/acton-ai/templates` on most systems.
+    #[must_use]
+    pub fn default_dir() -> Option<PathBuf> {
+        crate::config::xdg_config_dir().map(|dir| dir.join("templates"))
+    }
+
+    /// Loads the templates in [`default_dir`](Self::default_dir).
+    ///
+    /// Returns an empty library if the directory does not exist; a
+    /// directory that fails to load is logged and skipped.
+    #[must_use]
+    pub fn load_default() -> Self {
+        let Some(dir) = Self::default_dir().filter(|dir| dir.is_dir()) else {
+            return Self::new();
+        };
+        match Self::load_dir(&dir) {
+            Ok(library) => {
+                tracing::info!(count = library.len(), dir = %dir.display(), "prompt templates loaded");
+                library
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to load prompt templates");
+                Self::new()
+            }
+        }
+    }
+}
+
+/// Local OpenAI-compatible server for tests that stream real responses.
+#[cfg(test)]
+pub(crate) mod test_server {
+    use crate::facade::ActonAI;
+    use std::time::Duration;
+
+    /// Serves one OpenAI-style response per entry of `responses`, each
+    /// either an error status or a stream of that many token chunks.
+    ///
+    /// Returns the server's base URL and a handle that resolves to the
+    /// request bodies it received.
+    pub(crate) async fn serve_responses(
+        responses: Vec<Result<usize, u16>>,
+    ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/v1", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let mut bodies = Vec::new();
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut chunk = [0u8; 4096];
+                loop {
+                    let n = socket.read(&mut chunk).await.unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&chunk[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length = head
+                            .lines()
+                            .find_map(|line| {
+                                line.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if body.len() >= length {
+                            break;
+                        }
+                    }
+                }
+                bodies.push(
+                    String::from_utf8_lossy(&request)
+                        .split_once("\r\n\r\n")
+                        .map(|(_, body)| body.to_string())
+                        .unwrap_or_default(),
+                );
+
+                let tokens = match response {
+                    Ok(tokens) => tokens,
+                    Err(status) => {
+                        let head = format!(
+                            "HTTP/1.1 {status} Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                        );
+                        let _ = socket.write_all(head.as_bytes()).await;
+                        continue;
+                    }
+                };
+                let _ = socket
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n",
+                    )
+                    .await;
+                for _ in 0..tokens {
+                    let event = "data: {\"id\":\"x\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\"tok \"}}]}\n\n";
+                    if socket.write_all(event.as_bytes()).await.is_err() {
+                        break;
+                    }
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+                let _ = socket.write_all(b"data: [DONE]\n\n").await;
+            }
+            bodies
+        });
+
+        (base_url, server)
+    }
+
+    pub(crate) async fn launch_against(base_url: String) -> ActonAI {
+        ActonAI::builder()
+            .app_name("prompt-test")
+            .provider(crate::llm::ProviderConfig::openai_compatible(
+                base_url,
+                "test-model",
+            ))
+            .launch()
+            .await
+            .unwrap()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::test_server::{launch_against, serve_responses};
     use super::*;
 
     #[test]
@@ -1427,6 +2973,61 @@ mod tests {
         assert!(cloned.on_result.is_none());
     }
 
+    fn examples() -> Vec<FewShotExample> {
+        FewShotExample::from_qa_pairs(vec![
+            ("2 + 2".to_string(), "4".to_string()),
+            ("3 + 3".to_string(), "6".to_string()),
+        ])
+    }
+
+    #[test]
+    fn few_shot_examples_follow_system_prompt() {
+        let messages = initial_messages(Some("Do arithmetic."), &examples(), None, "4 + 4");
+
+        assert_eq!(
+            messages,
+            vec![
+                Message::system("Do arithmetic."),
+                Message::user("2 + 2"),
+                Message::assistant("4"),
+                Message::user("3 + 3"),
+                Message::assistant("6"),
+                Message::user("4 + 4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn few_shot_examples_precede_conversation_history() {
+        let history = vec![
+            Message::user("1 + 1"),
+            Message::assistant("2"),
+            Message::user("5 + 5"),
+        ];
+        let messages = initial_messages(Some("Do arithmetic."), &examples(), Some(history), "");
+
+        assert_eq!(
+            messages,
+            vec![
+                Message::system("Do arithmetic."),
+                Message::user("2 + 2"),
+                Message::assistant("4"),
+                Message::user("3 + 3"),
+                Message::assistant("6"),
+                Message::user("1 + 1"),
+                Message::assistant("2"),
+                Message::user("5 + 5"),
+            ]
+        );
+    }
+
+    #[test]
+    fn no_few_shot_examples_leaves_messages_unchanged() {
+        let messages = initial_messages(None, &[], None, "hello");
+
+        assert_eq!(messages, vec![Message::user("hello")]);
+    }
+
     #[test]
     fn collected_response_new_creates_correctly() {
         let response = CollectedResponse::new("Hello world".to_string(), StopReason::EndTurn, 2);
@@ -1454,4 +3055,439 @@ mod tests {
         let complete = CollectedResponse::new("test".to_string(), StopReason::EndTurn, 1);
         assert!(!complete.is_truncated());
     }
+
+    #[tokio::test]
+    async fn token_budget_truncates_streamed_response() {
+        let (base_url, server) = serve_responses(vec![Ok(50)]).await;
+        let runtime = launch_against(base_url).await;
+
+        let response = runtime
+            .prompt("Count to fifty")
+            .with_token_budget(5)
+            .collect()
+            .await
+            .unwrap();
+
+        assert!(response.is_truncated());
+        assert_eq!(response.stop_reason, StopReason::MaxTokens);
+        assert!(response.token_count <= 5);
+
+        let bodies = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(body["max_tokens"], 5);
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn template_prompt_sends_rendered_content() {
+        let (base_url, server) = serve_responses(vec![Ok(1)]).await;
+        let runtime = launch_against(base_url).await;
+        let template = PromptTemplate::new("Review this {{language}} code.");
+        let vars = HashMap::from([("language", "Rust")]);
+
+        PromptBuilder::from_template(runtime.clone(), &template, &vars)
+            .collect()
+            .await
+            .unwrap();
+
+        let bodies = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(body["messages"][0]["content"], "Review this Rust code.");
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn collect_to_writer_streams_tokens() {
+        let (base_url, _server) = serve_responses(vec![Ok(3), Ok(2)]).await;
+        let runtime = launch_against(base_url).await;
+
+        let tokens = Arc::new(std::sync::Mutex::new(0));
+        let counter = tokens.clone();
+        let mut out = Vec::new();
+        let response = runtime
+            .prompt("Count")
+            .on_token(move |_| *counter.lock().unwrap() += 1)
+            .collect_to_writer(&mut out)
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), response.text);
+        assert_eq!(*tokens.lock().unwrap(), 3);
+
+        let file = std::env::temp_dir().join(format!("acton-ai-{}.txt", CorrelationId::new()));
+        let response = runtime
+            .prompt("Count")
+            .collect_to_sync_writer(std::fs::File::create(&file).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "tok tok ");
+        assert_eq!(response.text, "tok tok ");
+        let _ = std::fs::remove_file(file);
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn output_schema_requests_json_response() {
+        let (base_url, server) = serve_responses(vec![Ok(2)]).await;
+        let runtime = launch_against(base_url).await;
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "answer": { "type": "string" } }
+        });
+
+        let response = runtime
+            .prompt("Answer in JSON")
+            .system("Be brief.")
+            .output_schema(schema.clone())
+            .collect()
+            .await
+            .unwrap();
+
+        assert!(response.is_json);
+
+        let bodies = server.await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(body["response_format"]["type"], "json_schema");
+        assert_eq!(body["response_format"]["json_schema"]["schema"], schema);
+        let system = body["messages"][0]["content"].as_str().unwrap();
+        assert!(system.starts_with("Be brief.\n\n"));
+        assert!(system.contains("\"answer\""));
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[test]
+    fn injected_instruction_goes_before_last_user_message() {
+        let mut messages = vec![
+            Message::system("Be brief."),
+            Message::user("one"),
+            Message::assistant("two"),
+            Message::user("three"),
+        ];
+        inject_before_last_user(&mut messages, "Stay on task.".to_string());
+
+        assert_eq!(messages.len(), 5);
+        assert_eq!(messages[3], Message::injected_instruction("Stay on task."));
+        assert_eq!(messages[3].role, MessageRole::Assistant);
+        assert!(messages[3].is_injected);
+        assert_eq!(messages[4].content, "three");
+
+        let mut no_user = vec![Message::system("Be brief.")];
+        inject_before_last_user(&mut no_user, "Stay on task.".to_string());
+        assert!(no_user[1].is_injected);
+    }
+
+    #[test]
+    fn structured_output_instruction_includes_schema() {
+        let schema = serde_json::json!({"type": "object", "required": ["city"]});
+        let instruction = structured_output_instruction(&schema);
+        assert!(instruction.contains("JSON schema"));
+        assert!(instruction.contains("\"city\""));
+
+        let any_object = structured_output_instruction(&serde_json::json!({}));
+        assert!(any_object.contains("JSON object"));
+    }
+
+    #[tokio::test]
+    async fn provider_sampling_updates_without_restart() {
+        let (base_url, server) = serve_responses(vec![Ok(1), Ok(1)]).await;
+        let runtime = launch_against(base_url).await;
+
+        runtime
+            .set_provider_sampling(
+                runtime.default_provider_name(),
+                SamplingParams::new().with_temperature(0.3),
+            )
+            .await
+            .unwrap();
+        runtime.prompt("First").collect().await.unwrap();
+
+        // Per-prompt values override field by field; temperature stays
+        runtime
+            .prompt("Second")
+            .sampling(SamplingParams::new().with_top_p(0.5))
+            .collect()
+            .await
+            .unwrap();
+
+        let bodies = server.await.unwrap();
+        let first: serde_json::Value = serde_json::from_str(&bodies[0]).unwrap();
+        assert_eq!(first["temperature"], 0.3);
+        let second: serde_json::Value = serde_json::from_str(&bodies[1]).unwrap();
+        assert_eq!(second["temperature"], 0.3);
+        assert_eq!(second["top_p"], 0.5);
+
+        assert!(runtime
+            .set_provider_sampling("missing", SamplingParams::new())
+            .await
+            .unwrap_err()
+            .is_configuration());
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn retry_resends_after_retriable_error() {
+        let (base_url, server) = serve_responses(vec![Err(503), Err(502), Ok(3)]).await;
+        let runtime = launch_against(base_url).await;
+
+        let response = runtime
+            .prompt("Say hello")
+            .retry(RetryPolicy::new(3).with_initial_backoff(Duration::from_millis(1)))
+            .collect()
+            .await
+            .unwrap();
+
+        assert_eq!(response.text, "tok tok tok ");
+        assert_eq!(server.await.unwrap().len(), 3);
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn held_stream_events_reach_callbacks_only_when_released() {
+        let tokens = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = tokens.clone();
+        let on_token: TokenCallback = Box::new(move |t| seen.lock().unwrap().push(t.to_string()));
+        let callbacks = StreamRoundCallbacks {
+            on_token: Some(Arc::new(std::sync::Mutex::new(on_token))),
+            ..StreamRoundCallbacks::default()
+        };
+        let stream = |held: &HeldStreamEvents, tokens: &[&str]| {
+            let recording = held.recording(&callbacks);
+            let mut on_token = recording.on_token.as_ref().unwrap().lock().unwrap();
+            for token in tokens {
+                on_token(token);
+            }
+        };
+
+        // A failed attempt's tokens are dropped with it
+        stream(&HeldStreamEvents::default(), &["lost "]);
+        assert!(tokens.lock().unwrap().is_empty());
+
+        let succeeded = HeldStreamEvents::default();
+        stream(&succeeded, &["tok ", "tok "]);
+        assert!(tokens.lock().unwrap().is_empty());
+        succeeded.release(&callbacks).await;
+        assert_eq!(*tokens.lock().unwrap(), ["tok ", "tok "]);
+    }
+
+    #[tokio::test]
+    async fn retry_gives_up_on_non_retriable_error() {
+        let (base_url, server) = serve_responses(vec![Err(400)]).await;
+        let runtime = launch_against(base_url).await;
+
+        let error = runtime
+            .prompt("Say hello")
+            .retry(RetryPolicy::new(3).with_initial_backoff(Duration::from_millis(1)))
+            .collect()
+            .await
+            .unwrap_err();
+
+        assert!(error.to_string().contains("400"), "{error}");
+        assert_eq!(server.await.unwrap().len(), 1);
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn provider_retry_policy_applies_to_prompts() {
+        let (base_url, server) = serve_responses(vec![Err(503), Ok(1)]).await;
+        let runtime = ActonAI::builder()
+            .app_name("prompt-test")
+            .provider(
+                crate::llm::ProviderConfig::openai_compatible(base_url, "test-model")
+                    .with_retry_policy(
+                        RetryPolicy::new(2).with_initial_backoff(Duration::from_millis(1)),
+                    ),
+            )
+            .launch()
+            .await
+            .unwrap();
+
+        let response = runtime.prompt("Say hello").collect().await.unwrap();
+
+        assert_eq!(response.text, "tok ");
+        assert_eq!(server.await.unwrap().len(), 2);
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn timeout_cancels_slow_prompt() {
+        let (base_url, _server) = serve_responses(vec![Ok(1000)]).await;
+        let runtime = launch_against(base_url).await;
+
+        let started = Instant::now();
+        let error = runtime
+            .prompt("Write an essay")
+            .timeout(Duration::from_millis(100))
+            .collect()
+            .await
+            .unwrap_err();
+
+        assert!(error.is_timeout(), "{error}");
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cancellation_aborts_slow_prompt() {
+        let (base_url, _server) = serve_responses(vec![Ok(1000)]).await;
+        let runtime = launch_against(base_url).await;
+
+        let (prompt, cancel) = runtime.prompt("Write an essay").with_cancellation();
+        let started = Instant::now();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            cancel.cancel();
+        });
+        let error = prompt.collect().await.unwrap_err();
+
+        assert!(error.is_cancelled(), "{error}");
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cancelled_token_stops_prompt_before_it_starts() {
+        let (base_url, _server) = serve_responses(vec![Ok(0)]).await;
+        let runtime = launch_against(base_url).await;
+
+        let token = CancellationToken::new();
+        token.cancel();
+        let error = runtime
+            .prompt("Hi")
+            .cancel_on(token)
+            .collect()
+            .await
+            .unwrap_err();
+
+        assert!(error.is_cancelled(), "{error}");
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn round_timeout_cancels_slow_round() {
+        let (base_url, _server) = serve_responses(vec![Ok(1000)]).await;
+        let runtime = launch_against(base_url).await;
+
+        let error = runtime
+            .prompt("Write an essay")
+            .round_timeout(Duration::from_millis(100))
+            .collect()
+            .await
+            .unwrap_err();
+
+        assert!(error.is_timeout(), "{error}");
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn provider_prompt_timeout_applies_to_prompts() {
+        let (base_url, _server) = serve_responses(vec![Ok(1000)]).await;
+        let runtime = ActonAI::builder()
+            .app_name("prompt-test")
+            .provider(
+                crate::llm::ProviderConfig::openai_compatible(base_url, "test-model")
+                    .with_default_prompt_timeout(Duration::from_millis(100)),
+            )
+            .launch()
+            .await
+            .unwrap();
+
+        let error = runtime
+            .prompt("Write an essay")
+            .collect()
+            .await
+            .unwrap_err();
+
+        assert!(error.is_timeout(), "{error}");
+
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn session_recovers_after_timed_out_round() {
+        let (base_url, _server) = serve_responses(vec![Ok(1000), Ok(2)]).await;
+        let runtime = launch_against(base_url).await;
+        let session = build_stream_collector(&runtime).await;
+
+        let error = runtime
+            .prompt("Write an essay")
+            .timeout(Duration::from_millis(100))
+            .collect_with_session(&session)
+            .await
+            .unwrap_err();
+        assert!(error.is_timeout(), "{error}");
+
+        let response = runtime
+            .prompt("Say hi")
+            .collect_with_session(&session)
+            .await
+            .unwrap();
+        assert_eq!(response.text, "tok tok ");
+
+        session.shutdown().await;
+        runtime.shutdown().await.unwrap();
+    }
+
+    #[test]
+    fn template_substitutes_variables() {
+        let template = PromptTemplate::new("Hi {{ name }}, you are {{role?}}{{missing}}.");
+        let vars = HashMap::from([("name", "Ada")]);
+        assert_eq!(template.render(&vars), "Hi Ada, you are {{missing}}.");
+    }
+
+    #[test]
+    fn template_uses_defaults_only_when_missing() {
+        let template = PromptTemplate::new("{{tone|formal}} / {{lang|en}}");
+        let vars = HashMap::from([("lang", "fr")]);
+        assert_eq!(template.render(&vars), "formal / fr");
+    }
+
+    #[test]
+    fn template_conditionals_nest_and_skip_empty_values() {
+        let template =
+            PromptTemplate::new("A{{#if x}}B{{#if y}}C{{/if}}D{{/if}}E{{#if empty}}F{{/if}}G");
+        let vars = HashMap::from([("x", "1"), ("empty", "")]);
+        assert_eq!(template.render(&vars), "ABDEG");
+
+        let vars = HashMap::from([("x", "1"), ("y", "1")]);
+        assert_eq!(template.render(&vars), "ABCDEG");
+    }
+
+    #[test]
+    fn template_values_are_not_reexpanded() {
+        let template = PromptTemplate::new("{{a}} {{unclosed");
+        let vars = HashMap::from([("a", "{{b}}"), ("b", "no")]);
+        assert_eq!(template.render(&vars), "{{b}} {{unclosed");
+    }
+
+    #[test]
+    fn template_library_loads_toml_files_in_name_order() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("a.toml"),
+            "[greet]\ntemplate = \"Hello\"\n\n[review]\ntemplate = \"Review {{code}}\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("b.toml"), "[greet]\ntemplate = \"Hi\"\n").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "not a template").unwrap();
+
+        let library = TemplateLibrary::load_dir(dir.path()).unwrap();
+        assert_eq!(library.names(), ["greet", "review"]);
+        assert_eq!(library.get("greet").unwrap().source(), "Hi");
+
+        std::fs::write(dir.path().join("c.toml"), "greet = 1\n").unwrap();
+        assert!(TemplateLibrary::load_dir(dir.path())
+            .unwrap_err()
+            .is_configuration());
+    }
 }
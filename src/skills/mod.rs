@@ -22,9 +22,24 @@
 //!     println!("Instructions: {}", skill.instructions());
 //! }
 //! ```
+//!
+//! ## Hot Reloading
+//!
+//! A registry shared behind an `Arc` can watch a directory and reload skills
+//! as their files change, yielding a [`SkillRegistryEvent`] for each change:
+//!
+//! ```rust,ignore
+//! use futures::StreamExt;
+//!
+//! let registry = Arc::new(SkillRegistry::from_paths(&[Path::new("./skills")]).await?);
+//! let mut events = Box::pin(registry.watch_directory(Path::new("./skills")));
+//! while let Some(event) = events.next().await {
+//!     println!("{event:?}");
+//! }
+//! ```
 
 mod registry;
 mod types;
 
-pub use registry::SkillRegistry;
-pub use types::{LoadedSkill, SkillInfo, SkillsError};
+pub use registry::{SkillRegistry, SkillRegistryEvent};
+pub use types::{LoadedSkill, SkillInfo, SkillParameter, SkillsError};
@@ -1,30 +1,77 @@
 //! Skill registry for loading and managing skills.
 //!
 //! Wraps the `agent-skills` crate to provide skill loading and querying.
+//! A registry shared behind an `Arc` can watch a directory and reload
+//! skills as their files change.
 
-use super::types::{LoadedSkill, SkillInfo, SkillsError};
+use super::types::{LoadedSkill, SkillInfo, SkillParameter, SkillsError};
 use acton_reactive::prelude::tokio;
 use agent_skills::Skill;
-use std::collections::HashMap;
-use std::path::Path;
+use futures::Stream;
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::{Duration, SystemTime};
+
+/// How often [`SkillRegistry::watch_directory`] rescans the directory.
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A change applied to a [`SkillRegistry`] by a directory watch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
+pub enum SkillRegistryEvent {
+    /// A skill file was created or modified and the skill was (re)loaded
+    SkillReloaded {
+        /// Name of the loaded skill
+        name: String,
+    },
+    /// A skill was removed because its file was deleted or renamed it
+    SkillRemoved {
+        /// Name of the removed skill
+        name: String,
+    },
+    /// A skill file or the watched directory could not be read or parsed
+    SkillWatchError {
+        /// The file or directory that failed
+        path: PathBuf,
+        /// Description of the failure
+        error: String,
+    },
+}
+
+#[derive(Debug, Default)]
+struct RegistryState {
+    /// Skills indexed by name
+    skills: HashMap<String, LoadedSkill>,
+    /// When each skill was last loaded
+    loaded_at: HashMap<String, SystemTime>,
+}
 
 /// Registry of loaded skills.
 ///
 /// Provides methods to load skills from paths, list available skills,
-/// and retrieve skills by name or trigger patterns.
+/// and retrieve skills by name or trigger patterns. Lookups return clones,
+/// so a registry shared behind an `Arc` can be updated in place by
+/// [`watch_directory`](Self::watch_directory).
 #[derive(Debug, Default)]
 pub struct SkillRegistry {
-    /// Skills indexed by name
-    skills: HashMap<String, LoadedSkill>,
+    state: RwLock<RegistryState>,
 }
 
 impl SkillRegistry {
     /// Creates a new empty skill registry.
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            skills: HashMap::new(),
-        }
+        Self::default()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, RegistryState> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, RegistryState> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
     }
 
     /// Loads skills from the given paths.
@@ -45,7 +92,7 @@ impl SkillRegistry {
     /// Returns an error if any path doesn't exist. Individual skill load
     /// failures are logged but don't prevent other skills from loading.
     pub async fn from_paths(paths: &[&Path]) -> Result<Self, SkillsError> {
-        let mut registry = Self::new();
+        let registry = Self::new();
 
         for path in paths {
             if !path.exists() {
@@ -67,32 +114,15 @@ impl SkillRegistry {
     }
 
     /// Loads a single skill file.
-    async fn load_skill_file(&mut self, path: &Path) -> Result<(), SkillsError> {
-        // Read the file content
-        let content =
-            tokio::fs::read_to_string(path)
-                .await
-                .map_err(|e| SkillsError::LoadFailed {
-                    path: path.to_path_buf(),
-                    reason: e.to_string(),
-                })?;
-
-        // Parse using agent-skills crate
-        let skill = Skill::parse(&content).map_err(|e| SkillsError::InvalidFormat {
-            path: path.to_path_buf(),
-            reason: e.to_string(),
-        })?;
-
-        // Convert to our LoadedSkill type
-        let loaded = convert_skill(skill, path);
+    async fn load_skill_file(&self, path: &Path) -> Result<(), SkillsError> {
+        let loaded = parse_skill_file(path).await?;
         tracing::debug!(
             name = %loaded.info.name,
             description = %loaded.info.description,
             path = %path.display(),
             "skill loaded",
         );
-        self.skills.insert(loaded.info.name.clone(), loaded);
-
+        self.add(loaded);
         Ok(())
     }
 
@@ -108,7 +138,7 @@ impl SkillRegistry {
     /// like `~/.claude/skills/` or a flat collection of `.md` files), the
     /// scanner loads top-level `.md` files as bare skills and recurses into
     /// non-hidden subdirectories.
-    async fn load_skill_directory(&mut self, dir: &Path) -> Result<(), SkillsError> {
+    async fn load_skill_directory(&self, dir: &Path) -> Result<(), SkillsError> {
         // Skill package: <dir>/SKILL.md present → load just that, don't recurse.
         let skill_md = dir.join("SKILL.md");
         if tokio::fs::metadata(&skill_md)
@@ -162,63 +192,337 @@ impl SkillRegistry {
     /// Returns the number of loaded skills.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.skills.len()
+        self.read().skills.len()
     }
 
     /// Returns true if no skills are loaded.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.skills.is_empty()
+        self.read().skills.is_empty()
     }
 
     /// Lists all loaded skills (metadata only).
     #[must_use]
     pub fn list(&self) -> Vec<SkillInfo> {
-        self.skills.values().map(|s| s.info.clone()).collect()
+        self.read()
+            .skills
+            .values()
+            .map(|s| s.info.clone())
+            .collect()
     }
 
     /// Gets a skill by name.
     #[must_use]
-    pub fn get(&self, name: &str) -> Option<&LoadedSkill> {
-        self.skills.get(name)
+    pub fn get(&self, name: &str) -> Option<LoadedSkill> {
+        self.read().skills.get(name).cloned()
     }
 
     /// Gets skills that match the given trigger text.
     #[must_use]
-    pub fn find_by_trigger(&self, text: &str) -> Vec<&LoadedSkill> {
-        self.skills
+    pub fn find_by_trigger(&self, text: &str) -> Vec<LoadedSkill> {
+        self.read()
+            .skills
             .values()
             .filter(|s| s.matches_trigger(text))
+            .cloned()
             .collect()
     }
 
     /// Gets all skills that are enabled by default.
     #[must_use]
-    pub fn default_skills(&self) -> Vec<&LoadedSkill> {
-        self.skills
+    pub fn default_skills(&self) -> Vec<LoadedSkill> {
+        self.read()
+            .skills
             .values()
             .filter(|s| s.enabled_by_default)
+            .cloned()
             .collect()
     }
 
-    /// Returns an iterator over all loaded skills.
-    pub fn iter(&self) -> impl Iterator<Item = &LoadedSkill> {
-        self.skills.values()
+    /// Returns an iterator over a snapshot of all loaded skills.
+    pub fn iter(&self) -> impl Iterator<Item = LoadedSkill> {
+        let skills: Vec<LoadedSkill> = self.read().skills.values().cloned().collect();
+        skills.into_iter()
+    }
+
+    /// Returns when the skill `name` was last successfully loaded.
+    #[must_use]
+    pub fn last_loaded_at(&self, name: &str) -> Option<SystemTime> {
+        self.read().loaded_at.get(name).copied()
     }
 
-    /// Adds a skill to the registry.
-    pub fn add(&mut self, skill: LoadedSkill) {
-        self.skills.insert(skill.info.name.clone(), skill);
+    /// Adds a skill to the registry, replacing any skill with the same name.
+    pub fn add(&self, skill: LoadedSkill) {
+        let mut state = self.write();
+        let name = skill.info.name.clone();
+        state.loaded_at.insert(name.clone(), SystemTime::now());
+        state.skills.insert(name, skill);
     }
 
     /// Removes a skill from the registry by name.
-    pub fn remove(&mut self, name: &str) -> Option<LoadedSkill> {
-        self.skills.remove(name)
+    pub fn remove(&self, name: &str) -> Option<LoadedSkill> {
+        let mut state = self.write();
+        state.loaded_at.remove(name);
+        state.skills.remove(name)
+    }
+
+    /// Names of the skills loaded from `path`.
+    fn names_loaded_from(&self, path: &Path) -> Vec<String> {
+        self.read()
+            .skills
+            .values()
+            .filter(|s| s.info.path == path)
+            .map(|s| s.info.name.clone())
+            .collect()
+    }
+
+    /// Watches `dir` for changes to skill files and applies them to the
+    /// registry.
+    ///
+    /// The directory is rescanned every 500 ms using the same layout rules as
+    /// [`from_paths`](Self::from_paths). A created or modified `.md` file is
+    /// re-parsed and replaces the skill of the same name; a deleted file
+    /// removes the skills loaded from it. Skills are matched to files by
+    /// path, so `dir` should be the path the skills were loaded from. Every
+    /// change is yielded as a [`SkillRegistryEvent`]; watching stops when the
+    /// stream is dropped.
+    ///
+    /// Tools holding the same `Arc<SkillRegistry>`, such as
+    /// [`ActivateSkillTool`](crate::tools::builtins::ActivateSkillTool), see
+    /// the changes on their next lookup.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use futures::StreamExt;
+    ///
+    /// let registry = Arc::new(SkillRegistry::from_paths(&[Path::new("./skills")]).await?);
+    /// let mut events = Box::pin(registry.watch_directory(Path::new("./skills")));
+    /// while let Some(event) = events.next().await {
+    ///     println!("{event:?}");
+    /// }
+    /// ```
+    pub fn watch_directory(
+        self: &Arc<Self>,
+        dir: &Path,
+    ) -> impl Stream<Item = SkillRegistryEvent> + Send + 'static {
+        self.watch_directory_every(dir, WATCH_POLL_INTERVAL)
+    }
+
+    fn watch_directory_every(
+        self: &Arc<Self>,
+        dir: &Path,
+        interval: Duration,
+    ) -> impl Stream<Item = SkillRegistryEvent> + Send + 'static {
+        let mut pending = VecDeque::new();
+        let snapshot = match scan_skill_files(dir) {
+            Ok(files) => files,
+            Err(e) => {
+                pending.push_back(SkillRegistryEvent::SkillWatchError {
+                    path: dir.to_path_buf(),
+                    error: e.to_string(),
+                });
+                HashMap::new()
+            }
+        };
+        let watch = DirectoryWatch {
+            registry: Arc::clone(self),
+            dir: dir.to_path_buf(),
+            interval,
+            dir_failed: !pending.is_empty(),
+            snapshot,
+            pending,
+        };
+
+        futures::stream::unfold(watch, |mut watch| async move {
+            loop {
+                if let Some(event) = watch.pending.pop_front() {
+                    return Some((event, watch));
+                }
+                tokio::time::sleep(watch.interval).await;
+                watch.poll().await;
+            }
+        })
+    }
+}
+
+/// File fingerprint used to detect modifications.
+type FileStamp = (SystemTime, u64);
+
+/// State of one [`SkillRegistry::watch_directory`] stream.
+struct DirectoryWatch {
+    registry: Arc<SkillRegistry>,
+    dir: PathBuf,
+    interval: Duration,
+    /// Whether the last scan of `dir` failed, so the error is reported once
+    dir_failed: bool,
+    snapshot: HashMap<PathBuf, FileStamp>,
+    pending: VecDeque<SkillRegistryEvent>,
+}
+
+impl DirectoryWatch {
+    /// Rescans the directory and queues an event for every change applied.
+    async fn poll(&mut self) {
+        let files = match scan_skill_files(&self.dir) {
+            Ok(files) => files,
+            Err(e) => {
+                if !self.dir_failed {
+                    self.dir_failed = true;
+                    self.pending.push_back(SkillRegistryEvent::SkillWatchError {
+                        path: self.dir.clone(),
+                        error: e.to_string(),
+                    });
+                }
+                return;
+            }
+        };
+        self.dir_failed = false;
+
+        for path in self.snapshot.keys() {
+            if !files.contains_key(path) {
+                for name in self.registry.names_loaded_from(path) {
+                    self.registry.remove(&name);
+                    tracing::info!(name = %name, path = %path.display(), "skill removed");
+                    self.pending
+                        .push_back(SkillRegistryEvent::SkillRemoved { name });
+                }
+            }
+        }
+
+        for (path, stamp) in &files {
+            if self.snapshot.get(path) == Some(stamp) {
+                continue;
+            }
+            match parse_skill_file(path).await {
+                Ok(skill) => {
+                    let name = skill.info.name.clone();
+                    // The file may have been edited to rename its skill
+                    for old in self.registry.names_loaded_from(path) {
+                        if old != name {
+                            self.registry.remove(&old);
+                            self.pending
+                                .push_back(SkillRegistryEvent::SkillRemoved { name: old });
+                        }
+                    }
+                    self.registry.add(skill);
+                    tracing::info!(name = %name, path = %path.display(), "skill reloaded");
+                    self.pending
+                        .push_back(SkillRegistryEvent::SkillReloaded { name });
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to reload skill");
+                    self.pending.push_back(SkillRegistryEvent::SkillWatchError {
+                        path: path.clone(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        self.snapshot = files;
+    }
+}
+
+/// Lists the skill files under `dir` with their modification stamps,
+/// following the layout rules of `SkillRegistry::load_skill_directory`.
+fn scan_skill_files(dir: &Path) -> std::io::Result<HashMap<PathBuf, FileStamp>> {
+    fn stamp(path: &Path) -> Option<FileStamp> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
     }
+
+    fn scan(dir: &Path, files: &mut HashMap<PathBuf, FileStamp>) -> std::io::Result<()> {
+        let skill_md = dir.join("SKILL.md");
+        if skill_md.is_file() {
+            if let Some(stamp) = stamp(&skill_md) {
+                files.insert(skill_md, stamp);
+            }
+            return Ok(());
+        }
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                if path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with('.'))
+                {
+                    continue;
+                }
+                // A subdirectory that vanishes mid-scan is picked up next time
+                let _ = scan(&path, files);
+            } else if path.extension().is_some_and(|ext| ext == "md") {
+                if let Some(stamp) = stamp(&path) {
+                    files.insert(path, stamp);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = HashMap::new();
+    scan(dir, &mut files)?;
+    Ok(files)
+}
+
+/// Reads and parses a skill file.
+async fn parse_skill_file(path: &Path) -> Result<LoadedSkill, SkillsError> {
+    // Read the file content
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| SkillsError::LoadFailed {
+            path: path.to_path_buf(),
+            reason: e.to_string(),
+        })?;
+
+    // Parse using agent-skills crate
+    let skill = Skill::parse(&content).map_err(|e| SkillsError::InvalidFormat {
+        path: path.to_path_buf(),
+        reason: e.to_string(),
+    })?;
+
+    // agent-skills ignores fields outside its spec, so parameters are read separately
+    let parameters = parse_parameters(&content).map_err(|e| SkillsError::InvalidFormat {
+        path: path.to_path_buf(),
+        reason: format!("invalid parameters: {e}"),
+    })?;
+
+    // Convert to our LoadedSkill type
+    Ok(convert_skill(skill, path, parameters))
+}
+
+/// Frontmatter fields that `agent-skills` does not parse.
+#[derive(Deserialize)]
+struct ExtendedFrontmatter {
+    #[serde(default)]
+    parameters: Vec<SkillParameter>,
+}
+
+/// Reads the `parameters:` section of a skill's frontmatter.
+fn parse_parameters(content: &str) -> Result<Vec<SkillParameter>, serde_yaml::Error> {
+    let Some(yaml) = frontmatter_yaml(content) else {
+        return Ok(Vec::new());
+    };
+    let extended: ExtendedFrontmatter = serde_yaml::from_str(yaml)?;
+    Ok(extended.parameters)
+}
+
+/// Returns the YAML between the opening and closing `---` lines.
+fn frontmatter_yaml(content: &str) -> Option<&str> {
+    let rest = content.trim_start().strip_prefix("---")?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']) == "---" {
+            return Some(&rest[..offset]);
+        }
+        offset += line.len();
+    }
+    None
 }
 
 /// Converts an agent_skills::Skill to our LoadedSkill type.
-fn convert_skill(skill: Skill, path: &Path) -> LoadedSkill {
+fn convert_skill(skill: Skill, path: &Path, parameters: Vec<SkillParameter>) -> LoadedSkill {
     let frontmatter = skill.frontmatter();
 
     // Get name from frontmatter
@@ -253,6 +557,7 @@ fn convert_skill(skill: Skill, path: &Path) -> LoadedSkill {
             description,
             path: path.to_path_buf(),
             tags,
+            parameters,
         },
         content: skill.body().to_string(),
         triggers,
@@ -263,6 +568,7 @@ fn convert_skill(skill: Skill, path: &Path) -> LoadedSkill {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use futures::StreamExt;
     use std::io::Write;
     use tempfile::TempDir;
 
@@ -304,6 +610,33 @@ This is the skill content.
         assert!(skill.enabled_by_default);
     }
 
+    #[tokio::test]
+    async fn load_skill_parameters_from_frontmatter() {
+        let dir = TempDir::new().unwrap();
+        let skill_content = r#"---
+name: code-review
+description: Review code
+parameters:
+  - name: language
+    description: Language under review
+    required: true
+  - name: style
+    default: concise
+---
+Review this {{language}} code.
+"#;
+        create_test_skill(dir.path(), "code-review", skill_content).await;
+
+        let registry = SkillRegistry::from_paths(&[dir.path()]).await.unwrap();
+        let parameters = registry.get("code-review").unwrap().info.parameters;
+
+        assert_eq!(parameters.len(), 2);
+        assert_eq!(parameters[0].name, "language");
+        assert!(parameters[0].required);
+        assert_eq!(parameters[1].default.as_deref(), Some("concise"));
+        assert!(!parameters[1].required);
+    }
+
     #[tokio::test]
     async fn load_skills_from_directory() {
         let dir = TempDir::new().unwrap();
@@ -402,9 +735,8 @@ Email body content
         for name in ["email", "bash"] {
             let pkg = dir.path().join(name);
             std::fs::create_dir_all(&pkg).unwrap();
-            let skill = format!(
-                "---\nname: {name}\ndescription: {name} skill\n---\n{name} body\n",
-            );
+            let skill =
+                format!("---\nname: {name}\ndescription: {name} skill\n---\n{name} body\n",);
             std::fs::write(pkg.join("SKILL.md"), skill).unwrap();
         }
 
@@ -438,7 +770,7 @@ Email body content
 
     #[test]
     fn add_and_remove_skill() {
-        let mut registry = SkillRegistry::new();
+        let registry = SkillRegistry::new();
 
         let skill = LoadedSkill {
             info: SkillInfo {
@@ -446,6 +778,7 @@ Email body content
                 description: "Added manually".to_string(),
                 path: std::path::PathBuf::from("/fake/path.md"),
                 tags: vec![],
+                parameters: vec![],
             },
             content: "Manual content".to_string(),
             triggers: vec![],
@@ -460,4 +793,62 @@ Email body content
         assert!(removed.is_some());
         assert!(registry.is_empty());
     }
+
+    async fn next_event(
+        events: &mut (impl Stream<Item = SkillRegistryEvent> + Unpin),
+    ) -> SkillRegistryEvent {
+        tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .expect("watch event")
+            .expect("stream open")
+    }
+
+    #[tokio::test]
+    async fn watch_directory_reloads_and_removes_skills() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("watched.md");
+        std::fs::write(&path, "---\nname: watched\ndescription: v1\n---\nOne\n").unwrap();
+
+        let registry = Arc::new(SkillRegistry::from_paths(&[dir.path()]).await.unwrap());
+        let first_load = registry.last_loaded_at("watched").unwrap();
+        let mut events =
+            Box::pin(registry.watch_directory_every(dir.path(), Duration::from_millis(20)));
+
+        std::fs::write(
+            &path,
+            "---\nname: watched\ndescription: version two\n---\nTwo\n",
+        )
+        .unwrap();
+        assert_eq!(
+            next_event(&mut events).await,
+            SkillRegistryEvent::SkillReloaded {
+                name: "watched".to_string()
+            }
+        );
+        assert_eq!(
+            registry.get("watched").unwrap().description(),
+            "version two"
+        );
+        assert!(registry.last_loaded_at("watched").unwrap() >= first_load);
+
+        std::fs::write(&path, "no frontmatter at all").unwrap();
+        assert!(matches!(
+            next_event(&mut events).await,
+            SkillRegistryEvent::SkillWatchError { path: p, .. } if p == path
+        ));
+        assert_eq!(
+            registry.get("watched").unwrap().description(),
+            "version two"
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            next_event(&mut events).await,
+            SkillRegistryEvent::SkillRemoved {
+                name: "watched".to_string()
+            }
+        );
+        assert!(registry.is_empty());
+        assert!(registry.last_loaded_at("watched").is_none());
+    }
 }
@@ -2,9 +2,38 @@
 //!
 //! Defines types for representing loaded skills and skill metadata.
 
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use std::path::PathBuf;
 
+/// A runtime parameter of a skill, declared in its frontmatter.
+///
+/// ```yaml
+/// parameters:
+///   - name: language
+///     description: Language of the code under review
+///     required: true
+///   - name: style
+///     default: concise
+/// ```
+///
+/// The skill's instructions refer to the parameter as `{{language}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillParameter {
+    /// Name used in `{{name}}` placeholders
+    pub name: String,
+    /// What the parameter controls
+    #[serde(default)]
+    pub description: String,
+    /// Whether activation fails without a value
+    #[serde(default)]
+    pub required: bool,
+    /// Value used when none is provided
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
 /// Information about a skill (without full content).
 ///
 /// Used for listing and querying skills without loading full content.
@@ -18,6 +47,8 @@ pub struct SkillInfo {
     pub path: PathBuf,
     /// Optional tags/categories for the skill
     pub tags: Vec<String>,
+    /// Runtime parameters substituted into the instructions
+    pub parameters: Vec<SkillParameter>,
 }
 
 /// A fully loaded skill with content.
@@ -68,6 +99,56 @@ impl LoadedSkill {
             .iter()
             .any(|t| text_lower.contains(&t.to_lowercase()))
     }
+
+    /// Checks that `params` provides every required parameter that has no
+    /// default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SkillsError::MissingParameter`] for the first missing one.
+    pub fn validate_params(&self, params: &HashMap<String, String>) -> Result<(), SkillsError> {
+        match self
+            .info
+            .parameters
+            .iter()
+            .find(|p| p.required && p.default.is_none() && !params.contains_key(&p.name))
+        {
+            Some(missing) => Err(SkillsError::MissingParameter {
+                skill: self.info.name.clone(),
+                parameter: missing.name.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the instructions with `{{name}}` placeholders replaced.
+    ///
+    /// Each placeholder takes its value from `params`, then from the declared
+    /// default; an optional parameter without either becomes empty.
+    /// Placeholders that are neither declared nor provided are left as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SkillsError::MissingParameter`] if a required parameter
+    /// has no value.
+    pub fn instructions_with_params(
+        &self,
+        params: &HashMap<String, String>,
+    ) -> Result<String, SkillsError> {
+        self.validate_params(params)?;
+
+        let mut instructions = self.content.clone();
+        for (name, value) in params {
+            instructions = instructions.replace(&format!("{{{{{name}}}}}"), value);
+        }
+        for parameter in &self.info.parameters {
+            if !params.contains_key(&parameter.name) {
+                let value = parameter.default.as_deref().unwrap_or_default();
+                instructions = instructions.replace(&format!("{{{{{}}}}}", parameter.name), value);
+            }
+        }
+        Ok(instructions)
+    }
 }
 
 /// Errors that can occur when working with skills.
@@ -97,6 +178,13 @@ pub enum SkillsError {
         /// Path that was not found
         path: PathBuf,
     },
+    /// A required skill parameter was not provided
+    MissingParameter {
+        /// Name of the skill being activated
+        skill: String,
+        /// Name of the missing parameter
+        parameter: String,
+    },
 }
 
 impl fmt::Display for SkillsError {
@@ -119,6 +207,13 @@ impl fmt::Display for SkillsError {
             Self::PathNotFound { path } => {
                 write!(f, "skill path not found: {}", path.display())
             }
+            Self::MissingParameter { skill, parameter } => {
+                write!(
+                    f,
+                    "skill '{}' requires parameter '{}' but none was provided",
+                    skill, parameter
+                )
+            }
         }
     }
 }
@@ -136,6 +231,7 @@ mod tests {
             description: "A test skill".to_string(),
             path: PathBuf::from("/path/to/skill.md"),
             tags: vec!["test".to_string(), "example".to_string()],
+            parameters: vec![],
         };
 
         assert_eq!(info.name, "test-skill");
@@ -151,6 +247,7 @@ mod tests {
                 description: "Review code changes".to_string(),
                 path: PathBuf::from("/skills/code-review.md"),
                 tags: vec!["code".to_string()],
+                parameters: vec![],
             },
             content: "# Code Review Instructions\n\nReview the code...".to_string(),
             triggers: vec!["review".to_string(), "code review".to_string()],
@@ -173,6 +270,7 @@ mod tests {
                 description: "Basic skill".to_string(),
                 path: PathBuf::from("/skills/basic.md"),
                 tags: vec![],
+                parameters: vec![],
             },
             content: "Instructions".to_string(),
             triggers: vec![],
@@ -183,6 +281,51 @@ mod tests {
         assert!(!skill.matches_trigger("anything"));
     }
 
+    #[test]
+    fn instructions_with_params_substitutes_placeholders() {
+        let skill = LoadedSkill {
+            info: SkillInfo {
+                name: "code-review".to_string(),
+                description: "Review code".to_string(),
+                path: PathBuf::from("/skills/code-review.md"),
+                tags: vec![],
+                parameters: vec![
+                    SkillParameter {
+                        name: "language".to_string(),
+                        description: "Language under review".to_string(),
+                        required: true,
+                        default: None,
+                    },
+                    SkillParameter {
+                        name: "style".to_string(),
+                        description: String::new(),
+                        required: false,
+                        default: Some("concise".to_string()),
+                    },
+                ],
+            },
+            content: "Review this {{language}} code. Be {{style}}. {{unknown}}".to_string(),
+            triggers: vec![],
+            enabled_by_default: false,
+        };
+
+        let params = HashMap::from([("language".to_string(), "rust".to_string())]);
+        assert_eq!(
+            skill.instructions_with_params(&params).unwrap(),
+            "Review this rust code. Be concise. {{unknown}}"
+        );
+
+        let err = skill.instructions_with_params(&HashMap::new()).unwrap_err();
+        assert_eq!(
+            err,
+            SkillsError::MissingParameter {
+                skill: "code-review".to_string(),
+                parameter: "language".to_string(),
+            }
+        );
+        assert!(err.to_string().contains("'language'"));
+    }
+
     #[test]
     fn skills_error_display() {
         let err = SkillsError::LoadFailed {
@@ -27,6 +27,7 @@
 //! }
 //! ```
 
+use crate::llm::TokenUsage;
 use crate::messages::StopReason;
 use crate::types::CorrelationId;
 
@@ -240,6 +241,22 @@ pub struct CollectedResponse {
     /// This is populated when tools were used and contains all tool calls
     /// that were executed during the conversation loop.
     pub tool_calls: Vec<ExecutedToolCall>,
+
+    /// Whether the response was requested as JSON.
+    ///
+    /// Set when the prompt used `PromptBuilder::output_schema()`; parse the
+    /// text with [`as_typed`](Self::as_typed).
+    pub is_json: bool,
+
+    /// The model's extended thinking trace, if thinking was enabled.
+    ///
+    /// Kept apart from `text` and not added to conversation history.
+    pub thinking: Option<String>,
+
+    /// Token usage reported by the provider, summed across tool rounds.
+    ///
+    /// `None` when the provider reported no usage.
+    pub token_usage: Option<TokenUsage>,
 }
 
 impl CollectedResponse {
@@ -251,6 +268,9 @@ impl CollectedResponse {
             stop_reason,
             token_count,
             tool_calls: Vec::new(),
+            is_json: false,
+            thinking: None,
+            token_usage: None,
         }
     }
 
@@ -267,6 +287,9 @@ impl CollectedResponse {
             stop_reason,
             token_count,
             tool_calls,
+            is_json: false,
+            thinking: None,
+            token_usage: None,
         }
     }
 
@@ -293,6 +316,122 @@ impl CollectedResponse {
     pub fn needs_tool_call(&self) -> bool {
         matches!(self.stop_reason, StopReason::ToolUse)
     }
+
+    /// Deserializes the response text as JSON.
+    ///
+    /// Pair with `PromptBuilder::output_schema()` so the model answers in
+    /// JSON matching `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text is not valid JSON for `T`.
+    pub fn as_typed<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(self.text.trim())
+    }
+
+    /// Returns the number of whitespace-separated words in the text.
+    #[must_use]
+    pub fn word_count(&self) -> usize {
+        self.text.split_whitespace().count()
+    }
+
+    /// Returns the number of Unicode scalar values in the text.
+    #[must_use]
+    pub fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Returns the number of sentences in the text.
+    ///
+    /// A sentence ends at a `.`, `!`, or `?` followed by whitespace or the
+    /// end of the text, so `"Really?!"` and `"Wait..."` count once.
+    #[must_use]
+    pub fn sentence_count(&self) -> usize {
+        let mut chars = self.text.chars().peekable();
+        let mut count = 0;
+        while let Some(c) = chars.next() {
+            if matches!(c, '.' | '!' | '?') && chars.peek().is_none_or(|next| next.is_whitespace())
+            {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Estimates how long the text takes to read at `words_per_minute`.
+    ///
+    /// A rate of 0 is treated as 1.
+    #[must_use]
+    pub fn reading_time_seconds(&self, words_per_minute: u32) -> f64 {
+        self.word_count() as f64 * 60.0 / f64::from(words_per_minute.max(1))
+    }
+
+    /// Returns true if the text contains a triple-backtick code fence.
+    #[must_use]
+    pub fn contains_code_block(&self) -> bool {
+        self.text.contains("```")
+    }
+
+    /// Extracts the fenced Markdown code blocks of the text, in order.
+    ///
+    /// A block left open at the end of the text (e.g., in a truncated
+    /// response) is included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use acton_ai::stream::CollectedResponse;
+    /// use acton_ai::messages::StopReason;
+    ///
+    /// let response = CollectedResponse::new(
+    ///     "Try this:\n```rust\nfn main() {}\n```".to_string(),
+    ///     StopReason::EndTurn,
+    ///     0,
+    /// );
+    /// let blocks = response.extract_code_blocks();
+    /// assert_eq!(blocks[0].language.as_deref(), Some("rust"));
+    /// assert_eq!(blocks[0].code, "fn main() {}");
+    /// ```
+    #[must_use]
+    pub fn extract_code_blocks(&self) -> Vec<CodeBlock> {
+        let mut blocks = Vec::new();
+        let mut open: Option<(Option<String>, Vec<&str>)> = None;
+        for line in self.text.lines() {
+            let fence = line.trim_start().strip_prefix("```");
+            match (&mut open, fence) {
+                (None, Some(info)) => {
+                    let language = info.split_whitespace().next().map(str::to_string);
+                    open = Some((language, Vec::new()));
+                }
+                (Some(_), Some(rest)) if rest.trim().is_empty() => {
+                    if let Some((language, lines)) = open.take() {
+                        blocks.push(CodeBlock {
+                            language,
+                            code: lines.join("\n"),
+                        });
+                    }
+                }
+                (Some((_, lines)), _) => lines.push(line),
+                (None, None) => {}
+            }
+        }
+        if let Some((language, lines)) = open {
+            blocks.push(CodeBlock {
+                language,
+                code: lines.join("\n"),
+            });
+        }
+        blocks
+    }
+}
+
+/// A fenced code block from [`CollectedResponse::extract_code_blocks`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The language named after the opening fence, if any
+    pub language: Option<String>,
+    /// The code between the fences, without a trailing newline
+    pub code: String,
 }
 
 impl Default for CollectedResponse {
@@ -302,6 +441,9 @@ impl Default for CollectedResponse {
             stop_reason: StopReason::EndTurn,
             token_count: 0,
             tool_calls: Vec::new(),
+            is_json: false,
+            thinking: None,
+            token_usage: None,
         }
     }
 }
@@ -380,6 +522,63 @@ mod tests {
         assert_eq!(response.stop_reason, StopReason::EndTurn);
         assert_eq!(response.token_count, 0);
         assert!(response.tool_calls.is_empty());
+        assert!(!response.is_json);
+    }
+
+    #[test]
+    fn collected_response_as_typed() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Answer {
+            value: u32,
+        }
+
+        let response =
+            CollectedResponse::new(" {\"value\": 42}\n".to_string(), StopReason::EndTurn, 5);
+        assert_eq!(response.as_typed::<Answer>().unwrap(), Answer { value: 42 });
+
+        let response = CollectedResponse::new("not json".to_string(), StopReason::EndTurn, 2);
+        assert!(response.as_typed::<Answer>().is_err());
+    }
+
+    fn response(text: &str) -> CollectedResponse {
+        CollectedResponse::new(text.to_string(), StopReason::EndTurn, 0)
+    }
+
+    #[test]
+    fn collected_response_text_statistics() {
+        let r = response("Hello there. How are you?! Fine...\nThe end");
+        assert_eq!(r.word_count(), 8);
+        assert_eq!(r.sentence_count(), 3);
+        assert_eq!(response("Version 1.5 is out.").sentence_count(), 1);
+        assert_eq!(response("naïve café").char_count(), 10);
+        assert!((r.reading_time_seconds(240) - 2.0).abs() < f64::EPSILON);
+        assert!(response("").reading_time_seconds(0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn collected_response_extracts_code_blocks() {
+        let r =
+            response("Run:\n```bash\ncargo test\n```\nthen\n```\nplain\ntext\n```\n```py\nopen(");
+        assert!(r.contains_code_block());
+        assert_eq!(
+            r.extract_code_blocks(),
+            vec![
+                CodeBlock {
+                    language: Some("bash".to_string()),
+                    code: "cargo test".to_string(),
+                },
+                CodeBlock {
+                    language: None,
+                    code: "plain\ntext".to_string(),
+                },
+                CodeBlock {
+                    language: Some("py".to_string()),
+                    code: "open(".to_string(),
+                },
+            ]
+        );
+        assert!(!response("no code").contains_code_block());
+        assert!(response("no code").extract_code_blocks().is_empty());
     }
 
     #[test]
@@ -0,0 +1,92 @@
+//! Loading system prompts from files.
+//!
+//! Long system prompts are often maintained as separate text or markdown
+//! files. [`read_system_prompt_file`] reads one synchronously, subject to the
+//! default [`PathValidator`] rules, and caches the content by canonical path
+//! and modification time so repeated builds do not hit the disk.
+
+use crate::error::ActonAIError;
+use crate::tools::security::PathValidator;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::SystemTime;
+
+/// File contents keyed by canonical path, tagged with the mtime they were
+/// read at.
+type PromptCache = HashMap<PathBuf, (Option<SystemTime>, String)>;
+
+fn cache() -> &'static Mutex<PromptCache> {
+    static CACHE: OnceLock<Mutex<PromptCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Reads a system prompt from `path`.
+///
+/// The content is used verbatim, so markdown formatting is preserved.
+///
+/// # Errors
+///
+/// Returns a configuration error if `path` is rejected by the default
+/// [`PathValidator`] (outside the working and temp directories, or
+/// containing `..`, `.git`, or `.env`), is not a file, or cannot be read as
+/// UTF-8.
+pub(crate) fn read_system_prompt_file(path: &Path) -> Result<String, ActonAIError> {
+    let canonical = PathValidator::new()
+        .validate_file(path)
+        .map_err(|e| ActonAIError::configuration("system_prompt_file", e.to_string()))?;
+    let modified = std::fs::metadata(&canonical)
+        .and_then(|m| m.modified())
+        .ok();
+
+    let mut cache = cache().lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some((cached_at, content)) = cache.get(&canonical) {
+        if modified.is_some() && *cached_at == modified {
+            return Ok(content.clone());
+        }
+    }
+
+    let content = std::fs::read_to_string(&canonical).map_err(|e| {
+        ActonAIError::configuration(
+            "system_prompt_file",
+            format!("cannot read '{}': {e}", canonical.display()),
+        )
+    })?;
+    cache.insert(canonical, (modified, content.clone()));
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn reads_and_refreshes_on_change() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "# Role\n\nYou are terse.").unwrap();
+
+        let prompt = read_system_prompt_file(file.path()).unwrap();
+        assert_eq!(prompt, "# Role\n\nYou are terse.");
+
+        // Force a different mtime so the cache entry is stale
+        std::fs::write(file.path(), "You are verbose.").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        file.as_file().set_modified(later).unwrap();
+
+        assert_eq!(
+            read_system_prompt_file(file.path()).unwrap(),
+            "You are verbose."
+        );
+    }
+
+    #[test]
+    fn rejects_missing_and_disallowed_paths() {
+        let missing = std::env::temp_dir().join("acton-ai-no-such-prompt.md");
+        let err = read_system_prompt_file(&missing).unwrap_err();
+        assert!(err.is_configuration());
+
+        let err = read_system_prompt_file(Path::new("../prompt.md")).unwrap_err();
+        assert!(err.to_string().contains("denied pattern"));
+    }
+}
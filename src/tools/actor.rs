@@ -4,12 +4,20 @@
 //! Each tool becomes its own actor, supervised by the agent that uses it.
 
 use crate::messages::ToolDefinition;
+use crate::tools::cache::{
+    self, execute_cached, ResultCacheConfig, ResultCaching, ToolCacheStatus, ToolResultCache,
+};
+use crate::tools::coercion::{self, CoercionRules, InputCoercing};
 use crate::tools::error::ToolError;
+use crate::tools::rate_limit::{self, RateLimiting, ToolRateLimit, ToolRateLimiter};
+use crate::tools::replay::{self, execute_recorded, CallLogging, ToolCallLog, ToolCallRecord};
+use crate::tools::ToolExecutorTrait;
 use crate::types::CorrelationId;
 use acton_reactive::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::future::Future;
+use std::sync::Arc;
 
 /// Message to execute a tool directly (sent to individual tool actors).
 ///
@@ -54,6 +62,12 @@ pub struct ToolActorResponse {
     pub tool_call_id: String,
     /// The result of execution (success content or error message)
     pub result: Result<String, String>,
+    /// Whether the call was rejected by a rate limit instead of executing
+    #[serde(default)]
+    pub rate_limited: bool,
+    /// Result cache outcome (`None` when the tool has no cache)
+    #[serde(default)]
+    pub cache: Option<ToolCacheStatus>,
 }
 
 impl ToolActorResponse {
@@ -68,6 +82,8 @@ impl ToolActorResponse {
             correlation_id,
             tool_call_id: tool_call_id.into(),
             result: Ok(content.into()),
+            rate_limited: false,
+            cache: None,
         }
     }
 
@@ -82,8 +98,31 @@ impl ToolActorResponse {
             correlation_id,
             tool_call_id: tool_call_id.into(),
             result: Err(error.into()),
+            rate_limited: false,
+            cache: None,
         }
     }
+
+    /// Creates an error response from a [`ToolError`], flagging rate-limit
+    /// rejections.
+    #[must_use]
+    pub fn from_error(
+        correlation_id: CorrelationId,
+        tool_call_id: impl Into<String>,
+        error: &ToolError,
+    ) -> Self {
+        Self {
+            rate_limited: error.is_rate_limited(),
+            ..Self::error(correlation_id, tool_call_id, error.to_string())
+        }
+    }
+
+    /// Attaches the result cache outcome of the call.
+    #[must_use]
+    pub fn with_cache_status(mut self, cache: Option<ToolCacheStatus>) -> Self {
+        self.cache = cache;
+        self
+    }
 }
 
 /// Trait for tools that can be spawned as actors.
@@ -132,6 +171,209 @@ pub trait ToolActor {
     ///
     /// The returned handle can be used to send `ExecuteToolDirect` messages.
     fn spawn(runtime: &mut ActorRuntime) -> impl Future<Output = ActorHandle> + Send;
+
+    /// Starts recording every call handled by the tool actor behind `handle`.
+    ///
+    /// Sends [`EnableCallLog`](replay::EnableCallLog); see [`crate::tools::replay`] for replaying
+    /// the recorded calls.
+    fn enable_call_log(handle: &ActorHandle) -> impl Future<Output = ()> + Send {
+        replay::enable_call_log(handle)
+    }
+
+    /// Starts caching results of the tool actor behind `handle`.
+    ///
+    /// Sends [`EnableResultCache`](cache::EnableResultCache); send
+    /// [`ClearToolCache`](cache::ClearToolCache) to drop cached results.
+    fn enable_result_cache(
+        handle: &ActorHandle,
+        config: ResultCacheConfig,
+    ) -> impl Future<Output = ()> + Send {
+        cache::enable_result_cache(handle, config)
+    }
+
+    /// Replaces the rate limit of the tool actor behind `handle`.
+    ///
+    /// Sends [`SetToolRateLimit`](rate_limit::SetToolRateLimit); `None`
+    /// removes the limit. Builtin tool actors start with
+    /// [`ToolRateLimit::builtin_default`].
+    fn set_rate_limit(
+        handle: &ActorHandle,
+        limit: Option<ToolRateLimit>,
+    ) -> impl Future<Output = ()> + Send {
+        rate_limit::set_tool_rate_limit(handle, limit)
+    }
+
+    /// Replaces the argument coercion rules of the tool actor behind
+    /// `handle`.
+    ///
+    /// Sends [`SetInputCoercion`](coercion::SetInputCoercion). Until then
+    /// the actor coerces with [`CoercionRules::from_schema`].
+    fn set_input_coercion(
+        handle: &ActorHandle,
+        rules: CoercionRules,
+    ) -> impl Future<Output = ()> + Send {
+        coercion::set_input_coercion(handle, rules)
+    }
+
+    /// Returns the calls recorded by the tool actor behind `handle`.
+    ///
+    /// Sends [`GetCallLog`](replay::GetCallLog) and waits for the
+    /// [`CallLogResponse`](replay::CallLogResponse).
+    /// Returns an empty list if recording was never enabled.
+    fn get_call_log(
+        runtime: &mut ActorRuntime,
+        handle: &ActorHandle,
+    ) -> impl Future<Output = Vec<ToolCallRecord>> + Send {
+        replay::get_call_log(runtime, handle)
+    }
+}
+
+/// Panics if coerced `args` violate `definition`'s input schema.
+///
+/// Tool actors call this before executing in debug builds so a schema that
+/// disagrees with the arguments it is fed fails loudly during development.
+/// Nothing is checked under [`CoercionRules::strict`], which leaves
+/// mistyped arguments for the tool to reject.
+///
+/// # Panics
+///
+/// Panics on any schema violation.
+#[cfg(debug_assertions)]
+pub(crate) fn debug_assert_valid_args(
+    definition: &ToolDefinition,
+    rules: Option<&CoercionRules>,
+    args: &Value,
+) {
+    if rules.is_some_and(CoercionRules::is_strict) {
+        return;
+    }
+    if let Err(violations) = definition.validate_against_schema(args) {
+        let details: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        panic!(
+            "arguments for tool '{}' violate its schema: {}",
+            definition.name,
+            details.join("; ")
+        );
+    }
+}
+
+/// Converts loosely typed `args` with `rules`, or to the types in
+/// `definition`'s input schema when no rules are configured.
+///
+/// Tool actors call this before executing; see [`crate::tools::coercion`].
+pub(crate) fn coerce_args(
+    definition: &ToolDefinition,
+    rules: Option<&CoercionRules>,
+    args: Value,
+) -> Value {
+    match rules {
+        Some(rules) => rules.apply(&definition.name, args),
+        None => CoercionRules::from_schema(&definition.input_schema).apply(&definition.name, args),
+    }
+}
+
+/// State every builtin tool actor keeps for its call log, result cache,
+/// rate limit and coercion handlers.
+///
+/// Actors embed it and implement [`HasToolActorExtras`], which gives them
+/// [`CallLogging`], [`ResultCaching`], [`RateLimiting`] and [`InputCoercing`].
+#[derive(Debug, Default)]
+pub(crate) struct ToolActorExtras {
+    /// Recorded calls while call logging is enabled
+    call_log: Option<ToolCallLog>,
+    /// Cached results while result caching is enabled
+    result_cache: Option<Arc<ToolResultCache>>,
+    /// Rate limiter applied to every call (`None` while unlimited)
+    rate_limiter: Option<Arc<ToolRateLimiter>>,
+    /// Coercion rules replacing the schema-derived ones, once configured
+    input_coercion: Option<CoercionRules>,
+}
+
+/// Tool actors whose state embeds a [`ToolActorExtras`].
+pub(crate) trait HasToolActorExtras {
+    /// Returns the actor's shared tool state.
+    fn extras(&self) -> &ToolActorExtras;
+
+    /// Returns the actor's shared tool state for updating.
+    fn extras_mut(&mut self) -> &mut ToolActorExtras;
+}
+
+impl<A: HasToolActorExtras> CallLogging for A {
+    fn call_log_mut(&mut self) -> &mut Option<ToolCallLog> {
+        &mut self.extras_mut().call_log
+    }
+}
+
+impl<A: HasToolActorExtras> ResultCaching for A {
+    fn result_cache_mut(&mut self) -> &mut Option<Arc<ToolResultCache>> {
+        &mut self.extras_mut().result_cache
+    }
+}
+
+impl<A: HasToolActorExtras> RateLimiting for A {
+    fn rate_limiter_mut(&mut self) -> &mut Option<Arc<ToolRateLimiter>> {
+        &mut self.extras_mut().rate_limiter
+    }
+}
+
+impl<A: HasToolActorExtras> InputCoercing for A {
+    fn input_coercion_mut(&mut self) -> &mut Option<CoercionRules> {
+        &mut self.extras_mut().input_coercion
+    }
+}
+
+/// Registers the [`ExecuteToolDirect`] handler of a builtin tool actor,
+/// along with its call log, result cache, rate limit and coercion handlers.
+///
+/// Each call coerces the arguments, runs the tool built by `make_tool`
+/// from the actor's state through the cache, call log and rate limiter,
+/// and broadcasts the [`ToolActorResponse`].
+pub(crate) fn configure_tool_actor<A, T, F>(builder: &mut ManagedActor<Idle, A>, make_tool: F)
+where
+    A: ToolActor + HasToolActorExtras + Default + Send + std::fmt::Debug + 'static,
+    T: ToolExecutorTrait + Send + Sync + 'static,
+    F: Fn(&A) -> T + Send + Sync + 'static,
+{
+    builder.act_on::<ExecuteToolDirect>(move |actor, envelope| {
+        let msg = envelope.message();
+        let correlation_id = msg.correlation_id.clone();
+        let tool_call_id = msg.tool_call_id.clone();
+        let extras = actor.model.extras();
+        let coercion = extras.input_coercion.as_ref();
+        let args = coerce_args(&A::definition(), coercion, msg.args.clone());
+        #[cfg(debug_assertions)]
+        debug_assert_valid_args(&A::definition(), coercion, &args);
+        let tool = make_tool(&actor.model);
+        let broker = actor.broker().clone();
+        let recorder = extras.call_log.is_some().then(|| actor.handle().clone());
+        let cache = extras.result_cache.clone();
+        let limiter = extras.rate_limiter.clone();
+
+        Reply::pending(async move {
+            let (result, cache_status) = execute_cached(cache.as_deref(), args, |args| {
+                execute_recorded(&tool, A::name(), args, recorder, limiter.as_deref())
+            })
+            .await;
+
+            let response = match result {
+                Ok(value) => {
+                    let result_str = serde_json::to_string(&value)
+                        .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
+                    ToolActorResponse::success(correlation_id, tool_call_id, result_str)
+                }
+                Err(e) => ToolActorResponse::from_error(correlation_id, tool_call_id, &e),
+            };
+
+            broker
+                .broadcast(response.with_cache_status(cache_status))
+                .await;
+        })
+    });
+
+    replay::configure_call_log_handlers(builder);
+    cache::configure_result_cache_handlers(builder);
+    rate_limit::configure_rate_limit_handlers(builder, A::name());
+    coercion::configure_input_coercion_handlers(builder);
 }
 
 /// Helper trait for executing tool logic asynchronously.
@@ -182,5 +424,32 @@ mod tests {
         assert_eq!(resp.tool_call_id, "tc_123");
         assert!(resp.result.is_err());
         assert_eq!(resp.result.unwrap_err(), "failed");
+        assert!(!resp.rate_limited);
+        assert!(resp.cache.is_none());
+    }
+
+    #[test]
+    fn tool_actor_response_with_cache_status() {
+        let status = ToolCacheStatus {
+            hit: true,
+            hits: 1,
+            misses: 1,
+        };
+        let resp = ToolActorResponse::success(CorrelationId::new(), "tc_123", "result")
+            .with_cache_status(Some(status));
+
+        assert_eq!(resp.cache, Some(status));
+    }
+
+    #[test]
+    fn tool_actor_response_from_rate_limited_error() {
+        let resp = ToolActorResponse::from_error(
+            CorrelationId::new(),
+            "tc_123",
+            &ToolError::rate_limited("bash", 5),
+        );
+
+        assert!(resp.rate_limited);
+        assert!(resp.result.unwrap_err().contains("rate limited"));
     }
 }
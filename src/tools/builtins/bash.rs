@@ -3,7 +3,7 @@
 //! Executes shell commands with timeout and output capture.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
 use serde::Deserialize;
@@ -30,7 +30,20 @@ pub struct BashTool {
 ///
 /// This actor wraps the `BashTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct BashToolActor;
+pub struct BashToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for BashToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 impl Default for BashTool {
     fn default() -> Self {
@@ -293,29 +306,7 @@ impl ToolActor for BashToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("bash_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = BashTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| BashTool::new());
 
         builder.start().await
     }
@@ -488,4 +479,23 @@ mod tests {
         assert!(schema["properties"]["timeout"].is_object());
         assert!(schema["properties"]["cwd"].is_object());
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(&BashTool::config().definition)
+            .valid_example(json!({"command": "ls"}))
+            .valid_example(json!({"command": "ls", "timeout": 600, "cwd": "/tmp"}))
+            .invalid_example(json!({}), "command is required")
+            .invalid_example(json!({"command": 42}), "command must be a string")
+            .invalid_example(
+                json!({"command": "ls", "timeout": 0}),
+                "timeout below minimum",
+            )
+            .invalid_example(
+                json!({"command": "ls", "timeout": 601}),
+                "timeout above maximum",
+            )
+            .run()
+            .assert_success();
+    }
 }
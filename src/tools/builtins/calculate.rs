@@ -4,8 +4,8 @@
 //! Note: fasteval is a safe math expression parser, not arbitrary code execution.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
-use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::{CoercionRules, ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
 use fasteval::ez_eval;
 use serde::Deserialize;
@@ -23,7 +23,20 @@ pub struct CalculateTool;
 ///
 /// This actor wraps the `CalculateTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct CalculateToolActor;
+pub struct CalculateToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for CalculateToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 /// Arguments for the calculate tool.
 #[derive(Debug, Deserialize)]
@@ -47,7 +60,7 @@ impl CalculateTool {
     pub fn config() -> ToolConfig {
         use crate::messages::ToolDefinition;
 
-        ToolConfig::new(ToolDefinition {
+        let definition = ToolDefinition {
             name: "calculate".to_string(),
             description: "Evaluate mathematical expressions. Supports arithmetic (+, -, *, /, ^, %), comparison, and built-in functions (sin, cos, tan, log, abs, min, max, floor, ceil, round, etc.).".to_string(),
             input_schema: json!({
@@ -67,7 +80,9 @@ impl CalculateTool {
                 },
                 "required": ["expression"]
             }),
-        })
+        };
+        let coercion = CoercionRules::from_schema(&definition.input_schema);
+        ToolConfig::new(definition).with_input_coercion(coercion)
     }
 }
 
@@ -166,29 +181,7 @@ impl ToolActor for CalculateToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("calculate_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = CalculateTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| CalculateTool::new());
 
         builder.start().await
     }
@@ -219,6 +212,52 @@ mod tests {
         assert_eq!(result["result"], 5.0);
     }
 
+    #[tokio::test]
+    async fn calculate_config_coerces_string_variables() {
+        let args = CalculateTool::config()
+            .coerce_args(json!({"expression": "x * 2", "variables": {"x": "21"}}));
+        assert_eq!(args["variables"]["x"], 21.0);
+
+        let result = CalculateTool::new().execute(args).await.unwrap();
+        assert_eq!(result["result"], 42.0);
+    }
+
+    #[tokio::test]
+    async fn calculate_actor_honors_strict_coercion() {
+        use crate::tools::actor::ExecuteToolDirect;
+        use crate::types::CorrelationId;
+        use std::time::Duration;
+
+        let mut app = ActonApp::launch_async().await;
+        let mut runtime = app.clone();
+        let handle = CalculateToolActor::spawn(&mut runtime).await;
+        CalculateToolActor::enable_call_log(&handle).await;
+        CalculateToolActor::set_input_coercion(&handle, CoercionRules::strict()).await;
+
+        handle
+            .send(ExecuteToolDirect::new(
+                CorrelationId::new(),
+                "tc_1",
+                json!({"expression": "x * 2", "variables": {"x": "5"}}),
+            ))
+            .await;
+
+        let mut records = Vec::new();
+        for _ in 0..50 {
+            records = CalculateToolActor::get_call_log(&mut runtime, &handle).await;
+            if !records.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].args["variables"]["x"], "5");
+        assert!(records[0].result.is_err());
+
+        app.shutdown_all().await.unwrap();
+    }
+
     #[tokio::test]
     async fn calculate_operator_precedence() {
         let tool = CalculateTool::new();
@@ -357,4 +396,19 @@ mod tests {
         assert!(schema["properties"]["expression"].is_object());
         assert!(schema["properties"]["variables"].is_object());
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(&CalculateTool::config().definition)
+            .valid_example(json!({"expression": "2 + 2"}))
+            .valid_example(json!({"expression": "x * y", "variables": {"x": 2, "y": 1.5}}))
+            .invalid_example(json!({}), "expression is required")
+            .invalid_example(json!({"expression": 4}), "expression must be a string")
+            .invalid_example(
+                json!({"expression": "x", "variables": {"x": "two"}}),
+                "variables must be numbers",
+            )
+            .run()
+            .assert_success();
+    }
 }
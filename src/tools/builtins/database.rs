@@ -0,0 +1,500 @@
+//! Database built-in tool.
+//!
+//! Runs SQL against a libSQL/SQLite database, limited by a
+//! [`DatabasePermissionLevel`] chosen when the tool is registered.
+
+use super::sql_query::{connect, query_rows};
+use crate::memory::PersistenceConfig;
+use crate::messages::ToolDefinition;
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::security::PathValidator;
+use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
+use acton_reactive::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// What a [`DatabaseTool`] may do to its database.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DatabasePermissionLevel {
+    /// Only queries such as `SELECT`
+    #[default]
+    ReadOnly,
+    /// Queries plus `INSERT`, `UPDATE` and `DELETE`
+    ReadWrite,
+    /// Any statement, including `CREATE`, `ALTER` and `DROP`
+    Admin,
+}
+
+impl DatabasePermissionLevel {
+    /// Returns true if statements of `kind` may run at this level.
+    fn allows(self, kind: StatementKind) -> bool {
+        match kind {
+            StatementKind::Read => true,
+            StatementKind::Write => self >= Self::ReadWrite,
+            StatementKind::Schema => self == Self::Admin,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "only read-only queries such as SELECT are allowed",
+            Self::ReadWrite => "SELECT, INSERT, UPDATE and DELETE are allowed; CREATE, ALTER, DROP and other schema changes are blocked",
+            Self::Admin => "all statements are allowed, including schema changes",
+        }
+    }
+}
+
+impl fmt::Display for DatabasePermissionLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ReadOnly => write!(f, "read-only"),
+            Self::ReadWrite => write!(f, "read-write"),
+            Self::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// The effect of a SQL statement, from least to most privileged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum StatementKind {
+    /// Returns rows without modifying anything
+    Read,
+    /// Modifies rows
+    Write,
+    /// Modifies the schema or the database itself
+    Schema,
+}
+
+/// Database tool executor.
+///
+/// The permission level defaults to [`DatabasePermissionLevel::ReadOnly`].
+/// Write levels run statements without asking; applications that need a
+/// human to approve changes should confirm the tool call before executing
+/// it.
+#[derive(Debug, Clone)]
+pub struct DatabaseTool {
+    /// What statements may run
+    permission_level: DatabasePermissionLevel,
+    /// Database used when the call does not name one
+    default_database: PathBuf,
+}
+
+/// Database tool actor state.
+///
+/// This actor wraps a read-only `DatabaseTool` executor for per-agent tool
+/// spawning.
+#[acton_actor]
+pub struct DatabaseToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for DatabaseToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
+
+/// Arguments for the database tool.
+#[derive(Debug, Deserialize)]
+struct DatabaseArgs {
+    /// Path to the database file
+    #[serde(default)]
+    database_path: Option<String>,
+    /// The SQL statement to run
+    statement: String,
+}
+
+impl Default for DatabaseTool {
+    fn default() -> Self {
+        Self {
+            permission_level: DatabasePermissionLevel::default(),
+            default_database: PathBuf::from(PersistenceConfig::default().db_path),
+        }
+    }
+}
+
+impl DatabaseTool {
+    /// Creates a read-only database tool that defaults to the persistence
+    /// database.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what statements the tool may run.
+    #[must_use]
+    pub fn with_permission_level(mut self, level: DatabasePermissionLevel) -> Self {
+        self.permission_level = level;
+        self
+    }
+
+    /// Sets the database used when the call has no `database_path`.
+    #[must_use]
+    pub fn with_default_database(mut self, path: impl Into<PathBuf>) -> Self {
+        self.default_database = path.into();
+        self
+    }
+
+    /// Returns the permission level.
+    #[must_use]
+    pub fn permission_level(&self) -> DatabasePermissionLevel {
+        self.permission_level
+    }
+
+    /// Returns the read-only tool configuration for registration.
+    #[must_use]
+    pub fn config() -> ToolConfig {
+        Self::config_for(DatabasePermissionLevel::ReadOnly)
+    }
+
+    /// Returns the tool configuration for `level`.
+    ///
+    /// The level is stated in the description the model sees, so it does not
+    /// attempt statements that will be rejected.
+    #[must_use]
+    pub fn config_for(level: DatabasePermissionLevel) -> ToolConfig {
+        ToolConfig::new(ToolDefinition {
+            name: "database".to_string(),
+            description: format!(
+                "Run a SQL statement against a SQLite/libSQL database. Queries return rows as JSON objects; other statements return rows_affected. Permission level: {level} ({}).",
+                level.describe()
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "database_path": {
+                        "type": "string",
+                        "description": "Path to the database file (default: the acton-ai persistence database)"
+                    },
+                    "statement": {
+                        "type": "string",
+                        "description": "A single SQL statement",
+                        "minLength": 1
+                    }
+                },
+                "required": ["statement"]
+            }),
+        })
+    }
+
+    /// Rejects `statement` unless it is one statement the permission level
+    /// allows.
+    fn check_statement(&self, statement: &str) -> Result<StatementKind, ToolError> {
+        let kind = classify(statement).map_err(|e| ToolError::validation_failed("database", e))?;
+        if !self.permission_level.allows(kind) {
+            return Err(ToolError::validation_failed(
+                "database",
+                format!(
+                    "statement not permitted at the {} permission level: {}",
+                    self.permission_level,
+                    self.permission_level.describe()
+                ),
+            ));
+        }
+        Ok(kind)
+    }
+
+    /// Resolves and validates the database path.
+    ///
+    /// Only [`Admin`](DatabasePermissionLevel::Admin) may create a database
+    /// that does not exist yet.
+    fn resolve_path(&self, path: &Path) -> Result<PathBuf, ToolError> {
+        let validator = PathValidator::new();
+        let validated = if self.permission_level == DatabasePermissionLevel::Admin && !path.exists()
+        {
+            validator.validate_parent(path)
+        } else {
+            validator.validate_file(path)
+        };
+        validated.map_err(|e| ToolError::validation_failed("database", e.to_string()))
+    }
+
+    /// Runs an already checked statement.
+    async fn run(
+        path: PathBuf,
+        statement: String,
+        kind: StatementKind,
+        level: DatabasePermissionLevel,
+    ) -> Result<Value, ToolError> {
+        if kind == StatementKind::Read {
+            let conn = connect("database", &path, libsql::OpenFlags::SQLITE_OPEN_READ_ONLY).await?;
+            let (rows, truncated) = query_rows("database", &conn, &statement).await?;
+            return Ok(json!({
+                "rows": rows,
+                "row_count": rows.len(),
+                "truncated": truncated
+            }));
+        }
+
+        let flags = if level == DatabasePermissionLevel::Admin {
+            libsql::OpenFlags::default()
+        } else {
+            libsql::OpenFlags::SQLITE_OPEN_READ_WRITE
+        };
+        let conn = connect("database", &path, flags).await?;
+        let rows_affected = conn.execute(&statement, ()).await.map_err(|e| {
+            ToolError::execution_failed("database", format!("statement failed: {e}"))
+        })?;
+
+        Ok(json!({ "rows_affected": rows_affected }))
+    }
+}
+
+/// Splits SQL into the keywords of each statement, skipping comments, string
+/// literals and quoted identifiers.
+fn statement_keywords(sql: &str) -> Result<Vec<Vec<String>>, String> {
+    let chars: Vec<char> = sql.chars().collect();
+    let mut statements = vec![Vec::new()];
+    let mut i = 0;
+
+    let skip_until = |from: usize, end: &str| -> Result<usize, String> {
+        let end: Vec<char> = end.chars().collect();
+        (from..chars.len())
+            .find(|&j| chars[j..].starts_with(&end))
+            .map(|j| j + end.len())
+            .ok_or_else(|| "unterminated quote or comment".to_string())
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                i = (i..chars.len())
+                    .find(|&j| chars[j] == '\n')
+                    .unwrap_or(chars.len());
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => i = skip_until(i + 2, "*/")?,
+            // Doubled quotes inside a literal restart the scan at the second
+            // quote, which is equivalent to skipping the escape
+            '\'' => i = skip_until(i + 1, "'")?,
+            '"' => i = skip_until(i + 1, "\"")?,
+            '`' => i = skip_until(i + 1, "`")?,
+            '[' => i = skip_until(i + 1, "]")?,
+            ';' => {
+                statements.push(Vec::new());
+                i += 1;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let end = (i..chars.len())
+                    .find(|&j| !(chars[j].is_ascii_alphanumeric() || chars[j] == '_'))
+                    .unwrap_or(chars.len());
+                let word: String = chars[i..end].iter().collect();
+                if let Some(statement) = statements.last_mut() {
+                    statement.push(word.to_ascii_uppercase());
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+
+    statements.retain(|statement| !statement.is_empty());
+    Ok(statements)
+}
+
+/// Classifies a single SQL statement by its effect.
+///
+/// Statements that are not recognized count as schema changes, so only
+/// [`Admin`](DatabasePermissionLevel::Admin) may run them.
+pub(super) fn classify(sql: &str) -> Result<StatementKind, String> {
+    let statements = statement_keywords(sql)?;
+    let keywords = match statements.as_slice() {
+        [] => return Err("statement is empty".to_string()),
+        [keywords] => keywords,
+        _ => return Err("run one statement per call".to_string()),
+    };
+
+    let is_write =
+        |word: &String| matches!(word.as_str(), "INSERT" | "UPDATE" | "DELETE" | "REPLACE");
+    Ok(match keywords[0].as_str() {
+        "SELECT" | "VALUES" | "EXPLAIN" => StatementKind::Read,
+        "WITH" if keywords.iter().any(is_write) => StatementKind::Write,
+        "WITH" => StatementKind::Read,
+        "INSERT" | "UPDATE" | "DELETE" | "REPLACE" => StatementKind::Write,
+        _ => StatementKind::Schema,
+    })
+}
+
+impl ToolExecutorTrait for DatabaseTool {
+    fn execute(&self, args: Value) -> ToolExecutionFuture {
+        let tool = self.clone();
+
+        Box::pin(async move {
+            let args: DatabaseArgs = serde_json::from_value(args).map_err(|e| {
+                ToolError::validation_failed("database", format!("invalid arguments: {e}"))
+            })?;
+
+            let kind = tool.check_statement(&args.statement)?;
+            let path = args
+                .database_path
+                .map_or_else(|| tool.default_database.clone(), PathBuf::from);
+            let path = tool.resolve_path(&path)?;
+
+            // libsql futures are not `Sync`, so the statement runs on its own task
+            let level = tool.permission_level;
+            tokio::spawn(Self::run(path, args.statement, kind, level))
+                .await
+                .map_err(|e| {
+                    ToolError::execution_failed("database", format!("statement task failed: {e}"))
+                })?
+        })
+    }
+
+    fn validate_args(&self, args: &Value) -> Result<(), ToolError> {
+        let args: DatabaseArgs = serde_json::from_value(args.clone()).map_err(|e| {
+            ToolError::validation_failed("database", format!("invalid arguments: {e}"))
+        })?;
+
+        self.check_statement(&args.statement).map(|_| ())
+    }
+}
+
+impl ToolActor for DatabaseToolActor {
+    fn name() -> &'static str {
+        "database"
+    }
+
+    fn definition() -> ToolDefinition {
+        DatabaseTool::config().definition
+    }
+
+    async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
+        let mut builder = runtime.new_actor_with_name::<Self>("database_tool".to_string());
+
+        configure_tool_actor(&mut builder, |_| DatabaseTool::new());
+
+        builder.start().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn sample_database() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app.db");
+        let db = libsql::Builder::new_local(&path).build().await.unwrap();
+        db.connect()
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT);
+                 INSERT INTO items (name) VALUES ('a'), ('b');",
+            )
+            .await
+            .unwrap();
+        (dir, path)
+    }
+
+    fn tool(level: DatabasePermissionLevel, path: &Path) -> DatabaseTool {
+        DatabaseTool::new()
+            .with_permission_level(level)
+            .with_default_database(path)
+    }
+
+    #[test]
+    fn classify_statements() {
+        assert_eq!(classify("SELECT * FROM t"), Ok(StatementKind::Read));
+        assert_eq!(
+            classify("with x as (select 1) select * from x"),
+            Ok(StatementKind::Read)
+        );
+        assert_eq!(
+            classify("WITH old AS (SELECT id FROM t) DELETE FROM t WHERE id IN old"),
+            Ok(StatementKind::Write)
+        );
+        assert_eq!(
+            classify("update t set name = 'drop table'"),
+            Ok(StatementKind::Write)
+        );
+        assert_eq!(classify("DROP TABLE t;"), Ok(StatementKind::Schema));
+        assert_eq!(classify("PRAGMA journal_mode"), Ok(StatementKind::Schema));
+        assert_eq!(
+            classify("-- comment\nSELECT 'a;b' /* ; */"),
+            Ok(StatementKind::Read)
+        );
+        assert!(classify("SELECT 1; DROP TABLE t").is_err());
+        assert!(classify("  ;  ").is_err());
+        assert!(classify("SELECT 'open").is_err());
+    }
+
+    #[test]
+    fn permission_levels_gate_statement_kinds() {
+        use DatabasePermissionLevel::*;
+
+        assert!(ReadOnly.allows(StatementKind::Read));
+        assert!(!ReadOnly.allows(StatementKind::Write));
+        assert!(ReadWrite.allows(StatementKind::Write));
+        assert!(!ReadWrite.allows(StatementKind::Schema));
+        assert!(Admin.allows(StatementKind::Schema));
+    }
+
+    #[tokio::test]
+    async fn read_write_level_modifies_rows() {
+        let (_dir, path) = sample_database().await;
+        let tool = tool(DatabasePermissionLevel::ReadWrite, &path);
+
+        let result = tool
+            .execute(json!({"statement": "UPDATE items SET name = 'z' WHERE id = 1"}))
+            .await
+            .unwrap();
+        assert_eq!(result["rows_affected"], 1);
+
+        let result = tool
+            .execute(json!({"statement": "SELECT name FROM items ORDER BY id"}))
+            .await
+            .unwrap();
+        assert_eq!(result["rows"], json!([{"name": "z"}, {"name": "b"}]));
+
+        let denied = tool
+            .execute(json!({"statement": "DROP TABLE items"}))
+            .await
+            .unwrap_err();
+        assert!(denied.to_string().contains("read-write"));
+    }
+
+    #[tokio::test]
+    async fn read_only_level_rejects_writes() {
+        let (_dir, path) = sample_database().await;
+        let tool = tool(DatabasePermissionLevel::ReadOnly, &path);
+
+        let args = json!({"statement": "DELETE FROM items"});
+        assert!(tool.validate_args(&args).is_err());
+        assert!(tool.execute(args).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn admin_level_changes_schema() {
+        let (_dir, path) = sample_database().await;
+        let tool = tool(DatabasePermissionLevel::Admin, &path);
+
+        tool.execute(json!({"statement": "ALTER TABLE items ADD COLUMN price REAL"}))
+            .await
+            .unwrap();
+        let result = tool
+            .execute(json!({"statement": "SELECT price FROM items LIMIT 1"}))
+            .await
+            .unwrap();
+        assert_eq!(result["rows"], json!([{"price": null}]));
+    }
+
+    #[test]
+    fn config_describes_permission_level() {
+        let config = DatabaseTool::config_for(DatabasePermissionLevel::ReadWrite);
+        assert_eq!(config.definition.name, "database");
+        assert!(config.definition.description.contains("read-write"));
+        assert!(DatabaseTool::config()
+            .definition
+            .description
+            .contains("read-only"));
+    }
+}
@@ -3,8 +3,8 @@
 //! Makes targeted string replacements in files.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
-use crate::tools::security::PathValidator;
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::security::{Operation, PathValidator, SecurityPolicy, SymlinkPolicy};
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
 use serde::Deserialize;
@@ -14,14 +14,41 @@ use std::path::Path;
 /// Edit file tool executor.
 ///
 /// Makes targeted string replacements in files.
-#[derive(Debug, Default, Clone)]
-pub struct EditFileTool;
+#[derive(Debug, Clone)]
+pub struct EditFileTool {
+    /// Rules restricting which paths may be edited
+    policy: Option<SecurityPolicy>,
+    /// How symlinks in the path are treated
+    symlink_policy: SymlinkPolicy,
+}
+
+impl Default for EditFileTool {
+    fn default() -> Self {
+        Self {
+            policy: None,
+            symlink_policy: SymlinkPolicy::AllowWithinRoot,
+        }
+    }
+}
 
 /// Edit file tool actor state.
 ///
 /// This actor wraps the `EditFileTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct EditFileToolActor;
+pub struct EditFileToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for EditFileToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 /// Arguments for the edit_file tool.
 #[derive(Debug, Deserialize)]
@@ -41,7 +68,23 @@ impl EditFileTool {
     /// Creates a new edit file tool.
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Enforces `policy` instead of the default path rules.
+    #[must_use]
+    pub fn with_policy(mut self, policy: SecurityPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Sets how symlinks in the path are treated.
+    ///
+    /// Defaults to [`SymlinkPolicy::AllowWithinRoot`].
+    #[must_use]
+    pub fn with_symlink_policy(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = policy;
+        self
     }
 
     /// Returns the tool configuration for registration.
@@ -125,6 +168,8 @@ impl EditFileTool {
 
 impl ToolExecutorTrait for EditFileTool {
     fn execute(&self, args: Value) -> ToolExecutionFuture {
+        let policy = self.policy.clone();
+        let symlink_policy = self.symlink_policy;
         Box::pin(async move {
             let args: EditFileArgs = serde_json::from_value(args).map_err(|e| {
                 ToolError::validation_failed("edit_file", format!("invalid arguments: {e}"))
@@ -141,9 +186,11 @@ impl ToolExecutorTrait for EditFileTool {
             }
 
             // Validate path using PathValidator for security
-            let validator = PathValidator::new();
+            let validator = policy
+                .map_or_else(PathValidator::new, PathValidator::new_with_policy)
+                .with_symlink_policy(symlink_policy);
             let canonical_path = validator
-                .validate_file(path)
+                .validate_file_for(Operation::Write, path)
                 .map_err(|e| ToolError::validation_failed("edit_file", e.to_string()))?;
 
             // Validate that old_string != new_string
@@ -245,29 +292,7 @@ impl ToolActor for EditFileToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("edit_file_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = EditFileTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| EditFileTool::new());
 
         builder.start().await
     }
@@ -486,4 +511,26 @@ mod tests {
         let config = EditFileTool::config();
         assert!(config.sandboxed, "edit_file tool should require sandbox");
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(&EditFileTool::config().definition)
+            .valid_example(json!({"path": "/tmp/a", "old_string": "a", "new_string": "b"}))
+            .valid_example(json!({
+                "path": "/tmp/a",
+                "old_string": "a",
+                "new_string": "b",
+                "replace_all": true
+            }))
+            .invalid_example(
+                json!({"path": "/tmp/a", "old_string": "a"}),
+                "new_string is required",
+            )
+            .invalid_example(
+                json!({"path": "/tmp/a", "old_string": "a", "new_string": "b", "replace_all": "yes"}),
+                "replace_all must be a boolean",
+            )
+            .run()
+            .assert_success();
+    }
 }
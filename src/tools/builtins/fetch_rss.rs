@@ -0,0 +1,664 @@
+//! RSS/Atom feed built-in tool.
+//!
+//! Fetches a feed through the [`WebFetchTool`] client and returns its most
+//! recent items. RSS 2.0, RSS 1.0 (RDF) and Atom 1.0 are recognized.
+
+use super::WebFetchTool;
+use crate::messages::ToolDefinition;
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
+use acton_reactive::prelude::*;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Maximum characters kept from an item summary.
+const MAX_SUMMARY_CHARS: usize = 500;
+
+/// RSS/Atom feed tool executor.
+///
+/// Documents that are not feeds produce an `{"error": ...}` result rather
+/// than a tool error, so the model can try another URL.
+#[derive(Debug, Clone, Default)]
+pub struct FetchRssTool {
+    /// HTTP client, URL checks and timeouts shared with `web_fetch`
+    fetcher: WebFetchTool,
+}
+
+/// RSS/Atom feed tool actor state.
+///
+/// This actor wraps the `FetchRssTool` executor for per-agent tool spawning.
+#[acton_actor]
+pub struct FetchRssToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for FetchRssToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
+
+/// Arguments for the fetch_rss tool.
+#[derive(Debug, Deserialize)]
+struct FetchRssArgs {
+    /// Feed URL
+    url: String,
+    /// Maximum number of items to return
+    #[serde(default = "default_max_items")]
+    max_items: usize,
+    /// Only return items published within this many hours
+    #[serde(default)]
+    since_hours: Option<u64>,
+    /// Timeout in seconds (default: 30)
+    #[serde(default)]
+    timeout: Option<u64>,
+}
+
+fn default_max_items() -> usize {
+    10
+}
+
+/// A feed entry as returned to the model.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct FeedItem {
+    title: String,
+    link: Option<String>,
+    /// Publication time (RFC 3339)
+    published: Option<String>,
+    summary: Option<String>,
+    #[serde(skip)]
+    published_at: Option<DateTime<FixedOffset>>,
+}
+
+/// A parsed feed.
+#[derive(Debug, Clone, PartialEq)]
+struct Feed {
+    title: Option<String>,
+    items: Vec<FeedItem>,
+}
+
+impl FetchRssTool {
+    /// Creates a new feed tool.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a feed tool with custom fetch settings.
+    #[must_use]
+    pub fn with_config(timeout: Duration, max_response_size: usize) -> Self {
+        Self {
+            fetcher: WebFetchTool::with_config(timeout, max_response_size),
+        }
+    }
+
+    /// Returns the tool configuration for registration.
+    #[must_use]
+    pub fn config() -> ToolConfig {
+        ToolConfig::new(ToolDefinition {
+            name: "fetch_rss".to_string(),
+            description: "Fetch an RSS or Atom feed and return its most recent items with title, link, publication time and summary.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "url": {
+                        "type": "string",
+                        "description": "Feed URL (must be http or https)"
+                    },
+                    "max_items": {
+                        "type": "integer",
+                        "description": "Maximum number of items to return (default: 10)",
+                        "minimum": 1,
+                        "maximum": 100
+                    },
+                    "since_hours": {
+                        "type": "integer",
+                        "description": "Only return items published within this many hours; undated items are skipped",
+                        "minimum": 1
+                    },
+                    "timeout": {
+                        "type": "integer",
+                        "description": "Timeout in seconds (default: 30, max: 120)",
+                        "minimum": 1,
+                        "maximum": 120
+                    }
+                },
+                "required": ["url"]
+            }),
+        })
+    }
+}
+
+/// Keeps the newest `max_items` items, dropping those older than
+/// `since_hours`.
+fn select_items(
+    mut items: Vec<FeedItem>,
+    max_items: usize,
+    since_hours: Option<u64>,
+) -> Vec<FeedItem> {
+    if let Some(hours) = since_hours {
+        let hours = i64::try_from(hours).unwrap_or(i64::MAX);
+        let cutoff =
+            Utc::now() - chrono::Duration::try_hours(hours).unwrap_or(chrono::Duration::MAX);
+        items.retain(|item| item.published_at.is_some_and(|at| at >= cutoff));
+    }
+    // Feeds are usually newest first, but not always
+    if items.iter().all(|item| item.published_at.is_some()) {
+        items.sort_by_key(|item| std::cmp::Reverse(item.published_at));
+    }
+    items.truncate(max_items);
+    items
+}
+
+impl ToolExecutorTrait for FetchRssTool {
+    fn execute(&self, args: Value) -> ToolExecutionFuture {
+        let fetcher = self.fetcher.clone();
+
+        Box::pin(async move {
+            let args: FetchRssArgs = serde_json::from_value(args).map_err(|e| {
+                ToolError::validation_failed("fetch_rss", format!("invalid arguments: {e}"))
+            })?;
+
+            let url = WebFetchTool::validate_url_for("fetch_rss", &args.url)?;
+            let (status, body) = fetcher.get("fetch_rss", &url, args.timeout).await?;
+
+            if !(200..300).contains(&status) {
+                return Ok(json!({
+                    "error": format!("feed request failed with HTTP status {status}"),
+                    "url": url
+                }));
+            }
+
+            let feed = match parse_feed(&String::from_utf8_lossy(&body)) {
+                Ok(feed) => feed,
+                Err(error) => {
+                    return Ok(json!({
+                        "error": format!("not a valid RSS or Atom feed: {error}"),
+                        "url": url
+                    }))
+                }
+            };
+
+            let total = feed.items.len();
+            let items = select_items(feed.items, args.max_items, args.since_hours);
+
+            Ok(json!({
+                "feed_title": feed.title,
+                "items": items,
+                "item_count": items.len(),
+                "total_items": total
+            }))
+        })
+    }
+
+    fn validate_args(&self, args: &Value) -> Result<(), ToolError> {
+        let args: FetchRssArgs = serde_json::from_value(args.clone()).map_err(|e| {
+            ToolError::validation_failed("fetch_rss", format!("invalid arguments: {e}"))
+        })?;
+
+        if args.max_items == 0 {
+            return Err(ToolError::validation_failed(
+                "fetch_rss",
+                "max_items must be at least 1",
+            ));
+        }
+
+        WebFetchTool::validate_url_for("fetch_rss", &args.url)?;
+
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Feed parsing
+// ---------------------------------------------------------------------------
+
+/// An XML element with namespace prefixes stripped from its name and
+/// attribute names.
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    attrs: HashMap<String, String>,
+    children: Vec<Element>,
+    /// Direct text and CDATA content, concatenated
+    text: String,
+}
+
+impl Element {
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Returns the trimmed text of the first child named in `names` that has
+    /// any.
+    fn child_text(&self, names: &[&str]) -> Option<String> {
+        names.iter().find_map(|name| {
+            self.child(name)
+                .map(|child| child.text.trim().to_string())
+                .filter(|text| !text.is_empty())
+        })
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Replaces the predefined XML entities and character references.
+///
+/// Unknown entities, such as HTML's `&nbsp;`, are left as they are.
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';').filter(|&end| end <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Parses the attributes of a start tag, after its name.
+fn parse_attrs(mut rest: &str) -> Result<HashMap<String, String>, String> {
+    let mut attrs = HashMap::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(attrs);
+        }
+        let eq = rest
+            .find('=')
+            .ok_or_else(|| format!("malformed attribute near '{rest}'"))?;
+        let name = local_name(rest[..eq].trim()).to_string();
+        rest = rest[eq + 1..].trim_start();
+        let quote = rest
+            .chars()
+            .next()
+            .filter(|c| *c == '"' || *c == '\'')
+            .ok_or_else(|| format!("unquoted value for attribute '{name}'"))?;
+        let end = rest[1..]
+            .find(quote)
+            .ok_or_else(|| format!("unterminated value for attribute '{name}'"))?;
+        attrs.insert(name, decode_entities(&rest[1..=end]));
+        rest = &rest[end + 2..];
+    }
+}
+
+/// Parses an XML document into its root element.
+///
+/// This is a tolerant reader for feeds, not a validating parser: DTDs and
+/// processing instructions are skipped, and only the predefined entities are
+/// decoded.
+fn parse_xml(xml: &str) -> Result<Element, String> {
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+    let mut rest = xml;
+
+    let skip_past = |rest: &str, marker: &str| -> Result<usize, String> {
+        rest.find(marker)
+            .map(|i| i + marker.len())
+            .ok_or_else(|| format!("unterminated markup, expected '{marker}'"))
+    };
+
+    while !rest.is_empty() {
+        if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let end = cdata.find("]]>").ok_or("unterminated CDATA section")?;
+            if let Some(top) = stack.last_mut() {
+                top.text.push_str(&cdata[..end]);
+            }
+            rest = &cdata[end + 3..];
+        } else if rest.starts_with("<!--") {
+            rest = &rest[skip_past(rest, "-->")?..];
+        } else if rest.starts_with("<?") {
+            rest = &rest[skip_past(rest, "?>")?..];
+        } else if rest.starts_with("<!") {
+            rest = &rest[skip_past(rest, ">")?..];
+        } else if let Some(close) = rest.strip_prefix("</") {
+            let end = close.find('>').ok_or("unterminated closing tag")?;
+            let name = local_name(close[..end].trim());
+            let element = stack
+                .pop()
+                .ok_or_else(|| format!("unexpected closing tag </{name}>"))?;
+            if element.name != name {
+                return Err(format!(
+                    "mismatched closing tag </{name}> for <{}>",
+                    element.name
+                ));
+            }
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(element),
+                None => root = Some(element),
+            }
+            rest = &close[end + 1..];
+        } else if let Some(open) = rest.strip_prefix('<') {
+            let end = tag_end(open).ok_or("unterminated start tag")?;
+            let tag = &open[..end];
+            let (tag, self_closing) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+            let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+            let element = Element {
+                name: local_name(&tag[..name_end]).to_string(),
+                attrs: parse_attrs(&tag[name_end..])?,
+                ..Element::default()
+            };
+            if element.name.is_empty() {
+                return Err("empty tag name".to_string());
+            }
+            if self_closing {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => root = Some(element),
+                }
+            } else {
+                stack.push(element);
+            }
+            rest = &open[end + 1..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            match stack.last_mut() {
+                Some(top) => top.text.push_str(&decode_entities(&rest[..end])),
+                None if !rest[..end].trim().is_empty() => {
+                    return Err("text outside the root element".to_string())
+                }
+                None => {}
+            }
+            rest = &rest[end..];
+        }
+
+        if root.is_some() && stack.is_empty() {
+            break;
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(format!("unclosed element <{}>", open.name));
+    }
+    root.ok_or_else(|| "document is empty".to_string())
+}
+
+/// Finds the `>` ending a start tag, ignoring any inside quoted attributes.
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if q == c => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses RFC 2822 (RSS) and RFC 3339 (Atom, Dublin Core) timestamps.
+fn parse_date(text: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc2822(text)
+        .or_else(|_| DateTime::parse_from_rfc3339(text))
+        .ok()
+}
+
+/// Strips HTML markup and shortens a summary.
+fn clean_summary(text: &str) -> Option<String> {
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                plain.push(' ');
+            }
+            c if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+    let plain = decode_entities(&plain);
+    let collapsed = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.is_empty() {
+        return None;
+    }
+    if collapsed.chars().count() > MAX_SUMMARY_CHARS {
+        let cut: String = collapsed.chars().take(MAX_SUMMARY_CHARS).collect();
+        return Some(format!("{}...", cut.trim_end()));
+    }
+    Some(collapsed)
+}
+
+fn feed_item(
+    title: Option<String>,
+    link: Option<String>,
+    date: Option<String>,
+    summary: Option<String>,
+) -> FeedItem {
+    let published_at = date.as_deref().and_then(parse_date);
+    FeedItem {
+        title: title.unwrap_or_default(),
+        link,
+        published: published_at.map(|at| at.to_rfc3339()),
+        summary: summary.as_deref().and_then(clean_summary),
+        published_at,
+    }
+}
+
+fn rss_item(item: &Element) -> FeedItem {
+    feed_item(
+        item.child_text(&["title"]),
+        item.child_text(&["link", "guid"]),
+        item.child_text(&["pubDate", "date"]),
+        item.child_text(&["description", "encoded", "summary"]),
+    )
+}
+
+fn atom_entry(entry: &Element) -> FeedItem {
+    let link = entry
+        .children_named("link")
+        .find(|link| link.attrs.get("rel").is_none_or(|rel| rel == "alternate"))
+        .or_else(|| entry.child("link"))
+        .and_then(|link| link.attrs.get("href").cloned());
+    feed_item(
+        entry.child_text(&["title"]),
+        link,
+        entry.child_text(&["published", "updated"]),
+        entry.child_text(&["summary", "content"]),
+    )
+}
+
+/// Parses an RSS 2.0, RSS 1.0 or Atom 1.0 document.
+fn parse_feed(xml: &str) -> Result<Feed, String> {
+    let root = parse_xml(xml)?;
+    match root.name.as_str() {
+        "rss" => {
+            let channel = root.child("channel").ok_or("RSS feed has no <channel>")?;
+            Ok(Feed {
+                title: channel.child_text(&["title"]),
+                items: channel.children_named("item").map(rss_item).collect(),
+            })
+        }
+        "RDF" => Ok(Feed {
+            title: root
+                .child("channel")
+                .and_then(|channel| channel.child_text(&["title"])),
+            items: root.children_named("item").map(rss_item).collect(),
+        }),
+        "feed" => Ok(Feed {
+            title: root.child_text(&["title"]),
+            items: root.children_named("entry").map(atom_entry).collect(),
+        }),
+        other => Err(format!("unexpected root element <{other}>")),
+    }
+}
+
+impl ToolActor for FetchRssToolActor {
+    fn name() -> &'static str {
+        "fetch_rss"
+    }
+
+    fn definition() -> ToolDefinition {
+        FetchRssTool::config().definition
+    }
+
+    async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
+        let mut builder = runtime.new_actor_with_name::<Self>("fetch_rss_tool".to_string());
+
+        configure_tool_actor(&mut builder, |_| FetchRssTool::new());
+
+        builder.start().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>Example News</title>
+    <!-- newest first -->
+    <item>
+      <title>Second &amp; latest</title>
+      <link>https://example.com/2</link>
+      <pubDate>Tue, 03 Jun 2025 09:00:00 GMT</pubDate>
+      <description><![CDATA[<p>Big <b>news</b> &amp; more</p>]]></description>
+    </item>
+    <item>
+      <title>First</title>
+      <link>https://example.com/1</link>
+      <dc:date>2025-06-01T08:00:00Z</dc:date>
+    </item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Example Blog</title>
+  <entry>
+    <title type="text">Hello</title>
+    <link rel="edit" href="https://example.com/edit/1"/>
+    <link rel="alternate" href="https://example.com/posts/1"/>
+    <updated>2025-06-02T10:30:00+02:00</updated>
+    <summary>An &lt;em&gt;intro&lt;/em&gt; post</summary>
+  </entry>
+</feed>"#;
+
+    #[test]
+    fn parses_rss() {
+        let feed = parse_feed(RSS).unwrap();
+
+        assert_eq!(feed.title.as_deref(), Some("Example News"));
+        assert_eq!(feed.items.len(), 2);
+        let latest = &feed.items[0];
+        assert_eq!(latest.title, "Second & latest");
+        assert_eq!(latest.link.as_deref(), Some("https://example.com/2"));
+        assert_eq!(
+            latest.published.as_deref(),
+            Some("2025-06-03T09:00:00+00:00")
+        );
+        assert_eq!(latest.summary.as_deref(), Some("Big news & more"));
+        assert_eq!(
+            feed.items[1].published.as_deref(),
+            Some("2025-06-01T08:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn parses_atom() {
+        let feed = parse_feed(ATOM).unwrap();
+
+        assert_eq!(feed.title.as_deref(), Some("Example Blog"));
+        let entry = &feed.items[0];
+        assert_eq!(entry.title, "Hello");
+        assert_eq!(entry.link.as_deref(), Some("https://example.com/posts/1"));
+        assert_eq!(
+            entry.published.as_deref(),
+            Some("2025-06-02T10:30:00+02:00")
+        );
+        assert_eq!(entry.summary.as_deref(), Some("An intro post"));
+    }
+
+    #[test]
+    fn rejects_non_feeds() {
+        assert!(parse_feed("<html><body>hi</body></html>")
+            .unwrap_err()
+            .contains("<html>"));
+        assert!(parse_feed("<rss><channel></rss>")
+            .unwrap_err()
+            .contains("mismatched"));
+        assert!(parse_feed("not xml at all").is_err());
+    }
+
+    #[test]
+    fn select_items_sorts_limits_and_filters() {
+        let items = parse_feed(RSS).unwrap().items;
+
+        let newest = select_items(items.clone(), 1, None);
+        assert_eq!(newest.len(), 1);
+        assert_eq!(newest[0].title, "Second & latest");
+
+        // Both sample items are far older than a day
+        assert!(select_items(items, 10, Some(24)).is_empty());
+    }
+
+    #[test]
+    fn summaries_are_truncated() {
+        let long = "word ".repeat(200);
+        let summary = clean_summary(&long).unwrap();
+        assert!(summary.ends_with("..."));
+        assert!(summary.chars().count() <= MAX_SUMMARY_CHARS + 3);
+    }
+
+    #[test]
+    fn validate_rejects_private_urls() {
+        let tool = FetchRssTool::new();
+        assert!(tool
+            .validate_args(&json!({"url": "http://localhost/feed.xml"}))
+            .is_err());
+        assert!(tool
+            .validate_args(&json!({"url": "https://example.com/feed.xml", "max_items": 5}))
+            .is_ok());
+    }
+}
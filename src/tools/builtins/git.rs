@@ -0,0 +1,490 @@
+//! Git built-in tool.
+//!
+//! Runs read-only git subcommands and returns their output.
+
+use crate::messages::ToolDefinition;
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::security::PathValidator;
+use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
+use acton_reactive::prelude::*;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// Subcommands the tool will run.
+const ALLOWED_SUBCOMMANDS: &[&str] = &[
+    "log",
+    "diff",
+    "status",
+    "blame",
+    "show",
+    "branch",
+    "rev-parse",
+];
+
+/// Options rejected for every subcommand.
+///
+/// They write output to files, read files outside the repository
+/// (`diff --no-index`, `blame --contents`), run commands defined in git
+/// config (`--ext-diff`, `--textconv`), or change where git finds its
+/// configuration and programs.
+const DENIED_OPTIONS: &[&str] = &[
+    "--output",
+    "--no-index",
+    "--contents",
+    "--ext-diff",
+    "--textconv",
+    "-c",
+    "--config",
+    "--config-env",
+    "--exec-path",
+    "--git-dir",
+    "--work-tree",
+];
+
+/// Subcommands that can run external diff and textconv programs; they are
+/// always run with `--no-ext-diff --no-textconv`.
+const DIFF_SUBCOMMANDS: &[&str] = &["diff", "log", "show"];
+
+/// `git branch` options that list branches and take a value.
+const BRANCH_VALUE_OPTIONS: &[&str] = &["--contains", "--no-contains", "--merged", "--no-merged"];
+
+/// `git branch` options that create, delete or modify branches.
+const BRANCH_WRITE_OPTIONS: &[&str] = &[
+    "-d",
+    "-D",
+    "--delete",
+    "-m",
+    "-M",
+    "--move",
+    "-c",
+    "-C",
+    "--copy",
+    "-f",
+    "--force",
+    "-u",
+    "--set-upstream-to",
+    "--unset-upstream",
+    "--edit-description",
+    "-t",
+    "--track",
+    "--no-track",
+];
+
+/// Git tool executor.
+///
+/// Wraps the `git` binary like [`BashTool`](super::BashTool) wraps the
+/// shell, but only runs the subcommands in the input schema's `enum`.
+#[derive(Debug, Clone)]
+pub struct GitTool {
+    /// Maximum bytes of stdout returned
+    max_output_bytes: usize,
+    /// Timeout for a single git invocation
+    timeout: Duration,
+}
+
+/// Git tool actor state.
+///
+/// This actor wraps the `GitTool` executor for per-agent tool spawning.
+#[acton_actor]
+pub struct GitToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for GitToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
+
+impl Default for GitTool {
+    fn default() -> Self {
+        Self {
+            max_output_bytes: 64 * 1024,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Arguments for the git tool.
+#[derive(Debug, Deserialize)]
+struct GitArgs {
+    /// The git subcommand
+    subcommand: String,
+    /// Extra arguments passed after the subcommand
+    #[serde(default)]
+    args: Vec<String>,
+    /// Directory to run git in
+    #[serde(default)]
+    path: Option<String>,
+}
+
+impl GitTool {
+    /// Creates a new git tool with default settings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of stdout bytes returned (default: 64KB).
+    #[must_use]
+    pub fn with_max_output_bytes(mut self, max_output_bytes: usize) -> Self {
+        self.max_output_bytes = max_output_bytes;
+        self
+    }
+
+    /// Returns the tool configuration for registration.
+    #[must_use]
+    pub fn config() -> ToolConfig {
+        ToolConfig::new(ToolDefinition {
+            name: "git".to_string(),
+            description: "Read git repository metadata: log, diff, status, blame, show, branch, or rev-parse. Returns git's output text. Cannot modify the repository.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "subcommand": {
+                        "type": "string",
+                        "enum": ALLOWED_SUBCOMMANDS,
+                        "description": "The git subcommand to run"
+                    },
+                    "args": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Arguments passed after the subcommand, e.g. [\"-n\", \"5\", \"--oneline\"]"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "Repository directory to run in (default: current directory)"
+                    }
+                },
+                "required": ["subcommand"]
+            }),
+        })
+    }
+
+    /// Rejects subcommands and arguments that could modify state.
+    fn check_command(subcommand: &str, args: &[String]) -> Result<(), ToolError> {
+        if !ALLOWED_SUBCOMMANDS.contains(&subcommand) {
+            return Err(ToolError::validation_failed(
+                "git",
+                format!(
+                    "subcommand '{subcommand}' is not allowed; use one of: {}",
+                    ALLOWED_SUBCOMMANDS.join(", ")
+                ),
+            ));
+        }
+
+        let denied = |arg: &&String| {
+            let option = arg.split('=').next().unwrap_or(arg);
+            // --output takes its path attached or as --output-directory
+            option.starts_with("--output") || DENIED_OPTIONS.contains(&option)
+        };
+        if let Some(arg) = args.iter().find(denied) {
+            return Err(ToolError::validation_failed(
+                "git",
+                format!("argument '{arg}' is not allowed"),
+            ));
+        }
+
+        if subcommand == "branch" {
+            Self::check_branch_args(args)?;
+        }
+
+        Ok(())
+    }
+
+    /// Allows `git branch` only to list branches.
+    ///
+    /// A bare name would create a branch, so positional arguments are only
+    /// accepted with `--list` or as the value of a filtering option.
+    fn check_branch_args(args: &[String]) -> Result<(), ToolError> {
+        let listing = args.iter().any(|arg| arg == "--list" || arg == "-l");
+        let mut expects_value = false;
+
+        for arg in args {
+            let option = arg.split('=').next().unwrap_or(arg);
+            if BRANCH_WRITE_OPTIONS.contains(&option) {
+                return Err(ToolError::validation_failed(
+                    "git",
+                    format!("branch option '{arg}' modifies branches and is not allowed"),
+                ));
+            }
+            if arg.starts_with('-') {
+                expects_value = BRANCH_VALUE_OPTIONS.contains(&arg.as_str());
+                continue;
+            }
+            if !listing && !expects_value {
+                return Err(ToolError::validation_failed(
+                    "git",
+                    format!("'git branch {arg}' would create a branch; use --list to filter"),
+                ));
+            }
+            expects_value = false;
+        }
+
+        Ok(())
+    }
+
+    /// Truncates `output` to at most `max_bytes`, on a character boundary.
+    fn truncate_output(output: &str, max_bytes: usize) -> (String, bool) {
+        if output.len() <= max_bytes {
+            return (output.to_string(), false);
+        }
+        let mut end = max_bytes;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        (
+            format!(
+                "{}\n\n... (output truncated, {} bytes total)",
+                &output[..end],
+                output.len()
+            ),
+            true,
+        )
+    }
+}
+
+impl ToolExecutorTrait for GitTool {
+    fn execute(&self, args: Value) -> ToolExecutionFuture {
+        let max_output_bytes = self.max_output_bytes;
+        let timeout_duration = self.timeout;
+
+        Box::pin(async move {
+            let args: GitArgs = serde_json::from_value(args).map_err(|e| {
+                ToolError::validation_failed("git", format!("invalid arguments: {e}"))
+            })?;
+
+            Self::check_command(&args.subcommand, &args.args)?;
+
+            let mut cmd = Command::new("git");
+            cmd.arg("--no-pager").arg(&args.subcommand);
+            if DIFF_SUBCOMMANDS.contains(&args.subcommand.as_str()) {
+                cmd.args(["--no-ext-diff", "--no-textconv"]);
+            }
+            cmd.args(&args.args)
+                .env("GIT_TERMINAL_PROMPT", "0")
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .stdin(Stdio::null())
+                .kill_on_drop(true);
+
+            // Validate path using PathValidator for security
+            if let Some(ref path) = args.path {
+                let validator = PathValidator::new();
+                let canonical_path = validator
+                    .validate_directory(Path::new(path))
+                    .map_err(|e| ToolError::validation_failed("git", e.to_string()))?;
+                cmd.current_dir(canonical_path);
+            }
+
+            let output = match timeout(timeout_duration, cmd.output()).await {
+                Ok(Ok(output)) => output,
+                Ok(Err(e)) => {
+                    return Err(ToolError::execution_failed(
+                        "git",
+                        format!("failed to run git: {e}"),
+                    ))
+                }
+                Err(_) => return Err(ToolError::timeout("git", timeout_duration)),
+            };
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(ToolError::execution_failed(
+                    "git",
+                    format!(
+                        "git {} exited with {}: {}",
+                        args.subcommand,
+                        output.status.code().unwrap_or(-1),
+                        stderr.trim()
+                    ),
+                ));
+            }
+
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let (output, truncated) = Self::truncate_output(&stdout, max_output_bytes);
+
+            Ok(json!({
+                "output": output,
+                "truncated": truncated
+            }))
+        })
+    }
+
+    fn validate_args(&self, args: &Value) -> Result<(), ToolError> {
+        let args: GitArgs = serde_json::from_value(args.clone())
+            .map_err(|e| ToolError::validation_failed("git", format!("invalid arguments: {e}")))?;
+
+        Self::check_command(&args.subcommand, &args.args)
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl ToolActor for GitToolActor {
+    fn name() -> &'static str {
+        "git"
+    }
+
+    fn definition() -> ToolDefinition {
+        GitTool::config().definition
+    }
+
+    async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
+        let mut builder = runtime.new_actor_with_name::<Self>("git_tool".to_string());
+
+        configure_tool_actor(&mut builder, |_| GitTool::new());
+
+        builder.start().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .env("GIT_AUTHOR_NAME", "Test")
+                .env("GIT_AUTHOR_EMAIL", "test@example.com")
+                .env("GIT_COMMITTER_NAME", "Test")
+                .env("GIT_COMMITTER_EMAIL", "test@example.com")
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q", "-b", "main"]);
+        std::fs::write(dir.path().join("notes.txt"), "first\n").unwrap();
+        git(&["add", "notes.txt"]);
+        git(&["commit", "-q", "-m", "Add notes"]);
+        dir
+    }
+
+    #[tokio::test]
+    async fn git_log_returns_output() {
+        let repo = sample_repo();
+
+        let tool = GitTool::new();
+        let result = tool
+            .execute(json!({
+                "subcommand": "log",
+                "args": ["--oneline"],
+                "path": repo.path().to_str().unwrap()
+            }))
+            .await
+            .unwrap();
+
+        assert!(result["output"].as_str().unwrap().contains("Add notes"));
+        assert!(!result["truncated"].as_bool().unwrap());
+    }
+
+    #[tokio::test]
+    async fn git_output_is_truncated() {
+        let repo = sample_repo();
+
+        let tool = GitTool::new().with_max_output_bytes(4);
+        let result = tool
+            .execute(json!({
+                "subcommand": "show",
+                "path": repo.path().to_str().unwrap()
+            }))
+            .await
+            .unwrap();
+
+        assert!(result["truncated"].as_bool().unwrap());
+        assert!(result["output"].as_str().unwrap().starts_with("comm"));
+    }
+
+    #[tokio::test]
+    async fn git_failure_reports_stderr() {
+        let repo = sample_repo();
+
+        let tool = GitTool::new();
+        let result = tool
+            .execute(json!({
+                "subcommand": "show",
+                "args": ["no-such-revision"],
+                "path": repo.path().to_str().unwrap()
+            }))
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("no-such-revision"));
+    }
+
+    #[tokio::test]
+    async fn git_diff_no_index_cannot_read_outside_repository() {
+        let repo = sample_repo();
+        let outside = TempDir::new().unwrap();
+        let secret = outside.path().join("secret.txt");
+        std::fs::write(&secret, "secret\n").unwrap();
+
+        let result = GitTool::new()
+            .execute(json!({
+                "subcommand": "diff",
+                "args": ["--no-index", secret.to_str().unwrap(), "/dev/null"],
+                "path": repo.path().to_str().unwrap()
+            }))
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("--no-index"));
+    }
+
+    #[test]
+    fn git_rejects_disallowed_commands() {
+        let tool = GitTool::new();
+
+        for args in [
+            json!({"subcommand": "push"}),
+            json!({"subcommand": "remote", "args": ["set-url", "origin", "x"]}),
+            json!({"subcommand": "diff", "args": ["--output=/tmp/x"]}),
+            json!({"subcommand": "diff", "args": ["--no-index", "/etc/shadow", "/dev/null"]}),
+            json!({"subcommand": "diff", "args": ["--ext-diff"]}),
+            json!({"subcommand": "log", "args": ["-p", "--textconv"]}),
+            json!({"subcommand": "blame", "args": ["--contents=/etc/shadow", "notes.txt"]}),
+            json!({"subcommand": "show", "args": ["--exec-path=/tmp"]}),
+            json!({"subcommand": "log", "args": ["-c"]}),
+            json!({"subcommand": "branch", "args": ["new-branch"]}),
+            json!({"subcommand": "branch", "args": ["-D", "main"]}),
+        ] {
+            assert!(tool.validate_args(&args).is_err(), "{args}");
+        }
+
+        for args in [
+            json!({"subcommand": "status", "args": ["--short"]}),
+            json!({"subcommand": "diff", "args": ["--stat", "HEAD"]}),
+            json!({"subcommand": "branch", "args": ["-a", "--contains", "HEAD"]}),
+            json!({"subcommand": "branch", "args": ["--list", "feat*"]}),
+        ] {
+            assert!(tool.validate_args(&args).is_ok(), "{args}");
+        }
+    }
+
+    #[test]
+    fn git_schema_enumerates_subcommands() {
+        let definition = GitTool::config().definition;
+
+        assert!(definition
+            .validate_against_schema(&json!({"subcommand": "blame", "args": ["notes.txt"]}))
+            .is_ok());
+        assert!(definition
+            .validate_against_schema(&json!({"subcommand": "push"}))
+            .is_err());
+    }
+}
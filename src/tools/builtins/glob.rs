@@ -3,7 +3,7 @@
 //! Finds files matching a glob pattern.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
 use crate::tools::security::PathValidator;
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
@@ -22,7 +22,20 @@ pub struct GlobTool;
 ///
 /// This actor wraps the `GlobTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct GlobToolActor;
+pub struct GlobToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for GlobToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 /// Arguments for the glob tool.
 #[derive(Debug, Deserialize)]
@@ -170,29 +183,7 @@ impl ToolActor for GlobToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("glob_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = GlobTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| GlobTool::new());
 
         builder.start().await
     }
@@ -341,4 +332,15 @@ mod tests {
             .unwrap()
             .contains(&json!("pattern")));
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(&GlobTool::config().definition)
+            .valid_example(json!({"pattern": "**/*.rs"}))
+            .valid_example(json!({"pattern": "*.md", "path": "/tmp"}))
+            .invalid_example(json!({"path": "/tmp"}), "pattern is required")
+            .invalid_example(json!({"pattern": ["*.rs"]}), "pattern must be a string")
+            .run()
+            .assert_success();
+    }
 }
@@ -3,13 +3,14 @@
 //! Searches file contents with regex support.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
 use crate::tools::security::PathValidator;
-use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
+use crate::tools::{CoercionRules, ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::BTreeMap;
 use std::path::Path;
 use walkdir::WalkDir;
 
@@ -23,7 +24,20 @@ pub struct GrepTool;
 ///
 /// This actor wraps the `GrepTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct GrepToolActor;
+pub struct GrepToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for GrepToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 /// Arguments for the grep tool.
 #[derive(Debug, Deserialize)]
@@ -39,9 +53,60 @@ struct GrepArgs {
     /// Number of context lines before and after each match
     #[serde(default)]
     context_lines: Option<usize>,
+    /// Number of context lines before each match (overrides `context_lines`)
+    #[serde(default)]
+    context_lines_before: Option<u32>,
+    /// Number of context lines after each match (overrides `context_lines`)
+    #[serde(default)]
+    context_lines_after: Option<u32>,
     /// Case insensitive search
     #[serde(default)]
     ignore_case: Option<bool>,
+    /// Match against whole files so patterns can span lines
+    #[serde(default)]
+    multiline: Option<bool>,
+    /// Maximum number of matches to return
+    #[serde(default)]
+    max_matches: Option<usize>,
+}
+
+impl GrepArgs {
+    /// Compiles the search pattern with the requested flags.
+    fn regex(&self) -> Result<Regex, ToolError> {
+        RegexBuilder::new(&self.pattern)
+            .case_insensitive(self.ignore_case.unwrap_or(false))
+            .multi_line(self.multiline.unwrap_or(false))
+            .build()
+            .map_err(|e| {
+                ToolError::validation_failed("grep", format!("invalid regex pattern: {e}"))
+            })
+    }
+
+    /// Returns how each file is searched.
+    fn search_options(&self) -> SearchOptions {
+        let context = self.context_lines.unwrap_or(0);
+        let side = |lines: Option<u32>| {
+            lines
+                .map_or(context, |lines| lines as usize)
+                .min(MAX_CONTEXT_LINES)
+        };
+        SearchOptions {
+            before: side(self.context_lines_before),
+            after: side(self.context_lines_after),
+            multiline: self.multiline.unwrap_or(false),
+        }
+    }
+}
+
+/// How a single file is searched.
+#[derive(Debug, Clone, Copy)]
+struct SearchOptions {
+    /// Context lines to include before each match
+    before: usize,
+    /// Context lines to include after each match
+    after: usize,
+    /// Whether the pattern is matched against the whole file
+    multiline: bool,
 }
 
 /// A single match result.
@@ -51,6 +116,9 @@ struct GrepMatch {
     file: String,
     /// Line number (1-indexed)
     line: usize,
+    /// Last line of a match spanning several lines (1-indexed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
     /// Matched line content
     content: String,
     /// Context lines before the match
@@ -61,9 +129,15 @@ struct GrepMatch {
     after: Vec<String>,
 }
 
+/// Lines of one file to print, by line number, with whether each matched.
+type NumberedLines<'a> = BTreeMap<usize, (&'a str, bool)>;
+
 /// Maximum number of matches to return.
 const MAX_MATCHES: usize = 500;
 
+/// Maximum number of context lines on each side of a match.
+const MAX_CONTEXT_LINES: usize = 10;
+
 /// Maximum file size to search (10MB).
 const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
@@ -79,9 +153,9 @@ impl GrepTool {
     pub fn config() -> ToolConfig {
         use crate::messages::ToolDefinition;
 
-        ToolConfig::new(ToolDefinition {
+        let definition = ToolDefinition {
             name: "grep".to_string(),
-            description: "Search file contents using regex patterns. Returns matching lines with optional context.".to_string(),
+            description: "Search file contents using regex patterns. Returns matching lines with optional context; with context, `output` lists them grep-style.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
@@ -103,14 +177,38 @@ impl GrepTool {
                         "minimum": 0,
                         "maximum": 10
                     },
+                    "context_lines_before": {
+                        "type": "integer",
+                        "description": "Number of context lines before each match, like grep -B (overrides context_lines)",
+                        "minimum": 0,
+                        "maximum": 10
+                    },
+                    "context_lines_after": {
+                        "type": "integer",
+                        "description": "Number of context lines after each match, like grep -A (overrides context_lines)",
+                        "minimum": 0,
+                        "maximum": 10
+                    },
                     "ignore_case": {
                         "type": "boolean",
                         "description": "Case insensitive search (default: false)"
+                    },
+                    "multiline": {
+                        "type": "boolean",
+                        "description": "Match against whole files so patterns can span lines (use \\n); ^ and $ match at line boundaries (default: false)"
+                    },
+                    "max_matches": {
+                        "type": "integer",
+                        "description": "Maximum number of matches to return (default: 500)",
+                        "minimum": 1,
+                        "maximum": 500
                     }
                 },
                 "required": ["pattern"]
             }),
-        })
+        };
+        let coercion = CoercionRules::from_schema(&definition.input_schema);
+        ToolConfig::new(definition).with_input_coercion(coercion)
     }
 
     /// Checks if a filename matches a glob-like pattern.
@@ -136,45 +234,116 @@ impl GrepTool {
     fn search_file(
         path: &Path,
         regex: &Regex,
-        context_lines: usize,
+        options: SearchOptions,
     ) -> Result<Vec<GrepMatch>, ToolError> {
         let content = std::fs::read_to_string(path).map_err(|e| {
             ToolError::execution_failed("grep", format!("failed to read {}: {e}", path.display()))
         })?;
 
         let lines: Vec<&str> = content.lines().collect();
-        let mut matches = Vec::new();
-
-        for (idx, line) in lines.iter().enumerate() {
-            if regex.is_match(line) {
-                let before: Vec<String> = if context_lines > 0 && idx > 0 {
-                    let start = idx.saturating_sub(context_lines);
-                    lines[start..idx].iter().map(|s| (*s).to_string()).collect()
-                } else {
-                    Vec::new()
-                };
-
-                let after: Vec<String> = if context_lines > 0 {
-                    let end = (idx + 1 + context_lines).min(lines.len());
-                    lines[idx + 1..end]
-                        .iter()
-                        .map(|s| (*s).to_string())
-                        .collect()
-                } else {
-                    Vec::new()
-                };
-
-                matches.push(GrepMatch {
+        let spans = if options.multiline {
+            Self::multiline_spans(&content, regex, lines.len())
+        } else {
+            lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| regex.is_match(line))
+                .map(|(idx, _)| (idx, idx))
+                .collect()
+        };
+
+        let to_strings = |slice: &[&str]| slice.iter().map(|s| (*s).to_string()).collect();
+        Ok(spans
+            .into_iter()
+            .map(|(first, last)| {
+                let after_end = (last + 1 + options.after).min(lines.len());
+                GrepMatch {
                     file: path.to_string_lossy().to_string(),
-                    line: idx + 1,
-                    content: (*line).to_string(),
-                    before,
-                    after,
-                });
+                    line: first + 1,
+                    end_line: (last > first).then_some(last + 1),
+                    content: lines[first..=last].join("\n"),
+                    before: to_strings(&lines[first.saturating_sub(options.before)..first]),
+                    after: to_strings(&lines[last + 1..after_end]),
+                }
+            })
+            .collect())
+    }
+
+    /// Returns the first and last line index of each match of `regex`
+    /// against the whole of `content`.
+    ///
+    /// Matches starting on a line already covered by the previous match are
+    /// merged into it, so each line is reported once.
+    fn multiline_spans(content: &str, regex: &Regex, line_count: usize) -> Vec<(usize, usize)> {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset) - 1;
+
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        for found in regex.find_iter(content) {
+            let first = line_of(found.start());
+            if first >= line_count {
+                continue;
+            }
+            // A match ending with a newline does not extend onto the next line
+            let last =
+                line_of(found.end().saturating_sub(1).max(found.start())).min(line_count - 1);
+            match spans.last_mut() {
+                Some((_, previous_last)) if first <= *previous_last => {
+                    *previous_last = (*previous_last).max(last);
+                }
+                _ => spans.push((first, last)),
             }
         }
+        spans
+    }
 
-        Ok(matches)
+    /// Renders matches with their context lines the way `grep -A/-B` does.
+    ///
+    /// Matched lines are prefixed `file:line:`, context lines `file-line-`,
+    /// and non-contiguous groups are separated by `--`.
+    fn format_with_context(matches: &[GrepMatch]) -> String {
+        // Lines to print per file, in order; a line is a match if any match
+        // covers it, even when it is also context of a neighbouring match
+        let mut files: Vec<(&str, NumberedLines<'_>)> = Vec::new();
+        for m in matches {
+            if files.last().is_none_or(|(file, _)| *file != m.file) {
+                files.push((&m.file, BTreeMap::new()));
+            }
+            let Some((_, lines)) = files.last_mut() else {
+                continue;
+            };
+            let matched = m.line..=m.end_line.unwrap_or(m.line);
+            let texts = m
+                .before
+                .iter()
+                .map(String::as_str)
+                .chain(m.content.split('\n'))
+                .chain(m.after.iter().map(String::as_str));
+            for (line, text) in (m.line - m.before.len()..).zip(texts) {
+                let is_match = matched.contains(&line);
+                lines
+                    .entry(line)
+                    .and_modify(|(_, seen_match)| *seen_match |= is_match)
+                    .or_insert((text, is_match));
+            }
+        }
+
+        let mut output: Vec<String> = Vec::new();
+        for (file, lines) in &files {
+            let mut previous: Option<usize> = None;
+            for (&line, &(text, is_match)) in lines {
+                let contiguous = previous.is_some_and(|previous| line == previous + 1);
+                if !output.is_empty() && !contiguous {
+                    output.push("--".to_string());
+                }
+                let separator = if is_match { ':' } else { '-' };
+                output.push(format!("{file}{separator}{line}{separator}{text}"));
+                previous = Some(line);
+            }
+        }
+        output.join("\n")
     }
 }
 
@@ -185,16 +354,7 @@ impl ToolExecutorTrait for GrepTool {
                 ToolError::validation_failed("grep", format!("invalid arguments: {e}"))
             })?;
 
-            // Build regex
-            let pattern = if args.ignore_case.unwrap_or(false) {
-                format!("(?i){}", args.pattern)
-            } else {
-                args.pattern.clone()
-            };
-
-            let regex = Regex::new(&pattern).map_err(|e| {
-                ToolError::validation_failed("grep", format!("invalid regex pattern: {e}"))
-            })?;
+            let regex = args.regex()?;
 
             // Determine and validate search path
             let validator = PathValidator::new();
@@ -220,7 +380,8 @@ impl ToolExecutorTrait for GrepTool {
                 })?,
             };
 
-            let context_lines = args.context_lines.unwrap_or(0).min(10);
+            let options = args.search_options();
+            let max_matches = args.max_matches.unwrap_or(MAX_MATCHES).min(MAX_MATCHES);
             let glob_pattern = args.glob.as_deref();
 
             let mut all_matches = Vec::new();
@@ -229,8 +390,9 @@ impl ToolExecutorTrait for GrepTool {
 
             // Search single file or directory
             if search_path.is_file() {
-                if let Ok(matches) = Self::search_file(&search_path, &regex, context_lines) {
-                    all_matches.extend(matches);
+                if let Ok(matches) = Self::search_file(&search_path, &regex, options) {
+                    truncated = matches.len() > max_matches;
+                    all_matches.extend(matches.into_iter().take(max_matches));
                     files_searched = 1;
                 }
             } else {
@@ -240,7 +402,7 @@ impl ToolExecutorTrait for GrepTool {
                     .into_iter()
                     .filter_map(Result::ok)
                 {
-                    if all_matches.len() >= MAX_MATCHES {
+                    if all_matches.len() >= max_matches {
                         truncated = true;
                         break;
                     }
@@ -286,8 +448,8 @@ impl ToolExecutorTrait for GrepTool {
                         }
                     }
 
-                    if let Ok(matches) = Self::search_file(path, &regex, context_lines) {
-                        let remaining = MAX_MATCHES - all_matches.len();
+                    if let Ok(matches) = Self::search_file(path, &regex, options) {
+                        let remaining = max_matches - all_matches.len();
                         if matches.len() > remaining {
                             all_matches.extend(matches.into_iter().take(remaining));
                             truncated = true;
@@ -299,13 +461,17 @@ impl ToolExecutorTrait for GrepTool {
                 }
             }
 
-            Ok(json!({
+            let mut result = json!({
                 "matches": all_matches,
                 "count": all_matches.len(),
                 "files_searched": files_searched,
                 "truncated": truncated,
                 "pattern": args.pattern
-            }))
+            });
+            if options.before > 0 || options.after > 0 {
+                result["output"] = Value::String(Self::format_with_context(&all_matches));
+            }
+            Ok(result)
         })
     }
 
@@ -321,15 +487,7 @@ impl ToolExecutorTrait for GrepTool {
         }
 
         // Validate regex
-        let pattern = if args.ignore_case.unwrap_or(false) {
-            format!("(?i){}", args.pattern)
-        } else {
-            args.pattern
-        };
-
-        Regex::new(&pattern).map_err(|e| {
-            ToolError::validation_failed("grep", format!("invalid regex pattern: {e}"))
-        })?;
+        args.regex()?;
 
         Ok(())
     }
@@ -347,29 +505,7 @@ impl ToolActor for GrepToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("grep_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = GrepTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| GrepTool::new());
 
         builder.start().await
     }
@@ -435,6 +571,93 @@ mod tests {
         assert_eq!(m["after"].as_array().unwrap().len(), 2);
     }
 
+    #[tokio::test]
+    async fn grep_context_lines_after_renders_grep_output() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("test.txt");
+        fs::write(&file, "match 1\na\nb\nc\nd\nmatch 2\nmatch 3\ne").unwrap();
+
+        let result = GrepTool::new()
+            .execute(json!({
+                "pattern": "match",
+                "path": file.to_str().unwrap(),
+                "context_lines_after": 2
+            }))
+            .await
+            .unwrap();
+
+        let matches = result["matches"].as_array().unwrap();
+        assert_eq!(matches[0]["after"], json!(["a", "b"]));
+        assert!(matches[0].get("before").is_none());
+        let f = file.to_str().unwrap();
+        assert_eq!(
+            result["output"],
+            [
+                format!("{f}:1:match 1"),
+                format!("{f}-2-a"),
+                format!("{f}-3-b"),
+                "--".to_string(),
+                format!("{f}:6:match 2"),
+                format!("{f}:7:match 3"),
+                format!("{f}-8-e"),
+            ]
+            .join("\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn grep_multiline_matches_across_lines() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("test.rs");
+        fs::write(&file, "fn main() {\n    run();\n}\nfn other() {}\n").unwrap();
+        let args = |multiline| {
+            json!({
+                "pattern": "\\{\\n\\s+run",
+                "path": file.to_str().unwrap(),
+                "multiline": multiline
+            })
+        };
+
+        let single = GrepTool::new().execute(args(false)).await.unwrap();
+        assert_eq!(single["count"], 0);
+
+        let result = GrepTool::new().execute(args(true)).await.unwrap();
+        assert_eq!(result["count"], 1);
+        let m = &result["matches"][0];
+        assert_eq!(m["line"], 1);
+        assert_eq!(m["end_line"], 2);
+        assert_eq!(m["content"], "fn main() {\n    run();");
+
+        // ^ anchors at every line start
+        let anchored = GrepTool::new()
+            .execute(json!({
+                "pattern": "^fn",
+                "path": file.to_str().unwrap(),
+                "multiline": true
+            }))
+            .await
+            .unwrap();
+        assert_eq!(anchored["count"], 2);
+    }
+
+    #[tokio::test]
+    async fn grep_max_matches_truncates() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("test.txt"), "hit\nhit\nhit").unwrap();
+
+        let result = GrepTool::new()
+            .execute(json!({
+                "pattern": "hit",
+                "path": dir.path().join("test.txt").to_str().unwrap(),
+                "max_matches": 2
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(result["count"], 2);
+        assert!(result["truncated"].as_bool().unwrap());
+    }
+
     #[tokio::test]
     async fn grep_case_insensitive() {
         let dir = TempDir::new().unwrap();
@@ -583,5 +806,41 @@ mod tests {
         assert!(schema["properties"]["glob"].is_object());
         assert!(schema["properties"]["context_lines"].is_object());
         assert!(schema["properties"]["ignore_case"].is_object());
+        assert!(schema["properties"]["context_lines_before"].is_object());
+        assert!(schema["properties"]["context_lines_after"].is_object());
+        assert!(schema["properties"]["multiline"].is_object());
+        assert!(schema["properties"]["max_matches"].is_object());
+    }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(&GrepTool::config().definition)
+            .valid_example(json!({"pattern": "fn main"}))
+            .valid_example(json!({
+                "pattern": "todo",
+                "path": "/tmp",
+                "glob": "*.rs",
+                "context_lines": 10,
+                "context_lines_before": 0,
+                "context_lines_after": 3,
+                "ignore_case": true,
+                "multiline": true,
+                "max_matches": 50
+            }))
+            .invalid_example(json!({}), "pattern is required")
+            .invalid_example(
+                json!({"pattern": "x", "context_lines": 11}),
+                "context_lines above maximum",
+            )
+            .invalid_example(
+                json!({"pattern": "x", "ignore_case": "true"}),
+                "ignore_case must be a boolean",
+            )
+            .invalid_example(
+                json!({"pattern": "x", "max_matches": 0}),
+                "max_matches below minimum",
+            )
+            .run()
+            .assert_success();
     }
 }
@@ -0,0 +1,974 @@
+//! JSON query built-in tool.
+//!
+//! Extracts and reshapes data from a JSON document with a subset of jq
+//! syntax. The evaluator is pure Rust and touches neither the filesystem nor
+//! the network.
+
+use crate::messages::ToolDefinition;
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
+use acton_reactive::prelude::*;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::cmp::Ordering;
+
+/// Maximum query length in characters.
+const MAX_QUERY_LENGTH: usize = 1000;
+
+/// JSON query tool executor.
+///
+/// Query failures are returned as `{"error": ...}` results rather than tool
+/// errors, so the model can correct the query and try again.
+#[derive(Debug, Default, Clone)]
+pub struct JsonTool;
+
+/// JSON query tool actor state.
+///
+/// This actor wraps the `JsonTool` executor for per-agent tool spawning.
+#[acton_actor]
+pub struct JsonToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for JsonToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
+
+/// Arguments for the json_query tool.
+#[derive(Debug, Deserialize)]
+struct JsonQueryArgs {
+    /// The JSON document, as text
+    json: String,
+    /// The jq-style query
+    query: String,
+}
+
+impl JsonTool {
+    /// Creates a new JSON query tool.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the tool configuration for registration.
+    #[must_use]
+    pub fn config() -> ToolConfig {
+        ToolConfig::new(ToolDefinition {
+            name: "json_query".to_string(),
+            description: "Query and transform a JSON document with a subset of jq syntax. Returns every output of the query in `results`.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "json": {
+                        "type": "string",
+                        "description": "The JSON document to query, as text"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "jq-style query. Supported: `.`, `.field`, `.\"key\"`, `.[n]` (negative counts from the end), `.[m:n]`, `.[]`, pipes `|`, `,`, `[...]`, `{key: ...}`, comparisons (== != < <= > >=), `and`, `or`, literals, and the functions keys, length, type, not, first, last, sort, add, empty, map(f), select(f) and has(key). Example: `.users[] | select(.age > 30) | .name`",
+                        "minLength": 1,
+                        "maxLength": MAX_QUERY_LENGTH
+                    }
+                },
+                "required": ["json", "query"]
+            }),
+        })
+    }
+
+    /// Parses `document` and runs `query` against it.
+    fn run(document: &str, query: &str) -> Result<Vec<Value>, String> {
+        let input: Value =
+            serde_json::from_str(document).map_err(|e| format!("invalid JSON input: {e}"))?;
+        let filter = Parser::parse(query)?;
+        filter.eval(&input)
+    }
+}
+
+impl ToolExecutorTrait for JsonTool {
+    fn execute(&self, args: Value) -> ToolExecutionFuture {
+        Box::pin(async move {
+            let args: JsonQueryArgs = serde_json::from_value(args).map_err(|e| {
+                ToolError::validation_failed("json_query", format!("invalid arguments: {e}"))
+            })?;
+
+            Ok(match Self::run(&args.json, &args.query) {
+                Ok(results) => json!({ "results": results }),
+                Err(error) => json!({
+                    "error": error,
+                    "query": args.query
+                }),
+            })
+        })
+    }
+
+    fn validate_args(&self, args: &Value) -> Result<(), ToolError> {
+        let args: JsonQueryArgs = serde_json::from_value(args.clone()).map_err(|e| {
+            ToolError::validation_failed("json_query", format!("invalid arguments: {e}"))
+        })?;
+
+        if args.query.is_empty() {
+            return Err(ToolError::validation_failed(
+                "json_query",
+                "query cannot be empty",
+            ));
+        }
+        if args.query.chars().count() > MAX_QUERY_LENGTH {
+            return Err(ToolError::validation_failed(
+                "json_query",
+                format!("query is too long (max {MAX_QUERY_LENGTH} characters)"),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Query language
+// ---------------------------------------------------------------------------
+
+/// A lexical token of a query.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// `.` not directly followed by a name
+    Dot,
+    /// `.name`
+    Field(String),
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Compare(CompareOp),
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Pipe,
+    Comma,
+    Colon,
+    Semicolon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = query.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    let take_while = |start: usize, pred: fn(char) -> bool| {
+        let mut end = start;
+        while end < chars.len() && pred(chars[end]) {
+            end += 1;
+        }
+        end
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '.' if next.is_some_and(is_ident_start) => {
+                let end = take_while(i + 1, is_ident_char);
+                tokens.push(Token::Field(chars[i + 1..end].iter().collect()));
+                i = end;
+            }
+            '.' => {
+                tokens.push(Token::Dot);
+                i += 1;
+            }
+            '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err("unterminated string".to_string()),
+                        Some('"') => break,
+                        Some('\\') => {
+                            let escaped = chars.get(i + 1).ok_or("unterminated string")?;
+                            s.push(match escaped {
+                                'n' => '\n',
+                                't' => '\t',
+                                other => *other,
+                            });
+                            i += 2;
+                        }
+                        Some(ch) => {
+                            s.push(*ch);
+                            i += 1;
+                        }
+                    }
+                }
+                tokens.push(Token::Str(s));
+                i += 1;
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let end = take_while(i + 1, |ch| ch.is_ascii_digit() || ch == '.');
+                let text: String = chars[i..end].iter().collect();
+                let n = text
+                    .parse()
+                    .map_err(|_| format!("invalid number '{text}'"))?;
+                tokens.push(Token::Num(n));
+                i = end;
+            }
+            c if is_ident_start(c) => {
+                let end = take_while(i, is_ident_char);
+                tokens.push(Token::Ident(chars[i..end].iter().collect()));
+                i = end;
+            }
+            '=' | '!' | '<' | '>' => {
+                let op = match (c, next) {
+                    ('=', Some('=')) => CompareOp::Eq,
+                    ('!', Some('=')) => CompareOp::Ne,
+                    ('<', Some('=')) => CompareOp::Le,
+                    ('>', Some('=')) => CompareOp::Ge,
+                    ('<', _) => CompareOp::Lt,
+                    ('>', _) => CompareOp::Gt,
+                    _ => return Err(format!("unexpected '{c}' at position {i}")),
+                };
+                i += if matches!(op, CompareOp::Lt | CompareOp::Gt) {
+                    1
+                } else {
+                    2
+                };
+                tokens.push(Token::Compare(op));
+            }
+            _ => {
+                tokens.push(match c {
+                    '[' => Token::LBracket,
+                    ']' => Token::RBracket,
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '{' => Token::LBrace,
+                    '}' => Token::RBrace,
+                    '|' => Token::Pipe,
+                    ',' => Token::Comma,
+                    ':' => Token::Colon,
+                    ';' => Token::Semicolon,
+                    _ => return Err(format!("unexpected '{c}' at position {i}")),
+                });
+                i += 1;
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// A parsed query.
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Identity,
+    Literal(Value),
+    Field(Box<Filter>, String),
+    Index(Box<Filter>, i64),
+    Slice(Box<Filter>, Option<i64>, Option<i64>),
+    Iterate(Box<Filter>),
+    Pipe(Box<Filter>, Box<Filter>),
+    Comma(Box<Filter>, Box<Filter>),
+    Compare(Box<Filter>, CompareOp, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Array(Option<Box<Filter>>),
+    Object(Vec<(String, Filter)>),
+    Call(String, Vec<Filter>),
+}
+
+/// Functions and their arity.
+const FUNCTIONS: &[(&str, usize)] = &[
+    ("keys", 0),
+    ("length", 0),
+    ("type", 0),
+    ("not", 0),
+    ("first", 0),
+    ("last", 0),
+    ("sort", 0),
+    ("add", 0),
+    ("empty", 0),
+    ("map", 1),
+    ("select", 1),
+    ("has", 1),
+];
+
+/// Recursive-descent parser over [`Token`]s.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn parse(query: &str) -> Result<Filter, String> {
+        let mut parser = Self {
+            tokens: tokenize(query)?,
+            pos: 0,
+        };
+        let filter = parser.pipe()?;
+        match parser.peek() {
+            None => Ok(filter),
+            Some(token) => Err(format!("unexpected {token:?} after complete query")),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(format!("expected {token:?}, found {:?}", self.peek()))
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(name)) if name == keyword) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn pipe(&mut self) -> Result<Filter, String> {
+        let mut lhs = self.comma()?;
+        while self.eat(&Token::Pipe) {
+            lhs = Filter::Pipe(Box::new(lhs), Box::new(self.comma()?));
+        }
+        Ok(lhs)
+    }
+
+    fn comma(&mut self) -> Result<Filter, String> {
+        let mut lhs = self.or()?;
+        while self.eat(&Token::Comma) {
+            lhs = Filter::Comma(Box::new(lhs), Box::new(self.or()?));
+        }
+        Ok(lhs)
+    }
+
+    fn or(&mut self) -> Result<Filter, String> {
+        let mut lhs = self.and()?;
+        while self.eat_keyword("or") {
+            lhs = Filter::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Filter, String> {
+        let mut lhs = self.compare()?;
+        while self.eat_keyword("and") {
+            lhs = Filter::And(Box::new(lhs), Box::new(self.compare()?));
+        }
+        Ok(lhs)
+    }
+
+    fn compare(&mut self) -> Result<Filter, String> {
+        let lhs = self.postfix()?;
+        if let Some(Token::Compare(op)) = self.peek().cloned() {
+            self.pos += 1;
+            let rhs = self.postfix()?;
+            return Ok(Filter::Compare(Box::new(lhs), op, Box::new(rhs)));
+        }
+        Ok(lhs)
+    }
+
+    fn postfix(&mut self) -> Result<Filter, String> {
+        let mut filter = self.primary()?;
+        loop {
+            match self.peek().cloned() {
+                Some(Token::Field(name)) => {
+                    self.pos += 1;
+                    filter = Filter::Field(Box::new(filter), name);
+                }
+                Some(Token::Dot) => match self.tokens.get(self.pos + 1) {
+                    Some(Token::Str(name)) => {
+                        let name = name.clone();
+                        self.pos += 2;
+                        filter = Filter::Field(Box::new(filter), name);
+                    }
+                    Some(Token::LBracket) => self.pos += 1,
+                    _ => return Err("expected a field name or '[' after '.'".to_string()),
+                },
+                Some(Token::LBracket) => {
+                    self.pos += 1;
+                    filter = self.bracket_suffix(filter)?;
+                }
+                _ => return Ok(filter),
+            }
+        }
+    }
+
+    /// Parses the inside of `[...]` after a filter, with `[` consumed.
+    fn bracket_suffix(&mut self, filter: Filter) -> Result<Filter, String> {
+        let filter = Box::new(filter);
+        let result = match self.next() {
+            Some(Token::RBracket) => return Ok(Filter::Iterate(filter)),
+            Some(Token::Str(name)) => Filter::Field(filter, name),
+            Some(Token::Colon) => Filter::Slice(filter, None, Some(self.integer()?)),
+            Some(Token::Num(n)) => {
+                let n = as_integer(n)?;
+                if self.eat(&Token::Colon) {
+                    let end = if self.peek() == Some(&Token::RBracket) {
+                        None
+                    } else {
+                        Some(self.integer()?)
+                    };
+                    Filter::Slice(filter, Some(n), end)
+                } else {
+                    Filter::Index(filter, n)
+                }
+            }
+            other => return Err(format!("unsupported index {other:?}")),
+        };
+        self.expect(&Token::RBracket)?;
+        Ok(result)
+    }
+
+    fn integer(&mut self) -> Result<i64, String> {
+        match self.next() {
+            Some(Token::Num(n)) => as_integer(n),
+            other => Err(format!("expected an integer, found {other:?}")),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Filter, String> {
+        match self.next() {
+            Some(Token::Dot) => match self.peek().cloned() {
+                Some(Token::Str(name)) => {
+                    self.pos += 1;
+                    Ok(Filter::Field(Box::new(Filter::Identity), name))
+                }
+                _ => Ok(Filter::Identity),
+            },
+            Some(Token::Field(name)) => Ok(Filter::Field(Box::new(Filter::Identity), name)),
+            Some(Token::Str(s)) => Ok(Filter::Literal(Value::String(s))),
+            Some(Token::Num(n)) => Ok(Filter::Literal(json!(n))),
+            Some(Token::LParen) => {
+                let inner = self.pipe()?;
+                self.expect(&Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::LBracket) => {
+                if self.eat(&Token::RBracket) {
+                    return Ok(Filter::Array(None));
+                }
+                let inner = self.pipe()?;
+                self.expect(&Token::RBracket)?;
+                Ok(Filter::Array(Some(Box::new(inner))))
+            }
+            Some(Token::LBrace) => self.object(),
+            Some(Token::Ident(name)) => self.ident(name),
+            other => Err(format!("unexpected {other:?}")),
+        }
+    }
+
+    fn ident(&mut self, name: String) -> Result<Filter, String> {
+        match name.as_str() {
+            "true" => return Ok(Filter::Literal(Value::Bool(true))),
+            "false" => return Ok(Filter::Literal(Value::Bool(false))),
+            "null" => return Ok(Filter::Literal(Value::Null)),
+            _ => {}
+        }
+
+        let arity = FUNCTIONS
+            .iter()
+            .find(|(function, _)| *function == name)
+            .map(|(_, arity)| *arity)
+            .ok_or_else(|| format!("unknown function '{name}'"))?;
+
+        let mut args = Vec::new();
+        if self.eat(&Token::LParen) {
+            args.push(self.pipe()?);
+            while self.eat(&Token::Semicolon) {
+                args.push(self.pipe()?);
+            }
+            self.expect(&Token::RParen)?;
+        }
+        if args.len() != arity {
+            return Err(format!(
+                "{name} takes {arity} argument(s), got {}",
+                args.len()
+            ));
+        }
+        Ok(Filter::Call(name, args))
+    }
+
+    /// Parses `{key: value, ...}` with `{` consumed.
+    fn object(&mut self) -> Result<Filter, String> {
+        let mut entries = Vec::new();
+        if self.eat(&Token::RBrace) {
+            return Ok(Filter::Object(entries));
+        }
+        loop {
+            let key = match self.next() {
+                Some(Token::Ident(key)) | Some(Token::Str(key)) => key,
+                other => return Err(format!("expected an object key, found {other:?}")),
+            };
+            let value = if self.eat(&Token::Colon) {
+                self.or()?
+            } else {
+                Filter::Field(Box::new(Filter::Identity), key.clone())
+            };
+            entries.push((key, value));
+            if self.eat(&Token::RBrace) {
+                return Ok(Filter::Object(entries));
+            }
+            self.expect(&Token::Comma)?;
+        }
+    }
+}
+
+fn as_integer(n: f64) -> Result<i64, String> {
+    if n.fract() == 0.0 {
+        Ok(n as i64)
+    } else {
+        Err(format!("expected an integer index, found {n}"))
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+/// Orders values like jq: null < false < true < numbers < strings < arrays
+/// < objects.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Bool(false) => 1,
+            Value::Bool(true) => 2,
+            Value::Number(_) => 3,
+            Value::String(_) => 4,
+            Value::Array(_) => 5,
+            Value::Object(_) => 6,
+        }
+    }
+
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .partial_cmp(&y.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Array(x), Value::Array(y)) => x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| compare_values(x, y))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| x.len().cmp(&y.len())),
+        (Value::Object(_), Value::Object(_)) => a.to_string().cmp(&b.to_string()),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// Resolves a possibly negative index against `len`.
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    let len = i64::try_from(len).ok()?;
+    let index = if index < 0 { len + index } else { index };
+    usize::try_from(index).ok().filter(|&i| (i as i64) < len)
+}
+
+fn slice_bounds(start: Option<i64>, end: Option<i64>, len: usize) -> (usize, usize) {
+    let clamp = |index: i64| {
+        let len = len as i64;
+        let index = if index < 0 { len + index } else { index };
+        index.clamp(0, len) as usize
+    };
+    let start = start.map_or(0, clamp);
+    let end = end.map_or(len, clamp);
+    (start, end.max(start))
+}
+
+impl Filter {
+    /// Runs the filter on `input`, returning every output.
+    fn eval(&self, input: &Value) -> Result<Vec<Value>, String> {
+        match self {
+            Self::Identity => Ok(vec![input.clone()]),
+            Self::Literal(value) => Ok(vec![value.clone()]),
+            Self::Field(inner, name) => inner
+                .eval(input)?
+                .into_iter()
+                .map(|value| match value {
+                    Value::Object(mut map) => Ok(map.remove(name).unwrap_or(Value::Null)),
+                    Value::Null => Ok(Value::Null),
+                    other => Err(format!(
+                        "cannot index {} with \"{name}\"",
+                        type_name(&other)
+                    )),
+                })
+                .collect(),
+            Self::Index(inner, index) => inner
+                .eval(input)?
+                .into_iter()
+                .map(|value| match value {
+                    Value::Array(items) => Ok(resolve_index(*index, items.len())
+                        .map_or(Value::Null, |i| items[i].clone())),
+                    Value::Null => Ok(Value::Null),
+                    other => Err(format!("cannot index {} with number", type_name(&other))),
+                })
+                .collect(),
+            Self::Slice(inner, start, end) => inner
+                .eval(input)?
+                .into_iter()
+                .map(|value| match value {
+                    Value::Array(items) => {
+                        let (start, end) = slice_bounds(*start, *end, items.len());
+                        Ok(Value::Array(items[start..end].to_vec()))
+                    }
+                    Value::String(s) => {
+                        let chars: Vec<char> = s.chars().collect();
+                        let (start, end) = slice_bounds(*start, *end, chars.len());
+                        Ok(Value::String(chars[start..end].iter().collect()))
+                    }
+                    Value::Null => Ok(Value::Null),
+                    other => Err(format!("cannot slice {}", type_name(&other))),
+                })
+                .collect(),
+            Self::Iterate(inner) => {
+                let mut out = Vec::new();
+                for value in inner.eval(input)? {
+                    match value {
+                        Value::Array(items) => out.extend(items),
+                        Value::Object(map) => out.extend(map.into_iter().map(|(_, v)| v)),
+                        other => return Err(format!("cannot iterate over {}", type_name(&other))),
+                    }
+                }
+                Ok(out)
+            }
+            Self::Pipe(lhs, rhs) => {
+                let mut out = Vec::new();
+                for value in lhs.eval(input)? {
+                    out.extend(rhs.eval(&value)?);
+                }
+                Ok(out)
+            }
+            Self::Comma(lhs, rhs) => {
+                let mut out = lhs.eval(input)?;
+                out.extend(rhs.eval(input)?);
+                Ok(out)
+            }
+            Self::Compare(lhs, op, rhs) => {
+                let rights = rhs.eval(input)?;
+                let mut out = Vec::new();
+                for left in lhs.eval(input)? {
+                    for right in &rights {
+                        let ordering = compare_values(&left, right);
+                        out.push(Value::Bool(match op {
+                            CompareOp::Eq => ordering.is_eq(),
+                            CompareOp::Ne => ordering.is_ne(),
+                            CompareOp::Lt => ordering.is_lt(),
+                            CompareOp::Le => ordering.is_le(),
+                            CompareOp::Gt => ordering.is_gt(),
+                            CompareOp::Ge => ordering.is_ge(),
+                        }));
+                    }
+                }
+                Ok(out)
+            }
+            Self::And(lhs, rhs) | Self::Or(lhs, rhs) => {
+                let is_and = matches!(self, Self::And(..));
+                let mut out = Vec::new();
+                for left in lhs.eval(input)? {
+                    // `and` stops at a false left side, `or` at a true one
+                    if is_truthy(&left) != is_and {
+                        out.push(Value::Bool(!is_and));
+                        continue;
+                    }
+                    for right in rhs.eval(input)? {
+                        out.push(Value::Bool(is_truthy(&right)));
+                    }
+                }
+                Ok(out)
+            }
+            Self::Array(None) => Ok(vec![Value::Array(Vec::new())]),
+            Self::Array(Some(inner)) => Ok(vec![Value::Array(inner.eval(input)?)]),
+            Self::Object(entries) => {
+                let mut objects = vec![Map::new()];
+                for (key, filter) in entries {
+                    let values = filter.eval(input)?;
+                    objects = objects
+                        .into_iter()
+                        .flat_map(|object| {
+                            values.iter().map(move |value| {
+                                let mut object = object.clone();
+                                object.insert(key.clone(), value.clone());
+                                object
+                            })
+                        })
+                        .collect();
+                }
+                Ok(objects.into_iter().map(Value::Object).collect())
+            }
+            Self::Call(name, args) => call(name, args, input),
+        }
+    }
+}
+
+fn call(name: &str, args: &[Filter], input: &Value) -> Result<Vec<Value>, String> {
+    let value = match (name, input) {
+        ("keys", Value::Object(map)) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            json!(keys)
+        }
+        ("keys", Value::Array(items)) => json!((0..items.len()).collect::<Vec<_>>()),
+        ("length", Value::Null) => json!(0),
+        ("length", Value::Array(items)) => json!(items.len()),
+        ("length", Value::Object(map)) => json!(map.len()),
+        ("length", Value::String(s)) => json!(s.chars().count()),
+        ("length", Value::Number(n)) => json!(n.as_f64().map(f64::abs)),
+        ("type", _) => json!(type_name(input)),
+        ("not", _) => Value::Bool(!is_truthy(input)),
+        ("first", Value::Array(items)) => items.first().cloned().unwrap_or(Value::Null),
+        ("last", Value::Array(items)) => items.last().cloned().unwrap_or(Value::Null),
+        ("sort", Value::Array(items)) => {
+            let mut items = items.clone();
+            items.sort_by(compare_values);
+            Value::Array(items)
+        }
+        ("add", Value::Array(items)) => add(items)?,
+        ("empty", _) => return Ok(Vec::new()),
+        ("map", Value::Array(_) | Value::Object(_)) => {
+            let iterate = Filter::Iterate(Box::new(Filter::Identity));
+            Value::Array(Filter::Pipe(Box::new(iterate), Box::new(args[0].clone())).eval(input)?)
+        }
+        ("select", _) => {
+            let matches = args[0].eval(input)?;
+            return Ok(matches
+                .iter()
+                .filter(|value| is_truthy(value))
+                .map(|_| input.clone())
+                .collect());
+        }
+        ("has", _) => {
+            return args[0]
+                .eval(input)?
+                .into_iter()
+                .map(|key| match (input, &key) {
+                    (Value::Object(map), Value::String(key)) => {
+                        Ok(Value::Bool(map.contains_key(key)))
+                    }
+                    (Value::Array(items), Value::Number(n)) => Ok(Value::Bool(
+                        n.as_f64()
+                            .is_some_and(|i| i >= 0.0 && (i as usize) < items.len()),
+                    )),
+                    _ => Err(format!(
+                        "cannot check whether {} has a {} key",
+                        type_name(input),
+                        type_name(&key)
+                    )),
+                })
+                .collect();
+        }
+        _ => return Err(format!("{name} cannot be applied to {}", type_name(input))),
+    };
+    Ok(vec![value])
+}
+
+/// Adds the items of an array: numbers are summed, strings and arrays
+/// concatenated, and objects merged.
+fn add(items: &[Value]) -> Result<Value, String> {
+    let mut total = Value::Null;
+    for item in items {
+        total = match (total, item) {
+            (Value::Null, item) => item.clone(),
+            (total, Value::Null) => total,
+            (Value::Number(a), Value::Number(b)) => {
+                json!(a.as_f64().unwrap_or_default() + b.as_f64().unwrap_or_default())
+            }
+            (Value::String(a), Value::String(b)) => Value::String(a + b),
+            (Value::Array(mut a), Value::Array(b)) => {
+                a.extend(b.iter().cloned());
+                Value::Array(a)
+            }
+            (Value::Object(mut a), Value::Object(b)) => {
+                a.extend(b.clone());
+                Value::Object(a)
+            }
+            (total, item) => {
+                return Err(format!(
+                    "cannot add {} and {}",
+                    type_name(&total),
+                    type_name(item)
+                ))
+            }
+        };
+    }
+    Ok(total)
+}
+
+impl ToolActor for JsonToolActor {
+    fn name() -> &'static str {
+        "json_query"
+    }
+
+    fn definition() -> ToolDefinition {
+        JsonTool::config().definition
+    }
+
+    async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
+        let mut builder = runtime.new_actor_with_name::<Self>("json_query_tool".to_string());
+
+        configure_tool_actor(&mut builder, |_| JsonTool::new());
+
+        builder.start().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = r#"{
+        "users": [
+            {"name": "ada", "age": 36, "tags": ["admin"]},
+            {"name": "bob", "age": 25, "tags": []},
+            {"name": "cy", "age": 41, "tags": ["ops", "admin"]}
+        ],
+        "meta": {"total": 3, "next page": null}
+    }"#;
+
+    fn query(q: &str) -> Vec<Value> {
+        JsonTool::run(DOC, q).unwrap()
+    }
+
+    #[test]
+    fn field_access_and_indexing() {
+        assert_eq!(query(".meta.total"), vec![json!(3)]);
+        assert_eq!(query(r#".meta."next page""#), vec![Value::Null]);
+        assert_eq!(query(r#".["meta"]["total"]"#), vec![json!(3)]);
+        assert_eq!(query(".users[0].name"), vec![json!("ada")]);
+        assert_eq!(query(".users[-1].name"), vec![json!("cy")]);
+        assert_eq!(query(".users[5]"), vec![Value::Null]);
+        assert_eq!(query(".users[1:] | length"), vec![json!(2)]);
+        assert_eq!(query(".missing.deeper"), vec![Value::Null]);
+    }
+
+    #[test]
+    fn iteration_and_filtering() {
+        assert_eq!(
+            query(".users[] | select(.age > 30) | .name"),
+            vec![json!("ada"), json!("cy")]
+        );
+        assert_eq!(
+            query(r#"[.users[] | select(.tags | length > 0 and .[0] == "ops") | .name]"#),
+            vec![json!(["cy"])]
+        );
+        assert_eq!(query(".users | map(.age) | add"), vec![json!(102.0)]);
+        assert_eq!(
+            query(".users | map(.name) | sort | first, last"),
+            vec![json!("ada"), json!("cy")]
+        );
+    }
+
+    #[test]
+    fn keys_and_construction() {
+        assert_eq!(query(".meta | keys"), vec![json!(["next page", "total"])]);
+        assert_eq!(
+            query(".users[0] | {name, admin: (.tags | length == 1)}"),
+            vec![json!({"name": "ada", "admin": true})]
+        );
+        assert_eq!(
+            query(r#".meta | has("total"), has("other")"#),
+            vec![json!(true), json!(false)]
+        );
+        assert_eq!(query(".users[0].name | type"), vec![json!("string")]);
+    }
+
+    #[test]
+    fn errors_are_descriptive() {
+        assert!(JsonTool::run(DOC, ".users[")
+            .unwrap_err()
+            .contains("unsupported index"));
+        assert!(JsonTool::run(DOC, "frobnicate")
+            .unwrap_err()
+            .contains("unknown function"));
+        assert!(JsonTool::run(DOC, ".meta.total.x")
+            .unwrap_err()
+            .contains("cannot index number"));
+        assert!(JsonTool::run("{", ".")
+            .unwrap_err()
+            .contains("invalid JSON input"));
+    }
+
+    #[tokio::test]
+    async fn failures_are_returned_as_results() {
+        let tool = JsonTool::new();
+
+        let ok = tool
+            .execute(json!({"json": DOC, "query": ".meta.total"}))
+            .await
+            .unwrap();
+        assert_eq!(ok, json!({"results": [3]}));
+
+        let failed = tool
+            .execute(json!({"json": DOC, "query": ".users | keys("}))
+            .await
+            .unwrap();
+        assert!(failed["error"].is_string());
+        assert_eq!(failed["query"], ".users | keys(");
+    }
+
+    #[test]
+    fn config_is_not_sandboxed() {
+        let config = JsonTool::config();
+        assert_eq!(config.definition.name, "json_query");
+        assert!(!config.sandboxed);
+        assert_eq!(
+            config.definition.input_schema["required"],
+            json!(["json", "query"])
+        );
+    }
+}
@@ -3,7 +3,7 @@
 //! Lists directory contents with metadata.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
 use crate::tools::security::PathValidator;
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
@@ -21,7 +21,20 @@ pub struct ListDirectoryTool;
 ///
 /// This actor wraps the `ListDirectoryTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct ListDirectoryToolActor;
+pub struct ListDirectoryToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for ListDirectoryToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 /// Arguments for the list_directory tool.
 #[derive(Debug, Deserialize)]
@@ -185,29 +198,7 @@ impl ToolActor for ListDirectoryToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("list_directory_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = ListDirectoryTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| ListDirectoryTool::new());
 
         builder.start().await
     }
@@ -365,4 +356,16 @@ mod tests {
             .unwrap()
             .contains(&json!("path")));
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(
+            &ListDirectoryTool::config().definition,
+        )
+        .valid_example(json!({"path": "/tmp"}))
+        .invalid_example(json!({}), "path is required")
+        .invalid_example(json!({"path": null}), "path must be a string")
+        .run()
+        .assert_success();
+    }
 }
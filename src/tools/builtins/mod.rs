@@ -13,6 +13,7 @@
 //! - **list_directory**: List directory contents with metadata
 //! - **glob**: Find files matching glob patterns
 //! - **grep**: Search file contents with regex
+//! - **git**: Read repository history, diffs and status
 //!
 //! ### Execution Tools
 //! - **bash**: Execute shell commands (sandboxed by default)
@@ -20,6 +21,13 @@
 //!
 //! ### Web Tools
 //! - **web_fetch**: Fetch content from URLs
+//! - **fetch_rss**: Fetch recent items from RSS and Atom feeds
+//!
+//! ### Data Tools
+//! - **sql_query**: Run read-only SQL against libSQL databases
+//! - **json_query**: Query and transform JSON with jq-style expressions
+//! - **database**: Run SQL under a configurable permission level (read-only
+//!   unless [`BuiltinTools::with_database_permission_level`] raises it)
 //!
 //! ## Usage
 //!
@@ -63,11 +71,16 @@
 
 mod bash;
 mod calculate;
+mod database;
 mod edit_file;
+mod fetch_rss;
+mod git;
 mod glob;
 mod grep;
+mod json_query;
 mod list_directory;
 mod read_file;
+mod sql_query;
 mod web_fetch;
 mod write_file;
 
@@ -77,11 +90,16 @@ mod skill_list;
 // Re-export tool implementations
 pub use bash::{BashTool, BashToolActor};
 pub use calculate::{CalculateTool, CalculateToolActor};
+pub use database::{DatabasePermissionLevel, DatabaseTool, DatabaseToolActor};
 pub use edit_file::{EditFileTool, EditFileToolActor};
+pub use fetch_rss::{FetchRssTool, FetchRssToolActor};
+pub use git::{GitTool, GitToolActor};
 pub use glob::{GlobTool, GlobToolActor};
 pub use grep::{GrepTool, GrepToolActor};
+pub use json_query::{JsonTool, JsonToolActor};
 pub use list_directory::{ListDirectoryTool, ListDirectoryToolActor};
 pub use read_file::{ReadFileTool, ReadFileToolActor};
+pub use sql_query::{SqlQueryTool, SqlQueryToolActor};
 pub use web_fetch::{WebFetchTool, WebFetchToolActor};
 pub use write_file::{WriteFileTool, WriteFileToolActor};
 
@@ -90,6 +108,8 @@ pub use skill_list::{ListSkillsTool, ListSkillsToolActor};
 
 use crate::messages::ToolDefinition;
 use crate::tools::actor::ToolActor;
+use crate::tools::cache::{ResultCacheConfig, ToolResultCache};
+use crate::tools::rate_limit::{ToolRateLimit, ToolRateLimiter};
 use crate::tools::{BoxedToolExecutor, ToolConfig, ToolError, ToolErrorKind};
 use acton_reactive::prelude::*;
 use std::collections::HashMap;
@@ -99,12 +119,24 @@ use std::sync::Arc;
 ///
 /// Provides methods to access tool configurations and executors
 /// for registration with the tool system.
+///
+/// `bash`, `web_fetch` and `fetch_rss` are rate limited out of the box (see
+/// [`ToolRateLimit::builtin_default`]); use
+/// [`with_rate_limits`](Self::with_rate_limits) and
+/// [`with_default_rate_limit`](Self::with_default_rate_limit) to adjust.
+/// Limits are enforced on calls made through [`ActonAI`](crate::ActonAI)
+/// prompts, as are result caches added with
+/// [`with_result_cache`](Self::with_result_cache).
 #[derive(Debug, Default)]
 pub struct BuiltinTools {
     /// Tool configurations by name
     configs: HashMap<String, ToolConfig>,
     /// Tool executors by name
     executors: HashMap<String, Arc<BoxedToolExecutor>>,
+    /// Rate limiters by tool name
+    rate_limiters: HashMap<String, Arc<ToolRateLimiter>>,
+    /// Result caches by tool name
+    result_caches: HashMap<String, Arc<ToolResultCache>>,
 }
 
 impl BuiltinTools {
@@ -136,6 +168,7 @@ impl BuiltinTools {
         );
         registry.register("glob", GlobTool::config(), Box::new(GlobTool::new()));
         registry.register("grep", GrepTool::config(), Box::new(GrepTool::new()));
+        registry.register("git", GitTool::config(), Box::new(GitTool::new()));
         registry.register("bash", BashTool::config(), Box::new(BashTool::new()));
         registry.register(
             "calculate",
@@ -147,7 +180,24 @@ impl BuiltinTools {
             WebFetchTool::config(),
             Box::new(WebFetchTool::new()),
         );
+        registry.register(
+            "fetch_rss",
+            FetchRssTool::config(),
+            Box::new(FetchRssTool::new()),
+        );
+        registry.register(
+            "sql_query",
+            SqlQueryTool::config(),
+            Box::new(SqlQueryTool::new()),
+        );
+        registry.register("json_query", JsonTool::config(), Box::new(JsonTool::new()));
+        registry.register(
+            "database",
+            DatabaseTool::config(),
+            Box::new(DatabaseTool::new()),
+        );
 
+        registry.apply_builtin_rate_limits();
         registry
     }
 
@@ -190,9 +240,110 @@ impl BuiltinTools {
                 .insert((*name).to_string(), Arc::clone(executor));
         }
 
+        registry.apply_builtin_rate_limits();
         Ok(registry)
     }
 
+    /// Applies rate limits to the named tools, replacing any existing limit.
+    ///
+    /// Names that are not in this registry are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tools = BuiltinTools::all().with_rate_limits(HashMap::from([(
+    ///     "bash".to_string(),
+    ///     ToolRateLimit::new(10, 100, 1),
+    /// )]));
+    /// ```
+    #[must_use]
+    pub fn with_rate_limits(mut self, limits: HashMap<String, ToolRateLimit>) -> Self {
+        for (name, limit) in limits {
+            if self.configs.contains_key(&name) {
+                self.rate_limiters
+                    .insert(name, Arc::new(ToolRateLimiter::new(limit)));
+            } else {
+                tracing::warn!(tool = %name, "Ignoring rate limit for unknown builtin tool");
+            }
+        }
+        self
+    }
+
+    /// Applies `limit` to every tool that does not already have one.
+    #[must_use]
+    pub fn with_default_rate_limit(mut self, limit: ToolRateLimit) -> Self {
+        for name in self.configs.keys() {
+            self.rate_limiters
+                .entry(name.clone())
+                .or_insert_with(|| Arc::new(ToolRateLimiter::new(limit)));
+        }
+        self
+    }
+
+    /// Sets the permission level of the `database` tool.
+    ///
+    /// The level is stated in the tool description the model sees. Has no
+    /// effect if `database` is not in this registry.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tools = BuiltinTools::all()
+    ///     .with_database_permission_level(DatabasePermissionLevel::ReadWrite);
+    /// ```
+    #[must_use]
+    pub fn with_database_permission_level(mut self, level: DatabasePermissionLevel) -> Self {
+        if self.configs.contains_key("database") {
+            self.register(
+                "database",
+                DatabaseTool::config_for(level),
+                Box::new(DatabaseTool::new().with_permission_level(level)),
+            );
+        }
+        self
+    }
+
+    /// Caches successful results of the named tool across prompts.
+    ///
+    /// Replaces any existing cache for the tool. Names that are not in this
+    /// registry are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tools = BuiltinTools::all().with_result_cache(
+    ///     "calculate",
+    ///     ResultCacheConfig::new(100).with_ttl(Duration::from_secs(300)),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn with_result_cache(mut self, name: &str, config: ResultCacheConfig) -> Self {
+        let Some(tool_config) = self.configs.remove(name) else {
+            tracing::warn!(tool = %name, "Ignoring result cache for unknown builtin tool");
+            return self;
+        };
+
+        self.result_caches.insert(
+            name.to_string(),
+            Arc::new(ToolResultCache::new(config.clone())),
+        );
+        self.configs
+            .insert(name.to_string(), tool_config.with_result_cache(config));
+        self
+    }
+
+    /// Returns the result cache for a specific tool, if it is cached.
+    #[must_use]
+    pub fn result_cache(&self, name: &str) -> Option<Arc<ToolResultCache>> {
+        self.result_caches.get(name).cloned()
+    }
+
+    /// Returns the rate limiter for a specific tool, if it is limited.
+    #[must_use]
+    pub fn rate_limiter(&self, name: &str) -> Option<Arc<ToolRateLimiter>> {
+        self.rate_limiters.get(name).cloned()
+    }
+
     /// Lists all available built-in tool names.
     #[must_use]
     pub fn available() -> Vec<&'static str> {
@@ -203,9 +354,14 @@ impl BuiltinTools {
             "list_directory",
             "glob",
             "grep",
+            "git",
             "bash",
             "calculate",
             "web_fetch",
+            "fetch_rss",
+            "sql_query",
+            "json_query",
+            "database",
         ]
     }
 
@@ -243,6 +399,16 @@ impl BuiltinTools {
         self.configs.is_empty()
     }
 
+    /// Installs [`ToolRateLimit::builtin_default`] for every tool that has one.
+    fn apply_builtin_rate_limits(&mut self) {
+        for name in self.configs.keys() {
+            if let Some(limit) = ToolRateLimit::builtin_default(name) {
+                self.rate_limiters
+                    .insert(name.clone(), Arc::new(ToolRateLimiter::new(limit)));
+            }
+        }
+    }
+
     /// Internal method to register a tool.
     fn register(&mut self, name: &str, config: ToolConfig, executor: BoxedToolExecutor) {
         self.configs.insert(name.to_string(), config);
@@ -307,6 +473,11 @@ pub async fn spawn_tool_actor(
             let definition = GrepToolActor::definition();
             Ok((handle, definition))
         }
+        "git" => {
+            let handle = GitToolActor::spawn(runtime).await;
+            let definition = GitToolActor::definition();
+            Ok((handle, definition))
+        }
         "bash" => {
             let handle = BashToolActor::spawn(runtime).await;
             let definition = BashToolActor::definition();
@@ -322,6 +493,26 @@ pub async fn spawn_tool_actor(
             let definition = WebFetchToolActor::definition();
             Ok((handle, definition))
         }
+        "fetch_rss" => {
+            let handle = FetchRssToolActor::spawn(runtime).await;
+            let definition = FetchRssToolActor::definition();
+            Ok((handle, definition))
+        }
+        "sql_query" => {
+            let handle = SqlQueryToolActor::spawn(runtime).await;
+            let definition = SqlQueryToolActor::definition();
+            Ok((handle, definition))
+        }
+        "json_query" => {
+            let handle = JsonToolActor::spawn(runtime).await;
+            let definition = JsonToolActor::definition();
+            Ok((handle, definition))
+        }
+        "database" => {
+            let handle = DatabaseToolActor::spawn(runtime).await;
+            let definition = DatabaseToolActor::definition();
+            Ok((handle, definition))
+        }
         _ => Err(ToolError::not_found(tool_name)),
     }
 }
@@ -343,9 +534,14 @@ pub fn get_tool_definition(tool_name: &str) -> Result<ToolDefinition, ToolError>
         "list_directory" => Ok(ListDirectoryToolActor::definition()),
         "glob" => Ok(GlobToolActor::definition()),
         "grep" => Ok(GrepToolActor::definition()),
+        "git" => Ok(GitToolActor::definition()),
         "bash" => Ok(BashToolActor::definition()),
         "calculate" => Ok(CalculateToolActor::definition()),
         "web_fetch" => Ok(WebFetchToolActor::definition()),
+        "fetch_rss" => Ok(FetchRssToolActor::definition()),
+        "sql_query" => Ok(SqlQueryToolActor::definition()),
+        "json_query" => Ok(JsonToolActor::definition()),
+        "database" => Ok(DatabaseToolActor::definition()),
         _ => Err(ToolError::not_found(tool_name)),
     }
 }
@@ -405,7 +601,7 @@ mod tests {
     #[test]
     fn builtin_tools_all_creates_all_tools() {
         let tools = BuiltinTools::all();
-        assert_eq!(tools.len(), 9);
+        assert_eq!(tools.len(), 14);
 
         for name in BuiltinTools::available() {
             assert!(
@@ -438,7 +634,7 @@ mod tests {
     #[test]
     fn builtin_tools_available_returns_all_names() {
         let names = BuiltinTools::available();
-        assert_eq!(names.len(), 9);
+        assert_eq!(names.len(), 14);
 
         assert!(names.contains(&"read_file"));
         assert!(names.contains(&"write_file"));
@@ -446,23 +642,104 @@ mod tests {
         assert!(names.contains(&"list_directory"));
         assert!(names.contains(&"glob"));
         assert!(names.contains(&"grep"));
+        assert!(names.contains(&"git"));
         assert!(names.contains(&"bash"));
         assert!(names.contains(&"calculate"));
         assert!(names.contains(&"web_fetch"));
+        assert!(names.contains(&"fetch_rss"));
+        assert!(names.contains(&"sql_query"));
+        assert!(names.contains(&"json_query"));
+        assert!(names.contains(&"database"));
+    }
+
+    #[test]
+    fn builtin_tools_with_result_cache() {
+        let tools = BuiltinTools::all()
+            .with_result_cache("calculate", ResultCacheConfig::new(10))
+            .with_result_cache("nonexistent", ResultCacheConfig::new(10));
+
+        assert!(tools.result_cache("calculate").is_some());
+        assert!(tools
+            .get_config("calculate")
+            .unwrap()
+            .result_cache
+            .is_some());
+        assert!(tools.result_cache("glob").is_none());
+        assert!(tools.result_cache("nonexistent").is_none());
+    }
+
+    #[test]
+    fn builtin_tools_database_permission_level() {
+        let tools =
+            BuiltinTools::all().with_database_permission_level(DatabasePermissionLevel::Admin);
+        let description = &tools.get_config("database").unwrap().definition.description;
+        assert!(description.contains("admin"));
+
+        let tools = BuiltinTools::select(&["read_file"])
+            .unwrap()
+            .with_database_permission_level(DatabasePermissionLevel::Admin);
+        assert!(tools.get_config("database").is_none());
     }
 
     #[test]
     fn builtin_tools_configs_iterator() {
         let tools = BuiltinTools::all();
         let configs: Vec<_> = tools.configs().collect();
-        assert_eq!(configs.len(), 9);
+        assert_eq!(configs.len(), 14);
     }
 
     #[test]
     fn builtin_tools_executors_iterator() {
         let tools = BuiltinTools::all();
         let executors: Vec<_> = tools.executors().collect();
-        assert_eq!(executors.len(), 9);
+        assert_eq!(executors.len(), 14);
+    }
+
+    #[test]
+    fn builtin_tools_rate_limit_bash_and_web_fetch_by_default() {
+        let tools = BuiltinTools::all();
+        assert_eq!(
+            tools
+                .rate_limiter("bash")
+                .unwrap()
+                .limit()
+                .max_calls_per_minute,
+            60
+        );
+        assert_eq!(
+            tools
+                .rate_limiter("web_fetch")
+                .unwrap()
+                .limit()
+                .max_calls_per_minute,
+            30
+        );
+        assert!(tools.rate_limiter("read_file").is_none());
+    }
+
+    #[test]
+    fn builtin_tools_explicit_rate_limits_win_over_default() {
+        let tools = BuiltinTools::select(&["bash", "read_file"])
+            .unwrap()
+            .with_rate_limits(HashMap::from([
+                ("bash".to_string(), ToolRateLimit::new(5, 50, 1)),
+                ("unknown".to_string(), ToolRateLimit::per_minute(1)),
+            ]))
+            .with_default_rate_limit(ToolRateLimit::per_minute(100));
+
+        assert_eq!(
+            tools.rate_limiter("bash").unwrap().limit().max_concurrent,
+            1
+        );
+        assert_eq!(
+            tools
+                .rate_limiter("read_file")
+                .unwrap()
+                .limit()
+                .max_calls_per_minute,
+            100
+        );
+        assert!(tools.rate_limiter("unknown").is_none());
     }
 
     #[test]
@@ -3,8 +3,8 @@
 //! Reads file contents and returns them with line numbers.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
-use crate::tools::security::PathValidator;
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::security::{Operation, PathValidator, SecurityPolicy, SymlinkPolicy};
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -15,14 +15,41 @@ use std::path::Path;
 ///
 /// Reads file contents with optional offset and limit,
 /// returning content with line numbers.
-#[derive(Debug, Default, Clone)]
-pub struct ReadFileTool;
+#[derive(Debug, Clone)]
+pub struct ReadFileTool {
+    /// Rules restricting which paths may be read
+    policy: Option<SecurityPolicy>,
+    /// How symlinks in the path are treated
+    symlink_policy: SymlinkPolicy,
+}
+
+impl Default for ReadFileTool {
+    fn default() -> Self {
+        Self {
+            policy: None,
+            symlink_policy: SymlinkPolicy::AllowWithinRoot,
+        }
+    }
+}
 
 /// Read file tool actor state.
 ///
 /// This actor wraps the `ReadFileTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct ReadFileToolActor;
+pub struct ReadFileToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for ReadFileToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 /// Arguments for the read_file tool.
 #[derive(Debug, Deserialize)]
@@ -56,7 +83,23 @@ impl ReadFileTool {
     /// Creates a new read file tool.
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Enforces `policy` instead of the default path rules.
+    #[must_use]
+    pub fn with_policy(mut self, policy: SecurityPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Sets how symlinks in the path are treated.
+    ///
+    /// Defaults to [`SymlinkPolicy::AllowWithinRoot`].
+    #[must_use]
+    pub fn with_symlink_policy(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = policy;
+        self
     }
 
     /// Returns the tool configuration for registration.
@@ -156,6 +199,8 @@ impl ReadFileTool {
 
 impl ToolExecutorTrait for ReadFileTool {
     fn execute(&self, args: Value) -> ToolExecutionFuture {
+        let policy = self.policy.clone();
+        let symlink_policy = self.symlink_policy;
         Box::pin(async move {
             let args: ReadFileArgs = serde_json::from_value(args).map_err(|e| {
                 ToolError::validation_failed("read_file", format!("invalid arguments: {e}"))
@@ -172,9 +217,11 @@ impl ToolExecutorTrait for ReadFileTool {
             }
 
             // Validate path using PathValidator for security
-            let validator = PathValidator::new();
+            let validator = policy
+                .map_or_else(PathValidator::new, PathValidator::new_with_policy)
+                .with_symlink_policy(symlink_policy);
             let canonical_path = validator
-                .validate_file(path)
+                .validate_file_for(Operation::Read, path)
                 .map_err(|e| ToolError::validation_failed("read_file", e.to_string()))?;
 
             // Check if file is likely binary
@@ -230,29 +277,7 @@ impl ToolActor for ReadFileToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("read_file_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = ReadFileTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| ReadFileTool::new());
 
         builder.start().await
     }
@@ -351,6 +376,27 @@ mod tests {
             .contains("cannot resolve path"));
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn read_file_rejects_symlink_leaving_its_root() {
+        // The link lives in the temp dir root but points into the working
+        // directory root
+        let target = std::env::current_dir().unwrap().join("Cargo.toml");
+        let dir = tempfile::tempdir().unwrap();
+        let link = dir.path().join("manifest.toml");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+        let args = json!({"path": link.to_str().unwrap(), "limit": 1});
+
+        let error = ReadFileTool::new().execute(args.clone()).await.unwrap_err();
+        assert!(error.to_string().contains("symlink"));
+
+        assert!(ReadFileTool::new()
+            .with_symlink_policy(SymlinkPolicy::Allow)
+            .execute(args)
+            .await
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn read_file_relative_path_rejected() {
         let tool = ReadFileTool::new();
@@ -375,4 +421,22 @@ mod tests {
         assert!(schema["properties"]["offset"].is_object());
         assert!(schema["properties"]["limit"].is_object());
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(&ReadFileTool::config().definition)
+            .valid_example(json!({"path": "/tmp/a"}))
+            .valid_example(json!({"path": "/tmp/a", "offset": 1, "limit": 50}))
+            .invalid_example(json!({"offset": 1}), "path is required")
+            .invalid_example(
+                json!({"path": "/tmp/a", "offset": 0}),
+                "offset below minimum",
+            )
+            .invalid_example(
+                json!({"path": "/tmp/a", "limit": 2.5}),
+                "limit must be an integer",
+            )
+            .run()
+            .assert_success();
+    }
 }
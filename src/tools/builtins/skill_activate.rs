@@ -4,11 +4,12 @@
 
 use crate::messages::ToolDefinition;
 use crate::skills::SkillRegistry;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Activate skill tool executor.
@@ -27,6 +28,18 @@ pub struct ActivateSkillTool {
 pub struct ActivateSkillToolActor {
     /// Reference to the skill registry
     registry: Arc<SkillRegistry>,
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for ActivateSkillToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
 }
 
 /// Arguments for the activate_skill tool.
@@ -34,6 +47,9 @@ pub struct ActivateSkillToolActor {
 struct ActivateSkillArgs {
     /// Name of the skill to activate
     name: String,
+    /// Values for the skill's `{{name}}` placeholders
+    #[serde(default)]
+    parameters: Option<HashMap<String, String>>,
 }
 
 /// Result of activating a skill.
@@ -70,6 +86,11 @@ impl ActivateSkillTool {
                     "name": {
                         "type": "string",
                         "description": "Name of the skill to activate"
+                    },
+                    "parameters": {
+                        "type": "object",
+                        "description": "Values for the skill's parameters, as listed by list_skills",
+                        "additionalProperties": {"type": "string"}
                     }
                 },
                 "required": ["name"]
@@ -101,10 +122,14 @@ impl ToolExecutorTrait for ActivateSkillTool {
                 )
             })?;
 
+            let instructions = skill
+                .instructions_with_params(&args.parameters.unwrap_or_default())
+                .map_err(|e| ToolError::validation_failed("activate_skill", e.to_string()))?;
+
             let result = ActivateSkillResult {
                 name: skill.name().to_string(),
                 description: skill.description().to_string(),
-                instructions: skill.instructions().to_string(),
+                instructions,
                 path: skill.info.path.display().to_string(),
                 tags: skill.info.tags.clone(),
             };
@@ -142,29 +167,8 @@ impl ToolActor for ActivateSkillToolActor {
         // Default spawn with empty registry - use spawn_with_registry for production
         let mut builder = runtime.new_actor_with_name::<Self>("activate_skill_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let registry = Arc::clone(&actor.model.registry);
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = ActivateSkillTool::new(registry);
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
+        configure_tool_actor(&mut builder, |actor| {
+            ActivateSkillTool::new(Arc::clone(&actor.registry))
         });
 
         builder.start().await
@@ -181,30 +185,9 @@ impl ActivateSkillToolActor {
     ) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("activate_skill_tool".to_string());
 
-        // Capture the registry in the handler closure
-        builder.act_on::<ExecuteToolDirect>(move |actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let registry = Arc::clone(&registry);
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = ActivateSkillTool::new(registry);
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
+        builder.model.registry = registry;
+        configure_tool_actor(&mut builder, |actor| {
+            ActivateSkillTool::new(Arc::clone(&actor.registry))
         });
 
         builder.start().await
@@ -214,10 +197,10 @@ impl ActivateSkillToolActor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::skills::{LoadedSkill, SkillInfo};
+    use crate::skills::{LoadedSkill, SkillInfo, SkillParameter};
 
     fn create_test_registry() -> Arc<SkillRegistry> {
-        let mut registry = SkillRegistry::new();
+        let registry = SkillRegistry::new();
 
         registry.add(LoadedSkill {
             info: SkillInfo {
@@ -225,6 +208,7 @@ mod tests {
                 description: "Review code for quality".to_string(),
                 path: std::path::PathBuf::from("/skills/code-review.md"),
                 tags: vec!["code".to_string(), "review".to_string()],
+                parameters: vec![],
             },
             content: "# Code Review\n\nReview the code carefully.".to_string(),
             triggers: vec![],
@@ -255,6 +239,51 @@ mod tests {
         assert_eq!(result["path"], "/skills/code-review.md");
     }
 
+    #[tokio::test]
+    async fn activate_sees_registry_updates() {
+        let registry = create_test_registry();
+        let tool = ActivateSkillTool::new(Arc::clone(&registry));
+
+        let mut skill = registry.get("code-review").unwrap();
+        skill.content = "# Updated".to_string();
+        registry.add(skill);
+
+        let result = tool.execute(json!({"name": "code-review"})).await.unwrap();
+        assert_eq!(result["instructions"], "# Updated");
+    }
+
+    #[tokio::test]
+    async fn activate_substitutes_parameters() {
+        let registry = create_test_registry();
+        registry.add(LoadedSkill {
+            info: SkillInfo {
+                name: "lint".to_string(),
+                description: "Lint code".to_string(),
+                path: std::path::PathBuf::from("/skills/lint.md"),
+                tags: vec![],
+                parameters: vec![SkillParameter {
+                    name: "language".to_string(),
+                    description: "Language to lint".to_string(),
+                    required: true,
+                    default: None,
+                }],
+            },
+            content: "Lint the {{language}} code.".to_string(),
+            triggers: vec![],
+            enabled_by_default: false,
+        });
+        let tool = ActivateSkillTool::new(registry);
+
+        let result = tool
+            .execute(json!({"name": "lint", "parameters": {"language": "rust"}}))
+            .await
+            .unwrap();
+        assert_eq!(result["instructions"], "Lint the rust code.");
+
+        let err = tool.execute(json!({"name": "lint"})).await.unwrap_err();
+        assert!(err.to_string().contains("requires parameter 'language'"));
+    }
+
     #[tokio::test]
     async fn activate_nonexistent_skill() {
         let registry = create_test_registry();
@@ -324,4 +353,17 @@ mod tests {
         let result = tool.validate_args(&json!({"name": "code-review"}));
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(
+            &ActivateSkillTool::config().definition,
+        )
+        .valid_example(json!({"name": "code-review"}))
+        .valid_example(json!({"name": "code-review", "parameters": {"language": "rust"}}))
+        .invalid_example(json!({}), "name is required")
+        .invalid_example(json!({"name": 7}), "name must be a string")
+        .run()
+        .assert_success();
+    }
 }
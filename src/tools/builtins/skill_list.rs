@@ -3,8 +3,8 @@
 //! Lists available agent skills with metadata (progressive disclosure).
 
 use crate::messages::ToolDefinition;
-use crate::skills::{SkillInfo, SkillRegistry};
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
+use crate::skills::{SkillInfo, SkillParameter, SkillRegistry};
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -27,6 +27,18 @@ pub struct ListSkillsTool {
 pub struct ListSkillsToolActor {
     /// Reference to the skill registry
     registry: Arc<SkillRegistry>,
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for ListSkillsToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
 }
 
 /// Arguments for the list_skills tool.
@@ -55,6 +67,9 @@ struct SkillSummary {
     description: String,
     /// Tags associated with the skill
     tags: Vec<String>,
+    /// Parameters accepted by activate_skill
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    parameters: Vec<SkillParameter>,
 }
 
 impl From<&SkillInfo> for SkillSummary {
@@ -63,6 +78,7 @@ impl From<&SkillInfo> for SkillSummary {
             name: info.name.clone(),
             description: info.description.clone(),
             tags: info.tags.clone(),
+            parameters: info.parameters.clone(),
         }
     }
 }
@@ -141,29 +157,8 @@ impl ToolActor for ListSkillsToolActor {
         // Use spawn_with_registry instead
         let mut builder = runtime.new_actor_with_name::<Self>("list_skills_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let registry = Arc::clone(&actor.model.registry);
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = ListSkillsTool::new(registry);
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
+        configure_tool_actor(&mut builder, |actor| {
+            ListSkillsTool::new(Arc::clone(&actor.registry))
         });
 
         builder.start().await
@@ -180,30 +175,9 @@ impl ListSkillsToolActor {
     ) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("list_skills_tool".to_string());
 
-        // Capture the registry in the handler closure
-        builder.act_on::<ExecuteToolDirect>(move |actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let registry = Arc::clone(&registry);
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = ListSkillsTool::new(registry);
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
+        builder.model.registry = registry;
+        configure_tool_actor(&mut builder, |actor| {
+            ListSkillsTool::new(Arc::clone(&actor.registry))
         });
 
         builder.start().await
@@ -216,7 +190,7 @@ mod tests {
     use crate::skills::LoadedSkill;
 
     fn create_test_registry() -> Arc<SkillRegistry> {
-        let mut registry = SkillRegistry::new();
+        let registry = SkillRegistry::new();
 
         registry.add(LoadedSkill {
             info: SkillInfo {
@@ -224,6 +198,7 @@ mod tests {
                 description: "Review code for quality".to_string(),
                 path: std::path::PathBuf::from("/skills/code-review.md"),
                 tags: vec!["code".to_string(), "review".to_string()],
+                parameters: vec![],
             },
             content: "# Code Review\n\nInstructions...".to_string(),
             triggers: vec![],
@@ -236,6 +211,7 @@ mod tests {
                 description: "Generate documentation".to_string(),
                 path: std::path::PathBuf::from("/skills/documentation.md"),
                 tags: vec!["docs".to_string()],
+                parameters: vec![],
             },
             content: "# Documentation\n\nInstructions...".to_string(),
             triggers: vec![],
@@ -315,4 +291,16 @@ mod tests {
         let schema = &config.definition.input_schema;
         assert!(schema["properties"]["filter"].is_object());
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(
+            &ListSkillsTool::config().definition,
+        )
+        .valid_example(json!({}))
+        .valid_example(json!({"filter": "review"}))
+        .invalid_example(json!({"filter": true}), "filter must be a string")
+        .run()
+        .assert_success();
+    }
 }
@@ -0,0 +1,396 @@
+//! SQL query built-in tool.
+//!
+//! Runs read-only SQL against a libSQL/SQLite database and returns the rows
+//! as JSON objects.
+
+use super::database::{classify, StatementKind};
+use crate::memory::PersistenceConfig;
+use crate::messages::ToolDefinition;
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::security::PathValidator;
+use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
+use acton_reactive::prelude::*;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Case-insensitive pattern matching statements that modify the database or
+/// reach beyond it.
+///
+/// Used in the input schema (under `not`); at runtime
+/// [`check_read_only`](SqlQueryTool::check_read_only) classifies the query
+/// instead.
+const WRITE_STATEMENT_PATTERN: &str =
+    r"(?i)\b(insert|update|delete|drop|alter|create|attach|detach|pragma)\b";
+
+/// Maximum number of rows returned by a single query.
+const MAX_ROWS: usize = 1000;
+
+/// SQL query tool executor.
+///
+/// Runs a single read-only statement per call, on a read-only connection.
+/// `ATTACH` and `PRAGMA` are rejected even though the connection is
+/// read-only: `ATTACH` would open databases outside the allowed roots.
+#[derive(Debug, Clone)]
+pub struct SqlQueryTool {
+    /// Database queried when the call does not name one
+    default_database: PathBuf,
+}
+
+/// SQL query tool actor state.
+///
+/// This actor wraps the `SqlQueryTool` executor for per-agent tool spawning.
+#[acton_actor]
+pub struct SqlQueryToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for SqlQueryToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
+
+/// Arguments for the sql_query tool.
+#[derive(Debug, Deserialize)]
+struct SqlQueryArgs {
+    /// Path to the database file
+    #[serde(default)]
+    database_path: Option<String>,
+    /// The SQL query to run
+    query: String,
+}
+
+impl Default for SqlQueryTool {
+    fn default() -> Self {
+        Self {
+            default_database: PathBuf::from(PersistenceConfig::default().db_path),
+        }
+    }
+}
+
+impl SqlQueryTool {
+    /// Creates a SQL query tool that defaults to the persistence database.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the database queried when the call has no `database_path`.
+    #[must_use]
+    pub fn with_default_database(mut self, path: impl Into<PathBuf>) -> Self {
+        self.default_database = path.into();
+        self
+    }
+
+    /// Returns the tool configuration for registration.
+    #[must_use]
+    pub fn config() -> ToolConfig {
+        ToolConfig::new(ToolDefinition {
+            name: "sql_query".to_string(),
+            description: "Run a read-only SQL query against a SQLite/libSQL database. Returns matching rows as JSON objects keyed by column name.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "database_path": {
+                        "type": "string",
+                        "description": "Path to the database file (default: the acton-ai persistence database)"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "SQL query to run. Only read-only statements such as SELECT are allowed.",
+                        "minLength": 1,
+                        "not": {
+                            "pattern": WRITE_STATEMENT_PATTERN,
+                            "description": "query must not contain INSERT, UPDATE, DELETE, DROP, ALTER, CREATE, ATTACH, DETACH or PRAGMA"
+                        }
+                    }
+                },
+                "required": ["query"]
+            }),
+        })
+    }
+
+    /// Rejects anything but a single read-only statement (`SELECT`,
+    /// `VALUES`, `EXPLAIN`, or a `WITH` query that only reads).
+    fn check_read_only(query: &str) -> Result<(), ToolError> {
+        match classify(query) {
+            Ok(StatementKind::Read) => Ok(()),
+            Ok(StatementKind::Write | StatementKind::Schema) => Err(ToolError::validation_failed(
+                "sql_query",
+                "only read-only queries (SELECT, VALUES, EXPLAIN or WITH) are allowed",
+            )),
+            Err(reason) => Err(ToolError::validation_failed("sql_query", reason)),
+        }
+    }
+
+    /// Runs `query` against the database at `path`.
+    async fn run_query(path: &Path, query: &str) -> Result<(Vec<Value>, bool), ToolError> {
+        let conn = connect("sql_query", path, libsql::OpenFlags::SQLITE_OPEN_READ_ONLY).await?;
+        query_rows("sql_query", &conn, query).await
+    }
+}
+
+/// Opens the database at `path` with `flags` on behalf of `tool_name`.
+pub(super) async fn connect(
+    tool_name: &str,
+    path: &Path,
+    flags: libsql::OpenFlags,
+) -> Result<libsql::Connection, ToolError> {
+    let db = libsql::Builder::new_local(path)
+        .flags(flags)
+        .build()
+        .await
+        .map_err(|e| {
+            ToolError::execution_failed(tool_name, format!("failed to open database: {e}"))
+        })?;
+    db.connect()
+        .map_err(|e| ToolError::execution_failed(tool_name, format!("failed to connect: {e}")))
+}
+
+/// Runs `query` and returns up to [`MAX_ROWS`] rows as JSON objects keyed by
+/// column name, plus whether rows were left out.
+pub(super) async fn query_rows(
+    tool_name: &str,
+    conn: &libsql::Connection,
+    query: &str,
+) -> Result<(Vec<Value>, bool), ToolError> {
+    let mut rows = conn
+        .query(query, ())
+        .await
+        .map_err(|e| ToolError::execution_failed(tool_name, format!("query failed: {e}")))?;
+
+    let columns: Vec<String> = (0..rows.column_count())
+        .map(|i| rows.column_name(i).unwrap_or_default().to_string())
+        .collect();
+
+    let mut results = Vec::new();
+    let mut truncated = false;
+    while let Some(row) = rows
+        .next()
+        .await
+        .map_err(|e| ToolError::execution_failed(tool_name, format!("query failed: {e}")))?
+    {
+        if results.len() >= MAX_ROWS {
+            truncated = true;
+            break;
+        }
+        let mut object = Map::new();
+        for (i, name) in (0..).zip(&columns) {
+            let value = row.get_value(i).map_err(|e| {
+                ToolError::execution_failed(tool_name, format!("failed to read row: {e}"))
+            })?;
+            object.insert(name.clone(), to_json(value));
+        }
+        results.push(Value::Object(object));
+    }
+
+    Ok((results, truncated))
+}
+
+/// Converts a column value to JSON; blobs become base64 strings.
+fn to_json(value: libsql::Value) -> Value {
+    match value {
+        libsql::Value::Null => Value::Null,
+        libsql::Value::Integer(i) => json!(i),
+        libsql::Value::Real(f) => json!(f),
+        libsql::Value::Text(s) => Value::String(s),
+        libsql::Value::Blob(bytes) => Value::String(BASE64.encode(bytes)),
+    }
+}
+
+impl ToolExecutorTrait for SqlQueryTool {
+    fn execute(&self, args: Value) -> ToolExecutionFuture {
+        let default_database = self.default_database.clone();
+
+        Box::pin(async move {
+            let args: SqlQueryArgs = serde_json::from_value(args).map_err(|e| {
+                ToolError::validation_failed("sql_query", format!("invalid arguments: {e}"))
+            })?;
+
+            Self::check_read_only(&args.query)?;
+
+            let path = args.database_path.map_or(default_database, PathBuf::from);
+
+            // Validate path using PathValidator for security
+            let validator = PathValidator::new();
+            let canonical_path = validator
+                .validate_file(&path)
+                .map_err(|e| ToolError::validation_failed("sql_query", e.to_string()))?;
+
+            // libsql futures are not `Sync`, so the query runs on its own task
+            let (rows, truncated) =
+                tokio::spawn(async move { Self::run_query(&canonical_path, &args.query).await })
+                    .await
+                    .map_err(|e| {
+                        ToolError::execution_failed("sql_query", format!("query task failed: {e}"))
+                    })??;
+
+            Ok(json!({
+                "rows": rows,
+                "row_count": rows.len(),
+                "truncated": truncated
+            }))
+        })
+    }
+
+    fn validate_args(&self, args: &Value) -> Result<(), ToolError> {
+        if let Err(violations) = Self::config().definition.validate_against_schema(args) {
+            let details: Vec<String> = violations.iter().map(ToString::to_string).collect();
+            return Err(ToolError::validation_failed(
+                "sql_query",
+                details.join("; "),
+            ));
+        }
+
+        let args: SqlQueryArgs = serde_json::from_value(args.clone()).map_err(|e| {
+            ToolError::validation_failed("sql_query", format!("invalid arguments: {e}"))
+        })?;
+
+        Self::check_read_only(&args.query)
+    }
+}
+
+impl ToolActor for SqlQueryToolActor {
+    fn name() -> &'static str {
+        "sql_query"
+    }
+
+    fn definition() -> ToolDefinition {
+        SqlQueryTool::config().definition
+    }
+
+    async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
+        let mut builder = runtime.new_actor_with_name::<Self>("sql_query_tool".to_string());
+
+        configure_tool_actor(&mut builder, |_| SqlQueryTool::new());
+
+        builder.start().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn sample_database() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("sample.db");
+        let db = libsql::Builder::new_local(&path).build().await.unwrap();
+        let conn = db.connect().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE users (id INTEGER, name TEXT, score REAL, avatar BLOB);
+             INSERT INTO users VALUES (1, 'ada', 9.5, x'0102');
+             INSERT INTO users VALUES (2, 'bob', NULL, NULL);",
+        )
+        .await
+        .unwrap();
+        (dir, path)
+    }
+
+    #[tokio::test]
+    async fn sql_query_returns_rows_as_objects() {
+        let (_dir, path) = sample_database().await;
+
+        let tool = SqlQueryTool::new();
+        let result = tool
+            .execute(json!({
+                "database_path": path.to_str().unwrap(),
+                "query": "SELECT * FROM users ORDER BY id"
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(result["row_count"], 2);
+        assert_eq!(
+            result["rows"][0],
+            json!({"id": 1, "name": "ada", "score": 9.5, "avatar": "AQI="})
+        );
+        assert_eq!(result["rows"][1]["score"], Value::Null);
+        assert!(!result["truncated"].as_bool().unwrap());
+    }
+
+    #[tokio::test]
+    async fn sql_query_uses_default_database() {
+        let (_dir, path) = sample_database().await;
+
+        let tool = SqlQueryTool::new().with_default_database(&path);
+        let result = tool
+            .execute(json!({"query": "SELECT count(*) AS n FROM users"}))
+            .await
+            .unwrap();
+
+        assert_eq!(result["rows"], json!([{"n": 2}]));
+    }
+
+    #[tokio::test]
+    async fn sql_query_rejects_write_statements() {
+        let (_dir, path) = sample_database().await;
+
+        let tool = SqlQueryTool::new();
+        for query in [
+            "DELETE FROM users",
+            "select 1; drop table users",
+            "Insert into users values (3, 'eve', 1, NULL)",
+            "PRAGMA table_info(users)",
+            "SELECT 1; SELECT 2",
+        ] {
+            let args = json!({"database_path": path.to_str().unwrap(), "query": query});
+            assert!(tool.validate_args(&args).is_err(), "{query}");
+            assert!(tool.execute(args).await.is_err(), "{query}");
+        }
+
+        // Keywords inside identifiers are not statements
+        let args = json!({"query": "SELECT created_at, updated FROM t"});
+        assert!(tool.validate_args(&args).is_ok());
+    }
+
+    #[test]
+    fn sql_query_schema_rejects_write_statements() {
+        let definition = SqlQueryTool::config().definition;
+
+        assert!(definition
+            .validate_against_schema(&json!({"query": "SELECT 1"}))
+            .is_ok());
+        assert!(definition
+            .validate_against_schema(&json!({"query": "UPDATE users SET name = 'x'"}))
+            .is_err());
+        assert!(definition
+            .validate_against_schema(&json!({"database_path": "a.db"}))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn sql_query_rejects_attach_outside_allowed_roots() {
+        let (_dir, path) = sample_database().await;
+        let (_outside, other) = sample_database().await;
+
+        let tool = SqlQueryTool::new();
+        for query in [
+            format!("ATTACH DATABASE '{}' AS x", other.display()),
+            format!("attach '{}' as x; select * from x.users", other.display()),
+        ] {
+            let args = json!({"database_path": path.to_str().unwrap(), "query": query});
+            assert!(tool.validate_args(&args).is_err(), "{query}");
+            assert!(tool.execute(args).await.is_err(), "{query}");
+        }
+    }
+
+    #[tokio::test]
+    async fn sql_query_rejects_path_outside_allowed_roots() {
+        let tool = SqlQueryTool::new();
+        let result = tool
+            .execute(json!({"database_path": "/etc/passwd", "query": "SELECT 1"}))
+            .await;
+
+        assert!(result.is_err());
+    }
+}
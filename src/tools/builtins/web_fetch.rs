@@ -3,7 +3,7 @@
 //! Fetches content from URLs.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
@@ -28,7 +28,20 @@ pub struct WebFetchTool {
 ///
 /// This actor wraps the `WebFetchTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct WebFetchToolActor;
+pub struct WebFetchToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for WebFetchToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 impl Default for WebFetchTool {
     fn default() -> Self {
@@ -137,16 +150,21 @@ impl WebFetchTool {
 
     /// Validates and normalizes the URL.
     fn validate_url(url: &str) -> Result<String, ToolError> {
+        Self::validate_url_for("web_fetch", url)
+    }
+
+    /// Validates and normalizes the URL, reporting errors against `tool_name`.
+    pub(super) fn validate_url_for(tool_name: &str, url: &str) -> Result<String, ToolError> {
         // Parse the URL
         let parsed = Url::parse(url)
-            .map_err(|e| ToolError::validation_failed("web_fetch", format!("invalid URL: {e}")))?;
+            .map_err(|e| ToolError::validation_failed(tool_name, format!("invalid URL: {e}")))?;
 
         // Only allow http and https
         match parsed.scheme() {
             "http" | "https" => {}
             scheme => {
                 return Err(ToolError::validation_failed(
-                    "web_fetch",
+                    tool_name,
                     format!("unsupported URL scheme: {scheme}; only http and https are allowed"),
                 ));
             }
@@ -169,7 +187,7 @@ impl WebFetchTool {
 
             if is_local {
                 return Err(ToolError::validation_failed(
-                    "web_fetch",
+                    tool_name,
                     "cannot fetch from localhost or private IP addresses",
                 ));
             }
@@ -177,6 +195,47 @@ impl WebFetchTool {
 
         Ok(parsed.to_string())
     }
+
+    /// Sends a GET request to an already validated `url` on behalf of
+    /// `tool_name`.
+    ///
+    /// Returns the status code and the body, cut to the maximum response
+    /// size. `timeout_secs` overrides the client timeout, capped at 120
+    /// seconds as for `web_fetch`.
+    pub(super) async fn get(
+        &self,
+        tool_name: &str,
+        url: &str,
+        timeout_secs: Option<u64>,
+    ) -> Result<(u16, Vec<u8>), ToolError> {
+        let mut request = self.client.get(url);
+        if let Some(timeout_secs) = timeout_secs {
+            request = request.timeout(Duration::from_secs(timeout_secs.min(120)));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Self::request_error(tool_name, &e, timeout_secs))?;
+        let status = response.status().as_u16();
+        let bytes = response.bytes().await.map_err(|e| {
+            ToolError::execution_failed(tool_name, format!("failed to read response: {e}"))
+        })?;
+
+        let end = bytes.len().min(self.max_response_size);
+        Ok((status, bytes[..end].to_vec()))
+    }
+
+    /// Maps a failed request to a timeout, connection or generic error.
+    fn request_error(tool_name: &str, e: &reqwest::Error, timeout_secs: Option<u64>) -> ToolError {
+        if e.is_timeout() {
+            ToolError::timeout(tool_name, Duration::from_secs(timeout_secs.unwrap_or(30)))
+        } else if e.is_connect() {
+            ToolError::execution_failed(tool_name, format!("connection failed: {e}"))
+        } else {
+            ToolError::execution_failed(tool_name, format!("request failed: {e}"))
+        }
+    }
 }
 
 impl ToolExecutorTrait for WebFetchTool {
@@ -248,15 +307,10 @@ impl ToolExecutorTrait for WebFetchTool {
             }
 
             // Execute request
-            let response = request.send().await.map_err(|e| {
-                if e.is_timeout() {
-                    ToolError::timeout("web_fetch", Duration::from_secs(args.timeout.unwrap_or(30)))
-                } else if e.is_connect() {
-                    ToolError::execution_failed("web_fetch", format!("connection failed: {e}"))
-                } else {
-                    ToolError::execution_failed("web_fetch", format!("request failed: {e}"))
-                }
-            })?;
+            let response = request
+                .send()
+                .await
+                .map_err(|e| Self::request_error("web_fetch", &e, args.timeout))?;
 
             let status = response.status();
             let status_code = status.as_u16();
@@ -333,29 +387,7 @@ impl ToolActor for WebFetchToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("web_fetch_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = WebFetchTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| WebFetchTool::new());
 
         builder.start().await
     }
@@ -452,4 +484,28 @@ mod tests {
     fn default_method_is_get() {
         assert_eq!(default_method(), "GET");
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(&WebFetchTool::config().definition)
+            .valid_example(json!({"url": "https://example.com"}))
+            .valid_example(json!({
+                "url": "https://example.com",
+                "method": "POST",
+                "headers": {"Accept": "text/html"},
+                "body": "{}",
+                "timeout": 120
+            }))
+            .invalid_example(json!({}), "url is required")
+            .invalid_example(
+                json!({"url": "https://example.com", "method": "DELETE"}),
+                "method not in enum",
+            )
+            .invalid_example(
+                json!({"url": "https://example.com", "headers": {"X-Count": 1}}),
+                "header values must be strings",
+            )
+            .run()
+            .assert_success();
+    }
 }
@@ -3,8 +3,8 @@
 //! Writes content to a file, creating parent directories if needed.
 
 use crate::messages::ToolDefinition;
-use crate::tools::actor::{ExecuteToolDirect, ToolActor, ToolActorResponse};
-use crate::tools::security::PathValidator;
+use crate::tools::actor::{configure_tool_actor, HasToolActorExtras, ToolActor, ToolActorExtras};
+use crate::tools::security::{Operation, PathValidator, SecurityPolicy, SymlinkPolicy};
 use crate::tools::{ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait};
 use acton_reactive::prelude::*;
 use serde::Deserialize;
@@ -14,14 +14,41 @@ use std::path::Path;
 /// Write file tool executor.
 ///
 /// Writes content to a file, creating parent directories as needed.
-#[derive(Debug, Default, Clone)]
-pub struct WriteFileTool;
+#[derive(Debug, Clone)]
+pub struct WriteFileTool {
+    /// Rules restricting which paths may be written
+    policy: Option<SecurityPolicy>,
+    /// How symlinks in the path are treated
+    symlink_policy: SymlinkPolicy,
+}
+
+impl Default for WriteFileTool {
+    fn default() -> Self {
+        Self {
+            policy: None,
+            symlink_policy: SymlinkPolicy::AllowWithinRoot,
+        }
+    }
+}
 
 /// Write file tool actor state.
 ///
 /// This actor wraps the `WriteFileTool` executor for per-agent tool spawning.
 #[acton_actor]
-pub struct WriteFileToolActor;
+pub struct WriteFileToolActor {
+    /// Call log, result cache, rate limit and coercion state
+    extras: ToolActorExtras,
+}
+
+impl HasToolActorExtras for WriteFileToolActor {
+    fn extras(&self) -> &ToolActorExtras {
+        &self.extras
+    }
+
+    fn extras_mut(&mut self) -> &mut ToolActorExtras {
+        &mut self.extras
+    }
+}
 
 /// Arguments for the write_file tool.
 #[derive(Debug, Deserialize)]
@@ -36,7 +63,23 @@ impl WriteFileTool {
     /// Creates a new write file tool.
     #[must_use]
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Enforces `policy` instead of the default path rules.
+    #[must_use]
+    pub fn with_policy(mut self, policy: SecurityPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Sets how symlinks in the path are treated.
+    ///
+    /// Defaults to [`SymlinkPolicy::AllowWithinRoot`].
+    #[must_use]
+    pub fn with_symlink_policy(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = policy;
+        self
     }
 
     /// Returns the tool configuration for registration.
@@ -68,6 +111,8 @@ impl WriteFileTool {
 
 impl ToolExecutorTrait for WriteFileTool {
     fn execute(&self, args: Value) -> ToolExecutionFuture {
+        let policy = self.policy.clone();
+        let symlink_policy = self.symlink_policy;
         Box::pin(async move {
             let args: WriteFileArgs = serde_json::from_value(args).map_err(|e| {
                 ToolError::validation_failed("write_file", format!("invalid arguments: {e}"))
@@ -85,16 +130,18 @@ impl ToolExecutorTrait for WriteFileTool {
 
             // Validate path using PathValidator for security
             // For write operations, validate the parent since the file may not exist yet
-            let validator = PathValidator::new();
+            let validator = policy
+                .map_or_else(PathValidator::new, PathValidator::new_with_policy)
+                .with_symlink_policy(symlink_policy);
             let validated_path = if path.exists() {
                 // File exists - validate the file path directly
                 validator
-                    .validate_file(path)
+                    .validate_file_for(Operation::Write, path)
                     .map_err(|e| ToolError::validation_failed("write_file", e.to_string()))?
             } else {
                 // File doesn't exist - validate the parent directory
                 validator
-                    .validate_parent(path)
+                    .validate_parent_for(Operation::Write, path)
                     .map_err(|e| ToolError::validation_failed("write_file", e.to_string()))?
             };
 
@@ -158,29 +205,7 @@ impl ToolActor for WriteFileToolActor {
     async fn spawn(runtime: &mut ActorRuntime) -> ActorHandle {
         let mut builder = runtime.new_actor_with_name::<Self>("write_file_tool".to_string());
 
-        builder.act_on::<ExecuteToolDirect>(|actor, envelope| {
-            let msg = envelope.message();
-            let correlation_id = msg.correlation_id.clone();
-            let tool_call_id = msg.tool_call_id.clone();
-            let args = msg.args.clone();
-            let broker = actor.broker().clone();
-
-            Reply::pending(async move {
-                let tool = WriteFileTool::new();
-                let result = tool.execute(args).await;
-
-                let response = match result {
-                    Ok(value) => {
-                        let result_str = serde_json::to_string(&value)
-                            .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
-                        ToolActorResponse::success(correlation_id, tool_call_id, result_str)
-                    }
-                    Err(e) => ToolActorResponse::error(correlation_id, tool_call_id, e.to_string()),
-                };
-
-                broker.broadcast(response).await;
-            })
-        });
+        configure_tool_actor(&mut builder, |_| WriteFileTool::new());
 
         builder.start().await
     }
@@ -255,6 +280,24 @@ mod tests {
         assert_eq!(content, "new content");
     }
 
+    #[tokio::test]
+    async fn write_file_rejected_by_read_only_policy() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("test.txt");
+
+        let tool =
+            WriteFileTool::new().with_policy(SecurityPolicy::read_only(dir.path().to_path_buf()));
+        let result = tool
+            .execute(json!({
+                "path": path.to_str().unwrap(),
+                "content": "test"
+            }))
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("write access"));
+        assert!(!path.exists());
+    }
+
     #[tokio::test]
     async fn write_file_relative_path_rejected() {
         let tool = WriteFileTool::new();
@@ -320,4 +363,14 @@ mod tests {
         let config = WriteFileTool::config();
         assert!(config.sandboxed, "write_file tool should require sandbox");
     }
+
+    #[test]
+    fn schema_harness_examples() {
+        crate::tools::testing::ToolSchemaTestHarness::for_tool(&WriteFileTool::config().definition)
+            .valid_example(json!({"path": "/tmp/a", "content": ""}))
+            .invalid_example(json!({"path": "/tmp/a"}), "content is required")
+            .invalid_example(json!({"content": "x"}), "path is required")
+            .run()
+            .assert_success();
+    }
 }
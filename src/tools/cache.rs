@@ -0,0 +1,481 @@
+//! Tool result caching.
+//!
+//! Deterministic tools such as `calculate`, `glob` and `read_file` on static
+//! files return the same result for the same arguments. A
+//! [`ToolResultCache`] remembers successful results so repeated calls skip
+//! the executor. Caching is opt-in per tool:
+//!
+//! - tool actors start caching when sent [`EnableResultCache`] (see
+//!   [`ToolActor::enable_result_cache`](crate::tools::ToolActor::enable_result_cache))
+//!   and report cache hits in [`ToolActorResponse::cache`](crate::tools::ToolActorResponse::cache)
+//! - [`BuiltinTools::with_result_cache`](crate::tools::builtins::BuiltinTools::with_result_cache)
+//!   caches a builtin for every [`ActonAI`](crate::facade::ActonAI) prompt that uses it
+//!
+//! Errors are never cached. Send [`ClearToolCache`] to drop a tool actor's
+//! cached results.
+
+use crate::tools::error::ToolError;
+use acton_reactive::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Derives a cache key from tool arguments.
+pub type CacheKeyFn = Arc<dyn Fn(&Value) -> String + Send + Sync>;
+
+/// Configuration for a tool result cache.
+#[derive(Clone)]
+pub struct ResultCacheConfig {
+    /// Maximum cached results; the oldest entry is evicted beyond this
+    pub max_entries: usize,
+    /// How long a result stays valid (`None` keeps it until evicted)
+    pub ttl: Option<Duration>,
+    /// Custom key derivation (`None` keys on the serialized arguments)
+    pub cache_key_fn: Option<CacheKeyFn>,
+}
+
+impl ResultCacheConfig {
+    /// Creates a config holding up to `max_entries` results with no TTL.
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            ttl: None,
+            cache_key_fn: None,
+        }
+    }
+
+    /// Expires cached results `ttl` after they were stored.
+    #[must_use]
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Derives cache keys with `key_fn` instead of the serialized arguments.
+    ///
+    /// Calls whose arguments map to the same key share a cached result, so
+    /// `key_fn` can ignore arguments that do not affect the output.
+    #[must_use]
+    pub fn with_key_fn<F>(mut self, key_fn: F) -> Self
+    where
+        F: Fn(&Value) -> String + Send + Sync + 'static,
+    {
+        self.cache_key_fn = Some(Arc::new(key_fn));
+        self
+    }
+}
+
+impl Default for ResultCacheConfig {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl fmt::Debug for ResultCacheConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResultCacheConfig")
+            .field("max_entries", &self.max_entries)
+            .field("ttl", &self.ttl)
+            .field("has_cache_key_fn", &self.cache_key_fn.is_some())
+            .finish()
+    }
+}
+
+/// Cache outcome attached to a tool response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolCacheStatus {
+    /// Whether this result was served from the cache
+    pub hit: bool,
+    /// Cache hits so far, including this call
+    pub hits: u64,
+    /// Cache misses so far, including this call
+    pub misses: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    value: Value,
+    stored_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    /// Keys in insertion order, oldest first
+    order: VecDeque<String>,
+    hits: u64,
+    misses: u64,
+}
+
+/// A bounded cache of successful tool results.
+///
+/// Shared between concurrent calls; all methods take `&self`.
+#[derive(Debug)]
+pub struct ToolResultCache {
+    config: ResultCacheConfig,
+    state: Mutex<CacheState>,
+}
+
+impl ToolResultCache {
+    /// Creates an empty cache.
+    #[must_use]
+    pub fn new(config: ResultCacheConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Returns the cache configuration.
+    #[must_use]
+    pub fn config(&self) -> &ResultCacheConfig {
+        &self.config
+    }
+
+    /// Returns the cache key for `args`.
+    #[must_use]
+    pub fn key(&self, args: &Value) -> String {
+        match &self.config.cache_key_fn {
+            Some(key_fn) => key_fn(args),
+            None => args.to_string(),
+        }
+    }
+
+    /// Looks up the cached result for `args`, counting a hit or a miss.
+    ///
+    /// Expired entries are dropped and count as misses.
+    pub fn get(&self, args: &Value) -> Option<Value> {
+        let key = self.key(args);
+        let mut state = self.lock();
+
+        let expired = match (state.entries.get(&key), self.config.ttl) {
+            (Some(entry), Some(ttl)) => entry.stored_at.elapsed() >= ttl,
+            _ => false,
+        };
+        if expired {
+            state.entries.remove(&key);
+            state.order.retain(|k| k != &key);
+        }
+
+        match state.entries.get(&key).map(|entry| entry.value.clone()) {
+            Some(value) => {
+                state.hits += 1;
+                Some(value)
+            }
+            None => {
+                state.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Stores `value` as the result for `args`, evicting the oldest entries
+    /// beyond `max_entries`.
+    pub fn insert(&self, args: &Value, value: Value) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+
+        let key = self.key(args);
+        let mut state = self.lock();
+        if state.entries.contains_key(&key) {
+            state.order.retain(|k| k != &key);
+        }
+        state.order.push_back(key.clone());
+        state.entries.insert(
+            key,
+            CacheEntry {
+                value,
+                stored_at: Instant::now(),
+            },
+        );
+
+        while state.entries.len() > self.config.max_entries {
+            let Some(oldest) = state.order.pop_front() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+    }
+
+    /// Drops every cached result. Hit and miss counts are kept.
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.order.clear();
+    }
+
+    /// Returns the number of cached results, including expired ones not yet
+    /// looked up.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns true if nothing is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the total cache hits.
+    #[must_use]
+    pub fn hits(&self) -> u64 {
+        self.lock().hits
+    }
+
+    /// Returns the total cache misses.
+    #[must_use]
+    pub fn misses(&self) -> u64 {
+        self.lock().misses
+    }
+
+    fn status(&self, hit: bool) -> ToolCacheStatus {
+        let state = self.lock();
+        ToolCacheStatus {
+            hit,
+            hits: state.hits,
+            misses: state.misses,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Answers from `cache` when possible, otherwise runs `run` and caches a
+/// successful result.
+///
+/// Without a cache this just runs `run` and reports no status.
+pub(crate) async fn execute_cached<F, Fut>(
+    cache: Option<&ToolResultCache>,
+    args: Value,
+    run: F,
+) -> (Result<Value, ToolError>, Option<ToolCacheStatus>)
+where
+    F: FnOnce(Value) -> Fut,
+    Fut: Future<Output = Result<Value, ToolError>>,
+{
+    let Some(cache) = cache else {
+        return (run(args).await, None);
+    };
+
+    if let Some(value) = cache.get(&args) {
+        return (Ok(value), Some(cache.status(true)));
+    }
+
+    let result = run(args.clone()).await;
+    if let Ok(value) = &result {
+        cache.insert(&args, value.clone());
+    }
+    (result, Some(cache.status(false)))
+}
+
+/// Message to start caching results on a tool actor.
+///
+/// Replaces any existing cache, dropping its results.
+#[acton_message]
+pub struct EnableResultCache {
+    /// Cache configuration
+    pub config: ResultCacheConfig,
+}
+
+/// Message to drop a tool actor's cached results.
+///
+/// Does nothing if caching is not enabled.
+#[acton_message]
+pub struct ClearToolCache;
+
+/// Actor models that can hold a result cache.
+pub(crate) trait ResultCaching {
+    /// Returns the actor's cache slot (`None` while caching is off).
+    fn result_cache_mut(&mut self) -> &mut Option<Arc<ToolResultCache>>;
+}
+
+/// Registers the result-cache message handlers on a tool actor builder.
+pub(crate) fn configure_result_cache_handlers<A>(builder: &mut ManagedActor<Idle, A>)
+where
+    A: ResultCaching + Default + Send + std::fmt::Debug + 'static,
+{
+    builder.mutate_on::<EnableResultCache>(|actor, envelope| {
+        let config = envelope.message().config.clone();
+        *actor.model.result_cache_mut() = Some(Arc::new(ToolResultCache::new(config)));
+        Reply::ready()
+    });
+
+    builder.mutate_on::<ClearToolCache>(|actor, _envelope| {
+        if let Some(cache) = actor.model.result_cache_mut() {
+            cache.clear();
+        }
+        Reply::ready()
+    });
+}
+
+/// Enables result caching on a running tool actor.
+pub async fn enable_result_cache(tool: &ActorHandle, config: ResultCacheConfig) {
+    tool.send(EnableResultCache { config }).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn run_counted(
+        cache: Option<&ToolResultCache>,
+        calls: &AtomicUsize,
+        args: Value,
+    ) -> (Result<Value, ToolError>, Option<ToolCacheStatus>) {
+        execute_cached(cache, args, |args| async move {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Ok(json!({"echo": args}))
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn second_call_is_served_from_cache() {
+        let cache = ToolResultCache::new(ResultCacheConfig::new(10));
+        let calls = AtomicUsize::new(0);
+
+        let (first, status) = run_counted(Some(&cache), &calls, json!({"a": 1})).await;
+        assert_eq!(
+            status,
+            Some(ToolCacheStatus {
+                hit: false,
+                hits: 0,
+                misses: 1
+            })
+        );
+
+        let (second, status) = run_counted(Some(&cache), &calls, json!({"a": 1})).await;
+        assert_eq!(first.unwrap(), second.unwrap());
+        assert!(status.unwrap().hit);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn without_cache_always_executes() {
+        let calls = AtomicUsize::new(0);
+        for _ in 0..2 {
+            let (_, status) = run_counted(None, &calls, json!(1)).await;
+            assert!(status.is_none());
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_cached() {
+        let cache = ToolResultCache::new(ResultCacheConfig::new(10));
+        let (result, _) = execute_cached(Some(&cache), json!(1), |_| async {
+            Err(ToolError::internal("boom"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn oldest_entry_is_evicted() {
+        let cache = ToolResultCache::new(ResultCacheConfig::new(2));
+        cache.insert(&json!(1), json!("one"));
+        cache.insert(&json!(2), json!("two"));
+        cache.insert(&json!(3), json!("three"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&json!(1)).is_none());
+        assert_eq!(cache.get(&json!(3)), Some(json!("three")));
+    }
+
+    #[test]
+    fn expired_entries_miss() {
+        let cache = ToolResultCache::new(ResultCacheConfig::new(10).with_ttl(Duration::ZERO));
+        cache.insert(&json!(1), json!("one"));
+
+        assert!(cache.get(&json!(1)).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn custom_key_fn_shares_entries() {
+        let config = ResultCacheConfig::new(10).with_key_fn(|args| args["path"].to_string());
+        let cache = ToolResultCache::new(config);
+        cache.insert(&json!({"path": "a", "verbose": true}), json!("content"));
+
+        assert_eq!(
+            cache.get(&json!({"path": "a", "verbose": false})),
+            Some(json!("content"))
+        );
+    }
+
+    #[test]
+    fn clear_drops_entries_but_keeps_counts() {
+        let cache = ToolResultCache::new(ResultCacheConfig::new(10));
+        cache.insert(&json!(1), json!("one"));
+        cache.get(&json!(1));
+        cache.clear();
+
+        assert!(cache.is_empty());
+        assert_eq!(cache.hits(), 1);
+    }
+
+    /// Sends one `calculate` call and returns the size of the call log once
+    /// it reaches `expected` records (or after a timeout).
+    async fn calculate_and_count_calls(
+        runtime: &mut ActorRuntime,
+        handle: &ActorHandle,
+        expected: usize,
+    ) -> usize {
+        use crate::tools::actor::{ExecuteToolDirect, ToolActor};
+        use crate::tools::builtins::CalculateToolActor;
+        use crate::types::CorrelationId;
+
+        handle
+            .send(ExecuteToolDirect::new(
+                CorrelationId::new(),
+                "tc",
+                json!({"expression": "2 + 2"}),
+            ))
+            .await;
+
+        let mut records = Vec::new();
+        for _ in 0..50 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            records = CalculateToolActor::get_call_log(runtime, handle).await;
+            if records.len() >= expected {
+                break;
+            }
+        }
+        records.len()
+    }
+
+    #[tokio::test]
+    async fn tool_actor_skips_executor_on_cache_hit() {
+        use crate::tools::actor::ToolActor;
+        use crate::tools::builtins::CalculateToolActor;
+
+        let mut app = ActonApp::launch_async().await;
+        let mut runtime = app.clone();
+        let handle = CalculateToolActor::spawn(&mut runtime).await;
+        CalculateToolActor::enable_call_log(&handle).await;
+        CalculateToolActor::enable_result_cache(&handle, ResultCacheConfig::new(10)).await;
+
+        // Cache hits never reach the executor, so only misses are recorded
+        assert_eq!(calculate_and_count_calls(&mut runtime, &handle, 1).await, 1);
+        assert_eq!(calculate_and_count_calls(&mut runtime, &handle, 2).await, 1);
+
+        handle.send(ClearToolCache).await;
+        assert_eq!(calculate_and_count_calls(&mut runtime, &handle, 2).await, 2);
+
+        app.shutdown_all().await.unwrap();
+    }
+}
@@ -0,0 +1,405 @@
+//! Normalization of loosely typed tool arguments.
+//!
+//! LLMs sometimes send `"5"` for an integer, `"true"` for a boolean, or a
+//! single value where a list is expected. [`CoercionRules`] are derived from
+//! a tool's input schema and convert such arguments to the declared type
+//! before the tool sees them, logging a [`CoercionWarning`] for each change.
+//!
+//! Top-level properties are coerced, as are the values of object properties
+//! whose `additionalProperties` declare a type. Conversions happen only when
+//! they are lossless; anything else is passed through for schema validation
+//! to report.
+//!
+//! Builtin tool actors coerce with rules derived from their schema until
+//! sent [`SetInputCoercion`] (see
+//! [`ToolActor::set_input_coercion`](crate::tools::ToolActor::set_input_coercion)).
+
+use acton_reactive::prelude::*;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// JSON type an argument can be coerced to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoercionTarget {
+    /// Strings of digits and whole floats become integers
+    Integer,
+    /// Numeric strings become numbers
+    Number,
+    /// `"true"`/`"false"`/`"1"`/`"0"` strings become booleans
+    Boolean,
+    /// Non-array values are wrapped in a single-element array
+    Array,
+}
+
+impl CoercionTarget {
+    fn from_schema_type(schema_type: &str) -> Option<Self> {
+        match schema_type {
+            "integer" => Some(Self::Integer),
+            "number" => Some(Self::Number),
+            "boolean" => Some(Self::Boolean),
+            "array" => Some(Self::Array),
+            _ => None,
+        }
+    }
+
+    /// Reads the coercible type of a property schema. A property with several
+    /// types (e.g., `["integer", "null"]`) uses the first coercible one.
+    fn of_property(property: &Value) -> Option<Self> {
+        match property.get("type")? {
+            Value::String(t) => Self::from_schema_type(t),
+            Value::Array(types) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .find_map(Self::from_schema_type),
+            _ => None,
+        }
+    }
+
+    /// Returns the converted value, or `None` if `value` already has this
+    /// type or cannot be converted losslessly.
+    fn coerce(self, value: &Value) -> Option<Value> {
+        match (self, value) {
+            (Self::Integer, Value::String(s)) => s.trim().parse::<i64>().ok().map(Value::from),
+            (Self::Integer, Value::Number(n)) if !n.is_i64() && !n.is_u64() => {
+                let f = n.as_f64()?;
+                (f.fract() == 0.0 && f.abs() < i64::MAX as f64).then(|| Value::from(f as i64))
+            }
+            (Self::Number, Value::String(s)) => s
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            (Self::Boolean, Value::String(s)) => match s.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => Some(Value::Bool(true)),
+                "false" | "0" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            (Self::Array, Value::Array(_) | Value::Null) => None,
+            (Self::Array, other) => Some(Value::Array(vec![other.clone()])),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CoercionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer => write!(f, "integer"),
+            Self::Number => write!(f, "number"),
+            Self::Boolean => write!(f, "boolean"),
+            Self::Array => write!(f, "array"),
+        }
+    }
+}
+
+/// An argument that was converted to its declared type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoercionWarning {
+    /// The argument name
+    pub field: String,
+    /// JSON type the LLM sent
+    pub from_type: &'static str,
+    /// Type declared by the schema
+    pub to_type: CoercionTarget,
+}
+
+impl fmt::Display for CoercionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "coerced '{}' from {} to {}",
+            self.field, self.from_type, self.to_type
+        )
+    }
+}
+
+/// How one argument is coerced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldRule {
+    /// The argument itself
+    Value(CoercionTarget),
+    /// Every value of an object argument
+    MapValues(CoercionTarget),
+}
+
+/// Per-argument type coercions for a tool.
+///
+/// # Example
+///
+/// ```rust
+/// use acton_ai::tools::CoercionRules;
+/// use serde_json::json;
+///
+/// let rules = CoercionRules::from_schema(&json!({
+///     "type": "object",
+///     "properties": {
+///         "limit": {"type": "integer"},
+///         "paths": {"type": "array", "items": {"type": "string"}}
+///     }
+/// }));
+///
+/// let (args, warnings) = rules.coerce(json!({"limit": "10", "paths": "src"}));
+/// assert_eq!(args, json!({"limit": 10, "paths": ["src"]}));
+/// assert_eq!(warnings.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoercionRules {
+    fields: BTreeMap<String, FieldRule>,
+}
+
+impl CoercionRules {
+    /// Derives rules from the `properties` of a JSON schema.
+    ///
+    /// Properties typed `integer`, `number`, `boolean`, or `array` get a
+    /// rule, and so do object properties whose `additionalProperties` have
+    /// one of those types.
+    #[must_use]
+    pub fn from_schema(schema: &Value) -> Self {
+        let fields = schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, property)| {
+                let rule = match CoercionTarget::of_property(property) {
+                    Some(target) => FieldRule::Value(target),
+                    None => FieldRule::MapValues(CoercionTarget::of_property(
+                        property.get("additionalProperties")?,
+                    )?),
+                };
+                Some((name.clone(), rule))
+            })
+            .collect();
+        Self { fields }
+    }
+
+    /// Rules that coerce nothing, for tools where type safety is critical.
+    #[must_use]
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the rule for `field`.
+    #[must_use]
+    pub fn with_field(mut self, field: impl Into<String>, target: CoercionTarget) -> Self {
+        self.fields.insert(field.into(), FieldRule::Value(target));
+        self
+    }
+
+    /// Returns whether no argument is coerced.
+    #[must_use]
+    pub fn is_strict(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Converts `args` to the declared types, returning what was changed.
+    #[must_use]
+    pub fn coerce(&self, mut args: Value) -> (Value, Vec<CoercionWarning>) {
+        let mut warnings = Vec::new();
+        if let Some(object) = args.as_object_mut() {
+            for (field, rule) in &self.fields {
+                match *rule {
+                    FieldRule::Value(target) => {
+                        warnings.extend(coerce_field(object, field, field, target));
+                    }
+                    FieldRule::MapValues(target) => {
+                        let Some(values) = object.get_mut(field).and_then(Value::as_object_mut)
+                        else {
+                            continue;
+                        };
+                        let keys: Vec<String> = values.keys().cloned().collect();
+                        for key in keys {
+                            let label = format!("{field}.{key}");
+                            warnings.extend(coerce_field(values, &key, &label, target));
+                        }
+                    }
+                }
+            }
+        }
+        (args, warnings)
+    }
+
+    /// Converts `args` to the declared types, logging each change for
+    /// `tool_name` with `tracing::warn!`.
+    #[must_use]
+    pub fn apply(&self, tool_name: &str, args: Value) -> Value {
+        let (args, warnings) = self.coerce(args);
+        for warning in &warnings {
+            tracing::warn!(
+                tool_name,
+                field = %warning.field,
+                from_type = warning.from_type,
+                to_type = %warning.to_type,
+                "Coerced tool argument"
+            );
+        }
+        args
+    }
+}
+
+/// Coerces `object[key]`, reporting it as `label`.
+fn coerce_field(
+    object: &mut Map<String, Value>,
+    key: &str,
+    label: &str,
+    target: CoercionTarget,
+) -> Option<CoercionWarning> {
+    let value = object.get_mut(key)?;
+    let coerced = target.coerce(value)?;
+    let from_type = json_type(value);
+    *value = coerced;
+    Some(CoercionWarning {
+        field: label.to_string(),
+        from_type,
+        to_type: target,
+    })
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Message to replace the coercion rules of a tool actor.
+///
+/// Send [`CoercionRules::strict`] to stop the actor coercing arguments.
+#[acton_message]
+pub struct SetInputCoercion {
+    /// The new rules
+    pub rules: CoercionRules,
+}
+
+/// Actor models that can hold configured coercion rules.
+pub(crate) trait InputCoercing {
+    /// Returns the actor's coercion rules slot (`None` uses the rules
+    /// derived from its schema).
+    fn input_coercion_mut(&mut self) -> &mut Option<CoercionRules>;
+}
+
+/// Registers the coercion message handler on a tool actor builder.
+pub(crate) fn configure_input_coercion_handlers<A>(builder: &mut ManagedActor<Idle, A>)
+where
+    A: InputCoercing + Default + Send + std::fmt::Debug + 'static,
+{
+    builder.mutate_on::<SetInputCoercion>(|actor, envelope| {
+        *actor.model.input_coercion_mut() = Some(envelope.message().rules.clone());
+        Reply::ready()
+    });
+}
+
+/// Replaces the coercion rules of a running tool actor.
+pub async fn set_input_coercion(tool: &ActorHandle, rules: CoercionRules) {
+    tool.send(SetInputCoercion { rules }).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rules() -> CoercionRules {
+        CoercionRules::from_schema(&json!({
+            "type": "object",
+            "properties": {
+                "limit": {"type": "integer"},
+                "ratio": {"type": "number"},
+                "recursive": {"type": "boolean"},
+                "tags": {"type": "array"},
+                "offset": {"type": ["integer", "null"]},
+                "name": {"type": "string"}
+            }
+        }))
+    }
+
+    #[test]
+    fn coerces_strings_to_declared_types() {
+        let (args, warnings) = rules().coerce(json!({
+            "limit": " 42 ",
+            "ratio": "0.5",
+            "recursive": "TRUE",
+            "tags": "rust",
+            "offset": 3.0,
+            "name": "7"
+        }));
+
+        assert_eq!(
+            args,
+            json!({
+                "limit": 42,
+                "ratio": 0.5,
+                "recursive": true,
+                "tags": ["rust"],
+                "offset": 3,
+                "name": "7"
+            })
+        );
+        assert_eq!(warnings.len(), 5);
+        assert!(warnings.contains(&CoercionWarning {
+            field: "limit".to_string(),
+            from_type: "string",
+            to_type: CoercionTarget::Integer,
+        }));
+    }
+
+    #[test]
+    fn leaves_well_typed_and_unconvertible_values_alone() {
+        let args = json!({
+            "limit": "many",
+            "recursive": "yes",
+            "tags": ["a"],
+            "offset": 1.5
+        });
+        let (coerced, warnings) = rules().coerce(args.clone());
+        assert_eq!(coerced, args);
+        assert!(warnings.is_empty());
+
+        let (coerced, _) = rules().coerce(json!({"recursive": "0", "tags": null}));
+        assert_eq!(coerced, json!({"recursive": false, "tags": null}));
+    }
+
+    #[test]
+    fn coerces_typed_object_values() {
+        let rules = CoercionRules::from_schema(&json!({
+            "properties": {
+                "variables": {
+                    "type": "object",
+                    "additionalProperties": {"type": "number"}
+                }
+            }
+        }));
+
+        let (args, warnings) = rules.coerce(json!({"variables": {"x": "5", "y": 2}}));
+        assert_eq!(args, json!({"variables": {"x": 5.0, "y": 2}}));
+        assert_eq!(warnings[0].field, "variables.x");
+        assert_eq!(
+            warnings[0].to_string(),
+            "coerced 'variables.x' from string to number"
+        );
+    }
+
+    #[test]
+    fn strict_rules_coerce_nothing() {
+        let strict = CoercionRules::strict();
+        assert!(strict.is_strict());
+        let (args, warnings) = strict.coerce(json!({"limit": "42"}));
+        assert_eq!(args, json!({"limit": "42"}));
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn with_field_adds_rules() {
+        let rules = CoercionRules::strict().with_field("count", CoercionTarget::Integer);
+        assert_eq!(
+            rules.apply("tool", json!({"count": "3"})),
+            json!({"count": 3})
+        );
+    }
+}
@@ -4,7 +4,11 @@
 //! that wraps tool definitions with execution configuration.
 
 use crate::messages::ToolDefinition;
+use crate::tools::cache::ResultCacheConfig;
+use crate::tools::coercion::CoercionRules;
 use crate::tools::error::ToolError;
+use crate::tools::middleware::{ToolMiddleware, ToolMiddlewareStack};
+use crate::tools::versioning::ToolSchemaVersion;
 use serde_json::Value;
 use std::fmt::Debug;
 use std::future::Future;
@@ -23,6 +27,12 @@ pub struct ToolConfig {
     pub sandboxed: bool,
     /// Execution timeout
     pub timeout: Duration,
+    /// Middleware run around every execution
+    pub middleware: ToolMiddlewareStack,
+    /// Result caching (`None` executes every call)
+    pub result_cache: Option<ResultCacheConfig>,
+    /// Argument type coercion (`None` passes arguments through unchanged)
+    pub input_coercion: Option<CoercionRules>,
 }
 
 impl ToolConfig {
@@ -33,6 +43,9 @@ impl ToolConfig {
             definition,
             sandboxed: false,
             timeout: Duration::from_secs(30),
+            middleware: ToolMiddlewareStack::new(),
+            result_cache: None,
+            input_coercion: None,
         }
     }
 
@@ -49,6 +62,57 @@ impl ToolConfig {
         self.timeout = timeout;
         self
     }
+
+    /// Adds a middleware that runs around every execution of this tool.
+    ///
+    /// Middleware added later runs inside middleware added earlier; see
+    /// [`crate::tools::middleware`].
+    #[must_use]
+    pub fn with_middleware(mut self, middleware: impl ToolMiddleware + 'static) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Caches successful results of this tool.
+    ///
+    /// Only worthwhile for tools whose output depends solely on their
+    /// arguments; see [`crate::tools::cache`]. Like `sandboxed`, this is
+    /// metadata the [`ToolRegistry`](crate::tools::ToolRegistry) does not act
+    /// on.
+    #[must_use]
+    pub fn with_result_cache(mut self, config: ResultCacheConfig) -> Self {
+        self.result_cache = Some(config);
+        self
+    }
+
+    /// Converts loosely typed arguments before every execution.
+    ///
+    /// Build the rules from the tool's schema with
+    /// [`CoercionRules::from_schema`], or pass [`CoercionRules::strict`] to
+    /// disable coercion; see [`crate::tools::coercion`].
+    #[must_use]
+    pub fn with_input_coercion(mut self, rules: CoercionRules) -> Self {
+        self.input_coercion = Some(rules);
+        self
+    }
+
+    /// Converts `args` with the configured coercion rules, if any.
+    #[must_use]
+    pub fn coerce_args(&self, args: Value) -> Value {
+        match &self.input_coercion {
+            Some(rules) => rules.apply(&self.definition.name, args),
+            None => args,
+        }
+    }
+
+    /// Returns the schema version of this tool's input schema.
+    ///
+    /// Standalone configs are always version 1; the
+    /// [`ToolRegistry`](crate::tools::ToolRegistry) numbers later versions.
+    #[must_use]
+    pub fn schema_version(&self) -> ToolSchemaVersion {
+        ToolSchemaVersion::of(&self.definition)
+    }
 }
 
 impl Default for ToolConfig {
@@ -65,6 +129,9 @@ impl Default for ToolConfig {
             },
             sandboxed: false,
             timeout: Duration::from_secs(30),
+            middleware: ToolMiddlewareStack::new(),
+            result_cache: None,
+            input_coercion: None,
         }
     }
 }
@@ -129,6 +196,19 @@ pub trait ToolExecutorTrait: Send + Sync + Debug {
     fn validate_args(&self, _args: &Value) -> Result<(), ToolError> {
         Ok(())
     }
+
+    /// Called before every execution, after argument validation.
+    ///
+    /// Returning an error rejects the call without executing it.
+    /// The default implementation does nothing.
+    fn pre_execute(&self, _args: &Value) -> Result<(), ToolError> {
+        Ok(())
+    }
+
+    /// Called after every successful execution with its result and duration.
+    ///
+    /// The default implementation does nothing.
+    fn post_execute(&self, _args: &Value, _result: &Value, _elapsed: Duration) {}
 }
 
 /// A boxed tool executor for dynamic dispatch.
@@ -171,5 +251,24 @@ mod tests {
         assert_eq!(config.definition.name, "unnamed");
         assert!(!config.sandboxed);
         assert_eq!(config.timeout, Duration::from_secs(30));
+        assert!(config.middleware.is_empty());
+        assert!(config.result_cache.is_none());
+    }
+
+    #[test]
+    fn tool_config_with_result_cache() {
+        let config = ToolConfig::new(make_test_definition("t", "d"))
+            .with_result_cache(ResultCacheConfig::new(5).with_ttl(Duration::from_secs(60)));
+        let cache = config.result_cache.unwrap();
+        assert_eq!(cache.max_entries, 5);
+        assert_eq!(cache.ttl, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn tool_config_with_middleware() {
+        let config = ToolConfig::new(make_test_definition("t", "d"))
+            .with_middleware(crate::tools::LoggingMiddleware)
+            .with_middleware(crate::tools::TimingMiddleware::new());
+        assert_eq!(config.middleware.len(), 2);
     }
 }
@@ -4,6 +4,7 @@
 //! validation, execution, and sandbox errors.
 
 use crate::types::CorrelationId;
+use std::error::Error;
 use std::fmt;
 use std::time::Duration;
 
@@ -58,6 +59,13 @@ pub enum ToolErrorKind {
         /// Description of the sandbox error
         message: String,
     },
+    /// Tool call rejected by a rate limit
+    RateLimited {
+        /// The name of the tool
+        tool_name: String,
+        /// Seconds until a call would be accepted
+        retry_after_secs: u32,
+    },
     /// Registry is shutting down
     ShuttingDown,
     /// Internal error
@@ -117,6 +125,35 @@ impl ToolError {
         })
     }
 
+    /// Creates an execution failed error from `error` and its sources.
+    ///
+    /// The reason is the error message followed by each source, separated
+    /// by `": "`.
+    #[must_use]
+    pub fn execution_from_error<E: Error>(tool_name: impl Into<String>, error: E) -> Self {
+        Self::execution_failed(tool_name, error_chain(&error))
+    }
+
+    /// Creates an execution failed error from a boxed error.
+    ///
+    /// Accepts anything convertible to `Box<dyn Error + Send + Sync>`,
+    /// including `anyhow::Error`.
+    #[must_use]
+    pub fn from_std(
+        tool_name: impl Into<String>,
+        error: impl Into<Box<dyn Error + Send + Sync>>,
+    ) -> Self {
+        Self::execution_failed(tool_name, error_chain(error.into().as_ref()))
+    }
+
+    /// Creates an execution failed error from an [`anyhow::Error`] and its
+    /// context chain.
+    #[cfg(feature = "anyhow")]
+    #[must_use]
+    pub fn from_anyhow(tool_name: impl Into<String>, error: anyhow::Error) -> Self {
+        Self::execution_failed(tool_name, format!("{error:#}"))
+    }
+
     /// Creates a timeout error.
     #[must_use]
     pub fn timeout(tool_name: impl Into<String>, duration: Duration) -> Self {
@@ -143,6 +180,15 @@ impl ToolError {
         })
     }
 
+    /// Creates a rate limited error.
+    #[must_use]
+    pub fn rate_limited(tool_name: impl Into<String>, retry_after_secs: u32) -> Self {
+        Self::new(ToolErrorKind::RateLimited {
+            tool_name: tool_name.into(),
+            retry_after_secs,
+        })
+    }
+
     /// Creates a shutting down error.
     #[must_use]
     pub fn shutting_down() -> Self {
@@ -162,10 +208,18 @@ impl ToolError {
     pub fn is_retriable(&self) -> bool {
         matches!(
             *self.kind,
-            ToolErrorKind::Timeout { .. } | ToolErrorKind::SandboxError { .. }
+            ToolErrorKind::Timeout { .. }
+                | ToolErrorKind::SandboxError { .. }
+                | ToolErrorKind::RateLimited { .. }
         )
     }
 
+    /// Returns true if this error indicates the call was rate limited.
+    #[must_use]
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(*self.kind, ToolErrorKind::RateLimited { .. })
+    }
+
     /// Returns true if this error indicates the tool was not found.
     #[must_use]
     pub fn is_not_found(&self) -> bool {
@@ -224,6 +278,16 @@ impl fmt::Display for ToolError {
             ToolErrorKind::SandboxError { message } => {
                 write!(f, "sandbox error: {}", message)
             }
+            ToolErrorKind::RateLimited {
+                tool_name,
+                retry_after_secs,
+            } => {
+                write!(
+                    f,
+                    "tool '{}' is rate limited; retry after {} seconds",
+                    tool_name, retry_after_secs
+                )
+            }
             ToolErrorKind::ShuttingDown => {
                 write!(
                     f,
@@ -239,6 +303,52 @@ impl fmt::Display for ToolError {
 
 impl std::error::Error for ToolError {}
 
+/// Converts a tool's own errors into [`ToolError`]s.
+///
+/// Lets tool executors use `?` on results whose error is any
+/// `std::error::Error` (or `anyhow::Error`):
+///
+/// ```
+/// use acton_ai::tools::{ToolError, ToolResultExt};
+///
+/// fn parse(input: &str) -> Result<u32, ToolError> {
+///     let value = input.parse::<u32>().map_tool_err("parse")?;
+///     Ok(value)
+/// }
+///
+/// assert!(parse("12").is_ok());
+/// assert!(parse("x").unwrap_err().to_string().contains("'parse'"));
+/// ```
+pub trait ToolResultExt<T> {
+    /// Maps the error to an execution failed error for `tool_name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the converted error if `self` is an error.
+    fn map_tool_err(self, tool_name: &str) -> Result<T, ToolError>;
+}
+
+impl<T, E> ToolResultExt<T> for Result<T, E>
+where
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    fn map_tool_err(self, tool_name: &str) -> Result<T, ToolError> {
+        self.map_err(|e| ToolError::from_std(tool_name, e))
+    }
+}
+
+/// Formats an error followed by its sources, like `anyhow`'s `{:#}`.
+fn error_chain(error: &dyn Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -322,6 +432,17 @@ mod tests {
         assert!(message.contains(&corr_id.to_string()));
     }
 
+    #[test]
+    fn tool_error_rate_limited_display_and_retriable() {
+        let error = ToolError::rate_limited("bash", 12);
+        let message = error.to_string();
+        assert!(message.contains("bash"));
+        assert!(message.contains("rate limited"));
+        assert!(message.contains("12"));
+        assert!(error.is_rate_limited());
+        assert!(error.is_retriable());
+    }
+
     #[test]
     fn tool_error_is_retriable_for_timeout() {
         let error = ToolError::timeout("slow_tool", Duration::from_secs(30));
@@ -392,4 +513,44 @@ mod tests {
         let error = ToolError::not_found("test");
         assert!(matches!(error.kind(), ToolErrorKind::NotFound { .. }));
     }
+
+    #[derive(Debug)]
+    struct Wrapped(std::num::ParseIntError);
+
+    impl fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "bad count")
+        }
+    }
+
+    impl Error for Wrapped {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn execution_from_error_includes_sources() {
+        let cause = "x".parse::<u32>().unwrap_err();
+        let error = ToolError::execution_from_error("count", Wrapped(cause));
+
+        match error.kind() {
+            ToolErrorKind::ExecutionFailed { tool_name, reason } => {
+                assert_eq!(tool_name, "count");
+                assert_eq!(reason, "bad count: invalid digit found in string");
+            }
+            other => panic!("unexpected kind: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn map_tool_err_converts_std_errors() {
+        let result: Result<u32, ToolError> = "x".parse::<u32>().map_tool_err("parse");
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            ToolErrorKind::ExecutionFailed { .. }
+        ));
+        assert!(error.to_string().contains("invalid digit"));
+    }
 }
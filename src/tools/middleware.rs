@@ -0,0 +1,341 @@
+//! Tool middleware.
+//!
+//! A [`ToolMiddleware`] runs around every execution of the tools it is
+//! attached to, which is where cross-cutting concerns such as logging,
+//! metrics and authorization checks belong. Middleware is attached with
+//! [`ToolConfig::with_middleware`](crate::tools::ToolConfig::with_middleware)
+//! and composed into a [`ToolMiddlewareStack`]: `pre_execute` hooks run in
+//! the order the middleware was added, `post_execute` hooks in reverse, so
+//! the first middleware added is the outermost layer.
+//!
+//! Two implementations are built in:
+//!
+//! - [`TimingMiddleware`] records execution durations into [`ToolMetrics`]
+//! - [`LoggingMiddleware`] logs each call with `tracing::info!`
+//!
+//! # Example
+//!
+//! ```rust
+//! use acton_ai::tools::{LoggingMiddleware, TimingMiddleware, ToolConfig, ToolDefinition};
+//!
+//! let timing = TimingMiddleware::new();
+//! let config = ToolConfig::new(ToolDefinition {
+//!     name: "echo".to_string(),
+//!     description: "Echoes its input".to_string(),
+//!     input_schema: serde_json::json!({"type": "object"}),
+//! })
+//! .with_middleware(LoggingMiddleware)
+//! .with_middleware(timing.clone());
+//!
+//! assert_eq!(config.middleware.len(), 2);
+//! assert!(timing.metrics("echo").is_none());
+//! ```
+
+use crate::tools::definition::ToolExecutorTrait;
+use crate::tools::error::ToolError;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Hooks that run around tool execution.
+///
+/// Both hooks default to no-ops, so an implementation only overrides the
+/// ones it needs.
+pub trait ToolMiddleware: Send + Sync + Debug {
+    /// Called before the tool executes.
+    ///
+    /// Returning an error rejects the call; the tool does not run and the
+    /// error is returned to the caller.
+    fn pre_execute(&self, tool_name: &str, args: &Value) -> Result<(), ToolError> {
+        let _ = (tool_name, args);
+        Ok(())
+    }
+
+    /// Called after the tool executed successfully.
+    ///
+    /// `elapsed` covers the tool's own execution only, not other middleware.
+    fn post_execute(&self, tool_name: &str, args: &Value, result: &Value, elapsed: Duration) {
+        let _ = (tool_name, args, result, elapsed);
+    }
+}
+
+/// An ordered chain of [`ToolMiddleware`].
+///
+/// The stack is itself a middleware, so stacks nest.
+#[derive(Debug, Clone, Default)]
+pub struct ToolMiddlewareStack {
+    layers: Vec<Arc<dyn ToolMiddleware>>,
+}
+
+impl ToolMiddlewareStack {
+    /// Creates an empty stack.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `middleware` inside the layers already in the stack.
+    #[must_use]
+    pub fn layer(mut self, middleware: impl ToolMiddleware + 'static) -> Self {
+        self.push(middleware);
+        self
+    }
+
+    /// Adds `middleware` inside the layers already in the stack.
+    pub fn push(&mut self, middleware: impl ToolMiddleware + 'static) {
+        self.layers.push(Arc::new(middleware));
+    }
+
+    /// Returns the number of middleware in the stack.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Returns true if the stack has no middleware.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl ToolMiddleware for ToolMiddlewareStack {
+    fn pre_execute(&self, tool_name: &str, args: &Value) -> Result<(), ToolError> {
+        self.layers
+            .iter()
+            .try_for_each(|layer| layer.pre_execute(tool_name, args))
+    }
+
+    fn post_execute(&self, tool_name: &str, args: &Value, result: &Value, elapsed: Duration) {
+        for layer in self.layers.iter().rev() {
+            layer.post_execute(tool_name, args, result, elapsed);
+        }
+    }
+}
+
+/// Runs `run` wrapped in `middleware` and the executor's own hooks.
+///
+/// `run` performs the actual execution, which lets sandboxed calls share the
+/// hooks of in-process ones.
+pub(crate) async fn execute_with_hooks<T, F, Fut>(
+    executor: &T,
+    middleware: &ToolMiddlewareStack,
+    tool_name: &str,
+    args: Value,
+    run: F,
+) -> Result<Value, ToolError>
+where
+    T: ToolExecutorTrait + ?Sized,
+    F: FnOnce(Value) -> Fut,
+    Fut: Future<Output = Result<Value, ToolError>>,
+{
+    middleware.pre_execute(tool_name, &args)?;
+    executor.pre_execute(&args)?;
+
+    let started = Instant::now();
+    let result = run(args.clone()).await?;
+    let elapsed = started.elapsed();
+
+    executor.post_execute(&args, &result, elapsed);
+    middleware.post_execute(tool_name, &args, &result, elapsed);
+    Ok(result)
+}
+
+/// Execution timings for one tool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolMetrics {
+    /// Number of successful executions
+    pub calls: u64,
+    /// Sum of all execution durations
+    pub total_duration: Duration,
+    /// Longest single execution
+    pub max_duration: Duration,
+    /// Duration of the most recent execution
+    pub last_duration: Duration,
+}
+
+impl ToolMetrics {
+    /// Returns the mean execution duration, or zero if nothing ran.
+    #[must_use]
+    pub fn average_duration(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            self.total_duration.div_f64(self.calls as f64)
+        }
+    }
+
+    fn record(&mut self, elapsed: Duration) {
+        self.calls += 1;
+        self.total_duration += elapsed;
+        self.max_duration = self.max_duration.max(elapsed);
+        self.last_duration = elapsed;
+    }
+}
+
+/// Middleware that records execution durations per tool.
+///
+/// Clones share the same metrics, so keep a clone to read them after
+/// attaching the middleware to one or more tools.
+#[derive(Debug, Clone, Default)]
+pub struct TimingMiddleware {
+    metrics: Arc<Mutex<HashMap<String, ToolMetrics>>>,
+}
+
+impl TimingMiddleware {
+    /// Creates a middleware with no recorded metrics.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the metrics recorded for `tool_name`, if it has run.
+    #[must_use]
+    pub fn metrics(&self, tool_name: &str) -> Option<ToolMetrics> {
+        self.metrics
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(tool_name)
+            .copied()
+    }
+
+    /// Returns the metrics recorded for every tool that has run.
+    #[must_use]
+    pub fn all_metrics(&self) -> HashMap<String, ToolMetrics> {
+        self.metrics
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+}
+
+impl ToolMiddleware for TimingMiddleware {
+    fn post_execute(&self, tool_name: &str, _args: &Value, _result: &Value, elapsed: Duration) {
+        self.metrics
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(tool_name.to_string())
+            .or_default()
+            .record(elapsed);
+    }
+}
+
+/// Middleware that logs every tool call at info level.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoggingMiddleware;
+
+impl ToolMiddleware for LoggingMiddleware {
+    fn pre_execute(&self, tool_name: &str, args: &Value) -> Result<(), ToolError> {
+        tracing::info!(tool_name = %tool_name, args = %args, "Executing tool");
+        Ok(())
+    }
+
+    fn post_execute(&self, tool_name: &str, _args: &Value, _result: &Value, elapsed: Duration) {
+        tracing::info!(
+            tool_name = %tool_name,
+            duration_ms = elapsed.as_millis() as u64,
+            "Tool executed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::definition::ToolExecutionFuture;
+    use serde_json::json;
+
+    #[derive(Debug)]
+    struct EchoTool;
+
+    impl ToolExecutorTrait for EchoTool {
+        fn execute(&self, args: Value) -> ToolExecutionFuture {
+            Box::pin(async move { Ok(args) })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Trace(&'static str, Arc<Mutex<Vec<String>>>);
+
+    impl ToolMiddleware for Trace {
+        fn pre_execute(&self, _tool_name: &str, _args: &Value) -> Result<(), ToolError> {
+            self.1.lock().unwrap().push(format!("pre {}", self.0));
+            Ok(())
+        }
+
+        fn post_execute(&self, _tool_name: &str, _args: &Value, _result: &Value, _: Duration) {
+            self.1.lock().unwrap().push(format!("post {}", self.0));
+        }
+    }
+
+    #[derive(Debug)]
+    struct Deny;
+
+    impl ToolMiddleware for Deny {
+        fn pre_execute(&self, tool_name: &str, _args: &Value) -> Result<(), ToolError> {
+            Err(ToolError::validation_failed(tool_name, "denied"))
+        }
+    }
+
+    #[tokio::test]
+    async fn stack_runs_pre_in_order_and_post_in_reverse() {
+        let trace = Arc::new(Mutex::new(Vec::new()));
+        let stack = ToolMiddlewareStack::new()
+            .layer(Trace("outer", Arc::clone(&trace)))
+            .layer(Trace("inner", Arc::clone(&trace)));
+
+        let result = execute_with_hooks(&EchoTool, &stack, "echo", json!(1), |args| {
+            EchoTool.execute(args)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result, json!(1));
+        assert_eq!(
+            *trace.lock().unwrap(),
+            ["pre outer", "pre inner", "post inner", "post outer"]
+        );
+    }
+
+    #[tokio::test]
+    async fn pre_execute_error_rejects_call() {
+        let timing = TimingMiddleware::new();
+        let stack = ToolMiddlewareStack::new().layer(Deny).layer(timing.clone());
+
+        let result = execute_with_hooks(&EchoTool, &stack, "echo", json!(1), |args| {
+            EchoTool.execute(args)
+        })
+        .await;
+
+        assert!(result.unwrap_err().to_string().contains("denied"));
+        assert!(timing.metrics("echo").is_none());
+    }
+
+    #[tokio::test]
+    async fn timing_middleware_records_per_tool() {
+        let timing = TimingMiddleware::new();
+        let stack = ToolMiddlewareStack::new().layer(timing.clone());
+
+        for _ in 0..3 {
+            execute_with_hooks(&EchoTool, &stack, "echo", json!({}), |args| {
+                EchoTool.execute(args)
+            })
+            .await
+            .unwrap();
+        }
+
+        let metrics = timing.metrics("echo").unwrap();
+        assert_eq!(metrics.calls, 3);
+        assert!(metrics.max_duration <= metrics.total_duration);
+        assert!(timing.metrics("other").is_none());
+        assert_eq!(timing.all_metrics().len(), 1);
+    }
+
+    #[test]
+    fn average_duration_of_no_calls_is_zero() {
+        assert_eq!(ToolMetrics::default().average_duration(), Duration::ZERO);
+    }
+}
@@ -5,6 +5,9 @@
 //! - **Tool Registry**: Central actor that manages tool registration and dispatch
 //! - **Tool Executor**: Supervised child actors for executing individual tools
 //! - **Tool Actors**: Per-agent tool actors for isolated tool execution
+//! - **Result Cache**: Opt-in caching of deterministic tool results
+//! - **Middleware**: Hooks for logging, metrics and checks around tool execution
+//! - **Replay**: Tool call recording and replay for reproducible debugging
 //! - **Sandbox**: Interface for sandboxed code execution (ProcessSandbox backend)
 //!
 //! ## Architecture
@@ -39,6 +42,7 @@
 //! |  UnregisterTool                                             |
 //! |  ExecuteTool --> Spawns ToolExecutor (Temporary)            |
 //! |  ListTools --> Returns Vec<ToolDefinition>                  |
+//! |  ListToolsWithMetadata --> Returns Vec<ToolMetadata>        |
 //! |                                                              |
 //! +-------------------------------------------------------------+
 //! ```
@@ -74,32 +78,61 @@
 //!         serde_json::json!({...}),
 //!     )),
 //!     executor: Arc::new(Box::new(CalculatorExecutor)),
+//!     owner_agent_id: None,
 //! }).await;
 //! ```
 
 pub mod actor;
 pub mod builtins;
+pub mod cache;
+pub mod coercion;
 pub mod definition;
 pub mod error;
 pub mod executor;
+pub mod middleware;
+pub mod rate_limit;
 pub mod registry;
+pub mod replay;
 pub mod sandbox;
+pub mod schema;
 pub mod security;
+pub mod testing;
+pub mod versioning;
 
 // Re-exports
 pub use crate::messages::ToolDefinition;
 pub use actor::{
     ExecuteToolDirect, ToolActor, ToolActorResponse, ToolExecutor as ToolExecutorAsync,
 };
+pub use cache::{
+    CacheKeyFn, ClearToolCache, EnableResultCache, ResultCacheConfig, ToolCacheStatus,
+    ToolResultCache,
+};
+pub use coercion::{CoercionRules, CoercionTarget, CoercionWarning, SetInputCoercion};
 pub use definition::{BoxedToolExecutor, ToolConfig, ToolExecutionFuture, ToolExecutorTrait};
-pub use error::{ToolError, ToolErrorKind};
+pub use error::{ToolError, ToolErrorKind, ToolResultExt};
 pub use executor::{Execute, InitExecutor, ToolExecutor};
+pub use middleware::{
+    LoggingMiddleware, TimingMiddleware, ToolMetrics, ToolMiddleware, ToolMiddlewareStack,
+};
+pub use rate_limit::{SetToolRateLimit, ToolRateLimit, ToolRateLimiter, ToolRatePermit};
 pub use registry::{
-    InitToolRegistry, ListTools, RegisterTool, RegisteredTool, RegistryMetrics, ToolListResponse,
-    ToolRegistry, UnregisterTool,
+    list_tools, list_tools_with_metadata, GetToolSchemaVersions, InitToolRegistry, ListTools,
+    ListToolsWithMetadata, RecordToolExecution, RegisterTool, RegisteredTool, RegistryMetrics,
+    ReplaceTool, ToolListResponse, ToolMetadata, ToolMetadataResponse, ToolRegistry,
+    ToolSchemaChanged, ToolSchemaVersions, ToolUsageStats, UnregisterAllForAgent, UnregisterTool,
+};
+pub use replay::{
+    CallLogResponse, EnableCallLog, GetCallLog, ToolCallLog, ToolCallRecord, ToolCallReplayer,
+};
+pub use sandbox::{
+    Sandbox, SandboxExecutionFuture, SandboxExecutionMetrics, SandboxFactory, SandboxFactoryFuture,
+    SandboxMetricsFuture,
 };
-pub use sandbox::{Sandbox, SandboxExecutionFuture, SandboxFactory, SandboxFactoryFuture};
-pub use security::{PathValidationError, PathValidator};
+pub use schema::SchemaViolation;
+pub use security::{PathValidationError, PathValidator, SecurityPolicy, SymlinkPolicy};
+pub use testing::{SchemaTestFailure, TestReport, ToolSchemaTestHarness};
+pub use versioning::{ToolSchemaChange, ToolSchemaRegistry, ToolSchemaVersion};
 
 // Stub implementation is only available in tests (security concern in production)
 #[cfg(test)]
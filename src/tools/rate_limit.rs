@@ -0,0 +1,309 @@
+//! Per-tool call rate limiting.
+//!
+//! A [`ToolRateLimiter`] combines two token buckets (per minute and per hour)
+//! with a concurrency cap. Calls that would exceed any of them are rejected
+//! with [`ToolError::rate_limited`](crate::tools::ToolError::rate_limited)
+//! rather than queued, so a runaway agent gets immediate feedback.
+//!
+//! Builtin tool actors start with [`ToolRateLimit::builtin_default`]; send
+//! [`SetToolRateLimit`] (see
+//! [`ToolActor::set_rate_limit`](crate::tools::ToolActor::set_rate_limit))
+//! to change it. Rejected calls are answered with a
+//! [`ToolActorResponse`](crate::tools::ToolActorResponse) flagged
+//! `rate_limited`.
+
+use acton_reactive::prelude::tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use acton_reactive::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Rate limit for a single tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolRateLimit {
+    /// Maximum calls in any rolling minute
+    pub max_calls_per_minute: u32,
+    /// Maximum calls in any rolling hour
+    pub max_calls_per_hour: u32,
+    /// Maximum calls executing at the same time
+    pub max_concurrent: usize,
+}
+
+impl ToolRateLimit {
+    /// Creates a rate limit.
+    #[must_use]
+    pub fn new(max_calls_per_minute: u32, max_calls_per_hour: u32, max_concurrent: usize) -> Self {
+        Self {
+            max_calls_per_minute,
+            max_calls_per_hour,
+            max_concurrent,
+        }
+    }
+
+    /// Creates a limit of `max` calls per minute with no hourly or
+    /// concurrency cap beyond what that implies.
+    #[must_use]
+    pub fn per_minute(max: u32) -> Self {
+        Self::new(max, max.saturating_mul(60), Semaphore::MAX_PERMITS)
+    }
+
+    /// Returns the limit applied to `tool_name` out of the box, if any.
+    ///
+    /// `bash` is limited to 60 calls per minute, and `web_fetch` and
+    /// `fetch_rss` to 30 each.
+    #[must_use]
+    pub fn builtin_default(tool_name: &str) -> Option<Self> {
+        match tool_name {
+            "bash" => Some(Self::per_minute(60)),
+            "web_fetch" | "fetch_rss" => Some(Self::per_minute(30)),
+            _ => None,
+        }
+    }
+}
+
+/// A token bucket refilled continuously over `period`.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    period: Duration,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32, period: Duration) -> Self {
+        Self {
+            capacity: f64::from(capacity),
+            tokens: f64::from(capacity),
+            period,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        let rate = self.capacity / self.period.as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Returns how long until one token is available.
+    fn wait_time(&self) -> Duration {
+        if self.tokens >= 1.0 {
+            return Duration::ZERO;
+        }
+        if self.capacity == 0.0 {
+            return self.period;
+        }
+        let rate = self.capacity / self.period.as_secs_f64();
+        Duration::from_secs_f64((1.0 - self.tokens) / rate)
+    }
+}
+
+/// Enforces a [`ToolRateLimit`] across every call to one tool.
+#[derive(Debug)]
+pub struct ToolRateLimiter {
+    limit: ToolRateLimit,
+    /// Per-minute and per-hour buckets
+    buckets: Mutex<[TokenBucket; 2]>,
+    concurrency: Arc<Semaphore>,
+}
+
+/// Held for the duration of a rate-limited call; releases the concurrency
+/// slot when dropped.
+#[derive(Debug)]
+pub struct ToolRatePermit {
+    _permit: OwnedSemaphorePermit,
+}
+
+impl ToolRateLimiter {
+    /// Creates a limiter with full buckets.
+    #[must_use]
+    pub fn new(limit: ToolRateLimit) -> Self {
+        Self {
+            limit,
+            buckets: Mutex::new([
+                TokenBucket::new(limit.max_calls_per_minute, Duration::from_secs(60)),
+                TokenBucket::new(limit.max_calls_per_hour, Duration::from_secs(3600)),
+            ]),
+            concurrency: Arc::new(Semaphore::new(
+                limit.max_concurrent.min(Semaphore::MAX_PERMITS),
+            )),
+        }
+    }
+
+    /// Returns the limit this limiter enforces.
+    #[must_use]
+    pub fn limit(&self) -> &ToolRateLimit {
+        &self.limit
+    }
+
+    /// Reserves one call.
+    ///
+    /// # Errors
+    ///
+    /// Returns the number of seconds to wait before retrying if the call
+    /// would exceed the limit.
+    pub fn try_acquire(&self) -> Result<ToolRatePermit, u32> {
+        let permit = Arc::clone(&self.concurrency)
+            .try_acquire_owned()
+            .map_err(|_| 1u32)?;
+
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let now = Instant::now();
+        for bucket in buckets.iter_mut() {
+            bucket.refill(now);
+        }
+
+        let wait = buckets
+            .iter()
+            .map(TokenBucket::wait_time)
+            .max()
+            .unwrap_or(Duration::ZERO);
+        if !wait.is_zero() {
+            let secs = wait.as_secs_f64().ceil().max(1.0);
+            return Err(secs.min(f64::from(u32::MAX)) as u32);
+        }
+
+        for bucket in buckets.iter_mut() {
+            bucket.tokens -= 1.0;
+        }
+        Ok(ToolRatePermit { _permit: permit })
+    }
+}
+
+/// Message to replace the rate limit of a tool actor (`None` removes it).
+#[acton_message]
+pub struct SetToolRateLimit {
+    /// The new limit
+    pub limit: Option<ToolRateLimit>,
+}
+
+/// Actor models that can hold a rate limiter.
+pub(crate) trait RateLimiting {
+    /// Returns the actor's rate limiter slot (`None` while unlimited).
+    fn rate_limiter_mut(&mut self) -> &mut Option<Arc<ToolRateLimiter>>;
+}
+
+/// Installs the builtin default limit for `tool_name` and registers the
+/// rate-limit message handler on a tool actor builder.
+pub(crate) fn configure_rate_limit_handlers<A>(builder: &mut ManagedActor<Idle, A>, tool_name: &str)
+where
+    A: RateLimiting + Default + Send + std::fmt::Debug + 'static,
+{
+    *builder.model.rate_limiter_mut() = ToolRateLimit::builtin_default(tool_name)
+        .map(|limit| Arc::new(ToolRateLimiter::new(limit)));
+
+    builder.mutate_on::<SetToolRateLimit>(|actor, envelope| {
+        *actor.model.rate_limiter_mut() = envelope
+            .message()
+            .limit
+            .map(|limit| Arc::new(ToolRateLimiter::new(limit)));
+        Reply::ready()
+    });
+}
+
+/// Replaces the rate limit of a running tool actor.
+pub async fn set_tool_rate_limit(tool: &ActorHandle, limit: Option<ToolRateLimit>) {
+    tool.send(SetToolRateLimit { limit }).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_after_minute_budget_spent() {
+        let limiter = ToolRateLimiter::new(ToolRateLimit::per_minute(2));
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().is_ok());
+
+        let retry_after = limiter.try_acquire().unwrap_err();
+        assert!((1..=30).contains(&retry_after), "retry_after={retry_after}");
+    }
+
+    #[test]
+    fn hourly_budget_applies_independently() {
+        let limiter = ToolRateLimiter::new(ToolRateLimit::new(10, 1, 4));
+        assert!(limiter.try_acquire().is_ok());
+        assert!(limiter.try_acquire().unwrap_err() > 60);
+    }
+
+    #[test]
+    fn concurrency_slot_released_on_drop() {
+        let limiter = ToolRateLimiter::new(ToolRateLimit::new(10, 100, 1));
+        let permit = limiter.try_acquire().unwrap();
+        assert_eq!(limiter.try_acquire().unwrap_err(), 1);
+
+        drop(permit);
+        assert!(limiter.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn zero_limit_always_rejects() {
+        let limiter = ToolRateLimiter::new(ToolRateLimit::new(0, 100, 1));
+        assert_eq!(limiter.try_acquire().unwrap_err(), 60);
+    }
+
+    #[test]
+    fn builtin_defaults_cover_bash_and_web_fetch() {
+        assert_eq!(
+            ToolRateLimit::builtin_default("bash")
+                .unwrap()
+                .max_calls_per_minute,
+            60
+        );
+        assert_eq!(
+            ToolRateLimit::builtin_default("web_fetch")
+                .unwrap()
+                .max_calls_per_minute,
+            30
+        );
+        assert!(ToolRateLimit::builtin_default("read_file").is_none());
+    }
+
+    #[tokio::test]
+    async fn tool_actor_rejects_calls_over_its_limit() {
+        use crate::tools::actor::{ExecuteToolDirect, ToolActor};
+        use crate::tools::builtins::CalculateToolActor;
+        use crate::types::CorrelationId;
+        use serde_json::json;
+
+        let mut app = ActonApp::launch_async().await;
+        let mut runtime = app.clone();
+        let handle = CalculateToolActor::spawn(&mut runtime).await;
+        CalculateToolActor::enable_call_log(&handle).await;
+        CalculateToolActor::set_rate_limit(&handle, Some(ToolRateLimit::per_minute(1))).await;
+
+        for call in ["tc_1", "tc_2"] {
+            handle
+                .send(ExecuteToolDirect::new(
+                    CorrelationId::new(),
+                    call,
+                    json!({"expression": "2 + 2"}),
+                ))
+                .await;
+        }
+
+        let mut records = Vec::new();
+        for _ in 0..50 {
+            records = CalculateToolActor::get_call_log(&mut runtime, &handle).await;
+            if records.len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(records.len(), 2);
+        let rejected: Vec<_> = records
+            .iter()
+            .filter_map(|record| record.result.as_ref().err())
+            .collect();
+        assert_eq!(rejected.len(), 1);
+        assert!(rejected[0].contains("rate limited"));
+
+        app.shutdown_all().await.unwrap();
+    }
+}
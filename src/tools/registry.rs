@@ -6,9 +6,14 @@
 use crate::messages::{ExecuteTool, ToolDefinition, ToolResponse};
 use crate::tools::definition::{BoxedToolExecutor, ToolConfig};
 use crate::tools::error::{ToolError, ToolErrorKind};
+use crate::tools::middleware::execute_with_hooks;
+use crate::tools::versioning::{ToolSchemaChange, ToolSchemaRegistry, ToolSchemaVersion};
+use crate::types::AgentId;
 use acton_reactive::prelude::*;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 
 /// Message to initialize the Tool Registry.
 #[acton_message]
@@ -21,6 +26,44 @@ pub struct RegisterTool {
     pub config: ToolConfig,
     /// The tool executor
     pub executor: Arc<BoxedToolExecutor>,
+    /// The agent that owns the tool, if any
+    ///
+    /// Owned tools are unregistered when their agent stops.
+    pub owner_agent_id: Option<AgentId>,
+}
+
+/// Message to register a tool, replacing any tool of the same name.
+///
+/// Unlike [`RegisterTool`], this does not fail for an existing name. If the
+/// replacement's input schema differs, [`ToolSchemaChanged`] is broadcast.
+#[acton_message]
+pub struct ReplaceTool {
+    /// The tool configuration
+    pub config: ToolConfig,
+    /// The tool executor
+    pub executor: Arc<BoxedToolExecutor>,
+}
+
+/// Broadcast when a registration changes a known tool's input schema.
+#[acton_message]
+pub struct ToolSchemaChanged {
+    /// The change
+    pub change: ToolSchemaChange,
+    /// The tool's version after the change
+    pub version: u32,
+}
+
+/// Message to request current tool schema versions and their changelog.
+#[acton_message]
+pub struct GetToolSchemaVersions;
+
+/// Response to [`GetToolSchemaVersions`].
+#[acton_message]
+pub struct ToolSchemaVersions {
+    /// The current version of every tool seen, sorted by name
+    pub versions: Vec<ToolSchemaVersion>,
+    /// Every schema change since startup
+    pub changelog: Vec<ToolSchemaChange>,
 }
 
 /// Message to unregister a tool from the registry.
@@ -30,6 +73,16 @@ pub struct UnregisterTool {
     pub tool_name: String,
 }
 
+/// Message to unregister every tool owned by an agent.
+///
+/// The kernel broadcasts this when an agent stops; the registry subscribes to
+/// it on spawn.
+#[acton_message]
+pub struct UnregisterAllForAgent {
+    /// The agent whose tools to unregister
+    pub agent_id: AgentId,
+}
+
 /// Message to list all registered tools.
 #[acton_message]
 pub struct ListTools;
@@ -41,6 +94,49 @@ pub struct ToolListResponse {
     pub tools: Vec<ToolDefinition>,
 }
 
+/// Message to list all registered tools with their configuration and usage.
+#[acton_message]
+pub struct ListToolsWithMetadata;
+
+/// Response to [`ListToolsWithMetadata`].
+#[acton_message]
+pub struct ToolMetadataResponse {
+    /// Metadata of every registered tool, sorted by name
+    pub tools: Vec<ToolMetadata>,
+}
+
+/// Records the outcome of one tool execution in the registry's metrics.
+///
+/// The registry sends this to itself after every [`ExecuteTool`].
+#[acton_message]
+pub struct RecordToolExecution {
+    /// The tool that was executed
+    pub tool_name: String,
+    /// How long validation and execution took
+    pub duration: Duration,
+    /// Whether the execution failed
+    pub error: bool,
+}
+
+/// A registered tool's configuration and usage statistics.
+#[derive(Debug, Clone)]
+pub struct ToolMetadata {
+    /// The tool definition
+    pub definition: ToolDefinition,
+    /// Whether the tool requires sandbox execution
+    pub sandboxed: bool,
+    /// Configured execution timeout
+    pub timeout: Duration,
+    /// Executions through the registry
+    pub execution_count: u64,
+    /// Executions that failed
+    pub error_count: u64,
+    /// Mean execution time in milliseconds (0 before the first execution)
+    pub avg_execution_ms: f64,
+    /// When the tool was last executed
+    pub last_used: Option<Instant>,
+}
+
 /// The Tool Registry actor state.
 ///
 /// Manages tool registration, validation, and execution dispatch.
@@ -52,6 +148,10 @@ pub struct ToolRegistry {
     pub shutting_down: bool,
     /// Metrics
     pub metrics: RegistryMetrics,
+    /// Schema versions of every tool seen since startup
+    pub schemas: ToolSchemaRegistry,
+    /// Names of the tools owned by each agent
+    pub tools_by_agent: HashMap<AgentId, Vec<String>>,
 }
 
 /// A registered tool entry.
@@ -61,6 +161,8 @@ pub struct RegisteredTool {
     pub config: ToolConfig,
     /// The tool executor (wrapped in Arc for cloning)
     pub executor: Arc<BoxedToolExecutor>,
+    /// The agent that registered the tool, if any
+    pub owner_agent_id: Option<AgentId>,
 }
 
 /// Metrics for the Tool Registry.
@@ -76,6 +178,59 @@ pub struct RegistryMetrics {
     pub executions_succeeded: u64,
     /// Total executions failed
     pub executions_failed: u64,
+    /// Execution statistics by tool name
+    pub tool_stats: HashMap<String, ToolUsageStats>,
+    /// Tools still registered when their owning agent stopped, removed by
+    /// [`UnregisterAllForAgent`]
+    pub orphaned_tools: usize,
+}
+
+impl RegistryMetrics {
+    /// Records one execution of `tool_name`.
+    pub fn record_execution(&mut self, tool_name: &str, duration: Duration, error: bool) {
+        if !error {
+            self.executions_succeeded += 1;
+        }
+        self.tool_stats
+            .entry(tool_name.to_string())
+            .or_default()
+            .record(duration, error);
+    }
+}
+
+/// Execution statistics of one tool.
+#[derive(Debug, Clone, Default)]
+pub struct ToolUsageStats {
+    /// Executions recorded
+    pub execution_count: u64,
+    /// Executions that failed
+    pub error_count: u64,
+    /// Summed duration of all executions
+    pub total_execution_time: Duration,
+    /// When the tool was last executed
+    pub last_used: Option<Instant>,
+}
+
+impl ToolUsageStats {
+    /// Records one execution.
+    pub fn record(&mut self, duration: Duration, error: bool) {
+        self.execution_count += 1;
+        if error {
+            self.error_count += 1;
+        }
+        self.total_execution_time += duration;
+        self.last_used = Some(Instant::now());
+    }
+
+    /// Returns the mean execution time in milliseconds, or 0 before the
+    /// first execution.
+    #[must_use]
+    pub fn avg_execution_ms(&self) -> f64 {
+        if self.execution_count == 0 {
+            return 0.0;
+        }
+        self.total_execution_time.as_secs_f64() * 1000.0 / self.execution_count as f64
+    }
 }
 
 impl ToolRegistry {
@@ -116,7 +271,12 @@ impl ToolRegistry {
         // Configure message handlers
         configure_handlers(&mut builder);
 
-        builder.start().await
+        let handle = builder.start().await;
+
+        // The kernel announces stopped agents so their tools can be removed
+        handle.subscribe::<UnregisterAllForAgent>().await;
+
+        handle
     }
 
     /// Returns the number of registered tools.
@@ -130,6 +290,130 @@ impl ToolRegistry {
     pub fn has_tool(&self, name: &str) -> bool {
         self.tools.contains_key(name)
     }
+
+    /// Returns the configuration and usage statistics of every registered
+    /// tool, sorted by name.
+    #[must_use]
+    pub fn list_with_metadata(&self) -> Vec<ToolMetadata> {
+        let mut tools: Vec<ToolMetadata> = self
+            .tools
+            .iter()
+            .map(|(name, tool)| {
+                let stats = self
+                    .metrics
+                    .tool_stats
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_default();
+                ToolMetadata {
+                    definition: tool.config.definition.clone(),
+                    sandboxed: tool.config.sandboxed,
+                    timeout: tool.config.timeout,
+                    execution_count: stats.execution_count,
+                    error_count: stats.error_count,
+                    avg_execution_ms: stats.avg_execution_ms(),
+                    last_used: stats.last_used,
+                }
+            })
+            .collect();
+        tools.sort_by(|a, b| a.definition.name.cmp(&b.definition.name));
+        tools
+    }
+
+    /// Unregisters every tool owned by `agent_id`.
+    ///
+    /// Returns the names of the removed tools, sorted.
+    pub fn unregister_all_for_agent(&mut self, agent_id: &AgentId) -> Vec<String> {
+        let mut removed: Vec<String> = self
+            .tools_by_agent
+            .remove(agent_id)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|name| self.tools.remove(name).is_some())
+            .collect();
+        removed.sort();
+        self.metrics.tools_unregistered += removed.len() as u64;
+        self.metrics.orphaned_tools += removed.len();
+        removed
+    }
+
+    /// Removes a tool and drops it from its owner's index.
+    fn remove_tool(&mut self, tool_name: &str) -> Option<RegisteredTool> {
+        let tool = self.tools.remove(tool_name)?;
+        if let Some(owner) = &tool.owner_agent_id {
+            self.forget_owner(owner, tool_name);
+        }
+        Some(tool)
+    }
+
+    /// Drops `tool_name` from the tools indexed under `owner`.
+    fn forget_owner(&mut self, owner: &AgentId, tool_name: &str) {
+        if let Some(names) = self.tools_by_agent.get_mut(owner) {
+            names.retain(|name| name != tool_name);
+            if names.is_empty() {
+                self.tools_by_agent.remove(owner);
+            }
+        }
+    }
+
+    /// Returns every tool schema change since startup.
+    #[must_use]
+    pub fn schema_changelog(&self) -> Vec<ToolSchemaChange> {
+        self.schemas.changelog().to_vec()
+    }
+
+    /// Stores a tool and records its schema version.
+    ///
+    /// Returns the schema change and new version if the schema differs from
+    /// the last one seen under this name.
+    fn insert_tool(
+        &mut self,
+        config: &ToolConfig,
+        executor: &Arc<BoxedToolExecutor>,
+        owner_agent_id: Option<&AgentId>,
+    ) -> Option<(ToolSchemaChange, u32)> {
+        let tool_name = config.definition.name.clone();
+        if let Some(owner) = owner_agent_id {
+            self.tools_by_agent
+                .entry(owner.clone())
+                .or_default()
+                .push(tool_name.clone());
+        }
+        let previous = self.tools.insert(
+            tool_name.clone(),
+            RegisteredTool {
+                config: config.clone(),
+                executor: executor.clone(),
+                owner_agent_id: owner_agent_id.cloned(),
+            },
+        );
+        if let Some(previous_owner) = previous.and_then(|tool| tool.owner_agent_id) {
+            if owner_agent_id != Some(&previous_owner) {
+                self.forget_owner(&previous_owner, &tool_name);
+            }
+        }
+        self.metrics.tools_registered += 1;
+
+        let change = self.schemas.record(&config.definition)?;
+        let version = self.schemas.get(&tool_name).map_or(1, |v| v.version);
+        tracing::warn!(
+            tool_name = %tool_name,
+            old_hash = %change.old_hash,
+            new_hash = %change.new_hash,
+            version,
+            "Tool schema changed"
+        );
+        Some((change, version))
+    }
+}
+
+/// Broadcasts a schema change, if there was one.
+async fn announce_schema_change(broker: ActorHandle, change: Option<(ToolSchemaChange, u32)>) {
+    if let Some((change, version)) = change {
+        broker
+            .broadcast(ToolSchemaChanged { change, version })
+            .await;
+    }
 }
 
 /// Configures message handlers for the Tool Registry actor.
@@ -156,14 +440,10 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
             }
 
             // Register the tool
-            actor.model.tools.insert(
-                tool_name.clone(),
-                RegisteredTool {
-                    config: msg.config.clone(),
-                    executor: msg.executor.clone(),
-                },
-            );
-            actor.model.metrics.tools_registered += 1;
+            let change =
+                actor
+                    .model
+                    .insert_tool(&msg.config, &msg.executor, msg.owner_agent_id.as_ref());
 
             tracing::info!(
                 tool_name = %tool_name,
@@ -171,7 +451,11 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
                 "Tool registered"
             );
 
-            Reply::try_ok(())
+            let broker = actor.broker().clone();
+            Reply::try_pending(async move {
+                announce_schema_change(broker, change).await;
+                Ok(())
+            })
         })
         .on_error::<RegisterTool, ToolError>(|_actor, envelope, error| {
             let tool_name = &envelope.message().config.definition.name;
@@ -183,6 +467,26 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
             Box::pin(async {})
         });
 
+    // Handle tool replacement
+    builder.mutate_on::<ReplaceTool>(|actor, envelope| {
+        if actor.model.shutting_down {
+            tracing::warn!("Rejecting ReplaceTool - registry is shutting down");
+            return Reply::ready();
+        }
+
+        let msg = envelope.message();
+        let replaced = actor.model.has_tool(&msg.config.definition.name);
+        let change = actor.model.insert_tool(&msg.config, &msg.executor, None);
+
+        tracing::info!(
+            tool_name = %msg.config.definition.name,
+            replaced,
+            "Tool registered"
+        );
+
+        Reply::pending(announce_schema_change(actor.broker().clone(), change))
+    });
+
     // Handle tool unregistration
     builder
         .try_mutate_on::<UnregisterTool, (), ToolError>(|actor, envelope| {
@@ -192,7 +496,7 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
 
             let tool_name = &envelope.message().tool_name;
 
-            if actor.model.tools.remove(tool_name).is_some() {
+            if actor.model.remove_tool(tool_name).is_some() {
                 actor.model.metrics.tools_unregistered += 1;
                 tracing::info!(tool_name = %tool_name, "Tool unregistered");
                 Reply::try_ok(())
@@ -210,6 +514,20 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
             Box::pin(async {})
         });
 
+    // Handle bulk unregistration when an agent stops
+    builder.mutate_on::<UnregisterAllForAgent>(|actor, envelope| {
+        let agent_id = &envelope.message().agent_id;
+        let removed = actor.model.unregister_all_for_agent(agent_id);
+        if !removed.is_empty() {
+            tracing::info!(
+                agent_id = %agent_id,
+                tools = ?removed,
+                "Unregistered tools of stopped agent"
+            );
+        }
+        Reply::ready()
+    });
+
     // Handle tool execution with fallible handler
     builder
         .try_mutate_on::<ExecuteTool, (), ToolError>(|actor, envelope| {
@@ -220,7 +538,6 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
             let msg = envelope.message();
             let correlation_id = msg.correlation_id.clone();
             let tool_name = msg.tool_call.name.clone();
-            let args = msg.tool_call.arguments.clone();
             let tool_call_id = msg.tool_call.id.clone();
 
             actor.model.metrics.executions_requested += 1;
@@ -235,8 +552,13 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
                 ));
             };
 
+            let args = registered
+                .config
+                .coerce_args(msg.tool_call.arguments.clone());
             let executor = registered.executor.clone();
+            let middleware = registered.config.middleware.clone();
             let broker = actor.broker().clone();
+            let registry = actor.handle().clone();
 
             // Execute the tool. The ToolRegistry always runs tools inline; the
             // `ToolConfig::sandboxed` flag is advisory metadata and is honored by
@@ -244,8 +566,16 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
             // sandboxed builtins through a configured `SandboxFactory` before
             // reaching any registry.
             Reply::try_pending(async move {
+                let started = Instant::now();
+                let record = |error: bool| RecordToolExecution {
+                    tool_name: tool_name.clone(),
+                    duration: started.elapsed(),
+                    error,
+                };
+
                 // Validate arguments
                 if let Err(e) = executor.validate_args(&args) {
+                    registry.send(record(true)).await;
                     broker
                         .broadcast(ToolResponse {
                             correlation_id: correlation_id.clone(),
@@ -256,7 +586,14 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
                     return Err(e);
                 }
 
-                match executor.execute(args).await {
+                let result =
+                    execute_with_hooks(&**executor, &middleware, &tool_name, args, |args| {
+                        executor.execute(args)
+                    })
+                    .await;
+                registry.send(record(result.is_err())).await;
+
+                match result {
                     Ok(result) => {
                         let result_str = serde_json::to_string(&result)
                             .unwrap_or_else(|e| format!("{{\"error\": \"{}\"}}", e));
@@ -316,6 +653,115 @@ fn configure_handlers(builder: &mut ManagedActor<Idle, ToolRegistry>) {
             reply.send(ToolListResponse { tools }).await;
         })
     });
+
+    builder.mutate_on::<RecordToolExecution>(|actor, envelope| {
+        let msg = envelope.message();
+        actor
+            .model
+            .metrics
+            .record_execution(&msg.tool_name, msg.duration, msg.error);
+        Reply::ready()
+    });
+
+    builder.act_on::<ListToolsWithMetadata>(|actor, envelope| {
+        let tools = actor.model.list_with_metadata();
+        let reply = envelope.reply_envelope();
+
+        Reply::pending(async move {
+            reply.send(ToolMetadataResponse { tools }).await;
+        })
+    });
+
+    builder.act_on::<GetToolSchemaVersions>(|actor, envelope| {
+        let response = ToolSchemaVersions {
+            versions: actor.model.schemas.versions(),
+            changelog: actor.model.schema_changelog(),
+        };
+        let reply = envelope.reply_envelope();
+
+        Reply::pending(async move {
+            reply.send(response).await;
+        })
+    });
+}
+
+/// Temporary actor that receives a single reply from the registry.
+#[acton_actor]
+struct RegistryReplyReceiver;
+
+/// Fetches the current tool schema versions from a running registry.
+///
+/// Spawns a short-lived receiver actor on `runtime` to collect the
+/// [`ToolSchemaVersions`], then stops it.
+pub async fn get_tool_schema_versions(
+    runtime: &mut ActorRuntime,
+    registry: &ActorHandle,
+) -> ToolSchemaVersions {
+    query_registry(runtime, registry, GetToolSchemaVersions)
+        .await
+        .unwrap_or(ToolSchemaVersions {
+            versions: Vec::new(),
+            changelog: Vec::new(),
+        })
+}
+
+/// Fetches the definition of every tool in a running registry.
+///
+/// Spawns a short-lived receiver actor on `runtime` to collect the
+/// [`ToolListResponse`], then stops it.
+pub async fn list_tools(runtime: &mut ActorRuntime, registry: &ActorHandle) -> Vec<ToolDefinition> {
+    query_registry(runtime, registry, ListTools)
+        .await
+        .map(|response: ToolListResponse| response.tools)
+        .unwrap_or_default()
+}
+
+/// Fetches the metadata of every tool in a running registry.
+///
+/// Spawns a short-lived receiver actor on `runtime` to collect the
+/// [`ToolMetadataResponse`], then stops it.
+pub async fn list_tools_with_metadata(
+    runtime: &mut ActorRuntime,
+    registry: &ActorHandle,
+) -> Vec<ToolMetadata> {
+    query_registry(runtime, registry, ListToolsWithMetadata)
+        .await
+        .map(|response: ToolMetadataResponse| response.tools)
+        .unwrap_or_default()
+}
+
+/// Sends `request` to `registry` and waits for its `R` reply.
+async fn query_registry<R>(
+    runtime: &mut ActorRuntime,
+    registry: &ActorHandle,
+    request: impl ActonMessage,
+) -> Option<R>
+where
+    R: ActonMessage + Clone + Send + Sync + 'static,
+{
+    let slot: Arc<Mutex<Option<R>>> = Arc::new(Mutex::new(None));
+    let received = Arc::new(Notify::new());
+
+    let mut receiver = runtime.new_actor::<RegistryReplyReceiver>();
+    let slot_for_handler = slot.clone();
+    let received_signal = received.clone();
+    receiver.mutate_on::<R>(move |_actor, envelope| {
+        if let Ok(mut slot) = slot_for_handler.lock() {
+            *slot = Some(envelope.message().clone());
+        }
+        received_signal.notify_one();
+        Reply::ready()
+    });
+    let receiver = receiver.start().await;
+
+    receiver
+        .create_envelope(Some(registry.reply_address()))
+        .send(request)
+        .await;
+    received.notified().await;
+    let _ = receiver.stop().await;
+
+    slot.lock().ok().and_then(|mut guard| guard.take())
 }
 
 #[cfg(test)]
@@ -331,6 +777,55 @@ mod tests {
         assert_eq!(metrics.executions_requested, 0);
         assert_eq!(metrics.executions_succeeded, 0);
         assert_eq!(metrics.executions_failed, 0);
+        assert!(metrics.tool_stats.is_empty());
+        assert_eq!(metrics.orphaned_tools, 0);
+    }
+
+    #[test]
+    fn record_execution_tracks_per_tool_stats() {
+        let mut metrics = RegistryMetrics::default();
+        metrics.record_execution("echo", Duration::from_millis(10), false);
+        metrics.record_execution("echo", Duration::from_millis(30), true);
+
+        assert_eq!(metrics.executions_succeeded, 1);
+        let stats = &metrics.tool_stats["echo"];
+        assert_eq!(stats.execution_count, 2);
+        assert_eq!(stats.error_count, 1);
+        assert!((stats.avg_execution_ms() - 20.0).abs() < f64::EPSILON);
+        assert!(stats.last_used.is_some());
+        assert_eq!(ToolUsageStats::default().avg_execution_ms(), 0.0);
+    }
+
+    #[derive(Debug)]
+    struct NoopTool;
+
+    impl crate::tools::ToolExecutorTrait for NoopTool {
+        fn execute(&self, _args: serde_json::Value) -> crate::tools::ToolExecutionFuture {
+            Box::pin(async { Ok(serde_json::Value::Null) })
+        }
+    }
+
+    #[test]
+    fn unregister_all_for_agent_removes_owned_tools() {
+        let owner = AgentId::new();
+        let executor: Arc<BoxedToolExecutor> = Arc::new(Box::new(NoopTool));
+        let mut registry = ToolRegistry::default();
+        for (name, owner) in [("a", Some(&owner)), ("b", Some(&owner)), ("c", None)] {
+            let config = ToolConfig::new(ToolDefinition {
+                name: name.to_string(),
+                description: String::new(),
+                input_schema: serde_json::json!({"type": "object"}),
+            });
+            registry.insert_tool(&config, &executor, owner);
+        }
+        registry.remove_tool("b");
+
+        assert_eq!(registry.unregister_all_for_agent(&owner), vec!["a"]);
+        assert!(registry.has_tool("c"));
+        assert_eq!(registry.tool_count(), 1);
+        assert_eq!(registry.metrics.orphaned_tools, 1);
+        assert!(registry.tools_by_agent.is_empty());
+        assert!(registry.unregister_all_for_agent(&owner).is_empty());
     }
 
     #[test]
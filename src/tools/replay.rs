@@ -0,0 +1,671 @@
+//! Tool call recording and replay for reproducible agent debugging.
+//!
+//! When an agent produces an unexpected result, replaying the exact tool
+//! calls it made is often the fastest way to reproduce the bug. This module
+//! provides:
+//!
+//! - [`ToolCallRecord`]: a single recorded invocation (name, args, result,
+//!   timing)
+//! - [`ToolCallLog`]: an ordered collection of records that can be saved to
+//!   and loaded from JSON files
+//! - [`ToolCallReplayer`]: a [`ToolExecutorTrait`] that answers matching
+//!   calls from a log and falls through to a real executor otherwise
+//! - [`EnableCallLog`] / [`GetCallLog`]: messages understood by every
+//!   built-in tool actor for capturing logs from a running agent
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use acton_ai::prelude::*;
+//! use acton_ai::tools::replay::ToolCallLog;
+//!
+//! // Record a session once...
+//! let log: ToolCallLog = records.into();
+//! log.save_to_file("fixtures/weather.json")?;
+//!
+//! // ...then replay it deterministically in tests.
+//! let response = runtime
+//!     .prompt("What's the weather in Paris?")
+//!     .tool("get_weather", "Gets weather", schema, |args| async move {
+//!         unreachable!("answered from the recorded log")
+//!     })
+//!     .replay_tools(ToolCallLog::load_from_file("fixtures/weather.json")?)
+//!     .collect()
+//!     .await?;
+//! ```
+
+use crate::tools::definition::BoxedToolExecutor;
+use crate::tools::error::ToolError;
+use crate::tools::middleware::{execute_with_hooks, ToolMiddlewareStack};
+use crate::tools::rate_limit::ToolRateLimiter;
+use crate::tools::{ToolExecutionFuture, ToolExecutorTrait};
+use acton_reactive::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// A single recorded tool invocation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallRecord {
+    /// Name of the tool that was called
+    pub tool_name: String,
+    /// Arguments passed to the tool
+    pub args: Value,
+    /// The tool's result (success value or error message)
+    pub result: Result<Value, String>,
+    /// When the call started (RFC 3339)
+    pub timestamp: String,
+    /// How long the call took, in milliseconds
+    pub duration_ms: u64,
+}
+
+impl ToolCallRecord {
+    /// Creates a record timestamped now with zero duration.
+    #[must_use]
+    pub fn new(tool_name: impl Into<String>, args: Value, result: Result<Value, String>) -> Self {
+        Self {
+            tool_name: tool_name.into(),
+            args,
+            result,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            duration_ms: 0,
+        }
+    }
+
+    /// Sets the recorded call duration.
+    #[must_use]
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        self
+    }
+
+    /// Sets the recorded timestamp.
+    #[must_use]
+    pub fn with_timestamp(mut self, timestamp: impl Into<String>) -> Self {
+        self.timestamp = timestamp.into();
+        self
+    }
+
+    /// Returns true if this record matches a call to `tool_name` with `args`.
+    #[must_use]
+    pub fn matches(&self, tool_name: &str, args: &Value) -> bool {
+        self.tool_name == tool_name && args_hash(&self.args) == args_hash(args)
+    }
+}
+
+/// An ordered log of tool calls.
+///
+/// Serializes to JSON so recorded sessions can be checked in as test
+/// fixtures and replayed with [`ToolCallReplayer`] or
+/// [`PromptBuilder::replay_tools`](crate::prompt::PromptBuilder::replay_tools).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallLog {
+    /// Recorded calls in execution order
+    records: Vec<ToolCallRecord>,
+}
+
+impl ToolCallLog {
+    /// Creates an empty log.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a record to the log.
+    pub fn push(&mut self, record: ToolCallRecord) {
+        self.records.push(record);
+    }
+
+    /// Returns the recorded calls in execution order.
+    #[must_use]
+    pub fn records(&self) -> &[ToolCallRecord] {
+        &self.records
+    }
+
+    /// Consumes the log, returning its records.
+    #[must_use]
+    pub fn into_records(self) -> Vec<ToolCallRecord> {
+        self.records
+    }
+
+    /// Returns the number of recorded calls.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns true if no calls have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns true if the log contains at least one call to `tool_name`.
+    #[must_use]
+    pub fn contains_tool(&self, tool_name: &str) -> bool {
+        self.records.iter().any(|r| r.tool_name == tool_name)
+    }
+
+    /// Writes the log to `path` as pretty-printed JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the log cannot be serialized or the file cannot
+    /// be written.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), ToolError> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| ToolError::internal(format!("failed to serialize call log: {e}")))?;
+        std::fs::write(path, json).map_err(|e| {
+            ToolError::internal(format!(
+                "failed to write call log to '{}': {e}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Reads a log previously written by [`save_to_file`](Self::save_to_file).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or does not contain a
+    /// valid call log.
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self, ToolError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            ToolError::internal(format!(
+                "failed to read call log from '{}': {e}",
+                path.display()
+            ))
+        })?;
+        serde_json::from_str(&json).map_err(|e| {
+            ToolError::internal(format!(
+                "failed to parse call log '{}': {e}",
+                path.display()
+            ))
+        })
+    }
+}
+
+impl From<Vec<ToolCallRecord>> for ToolCallLog {
+    fn from(records: Vec<ToolCallRecord>) -> Self {
+        Self { records }
+    }
+}
+
+impl Extend<ToolCallRecord> for ToolCallLog {
+    fn extend<I: IntoIterator<Item = ToolCallRecord>>(&mut self, iter: I) {
+        self.records.extend(iter);
+    }
+}
+
+/// A tool executor that answers calls from recorded results.
+///
+/// Calls are matched on tool name plus a hash of the arguments (object key
+/// order is ignored). Identical calls replay their recorded results in
+/// order; once exhausted, the last recorded result is reused. Calls with no
+/// matching record fall through to the fallback executor, or fail with a
+/// not-found error if none is set.
+#[derive(Debug)]
+pub struct ToolCallReplayer {
+    /// The tool this replayer answers for
+    tool_name: String,
+    /// Recorded results keyed by argument hash
+    recorded: Mutex<HashMap<u64, VecDeque<Result<Value, String>>>>,
+    /// Executor used when no recorded call matches
+    fallback: Option<Arc<BoxedToolExecutor>>,
+}
+
+impl ToolCallReplayer {
+    /// Creates a replayer for `tool_name` from the given records.
+    ///
+    /// Records for other tools are ignored, so a whole session's log can be
+    /// passed in directly.
+    #[must_use]
+    pub fn new(
+        tool_name: impl Into<String>,
+        records: impl IntoIterator<Item = ToolCallRecord>,
+    ) -> Self {
+        let tool_name = tool_name.into();
+        let mut recorded: HashMap<u64, VecDeque<Result<Value, String>>> = HashMap::new();
+        for record in records {
+            if record.tool_name == tool_name {
+                recorded
+                    .entry(args_hash(&record.args))
+                    .or_default()
+                    .push_back(record.result);
+            }
+        }
+        Self {
+            tool_name,
+            recorded: Mutex::new(recorded),
+            fallback: None,
+        }
+    }
+
+    /// Sets the executor used for calls that have no recorded result.
+    #[must_use]
+    pub fn with_fallback(mut self, executor: Arc<BoxedToolExecutor>) -> Self {
+        self.fallback = Some(executor);
+        self
+    }
+
+    /// Returns the tool name this replayer answers for.
+    #[must_use]
+    pub fn tool_name(&self) -> &str {
+        &self.tool_name
+    }
+
+    /// Returns the recorded result for `args`, if one exists.
+    ///
+    /// Recorded errors are surfaced as [`ToolError::execution_failed`].
+    #[must_use]
+    pub fn replay(&self, args: &Value) -> Option<Result<Value, ToolError>> {
+        let mut recorded = self.recorded.lock().ok()?;
+        let queue = recorded.get_mut(&args_hash(args))?;
+        let result = if queue.len() > 1 {
+            queue.pop_front()?
+        } else {
+            queue.front()?.clone()
+        };
+        Some(result.map_err(|e| ToolError::execution_failed(&self.tool_name, e)))
+    }
+}
+
+impl ToolExecutorTrait for ToolCallReplayer {
+    fn execute(&self, args: Value) -> ToolExecutionFuture {
+        if let Some(result) = self.replay(&args) {
+            tracing::debug!(tool_name = %self.tool_name, "Replaying recorded tool call");
+            return Box::pin(async move { result });
+        }
+
+        match self.fallback.clone() {
+            Some(executor) => Box::pin(async move { executor.execute(args).await }),
+            None => {
+                let tool_name = self.tool_name.clone();
+                Box::pin(async move { Err(ToolError::not_found(tool_name)) })
+            }
+        }
+    }
+}
+
+/// Hashes a JSON value so that object key order does not affect the result.
+fn args_hash(value: &Value) -> u64 {
+    fn feed(value: &Value, state: &mut DefaultHasher) {
+        match value {
+            Value::Null => 0u8.hash(state),
+            Value::Bool(b) => (1u8, b).hash(state),
+            Value::Number(n) => (2u8, n.to_string()).hash(state),
+            Value::String(s) => (3u8, s).hash(state),
+            Value::Array(items) => {
+                (4u8, items.len()).hash(state);
+                for item in items {
+                    feed(item, state);
+                }
+            }
+            Value::Object(map) => {
+                (5u8, map.len()).hash(state);
+                let mut keys: Vec<&String> = map.keys().collect();
+                keys.sort();
+                for key in keys {
+                    key.hash(state);
+                    feed(&map[key], state);
+                }
+            }
+        }
+    }
+
+    let mut state = DefaultHasher::new();
+    feed(value, &mut state);
+    state.finish()
+}
+
+/// Message to start recording calls on a tool actor.
+///
+/// Recording is off by default. Sending this to an actor that is already
+/// recording leaves its existing log intact.
+#[acton_message]
+pub struct EnableCallLog;
+
+/// Request for a tool actor's recorded calls.
+///
+/// The actor replies with [`CallLogResponse`].
+#[acton_message]
+pub struct GetCallLog;
+
+/// Reply to [`GetCallLog`].
+#[acton_message]
+pub struct CallLogResponse {
+    /// Recorded calls in execution order (empty if recording is disabled)
+    pub records: Vec<ToolCallRecord>,
+}
+
+/// Internal message a tool actor sends itself after each recorded call.
+#[acton_message]
+pub(crate) struct RecordToolCall {
+    record: ToolCallRecord,
+}
+
+/// Actor models that can hold a call log.
+pub(crate) trait CallLogging {
+    /// Returns the actor's call log slot (`None` while recording is off).
+    fn call_log_mut(&mut self) -> &mut Option<ToolCallLog>;
+}
+
+/// Registers the call-log message handlers on a tool actor builder.
+pub(crate) fn configure_call_log_handlers<A>(builder: &mut ManagedActor<Idle, A>)
+where
+    A: CallLogging + Default + Send + std::fmt::Debug + 'static,
+{
+    builder.mutate_on::<EnableCallLog>(|actor, _envelope| {
+        actor
+            .model
+            .call_log_mut()
+            .get_or_insert_with(ToolCallLog::new);
+        Reply::ready()
+    });
+
+    builder.mutate_on::<RecordToolCall>(|actor, envelope| {
+        if let Some(log) = actor.model.call_log_mut() {
+            log.push(envelope.message().record.clone());
+        }
+        Reply::ready()
+    });
+
+    builder.mutate_on::<GetCallLog>(|actor, envelope| {
+        let reply = envelope.reply_envelope();
+        let records = actor
+            .model
+            .call_log_mut()
+            .as_ref()
+            .map(|log| log.records().to_vec())
+            .unwrap_or_default();
+
+        Reply::pending(async move {
+            reply.send(CallLogResponse { records }).await;
+        })
+    });
+}
+
+/// Executes a tool, recording the call on `recorder` when one is given.
+///
+/// The tool's [`pre_execute`](ToolExecutorTrait::pre_execute) and
+/// [`post_execute`](ToolExecutorTrait::post_execute) hooks run around the
+/// call.
+///
+/// Tool actors pass their own handle as `recorder` while call logging is
+/// enabled, so the record is appended through [`RecordToolCall`] once the
+/// call completes. Calls rejected by `limiter` are recorded with their
+/// [`ToolError::rate_limited`] error.
+pub(crate) async fn execute_recorded<T>(
+    tool: &T,
+    tool_name: &str,
+    args: Value,
+    recorder: Option<ActorHandle>,
+    limiter: Option<&ToolRateLimiter>,
+) -> Result<Value, ToolError>
+where
+    T: ToolExecutorTrait + ?Sized,
+{
+    let Some(recorder) = recorder else {
+        return execute_limited(tool, tool_name, args, limiter).await;
+    };
+
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    let started = Instant::now();
+    let result = execute_limited(tool, tool_name, args.clone(), limiter).await;
+
+    let record = ToolCallRecord::new(tool_name, args, result.clone().map_err(|e| e.to_string()))
+        .with_timestamp(timestamp)
+        .with_duration(started.elapsed());
+    recorder.send(RecordToolCall { record }).await;
+
+    result
+}
+
+/// Executes a tool through its hooks, or rejects the call with
+/// [`ToolError::rate_limited`] when `limiter` has no room for it.
+async fn execute_limited<T>(
+    tool: &T,
+    tool_name: &str,
+    args: Value,
+    limiter: Option<&ToolRateLimiter>,
+) -> Result<Value, ToolError>
+where
+    T: ToolExecutorTrait + ?Sized,
+{
+    let _permit = match limiter.map(ToolRateLimiter::try_acquire) {
+        Some(Err(retry_after_secs)) => {
+            return Err(ToolError::rate_limited(tool_name, retry_after_secs))
+        }
+        Some(Ok(permit)) => Some(permit),
+        None => None,
+    };
+
+    let middleware = ToolMiddlewareStack::new();
+    execute_with_hooks(tool, &middleware, tool_name, args, |args| {
+        tool.execute(args)
+    })
+    .await
+}
+
+/// Temporary actor that receives a single [`CallLogResponse`].
+#[acton_actor]
+struct CallLogReceiver;
+
+/// Enables call logging on a running tool actor.
+pub async fn enable_call_log(tool: &ActorHandle) {
+    tool.send(EnableCallLog).await;
+}
+
+/// Fetches the recorded calls from a running tool actor.
+///
+/// Spawns a short-lived receiver actor on `runtime` to collect the
+/// [`CallLogResponse`], then stops it.
+pub async fn get_call_log(runtime: &mut ActorRuntime, tool: &ActorHandle) -> Vec<ToolCallRecord> {
+    let slot: Arc<Mutex<Option<Vec<ToolCallRecord>>>> = Arc::new(Mutex::new(None));
+    let received = Arc::new(Notify::new());
+
+    let mut receiver = runtime.new_actor::<CallLogReceiver>();
+    let slot_for_handler = slot.clone();
+    let received_signal = received.clone();
+    receiver.mutate_on::<CallLogResponse>(move |_actor, envelope| {
+        if let Ok(mut slot) = slot_for_handler.lock() {
+            *slot = Some(envelope.message().records.clone());
+        }
+        received_signal.notify_one();
+        Reply::ready()
+    });
+    let receiver = receiver.start().await;
+
+    receiver
+        .create_envelope(Some(tool.reply_address()))
+        .send(GetCallLog)
+        .await;
+    received.notified().await;
+    let _ = receiver.stop().await;
+
+    slot.lock()
+        .ok()
+        .and_then(|mut guard| guard.take())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[derive(Debug)]
+    struct EchoTool;
+
+    impl ToolExecutorTrait for EchoTool {
+        fn execute(&self, args: Value) -> ToolExecutionFuture {
+            Box::pin(async move { Ok(json!({"live": args})) })
+        }
+    }
+
+    fn sample_log() -> ToolCallLog {
+        vec![
+            ToolCallRecord::new("weather", json!({"city": "Paris"}), Ok(json!({"temp": 21}))),
+            ToolCallRecord::new("weather", json!({"city": "Oslo"}), Err("offline".into())),
+            ToolCallRecord::new("clock", json!({}), Ok(json!("12:00"))),
+        ]
+        .into()
+    }
+
+    #[test]
+    fn args_hash_ignores_key_order() {
+        let a = json!({"a": 1, "b": {"x": true, "y": [1, 2]}});
+        let b = json!({"b": {"y": [1, 2], "x": true}, "a": 1});
+        assert_eq!(args_hash(&a), args_hash(&b));
+        assert_ne!(args_hash(&a), args_hash(&json!({"a": 2})));
+    }
+
+    #[test]
+    fn record_matches_by_name_and_args() {
+        let record = ToolCallRecord::new("t", json!({"k": "v"}), Ok(json!(1)));
+        assert!(record.matches("t", &json!({"k": "v"})));
+        assert!(!record.matches("other", &json!({"k": "v"})));
+        assert!(!record.matches("t", &json!({"k": "w"})));
+    }
+
+    #[test]
+    fn record_with_duration() {
+        let record = ToolCallRecord::new("t", json!({}), Ok(json!(null)))
+            .with_duration(Duration::from_millis(42));
+        assert_eq!(record.duration_ms, 42);
+    }
+
+    #[test]
+    fn log_basics() {
+        let log = sample_log();
+        assert_eq!(log.len(), 3);
+        assert!(!log.is_empty());
+        assert!(log.contains_tool("clock"));
+        assert!(!log.contains_tool("bash"));
+        assert!(ToolCallLog::new().is_empty());
+    }
+
+    #[test]
+    fn log_file_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("calls.json");
+
+        let log = sample_log();
+        log.save_to_file(&path).unwrap();
+        let loaded = ToolCallLog::load_from_file(&path).unwrap();
+
+        assert_eq!(loaded, log);
+    }
+
+    #[test]
+    fn load_from_missing_file_fails() {
+        let dir = TempDir::new().unwrap();
+        assert!(ToolCallLog::load_from_file(dir.path().join("missing.json")).is_err());
+    }
+
+    #[tokio::test]
+    async fn replayer_returns_recorded_results() {
+        let replayer = ToolCallReplayer::new("weather", sample_log().into_records());
+
+        let ok = replayer.execute(json!({"city": "Paris"})).await.unwrap();
+        assert_eq!(ok, json!({"temp": 21}));
+
+        let err = replayer.execute(json!({"city": "Oslo"})).await.unwrap_err();
+        assert!(err.to_string().contains("offline"));
+    }
+
+    #[tokio::test]
+    async fn replayer_replays_repeated_calls_in_order() {
+        let records = vec![
+            ToolCallRecord::new("counter", json!({}), Ok(json!(1))),
+            ToolCallRecord::new("counter", json!({}), Ok(json!(2))),
+        ];
+        let replayer = ToolCallReplayer::new("counter", records);
+
+        assert_eq!(replayer.execute(json!({})).await.unwrap(), json!(1));
+        assert_eq!(replayer.execute(json!({})).await.unwrap(), json!(2));
+        // Exhausted: the last result sticks
+        assert_eq!(replayer.execute(json!({})).await.unwrap(), json!(2));
+    }
+
+    #[tokio::test]
+    async fn replayer_ignores_other_tools_records() {
+        let replayer = ToolCallReplayer::new("clock", sample_log().into_records());
+        assert!(replayer.replay(&json!({"city": "Paris"})).is_none());
+        assert!(replayer.replay(&json!({})).is_some());
+    }
+
+    #[tokio::test]
+    async fn replayer_falls_through_on_mismatch() {
+        let replayer = ToolCallReplayer::new("weather", sample_log().into_records())
+            .with_fallback(Arc::new(Box::new(EchoTool)));
+
+        let result = replayer.execute(json!({"city": "Rome"})).await.unwrap();
+        assert_eq!(result, json!({"live": {"city": "Rome"}}));
+    }
+
+    #[tokio::test]
+    async fn replayer_without_fallback_reports_not_found() {
+        let replayer = ToolCallReplayer::new("weather", sample_log().into_records());
+        let err = replayer.execute(json!({"city": "Rome"})).await.unwrap_err();
+        assert!(err.is_not_found());
+    }
+
+    #[tokio::test]
+    async fn tool_actor_records_calls_when_enabled() {
+        use crate::tools::actor::{ExecuteToolDirect, ToolActor};
+        use crate::tools::builtins::CalculateToolActor;
+        use crate::types::CorrelationId;
+
+        let mut app = ActonApp::launch_async().await;
+        let mut runtime = app.clone();
+        let handle = CalculateToolActor::spawn(&mut runtime).await;
+
+        assert!(CalculateToolActor::get_call_log(&mut runtime, &handle)
+            .await
+            .is_empty());
+
+        CalculateToolActor::enable_call_log(&handle).await;
+        handle
+            .send(ExecuteToolDirect::new(
+                CorrelationId::new(),
+                "tc_1",
+                json!({"expression": "2 + 2"}),
+            ))
+            .await;
+
+        // The record is appended once the call completes
+        let mut records = Vec::new();
+        for _ in 0..50 {
+            records = CalculateToolActor::get_call_log(&mut runtime, &handle).await;
+            if !records.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].tool_name, "calculate");
+        assert_eq!(records[0].args, json!({"expression": "2 + 2"}));
+        assert!(records[0].result.is_ok());
+
+        app.shutdown_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn execute_recorded_without_recorder_just_executes() {
+        let result = execute_recorded(&EchoTool, "echo", json!(1), None, None)
+            .await
+            .unwrap();
+        assert_eq!(result, json!({"live": 1}));
+    }
+}
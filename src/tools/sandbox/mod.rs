@@ -37,7 +37,10 @@ mod traits;
 pub mod process;
 
 // Re-export traits
-pub use traits::{Sandbox, SandboxExecutionFuture, SandboxFactory, SandboxFactoryFuture};
+pub use traits::{
+    Sandbox, SandboxExecutionFuture, SandboxExecutionMetrics, SandboxFactory, SandboxFactoryFuture,
+    SandboxMetricsFuture,
+};
 
 // Stub implementation is only available in tests
 #[cfg(test)]
@@ -151,6 +151,25 @@ mod tests {
         assert!(err.to_string().contains("destroyed"));
     }
 
+    #[tokio::test]
+    async fn default_execute_with_metrics_reports_wall_time() {
+        let sandbox = StubSandbox::new();
+        let (value, metrics) = sandbox
+            .execute_with_metrics("code", serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(value.get("status").unwrap(), "stub");
+        assert_eq!(metrics.exit_code, 0);
+        assert_eq!(metrics.peak_memory_bytes, 0);
+
+        let mut destroyed = StubSandbox::new();
+        destroyed.destroy();
+        assert!(destroyed
+            .execute_with_metrics("code", serde_json::json!({}))
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn stub_sandbox_is_alive_initially() {
         let sandbox = StubSandbox::new();
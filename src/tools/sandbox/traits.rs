@@ -7,11 +7,37 @@ use serde_json::Value;
 use std::fmt::Debug;
 use std::future::Future;
 use std::pin::Pin;
+use std::time::{Duration, Instant};
 
 /// The result type for sandbox execution futures.
 pub type SandboxExecutionFuture =
     Pin<Box<dyn Future<Output = Result<Value, ToolError>> + Send + Sync + 'static>>;
 
+/// The result type for sandbox executions that report telemetry.
+pub type SandboxMetricsFuture = Pin<
+    Box<
+        dyn Future<Output = Result<(Value, SandboxExecutionMetrics), ToolError>>
+            + Send
+            + Sync
+            + 'static,
+    >,
+>;
+
+/// Telemetry of one sandboxed execution.
+///
+/// Fields a sandbox cannot measure are left at zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SandboxExecutionMetrics {
+    /// Time from the start of the execution until its result was available
+    pub wall_time: Duration,
+    /// CPU time consumed by the executed code
+    pub cpu_time: Duration,
+    /// Peak memory used by the executed code
+    pub peak_memory_bytes: u64,
+    /// Exit code of the executed code
+    pub exit_code: i32,
+}
+
 /// The result type for sandbox factory futures.
 pub type SandboxFactoryFuture =
     Pin<Box<dyn Future<Output = Result<Box<dyn Sandbox>, ToolError>> + Send + Sync + 'static>>;
@@ -55,6 +81,30 @@ pub trait Sandbox: Send + Sync + Debug {
     /// Returns `ToolError::SandboxError` if execution fails within the sandbox.
     fn execute(&self, code: &str, args: Value) -> SandboxExecutionFuture;
 
+    /// Executes code in the sandbox and reports execution telemetry.
+    ///
+    /// # Default Implementation
+    ///
+    /// Wraps [`execute`](Self::execute) with wall-clock timing and reports
+    /// an exit code of 0 for successful executions. Override this method for
+    /// sandboxes that can measure CPU time and memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ToolError::SandboxError` if execution fails within the sandbox.
+    fn execute_with_metrics(&self, code: &str, args: Value) -> SandboxMetricsFuture {
+        let started = Instant::now();
+        let execution = self.execute(code, args);
+        Box::pin(async move {
+            let value = execution.await?;
+            let metrics = SandboxExecutionMetrics {
+                wall_time: started.elapsed(),
+                ..SandboxExecutionMetrics::default()
+            };
+            Ok((value, metrics))
+        })
+    }
+
     /// Destroys the sandbox, releasing all resources.
     ///
     /// After calling this, the sandbox cannot be used again.
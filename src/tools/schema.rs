@@ -0,0 +1,376 @@
+//! Validation of tool arguments against their JSON Schema.
+//!
+//! Covers the subset of JSON Schema used by tool input schemas: `type`,
+//! `enum`, `const`, `required`, `properties`, `additionalProperties`,
+//! `items`, numeric bounds, length bounds, `pattern`, and the `allOf` /
+//! `anyOf` / `oneOf` / `not` combinators. Unrecognized keywords are ignored.
+
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// A single way in which a value fails its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    /// JSON Pointer to the offending value (empty for the root)
+    pub path: String,
+    /// What was wrong
+    pub message: String,
+}
+
+impl SchemaViolation {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Validates `value` against `schema`.
+///
+/// # Errors
+///
+/// Returns every violation found.
+pub fn validate(schema: &Value, value: &Value) -> Result<(), Vec<SchemaViolation>> {
+    let mut violations = Vec::new();
+    check(schema, value, "", &mut violations);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn check(schema: &Value, value: &Value, path: &str, out: &mut Vec<SchemaViolation>) {
+    let schema = match schema {
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            out.push(SchemaViolation::new(path, "no value is allowed here"));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        if !matches_type(expected, value) {
+            out.push(SchemaViolation::new(
+                path,
+                format!("expected type {}, got {}", expected, type_name(value)),
+            ));
+            // Further keywords would only pile on noise for a wrong type.
+            return;
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            out.push(SchemaViolation::new(
+                path,
+                format!("{} is not one of {}", value, Value::Array(options.clone())),
+            ));
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            out.push(SchemaViolation::new(path, format!("expected {constant}")));
+        }
+    }
+
+    match value {
+        Value::Object(object) => check_object(schema, object, path, out),
+        Value::Array(items) => check_array(schema, items, path, out),
+        Value::String(s) => check_string(schema, s, path, out),
+        Value::Number(n) => {
+            if let Some(n) = n.as_f64() {
+                check_number(schema, n, path, out);
+            }
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+
+    check_combinators(schema, value, path, out);
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(name) {
+                out.push(SchemaViolation::new(
+                    path,
+                    format!("missing required property '{name}'"),
+                ));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (name, child) in object {
+        let child_path = format!("{path}/{name}");
+        match properties.and_then(|p| p.get(name)) {
+            Some(child_schema) => check(child_schema, child, &child_path, out),
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => out.push(SchemaViolation::new(
+                    &child_path,
+                    "additional property is not allowed",
+                )),
+                Some(additional) => check(additional, child, &child_path, out),
+                None => {}
+            },
+        }
+    }
+}
+
+fn check_array(
+    schema: &Map<String, Value>,
+    items: &[Value],
+    path: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if (items.len() as u64) < min {
+            out.push(SchemaViolation::new(
+                path,
+                format!("expected at least {min} items"),
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if items.len() as u64 > max {
+            out.push(SchemaViolation::new(
+                path,
+                format!("expected at most {max} items"),
+            ));
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            check(item_schema, item, &format!("{path}/{i}"), out);
+        }
+    }
+}
+
+fn check_string(schema: &Map<String, Value>, s: &str, path: &str, out: &mut Vec<SchemaViolation>) {
+    let len = s.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if len < min {
+            out.push(SchemaViolation::new(
+                path,
+                format!("expected at least {min} characters"),
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if len > max {
+            out.push(SchemaViolation::new(
+                path,
+                format!("expected at most {max} characters"),
+            ));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        match regex::Regex::new(pattern) {
+            Ok(re) if !re.is_match(s) => out.push(SchemaViolation::new(
+                path,
+                format!("does not match pattern '{pattern}'"),
+            )),
+            Ok(_) => {}
+            Err(e) => out.push(SchemaViolation::new(
+                path,
+                format!("schema pattern '{pattern}' is invalid: {e}"),
+            )),
+        }
+    }
+}
+
+fn check_number(schema: &Map<String, Value>, n: f64, path: &str, out: &mut Vec<SchemaViolation>) {
+    let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+    if let Some(min) = bound("minimum") {
+        if n < min {
+            out.push(SchemaViolation::new(path, format!("must be >= {min}")));
+        }
+    }
+    if let Some(max) = bound("maximum") {
+        if n > max {
+            out.push(SchemaViolation::new(path, format!("must be <= {max}")));
+        }
+    }
+    if let Some(min) = bound("exclusiveMinimum") {
+        if n <= min {
+            out.push(SchemaViolation::new(path, format!("must be > {min}")));
+        }
+    }
+    if let Some(max) = bound("exclusiveMaximum") {
+        if n >= max {
+            out.push(SchemaViolation::new(path, format!("must be < {max}")));
+        }
+    }
+}
+
+fn check_combinators(
+    schema: &Map<String, Value>,
+    value: &Value,
+    path: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        for sub in all {
+            check(sub, value, path, out);
+        }
+    }
+    if let Some(Value::Array(any)) = schema.get("anyOf") {
+        if !any.iter().any(|sub| validate(sub, value).is_ok()) {
+            out.push(SchemaViolation::new(
+                path,
+                "does not match any allowed schema",
+            ));
+        }
+    }
+    if let Some(Value::Array(one)) = schema.get("oneOf") {
+        let matching = one
+            .iter()
+            .filter(|sub| validate(sub, value).is_ok())
+            .count();
+        if matching != 1 {
+            out.push(SchemaViolation::new(
+                path,
+                format!("must match exactly one schema, matched {matching}"),
+            ));
+        }
+    }
+    if let Some(excluded) = schema.get("not") {
+        if validate(excluded, value).is_ok() {
+            let message = excluded
+                .get("description")
+                .and_then(Value::as_str)
+                .map_or_else(|| "matches a disallowed schema".to_string(), str::to_string);
+            out.push(SchemaViolation::new(path, message));
+        }
+    }
+}
+
+fn matches_type(expected: &Value, value: &Value) -> bool {
+    match expected {
+        Value::String(name) => matches_type_name(name, value),
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|name| matches_type_name(name, value)),
+        _ => true,
+    }
+}
+
+fn matches_type_name(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => match value {
+            Value::Number(n) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        },
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "count": {"type": "integer", "minimum": 0, "maximum": 10},
+                "mode": {"type": "string", "enum": ["fast", "slow"]},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["name"],
+            "additionalProperties": false
+        })
+    }
+
+    #[test]
+    fn accepts_conforming_value() {
+        let value = json!({"name": "x", "count": 3, "mode": "fast", "tags": ["a"]});
+        assert!(validate(&schema(), &value).is_ok());
+    }
+
+    #[test]
+    fn reports_every_violation_with_path() {
+        let value = json!({"count": 11, "mode": "medium", "tags": [1], "extra": true});
+        let violations = validate(&schema(), &value).unwrap_err();
+        let paths: Vec<_> = violations.iter().map(|v| v.path.as_str()).collect();
+
+        assert_eq!(violations.len(), 5, "{violations:?}");
+        assert!(paths.contains(&""));
+        assert!(paths.contains(&"/count"));
+        assert!(paths.contains(&"/mode"));
+        assert!(paths.contains(&"/tags/0"));
+        assert!(paths.contains(&"/extra"));
+    }
+
+    #[test]
+    fn integer_accepts_whole_floats_only() {
+        let schema = json!({"type": "integer"});
+        assert!(validate(&schema, &json!(2.0)).is_ok());
+        assert!(validate(&schema, &json!(2.5)).is_err());
+        assert!(validate(&schema, &json!("2")).is_err());
+    }
+
+    #[test]
+    fn combinators() {
+        let schema = json!({"anyOf": [{"type": "string"}, {"type": "integer"}]});
+        assert!(validate(&schema, &json!(1)).is_ok());
+        assert!(validate(&schema, &json!(true)).is_err());
+
+        let schema = json!({"oneOf": [{"type": "number"}, {"type": "integer"}]});
+        assert!(validate(&schema, &json!(1)).is_err());
+        assert!(validate(&schema, &json!(1.5)).is_ok());
+
+        let schema = json!({"not": {"pattern": "^x", "description": "must not start with x"}});
+        assert!(validate(&schema, &json!("yx")).is_ok());
+        let violations = validate(&schema, &json!("xy")).unwrap_err();
+        assert_eq!(violations[0].message, "must not start with x");
+    }
+
+    #[test]
+    fn violation_display_includes_path() {
+        let violation = SchemaViolation::new("/count", "must be <= 10");
+        assert_eq!(violation.to_string(), "/count: must be <= 10");
+    }
+}
@@ -0,0 +1,236 @@
+//! Audit trail of path validation decisions.
+//!
+//! A [`PathValidator`](super::PathValidator) emits a tracing event under
+//! [`PATH_AUDIT_TARGET`] for every decision of its `*_for` methods and, when
+//! configured with an audit buffer, keeps the most recent decisions as
+//! [`PathValidationAuditRecord`]s that can be written out as NDJSON.
+
+use super::path::{Operation, PathValidationError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Tracing target of path validation audit events.
+pub const PATH_AUDIT_TARGET: &str = "acton_ai::path_audit";
+
+/// Outcome of a path validation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "lowercase")]
+pub enum ValidationResult {
+    /// The path was permitted
+    Allowed,
+    /// The path was rejected
+    Denied {
+        /// Short description of why (e.g., "traversal attempt")
+        reason: String,
+    },
+}
+
+impl ValidationResult {
+    /// Returns whether the path was permitted.
+    #[must_use]
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allowed)
+    }
+
+    /// Returns the reason the path was rejected, if it was.
+    #[must_use]
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Allowed => None,
+            Self::Denied { reason } => Some(reason),
+        }
+    }
+}
+
+impl From<&PathValidationError> for ValidationResult {
+    fn from(error: &PathValidationError) -> Self {
+        let reason = match error {
+            PathValidationError::DeniedPattern { pattern, .. } if pattern == ".." => {
+                "traversal attempt".to_string()
+            }
+            PathValidationError::DeniedPattern { pattern, .. } => {
+                format!("denied pattern '{pattern}'")
+            }
+            PathValidationError::OutsideAllowedRoots { .. } => "outside root".to_string(),
+            PathValidationError::CanonicalizeError { reason, .. } => {
+                format!("cannot resolve path: {reason}")
+            }
+            PathValidationError::OperationDenied { operation, .. } => {
+                format!("{operation} not permitted by policy")
+            }
+            PathValidationError::SymlinkNotAllowed { .. } => "symlink not allowed".to_string(),
+        };
+        Self::Denied { reason }
+    }
+}
+
+/// One audited path validation decision.
+///
+/// Serializes to a flat object, e.g.
+/// `{"path":"/etc/passwd","operation":"read","result":"denied","reason":"outside root","timestamp":"..."}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathValidationAuditRecord {
+    /// The path as requested, before canonicalization
+    pub path: PathBuf,
+    /// The operation the path was validated for
+    pub operation: Operation,
+    /// Whether the path was permitted, and why not
+    #[serde(flatten)]
+    pub result: ValidationResult,
+    /// When the decision was made
+    pub timestamp: DateTime<Utc>,
+}
+
+impl PathValidationAuditRecord {
+    /// Returns the reason the path was rejected, if it was.
+    #[must_use]
+    pub fn reason(&self) -> Option<&str> {
+        self.result.reason()
+    }
+}
+
+/// Ring buffer of the most recent audit records.
+#[derive(Debug)]
+pub(super) struct AuditBuffer {
+    capacity: usize,
+    records: Mutex<VecDeque<PathValidationAuditRecord>>,
+}
+
+impl AuditBuffer {
+    pub(super) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            records: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<PathValidationAuditRecord>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Stores `record`, dropping the oldest record if the buffer is full.
+    pub(super) fn push(&self, record: PathValidationAuditRecord) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut records = self.lock();
+        if records.len() >= self.capacity {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+
+    /// Returns the stored records, oldest first.
+    pub(super) fn records(&self) -> Vec<PathValidationAuditRecord> {
+        self.lock().iter().cloned().collect()
+    }
+
+    /// Appends the stored records to `path` as NDJSON and clears the buffer.
+    ///
+    /// Records stay buffered if the file cannot be written.
+    pub(super) fn flush_to_file(&self, path: &Path) -> std::io::Result<()> {
+        let mut records = self.lock();
+        let mut out = String::new();
+        for record in records.iter() {
+            out.push_str(&serde_json::to_string(record).map_err(std::io::Error::other)?);
+            out.push('\n');
+        }
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(out.as_bytes())?;
+        records.clear();
+        Ok(())
+    }
+}
+
+/// Emits the tracing event of a validation decision.
+pub(super) fn emit_audit_event(path: &Path, operation: Operation, result: &ValidationResult) {
+    let span_id = tracing::Span::current().id().map(|id| id.into_u64());
+    match result {
+        ValidationResult::Allowed => tracing::info!(
+            target: PATH_AUDIT_TARGET,
+            path = %path.display(),
+            %operation,
+            result = "allowed",
+            span_id,
+            "path access allowed"
+        ),
+        ValidationResult::Denied { reason } => tracing::warn!(
+            target: PATH_AUDIT_TARGET,
+            path = %path.display(),
+            %operation,
+            result = "denied",
+            reason = %reason,
+            span_id,
+            "path access denied"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(path: &str, result: ValidationResult) -> PathValidationAuditRecord {
+        PathValidationAuditRecord {
+            path: PathBuf::from(path),
+            operation: Operation::Read,
+            result,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn reasons_describe_the_rejection() {
+        let traversal = PathValidationError::DeniedPattern {
+            path: PathBuf::from("/a/../b"),
+            pattern: "..".to_string(),
+        };
+        let outside = PathValidationError::OutsideAllowedRoots {
+            path: PathBuf::from("/etc/passwd"),
+            allowed_roots: Vec::new(),
+        };
+        assert_eq!(
+            ValidationResult::from(&traversal).reason(),
+            Some("traversal attempt")
+        );
+        assert_eq!(
+            ValidationResult::from(&outside).reason(),
+            Some("outside root")
+        );
+    }
+
+    #[test]
+    fn buffer_keeps_most_recent_records() {
+        let buffer = AuditBuffer::new(2);
+        buffer.push(record("/a", ValidationResult::Allowed));
+        buffer.push(record("/b", ValidationResult::Allowed));
+        buffer.push(record("/c", ValidationResult::Allowed));
+
+        let paths: Vec<PathBuf> = buffer.records().into_iter().map(|r| r.path).collect();
+        assert_eq!(paths, vec![PathBuf::from("/b"), PathBuf::from("/c")]);
+    }
+
+    #[test]
+    fn records_serialize_as_flat_json() {
+        let denied = record(
+            "/etc/passwd",
+            ValidationResult::Denied {
+                reason: "outside root".to_string(),
+            },
+        );
+        let value = serde_json::to_value(&denied).unwrap();
+        assert_eq!(value["operation"], "read");
+        assert_eq!(value["result"], "denied");
+        assert_eq!(value["reason"], "outside root");
+
+        let round_trip: PathValidationAuditRecord = serde_json::from_value(value).unwrap();
+        assert_eq!(round_trip, denied);
+    }
+}
@@ -3,6 +3,8 @@
 //! This module provides security controls for filesystem and other operations:
 //!
 //! - **Path Validation**: Restricts filesystem access to allowed directories
+//! - **Security Policies**: Separate read and write rules with access auditing
+//! - **Audit Trail**: Tracing events and NDJSON records of validation decisions
 //!
 //! ## Path Validation
 //!
@@ -30,7 +32,42 @@
 //! - Environment files (`.env`)
 //!
 //! And restricts access to the current working directory.
+//!
+//! ## Security Policies
+//!
+//! A [`SecurityPolicy`] gives reads and writes their own allowed roots and
+//! can record every permitted access:
+//!
+//! ```rust,ignore
+//! use acton_ai::tools::security::{Operation, PathValidator, SecurityPolicy};
+//!
+//! let policy = SecurityPolicy::read_only(PathBuf::from("/srv/docs"))
+//!     .on_access(|op, path| tracing::info!(%op, path = %path.display(), "file access"));
+//! let validator = PathValidator::new_with_policy(policy);
+//!
+//! // Reads succeed, writes are rejected with `OperationDenied`
+//! validator.validate_file_for(Operation::Read, Path::new("/srv/docs/guide.md"))?;
+//! assert!(validator.validate_parent_for(Operation::Write, Path::new("/srv/docs/new.md")).is_err());
+//! ```
+//!
+//! ## Audit Trail
+//!
+//! The `*_for` methods emit every decision under [`PATH_AUDIT_TARGET`].
+//! With an audit buffer, recent decisions can also be inspected or saved:
+//!
+//! ```rust,ignore
+//! let validator = PathValidator::new().with_audit_buffer(1000);
+//! // ... validations ...
+//! for record in validator.audit_records() {
+//!     println!("{} {:?} {:?}", record.operation, record.path, record.reason());
+//! }
+//! validator.flush_audit_to_file(Path::new("/var/log/agent-paths.ndjson"))?;
+//! ```
 
+mod audit;
 mod path;
 
-pub use path::{PathValidationError, PathValidator};
+pub use audit::{PathValidationAuditRecord, ValidationResult, PATH_AUDIT_TARGET};
+pub use path::{
+    AccessCallback, Operation, PathValidationError, PathValidator, SecurityPolicy, SymlinkPolicy,
+};
@@ -1,10 +1,14 @@
 //! Path validation for filesystem security.
 //!
 //! Provides `PathValidator` for restricting filesystem access to allowed
-//! directories and blocking paths containing denied patterns.
+//! directories and blocking paths containing denied patterns, and
+//! `SecurityPolicy` for separate read and write rules.
 
+use super::audit::{emit_audit_event, AuditBuffer, PathValidationAuditRecord, ValidationResult};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Error returned when path validation fails.
 ///
@@ -33,6 +37,24 @@ pub enum PathValidationError {
         /// The pattern that matched.
         pattern: String,
     },
+    /// The security policy does not permit the operation on this path.
+    OperationDenied {
+        /// The path that was rejected.
+        path: PathBuf,
+        /// The operation that was attempted.
+        operation: Operation,
+        /// The roots the policy permits this operation in.
+        allowed_roots: Vec<PathBuf>,
+    },
+    /// The path traverses a symlink the [`SymlinkPolicy`] does not permit.
+    SymlinkNotAllowed {
+        /// The path that was rejected.
+        path: PathBuf,
+        /// The symlink in the path.
+        link: PathBuf,
+        /// The policy that rejected it.
+        policy: SymlinkPolicy,
+    },
 }
 
 impl fmt::Display for PathValidationError {
@@ -71,18 +93,227 @@ impl fmt::Display for PathValidationError {
                     pattern
                 )
             }
+            Self::OperationDenied {
+                path,
+                operation,
+                allowed_roots,
+            } => {
+                if allowed_roots.is_empty() {
+                    return write!(
+                        f,
+                        "{} access to '{}' is denied; the security policy permits no {} operations",
+                        operation,
+                        path.display(),
+                        operation
+                    );
+                }
+                let roots: Vec<String> = allowed_roots
+                    .iter()
+                    .map(|p| p.display().to_string())
+                    .collect();
+                write!(
+                    f,
+                    "{} access to '{}' is denied; the security policy permits it only in [{}]",
+                    operation,
+                    path.display(),
+                    roots.join(", ")
+                )
+            }
+            Self::SymlinkNotAllowed { path, link, policy } => match policy {
+                SymlinkPolicy::AllowWithinRoot => write!(
+                    f,
+                    "path '{}' goes through symlink '{}', which points outside its allowed directory",
+                    path.display(),
+                    link.display()
+                ),
+                SymlinkPolicy::Allow | SymlinkPolicy::Deny => write!(
+                    f,
+                    "path '{}' goes through symlink '{}'; symlinks are not allowed",
+                    path.display(),
+                    link.display()
+                ),
+            },
         }
     }
 }
 
 impl std::error::Error for PathValidationError {}
 
+/// Kind of filesystem access being validated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operation {
+    /// Reading file or directory contents
+    Read,
+    /// Creating, modifying, or deleting files
+    Write,
+    /// Running a file as a program
+    Execute,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Write => write!(f, "write"),
+            Self::Execute => write!(f, "execute"),
+        }
+    }
+}
+
+/// How a [`PathValidator`] treats symlinks in validated paths.
+///
+/// Applies to the path itself and to every directory it goes through,
+/// except the directories leading up to an allowed root.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymlinkPolicy {
+    /// Symlinks are resolved and only the final target is validated
+    #[default]
+    Allow,
+    /// Any symlink rejects the path
+    Deny,
+    /// A symlink is permitted only if its target is within the same allowed
+    /// root as the link itself
+    AllowWithinRoot,
+}
+
+/// Callback invoked with every access a [`SecurityPolicy`] permits.
+pub type AccessCallback = Arc<dyn Fn(Operation, &Path) + Send + Sync>;
+
+/// Per-operation rules for a [`PathValidator`].
+///
+/// Reads are permitted under `allowed_read_roots` and writes under
+/// `allowed_write_roots`. Execution can modify the filesystem, so it is
+/// permitted only under the write roots.
+///
+/// # Example
+///
+/// ```rust
+/// use std::path::{Path, PathBuf};
+/// use acton_ai::tools::security::{Operation, PathValidator, SecurityPolicy};
+///
+/// let root = std::env::temp_dir();
+/// let validator = PathValidator::new_with_policy(SecurityPolicy::read_only(root.clone()));
+///
+/// assert!(validator.validate_for(Operation::Read, &root).is_ok());
+/// assert!(validator.validate_for(Operation::Write, &root).is_err());
+/// ```
+#[derive(Clone, Default)]
+pub struct SecurityPolicy {
+    /// Directories that may be read
+    pub allowed_read_roots: Vec<PathBuf>,
+    /// Directories that may be written to
+    pub allowed_write_roots: Vec<PathBuf>,
+    /// Called with the operation and canonical path of every permitted access
+    pub on_access: Option<AccessCallback>,
+}
+
+impl SecurityPolicy {
+    /// Creates a policy that permits nothing until roots are added.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy that permits reads under `root` and rejects all writes.
+    #[must_use]
+    pub fn read_only(root: PathBuf) -> Self {
+        Self::new().with_read_root(root)
+    }
+
+    /// Creates a policy that permits reads and writes under `root`.
+    #[must_use]
+    pub fn read_write(root: PathBuf) -> Self {
+        Self::new()
+            .with_read_root(root.clone())
+            .with_write_root(root)
+    }
+
+    /// Adds a directory that may be read.
+    #[must_use]
+    pub fn with_read_root(mut self, root: PathBuf) -> Self {
+        self.allowed_read_roots.push(root);
+        self
+    }
+
+    /// Adds a directory that may be written to.
+    #[must_use]
+    pub fn with_write_root(mut self, root: PathBuf) -> Self {
+        self.allowed_write_roots.push(root);
+        self
+    }
+
+    /// Sets a callback to record every permitted access.
+    #[must_use]
+    pub fn on_access(
+        mut self,
+        callback: impl Fn(Operation, &Path) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_access = Some(Arc::new(callback));
+        self
+    }
+
+    /// Returns the roots `operation` is permitted in.
+    #[must_use]
+    pub fn roots_for(&self, operation: Operation) -> &[PathBuf] {
+        match operation {
+            Operation::Read => &self.allowed_read_roots,
+            Operation::Write | Operation::Execute => &self.allowed_write_roots,
+        }
+    }
+
+    /// Returns the read and write roots without duplicates.
+    fn all_roots(&self) -> Vec<PathBuf> {
+        let mut roots = self.allowed_read_roots.clone();
+        for root in &self.allowed_write_roots {
+            if !roots.contains(root) {
+                roots.push(root.clone());
+            }
+        }
+        roots
+    }
+}
+
+impl fmt::Debug for SecurityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecurityPolicy")
+            .field("allowed_read_roots", &self.allowed_read_roots)
+            .field("allowed_write_roots", &self.allowed_write_roots)
+            .field("on_access", &self.on_access.is_some())
+            .finish()
+    }
+}
+
+/// Returns whether `path`, or its nearest existing ancestor when it does
+/// not exist yet, is inside one of `roots`.
+fn within_roots(path: &Path, roots: &[PathBuf]) -> bool {
+    let Some(canonical) = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .and_then(|ancestor| ancestor.canonicalize().ok())
+    else {
+        return false;
+    };
+    roots.iter().any(|root| {
+        root.canonicalize()
+            .map(|canonical_root| canonical.starts_with(&canonical_root))
+            .unwrap_or(false)
+    })
+}
+
 /// Validates paths against an allowlist for filesystem operations.
 ///
 /// `PathValidator` provides security controls for filesystem access by:
 /// - Restricting operations to allowed root directories
 /// - Blocking paths containing denied patterns (like `..` or `.env`)
 /// - Canonicalizing paths to prevent symlink attacks
+/// - Optionally restricting symlinks further with a [`SymlinkPolicy`]
+///
+/// Every decision of the `*_for` methods is emitted as a tracing event under
+/// [`PATH_AUDIT_TARGET`](super::PATH_AUDIT_TARGET), and kept in memory when
+/// an audit buffer is configured with
+/// [`with_audit_buffer`](Self::with_audit_buffer). Clones share the buffer.
 ///
 /// # Example
 ///
@@ -106,6 +337,12 @@ pub struct PathValidator {
     allowed_roots: Vec<PathBuf>,
     /// Patterns that are blocked from appearing in paths.
     denied_patterns: Vec<String>,
+    /// Per-operation rules checked by the `*_for` methods.
+    policy: Option<SecurityPolicy>,
+    /// Most recent decisions of the `*_for` methods.
+    audit: Option<Arc<AuditBuffer>>,
+    /// How symlinks in validated paths are treated.
+    symlink_policy: SymlinkPolicy,
 }
 
 impl PathValidator {
@@ -129,9 +366,112 @@ impl PathValidator {
         Self {
             allowed_roots,
             denied_patterns: vec!["..".to_string(), ".git".to_string(), ".env".to_string()],
+            policy: None,
+            audit: None,
+            symlink_policy: SymlinkPolicy::Allow,
+        }
+    }
+
+    /// Creates a `PathValidator` that enforces `policy`.
+    ///
+    /// The allowed roots are the policy's read and write roots, and the
+    /// default denied patterns apply. Use the `*_for` methods to check a
+    /// path for a specific [`Operation`].
+    #[must_use]
+    pub fn new_with_policy(policy: SecurityPolicy) -> Self {
+        Self {
+            allowed_roots: policy.all_roots(),
+            policy: Some(policy),
+            ..Self::new()
+        }
+    }
+
+    /// Sets how symlinks in validated paths are treated.
+    ///
+    /// Defaults to [`SymlinkPolicy::Allow`].
+    #[must_use]
+    pub fn with_symlink_policy(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlink_policy = policy;
+        self
+    }
+
+    /// Returns the symlink policy.
+    #[must_use]
+    pub fn symlink_policy(&self) -> SymlinkPolicy {
+        self.symlink_policy
+    }
+
+    /// Keeps the last `capacity` validation decisions for inspection.
+    #[must_use]
+    pub fn with_audit_buffer(mut self, capacity: usize) -> Self {
+        self.audit = Some(Arc::new(AuditBuffer::new(capacity)));
+        self
+    }
+
+    /// Returns the buffered validation decisions, oldest first.
+    ///
+    /// Empty unless an audit buffer was configured.
+    #[must_use]
+    pub fn audit_records(&self) -> Vec<PathValidationAuditRecord> {
+        self.audit
+            .as_ref()
+            .map(|audit| audit.records())
+            .unwrap_or_default()
+    }
+
+    /// Appends the buffered validation decisions to `path` as NDJSON and
+    /// clears the buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be written; the records stay
+    /// buffered in that case.
+    pub fn flush_audit_to_file(&self, path: &Path) -> std::io::Result<()> {
+        match &self.audit {
+            Some(audit) => audit.flush_to_file(path),
+            None => Ok(()),
         }
     }
 
+    /// Records a validation decision.
+    ///
+    /// Emits a tracing event under
+    /// [`PATH_AUDIT_TARGET`](super::PATH_AUDIT_TARGET) and stores the
+    /// decision in the audit buffer, if one is configured. The `*_for`
+    /// methods call this for every path they check.
+    pub fn audit_log(&self, path: &Path, op: Operation, result: ValidationResult) {
+        emit_audit_event(path, op, &result);
+        if let Some(audit) = &self.audit {
+            audit.push(PathValidationAuditRecord {
+                path: path.to_path_buf(),
+                operation: op,
+                result,
+                timestamp: chrono::Utc::now(),
+            });
+        }
+    }
+
+    /// Audits the outcome of validating `path` for `operation`.
+    fn audited(
+        &self,
+        operation: Operation,
+        path: &Path,
+        result: Result<PathBuf, PathValidationError>,
+    ) -> Result<PathBuf, PathValidationError> {
+        let outcome = match &result {
+            Ok(_) => ValidationResult::Allowed,
+            Err(e) => ValidationResult::from(e),
+        };
+        self.audit_log(path, operation, outcome);
+        result
+    }
+
+    /// Returns the security policy, if one was set.
+    #[must_use]
+    pub fn policy(&self) -> Option<&SecurityPolicy> {
+        self.policy.as_ref()
+    }
+
     /// Adds an allowed root directory.
     ///
     /// Paths must be within at least one allowed root to pass validation.
@@ -184,8 +524,9 @@ impl PathValidator {
     ///
     /// Validation steps:
     /// 1. Check for denied patterns in the original path string
-    /// 2. Canonicalize the path (resolves symlinks, normalizes)
-    /// 3. Verify the canonical path is within an allowed root
+    /// 2. Check the symlinks in the path against the symlink policy
+    /// 3. Canonicalize the path (resolves symlinks, normalizes)
+    /// 4. Verify the canonical path is within an allowed root
     ///
     /// # Returns
     ///
@@ -195,6 +536,7 @@ impl PathValidator {
     ///
     /// Returns `PathValidationError` if:
     /// - The path contains a denied pattern
+    /// - The path goes through a symlink the symlink policy rejects
     /// - The path cannot be canonicalized (doesn't exist, permission denied)
     /// - The canonical path is outside all allowed roots
     pub fn validate(&self, path: &Path) -> Result<PathBuf, PathValidationError> {
@@ -211,7 +553,10 @@ impl PathValidator {
             }
         }
 
-        // 2. Canonicalize the path (resolves symlinks, normalizes)
+        // 2. Check symlinks before canonicalization resolves them away
+        self.check_symlinks(path)?;
+
+        // 3. Canonicalize the path (resolves symlinks, normalizes)
         let canonical =
             path.canonicalize()
                 .map_err(|e| PathValidationError::CanonicalizeError {
@@ -219,7 +564,7 @@ impl PathValidator {
                     reason: e.to_string(),
                 })?;
 
-        // 3. Check if canonical path is within an allowed root
+        // 4. Check if canonical path is within an allowed root
         let allowed = self.allowed_roots.iter().any(|root| {
             // Canonicalize the root too for proper comparison
             root.canonicalize()
@@ -290,6 +635,7 @@ impl PathValidator {
     ///
     /// Returns error if:
     /// - The path contains denied patterns
+    /// - The path goes through a symlink the symlink policy rejects
     /// - The parent directory cannot be resolved
     /// - The parent is outside allowed roots
     pub fn validate_parent(&self, path: &Path) -> Result<PathBuf, PathValidationError> {
@@ -303,6 +649,7 @@ impl PathValidator {
                 });
             }
         }
+        self.check_symlinks(path)?;
 
         // Get parent directory
         let parent = path
@@ -354,6 +701,141 @@ impl PathValidator {
             })
         }
     }
+
+    /// Validates a path for `operation`.
+    ///
+    /// Same as `validate`, but when a [`SecurityPolicy`] is set the path must
+    /// also be within the policy's roots for `operation`, and the policy's
+    /// `on_access` callback is invoked on success.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if validation fails or the policy denies `operation`.
+    pub fn validate_for(
+        &self,
+        operation: Operation,
+        path: &Path,
+    ) -> Result<PathBuf, PathValidationError> {
+        let result = self
+            .validate(path)
+            .and_then(|validated| self.check_operation(operation, path, validated));
+        self.audited(operation, path, result)
+    }
+
+    /// Validates a file path for `operation`.
+    ///
+    /// Same as `validate_file`, with the policy checks of `validate_for`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if validation fails or the policy denies `operation`.
+    pub fn validate_file_for(
+        &self,
+        operation: Operation,
+        path: &Path,
+    ) -> Result<PathBuf, PathValidationError> {
+        let result = self
+            .validate_file(path)
+            .and_then(|validated| self.check_operation(operation, path, validated));
+        self.audited(operation, path, result)
+    }
+
+    /// Validates the parent of a file to be created for `operation`.
+    ///
+    /// Same as `validate_parent`, with the policy checks of `validate_for`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if validation fails or the policy denies `operation`.
+    pub fn validate_parent_for(
+        &self,
+        operation: Operation,
+        path: &Path,
+    ) -> Result<PathBuf, PathValidationError> {
+        let result = self
+            .validate_parent(path)
+            .and_then(|validated| self.check_operation(operation, path, validated));
+        self.audited(operation, path, result)
+    }
+
+    /// Applies the symlink policy to every existing symlink in `path`.
+    ///
+    /// Directories leading up to an allowed root are not checked, so a root
+    /// may itself be reached through a symlink.
+    fn check_symlinks(&self, path: &Path) -> Result<(), PathValidationError> {
+        if self.symlink_policy == SymlinkPolicy::Allow {
+            return Ok(());
+        }
+
+        let links = path.ancestors().filter(|ancestor| {
+            !ancestor.as_os_str().is_empty()
+                && !self
+                    .allowed_roots
+                    .iter()
+                    .any(|root| root.starts_with(ancestor))
+                && ancestor
+                    .symlink_metadata()
+                    .is_ok_and(|metadata| metadata.file_type().is_symlink())
+        });
+        for link in links {
+            if self.symlink_policy == SymlinkPolicy::Deny || !self.link_stays_within_root(link) {
+                return Err(PathValidationError::SymlinkNotAllowed {
+                    path: path.to_path_buf(),
+                    link: link.to_path_buf(),
+                    policy: self.symlink_policy,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether `link` and its resolved target are within the same
+    /// allowed root.
+    fn link_stays_within_root(&self, link: &Path) -> bool {
+        let Some(location) = link
+            .parent()
+            .and_then(|parent| parent.canonicalize().ok())
+            .zip(link.file_name())
+            .map(|(parent, name)| parent.join(name))
+        else {
+            return false;
+        };
+        // A dangling link fails canonicalization during validation anyway
+        let Ok(target) = link.canonicalize() else {
+            return true;
+        };
+        self.allowed_roots.iter().any(|root| {
+            root.canonicalize()
+                .map(|root| location.starts_with(&root) && target.starts_with(&root))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Applies the policy's rules for `operation` to an already validated path.
+    fn check_operation(
+        &self,
+        operation: Operation,
+        path: &Path,
+        validated: PathBuf,
+    ) -> Result<PathBuf, PathValidationError> {
+        let Some(policy) = &self.policy else {
+            return Ok(validated);
+        };
+
+        let roots = policy.roots_for(operation);
+        if !within_roots(&validated, roots) {
+            return Err(PathValidationError::OperationDenied {
+                path: path.to_path_buf(),
+                operation,
+                allowed_roots: roots.to_vec(),
+            });
+        }
+
+        if let Some(on_access) = &policy.on_access {
+            on_access(operation, &validated);
+        }
+        Ok(validated)
+    }
 }
 
 impl Default for PathValidator {
@@ -702,4 +1184,212 @@ mod tests {
             Err(PathValidationError::OutsideAllowedRoots { .. })
         ));
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_policy_controls_links_between_roots() {
+        let project = TempDir::new().unwrap();
+        let shared = TempDir::new().unwrap();
+        let shared_file = shared.path().join("shared.txt");
+        fs::write(&shared_file, "shared").unwrap();
+        let link = project.path().join("shared.txt");
+        std::os::unix::fs::symlink(&shared_file, &link).unwrap();
+
+        let validator = PathValidator::new()
+            .clear_allowed_roots()
+            .with_allowed_root(project.path().to_path_buf())
+            .with_allowed_root(shared.path().to_path_buf());
+
+        // The target is within an allowed root, so the resolved path passes
+        assert_eq!(
+            validator.validate(&link).unwrap(),
+            shared_file.canonicalize().unwrap()
+        );
+        // ...but the link points outside the root it lives in
+        let within_root = validator
+            .clone()
+            .with_symlink_policy(SymlinkPolicy::AllowWithinRoot);
+        assert!(matches!(
+            within_root.validate(&link),
+            Err(PathValidationError::SymlinkNotAllowed { link: l, .. }) if l == link
+        ));
+        assert!(within_root.validate_parent(&link.join("new.txt")).is_err());
+        assert!(within_root.validate(&shared_file).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_policy_within_root_and_deny() {
+        let dir = TempDir::new().unwrap();
+        let real_dir = dir.path().join("real");
+        fs::create_dir(&real_dir).unwrap();
+        fs::write(real_dir.join("file.txt"), "content").unwrap();
+        let linked_dir = dir.path().join("linked");
+        std::os::unix::fs::symlink(&real_dir, &linked_dir).unwrap();
+        let through_link = linked_dir.join("file.txt");
+
+        let validator = PathValidator::new()
+            .clear_allowed_roots()
+            .with_allowed_root(dir.path().to_path_buf());
+
+        assert!(validator
+            .clone()
+            .with_symlink_policy(SymlinkPolicy::AllowWithinRoot)
+            .validate(&through_link)
+            .is_ok());
+
+        let deny = validator.with_symlink_policy(SymlinkPolicy::Deny);
+        let error = deny.validate(&through_link).unwrap_err();
+        assert!(matches!(
+            &error,
+            PathValidationError::SymlinkNotAllowed { link, .. } if *link == linked_dir
+        ));
+        assert!(error.to_string().contains("symlinks are not allowed"));
+        assert!(deny.validate(&real_dir.join("file.txt")).is_ok());
+    }
+
+    #[test]
+    fn read_only_policy_permits_reads_and_rejects_writes() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("notes.txt");
+        fs::write(&file_path, "content").unwrap();
+
+        let validator =
+            PathValidator::new_with_policy(SecurityPolicy::read_only(dir.path().to_path_buf()));
+
+        assert!(validator
+            .validate_file_for(Operation::Read, &file_path)
+            .is_ok());
+        assert!(matches!(
+            validator.validate_file_for(Operation::Write, &file_path),
+            Err(PathValidationError::OperationDenied {
+                operation: Operation::Write,
+                ..
+            })
+        ));
+        assert!(validator
+            .validate_parent_for(Operation::Write, &dir.path().join("new/file.txt"))
+            .is_err());
+        assert!(validator
+            .validate_for(Operation::Execute, &file_path)
+            .is_err());
+    }
+
+    #[test]
+    fn policy_roots_are_separate_per_operation() {
+        let read_dir = TempDir::new().unwrap();
+        let write_dir = TempDir::new().unwrap();
+        let policy = SecurityPolicy::new()
+            .with_read_root(read_dir.path().to_path_buf())
+            .with_write_root(write_dir.path().to_path_buf());
+        let validator = PathValidator::new_with_policy(policy);
+
+        assert_eq!(validator.allowed_roots().len(), 2);
+        assert!(validator
+            .validate_parent_for(Operation::Write, &write_dir.path().join("out.txt"))
+            .is_ok());
+        let err = validator
+            .validate_parent_for(Operation::Write, &read_dir.path().join("out.txt"))
+            .unwrap_err();
+        assert!(err.to_string().contains("write access"));
+        assert!(validator
+            .validate_for(Operation::Read, write_dir.path())
+            .is_err());
+    }
+
+    #[test]
+    fn policy_reports_permitted_accesses() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("notes.txt");
+        fs::write(&file_path, "content").unwrap();
+
+        let accesses = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&accesses);
+        let policy = SecurityPolicy::read_only(dir.path().to_path_buf())
+            .on_access(move |op, path| recorded.lock().unwrap().push((op, path.to_path_buf())));
+        let validator = PathValidator::new_with_policy(policy);
+
+        validator
+            .validate_file_for(Operation::Read, &file_path)
+            .unwrap();
+        let _ = validator.validate_file_for(Operation::Write, &file_path);
+
+        assert_eq!(
+            *accesses.lock().unwrap(),
+            vec![(Operation::Read, file_path.canonicalize().unwrap())]
+        );
+    }
+
+    #[test]
+    fn validator_without_policy_permits_every_operation() {
+        let dir = TempDir::new().unwrap();
+        let validator = PathValidator::new()
+            .clear_allowed_roots()
+            .with_allowed_root(dir.path().to_path_buf());
+
+        assert!(validator.policy().is_none());
+        assert!(validator.validate_for(Operation::Write, dir.path()).is_ok());
+    }
+
+    #[test]
+    fn audit_buffer_records_each_decision() {
+        let dir = TempDir::new().unwrap();
+        let file_path = dir.path().join("notes.txt");
+        fs::write(&file_path, "content").unwrap();
+
+        let validator =
+            PathValidator::new_with_policy(SecurityPolicy::read_only(dir.path().to_path_buf()))
+                .with_audit_buffer(10);
+        validator
+            .validate_file_for(Operation::Read, &file_path)
+            .unwrap();
+        let _ = validator.validate_file_for(Operation::Write, &file_path);
+        let _ = validator.validate_for(Operation::Read, Path::new("/tmp/../etc/passwd"));
+
+        let records = validator.audit_records();
+        assert_eq!(records.len(), 3);
+        assert!(records[0].result.is_allowed());
+        assert_eq!(records[0].path, file_path);
+        assert_eq!(records[1].operation, Operation::Write);
+        assert_eq!(records[1].reason(), Some("write not permitted by policy"));
+        assert_eq!(records[2].reason(), Some("traversal attempt"));
+
+        // Clones share the buffer
+        let _ = validator
+            .clone()
+            .validate_for(Operation::Read, Path::new("/nonexistent"));
+        assert_eq!(validator.audit_records().len(), 4);
+    }
+
+    #[test]
+    fn flush_audit_writes_ndjson_and_clears_buffer() {
+        let dir = TempDir::new().unwrap();
+        let log_path = dir.path().join("audit.ndjson");
+        let validator = PathValidator::new().with_audit_buffer(10);
+        let _ = validator.validate_for(Operation::Read, dir.path());
+        let _ = validator.validate_for(Operation::Write, Path::new("/a/../b"));
+
+        validator.flush_audit_to_file(&log_path).unwrap();
+        assert!(validator.audit_records().is_empty());
+
+        let lines: Vec<serde_json::Value> = fs::read_to_string(&log_path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["result"], "allowed");
+        assert_eq!(lines[1]["operation"], "write");
+        assert_eq!(lines[1]["reason"], "traversal attempt");
+    }
+
+    #[test]
+    fn validator_without_audit_buffer_keeps_no_records() {
+        let validator = PathValidator::new();
+        let _ = validator.validate_for(Operation::Read, Path::new("/a/../b"));
+        assert!(validator.audit_records().is_empty());
+        assert!(validator
+            .flush_audit_to_file(Path::new("/nonexistent/audit.ndjson"))
+            .is_ok());
+    }
 }
@@ -0,0 +1,228 @@
+//! Test helpers for tool definitions.
+//!
+//! [`ToolSchemaTestHarness`] checks that a tool's input schema accepts the
+//! arguments it should and rejects the ones it should not, catching
+//! mistakes like optional fields marked required or wrong property types.
+//!
+//! ```rust,ignore
+//! use acton_ai::tools::testing::ToolSchemaTestHarness;
+//!
+//! #[test]
+//! fn my_tool_schema() {
+//!     ToolSchemaTestHarness::for_tool(&MyTool::config().definition)
+//!         .valid_example(json!({"query": "rust"}))
+//!         .invalid_example(json!({}), "query is required")
+//!         .run()
+//!         .assert_success();
+//! }
+//! ```
+
+use crate::messages::ToolDefinition;
+use crate::tools::schema::SchemaViolation;
+use serde_json::Value;
+use std::fmt;
+
+/// What a test example is expected to do.
+#[derive(Debug, Clone)]
+enum Expectation {
+    Valid,
+    Invalid { reason: String },
+}
+
+/// A single example registered with the harness.
+#[derive(Debug, Clone)]
+struct SchemaCase {
+    example: Value,
+    expectation: Expectation,
+}
+
+/// Runs valid and invalid examples against a tool's input schema.
+#[derive(Debug, Clone)]
+pub struct ToolSchemaTestHarness {
+    definition: ToolDefinition,
+    cases: Vec<SchemaCase>,
+}
+
+/// An example whose outcome did not match its expectation.
+#[derive(Debug, Clone)]
+pub struct SchemaTestFailure {
+    /// The example arguments
+    pub example: Value,
+    /// Why the example was expected to fail, or `None` if it was expected
+    /// to pass
+    pub expected_failure: Option<String>,
+    /// Violations reported for an example expected to pass
+    pub violations: Vec<SchemaViolation>,
+}
+
+impl fmt::Display for SchemaTestFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expected_failure {
+            Some(reason) => write!(
+                f,
+                "{} was accepted but should fail ({})",
+                self.example, reason
+            ),
+            None => {
+                write!(f, "{} was rejected:", self.example)?;
+                for violation in &self.violations {
+                    write!(f, " [{}]", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Outcome of [`ToolSchemaTestHarness::run`].
+#[derive(Debug, Clone)]
+pub struct TestReport {
+    /// The tool under test
+    pub tool_name: String,
+    /// Examples that behaved as expected
+    pub passed: usize,
+    /// Examples that did not
+    pub failures: Vec<SchemaTestFailure>,
+}
+
+impl TestReport {
+    /// Returns true if every example behaved as expected.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Panics with a description of every failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any example did not behave as expected.
+    pub fn assert_success(&self) {
+        if self.is_success() {
+            return;
+        }
+        let details: Vec<String> = self.failures.iter().map(ToString::to_string).collect();
+        panic!(
+            "schema test for tool '{}' failed {} of {} examples:\n  {}",
+            self.tool_name,
+            self.failures.len(),
+            self.failures.len() + self.passed,
+            details.join("\n  ")
+        );
+    }
+}
+
+impl ToolSchemaTestHarness {
+    /// Creates a harness for `definition`'s input schema.
+    #[must_use]
+    pub fn for_tool(definition: &ToolDefinition) -> Self {
+        Self {
+            definition: definition.clone(),
+            cases: Vec::new(),
+        }
+    }
+
+    /// Adds an example the schema must accept.
+    pub fn valid_example(&mut self, example: Value) -> &mut Self {
+        self.cases.push(SchemaCase {
+            example,
+            expectation: Expectation::Valid,
+        });
+        self
+    }
+
+    /// Adds an example the schema must reject; `reason` documents why.
+    pub fn invalid_example(&mut self, example: Value, reason: &str) -> &mut Self {
+        self.cases.push(SchemaCase {
+            example,
+            expectation: Expectation::Invalid {
+                reason: reason.to_string(),
+            },
+        });
+        self
+    }
+
+    /// Validates every example and reports the ones that misbehaved.
+    #[must_use]
+    pub fn run(&self) -> TestReport {
+        let mut passed = 0;
+        let mut failures = Vec::new();
+
+        for case in &self.cases {
+            let outcome = self.definition.validate_against_schema(&case.example);
+            match (&case.expectation, outcome) {
+                (Expectation::Valid, Ok(())) | (Expectation::Invalid { .. }, Err(_)) => {
+                    passed += 1;
+                }
+                (Expectation::Valid, Err(violations)) => failures.push(SchemaTestFailure {
+                    example: case.example.clone(),
+                    expected_failure: None,
+                    violations,
+                }),
+                (Expectation::Invalid { reason }, Ok(())) => failures.push(SchemaTestFailure {
+                    example: case.example.clone(),
+                    expected_failure: Some(reason.clone()),
+                    violations: Vec::new(),
+                }),
+            }
+        }
+
+        TestReport {
+            tool_name: self.definition.name.clone(),
+            passed,
+            failures,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definition() -> ToolDefinition {
+        ToolDefinition {
+            name: "search".to_string(),
+            description: "Searches".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {"query": {"type": "string"}},
+                "required": ["query"]
+            }),
+        }
+    }
+
+    #[test]
+    fn report_counts_passes() {
+        let report = ToolSchemaTestHarness::for_tool(&definition())
+            .valid_example(json!({"query": "rust"}))
+            .invalid_example(json!({}), "query is required")
+            .run();
+
+        assert!(report.is_success());
+        assert_eq!(report.passed, 2);
+        report.assert_success();
+    }
+
+    #[test]
+    fn report_lists_misbehaving_examples() {
+        let report = ToolSchemaTestHarness::for_tool(&definition())
+            .valid_example(json!({"query": 1}))
+            .invalid_example(json!({"query": "ok"}), "should not pass")
+            .run();
+
+        assert_eq!(report.passed, 0);
+        assert_eq!(report.failures.len(), 2);
+        assert_eq!(report.failures[0].violations.len(), 1);
+        assert!(report.failures[1].to_string().contains("should not pass"));
+    }
+
+    #[test]
+    #[should_panic(expected = "schema test for tool 'search' failed 1 of 1")]
+    fn assert_success_panics_on_failure() {
+        ToolSchemaTestHarness::for_tool(&definition())
+            .valid_example(json!({}))
+            .run()
+            .assert_success();
+    }
+}
@@ -0,0 +1,272 @@
+//! Tool schema versioning.
+//!
+//! Conversations cached before a deployment may contain tool calls whose
+//! arguments no longer fit the tool's current input schema. Each schema is
+//! identified by the SHA-256 of its canonical JSON, and a
+//! [`ToolSchemaRegistry`] numbers successive hashes per tool and records
+//! every change, so callers can detect stale conversations before replaying
+//! them.
+
+use crate::messages::ToolDefinition;
+use ring::digest::{digest, SHA256};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+/// A tool's input schema at a point in time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolSchemaVersion {
+    /// The tool name
+    pub tool_name: String,
+    /// Hex SHA-256 of the canonical schema JSON
+    pub schema_hash: String,
+    /// Increments each time the tool's schema hash changes, starting at 1
+    pub version: u32,
+}
+
+impl ToolSchemaVersion {
+    /// Returns version 1 of `definition`'s schema.
+    #[must_use]
+    pub fn of(definition: &ToolDefinition) -> Self {
+        Self {
+            tool_name: definition.name.clone(),
+            schema_hash: schema_hash(&definition.input_schema),
+            version: 1,
+        }
+    }
+}
+
+/// A change in a tool's schema hash.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolSchemaChange {
+    /// The tool name
+    pub tool_name: String,
+    /// Hash before the change
+    pub old_hash: String,
+    /// Hash after the change
+    pub new_hash: String,
+    /// When the change was recorded (RFC 3339)
+    pub changed_at: String,
+}
+
+/// Hashes a JSON schema so that object key order does not affect the result.
+#[must_use]
+pub fn schema_hash(schema: &Value) -> String {
+    let mut canonical = String::new();
+    write_canonical(schema, &mut canonical);
+
+    let hash = digest(&SHA256, canonical.as_bytes());
+    let mut hex = String::with_capacity(64);
+    for byte in hash.as_ref() {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+/// Serializes `value` as JSON with object keys sorted.
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (i, key) in keys.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(&map[key], out);
+            }
+            out.push('}');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Current schema version of every tool seen, plus the changes since startup.
+///
+/// Tools keep their entry after being unregistered, so re-registering one
+/// with a different schema still counts as a change.
+#[derive(Debug, Clone, Default)]
+pub struct ToolSchemaRegistry {
+    versions: BTreeMap<String, ToolSchemaVersion>,
+    changelog: Vec<ToolSchemaChange>,
+}
+
+impl ToolSchemaRegistry {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `definition`'s current schema.
+    ///
+    /// Returns the change if the tool was known with a different hash.
+    pub fn record(&mut self, definition: &ToolDefinition) -> Option<ToolSchemaChange> {
+        let hash = schema_hash(&definition.input_schema);
+        match self.versions.get_mut(&definition.name) {
+            None => {
+                self.versions
+                    .insert(definition.name.clone(), ToolSchemaVersion::of(definition));
+                None
+            }
+            Some(current) if current.schema_hash == hash => None,
+            Some(current) => {
+                let change = ToolSchemaChange {
+                    tool_name: definition.name.clone(),
+                    old_hash: std::mem::replace(&mut current.schema_hash, hash.clone()),
+                    new_hash: hash,
+                    changed_at: chrono::Utc::now().to_rfc3339(),
+                };
+                current.version += 1;
+                self.changelog.push(change.clone());
+                Some(change)
+            }
+        }
+    }
+
+    /// Returns the current version of `tool_name`.
+    #[must_use]
+    pub fn get(&self, tool_name: &str) -> Option<&ToolSchemaVersion> {
+        self.versions.get(tool_name)
+    }
+
+    /// Returns the current version of every tool, sorted by name.
+    #[must_use]
+    pub fn versions(&self) -> Vec<ToolSchemaVersion> {
+        self.versions.values().cloned().collect()
+    }
+
+    /// Returns every schema change recorded since this registry was created.
+    #[must_use]
+    pub fn changelog(&self) -> &[ToolSchemaChange] {
+        &self.changelog
+    }
+
+    /// Returns the entries of `recorded` whose schema no longer matches.
+    ///
+    /// Tools that are unknown here are not reported.
+    #[must_use]
+    pub fn outdated<'a>(&self, recorded: &'a [ToolSchemaVersion]) -> Vec<&'a ToolSchemaVersion> {
+        recorded
+            .iter()
+            .filter(|old| {
+                self.versions
+                    .get(&old.tool_name)
+                    .is_some_and(|current| current.schema_hash != old.schema_hash)
+            })
+            .collect()
+    }
+
+    /// Writes the current versions to `path` as JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn persist_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.versions())?;
+        std::fs::write(path, json)
+    }
+
+    /// Loads versions written by [`persist_to_file`](Self::persist_to_file).
+    ///
+    /// The changelog of the loaded registry is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed.
+    pub fn load_from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        let versions: Vec<ToolSchemaVersion> = serde_json::from_str(&json)?;
+        Ok(Self {
+            versions: versions
+                .into_iter()
+                .map(|v| (v.tool_name.clone(), v))
+                .collect(),
+            changelog: Vec::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tool(schema: Value) -> ToolDefinition {
+        ToolDefinition {
+            name: "search".to_string(),
+            description: "Searches".to_string(),
+            input_schema: schema,
+        }
+    }
+
+    #[test]
+    fn hash_ignores_key_order() {
+        let a = json!({"type": "object", "properties": {"q": {"type": "string"}}});
+        let b = json!({"properties": {"q": {"type": "string"}}, "type": "object"});
+        assert_eq!(schema_hash(&a), schema_hash(&b));
+        assert_eq!(schema_hash(&a).len(), 64);
+        assert_ne!(schema_hash(&a), schema_hash(&json!({"type": "object"})));
+    }
+
+    #[test]
+    fn record_tracks_changes() {
+        let mut registry = ToolSchemaRegistry::new();
+        let v1 = tool(json!({"type": "object"}));
+        let v2 = tool(json!({"type": "object", "required": ["q"]}));
+
+        assert!(registry.record(&v1).is_none());
+        assert!(registry.record(&v1).is_none());
+        let change = registry.record(&v2).unwrap();
+
+        assert_eq!(change.tool_name, "search");
+        assert_eq!(change.old_hash, schema_hash(&v1.input_schema));
+        assert_eq!(change.new_hash, schema_hash(&v2.input_schema));
+        assert_eq!(registry.get("search").unwrap().version, 2);
+        assert_eq!(registry.changelog().len(), 1);
+    }
+
+    #[test]
+    fn outdated_reports_stale_versions() {
+        let mut registry = ToolSchemaRegistry::new();
+        let old = ToolSchemaVersion::of(&tool(json!({"type": "object"})));
+        registry.record(&tool(json!({"type": "object", "required": ["q"]})));
+
+        let unknown = ToolSchemaVersion {
+            tool_name: "gone".to_string(),
+            ..old.clone()
+        };
+        let recorded = [old.clone(), unknown];
+        assert_eq!(registry.outdated(&recorded), vec![&old]);
+    }
+
+    #[test]
+    fn persist_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("schemas.json");
+        let mut registry = ToolSchemaRegistry::new();
+        registry.record(&tool(json!({"type": "object"})));
+
+        registry.persist_to_file(&path).unwrap();
+        let loaded = ToolSchemaRegistry::load_from_file(&path).unwrap();
+
+        assert_eq!(loaded.versions(), registry.versions());
+        assert!(loaded.changelog().is_empty());
+    }
+}
@@ -7,6 +7,7 @@ use mti::prelude::*;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::str::FromStr;
+use std::time::SystemTime;
 
 /// A validated conversation identifier.
 ///
@@ -86,6 +87,15 @@ impl ConversationId {
     pub fn inner(&self) -> &MagicTypeId {
         &self.0
     }
+
+    /// Returns when this ID was created, to the millisecond.
+    ///
+    /// Read from the timestamp embedded in the ID's UUIDv7, so it needs no
+    /// separate storage.
+    #[must_use]
+    pub fn created_at(&self) -> SystemTime {
+        super::uuid_v7_time(&self.0)
+    }
 }
 
 impl Default for ConversationId {
@@ -207,4 +217,12 @@ mod tests {
         let deserialized: ConversationId = serde_json::from_str(&json).unwrap();
         assert_eq!(id, deserialized);
     }
+
+    #[test]
+    fn created_at_is_close_to_now() {
+        let before = SystemTime::now() - std::time::Duration::from_millis(1);
+        let created_at = ConversationId::new().created_at();
+        assert!(created_at >= before);
+        assert!(created_at <= SystemTime::now());
+    }
 }
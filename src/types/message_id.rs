@@ -7,6 +7,7 @@ use mti::prelude::*;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::str::FromStr;
+use std::time::SystemTime;
 
 /// A validated message identifier.
 ///
@@ -85,6 +86,15 @@ impl MessageId {
     pub fn inner(&self) -> &MagicTypeId {
         &self.0
     }
+
+    /// Returns when this ID was created, to the millisecond.
+    ///
+    /// Read from the timestamp embedded in the ID's UUIDv7, so it needs no
+    /// separate storage.
+    #[must_use]
+    pub fn timestamp(&self) -> SystemTime {
+        super::uuid_v7_time(&self.0)
+    }
 }
 
 impl Default for MessageId {
@@ -206,4 +216,24 @@ mod tests {
         let deserialized: MessageId = serde_json::from_str(&json).unwrap();
         assert_eq!(id, deserialized);
     }
+
+    #[test]
+    fn timestamps_track_creation_time() {
+        let first = MessageId::new();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        let second = MessageId::new();
+
+        let elapsed = second
+            .timestamp()
+            .duration_since(first.timestamp())
+            .expect("second ID is later");
+        assert!(
+            (std::time::Duration::from_millis(95)..std::time::Duration::from_millis(500))
+                .contains(&elapsed),
+            "elapsed {elapsed:?}"
+        );
+
+        let parsed = MessageId::parse(&second.to_string()).unwrap();
+        assert_eq!(parsed.timestamp(), second.timestamp());
+    }
 }
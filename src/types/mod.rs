@@ -20,3 +20,18 @@ pub use memory_id::{InvalidMemoryId, MemoryId};
 pub use message_id::{InvalidMessageId, MessageId};
 pub use task_id::{InvalidTaskId, TaskId};
 pub use tool_name::{InvalidToolName, ToolName};
+
+use mti::prelude::MagicTypeId;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Returns the time embedded in a UUIDv7-based TypeID.
+///
+/// A UUIDv7 starts with a 48-bit count of milliseconds since the Unix
+/// epoch.
+fn uuid_v7_time(id: &MagicTypeId) -> SystemTime {
+    let uuid = id.suffix().to_uuid();
+    let millis = uuid.as_bytes()[..6]
+        .iter()
+        .fold(0u64, |acc, &byte| (acc << 8) | u64::from(byte));
+    UNIX_EPOCH + Duration::from_millis(millis)
+}
@@ -55,6 +55,253 @@ async fn test_spawn_agent_via_kernel() {
     runtime.shutdown_all().await.expect("Shutdown failed");
 }
 
+/// Test spawning an agent from a YAML definition.
+#[tokio::test]
+async fn test_spawn_agent_from_yaml() {
+    let mut runtime = ActonApp::launch_async().await;
+    let kernel_handle = Kernel::spawn(&mut runtime).await;
+
+    let yaml = "system_prompt: You are a helpful test assistant.\nname: YamlAgent\n";
+    let agent_id = Kernel::spawn_agent_from_yaml(&kernel_handle, yaml)
+        .await
+        .expect("valid agent definition");
+    assert!(!agent_id.to_string().is_empty());
+
+    assert!(
+        Kernel::spawn_agent_from_yaml(&kernel_handle, "tools: [read_file]")
+            .await
+            .is_err()
+    );
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
+#[acton_actor]
+struct WatchdogObserver;
+
+/// Test that the watchdog fires for unresponsive agents only.
+#[tokio::test]
+async fn test_watchdog_triggers_for_unresponsive_agent() {
+    let mut runtime = ActonApp::launch_async().await;
+    let config = KernelConfig::new()
+        .without_logging()
+        .with_heartbeat_interval(Duration::from_millis(50))
+        .with_heartbeat_timeout(Duration::from_millis(50));
+    let kernel_handle = Kernel::spawn_with_config(&mut runtime, config).await;
+
+    let triggered = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut observer = runtime.new_actor::<WatchdogObserver>();
+    let sink = triggered.clone();
+    observer.mutate_on::<WatchdogTriggered>(move |_actor, envelope| {
+        sink.lock()
+            .unwrap()
+            .push(envelope.message().agent_id.clone());
+        Reply::ready()
+    });
+    let observer = observer.start().await;
+    observer.subscribe::<WatchdogTriggered>().await;
+
+    let mut register = |name: &str| {
+        let agent_id = AgentId::new();
+        let config = AgentConfig::new(name).with_id(agent_id.clone());
+        (agent_id, config, Agent::create(&mut runtime))
+    };
+    let (live_id, live_config, live) = register("Live");
+    let (dead_id, dead_config, dead) = register("Dead");
+
+    let live = live.start().await;
+    live.send(InitAgent {
+        config: live_config.clone(),
+    })
+    .await;
+    let dead = dead.start().await;
+    dead.stop().await.expect("stop failed");
+
+    for (agent_id, handle, config) in [
+        (live_id.clone(), live, live_config),
+        (dead_id.clone(), dead, dead_config),
+    ] {
+        kernel_handle
+            .send(RegisterAgent {
+                agent_id,
+                handle,
+                config,
+            })
+            .await;
+    }
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+
+    let triggered = triggered.lock().unwrap().clone();
+    assert!(triggered.contains(&dead_id));
+    assert!(!triggered.contains(&live_id));
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
+#[acton_actor]
+struct LLMRequestObserver;
+
+/// Test that a paused agent queues prompts and replays them in order after
+/// resuming.
+#[tokio::test]
+async fn test_paused_agent_replays_queued_prompts_after_resume() {
+    let mut runtime = ActonApp::launch_async().await;
+    let kernel_handle =
+        Kernel::spawn_with_config(&mut runtime, KernelConfig::new().without_logging()).await;
+
+    let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut observer = runtime.new_actor::<LLMRequestObserver>();
+    let sink = requests.clone();
+    observer.mutate_on::<LLMRequest>(move |_actor, envelope| {
+        sink.lock()
+            .unwrap()
+            .push(envelope.message().correlation_id.clone());
+        Reply::ready()
+    });
+    let observer = observer.start().await;
+    observer.subscribe::<LLMRequest>().await;
+
+    let agent_id = AgentId::new();
+    let config = AgentConfig::new("You are a test assistant.").with_id(agent_id.clone());
+    let agent = Agent::create(&mut runtime).start().await;
+    agent
+        .send(InitAgent {
+            config: config.clone(),
+        })
+        .await;
+    kernel_handle
+        .send(RegisterAgent {
+            agent_id: agent_id.clone(),
+            handle: agent.clone(),
+            config,
+        })
+        .await;
+
+    kernel_handle
+        .send(PauseAgent {
+            agent_id: agent_id.clone(),
+        })
+        .await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let first = UserPrompt::new("first");
+    let second = UserPrompt::new("second");
+    agent.send(first.clone()).await;
+    agent.send(second.clone()).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(requests.lock().unwrap().is_empty());
+
+    kernel_handle
+        .send(ResumeAgent {
+            agent_id: agent_id.clone(),
+        })
+        .await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(requests.lock().unwrap().len(), 1);
+    assert_eq!(requests.lock().unwrap()[0], first.correlation_id);
+
+    // Finishing the first turn runs the next queued prompt
+    agent
+        .send(LLMResponse {
+            correlation_id: first.correlation_id.clone(),
+            content: "done".to_string(),
+            tool_calls: None,
+            stop_reason: StopReason::EndTurn,
+        })
+        .await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(
+        *requests.lock().unwrap(),
+        [first.correlation_id, second.correlation_id]
+    );
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
+/// Test that a memory-enabled agent registered with a kernel that has a
+/// memory store prompts with its stored memories.
+#[tokio::test]
+async fn test_agent_with_memory_adds_memories_to_prompt() {
+    let mut runtime = ActonApp::launch_async().await;
+    let kernel_handle =
+        Kernel::spawn_with_config(&mut runtime, KernelConfig::new().without_logging()).await;
+    let store = MemoryStore::spawn_initialized(&mut runtime, PersistenceConfig::in_memory())
+        .await
+        .expect("memory store");
+    kernel_handle
+        .send(UseMemoryStore {
+            store: store.clone(),
+        })
+        .await;
+
+    let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut observer = runtime.new_actor::<LLMRequestObserver>();
+    let sink = requests.clone();
+    observer.mutate_on::<LLMRequest>(move |_actor, envelope| {
+        sink.lock().unwrap().push(envelope.message().clone());
+        Reply::ready()
+    });
+    let observer = observer.start().await;
+    observer.subscribe::<LLMRequest>().await;
+
+    let remembering_id = AgentId::new();
+    let forgetful_id = AgentId::new();
+    for agent_id in [&remembering_id, &forgetful_id] {
+        store
+            .send(StoreMemory {
+                agent_id: agent_id.clone(),
+                content: "The user's name is Ada.".to_string(),
+                embedding: None,
+                importance: None,
+                expires_at: None,
+                dedup_threshold: None,
+            })
+            .await;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let configs = [
+        AgentConfig::new("You are a test assistant.")
+            .with_id(remembering_id.clone())
+            .with_memory(),
+        AgentConfig::new("You are a test assistant.").with_id(forgetful_id.clone()),
+    ];
+    for config in configs {
+        let agent = Agent::create(&mut runtime).start().await;
+        agent
+            .send(InitAgent {
+                config: config.clone(),
+            })
+            .await;
+        kernel_handle
+            .send(RegisterAgent {
+                agent_id: config.agent_id(),
+                handle: agent.clone(),
+                config,
+            })
+            .await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        agent.send(UserPrompt::new("What is my name?")).await;
+    }
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let requests = requests.lock().unwrap().clone();
+    let system_prompt = |agent_id: &AgentId| {
+        requests
+            .iter()
+            .find(|r| &r.agent_id == agent_id)
+            .map(|r| r.messages[0].content.clone())
+            .expect("request sent")
+    };
+    let remembering = system_prompt(&remembering_id);
+    assert!(remembering.starts_with("You are a test assistant."));
+    assert!(remembering.contains("The user's name is Ada."));
+    assert_eq!(system_prompt(&forgetful_id), "You are a test assistant.");
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
 /// Test creating an agent directly and sending a user prompt.
 #[tokio::test]
 async fn test_agent_receives_user_prompt() {
@@ -283,6 +530,7 @@ fn test_llm_request_creation() {
         messages: vec![Message::system("You are helpful"), Message::user("Hello")],
         tools: None,
         sampling: None,
+        max_tokens: None,
     };
 
     assert_eq!(request.correlation_id, corr_id);
@@ -431,7 +679,8 @@ fn test_tool_definition() {
 // ============================================================================
 
 use acton_ai::tools::{
-    RegisterTool, ToolConfig, ToolError, ToolExecutionFuture, ToolExecutorTrait, ToolRegistry,
+    list_tools, list_tools_with_metadata, RegisterTool, ToolConfig, ToolError, ToolExecutionFuture,
+    ToolExecutorTrait, ToolRegistry, ToolResultExt,
 };
 use std::sync::Arc;
 
@@ -500,6 +749,7 @@ async fn test_register_tool() {
         .send(RegisterTool {
             config: ToolConfig::new(tool_def),
             executor: Arc::new(Box::new(EchoTool) as Box<dyn ToolExecutorTrait>),
+            owner_agent_id: None,
         })
         .await;
 
@@ -510,6 +760,158 @@ async fn test_register_tool() {
     runtime.shutdown_all().await.expect("Shutdown failed");
 }
 
+/// Test that the registry reports per-tool usage with the tool metadata.
+#[tokio::test]
+async fn test_list_tools_with_metadata_tracks_executions() {
+    let mut runtime = ActonApp::launch_async().await;
+    let registry = ToolRegistry::spawn(&mut runtime).await;
+
+    for (name, executor) in [
+        ("echo", Box::new(EchoTool) as Box<dyn ToolExecutorTrait>),
+        (
+            "failing_tool",
+            Box::new(FailingTool) as Box<dyn ToolExecutorTrait>,
+        ),
+    ] {
+        let definition = ToolDefinition {
+            name: name.to_string(),
+            description: format!("{name} tool"),
+            input_schema: serde_json::json!({"type": "object"}),
+        };
+        registry
+            .send(RegisterTool {
+                config: ToolConfig::new(definition).with_sandbox(name == "failing_tool"),
+                executor: Arc::new(executor),
+                owner_agent_id: None,
+            })
+            .await;
+    }
+    for name in ["echo", "echo", "failing_tool"] {
+        registry
+            .send(ExecuteTool {
+                correlation_id: CorrelationId::new(),
+                tool_call: ToolCall {
+                    id: format!("call_{name}"),
+                    name: name.to_string(),
+                    arguments: serde_json::json!({}),
+                },
+                requesting_agent: AgentId::new(),
+            })
+            .await;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let tools = list_tools_with_metadata(&mut runtime, &registry).await;
+    assert_eq!(tools.len(), 2);
+    assert_eq!(tools[0].definition.name, "echo");
+    assert_eq!(tools[0].execution_count, 2);
+    assert_eq!(tools[0].error_count, 0);
+    assert!(tools[0].last_used.is_some());
+    assert!(!tools[0].sandboxed);
+    assert_eq!(tools[1].definition.name, "failing_tool");
+    assert_eq!(tools[1].execution_count, 1);
+    assert_eq!(tools[1].error_count, 1);
+    assert!(tools[1].sandboxed);
+    assert_eq!(tools[1].timeout, Duration::from_secs(30));
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
+/// Test that a stopped agent's tools are removed from the registry.
+#[tokio::test]
+async fn test_agent_tools_unregistered_on_stop() {
+    let mut runtime = ActonApp::launch_async().await;
+    let kernel = Kernel::spawn(&mut runtime).await;
+    let registry = ToolRegistry::spawn(&mut runtime).await;
+
+    let agent_id = AgentId::new();
+    let config = AgentConfig::new("You own tools.").with_id(agent_id.clone());
+    let agent = Agent::create(&mut runtime).start().await;
+    agent
+        .send(InitAgent {
+            config: config.clone(),
+        })
+        .await;
+    kernel
+        .send(RegisterAgent {
+            agent_id: agent_id.clone(),
+            handle: agent,
+            config,
+        })
+        .await;
+    for (name, owner) in [("owned", Some(agent_id.clone())), ("shared", None)] {
+        let definition = ToolDefinition {
+            name: name.to_string(),
+            description: format!("{name} tool"),
+            input_schema: serde_json::json!({"type": "object"}),
+        };
+        registry
+            .send(RegisterTool {
+                config: ToolConfig::new(definition),
+                executor: Arc::new(Box::new(EchoTool) as Box<dyn ToolExecutorTrait>),
+                owner_agent_id: owner,
+            })
+            .await;
+    }
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(list_tools(&mut runtime, &registry).await.len(), 2);
+
+    kernel.send(StopAgent { agent_id }).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let tools = list_tools(&mut runtime, &registry).await;
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "shared");
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
+/// A tool actor that counts the calls it receives and never responds.
+#[acton_actor]
+struct SilentTool;
+
+/// Test that a tool call that never responds frees its slot after the
+/// kernel's tool call timeout.
+#[tokio::test]
+async fn test_unresponsive_tool_call_frees_its_slot() {
+    use acton_ai::kernel::ScheduleToolCall;
+    use acton_ai::tools::ExecuteToolDirect;
+    use std::num::NonZeroUsize;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let mut runtime = ActonApp::launch_async().await;
+    let config = KernelConfig::default()
+        .with_max_concurrent_tool_calls(NonZeroUsize::MIN)
+        .with_tool_call_timeout(Duration::from_millis(100));
+    let kernel = Kernel::spawn_with_config(&mut runtime, config).await;
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let mut tool = runtime.new_actor::<SilentTool>();
+    let counter = received.clone();
+    tool.mutate_on::<ExecuteToolDirect>(move |_actor, _envelope| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Reply::ready()
+    });
+    let tool = tool.start().await;
+
+    let correlation_id = CorrelationId::new();
+    for call in ["call_1", "call_2"] {
+        kernel
+            .send(ScheduleToolCall {
+                tool: tool.clone(),
+                call: ExecuteToolDirect::new(correlation_id.clone(), call, serde_json::json!({})),
+            })
+            .await;
+    }
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(received.load(Ordering::SeqCst), 2);
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
 /// Test tool error types.
 #[test]
 fn test_tool_error_not_found() {
@@ -824,6 +1226,7 @@ fn test_persistence_error_shutting_down() {
 #[test]
 fn test_agent_state_snapshot_serialization() {
     let snapshot = AgentStateSnapshot {
+        schema_version: acton_ai::memory::SCHEMA_VERSION,
         agent_id: AgentId::new(),
         conversation_id: Some(ConversationId::new()),
         conversation: vec![Message::user("Hello"), Message::assistant("Hi there")],
@@ -1021,6 +1424,9 @@ async fn test_memory_store_with_embeddings() {
                 agent_id: agent_id.clone(),
                 content: content.to_string(),
                 embedding: Some(embedding),
+                importance: None,
+                expires_at: None,
+                dedup_threshold: None,
             })
             .await;
     }
@@ -1041,6 +1447,11 @@ fn test_truncation_strategies() {
         truncation_strategy: TruncationStrategy::KeepRecent,
         reserved_for_response: 5,
         tokens_per_char: 0.5, // Higher ratio to make messages "larger"
+        importance_weight: 1.0,
+        dedup_threshold: None,
+        hybrid_search_alpha: 0.7,
+        accurate_token_count: false,
+        model_name: None,
     };
     let window = ContextWindow::new(config);
 
@@ -1363,6 +1774,91 @@ async fn test_task_delegation_flow() {
     runtime.shutdown_all().await.expect("Shutdown failed");
 }
 
+#[acton_actor]
+struct TaskAcceptedObserver;
+
+/// Test a delegated task's result reaching the delegating agent.
+#[tokio::test]
+async fn test_task_delegation_round_trip() {
+    let mut runtime = ActonApp::launch_async().await;
+    let kernel =
+        Kernel::spawn_with_config(&mut runtime, KernelConfig::new().without_logging()).await;
+
+    let accepted = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut observer = runtime.new_actor::<TaskAcceptedObserver>();
+    let recorded = accepted.clone();
+    observer.mutate_on::<TaskAccepted>(move |_actor, envelope| {
+        recorded.lock().unwrap().push(envelope.message().clone());
+        Reply::ready()
+    });
+    let observer = observer.start().await;
+    observer.subscribe::<TaskAccepted>().await;
+
+    let supervisor_id = AgentId::new();
+    let worker_id = AgentId::new();
+    let mut handles = Vec::new();
+    for (agent_id, name) in [(&supervisor_id, "Supervisor"), (&worker_id, "Worker")] {
+        let config = AgentConfig::new(name).with_id(agent_id.clone());
+        let handle = Agent::create(&mut runtime).start().await;
+        handle
+            .send(InitAgent {
+                config: config.clone(),
+            })
+            .await;
+        kernel
+            .send(RegisterAgent {
+                agent_id: agent_id.clone(),
+                handle: handle.clone(),
+                config,
+            })
+            .await;
+        handles.push(handle);
+    }
+    let worker = handles.pop().unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Delegate
+    let (tx, rx) = std::sync::mpsc::channel();
+    let task = DelegateTask::new(
+        supervisor_id.clone(),
+        worker_id.clone(),
+        "sum",
+        serde_json::json!({"data": [1, 2, 3]}),
+    )
+    .with_callback(move |result| {
+        let _ = tx.send(result);
+    });
+    let task_id = task.task_id.clone();
+    kernel.send(task).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    // Accept
+    let accepted = accepted.lock().unwrap().clone();
+    assert_eq!(accepted.len(), 1);
+    assert_eq!(accepted[0].task_id, task_id);
+    assert_eq!(accepted[0].agent_id, worker_id);
+
+    // Work
+    worker
+        .send(CompleteTask::success(
+            task_id.clone(),
+            serde_json::json!({"sum": 6}),
+        ))
+        .await;
+
+    // Receive the result
+    let result = tokio::task::spawn_blocking(move || rx.recv_timeout(Duration::from_secs(5)))
+        .await
+        .unwrap()
+        .expect("callback should receive the task result");
+    assert_eq!(result.task_id, task_id);
+    assert_eq!(result.from, worker_id);
+    assert_eq!(result.to, supervisor_id);
+    assert_eq!(result.result, Ok(serde_json::json!({"sum": 6})));
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
 /// Test agent discovery by capability.
 #[tokio::test]
 async fn test_agent_discovery() {
@@ -1589,3 +2085,258 @@ fn test_find_capable_agent_creation() {
     assert_eq!(query.capability, "code_review");
     assert!(query.correlation_id.to_string().starts_with("corr_"));
 }
+
+/// Test FindBestCapableAgent creation and scored announcements.
+#[test]
+fn test_find_best_capable_agent_creation() {
+    let query = FindBestCapableAgent::new("code_review", true);
+    assert_eq!(query.capability, "code_review");
+    assert!(query.prefer_idle);
+
+    let caps =
+        AnnounceCapabilities::new(AgentId::new(), vec!["code_review".to_string()]).with_scores(
+            std::collections::HashMap::from([("code_review".to_string(), 0.9)]),
+        );
+    assert_eq!(caps.scores.unwrap()["code_review"], 0.9);
+}
+
+use acton_ai::kernel::{MultiAgentWorkflow, TaskSpec, WorkflowDefinition, WorkflowStep};
+
+/// Worker that completes every delegated task with its type and input.
+#[acton_actor]
+struct EchoWorker;
+
+/// Spawns an echo worker, registers it with the kernel, and announces
+/// `capabilities` for it.
+async fn spawn_echo_worker(
+    runtime: &mut ActorRuntime,
+    kernel: &ActorHandle,
+    capabilities: &[&str],
+) -> AgentId {
+    let mut worker = runtime.new_actor::<EchoWorker>();
+    worker.mutate_on::<IncomingTask>(|actor, envelope| {
+        let task = envelope.message();
+        let completed = TaskCompleted {
+            task_id: task.task_id.clone(),
+            result: serde_json::json!({
+                "task": task.task_type,
+                "input": task.payload["input"],
+                "payload": task.payload["payload"],
+            }),
+        };
+        let broker = actor.broker().clone();
+        Reply::pending(async move {
+            broker.broadcast(completed).await;
+        })
+    });
+    let handle = worker.start().await;
+
+    let agent_id = AgentId::new();
+    kernel
+        .send(RegisterAgent {
+            agent_id: agent_id.clone(),
+            handle,
+            config: AgentConfig::new("Echo worker"),
+        })
+        .await;
+    kernel
+        .send(AnnounceCapabilities::new(
+            agent_id.clone(),
+            capabilities.iter().map(|c| c.to_string()).collect(),
+        ))
+        .await;
+    agent_id
+}
+
+fn task_name(value: serde_json::Value) -> serde_json::Value {
+    value["task"].clone()
+}
+
+/// Test a workflow that chains sequential and parallel steps.
+#[tokio::test]
+async fn test_multi_agent_workflow_feeds_outputs_forward() {
+    let mut runtime = ActonApp::launch_async().await;
+    let kernel =
+        Kernel::spawn_with_config(&mut runtime, KernelConfig::new().without_logging()).await;
+    spawn_echo_worker(&mut runtime, &kernel, &["plan", "research", "write"]).await;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let definition = WorkflowDefinition::new()
+        .then(WorkflowStep::Sequential(vec![TaskSpec::new(
+            "plan",
+            serde_json::json!("outline"),
+        )
+        .with_transform(task_name)]))
+        .then(WorkflowStep::Parallel(vec![
+            TaskSpec::new("research", serde_json::json!(1)).with_transform(task_name),
+            TaskSpec::new("research", serde_json::json!(2)),
+        ]))
+        .then(WorkflowStep::Sequential(vec![TaskSpec::new(
+            "write",
+            serde_json::json!("draft"),
+        )
+        .with_deadline(Duration::from_secs(5))]));
+
+    let result = MultiAgentWorkflow::run(&mut runtime, kernel.clone(), definition)
+        .await
+        .expect("workflow should succeed");
+
+    assert_eq!(result.step_results.len(), 3);
+    assert_eq!(result.step_results[0], "plan");
+    assert_eq!(result.step_results[1][0], "research");
+    assert_eq!(result.step_results[1][1]["input"], "plan");
+    assert_eq!(result.step_results[1][1]["payload"], 2);
+    assert_eq!(result.step_results[2]["input"], result.step_results[1]);
+    assert_eq!(result.step_results[2]["payload"], "draft");
+    assert!(result.total_elapsed > Duration::ZERO);
+
+    let missing = WorkflowDefinition::new().then(WorkflowStep::Sequential(vec![TaskSpec::new(
+        "review",
+        serde_json::json!({}),
+    )]));
+    let error = MultiAgentWorkflow::run(&mut runtime, kernel, missing)
+        .await
+        .unwrap_err();
+    assert!(error.is_no_capable_agent());
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
+/// Agent that records the content of every message it receives.
+#[acton_actor]
+struct InboxAgent;
+
+/// Spawns an inbox agent, registers it with the kernel, and announces
+/// `capabilities` for it.
+async fn spawn_inbox_agent(
+    runtime: &mut ActorRuntime,
+    kernel: &ActorHandle,
+    inbox: &std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+    capabilities: &[&str],
+) -> AgentId {
+    let mut agent = runtime.new_actor::<InboxAgent>();
+    let inbox = inbox.clone();
+    agent.mutate_on::<IncomingAgentMessage>(move |_actor, envelope| {
+        inbox
+            .lock()
+            .unwrap()
+            .push(envelope.message().content.clone());
+        Reply::ready()
+    });
+    let handle = agent.start().await;
+
+    let agent_id = AgentId::new();
+    kernel
+        .send(RegisterAgent {
+            agent_id: agent_id.clone(),
+            handle,
+            config: AgentConfig::new("Inbox agent"),
+        })
+        .await;
+    kernel
+        .send(AnnounceCapabilities::new(
+            agent_id.clone(),
+            capabilities.iter().map(|c| c.to_string()).collect(),
+        ))
+        .await;
+    agent_id
+}
+
+/// Test broadcasting a message to every registered agent.
+#[tokio::test]
+async fn test_kernel_broadcast_reaches_every_agent() {
+    let mut runtime = ActonApp::launch_async().await;
+    let kernel =
+        Kernel::spawn_with_config(&mut runtime, KernelConfig::new().without_logging()).await;
+    let inbox = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    for capabilities in [&["review"][..], &["review"], &[]] {
+        spawn_inbox_agent(&mut runtime, &kernel, &inbox, capabilities).await;
+    }
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let sender = AgentId::new();
+    let message = AgentMessage::new(sender.clone(), AgentId::new(), "shutdown in 5 seconds");
+    let complete = Kernel::broadcast(&mut runtime, &kernel, message)
+        .await
+        .expect("kernel should reply");
+    assert_eq!(complete.sent_count, 3);
+    assert_eq!(complete.failed_count, 0);
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(*inbox.lock().unwrap(), vec!["shutdown in 5 seconds"; 3]);
+
+    inbox.lock().unwrap().clear();
+    let message = AgentMessage::new(sender, AgentId::new(), "new tool available");
+    let complete = Kernel::broadcast_to_capable(&mut runtime, &kernel, "review", message)
+        .await
+        .expect("kernel should reply");
+    assert_eq!(complete.sent_count, 2);
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(*inbox.lock().unwrap(), vec!["new tool available"; 2]);
+
+    runtime.shutdown_all().await.expect("Shutdown failed");
+}
+
+// =============================================================================
+// Tool Error Conversion Tests
+// =============================================================================
+
+/// A tool whose logic is written with `anyhow`.
+#[derive(Debug)]
+struct CountTool;
+
+fn count_items(args: &serde_json::Value) -> anyhow::Result<usize> {
+    let items = args
+        .get("items")
+        .and_then(serde_json::Value::as_array)
+        .ok_or_else(|| anyhow::anyhow!("missing 'items' array"))?;
+    Ok(items.len())
+}
+
+impl ToolExecutorTrait for CountTool {
+    fn execute(&self, args: serde_json::Value) -> ToolExecutionFuture {
+        Box::pin(async move {
+            let count = count_items(&args).map_tool_err("count")?;
+            Ok(serde_json::json!({ "count": count }))
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_tool_errors_from_anyhow_with_question_mark() {
+    let result = CountTool
+        .execute(serde_json::json!({ "items": [1, 2, 3] }))
+        .await
+        .expect("count should succeed");
+    assert_eq!(result["count"], 3);
+
+    let error = CountTool.execute(serde_json::json!({})).await.unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "tool 'count' execution failed: missing 'items' array"
+    );
+}
+
+#[test]
+fn test_tool_error_from_std_keeps_message() {
+    let io = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+    let error = ToolError::from_std("read_file", io);
+    assert!(error.to_string().contains("no such file"));
+}
+
+#[cfg(feature = "anyhow")]
+#[test]
+fn test_tool_error_from_anyhow_keeps_context() {
+    use anyhow::Context;
+
+    let error = "x"
+        .parse::<u32>()
+        .context("parsing limit")
+        .map_err(|e| ToolError::from_anyhow("limit", e))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "tool 'limit' execution failed: parsing limit: invalid digit found in string"
+    );
+}